@@ -0,0 +1,453 @@
+//! `cargo frame` runs the common Frame workflows of a package from the command line, with the
+//! same configuration as its `build.rs` script, so that generated code can be inspected without
+//! a full `cargo build`.
+//!
+//! ```text
+//! cargo frame build      compile the package's Frame files to the output directory
+//! cargo frame check      fail if the output directory isn't up to date with the Frame files
+//! cargo frame diagram    generate a state diagram of each Frame file
+//! cargo frame fmt        format the package's Frame files in place
+//! cargo frame new        create a starter Frame file, with a Rust file implementing its actions
+//! ```
+//!
+//! The Frame files are found and compiled by `frame_build`, run from the package directory as
+//! from a build script. Its configuration is read from the `[package.metadata.frame]` table of
+//! the package's `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.frame]
+//! input-dir = "src"            # directory searched for .frm files
+//! output-dir = "target/frame"  # directory the generated files are written to
+//! targets = ["rust"]           # target languages, by their framec names
+//! config = "config.yaml"       # Frame config file, by default the one in the package directory
+//! max-depth = 3                # how deep to search the input directory
+//! follow-links = false         # whether to follow symbolic links in the input directory
+//! diagram-dir = "docs/diagrams" # directory svg, png and dot diagrams are written to
+//! ```
+//!
+//! All keys are optional, and the defaults are those of `frame_build`, except for the output
+//! directory, which is `target/frame` since `OUT_DIR` is only set for build scripts. Diagrams are
+//! written to the output directory if no diagram directory is configured.
+
+use anyhow::{bail, Context, Error, Result};
+use clap::{Arg, ArgMatches, Command};
+use frame_build::{graphviz, DiagramFormat, FrameBuild, TargetLanguage};
+use framec::frame_c::compiler::Exe;
+use framec::frame_c::{formatter, scaffold};
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The configuration of `frame_build` in the `[package.metadata.frame]` table of `Cargo.toml`.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct FrameMetadata {
+    input_dir: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    targets: Option<Vec<String>>,
+    config: Option<PathBuf>,
+    max_depth: Option<usize>,
+    follow_links: bool,
+    diagram_dir: Option<PathBuf>,
+}
+
+impl FrameMetadata {
+    /// Read the configuration from a package manifest. A manifest without the table has the
+    /// default configuration.
+    fn read(manifest_path: &Path) -> Result<FrameMetadata> {
+        let manifest = fs::read_to_string(manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        FrameMetadata::parse(&manifest).with_context(|| {
+            format!(
+                "invalid [package.metadata.frame] in {}",
+                manifest_path.display()
+            )
+        })
+    }
+
+    fn parse(manifest: &str) -> Result<FrameMetadata> {
+        let manifest: toml::Value = toml::from_str(manifest)?;
+        match manifest
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("frame"))
+        {
+            Some(frame) => Ok(frame.clone().try_into()?),
+            None => Ok(FrameMetadata::default()),
+        }
+    }
+
+    fn output_dir(&self) -> PathBuf {
+        self.output_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("target/frame"))
+    }
+
+    fn targets(&self) -> Result<Vec<TargetLanguage>> {
+        match &self.targets {
+            Some(targets) => targets
+                .iter()
+                .map(|target| TargetLanguage::try_from(target.as_str()).map_err(Error::msg))
+                .collect(),
+            None => Ok(vec![TargetLanguage::Rust]),
+        }
+    }
+
+    /// The `frame_build` process for this configuration, writing to the given directory.
+    fn frame_build(&self, output_dir: &Path, targets: &[TargetLanguage]) -> FrameBuild {
+        let mut frame_build = FrameBuild::new()
+            .output_dir(output_dir)
+            .set_targets(targets);
+        if let Some(input_dir) = &self.input_dir {
+            frame_build = frame_build.input_dir(input_dir);
+        }
+        if let Some(config) = &self.config {
+            frame_build = frame_build.frame_config(config);
+        }
+        if let Some(max_depth) = self.max_depth {
+            frame_build = frame_build.max_depth(max_depth);
+        }
+        if self.follow_links {
+            frame_build = frame_build.follow_links();
+        }
+        frame_build
+    }
+}
+
+fn main() -> Result<()> {
+    let targets_arg = || {
+        Arg::new("LANGUAGE")
+            .short('l')
+            .long("language")
+            .takes_value(true)
+            .help("Target languages separated by commas, instead of the configured ones")
+    };
+    let out_dir_arg = || {
+        Arg::new("OUT-DIR")
+            .long("out-dir")
+            .takes_value(true)
+            .help("Directory to write to, instead of the configured output directory")
+    };
+    // cargo runs `cargo-frame frame <args>` for `cargo frame <args>`
+    let matches = Command::new("cargo")
+        .bin_name("cargo")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("frame")
+                .version(env!("CARGO_PKG_VERSION"))
+                .about("Compile, check, diagram, format and create the Frame files of a package")
+                .subcommand_required(true)
+                .arg(
+                    Arg::new("MANIFEST-PATH")
+                        .long("manifest-path")
+                        .takes_value(true)
+                        .global(true)
+                        .help("Path to the package's Cargo.toml"),
+                )
+                .subcommand(
+                    Command::new("build")
+                        .about("Compile the package's Frame files to the output directory")
+                        .arg(targets_arg())
+                        .arg(out_dir_arg()),
+                )
+                .subcommand(
+                    Command::new("check")
+                        .about("Fail if the files in the output directory are not up to date")
+                        .arg(targets_arg())
+                        .arg(out_dir_arg()),
+                )
+                .subcommand(
+                    Command::new("diagram")
+                        .about("Generate a state diagram of each of the package's Frame files")
+                        .arg(
+                            Arg::new("FORMAT")
+                                .long("format")
+                                .takes_value(true)
+                                .possible_values(["smcat", "plantuml", "svg", "png", "dot"])
+                                .default_value("smcat")
+                                .help("Diagram language, or image rendered by Graphviz"),
+                        )
+                        .arg(
+                            Arg::new("DOT")
+                                .long("dot")
+                                .takes_value(true)
+                                .help("Graphviz program that renders svg and png diagrams, instead of dot"),
+                        )
+                        .arg(out_dir_arg()),
+                )
+                .subcommand(
+                    Command::new("fmt")
+                        .about("Format the package's Frame files in the canonical style")
+                        .arg(Arg::new("CHECK").long("check").help(
+                            "Exit with an error if any file is not formatted, without changing it",
+                        )),
+                )
+                .subcommand(
+                    Command::new("new")
+                        .about("Create a starter Frame file, with a Rust file implementing its actions and tests")
+                        .arg(
+                            Arg::new("NAME")
+                                .required(true)
+                                .help("Name of the machine, e.g. traffic_light or TrafficLight"),
+                        )
+                        .arg(
+                            Arg::new("DIR")
+                                .long("dir")
+                                .takes_value(true)
+                                .help("Directory to create the files in, instead of the input directory"),
+                        ),
+                ),
+        )
+        .get_matches();
+    let matches = matches.subcommand_matches("frame").unwrap();
+
+    // the paths of the configuration are relative to the package, as for a build script
+    let manifest_path = PathBuf::from(matches.value_of("MANIFEST-PATH").unwrap_or("Cargo.toml"));
+    let metadata = FrameMetadata::read(&manifest_path)?;
+    if let Some(package_dir) = manifest_path.parent() {
+        if !package_dir.as_os_str().is_empty() {
+            std::env::set_current_dir(package_dir)
+                .with_context(|| format!("failed to enter {}", package_dir.display()))?;
+        }
+    }
+
+    match matches.subcommand() {
+        Some(("build", args)) => build(&metadata, args),
+        Some(("check", args)) => check(&metadata, args),
+        Some(("diagram", args)) => diagram(&metadata, args),
+        Some(("fmt", args)) => fmt(&metadata, args.is_present("CHECK")),
+        Some(("new", args)) => new(&metadata, args),
+        _ => unreachable!("a subcommand is required"),
+    }
+}
+
+/// The target languages given on the command line, or the configured ones.
+fn targets(metadata: &FrameMetadata, args: &ArgMatches) -> Result<Vec<TargetLanguage>> {
+    match args.value_of("LANGUAGE") {
+        Some(languages) => languages
+            .split(',')
+            .map(|target| TargetLanguage::try_from(target.trim()).map_err(Error::msg))
+            .collect(),
+        None => metadata.targets(),
+    }
+}
+
+/// The output directory given on the command line, or the configured one.
+fn output_dir(metadata: &FrameMetadata, args: &ArgMatches) -> PathBuf {
+    args.value_of("OUT-DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| metadata.output_dir())
+}
+
+fn build(metadata: &FrameMetadata, args: &ArgMatches) -> Result<()> {
+    let output_dir = output_dir(metadata, args);
+    let generated = metadata
+        .frame_build(&output_dir, &targets(metadata, args)?)
+        .run()?;
+    eprintln!(
+        "Generated {} file(s) in {}",
+        generated.len(),
+        output_dir.display()
+    );
+    Ok(())
+}
+
+/// Compile the Frame files to a scratch directory and compare the generated files with those in
+/// the output directory.
+fn check(metadata: &FrameMetadata, args: &ArgMatches) -> Result<()> {
+    let output_dir = output_dir(metadata, args);
+    let scratch_dir =
+        std::env::temp_dir().join(format!("cargo-frame-check-{}", std::process::id()));
+    let result = metadata
+        .frame_build(&scratch_dir, &targets(metadata, args)?)
+        .run()
+        .and_then(|generated| stale_files(&scratch_dir, &output_dir, &generated));
+    let _ = fs::remove_dir_all(&scratch_dir);
+
+    let stale = result?;
+    if !stale.is_empty() {
+        bail!(
+            "{} generated file(s) are not up to date, run `cargo frame build` to update them:\n{}",
+            stale.len(),
+            stale.join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// Describe the files generated into the scratch directory that differ from their counterparts
+/// in the output directory.
+fn stale_files(
+    scratch_dir: &Path,
+    output_dir: &Path,
+    generated: &[PathBuf],
+) -> Result<Vec<String>> {
+    let mut stale = Vec::new();
+    for path in generated {
+        let existing_path = output_dir.join(path.strip_prefix(scratch_dir)?);
+        match fs::read(&existing_path) {
+            Ok(existing) if existing == fs::read(path)? => {}
+            Ok(_) => stale.push(format!("{} differs", existing_path.display())),
+            Err(_) => stale.push(format!("{} is missing", existing_path.display())),
+        }
+    }
+    Ok(stale)
+}
+
+fn diagram(metadata: &FrameMetadata, args: &ArgMatches) -> Result<()> {
+    let image_format = match args.value_of("FORMAT") {
+        Some("svg") => Some(DiagramFormat::Svg),
+        Some("png") => Some(DiagramFormat::Png),
+        Some("dot") => Some(DiagramFormat::Dot),
+        _ => None,
+    };
+    let generated = match image_format {
+        Some(format) => {
+            let diagram_dir = args
+                .value_of("OUT-DIR")
+                .map(PathBuf::from)
+                .or_else(|| metadata.diagram_dir.clone())
+                .unwrap_or_else(|| metadata.output_dir());
+            let program = PathBuf::from(args.value_of("DOT").unwrap_or("dot"));
+            metadata
+                .frame_build(&diagram_dir, &[])
+                .diagrams(&diagram_dir, &[format])
+                .diagram_renderer(graphviz(&program))
+                .run()?
+        }
+        None => {
+            let target = match args.value_of("FORMAT") {
+                Some("plantuml") => TargetLanguage::PlantUml,
+                _ => TargetLanguage::Smcat,
+            };
+            let output_dir = output_dir(metadata, args);
+            metadata.frame_build(&output_dir, &[target]).run()?
+        }
+    };
+    for path in generated {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn fmt(metadata: &FrameMetadata, check: bool) -> Result<()> {
+    let mut unformatted = Vec::new();
+    for path in metadata.frame_build(Path::new(""), &[]).input_files()? {
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let formatted = formatter::format_source(&source);
+        if formatted == source {
+            continue;
+        }
+        if check {
+            unformatted.push(path.display().to_string());
+        } else {
+            fs::write(&path, formatted)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+    }
+    if !unformatted.is_empty() {
+        bail!("not formatted:\n{}", unformatted.join("\n"));
+    }
+    Ok(())
+}
+
+/// Create the spec of a new machine, and the Rust file next to it that includes the code
+/// generated for it, as recommended by `frame_build`.
+fn new(metadata: &FrameMetadata, args: &ArgMatches) -> Result<()> {
+    let name = args.value_of("NAME").unwrap();
+    let valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        bail!("invalid machine name `{}`, expected an identifier", name);
+    }
+
+    // the generated code is included from the path of the spec in the input directory
+    let input_dir = metadata
+        .input_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("src"));
+    let dir = args
+        .value_of("DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input_dir.clone());
+    let local_dir = dir.strip_prefix(&input_dir).with_context(|| {
+        format!(
+            "{} is not in the input directory {}",
+            dir.display(),
+            input_dir.display()
+        )
+    })?;
+
+    let stem = scaffold::file_stem(name);
+    let spec_path = dir.join(&stem).with_extension("frm");
+    let rust_path = dir.join(&stem).with_extension("rs");
+    for path in [&spec_path, &rust_path] {
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+    }
+
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    fs::write(&spec_path, scaffold::spec(name))
+        .with_context(|| format!("failed to write {}", spec_path.display()))?;
+    let include_path = local_dir
+        .join(&stem)
+        .with_extension("rs")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let code = Exe::new()
+        .scaffold_wrapper_file(&metadata.config, &spec_path, &include_path)
+        .map_err(|err| Error::msg(err.error))?;
+    fs::write(&rust_path, code)
+        .with_context(|| format!("failed to write {}", rust_path.display()))?;
+
+    eprintln!(
+        "Created {} and {}",
+        spec_path.display(),
+        rust_path.display()
+    );
+    eprintln!("Add `mod {};` to the parent module to build them", stem);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata() {
+        let manifest = r#"
+[package]
+name = "lamp"
+
+[package.metadata.frame]
+input-dir = "specs"
+targets = ["rust", "smcat"]
+follow-links = true
+diagram-dir = "docs/diagrams"
+"#;
+        let metadata = FrameMetadata::parse(manifest).unwrap();
+        assert_eq!(metadata.input_dir, Some(PathBuf::from("specs")));
+        assert_eq!(metadata.output_dir(), PathBuf::from("target/frame"));
+        assert_eq!(
+            metadata.targets().unwrap(),
+            vec![TargetLanguage::Rust, TargetLanguage::Smcat]
+        );
+        assert!(metadata.follow_links);
+        assert_eq!(metadata.diagram_dir, Some(PathBuf::from("docs/diagrams")));
+
+        let metadata = FrameMetadata::parse("[package]\nname = \"lamp\"\n").unwrap();
+        assert_eq!(metadata, FrameMetadata::default());
+
+        let typo = manifest.replace("input-dir", "input_dir");
+        assert!(FrameMetadata::parse(&typo).is_err());
+        let target = manifest.replace("smcat", "scmat");
+        assert!(FrameMetadata::parse(&target).unwrap().targets().is_err());
+    }
+}
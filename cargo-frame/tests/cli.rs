@@ -0,0 +1,178 @@
+//! Tests of the `cargo frame` subcommands on a package in a temporary directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+const MANIFEST: &str = "[package]
+name = \"lamp\"
+version = \"0.1.0\"
+
+[package.metadata.frame]
+input-dir = \"specs\"
+output-dir = \"generated\"
+";
+
+/// Create a package with a Frame file in a new temporary directory.
+fn package(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cargo_frame_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("specs/sub")).unwrap();
+    fs::write(dir.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(dir.join("specs/sub/lamp.frm"), SPEC).unwrap();
+    dir
+}
+
+/// Run `cargo frame` with the given arguments on the package in `dir`.
+fn cargo_frame(dir: &Path, args: &[&str]) -> Output {
+    let manifest_path = dir.join("Cargo.toml");
+    Command::new(env!("CARGO_BIN_EXE_cargo-frame"))
+        .arg("frame")
+        .args(args)
+        .arg("--manifest-path")
+        .arg(&manifest_path)
+        .output()
+        .unwrap()
+}
+
+/// Test that `build` writes to the configured output directory, and that `check` fails until the
+/// generated files are up to date with the specs.
+#[test]
+fn build_and_check() {
+    let dir = package("build");
+    let output = cargo_frame(&dir, &["check"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("generated/sub/lamp.rs is missing"));
+
+    let output = cargo_frame(&dir, &["build"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let code = fs::read_to_string(dir.join("generated/sub/lamp.rs")).unwrap();
+    assert!(code.contains("pub struct Lamp"));
+    assert!(cargo_frame(&dir, &["check"]).status.success());
+
+    fs::write(dir.join("specs/sub/lamp.frm"), SPEC.replace("$On", "$Lit")).unwrap();
+    let output = cargo_frame(&dir, &["check"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("generated/sub/lamp.rs differs"));
+
+    let output = cargo_frame(&dir, &["build", "-l", "rust,python_3"]);
+    assert!(output.status.success());
+    assert!(dir.join("generated/sub/lamp.py").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `diagram` generates a diagram of each spec and prints its path.
+#[test]
+fn diagram() {
+    let dir = package("diagram");
+    let output = cargo_frame(&dir, &["diagram", "--out-dir", "diagrams"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "diagrams/sub/lamp.smcat");
+    let diagram = fs::read_to_string(dir.join("diagrams/sub/lamp.smcat")).unwrap();
+    assert!(diagram.contains("Off -> On"));
+
+    let output = cargo_frame(&dir, &["diagram", "--format", "plantuml"]);
+    assert!(output.status.success());
+    assert!(dir.join("generated/sub/lamp.puml").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `diagram` renders images through Graphviz, or writes the Graphviz source.
+#[test]
+fn rendered_diagram() {
+    let dir = package("rendered_diagram");
+    let output = cargo_frame(&dir, &["diagram", "--format", "dot", "--out-dir", "docs"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "docs/sub/lamp.dot");
+    let source = fs::read_to_string(dir.join("docs/sub/lamp.dot")).unwrap();
+    assert!(source.starts_with("digraph {"));
+    assert!(source.contains("\"Off\" -> \"On\" [label=\"toggle\"]"));
+
+    let output = cargo_frame(
+        &dir,
+        &["diagram", "--format", "svg", "--dot", "no-such-dot"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to run no-such-dot, is Graphviz installed?"));
+
+    // a stand-in for Graphviz that echoes its format and input
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let dot = dir.join("fake-dot");
+        fs::write(&dot, "#!/bin/sh\necho \"<svg $1>\"\ncat\n").unwrap();
+        fs::set_permissions(&dot, fs::Permissions::from_mode(0o755)).unwrap();
+        let dot = dot.to_str().unwrap();
+        let output = cargo_frame(&dir, &["diagram", "--format", "svg", "--dot", dot]);
+        assert!(output.status.success());
+        let svg = fs::read_to_string(dir.join("generated/sub/lamp.svg")).unwrap();
+        assert!(svg.starts_with("<svg -Tsvg>\ndigraph {"));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `fmt` formats the specs in place, and that `fmt --check` only reports them.
+#[test]
+fn fmt() {
+    let dir = package("fmt");
+    let spec_path = dir.join("specs/sub/lamp.frm");
+    let unformatted = SPEC.replace("    $Off", "  $Off");
+    fs::write(&spec_path, &unformatted).unwrap();
+
+    let output = cargo_frame(&dir, &["fmt", "--check"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("specs/sub/lamp.frm"));
+    assert_eq!(fs::read_to_string(&spec_path).unwrap(), unformatted);
+
+    assert!(cargo_frame(&dir, &["fmt"]).status.success());
+    assert_eq!(fs::read_to_string(&spec_path).unwrap(), SPEC);
+    assert!(cargo_frame(&dir, &["fmt", "--check"]).status.success());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `new` creates a spec and the Rust file including its generated code, and doesn't
+/// overwrite existing files.
+#[test]
+fn new() {
+    let dir = package("new");
+    let output = cargo_frame(&dir, &["new", "TrafficLight", "--dir", "specs/sub"]);
+    assert!(output.status.success());
+    let spec = fs::read_to_string(dir.join("specs/sub/traffic_light.frm")).unwrap();
+    assert!(spec.starts_with("#TrafficLight\n"));
+    let code = fs::read_to_string(dir.join("specs/sub/traffic_light.rs")).unwrap();
+    assert!(code
+        .starts_with("include!(concat!(env!(\"OUT_DIR\"), \"/\", \"sub/traffic_light.rs\"));\n"));
+    assert!(code.contains("mod tests {"));
+
+    let output = cargo_frame(&dir, &["new", "traffic_light", "--dir", "specs/sub"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("traffic_light.frm already exists"));
+    assert!(!cargo_frame(&dir, &["new", "lamp", "--dir", "other"])
+        .status
+        .success());
+
+    assert!(cargo_frame(&dir, &["build"]).status.success());
+    assert!(dir.join("generated/sub/traffic_light.rs").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -48,8 +48,7 @@
 //!
 //! For example, the following `build.rs` script would compile all of the Frame files in the
 //! package's `tests` directory to both Rust and [smcat](https://state-machine-cat.js.org/),
-//! excluding files with the string `"stack"` in their name (since the smcat backend does not
-//! support Frame's state stack feature).
+//! excluding files with the string `"draft"` in their name.
 //!
 //! ```no_run
 //! use anyhow::Result;
@@ -60,7 +59,7 @@
 //!     FrameBuild::new()
 //!         .input_dir(PathBuf::from("tests").as_path())
 //!         .add_target(TargetLanguage::Smcat)
-//!         .include_only_if(|path| !path.to_str().unwrap().contains("stack"))
+//!         .include_only_if(|path| !path.to_str().unwrap().contains("draft"))
 //!         .run()?;
 //!     Ok(())
 //! }
@@ -69,6 +68,24 @@
 //! The generated Rust and Smcat files will be stored side-by-side in their relative positions with
 //! the default output directory.
 //!
+//! Framec can be configured in code rather than with a `config.yaml` file by passing
+//! [`CompilerOptions`]. For example, the following script enables runtime support for all of the
+//! generated state machines.
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use frame_build::{CompilerOptions, FrameBuild, FrameConfig};
+//!
+//! fn main() -> Result<()> {
+//!     let mut config = FrameConfig::default();
+//!     config.codegen.rust.features.runtime_support = true;
+//!     FrameBuild::new()
+//!         .compiler_options(CompilerOptions::from(config))
+//!         .run()?;
+//!     Ok(())
+//! }
+//! ```
+//!
 //!
 //! # Incorporating Frame-generated Rust in your project
 //!
@@ -87,29 +104,655 @@
 //!
 //! // ... action implementations and supplemental definitions go here
 //! ```
+//!
+//! Alternatively, [`FrameBuild::generate_module_index`] writes a [`MODULE_INDEX`] file to the
+//! output directory that declares a module for each generated Rust file, nested as the Frame files
+//! are in the input directory, so a single `include!` pulls in every state machine. For example,
+//! with Frame files at `src/machines/lamp.frm` and `src/machines/door/lock.frm`, the following
+//! declares the modules `machines::lamp` and `machines::door::lock`:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/frame_generated.rs"));
+//! ```
+//!
+//! Modules are named after the files and directories, with characters that are not valid in
+//! identifiers replaced by `_`. The actions of the machines can then be implemented in any module
+//! of the package, e.g. `impl machines::lamp::Lamp { ... }`.
+//!
+//! [`FrameBuild::generate_include_stubs`] creates the recommended `.rs` file of each Frame file
+//! that doesn't have one yet, with empty implementations of its actions.
+//!
+//! [`FrameBuild::aggregate_output`] goes one step further and writes the code generated from all
+//! of the Frame files into a single file of nested modules, rather than a file for each of them.
+//!
+//!
+//! # Sharing definitions between Frame files
+//!
+//! A Frame file can pull in definitions from another file with an `#include "path"` directive,
+//! where the path is relative to the including file, e.g. the actions or the domain shared by
+//! several machines. We recommend another extension for files that are only meant to be included,
+//! such as `.frmi`, so that they are not compiled on their own.
+//!
+//! The build process compiles Frame files included by other Frame files before the files that
+//! include them, and tells Cargo to rerun the build script when an included file changes, even if
+//! it is outside of the input directories.
+//!
+//!
+//! # Rendering diagrams
+//!
+//! The build process can also render a diagram of the states and transitions of each machine,
+//! e.g. into a directory of documentation. The diagrams are laid out by Graphviz's `dot` program
+//! by default, which must be installed; [`FrameBuild::diagram_renderer`] plugs in another
+//! renderer, such as a bundled layout engine. The following script renders an SVG diagram of
+//! each Frame file into `docs/diagrams`, e.g. `docs/diagrams/a/b/sm.svg` for `src/a/b/sm.frm`.
+//!
+//! ```no_run
+//! use anyhow::Result;
+//! use frame_build::{DiagramFormat, FrameBuild};
+//! use std::path::Path;
+//!
+//! fn main() -> Result<()> {
+//!     FrameBuild::new()
+//!         .diagrams(Path::new("docs/diagrams"), &[DiagramFormat::Svg])
+//!         .run()?;
+//!     Ok(())
+//! }
+//! ```
 
-use anyhow::{Error, Result};
+use anyhow::{bail, Context, Error, Result};
 use framec::frame_c::compiler::Exe;
+use framec::frame_c::html;
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{ErrorKind, Write};
+use std::panic::AssertUnwindSafe;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{env, fs};
 use walkdir::WalkDir;
 
-// re-export `TargetLanguage` struct here since it's part of the `frame_build` interface
-pub use framec::frame_c::compiler::TargetLanguage;
+// re-export types here that are part of the `frame_build` interface
+pub use framec::frame_c::compiler::{CompilerOptions, TargetLanguage};
+pub use framec::frame_c::config::FrameConfig;
+pub use framec::frame_c::diagnostics::{Diagnostic, Severity};
+pub use framec::frame_c::ir::MachineModel;
+pub use framec::frame_c::utils::RunError;
+pub use log::{Level, LevelFilter};
+
+/// The formats of the diagrams rendered by [`FrameBuild::diagrams`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DiagramFormat {
+    /// The Graphviz source of the diagram, which is written without rendering it.
+    Dot,
+    Svg,
+    Png,
+}
+
+impl DiagramFormat {
+    /// The extension of diagram files in this format, which is also its name for Graphviz.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            DiagramFormat::Dot => "dot",
+            DiagramFormat::Svg => "svg",
+            DiagramFormat::Png => "png",
+        }
+    }
+}
+
+/// The formats of the machine metadata written by [`FrameBuild::emit_metadata`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MetadataFormat {
+    /// The JSON of the [`MachineModel`] of the machine, as emitted by Framec's `ir` target.
+    Json,
+}
+
+impl MetadataFormat {
+    /// The extension of metadata files in this format, after the name of the Frame file.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            MetadataFormat::Json => "machine.json",
+        }
+    }
+}
+
+/// How [`FrameBuild::embed_source`] keeps the source of each Frame file with the code generated
+/// from it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SourceEmbedding {
+    /// Copy the Frame file to the output directory, next to the generated files, e.g. `a/sm.frm`.
+    Copy,
+    /// Append a `pub const FRAME_SOURCE: &str` with the source of the Frame file to the generated
+    /// Rust code.
+    Const,
+}
+
+/// The name of the file written to the output directory by
+/// [`FrameBuild::generate_module_index`].
+pub const MODULE_INDEX: &str = "frame_generated.rs";
+
+/// The name of the file written to the output directory by [`FrameBuild::prune_stale_outputs`],
+/// which lists the files generated by the last build.
+pub const OUTPUT_MANIFEST: &str = "frame_build_outputs.txt";
+
+/// A function that lays out the Graphviz source of a diagram and renders it in a format.
+pub type DiagramRenderer = dyn Fn(&str, DiagramFormat) -> Result<Vec<u8>>;
+
+/// A function that transforms the code generated for a target into the code written to the file
+/// at a path.
+pub type PostProcessor = dyn Fn(TargetLanguage, &Path, &str) -> String;
+
+/// A function that maps the path of a Frame file relative to its input directory to the path of
+/// the file generated for a target relative to the output directory.
+pub type OutputNameMapper = dyn Fn(&Path, TargetLanguage) -> PathBuf;
+
+/// A function that generates the code of a custom target from a Frame file, given its path and
+/// its source.
+pub type CustomGenerator = dyn Fn(&Path, &str) -> Result<String>;
+
+/// A function that transforms the source of a Frame file at a path before it is compiled.
+pub type PreProcessor = dyn Fn(&Path, String) -> Result<String>;
+
+/// A function that is told the progress of the build.
+pub type ProgressCallback = dyn Fn(BuildProgress);
+
+/// A function that is told each failure of the build.
+pub type ErrorCallback = dyn Fn(&CompileErrorContext);
+
+/// A function that gets the messages of the build, with their level.
+pub type Logger = dyn Fn(Level, &str);
+
+/// A function that writes the part of an include stub after its `include!`, e.g. the
+/// implementation of the actions, given the model of the machine.
+pub type IncludeStubTemplate = dyn Fn(&MachineModel) -> String;
+
+/// A renderer that runs a Graphviz program, e.g. `dot`, with the source of the diagram on its
+/// standard input.
+pub fn graphviz(program: &Path) -> impl Fn(&str, DiagramFormat) -> Result<Vec<u8>> {
+    let program = program.to_path_buf();
+    move |source, format| {
+        let mut child = Command::new(&program)
+            .arg(format!("-T{}", format.file_extension()))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!(
+                    "failed to run {}, is Graphviz installed?",
+                    program.display()
+                )
+            })?;
+        // Graphviz reads the whole graph before writing the diagram
+        child.stdin.take().unwrap().write_all(source.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "{} failed: {}",
+                program.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// Format Rust code with the `rustfmt` program of the `RUSTFMT` environment variable, or on the
+/// `PATH`.
+fn rustfmt(code: &str) -> Result<String> {
+    let program = env::var_os("RUSTFMT").unwrap_or_else(|| "rustfmt".into());
+    let mut child = Command::new(&program)
+        .args(["--emit", "stdout", "--edition", "2018"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {:?}", program))?;
+    // rustfmt reads the whole file before writing it
+    child.stdin.take().unwrap().write_all(code.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "{:?} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// How [`FrameBuild::run`] resolves Frame files of different input directories at the same
+/// relative path, whose generated files would be stored at the same path of the output directory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputCollision {
+    /// Fail the build. This is the default.
+    Fail,
+    /// Compile the file of the input directory added first, and skip the others.
+    FirstWins,
+    /// Store the files generated from each input directory in a subdirectory of the output
+    /// directory named after the input directory, e.g. `$OUT_DIR/protocol/a/sm.rs` for
+    /// `protocol/a/sm.frm`, whether their paths collide or not.
+    NestByInputDir,
+}
+
+/// Where [`FrameBuild::run`] writes the generated code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CodegenMode {
+    /// Write the generated files to the output directory, by default `OUT_DIR`, every time. This
+    /// is the default.
+    OutDir,
+    /// The generated files are committed to the repository, in an output directory in the source
+    /// tree, e.g. so that IDEs find them. Files are only written when their content changes. With
+    /// `verify`, nothing is written, and the build fails if a generated file differs from the
+    /// committed one or is missing, e.g. in CI.
+    InTree { verify: bool },
+}
+
+/// The progress of the build, told to the function set by [`FrameBuild::on_progress`].
+#[derive(Clone, Copy, Debug)]
+pub enum BuildProgress<'a> {
+    /// A Frame file is about to be compiled to each of the targets.
+    FileStarted { input_path: &'a Path },
+    /// A Frame file was compiled to a target, named as in [`BuildErrors`], e.g. `rust`, the name
+    /// of a custom target, `metadata` or `diagrams`, successfully or not.
+    TargetFinished {
+        input_path: &'a Path,
+        target: &'a str,
+        duration: Duration,
+        succeeded: bool,
+    },
+    /// A Frame file was found but is not compiled, because it was excluded by
+    /// [`FrameBuild::include_only_if`] or its output collides with that of a file compiled
+    /// instead.
+    FileSkipped { input_path: &'a Path },
+    /// The build finished, successfully or not, after the total wall-clock time of the build.
+    Finished { duration: Duration },
+}
+
+/// A file generated by [`FrameBuild::run_collect`], kept in memory rather than written.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneratedArtifact {
+    /// The Frame file the file is generated from, or `None` for the module index.
+    pub input_path: Option<PathBuf>,
+    /// The name of the target the file is generated for, as in
+    /// [`BuildProgress::TargetFinished`], or `"source"` for the copy of a Frame file.
+    pub target: String,
+    /// The path [`FrameBuild::run`] would write the file to.
+    pub output_path: PathBuf,
+    pub contents: String,
+}
+
+/// Where [`FrameBuild::write_generated`] puts the generated files of a build.
+#[derive(Default)]
+struct Outputs {
+    /// How the committed files are stale, when verifying in-tree codegen.
+    stale: Vec<String>,
+    /// The generated files, kept in memory by [`FrameBuild::run_collect`] rather than written.
+    artifacts: Option<Vec<GeneratedArtifact>>,
+}
+
+/// The failures of a build, handled by [`FrameBuild::fail`].
+#[derive(Default)]
+struct Failures {
+    /// The failures collected by [`FrameBuild::collect_errors`].
+    errors: Vec<BuildError>,
+    /// Whether a Frame file failed to build, however the failure was handled.
+    any: bool,
+}
+
+/// A target added with [`FrameBuild::add_custom_target`].
+struct CustomTarget {
+    name: String,
+    extension: String,
+    generator: Box<CustomGenerator>,
+}
+
+/// A failure of the build, told to the function set by [`FrameBuild::on_error`].
+pub struct CompileErrorContext<'a> {
+    /// The Frame file that failed to build.
+    pub input_path: &'a Path,
+    /// What failed, see [`BuildError::target_name`].
+    pub target: &'a str,
+    /// The failure as diagnostics, see [`BuildError::diagnostics`].
+    pub diagnostics: Vec<Diagnostic>,
+    pub error: &'a BuildError,
+}
+
+/// A Frame file that failed to build, collected by [`FrameBuild::collect_errors`].
+#[derive(Debug)]
+pub struct BuildError {
+    pub input_path: PathBuf,
+    /// The target language the file failed to compile to, or `None` if it failed to be
+    /// pre-processed, its diagrams failed to render, its include stub failed to be created or it
+    /// failed to compile to a custom target.
+    pub target: Option<TargetLanguage>,
+    pub failure: BuildFailure,
+}
+
+/// How a Frame file failed to build.
+#[derive(Debug)]
+pub enum BuildFailure {
+    /// Framec returned an error, with its diagnostics.
+    Errored(RunError),
+    /// Framec panicked, with the message of the panic.
+    Panicked(String),
+    /// The diagrams of the file failed to render.
+    Diagram(Error),
+    /// The metadata of the file, written by [`FrameBuild::emit_metadata`], failed to generate.
+    Metadata(Error),
+    /// The function set by [`FrameBuild::pre_process`] returned an error, so the file was not
+    /// compiled.
+    PreProcess(Error),
+    /// The generator of a custom target returned an error.
+    CustomTarget { name: String, error: Error },
+    /// The include stub of the file, created by [`FrameBuild::generate_include_stubs`], failed to
+    /// be created, or the existing one doesn't include the generated file.
+    IncludeStub(Error),
+    /// The Rust module next to the file has no method for the actions, checked by
+    /// [`FrameBuild::verify_actions_implemented`].
+    MissingActions {
+        module: PathBuf,
+        actions: Vec<String>,
+    },
+    /// The actions of the file failed to be checked by
+    /// [`FrameBuild::verify_actions_implemented`], e.g. because the Rust module next to it failed
+    /// to be read.
+    VerifyActions(Error),
+}
+
+/// The Frame files that failed to build, returned by [`FrameBuild::run`] when
+/// [`FrameBuild::collect_errors`] is set.
+#[derive(Debug)]
+pub struct BuildErrors {
+    pub errors: Vec<BuildError>,
+}
+
+impl BuildError {
+    /// The name of what failed to build: the name of the target, as in
+    /// [`BuildProgress::TargetFinished`], or e.g. `"pre-process"`, `"include stub"` or
+    /// `"actions"`.
+    pub fn target_name(&self) -> &str {
+        match (self.target, &self.failure) {
+            (Some(target), _) => target.name(),
+            (None, BuildFailure::CustomTarget { name, .. }) => name,
+            (None, BuildFailure::PreProcess(_)) => "pre-process",
+            (None, BuildFailure::IncludeStub(_)) => "include stub",
+            (None, BuildFailure::MissingActions { .. })
+            | (None, BuildFailure::VerifyActions(_)) => "actions",
+            (None, BuildFailure::Metadata(_)) => "metadata",
+            (None, _) => "diagrams",
+        }
+    }
+
+    /// The failure as diagnostics, e.g. to print them in a format that IDEs can jump to. These are
+    /// the diagnostics of Framec, with the line and column of each error, a diagnostic for each
+    /// missing action, located in the Rust module, or a diagnostic without location otherwise.
+    /// Every diagnostic has a file, by default the Frame file.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let input_path = self.input_path.to_string_lossy();
+        let diagnostics = match &self.failure {
+            BuildFailure::Errored(err) => err.diagnostics.clone(),
+            BuildFailure::Panicked(msg) => {
+                vec![Diagnostic::error(
+                    "panic",
+                    &format!("Framec panicked: {}", msg),
+                )]
+            }
+            BuildFailure::Diagram(err)
+            | BuildFailure::Metadata(err)
+            | BuildFailure::PreProcess(err)
+            | BuildFailure::IncludeStub(err)
+            | BuildFailure::VerifyActions(err)
+            | BuildFailure::CustomTarget { error: err, .. } => {
+                vec![Diagnostic::error("build-error", &format!("{:#}", err))]
+            }
+            BuildFailure::MissingActions { module, actions } => actions
+                .iter()
+                .map(|action| {
+                    Diagnostic::error(
+                        "missing-action",
+                        &format!("no `fn {}` implements the action", action),
+                    )
+                    .with_file(Some(&module.to_string_lossy()))
+                })
+                .collect(),
+        };
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| match diagnostic.file {
+                Some(_) => diagnostic,
+                None => diagnostic.with_file(Some(&input_path)),
+            })
+            .collect()
+    }
+}
+
+/// A summary of the failure on one line, e.g. for a `cargo:warning` directive.
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let summary = match &self.failure {
+            BuildFailure::Errored(err) => match err.diagnostics.first() {
+                // the path of the file is already in the summary
+                Some(diagnostic) => diagnostic.clone().with_file(None).to_string(),
+                None => err.error.clone(),
+            },
+            BuildFailure::Panicked(msg) => format!("Framec panicked: {}", msg),
+            BuildFailure::Diagram(err)
+            | BuildFailure::Metadata(err)
+            | BuildFailure::PreProcess(err)
+            | BuildFailure::IncludeStub(err)
+            | BuildFailure::VerifyActions(err) => format!("{:#}", err),
+            BuildFailure::CustomTarget { error, .. } => format!("{:#}", error),
+            BuildFailure::MissingActions { module, actions } => format!(
+                "{} doesn't implement {}",
+                module.display(),
+                actions.join(", ")
+            ),
+        };
+        let what = match (self.target, &self.failure) {
+            (Some(target), _) => format!("generate {}", target.name()),
+            (None, BuildFailure::CustomTarget { name, .. }) => format!("generate {}", name),
+            (None, BuildFailure::PreProcess(_)) => String::from("pre-process"),
+            (None, BuildFailure::IncludeStub(_)) => String::from("create the include stub"),
+            (None, BuildFailure::MissingActions { .. })
+            | (None, BuildFailure::VerifyActions(_)) => String::from("verify the actions"),
+            (None, BuildFailure::Metadata(_)) => String::from("generate metadata"),
+            (None, _) => String::from("render diagrams"),
+        };
+        write!(
+            f,
+            "failed to {} from {}: {}",
+            what,
+            self.input_path.display(),
+            summary.split_whitespace().collect::<Vec<_>>().join(" ")
+        )
+    }
+}
+
+impl fmt::Display for BuildErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file_count = self
+            .errors
+            .iter()
+            .map(|error| &error.input_path)
+            .collect::<BTreeSet<_>>()
+            .len();
+        write!(
+            f,
+            "{} Frame file{} failed to build:",
+            file_count,
+            if file_count == 1 { "" } else { "s" }
+        )?;
+        for error in &self.errors {
+            write!(
+                f,
+                "\n{} ({}):",
+                error.input_path.display(),
+                error.target_name()
+            )?;
+            match &error.failure {
+                BuildFailure::Errored(err) => {
+                    for diagnostic in &err.diagnostics {
+                        write!(f, "\n    {}", diagnostic)?;
+                    }
+                }
+                BuildFailure::Panicked(msg) => write!(f, " Framec panicked: {}", msg)?,
+                BuildFailure::Diagram(err)
+                | BuildFailure::Metadata(err)
+                | BuildFailure::PreProcess(err)
+                | BuildFailure::IncludeStub(err)
+                | BuildFailure::VerifyActions(err) => write!(f, " {:#}", err)?,
+                BuildFailure::CustomTarget { error, .. } => write!(f, " {:#}", error)?,
+                BuildFailure::MissingActions { module, actions } => {
+                    for action in actions {
+                        write!(f, "\n    no `fn {}` in {}", action, module.display())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BuildErrors {}
+
+impl std::error::Error for BuildError {}
+
+/// Compile a single Frame file to a target language and return the generated code, e.g. in tests
+/// or tools that don't need [`FrameBuild`]'s traversal of the input directories. The Frame config
+/// file is found as by [`FrameBuild::frame_config`].
+///
+/// If Framec returns an error or panics, this fails with a [`BuildError`], which can be downcast
+/// from the returned error to inspect the failure.
+pub fn compile_file(input: &Path, target: TargetLanguage, config: Option<&Path>) -> Result<String> {
+    let config = config.map(Path::to_path_buf);
+    // a compiler of its own, which is dropped if it panics
+    let result = std::panic::catch_unwind(|| Exe::new().run_file(&config, input, Some(target)));
+    let failure = match result {
+        Ok(Ok(code)) => return Ok(code),
+        Ok(Err(err)) => BuildFailure::Errored(err),
+        Err(err) => BuildFailure::Panicked(panic_message(err)),
+    };
+    Err(Error::new(BuildError {
+        input_path: input.to_path_buf(),
+        target: Some(target),
+        failure,
+    }))
+}
+
+/// The `FRAME_SOURCE` constant that [`SourceEmbedding::Const`] appends to the Rust code generated
+/// from a Frame file, given its path relative to the input directory and its source.
+fn source_const(local_path: &Path, source: &str) -> String {
+    // a raw string, with enough `#`s not to be ended by the source
+    let mut hashes = String::from("#");
+    while source.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!(
+        "\n/// The Frame specification this code is generated from, `{}`.\n\
+         pub const FRAME_SOURCE: &str = r{}\"{}\"{};\n",
+        local_path.display(),
+        hashes,
+        source,
+        hashes
+    )
+}
+
+/// Whether the build runs in a build script, whose output Cargo reads, which is the default of
+/// [`FrameBuild::cargo_directives`]. Cargo only sets `OUT_DIR` for build scripts.
+fn in_build_script() -> bool {
+    env::var_os("OUT_DIR").is_some()
+}
+
+/// The current time in UTC, e.g. `2024-01-31T12:00:00Z`, or the time of the `SOURCE_DATE_EPOCH`
+/// environment variable if it is set.
+fn timestamp() -> String {
+    let secs = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default()
+        });
+    // the civil date of the day, after Howard Hinnant's `civil_from_days`
+    let days = (secs / 86400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// The message of a panic, if it is a string.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
 
 /// Create, configure, and run a Frame build process. The methods associated with this struct are
 /// designed to be chained to override the default configuration. After the process has been
 /// configured, the [`FrameBuild::run`] method starts the build process.
 pub struct FrameBuild {
+    compiler_options: CompilerOptions,
     frame_config: Option<PathBuf>,
-    input_dir: PathBuf,
+    input_dirs: Vec<PathBuf>,
+    output_collision: OutputCollision,
     output_dir: PathBuf,
+    codegen_mode: CodegenMode,
     targets: Vec<TargetLanguage>,
+    custom_targets: Vec<CustomTarget>,
+    file_extensions: Vec<String>,
     input_filter: Box<dyn Fn(&Path) -> bool>,
     max_depth: usize,
     min_depth: usize,
     follow_links: bool,
+    max_file_size: Option<u64>,
+    sort_by_path: bool,
+    cargo_directives: bool,
     continue_on_error: bool,
+    collect_errors: bool,
+    discover_configs: bool,
+    diagram_dir: Option<PathBuf>,
+    diagram_formats: Vec<DiagramFormat>,
+    diagram_renderer: Box<DiagramRenderer>,
+    metadata_format: Option<MetadataFormat>,
+    source_embedding: Option<SourceEmbedding>,
+    prune_stale_outputs: bool,
+    module_index: bool,
+    aggregate_output: Option<PathBuf>,
+    include_stubs: bool,
+    include_stub_template: Option<Box<IncludeStubTemplate>>,
+    verify_actions: bool,
+    pre_processor: Option<Box<PreProcessor>>,
+    file_header: Option<String>,
+    target_file_headers: Vec<(TargetLanguage, String)>,
+    post_processor: Option<Box<PostProcessor>>,
+    format_rust_output: bool,
+    output_name_mapper: Option<Box<OutputNameMapper>>,
+    progress_callback: Option<Box<ProgressCallback>>,
+    error_callback: Option<Box<ErrorCallback>>,
+    verbosity: LevelFilter,
+    logger: Option<Box<Logger>>,
 }
 
 impl Default for FrameBuild {
@@ -122,15 +765,48 @@ impl FrameBuild {
     /// Construct a new default configuration.
     pub fn new() -> Self {
         FrameBuild {
+            compiler_options: CompilerOptions::default(),
             frame_config: None,
-            input_dir: PathBuf::from("src"),
-            output_dir: PathBuf::from(env::var("OUT_DIR").unwrap()),
+            input_dirs: vec![PathBuf::from("src")],
+            output_collision: OutputCollision::Fail,
+            output_dir: env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_default(),
+            codegen_mode: CodegenMode::OutDir,
             targets: vec![TargetLanguage::Rust],
+            custom_targets: Vec::new(),
+            file_extensions: vec![String::from("frm")],
             input_filter: Box::new(|_| true),
             max_depth: ::std::usize::MAX,
             min_depth: 0,
             follow_links: false,
+            max_file_size: None,
+            sort_by_path: true,
+            cargo_directives: in_build_script(),
             continue_on_error: false,
+            collect_errors: false,
+            discover_configs: false,
+            diagram_dir: None,
+            diagram_formats: Vec::new(),
+            diagram_renderer: Box::new(graphviz(Path::new("dot"))),
+            metadata_format: None,
+            source_embedding: None,
+            prune_stale_outputs: false,
+            module_index: false,
+            aggregate_output: None,
+            include_stubs: false,
+            include_stub_template: None,
+            verify_actions: false,
+            pre_processor: None,
+            file_header: None,
+            target_file_headers: Vec::new(),
+            post_processor: None,
+            format_rust_output: false,
+            output_name_mapper: None,
+            progress_callback: None,
+            error_callback: None,
+            verbosity: LevelFilter::Info,
+            logger: None,
         }
     }
 
@@ -142,11 +818,50 @@ impl FrameBuild {
         self
     }
 
-    /// Set the root input directory to traverse, searching for `.frm` files.
+    /// Look for a Frame config file for each Frame file, to configure machines differently. The
+    /// config file of `sm.frm` is `sm.config.yaml` next to it if there is one, and otherwise the
+    /// `config.yaml` file of the nearest directory of the Frame file, up to the input directory.
+    /// Frame files with neither use the file set by [`FrameBuild::frame_config`], or Framec's
+    /// default.
+    ///
+    /// A discovered config file replaces the global one rather than being merged with it.
+    pub fn discover_configs(mut self) -> Self {
+        self.discover_configs = true;
+        self
+    }
+
+    /// Configure Framec in code. The configuration in the options takes the place of the default
+    /// configuration; a `config.yaml` file and the attributes in each Frame spec are merged on top
+    /// of it.
+    pub fn compiler_options(mut self, options: CompilerOptions) -> Self {
+        self.compiler_options = options;
+        self
+    }
+
+    /// Set the root input directory to traverse, searching for Frame files. This replaces any
+    /// directories added with [`FrameBuild::add_input_dir`].
     ///
     /// If unset, we will search the project's `src` directory.
     pub fn input_dir(mut self, path: &Path) -> Self {
-        self.input_dir = path.to_path_buf();
+        self.input_dirs = vec![path.to_path_buf()];
+        self
+    }
+
+    /// Add another root input directory to traverse, e.g. `protocol` next to the default `src`.
+    /// The directories are traversed in the order they were added.
+    ///
+    /// The generated files of each directory are stored at their relative path in the output
+    /// directory, so files at the same relative path in two directories collide. How collisions
+    /// are resolved is set by [`FrameBuild::on_output_collision`].
+    pub fn add_input_dir(mut self, path: &Path) -> Self {
+        self.input_dirs.push(path.to_path_buf());
+        self
+    }
+
+    /// Set how Frame files of different input directories at the same relative path are
+    /// resolved. By default, they fail the build.
+    pub fn on_output_collision(mut self, output_collision: OutputCollision) -> Self {
+        self.output_collision = output_collision;
         self
     }
 
@@ -159,12 +874,26 @@ impl FrameBuild {
     /// Cargo.
     ///
     /// If this library is used outside of the context of a `build.rs` script, this value must be
-    /// changed.
+    /// set, or [`FrameBuild::run`] fails.
     pub fn output_dir(mut self, path: &Path) -> Self {
         self.output_dir = path.to_path_buf();
         self
     }
 
+    /// Set where the generated code is written. By default, it is written to the output directory
+    /// on every build.
+    ///
+    /// With [`CodegenMode::InTree`], the generated files are committed to the repository, like
+    /// the code generated by `prost` or `tonic` in some projects. The output directory must then be
+    /// set with [`FrameBuild::output_dir`] to a directory of the source tree, e.g.
+    /// `src/generated`. When verifying, [`FrameBuild::run`] fails with a list of the generated
+    /// files that differ from the committed ones, and the build script is rerun when one of those
+    /// files changes. Diagrams are always written.
+    pub fn codegen_mode(mut self, codegen_mode: CodegenMode) -> Self {
+        self.codegen_mode = codegen_mode;
+        self
+    }
+
     /// Set the list of target languages to compile each Frame file to using Framec.
     ///
     /// By default, the build process compiles each Frame file to only Rust. Use this method if
@@ -175,6 +904,30 @@ impl FrameBuild {
         self
     }
 
+    /// Set a function that names the generated files, given the path of the Frame file relative to
+    /// its input directory and the target language, and returning the path of the generated file
+    /// relative to the output directory. For example, the following names the Rust file generated
+    /// from `a/sm.frm` `a/sm_generated.rs`, so that it doesn't clash with a hand-written `a/sm.rs`:
+    ///
+    /// ```no_run
+    /// # use frame_build::FrameBuild;
+    /// FrameBuild::new().output_name_mapper(|path, target| {
+    ///     let stem = path.file_stem().unwrap().to_string_lossy();
+    ///     path.with_file_name(format!("{}_generated.{}", stem, target.file_extension()))
+    /// });
+    /// ```
+    ///
+    /// By default, the generated file has the path of the Frame file with the extension of the
+    /// target, e.g. `a/sm.rs`. The files of state modules are stored relative to the generated
+    /// file that includes them, and the module index includes the renamed Rust files.
+    pub fn output_name_mapper(
+        mut self,
+        mapper: impl Fn(&Path, TargetLanguage) -> PathBuf + 'static,
+    ) -> Self {
+        self.output_name_mapper = Some(Box::new(mapper));
+        self
+    }
+
     /// Add an additional target language to compile to.
     ///
     /// By default, the build process compiles each Frame file to Rust. If Rust output is not
@@ -185,6 +938,58 @@ impl FrameBuild {
         self
     }
 
+    /// Add the target languages of the enabled Cargo features of the package, given pairs of a
+    /// feature and the target it enables, e.g. `&[("diagram", TargetLanguage::Smcat)]` to also
+    /// compile to smcat when the `diagram` feature is on. Cargo tells build scripts the enabled
+    /// features in the `CARGO_FEATURE_<NAME>` environment variables, with the name of the feature
+    /// in uppercase and `-` replaced by `_`, and reruns them when the features change. Targets that
+    /// are already in the list are not added again.
+    pub fn targets_from_features(mut self, features: &[(&str, TargetLanguage)]) -> Self {
+        for (feature, target) in features {
+            let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+            if env::var_os(var).is_some() && !self.targets.contains(target) {
+                self.targets.push(*target);
+            }
+        }
+        self
+    }
+
+    /// Add a target generated by a function rather than by Framec, e.g. a backend for an internal
+    /// DSL. The function is called with the path of each Frame file and its source, after the
+    /// built-in targets, and returns the generated code, which is written to the output directory
+    /// at the relative path of the Frame file with the given extension, e.g. `a/sm.dsl` for
+    /// `a/sm.frm` and `"dsl"`.
+    ///
+    /// The name of the target identifies it in errors. An error returned by the function is
+    /// handled like an error of Framec, as configured by [`FrameBuild::continue_on_error`] and
+    /// [`FrameBuild::collect_errors`]. The functions set by [`FrameBuild::output_name_mapper`]
+    /// and [`FrameBuild::post_process`] only apply to the built-in targets.
+    pub fn add_custom_target(
+        mut self,
+        name: &str,
+        extension: &str,
+        generator: impl Fn(&Path, &str) -> Result<String> + 'static,
+    ) -> Self {
+        self.custom_targets.push(CustomTarget {
+            name: name.to_string(),
+            extension: extension.to_string(),
+            generator: Box::new(generator),
+        });
+        self
+    }
+
+    /// Set the extensions of the Frame files to search for in the input directories, e.g.
+    /// `&["frm", "frame"]`.
+    ///
+    /// By default, only `.frm` files are Frame files.
+    pub fn file_extensions(mut self, extensions: &[&str]) -> Self {
+        self.file_extensions = extensions
+            .iter()
+            .map(|extension| extension.to_string())
+            .collect();
+        self
+    }
+
     /// Set a function that filters the Frame files found in the input directory based on their
     /// paths. For the path of each Frame file found, if this function returns `true`, the file is
     /// compiled into all of the target languages. If the function returns `false`, it is skipped.
@@ -209,102 +1014,1690 @@ impl FrameBuild {
     ///
     /// By default, the min depth is `0`.
     pub fn min_depth(mut self, depth: usize) -> Self {
-        self.max_depth = depth;
+        self.min_depth = depth;
         self
     }
 
     /// By default, the traversal of the input directory does not follow symbolic links. Calling
     /// this method sets a flag indicating that symbolic links *should* be followed.
+    ///
+    /// A link to one of its ancestor directories fails the build, since the traversal would never
+    /// end. A directory that is reached again through another link is not traversed again, so its
+    /// Frame files are only compiled once.
     pub fn follow_links(mut self) -> Self {
         self.follow_links = true;
         self
     }
 
+    /// Fail the build if a Frame file is larger than the given number of bytes, rather than read
+    /// it, e.g. when a large file ends up in the input directory with a Frame extension.
+    ///
+    /// By default, Frame files of any size are compiled.
+    pub fn max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Set whether the input directories are traversed in the order of the names of their
+    /// entries, so that the Frame files are compiled, and the generated files returned by
+    /// [`FrameBuild::run`], in the same order on every machine, e.g. for reproducible builds.
+    /// Otherwise, the order is that of the file system.
+    ///
+    /// By default, the traversal is sorted.
+    pub fn sort_by_path(mut self, sort: bool) -> Self {
+        self.sort_by_path = sort;
+        self
+    }
+
+    /// Set whether the build prints directives for Cargo to its standard output, which Cargo reads
+    /// from build scripts:
+    ///
+    /// * `cargo:rerun-if-changed` for the input directories, so that adding a Frame file reruns
+    ///   the build script, for each Frame file, for the Frame config file set by
+    ///   [`FrameBuild::frame_config`], or the `config.yaml` file that Framec reads by default, and
+    ///   for the files read by [`CodegenMode::InTree`] verification and
+    ///   [`FrameBuild::verify_actions_implemented`].
+    /// * `cargo:rerun-if-env-changed` for the variables of [`FrameBuild::apply_env_overrides`].
+    /// * `cargo:warning` for the Frame files skipped by [`FrameBuild::continue_on_error`].
+    ///
+    /// By default, the directives are printed in build scripts only, which are detected by the
+    /// `OUT_DIR` environment variable. Unset this for tools that run the build from a build script
+    /// but own its output, or set it to print the directives from elsewhere, e.g. a helper process
+    /// of a build script.
+    pub fn cargo_directives(mut self, print: bool) -> Self {
+        self.cargo_directives = print;
+        self
+    }
+
     /// By default, the build process halts if Framec panics or returns an error. Calling this
-    /// method sets a flag that changes this behavior to instead print an error message to `stderr`
-    /// and continue searching for and translating Frame files. This can be useful, for example,
-    /// when some Frame files are known to not translate to all target languages, and the failing
-    /// ones are not needed, or in test suites where some translations are expected to fail.
+    /// method sets a flag that changes this behavior to instead log an error message at the
+    /// [`Level::Warn`] level, to the function set by [`FrameBuild::logger`] or to the [`log`]
+    /// crate, and continue searching for and translating Frame files. This can be useful, for
+    /// example, when some Frame files are known to not translate to all target languages, and the
+    /// failing ones are not needed, or in test suites where some translations are expected to
+    /// fail.
     ///
     /// Rather than setting this flag, consider also invoking [`FrameBuild::run`] multiple times
     /// with different target languages and different configurations of
     /// [`FrameBuild::include_only_if`].
     ///
+    /// In a build script, each failure is also reported as a `cargo:warning`, with the first error
+    /// of Framec, so that it shows up in the output of Cargo. Unless a logger is set, this is the
+    /// only report of the failure that is visible by default.
+    ///
     /// Non-Frame errors (e.g. file I/O errors) will halt the build regardless of this setting.
     pub fn continue_on_error(mut self) -> Self {
         self.continue_on_error = true;
         self
     }
 
+    /// By default, the build process halts at the first Frame file that fails to build. Calling
+    /// this method sets a flag that changes this behavior to build all of the Frame files, and then
+    /// fail with a [`BuildErrors`] error listing every failure, e.g. so that CI reports all of the
+    /// broken specs at once. The error can be downcast from the error returned by
+    /// [`FrameBuild::run`] to inspect the failures.
+    ///
+    /// This takes precedence over [`FrameBuild::continue_on_error`]. Non-Frame errors (e.g. file
+    /// I/O errors) halt the build regardless of this setting.
+    pub fn collect_errors(mut self) -> Self {
+        self.collect_errors = true;
+        self
+    }
+
+    /// Also render a diagram of the states and transitions of each Frame file in each of the
+    /// formats. The diagrams are stored in the given directory, e.g. `docs/diagrams`, at the
+    /// relative position of the generated files in the output directory, and are included in the
+    /// vector returned by [`FrameBuild::run`].
+    ///
+    /// Failing to render a diagram halts the build unless [`FrameBuild::continue_on_error`] is
+    /// set, like an error of Framec.
+    pub fn diagrams(mut self, dir: &Path, formats: &[DiagramFormat]) -> Self {
+        self.diagram_dir = Some(dir.to_path_buf());
+        self.diagram_formats = formats.to_vec();
+        self
+    }
+
+    /// Set the function that lays out and renders the diagrams, given their Graphviz source.
+    ///
+    /// By default, diagrams are rendered by [`graphviz`] with the `dot` program on the `PATH`.
+    /// Use this method to run another installation of Graphviz, a layout engine bundled with the
+    /// build script, or a rendering service.
+    pub fn diagram_renderer(
+        mut self,
+        renderer: impl Fn(&str, DiagramFormat) -> Result<Vec<u8>> + 'static,
+    ) -> Self {
+        self.diagram_renderer = Box::new(renderer);
+        self
+    }
+
+    /// Also write the metadata of each machine to the output directory in the given format, e.g.
+    /// `a/sm.machine.json` for `a/sm.frm`, for tools that document or simulate the machines
+    /// without parsing Frame. The metadata describes the interface, states, event handlers,
+    /// transitions, actions and domain of the machine; see [`MachineModel`]. The metadata files
+    /// are included in the vector returned by [`FrameBuild::run`].
+    ///
+    /// Failing to generate the metadata is handled like an error of Framec, as configured by
+    /// [`FrameBuild::continue_on_error`] and [`FrameBuild::collect_errors`].
+    pub fn emit_metadata(mut self, format: MetadataFormat) -> Self {
+        self.metadata_format = Some(format);
+        self
+    }
+
+    /// Keep the source of each Frame file with the code generated from it, so that an error of a
+    /// machine at runtime can be traced back to its spec without looking for it in the source
+    /// tree. The source is the content of the Frame file, before it is pre-processed.
+    ///
+    /// With [`SourceEmbedding::Copy`], the Frame files are copied to the output directory and
+    /// included in the vector returned by [`FrameBuild::run`]. The output directory can't be in an
+    /// input directory then, where the copies would be compiled too. With
+    /// [`SourceEmbedding::Const`], the Rust code generated from each Frame file ends with a
+    /// `FRAME_SOURCE` constant, so the modules that include the generated code have one.
+    pub fn embed_source(mut self, embedding: SourceEmbedding) -> Self {
+        self.source_embedding = Some(embedding);
+        self
+    }
+
+    /// Remove the files generated by the previous build that the current build doesn't generate,
+    /// e.g. because their Frame file was deleted or renamed, so that they can't be included by
+    /// mistake. The files generated by each build are listed in an [`OUTPUT_MANIFEST`] file in the
+    /// output directory, so only files generated by a previous build with this option are removed,
+    /// and so are the directories they leave empty. Diagrams and include stubs are not removed.
+    ///
+    /// Nothing is removed when a Frame file fails to build, since its generated files are not
+    /// updated; they are removed by the next build that succeeds if they are stale. With
+    /// [`CodegenMode::InTree`] and `verify: true`, stale files are reported rather than removed.
+    pub fn prune_stale_outputs(mut self) -> Self {
+        self.prune_stale_outputs = true;
+        self
+    }
+
+    /// Format the generated Rust code with `rustfmt`, e.g. to read it when debugging an error in
+    /// the included code. The program is taken from the `RUSTFMT` environment variable if it is
+    /// set, and otherwise found on the `PATH`.
+    ///
+    /// Formatting is best effort: if `rustfmt` can't be run or fails, the code is written as
+    /// generated and a warning is logged at the [`Level::Warn`] level, to the function set by
+    /// [`FrameBuild::logger`] or to the [`log`] crate, so it is not visible by default. Code maps
+    /// are written as generated, so they don't match formatted files.
+    pub fn format_rust_output(mut self) -> Self {
+        self.format_rust_output = true;
+        self
+    }
+
+    /// Set a function that transforms the source of each Frame file before it is compiled, e.g. to
+    /// expand custom macros. It is called with the path of the file and its source, and returns
+    /// the Frame specification to compile, for all of the targets, custom targets, diagrams and
+    /// include stubs. Errors and code maps refer to the lines of the transformed source.
+    ///
+    /// An error returned by the function is handled like an error of Framec, as configured by
+    /// [`FrameBuild::continue_on_error`] and [`FrameBuild::collect_errors`].
+    pub fn pre_process(
+        mut self,
+        pre_processor: impl Fn(&Path, String) -> Result<String> + 'static,
+    ) -> Self {
+        self.pre_processor = Some(Box::new(pre_processor));
+        self
+    }
+
+    /// Prepend a header to every generated file, e.g. a license and a "DO NOT EDIT" banner. The
+    /// text is written as it is, so it must be a comment in the languages of the targets; use
+    /// [`FrameBuild::target_file_header`] for targets with another comment syntax. The following
+    /// variables in the text are replaced:
+    ///
+    /// * `{input_path}` - the path of the Frame file, or the input directories for the module
+    ///   index.
+    /// * `{timestamp}` - the time the file is generated, in UTC, e.g. `2024-01-31T12:00:00Z`, or
+    ///   the time of the `SOURCE_DATE_EPOCH` environment variable for reproducible builds. A
+    ///   timestamp changes the file on every build, which makes [`CodegenMode::InTree`]
+    ///   verification fail unless `SOURCE_DATE_EPOCH` is set.
+    /// * `{framec_version}` - the version of Framec, e.g. `0.8.0`.
+    ///
+    /// The header is added after the code is formatted by [`FrameBuild::format_rust_output`] and
+    /// before the function set by [`FrameBuild::post_process`] is called. Code maps and diagrams
+    /// have no header, and code maps don't account for its lines.
+    pub fn file_header(mut self, text: &str) -> Self {
+        self.file_header = Some(text.to_string());
+        self
+    }
+
+    /// Prepend a header to the files generated for a target, instead of the one set by
+    /// [`FrameBuild::file_header`], e.g. with the comment syntax of the target. The text has the
+    /// same variables.
+    pub fn target_file_header(mut self, target: TargetLanguage, text: &str) -> Self {
+        self.target_file_headers
+            .retain(|(other_target, _)| *other_target != target);
+        self.target_file_headers.push((target, text.to_string()));
+        self
+    }
+
+    /// Set a function that transforms the generated code before it is written, e.g. to add a
+    /// license header or rewrite a module path. It is called with the target language, the path
+    /// the code is written to and the generated code, and returns the code to write. The files of
+    /// state modules are transformed too, with the target language of the file that includes
+    /// them.
+    ///
+    /// Code maps are written as generated, so they no longer match a file whose lines the function
+    /// adds or removes.
+    pub fn post_process(
+        mut self,
+        post_processor: impl Fn(TargetLanguage, &Path, &str) -> String + 'static,
+    ) -> Self {
+        self.post_processor = Some(Box::new(post_processor));
+        self
+    }
+
+    /// Also write a [`MODULE_INDEX`] file to the output directory that declares a module for each
+    /// generated Rust file, nested in modules named after the directories of the Frame file, and
+    /// includes the file in it. The index is included in the vector returned by
+    /// [`FrameBuild::run`].
+    ///
+    /// Files that Framec failed to compile with [`FrameBuild::continue_on_error`] are left out of
+    /// the index. Two Frame files whose modules would have the same name, e.g. `a-b.frm` and
+    /// `a_b.frm`, make the build fail.
+    pub fn generate_module_index(mut self) -> Self {
+        self.module_index = true;
+        self
+    }
+
+    /// Write the Rust code generated from all of the Frame files into a single file of the output
+    /// directory with the given name, e.g. `frame_machines.rs`, rather than a file for each Frame
+    /// file. The code generated from each Frame file is in a module named after it, nested in
+    /// modules named after its directories as in the [`MODULE_INDEX`], e.g. `a::b::sm` for
+    /// `src/a/b/sm.frm`, so a single `include!` of the file pulls in every machine, which IDEs
+    /// such as rust-analyzer handle better than many `include!`s. The file is included in the
+    /// vector returned by [`FrameBuild::run`].
+    ///
+    /// Code maps are not written, since their lines are those of the separate files, and machines
+    /// whose state modules are split into files fail the build. The module index and include stubs
+    /// include the separate files, so they can't be generated with the aggregated output.
+    pub fn aggregate_output(mut self, file_name: &str) -> Self {
+        self.aggregate_output = Some(PathBuf::from(file_name));
+        self
+    }
+
+    /// Also create the Rust file recommended for each Frame file next to it, e.g. `src/a/b/sm.rs`
+    /// for `src/a/b/sm.frm`, if it doesn't exist. The file includes the generated Rust file from
+    /// `OUT_DIR`, implements the actions of the machine with empty methods, and ends with its test
+    /// skeleton, like the file created by `cargo frame new`. Use
+    /// [`FrameBuild::include_stub_template`] to write something else after the `include!`.
+    ///
+    /// Existing files are never overwritten, but the build fails if one doesn't include its
+    /// generated file, e.g. after the Frame file was moved or the generated file renamed. Stubs are
+    /// only created for Frame files compiled to Rust, and are not included in the vector returned
+    /// by [`FrameBuild::run`].
+    pub fn generate_include_stubs(mut self) -> Self {
+        self.include_stubs = true;
+        self
+    }
+
+    /// Set a function that writes the part of the include stubs created by
+    /// [`FrameBuild::generate_include_stubs`] after their `include!`, e.g. to implement the
+    /// actions in a trait rather than with inherent methods. For example, the following leaves a
+    /// `todo!()` in each action:
+    ///
+    /// ```no_run
+    /// # use frame_build::FrameBuild;
+    /// FrameBuild::new()
+    ///     .generate_include_stubs()
+    ///     .include_stub_template(|model| {
+    ///         let mut code = format!("\nimpl {} {{\n", model.name);
+    ///         for action in &model.actions {
+    ///             code.push_str(&format!("    fn {}(&self) {{\n", action.name));
+    ///             code.push_str("        todo!()\n    }\n");
+    ///         }
+    ///         code.push_str("}\n");
+    ///         code
+    ///     });
+    /// ```
+    pub fn include_stub_template(
+        mut self,
+        template: impl Fn(&MachineModel) -> String + 'static,
+    ) -> Self {
+        self.include_stub_template = Some(Box::new(template));
+        self
+    }
+
+    /// Set a function that is told the progress of the build, e.g. to print it from `build.rs` or
+    /// record the time each Frame file takes to compile. See [`BuildProgress`] for the events.
+    pub fn on_progress(mut self, callback: impl Fn(BuildProgress) + 'static) -> Self {
+        self.progress_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Set a function that is told each failure of the build, with its diagnostics, e.g. to print
+    /// them as `path:line:column` so that IDEs can jump to them, or to write a report file. The
+    /// function is told the failures as they happen, however they are handled, as configured by
+    /// [`FrameBuild::continue_on_error`] and [`FrameBuild::collect_errors`].
+    pub fn on_error(mut self, callback: impl Fn(&CompileErrorContext) + 'static) -> Self {
+        self.error_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the most verbose level of the messages of the build, e.g. [`LevelFilter::Off`] to
+    /// silence them, or [`LevelFilter::Debug`] to trace which Frame files are compiled and where
+    /// their generated files go. The messages are:
+    ///
+    /// * warnings for the Frame files skipped by [`FrameBuild::continue_on_error`] and the files
+    ///   that fail to be formatted,
+    /// * info about the files removed by [`FrameBuild::prune_stale_outputs`], and a summary of the
+    ///   build,
+    /// * debug traces of the files that are skipped or excluded by the traversal, the files each
+    ///   Frame file includes, and the path of each generated file,
+    /// * and traces of the files that are ignored because of their extension.
+    ///
+    /// By default, the level is [`LevelFilter::Info`]. The `cargo:` directives of build scripts
+    /// are not messages, they are always printed as configured by [`FrameBuild::cargo_directives`].
+    pub fn verbosity(mut self, level: LevelFilter) -> Self {
+        self.verbosity = level;
+        self
+    }
+
+    /// Set a function that gets the messages of the build up to the level set by
+    /// [`FrameBuild::verbosity`], e.g. to print them or capture them. By default, the messages go
+    /// to the logger of the [`log`] crate, with the `frame_build` target, so they are only
+    /// printed if the build script sets a logger, e.g. `env_logger`.
+    pub fn logger(mut self, logger: impl Fn(Level, &str) + 'static) -> Self {
+        self.logger = Some(Box::new(logger));
+        self
+    }
+
+    /// Check that the actions of each machine without a body in the spec are implemented by the
+    /// Rust module next to the Frame file, e.g. `src/a/b/sm.rs` for `src/a/b/sm.frm`, which is the
+    /// file recommended for including the generated code. Otherwise, a machine whose actions are
+    /// not implemented fails later with errors in the generated code. An action is implemented if
+    /// the module defines a function named like its method in the generated code, e.g. `fn log(`.
+    ///
+    /// Missing actions are handled like an error of Framec, as configured by
+    /// [`FrameBuild::continue_on_error`] and [`FrameBuild::collect_errors`], and listed in the
+    /// [`BuildFailure::MissingActions`] failure, and so is a module that can't be read, in the
+    /// [`BuildFailure::VerifyActions`] failure. Only Frame files compiled to Rust are checked.
+    pub fn verify_actions_implemented(mut self) -> Self {
+        self.verify_actions = true;
+        self
+    }
+
+    /// Override the configuration with environment variables, e.g. to change the targets in CI
+    /// without editing `build.rs`. The variables that are set replace the configuration made by
+    /// the methods called before this one:
+    ///
+    /// * `FRAME_BUILD_TARGETS` - the target languages, separated by commas, e.g. `rust,smcat`, as
+    ///   by [`FrameBuild::set_targets`].
+    /// * `FRAME_BUILD_INPUT_DIR` - the input directories, separated as in `PATH`, e.g.
+    ///   `src:protocol` on Unix.
+    /// * `FRAME_BUILD_OUTPUT_DIR` - the output directory.
+    /// * `FRAME_BUILD_CONFIG` - the Frame config file.
+    /// * `FRAME_BUILD_CONTINUE_ON_ERROR` and `FRAME_BUILD_COLLECT_ERRORS` - `true` or `1` to set
+    ///   [`FrameBuild::continue_on_error`] or [`FrameBuild::collect_errors`], `false` or `0` to
+    ///   unset it.
+    /// * `FRAME_BUILD_VERBOSITY` - the level of [`FrameBuild::verbosity`], e.g. `debug` or `off`.
+    ///
+    /// Cargo is told to rerun the build script when any of the variables changes, unless
+    /// [`FrameBuild::cargo_directives`] is unset. The variables are read when this method is
+    /// called, so it must come after [`FrameBuild::cargo_directives`].
+    /// This fails if a variable has an invalid value, e.g. an unknown target language.
+    pub fn apply_env_overrides(mut self) -> Result<Self> {
+        let cargo_directives = self.cargo_directives;
+        let var = |name: &str| {
+            if cargo_directives {
+                println!("cargo:rerun-if-env-changed={}", name);
+            }
+            env::var_os(name).filter(|value| !value.is_empty())
+        };
+        let flag = |name: &str| -> Result<Option<bool>> {
+            match var(name) {
+                Some(value) => match value.to_string_lossy().trim() {
+                    "1" | "true" => Ok(Some(true)),
+                    "0" | "false" => Ok(Some(false)),
+                    value => bail!(
+                        "invalid value `{}` of {}, expected true or false",
+                        value,
+                        name
+                    ),
+                },
+                None => Ok(None),
+            }
+        };
+
+        if let Some(targets) = var("FRAME_BUILD_TARGETS") {
+            self.targets = targets
+                .to_string_lossy()
+                .split(',')
+                .map(|target| TargetLanguage::try_from(target.trim()).map_err(Error::msg))
+                .collect::<Result<_>>()
+                .context("invalid FRAME_BUILD_TARGETS")?;
+        }
+        if let Some(input_dirs) = var("FRAME_BUILD_INPUT_DIR") {
+            self.input_dirs = env::split_paths(&input_dirs).collect();
+        }
+        if let Some(output_dir) = var("FRAME_BUILD_OUTPUT_DIR") {
+            self.output_dir = PathBuf::from(output_dir);
+        }
+        if let Some(frame_config) = var("FRAME_BUILD_CONFIG") {
+            self.frame_config = Some(PathBuf::from(frame_config));
+        }
+        if let Some(continue_on_error) = flag("FRAME_BUILD_CONTINUE_ON_ERROR")? {
+            self.continue_on_error = continue_on_error;
+        }
+        if let Some(collect_errors) = flag("FRAME_BUILD_COLLECT_ERRORS")? {
+            self.collect_errors = collect_errors;
+        }
+        if let Some(verbosity) = var("FRAME_BUILD_VERBOSITY") {
+            let verbosity = verbosity.to_string_lossy();
+            self.verbosity = verbosity.trim().parse().map_err(|_| {
+                Error::msg(format!(
+                    "invalid value `{}` of FRAME_BUILD_VERBOSITY, expected off, error, warn, \
+                     info, debug or trace",
+                    verbosity
+                ))
+            })?;
+        }
+        Ok(self)
+    }
+
+    /// Check the configuration for errors that would make [`FrameBuild::run`] fail or do nothing,
+    /// e.g. to report them before a long build. [`FrameBuild::run`] checks the configuration
+    /// first too. The configuration is invalid if:
+    ///
+    /// * the min depth is greater than the max depth,
+    /// * an input directory doesn't exist,
+    /// * there is nothing to generate, i.e. no targets, custom targets or diagrams,
+    /// * the output directory is not set outside of a build script, where `OUT_DIR` is not set,
+    /// * the output directory is in `OUT_DIR` with [`CodegenMode::InTree`],
+    /// * the output directory is in an input directory with [`SourceEmbedding::Copy`],
+    /// * or the Rust code is aggregated with [`FrameBuild::aggregate_output`] and the module index
+    ///   or include stubs are generated.
+    pub fn validate(&self) -> Result<()> {
+        if self.min_depth > self.max_depth {
+            bail!(
+                "the min depth {} is greater than the max depth {}",
+                self.min_depth,
+                self.max_depth
+            );
+        }
+        for input_dir in &self.input_dirs {
+            if !input_dir.is_dir() {
+                bail!("the input directory {:?} doesn't exist", input_dir);
+            }
+        }
+        if self.targets.is_empty() && self.custom_targets.is_empty() && self.diagram_dir.is_none() {
+            bail!("there are no targets to generate, add one with `FrameBuild::add_target`");
+        }
+        if self.output_dir.as_os_str().is_empty() {
+            bail!("OUT_DIR is not set, set the output directory with `FrameBuild::output_dir`");
+        }
+        if let (CodegenMode::InTree { .. }, Some(out_dir)) =
+            (self.codegen_mode, env::var_os("OUT_DIR"))
+        {
+            if self.output_dir.starts_with(out_dir) {
+                bail!(
+                    "in-tree codegen needs an output directory in the source tree, \
+                     set it with `FrameBuild::output_dir`"
+                );
+            }
+        }
+        if self.source_embedding == Some(SourceEmbedding::Copy) {
+            if let Some(input_dir) = self
+                .input_dirs
+                .iter()
+                .find(|input_dir| self.output_dir.starts_with(input_dir))
+            {
+                bail!(
+                    "the copies of the Frame files in {:?} would be compiled from the input \
+                     directory {:?}, set another output directory with `FrameBuild::output_dir`",
+                    self.output_dir,
+                    input_dir
+                );
+            }
+        }
+        if self.aggregate_output.is_some() && (self.module_index || self.include_stubs) {
+            bail!(
+                "the module index and include stubs include the separate Rust files, which are \
+                 not written with `FrameBuild::aggregate_output`"
+            );
+        }
+        Ok(())
+    }
+
     /// Run the Frame build process. The build process is highly configurable using the other
     /// methods associated with this struct.
     ///
     /// On success, this function returns a vector of paths to each of the generated files.
+    /// When the `codegen.rust.features.generate_source_map` feature is enabled, the code map of
+    /// each generated Rust file is written next to it, e.g. `traffic.rs.map.json` for
+    /// `traffic.rs`. Code maps are not included in the returned vector. When the
+    /// `codegen.rust.features.split_states` feature is enabled, the files of the state modules
+    /// are written next to the generated file that includes them and are included in the vector.
     pub fn run(&self) -> Result<Vec<PathBuf>> {
+        self.build(&mut Outputs::default())
+    }
+
+    /// Run the Frame build process like [`FrameBuild::run`], but keep the generated files in
+    /// memory rather than write them, e.g. to post-process them and write them elsewhere. Each
+    /// [`GeneratedArtifact`] has the path the file would be written to, and its contents.
+    ///
+    /// The artifacts include the code maps, state modules, metadata, copies of the Frame files
+    /// and module index that [`FrameBuild::run`] would write to the output directory, in the
+    /// order they are generated. Diagrams and include stubs are still written, and
+    /// [`FrameBuild::prune_stale_outputs`] doesn't remove files.
+    pub fn run_collect(&self) -> Result<Vec<GeneratedArtifact>> {
+        let mut outputs = Outputs {
+            artifacts: Some(Vec::new()),
+            ..Outputs::default()
+        };
+        self.build(&mut outputs)?;
+        Ok(outputs.artifacts.unwrap_or_default())
+    }
+
+    /// Run the Frame build process, and put the generated files in `outputs`.
+    fn build(&self, outputs: &mut Outputs) -> Result<Vec<PathBuf>> {
+        let start = Instant::now();
         let mut generated_files = Vec::new();
+        let mut modules = ModuleTree::default();
+        let mut aggregate = ModuleTree::default();
+        let mut failures = Failures::default();
+        // the generated files that are not returned
+        let mut code_maps = Vec::new();
+        // one compiler for all files, so that the configuration file is only read once
+        let exe = Exe::with_options(self.compiler_options.clone());
 
-        let walk_dir = WalkDir::new(&self.input_dir)
-            .max_depth(self.max_depth)
-            .min_depth(self.min_depth)
-            .follow_links(self.follow_links);
+        self.validate()?;
 
-        for entry in walk_dir {
-            let entry = entry?;
-            let input_path = entry.path();
-            if input_path.extension().unwrap_or_default() == "frm"
-                && (&self.input_filter)(input_path)
-            {
-                // tell Cargo this is a source file
-                println!("cargo:rerun-if-changed={:?}", &input_path);
-
-                let local_path = input_path.strip_prefix(&self.input_dir)?;
-                let output_path = self.output_dir.join(local_path);
-                fs::create_dir_all(output_path.parent().unwrap())?;
-
-                for target in &self.targets {
-                    let mut target_output_path = output_path.clone();
-                    target_output_path.set_extension(target.file_extension());
-
-                    let frame_config = &self.frame_config;
-                    let framec_result = std::panic::catch_unwind(move || {
-                        Exe::new().run_file(frame_config, input_path, Some(*target))
-                    });
-
-                    match framec_result {
-                        Ok(Ok(output_content)) => {
-                            // success, write the file
-                            fs::write(&target_output_path, output_content)?;
-                            generated_files.push(target_output_path);
+        if self.cargo_directives {
+            // Cargo reruns the script when a file in the directories is added, removed or changed
+            for input_dir in &self.input_dirs {
+                println!("cargo:rerun-if-changed={}", input_dir.display());
+            }
+            // a path that doesn't exist would rerun the script on every build
+            let local_config = Some(PathBuf::from("config.yaml"))
+                .filter(|_| self.compiler_options.load_local_config);
+            let frame_config = self
+                .frame_config
+                .clone()
+                .or(local_config)
+                .filter(|path| path.exists());
+            if let Some(frame_config) = frame_config {
+                println!("cargo:rerun-if-changed={}", frame_config.display());
+            }
+        }
+
+        let mut skipped = Vec::new();
+        let sources = self.sources(&mut skipped)?;
+        for input_path in &skipped {
+            self.progress(BuildProgress::FileSkipped { input_path });
+        }
+        for (input_path, local_path, includes) in self.include_order(&exe, sources) {
+            let input_path = input_path.as_path();
+            let local_path = local_path.as_path();
+            if self.cargo_directives {
+                // tell Cargo this is a source file, and so are the files it includes, which may
+                // be outside of the input directories
+                println!("cargo:rerun-if-changed={}", input_path.display());
+                for include in &includes {
+                    println!("cargo:rerun-if-changed={}", include.display());
+                }
+            }
+            self.progress(BuildProgress::FileStarted { input_path });
+            for include in &includes {
+                self.log(
+                    Level::Debug,
+                    format_args!("{:?} includes {:?}", input_path, include),
+                );
+            }
+
+            let original = fs::read_to_string(input_path)
+                .with_context(|| format!("failed to read {:?}", input_path))?;
+            let source = match &self.pre_processor {
+                Some(pre_processor) => match pre_processor(input_path, original.clone()) {
+                    Ok(source) => source,
+                    Err(err) => {
+                        let msg = format!("Failed to pre-process {:?}: {:#}", input_path, err);
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: None,
+                            failure: BuildFailure::PreProcess(err),
+                        };
+                        self.fail(&mut failures, error, msg)?;
+                        continue;
+                    }
+                },
+                None => original.clone(),
+            };
+            let frame_config = self.frame_config_of(input_path);
+
+            for target in &self.targets {
+                let target_local_path = self.output_name(local_path, *target);
+                let target_output_path = self.output_dir.join(&target_local_path);
+
+                let target_start = Instant::now();
+                let frame_config = &frame_config;
+                let source = &source;
+                // the per-run state of `exe` is reset at the start of each run, so it can be
+                // reused after a panic
+                let framec = AssertUnwindSafe(&exe);
+                let framec_result = std::panic::catch_unwind(move || {
+                    let result = framec.run(
+                        frame_config,
+                        input_path.to_str(),
+                        source.clone(),
+                        Some(*target),
+                    );
+                    result.map(|output_content| {
+                        (output_content, framec.code_map(), framec.state_files())
+                    })
+                });
+                let succeeded = matches!(framec_result, Ok(Ok(_)));
+
+                match framec_result {
+                    Ok(Ok((mut output_content, code_map_opt, state_files))) => {
+                        // success, write the file
+                        if self.source_embedding == Some(SourceEmbedding::Const)
+                            && *target == TargetLanguage::Rust
+                        {
+                            output_content.push_str(&source_const(local_path, &original));
                         }
-                        Ok(Err(err)) => {
-                            // framec returned an error
-                            let msg = format!(
-                                "Framec errored while generating {:?}: {:?}",
-                                target_output_path, err
-                            );
-                            if self.continue_on_error {
-                                eprintln!("{}", msg);
-                            } else {
-                                return Err(Error::msg(msg));
+                        let output_content = self.post_process_code(
+                            *target,
+                            input_path,
+                            &target_output_path,
+                            output_content,
+                        );
+                        let target_name = target.name();
+                        // aggregated code is written after all of the Frame files are compiled
+                        let aggregated =
+                            self.aggregate_output.is_some() && *target == TargetLanguage::Rust;
+                        if aggregated {
+                            if !state_files.is_empty() {
+                                bail!(
+                                    "the state modules of {:?} can't be aggregated, the code \
+                                     generated from it includes them from their files",
+                                    input_path
+                                );
                             }
+                            aggregate.insert_code(local_path, output_content)?;
+                        } else {
+                            self.write_generated(
+                                outputs,
+                                Some(input_path),
+                                target_name,
+                                &target_output_path,
+                                output_content,
+                            )?;
                         }
-                        Err(err) => {
-                            // framec panicked
-                            let msg = format!(
-                                "Framec panicked while generating {:?}: {:?}",
-                                target_output_path, err
+                        // and the files of its state modules, which it includes
+                        for (path, state_code) in state_files {
+                            let state_path = target_output_path.parent().unwrap().join(path);
+                            let state_code = self.post_process_code(
+                                *target,
+                                input_path,
+                                &state_path,
+                                state_code,
                             );
-                            if self.continue_on_error {
-                                eprintln!("{}", msg);
-                            } else {
-                                return Err(Error::msg(msg));
+                            self.write_generated(
+                                outputs,
+                                Some(input_path),
+                                target_name,
+                                &state_path,
+                                state_code,
+                            )?;
+                            generated_files.push(state_path);
+                        }
+                        // and its code map, if the backend generated one, whose lines are those of
+                        // the generated file
+                        if let Some(code_map) = code_map_opt.filter(|_| !aggregated) {
+                            let mut map_path = target_output_path.clone().into_os_string();
+                            map_path.push(".map.json");
+                            self.write_generated(
+                                outputs,
+                                Some(input_path),
+                                target_name,
+                                Path::new(&map_path),
+                                code_map.to_json(),
+                            )?;
+                            code_maps.push(PathBuf::from(map_path));
+                        }
+                        if self.module_index && *target == TargetLanguage::Rust {
+                            modules.insert(local_path, &target_local_path)?;
+                        }
+                        if self.include_stubs && *target == TargetLanguage::Rust {
+                            if let Err(err) = self.include_stub(
+                                &exe,
+                                frame_config,
+                                input_path,
+                                &source,
+                                &target_local_path,
+                                &target_output_path,
+                            ) {
+                                let error = BuildError {
+                                    input_path: input_path.to_path_buf(),
+                                    target: None,
+                                    failure: BuildFailure::IncludeStub(err),
+                                };
+                                let msg = error.to_string();
+                                self.fail(&mut failures, error, msg)?;
+                            }
+                        }
+                        if self.verify_actions && *target == TargetLanguage::Rust {
+                            let module = input_path.with_extension("rs");
+                            let failure = match self.missing_actions(
+                                &exe,
+                                frame_config,
+                                input_path,
+                                &source,
+                                &module,
+                            ) {
+                                Ok(actions) if actions.is_empty() => None,
+                                Ok(actions) => {
+                                    Some(BuildFailure::MissingActions { module, actions })
+                                }
+                                Err(err) => Some(BuildFailure::VerifyActions(err)),
+                            };
+                            if let Some(failure) = failure {
+                                let error = BuildError {
+                                    input_path: input_path.to_path_buf(),
+                                    target: None,
+                                    failure,
+                                };
+                                let msg = error.to_string();
+                                self.fail(&mut failures, error, msg)?;
                             }
                         }
+                        if !aggregated {
+                            generated_files.push(target_output_path);
+                        }
+                    }
+                    Ok(Err(err)) => {
+                        // framec returned an error
+                        let msg = format!(
+                            "Framec errored while generating {:?}: {:?}",
+                            target_output_path, err
+                        );
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: Some(*target),
+                            failure: BuildFailure::Errored(err),
+                        };
+                        self.fail(&mut failures, error, msg)?;
+                    }
+                    Err(err) => {
+                        // framec panicked
+                        let msg = format!(
+                            "Framec panicked while generating {:?}: {:?}",
+                            target_output_path, err
+                        );
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: Some(*target),
+                            failure: BuildFailure::Panicked(panic_message(err)),
+                        };
+                        self.fail(&mut failures, error, msg)?;
                     }
                 }
+                self.progress(BuildProgress::TargetFinished {
+                    input_path,
+                    target: target.name(),
+                    duration: target_start.elapsed(),
+                    succeeded,
+                });
             }
+
+            for custom_target in &self.custom_targets {
+                let target_start = Instant::now();
+                let output_path = self
+                    .output_dir
+                    .join(local_path.with_extension(&custom_target.extension));
+                let result = (custom_target.generator)(input_path, &source);
+                let succeeded = result.is_ok();
+                match result {
+                    Ok(code) => {
+                        let code =
+                            self.add_file_header(None, &input_path.display().to_string(), code);
+                        self.write_generated(
+                            outputs,
+                            Some(input_path),
+                            &custom_target.name,
+                            &output_path,
+                            code,
+                        )?;
+                        generated_files.push(output_path);
+                    }
+                    Err(err) => {
+                        let msg = format!(
+                            "Custom target {} errored while generating {:?}: {:#}",
+                            custom_target.name, output_path, err
+                        );
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: None,
+                            failure: BuildFailure::CustomTarget {
+                                name: custom_target.name.clone(),
+                                error: err,
+                            },
+                        };
+                        self.fail(&mut failures, error, msg)?;
+                    }
+                }
+                self.progress(BuildProgress::TargetFinished {
+                    input_path,
+                    target: &custom_target.name,
+                    duration: target_start.elapsed(),
+                    succeeded,
+                });
+            }
+
+            if let Some(format) = self.metadata_format {
+                let target_start = Instant::now();
+                let metadata_path = self
+                    .output_dir
+                    .join(local_path.with_extension(format.file_extension()));
+                let result = self.metadata(&exe, input_path, &source, format);
+                let succeeded = result.is_ok();
+                match result {
+                    Ok(metadata) => {
+                        self.write_generated(
+                            outputs,
+                            Some(input_path),
+                            "metadata",
+                            &metadata_path,
+                            metadata,
+                        )?;
+                        generated_files.push(metadata_path);
+                    }
+                    Err(err) => {
+                        let msg = format!(
+                            "Failed to generate the metadata of {:?}: {:#}",
+                            input_path, err
+                        );
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: None,
+                            failure: BuildFailure::Metadata(err),
+                        };
+                        self.fail(&mut failures, error, msg)?;
+                    }
+                }
+                self.progress(BuildProgress::TargetFinished {
+                    input_path,
+                    target: "metadata",
+                    duration: target_start.elapsed(),
+                    succeeded,
+                });
+            }
+
+            if self.source_embedding == Some(SourceEmbedding::Copy) {
+                let copy_path = self.output_dir.join(local_path);
+                self.write_generated(outputs, Some(input_path), "source", &copy_path, original)?;
+                generated_files.push(copy_path);
+            }
+
+            if let Some(diagram_dir) = &self.diagram_dir {
+                let target_start = Instant::now();
+                let result =
+                    self.render_diagrams(&exe, input_path, &source, &diagram_dir.join(local_path));
+                let succeeded = result.is_ok();
+                match result {
+                    Ok(diagram_files) => generated_files.extend(diagram_files),
+                    Err(err) => {
+                        let msg = format!(
+                            "Failed to render the diagrams of {:?}: {:#}",
+                            input_path, err
+                        );
+                        let error = BuildError {
+                            input_path: input_path.to_path_buf(),
+                            target: None,
+                            failure: BuildFailure::Diagram(err),
+                        };
+                        self.fail(&mut failures, error, msg)?;
+                    }
+                }
+                self.progress(BuildProgress::TargetFinished {
+                    input_path,
+                    target: "diagrams",
+                    duration: target_start.elapsed(),
+                    succeeded,
+                });
+            }
+        }
+
+        let input_dirs = self
+            .input_dirs
+            .iter()
+            .map(|input_dir| input_dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.module_index {
+            let index_path = self.output_dir.join(MODULE_INDEX);
+            let index = modules.render(
+                "// Generated by frame_build: a module for each Rust file generated from a Frame \
+                 file.\n",
+            );
+            let index = self.add_file_header(Some(TargetLanguage::Rust), &input_dirs, index);
+            let target_name = TargetLanguage::Rust.name();
+            self.write_generated(outputs, None, target_name, &index_path, index)?;
+            generated_files.push(index_path);
+        }
+        if let Some(aggregate_output) = &self.aggregate_output {
+            let aggregate_path = self.output_dir.join(aggregate_output);
+            let code = aggregate.render(
+                "// Generated by frame_build: a module with the Rust code generated from each \
+                 Frame file.\n",
+            );
+            let code = self.add_file_header(Some(TargetLanguage::Rust), &input_dirs, code);
+            let target_name = TargetLanguage::Rust.name();
+            self.write_generated(outputs, None, target_name, &aggregate_path, code)?;
+            generated_files.push(aggregate_path);
+        }
+
+        if self.prune_stale_outputs && outputs.artifacts.is_none() {
+            let generated = generated_files.iter().chain(&code_maps);
+            self.prune_outputs(generated, failures.any, outputs)?;
         }
 
+        self.progress(BuildProgress::Finished {
+            duration: start.elapsed(),
+        });
+        self.log(
+            Level::Info,
+            format_args!(
+                "Generated {} file(s) in {:?}",
+                generated_files.len(),
+                start.elapsed()
+            ),
+        );
+        if !failures.errors.is_empty() {
+            let errors = failures.errors;
+            return Err(Error::new(BuildErrors { errors }));
+        }
+        if !outputs.stale.is_empty() {
+            bail!(
+                "{} generated file(s) are not up to date, \
+                 build without verifying to update them:\n{}",
+                outputs.stale.len(),
+                outputs.stale.join("\n")
+            );
+        }
         Ok(generated_files)
     }
+
+    /// Tell the function set by [`FrameBuild::on_progress`], if any, the progress of the build.
+    fn progress(&self, event: BuildProgress) {
+        if let Some(callback) = &self.progress_callback {
+            callback(event);
+        }
+    }
+
+    /// Send a message to the logger set by [`FrameBuild::logger`], or to the `log` crate, unless
+    /// it is more verbose than [`FrameBuild::verbosity`].
+    fn log(&self, level: Level, message: fmt::Arguments) {
+        if level > self.verbosity {
+            return;
+        }
+        match &self.logger {
+            Some(logger) => logger(level, &message.to_string()),
+            None => log::log!(target: "frame_build", level, "{}", message),
+        }
+    }
+
+    /// Remove the files listed in the [`OUTPUT_MANIFEST`] of the previous build that are not among
+    /// the given outputs of this build, unless `keep` is set, and list the outputs in the
+    /// manifest, with the files that are kept.
+    fn prune_outputs<'a>(
+        &self,
+        generated: impl Iterator<Item = &'a PathBuf>,
+        keep: bool,
+        outputs: &mut Outputs,
+    ) -> Result<()> {
+        let manifest_path = self.output_dir.join(OUTPUT_MANIFEST);
+        // the paths relative to the output directory, separated by `/` on every platform
+        let mut listed: BTreeSet<String> = generated
+            .filter_map(|output| output.strip_prefix(&self.output_dir).ok())
+            .map(|output| {
+                output
+                    .iter()
+                    .map(|component| component.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect();
+        let previous = fs::read_to_string(&manifest_path).unwrap_or_default();
+        for output in previous.lines() {
+            // never remove files outside of the output directory, e.g. of an edited manifest
+            let outside = Path::new(output).is_absolute() || output.split('/').any(|c| c == "..");
+            if listed.contains(output) || output.is_empty() || outside {
+                continue;
+            }
+            let path = self.output_dir.join(output);
+            if keep {
+                listed.insert(output.to_string());
+            } else if self.codegen_mode == (CodegenMode::InTree { verify: true }) {
+                if path.exists() {
+                    let message = format!("{} is no longer generated", path.display());
+                    outputs.stale.push(message);
+                }
+            } else if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("failed to remove {:?}", path))?;
+                self.log(
+                    Level::Info,
+                    format_args!("{:?} is removed, it is no longer generated", path),
+                );
+                // and the directories it leaves empty, which fail to be removed otherwise
+                for dir in path.ancestors().skip(1) {
+                    if dir == self.output_dir || fs::remove_dir(dir).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let manifest: String = listed
+            .iter()
+            .map(|output| format!("{}\n", output))
+            .collect();
+        self.write_generated(outputs, None, "manifest", &manifest_path, manifest)
+    }
+
+    /// Write a file generated from a Frame file for a target as configured by
+    /// [`FrameBuild::codegen_mode`], describe how the committed file is stale when verifying it,
+    /// or keep it in memory for [`FrameBuild::run_collect`].
+    fn write_generated(
+        &self,
+        outputs: &mut Outputs,
+        input_path: Option<&Path>,
+        target: &str,
+        path: &Path,
+        contents: impl Into<String>,
+    ) -> Result<()> {
+        let contents = contents.into();
+        match input_path {
+            Some(input_path) => self.log(
+                Level::Debug,
+                format_args!("{:?} generates {:?} for {}", input_path, path, target),
+            ),
+            None => self.log(
+                Level::Debug,
+                format_args!("{:?} is generated for {}", path, target),
+            ),
+        }
+        if let Some(artifacts) = &mut outputs.artifacts {
+            artifacts.push(GeneratedArtifact {
+                input_path: input_path.map(Path::to_path_buf),
+                target: target.to_string(),
+                output_path: path.to_path_buf(),
+                contents,
+            });
+            return Ok(());
+        }
+        let contents = contents.as_bytes();
+        match self.codegen_mode {
+            CodegenMode::InTree { verify: true } => {
+                if self.cargo_directives {
+                    println!("cargo:rerun-if-changed={}", path.display());
+                }
+                match fs::read(path) {
+                    Ok(existing) if existing == contents => {}
+                    Ok(_) => outputs.stale.push(format!("{} differs", path.display())),
+                    Err(_) => outputs.stale.push(format!("{} is missing", path.display())),
+                }
+                return Ok(());
+            }
+            // leave unchanged files alone, so that they are not rebuilt or reindexed
+            CodegenMode::InTree { verify: false }
+                if fs::read(path).ok().as_deref() == Some(contents) =>
+            {
+                return Ok(());
+            }
+            _ => {}
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Handle the failure to build a Frame file: tell it to the function set by
+    /// [`FrameBuild::on_error`], if any, then collect it if [`FrameBuild::collect_errors`] is
+    /// set, log the message if [`FrameBuild::continue_on_error`] is set, and otherwise fail the
+    /// build with the message.
+    fn fail(&self, failures: &mut Failures, error: BuildError, msg: String) -> Result<()> {
+        failures.any = true;
+        if let Some(callback) = &self.error_callback {
+            callback(&CompileErrorContext {
+                input_path: &error.input_path,
+                target: error.target_name(),
+                diagnostics: error.diagnostics(),
+                error: &error,
+            });
+        }
+        if self.collect_errors {
+            failures.errors.push(error);
+        } else if self.continue_on_error {
+            self.log(Level::Warn, format_args!("{}", msg));
+            if self.cargo_directives {
+                // Cargo only shows the output of a build script that succeeds as warnings
+                println!("cargo:warning=Frame file skipped, {}", error);
+            }
+        } else {
+            return Err(Error::msg(msg));
+        }
+        Ok(())
+    }
+
+    /// Create the include stub of a Frame file, given the path of the Rust file generated from it
+    /// relative to the output directory, or check that the existing stub includes it.
+    fn include_stub(
+        &self,
+        exe: &Exe,
+        frame_config: &Option<PathBuf>,
+        input_path: &Path,
+        source: &str,
+        rust_local_path: &Path,
+        rust_output_path: &Path,
+    ) -> Result<()> {
+        let stub_path = input_path.with_extension("rs");
+        if stub_path == rust_output_path {
+            bail!(
+                "the include stub of {:?} would overwrite the Rust file generated from it",
+                input_path
+            );
+        }
+        // `include!` paths are separated by `/` on every platform
+        let include_path = rust_local_path
+            .iter()
+            .map(|component| component.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if stub_path.exists() {
+            let stub = fs::read_to_string(&stub_path)?;
+            if !stub.contains(&format!("{:?}", include_path)) {
+                bail!(
+                    "the include stub {:?} doesn't include {:?}, generated from {:?}",
+                    stub_path,
+                    include_path,
+                    input_path
+                );
+            }
+            return Ok(());
+        }
+
+        let stub = match &self.include_stub_template {
+            Some(template) => {
+                let model = exe
+                    .machine_model(input_path.to_str(), source.to_string())
+                    .map_err(|err| Error::msg(err.error))?;
+                format!(
+                    "include!(concat!(env!(\"OUT_DIR\"), \"/\", {:?}));\n{}",
+                    include_path,
+                    template(&model)
+                )
+            }
+            None => exe
+                .scaffold_wrapper(
+                    frame_config,
+                    input_path.to_str(),
+                    source.to_string(),
+                    &include_path,
+                )
+                .map_err(|err| Error::msg(err.error))?,
+        };
+        fs::write(&stub_path, stub)
+            .with_context(|| format!("failed to write the include stub {:?}", stub_path))
+    }
+
+    /// The methods of the actions of a Frame file, given its source, that are not defined by the
+    /// Rust module next to it.
+    fn missing_actions(
+        &self,
+        exe: &Exe,
+        frame_config: &Option<PathBuf>,
+        input_path: &Path,
+        source: &str,
+        module: &Path,
+    ) -> Result<Vec<String>> {
+        if self.cargo_directives {
+            println!("cargo:rerun-if-changed={}", module.display());
+        }
+        let methods = exe
+            .scaffold_action_methods(frame_config, input_path.to_str(), source.to_string())
+            .map_err(|err| Error::msg(err.error))?;
+        // a missing module implements none of them
+        let code = match fs::read_to_string(module) {
+            Ok(code) => code,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read the module {:?}", module))
+            }
+        };
+        Ok(methods
+            .into_iter()
+            .filter(|method| !defines_fn(&code, method))
+            .collect())
+    }
+
+    /// The path of the file generated for a target from a Frame file, relative to the output
+    /// directory, as named by the function set by [`FrameBuild::output_name_mapper`].
+    fn output_name(&self, local_path: &Path, target: TargetLanguage) -> PathBuf {
+        match &self.output_name_mapper {
+            Some(mapper) => mapper(local_path, target),
+            None => local_path.with_extension(target.file_extension()),
+        }
+    }
+
+    /// The Frame config file of a Frame file, as configured by [`FrameBuild::discover_configs`].
+    fn frame_config_of(&self, input_path: &Path) -> Option<PathBuf> {
+        if !self.discover_configs {
+            return self.frame_config.clone();
+        }
+        let sidecar = input_path.with_extension("config.yaml");
+        if sidecar.is_file() {
+            return Some(sidecar);
+        }
+        if let Some(input_dir) = self
+            .input_dirs
+            .iter()
+            .find(|input_dir| input_path.starts_with(input_dir))
+        {
+            for dir in input_path.ancestors().skip(1) {
+                if !dir.starts_with(input_dir) {
+                    break;
+                }
+                let config = dir.join("config.yaml");
+                if config.is_file() {
+                    return Some(config);
+                }
+            }
+        }
+        self.frame_config.clone()
+    }
+
+    /// Transform the code generated from a Frame file before it is written: format it if it is
+    /// Rust and [`FrameBuild::format_rust_output`] is set, add the file header, then apply the
+    /// function set by [`FrameBuild::post_process`], if any.
+    fn post_process_code(
+        &self,
+        target: TargetLanguage,
+        input_path: &Path,
+        path: &Path,
+        code: String,
+    ) -> String {
+        let code = if self.format_rust_output && target == TargetLanguage::Rust {
+            match rustfmt(&code) {
+                Ok(formatted) => formatted,
+                Err(err) => {
+                    self.log(
+                        Level::Warn,
+                        format_args!(
+                            "Failed to format {:?}, writing it unformatted: {:#}",
+                            path, err
+                        ),
+                    );
+                    code
+                }
+            }
+        } else {
+            code
+        };
+        let code = self.add_file_header(Some(target), &input_path.display().to_string(), code);
+        match &self.post_processor {
+            Some(post_processor) => post_processor(target, path, &code),
+            None => code,
+        }
+    }
+
+    /// Prepend the header of a target, set by [`FrameBuild::target_file_header`] or
+    /// [`FrameBuild::file_header`], to generated code, with the given value of `{input_path}`. The
+    /// header of custom targets is the one for all targets.
+    fn add_file_header(&self, target: Option<TargetLanguage>, input: &str, code: String) -> String {
+        let text = self
+            .target_file_headers
+            .iter()
+            .find(|(header_target, _)| Some(*header_target) == target)
+            .map(|(_, text)| text)
+            .or_else(|| self.file_header.as_ref());
+        let text = match text {
+            Some(text) => text,
+            None => return code,
+        };
+        let mut header = text
+            .replace("{input_path}", input)
+            .replace("{framec_version}", framec::frame_c::compiler::VERSION);
+        if header.contains("{timestamp}") {
+            header = header.replace("{timestamp}", &timestamp());
+        }
+        if !header.ends_with('\n') {
+            header.push('\n');
+        }
+        header + &code
+    }
+
+    /// Generate the metadata of a Frame file, given its source, in a format.
+    fn metadata(
+        &self,
+        exe: &Exe,
+        input_path: &Path,
+        source: &str,
+        format: MetadataFormat,
+    ) -> Result<String> {
+        let exe = AssertUnwindSafe(exe);
+        let model = match std::panic::catch_unwind(move || {
+            exe.machine_model(input_path.to_str(), source.to_string())
+        }) {
+            Ok(Ok(model)) => model,
+            Ok(Err(err)) => bail!("Framec errored: {:?}", err),
+            Err(err) => bail!("Framec panicked: {:?}", err),
+        };
+        match format {
+            MetadataFormat::Json => Ok(model.to_json()),
+        }
+    }
+
+    /// Render the diagram of a Frame file, given its source, in each of the configured formats,
+    /// next to the given path, and return the paths of the diagram files.
+    fn render_diagrams(
+        &self,
+        exe: &Exe,
+        input_path: &Path,
+        source: &str,
+        diagram_path: &Path,
+    ) -> Result<Vec<PathBuf>> {
+        let exe = AssertUnwindSafe(exe);
+        let model = match std::panic::catch_unwind(move || {
+            exe.machine_model(input_path.to_str(), source.to_string())
+        }) {
+            Ok(Ok(model)) => model,
+            Ok(Err(err)) => bail!("Framec errored: {:?}", err),
+            Err(err) => bail!("Framec panicked: {:?}", err),
+        };
+        let source = html::dot(&model);
+
+        let mut diagram_files = Vec::new();
+        for format in &self.diagram_formats {
+            let diagram = match format {
+                DiagramFormat::Dot => source.clone().into_bytes(),
+                _ => (self.diagram_renderer)(&source, *format)?,
+            };
+            let path = diagram_path.with_extension(format.file_extension());
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, diagram)?;
+            diagram_files.push(path);
+        }
+        Ok(diagram_files)
+    }
+
+    /// Find the Frame files that [`FrameBuild::run`] compiles, by traversing the input directories
+    /// as configured and applying the filter set by [`FrameBuild::include_only_if`]. The files are
+    /// returned in the order they are found, which is sorted unless [`FrameBuild::sort_by_path`]
+    /// is unset, and fail if their output paths collide, as for [`FrameBuild::run`].
+    pub fn input_files(&self) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .sources(&mut Vec::new())?
+            .into_iter()
+            .map(|(input_path, _)| input_path)
+            .collect())
+    }
+
+    /// Find the Frame files to compile, with their paths relative to the output directory, and
+    /// resolve the collisions of those paths. The Frame files that are found but not compiled are
+    /// added to `skipped`.
+    fn sources(&self, skipped: &mut Vec<PathBuf>) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut sources: Vec<(PathBuf, PathBuf)> = Vec::new();
+        for input_dir in &self.input_dirs {
+            let mut walk_dir = WalkDir::new(input_dir)
+                .max_depth(self.max_depth)
+                .min_depth(self.min_depth)
+                .follow_links(self.follow_links);
+            if self.sort_by_path {
+                walk_dir = walk_dir.sort_by_file_name();
+            }
+
+            // the directories reached by following links, by their canonical paths
+            let mut visited = HashSet::new();
+            let mut entries = walk_dir.into_iter();
+            while let Some(entry) = entries.next() {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => match (err.path(), err.loop_ancestor()) {
+                        (Some(path), Some(ancestor)) => bail!(
+                            "the symbolic link {:?} leads to its ancestor {:?}, \
+                             so the input directory would be traversed forever",
+                            path,
+                            ancestor
+                        ),
+                        _ => return Err(err.into()),
+                    },
+                };
+                let input_path = entry.path();
+                if self.follow_links && entry.file_type().is_dir() {
+                    let canonical = input_path
+                        .canonicalize()
+                        .with_context(|| format!("failed to resolve {:?}", input_path))?;
+                    if !visited.insert(canonical) {
+                        self.log(
+                            Level::Debug,
+                            format_args!("{:?} is skipped, it was already traversed", input_path),
+                        );
+                        entries.skip_current_dir();
+                        continue;
+                    }
+                }
+                let extension = input_path.extension().unwrap_or_default();
+                if !self
+                    .file_extensions
+                    .iter()
+                    .any(|file_extension| extension == file_extension.as_str())
+                {
+                    if !entry.file_type().is_dir() {
+                        self.log(
+                            Level::Trace,
+                            format_args!("{:?} is ignored, it isn't a Frame file", input_path),
+                        );
+                    }
+                    continue;
+                }
+                if !(&self.input_filter)(input_path) {
+                    self.log(
+                        Level::Debug,
+                        format_args!("{:?} is excluded by the filter", input_path),
+                    );
+                    skipped.push(input_path.to_path_buf());
+                    continue;
+                }
+                if let Some(max_file_size) = self.max_file_size {
+                    let size = entry.metadata()?.len();
+                    if size > max_file_size {
+                        bail!(
+                            "the Frame file {:?} is {} bytes, more than the max file size of {} \
+                             bytes",
+                            input_path,
+                            size,
+                            max_file_size
+                        );
+                    }
+                }
+                let mut local_path = input_path.strip_prefix(input_dir)?.to_path_buf();
+                if self.output_collision == OutputCollision::NestByInputDir {
+                    let dir_name = input_dir.file_name().with_context(|| {
+                        format!(
+                            "input directory {:?} has no name to nest its files in",
+                            input_dir
+                        )
+                    })?;
+                    local_path = Path::new(dir_name).join(local_path);
+                }
+                // files that differ only by their extension, e.g. `sm.frm` and `sm.frame`, also
+                // generate the same files
+                let collision = sources
+                    .iter()
+                    .find(|(_, other)| other.with_extension("") == local_path.with_extension(""));
+                match (collision, self.output_collision) {
+                    (None, _) => sources.push((input_path.to_path_buf(), local_path)),
+                    (Some((other_path, _)), OutputCollision::FirstWins) => {
+                        self.log(
+                            Level::Debug,
+                            format_args!(
+                                "{:?} is skipped, {:?} is generated at {:?} instead",
+                                input_path, other_path, local_path
+                            ),
+                        );
+                        skipped.push(input_path.to_path_buf());
+                    }
+                    (Some((other_path, _)), _) => bail!(
+                        "{:?} and {:?} would both be generated at {:?} in the output directory",
+                        other_path,
+                        input_path,
+                        local_path
+                    ),
+                }
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Find the files included by each Frame file to compile, and order the Frame files so that
+    /// those included by other Frame files are compiled first. Otherwise the files keep their
+    /// order. The includes are read from the Frame files before they are pre-processed. The
+    /// includes of a Frame file that fails to read or expand, e.g. because of an include cycle,
+    /// are ignored here: the error is reported when the file is compiled.
+    fn include_order(
+        &self,
+        exe: &Exe,
+        sources: Vec<(PathBuf, PathBuf)>,
+    ) -> Vec<(PathBuf, PathBuf, Vec<PathBuf>)> {
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let includes: Vec<Vec<PathBuf>> = sources
+            .iter()
+            .map(|(input_path, _)| {
+                fs::read_to_string(input_path)
+                    .ok()
+                    .and_then(|source| exe.include_dependencies(input_path.to_str(), source).ok())
+                    .unwrap_or_default()
+            })
+            .collect();
+        let source_paths: Vec<PathBuf> = sources
+            .iter()
+            .map(|(input_path, _)| canonical(input_path))
+            .collect();
+        // the Frame files to compile included by each one, directly or not
+        let dependencies: Vec<Vec<usize>> = includes
+            .iter()
+            .map(|includes| {
+                let includes: Vec<PathBuf> =
+                    includes.iter().map(|include| canonical(include)).collect();
+                (0..source_paths.len())
+                    .filter(|index| includes.contains(&source_paths[*index]))
+                    .collect()
+            })
+            .collect();
+
+        // framec rejects include cycles, so there are none among the includes that were expanded
+        fn visit(index: usize, dependencies: &[Vec<usize>], order: &mut Vec<usize>) {
+            if order.contains(&index) {
+                return;
+            }
+            for dependency in &dependencies[index] {
+                visit(*dependency, dependencies, order);
+            }
+            order.push(index);
+        }
+        let mut order = Vec::new();
+        for index in 0..sources.len() {
+            visit(index, &dependencies, &mut order);
+        }
+
+        let mut sources: Vec<Option<(PathBuf, PathBuf)>> = sources.into_iter().map(Some).collect();
+        let mut includes: Vec<Option<Vec<PathBuf>>> = includes.into_iter().map(Some).collect();
+        order
+            .into_iter()
+            .map(|index| {
+                let (input_path, local_path) = sources[index].take().unwrap();
+                (input_path, local_path, includes[index].take().unwrap())
+            })
+            .collect()
+    }
+}
+
+/// The modules declared by the module index or the aggregated output, named after the directories
+/// and files they are generated from.
+#[derive(Default)]
+struct ModuleTree {
+    /// The path of the generated file included in the module, relative to the output directory.
+    file: Option<String>,
+    /// The path of the Frame file relative to the input directory and the Rust code generated from
+    /// it, in the module of the aggregated output.
+    code: Option<(PathBuf, String)>,
+    children: BTreeMap<String, ModuleTree>,
+}
+
+impl ModuleTree {
+    /// The module of a Frame file, given its path relative to the input directory.
+    fn module(&mut self, local_path: &Path) -> &mut ModuleTree {
+        let mut module = self;
+        for component in local_path.with_extension("").iter() {
+            let name = module_name(&component.to_string_lossy());
+            module = module.children.entry(name).or_default();
+        }
+        module
+    }
+
+    /// Add the module of the Rust file generated from a Frame file, given the paths of the Frame
+    /// file relative to the input directory and of the Rust file relative to the output directory.
+    fn insert(&mut self, local_path: &Path, rust_path: &Path) -> Result<()> {
+        let module = self.module(local_path);
+        let file = rust_path
+            .iter()
+            .map(|component| component.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if let Some(other_file) = &module.file {
+            bail!(
+                "{} and {} are generated into the same module of the module index",
+                other_file,
+                file
+            );
+        }
+        module.file = Some(file);
+        Ok(())
+    }
+
+    /// Add the module of the Rust code generated from a Frame file to the aggregated output, given
+    /// the path of the Frame file relative to the input directory.
+    fn insert_code(&mut self, local_path: &Path, code: String) -> Result<()> {
+        let module = self.module(local_path);
+        if let Some((other_path, _)) = &module.code {
+            bail!(
+                "{:?} and {:?} are generated into the same module of the aggregated output",
+                other_path,
+                local_path
+            );
+        }
+        module.code = Some((local_path.to_path_buf(), code));
+        Ok(())
+    }
+
+    /// The Rust source of the module index or the aggregated output, after a header comment.
+    fn render(&self, header: &str) -> String {
+        let mut code = String::from(header);
+        self.render_children(&mut code, 0);
+        code
+    }
+
+    fn render_children(&self, code: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for (name, module) in &self.children {
+            code.push_str(&format!("{}pub mod {} {{\n", indent, name));
+            if let Some(file) = &module.file {
+                // relative to the index, which is in the output directory
+                code.push_str(&format!("{}    include!({:?});\n", indent, file));
+            }
+            // not indented, which would change the multi-line strings of the code
+            if let Some((_, module_code)) = &module.code {
+                code.push_str(module_code);
+                if !module_code.ends_with('\n') {
+                    code.push('\n');
+                }
+            }
+            module.render_children(code, depth + 1);
+            code.push_str(&format!("{}}}\n", indent));
+        }
+    }
+}
+
+/// Whether Rust code defines a function with the given name, e.g. `fn log(&self)` or
+/// `pub fn log<T>(`, outside of line comments.
+fn defines_fn(code: &str, name: &str) -> bool {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let code = code
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+    code.match_indices(name).any(|(start, _)| {
+        let before = &code[..start];
+        let after = code[start + name.len()..].trim_start();
+        let keyword = before.trim_end();
+        keyword.len() < before.len()
+            && keyword.ends_with("fn")
+            && !keyword[..keyword.len() - 2].ends_with(is_ident_char)
+            && (after.starts_with('(') || after.starts_with('<'))
+    })
+}
+
+/// The name of the module of a file or directory, e.g. `traffic_light` for `traffic-light`.
+fn module_name(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    match ident.as_str() {
+        // keywords that can't be raw identifiers
+        "crate" | "self" | "super" | "Self" => ident.push('_'),
+        "abstract" | "as" | "async" | "await" | "become" | "box" | "break" | "const"
+        | "continue" | "do" | "dyn" | "else" | "enum" | "extern" | "false" | "final" | "fn"
+        | "for" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod" | "move"
+        | "mut" | "override" | "priv" | "pub" | "ref" | "return" | "static" | "struct"
+        | "trait" | "true" | "try" | "type" | "typeof" | "unsafe" | "unsized" | "use"
+        | "virtual" | "where" | "while" | "yield" => ident.insert_str(0, "r#"),
+        _ => {}
+    }
+    ident
 }
@@ -0,0 +1,1252 @@
+//! Tests of the build process on Frame files in a temporary directory.
+
+use frame_build::{
+    BuildError, BuildErrors, BuildFailure, BuildProgress, CodegenMode, FrameBuild,
+    GeneratedArtifact, LevelFilter, MachineModel, MetadataFormat, OutputCollision, Severity,
+    SourceEmbedding, TargetLanguage, MODULE_INDEX, OUTPUT_MANIFEST,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+/// Create an input directory with Frame files of the given paths and machines, and an empty output
+/// directory, in a new temporary directory.
+fn input_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("frame_build_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (file, machine) in files {
+        let path = dir.join("src").join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, SPEC.replace("Lamp", machine)).unwrap();
+    }
+    fs::create_dir_all(dir.join("out")).unwrap();
+    dir
+}
+
+fn build(dir: &Path) -> FrameBuild {
+    FrameBuild::new()
+        .input_dir(&dir.join("src"))
+        .output_dir(&dir.join("out"))
+}
+
+/// Test that the module index declares a module for each generated file, nested as the Frame
+/// files are, and that it compiles.
+#[test]
+fn module_index() {
+    let dir = input_dir(
+        "module_index",
+        &[
+            ("lamp.frm", "Lamp"),
+            ("rooms/hall-way.frm", "Hall"),
+            ("rooms/hall-way/type.frm", "Type"),
+        ],
+    );
+    let generated = build(&dir).generate_module_index().run().unwrap();
+    let index_path = dir.join("out").join(MODULE_INDEX);
+    assert_eq!(generated.last(), Some(&index_path));
+    assert_eq!(
+        fs::read_to_string(&index_path).unwrap(),
+        "// Generated by frame_build: a module for each Rust file generated from a Frame file.
+pub mod lamp {
+    include!(\"lamp.rs\");
+}
+pub mod rooms {
+    pub mod hall_way {
+        include!(\"rooms/hall-way.rs\");
+        pub mod r#type {
+            include!(\"rooms/hall-way/type.rs\");
+        }
+    }
+}
+"
+    );
+
+    let lib_path = dir.join("lib.rs");
+    fs::write(
+        &lib_path,
+        "include!(\"out/frame_generated.rs\");\n\
+         pub fn machines() -> (lamp::Lamp, rooms::hall_way::Hall, rooms::hall_way::r#type::Type) {\n\
+         \x20   (lamp::Lamp::new(), rooms::hall_way::Hall::new(), rooms::hall_way::r#type::Type::new())\n\
+         }\n",
+    )
+    .unwrap();
+    let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .args([
+            "--crate-type",
+            "lib",
+            "--emit",
+            "metadata",
+            "--edition",
+            "2018",
+        ])
+        .arg("--out-dir")
+        .arg(&dir)
+        .arg(&lib_path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Test that files that map to the same module fail the build.
+#[test]
+fn module_index_conflict() {
+    let dir = input_dir(
+        "module_index_conflict",
+        &[("a-b.frm", "Lamp"), ("a_b.frm", "Lamp")],
+    );
+    let err = build(&dir).generate_module_index().run().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("are generated into the same module of the module index"));
+}
+
+/// Test that the generated code is transformed before it is written.
+#[test]
+fn post_process() {
+    let dir = input_dir("post_process", &[("lamp.frm", "Lamp")]);
+    let generated = build(&dir)
+        .add_target(TargetLanguage::Smcat)
+        .post_process(|target, path, code| {
+            if target != TargetLanguage::Rust {
+                return code.to_string();
+            }
+            let mut header = format!("// {}\n", path.file_name().unwrap().to_string_lossy());
+            for line in code.lines().filter(|line| !line.contains("#[allow")) {
+                header.push_str(line);
+                header.push('\n');
+            }
+            header
+        })
+        .run()
+        .unwrap();
+    assert_eq!(generated.len(), 2);
+
+    let rust = fs::read_to_string(dir.join("out/lamp.rs")).unwrap();
+    assert!(rust.starts_with("// lamp.rs\n// emitted from framec_v0.8.0\n"));
+    assert!(!rust.contains("#[allow"));
+    let smcat = fs::read_to_string(dir.join("out/lamp.smcat")).unwrap();
+    assert!(smcat.starts_with("initial,"));
+}
+
+/// Test that the generated Rust code is formatted with rustfmt, and written as generated if
+/// rustfmt fails.
+#[test]
+fn format_rust_output() {
+    let dir = input_dir("format_rust_output", &[("lamp.frm", "Lamp")]);
+    build(&dir).run().unwrap();
+    let generated = fs::read_to_string(dir.join("out/lamp.rs")).unwrap();
+
+    build(&dir).format_rust_output().run().unwrap();
+    let formatted = fs::read_to_string(dir.join("out/lamp.rs")).unwrap();
+    assert_ne!(formatted, generated);
+    let output = Command::new("rustfmt")
+        .args(["--check", "--edition", "2018"])
+        .arg(dir.join("out/lamp.rs"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    std::env::set_var("RUSTFMT", dir.join("missing-rustfmt"));
+    build(&dir).format_rust_output().run().unwrap();
+    std::env::remove_var("RUSTFMT");
+    assert_eq!(
+        fs::read_to_string(dir.join("out/lamp.rs")).unwrap(),
+        generated
+    );
+}
+
+/// Test that the Frame files of several input directories are compiled, and that their output
+/// paths are resolved by the collision strategy.
+#[test]
+fn input_dirs() {
+    let dir = input_dir(
+        "input_dirs",
+        &[("lamp.frm", "Lamp"), ("net/socket.frm", "Socket")],
+    );
+    fs::create_dir_all(dir.join("protocol/net")).unwrap();
+    fs::write(
+        dir.join("protocol/net/socket.frm"),
+        SPEC.replace("Lamp", "Peer"),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("protocol/handshake.frm"),
+        SPEC.replace("Lamp", "Handshake"),
+    )
+    .unwrap();
+    let build = || build(&dir).add_input_dir(&dir.join("protocol"));
+
+    let err = build().run().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("would both be generated at \"net/socket.frm\" in the output directory"));
+
+    let generated = build()
+        .on_output_collision(OutputCollision::FirstWins)
+        .run()
+        .unwrap();
+    assert_eq!(generated.len(), 3);
+    let socket = fs::read_to_string(dir.join("out/net/socket.rs")).unwrap();
+    assert!(socket.contains("struct Socket"));
+    assert!(dir.join("out/handshake.rs").exists());
+
+    let generated = build()
+        .on_output_collision(OutputCollision::NestByInputDir)
+        .run()
+        .unwrap();
+    let out = dir.join("out");
+    let mut generated: Vec<_> = generated
+        .iter()
+        .map(|path| path.strip_prefix(&out).unwrap().to_path_buf())
+        .collect();
+    generated.sort();
+    assert_eq!(
+        generated,
+        [
+            "protocol/handshake.rs",
+            "protocol/net/socket.rs",
+            "src/lamp.rs",
+            "src/net/socket.rs"
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>()
+    );
+}
+
+/// Test that all of the Frame files are built before the failures are reported together.
+#[test]
+fn collect_errors() {
+    let dir = input_dir(
+        "collect_errors",
+        &[("a.frm", "A"), ("b.frm", "B"), ("c.frm", "C")],
+    );
+    fs::write(dir.join("src/a.frm"), "#A\n    -machine-\n    S\n##\n").unwrap();
+    fs::write(dir.join("src/c.frm"), "#C\n    -interface-\n    e [\n##\n").unwrap();
+
+    let err = build(&dir).collect_errors().run().unwrap_err();
+    assert!(dir.join("out/b.rs").exists());
+    let errors = err.downcast_ref::<BuildErrors>().unwrap();
+    let mut failed: Vec<_> = errors
+        .errors
+        .iter()
+        .map(|error| (error.input_path.file_name().unwrap(), error.target))
+        .collect();
+    failed.sort();
+    assert_eq!(
+        failed,
+        [
+            ("a.frm".as_ref(), Some(TargetLanguage::Rust)),
+            ("c.frm".as_ref(), Some(TargetLanguage::Rust))
+        ]
+    );
+    assert!(errors
+        .errors
+        .iter()
+        .all(|error| matches!(error.failure, BuildFailure::Errored(_))));
+    let c_error = errors
+        .errors
+        .iter()
+        .find(|error| error.input_path.ends_with("c.frm"))
+        .unwrap();
+    let summary = c_error.to_string();
+    assert!(summary.starts_with("failed to generate rust from "));
+    assert!(summary.ends_with("c.frm: 4:1: error[parse-error]: Expected parameter name."));
+    assert!(!summary.contains('\n'));
+    let msg = err.to_string();
+    assert!(msg.starts_with("2 Frame files failed to build:\n"));
+    assert!(msg.contains("a.frm (rust):\n    "));
+}
+
+/// Test that each Frame file is compiled with its own config file, the nearest config file of its
+/// directories, or the global config file.
+#[test]
+fn discover_configs() {
+    let dir = input_dir(
+        "discover_configs",
+        &[
+            ("a.frm", "A"),
+            ("b.frm", "B"),
+            ("sub/c.frm", "C"),
+            ("sub/deeper/d.frm", "D"),
+        ],
+    );
+    let runtime = "codegen:\n  rust:\n    features:\n      runtime_support: true\n";
+    fs::write(dir.join("src/a.config.yaml"), runtime).unwrap();
+    fs::write(dir.join("src/sub/config.yaml"), runtime).unwrap();
+    // outside of the input directory
+    fs::write(dir.join("config.yaml"), runtime).unwrap();
+
+    build(&dir).discover_configs().run().unwrap();
+    let uses_runtime = |file: &str| {
+        fs::read_to_string(dir.join("out").join(file))
+            .unwrap()
+            .contains("frame_runtime")
+    };
+    assert!(uses_runtime("a.rs"));
+    assert!(!uses_runtime("b.rs"));
+    assert!(uses_runtime("sub/c.rs"));
+    assert!(uses_runtime("sub/deeper/d.rs"));
+}
+
+/// Test that the generated files are named by the output name mapper.
+#[test]
+fn output_name_mapper() {
+    let dir = input_dir("output_name_mapper", &[("a/lamp.frm", "Lamp")]);
+    let generated = build(&dir)
+        .add_target(TargetLanguage::Smcat)
+        .generate_module_index()
+        .output_name_mapper(|path, target| match target {
+            TargetLanguage::Rust => {
+                let stem = path.file_stem().unwrap().to_string_lossy();
+                path.with_file_name(format!("{}_generated.rs", stem))
+            }
+            _ => Path::new("diagrams")
+                .join(path)
+                .with_extension(target.file_extension()),
+        })
+        .run()
+        .unwrap();
+    let out = dir.join("out");
+    assert_eq!(
+        generated,
+        [
+            out.join("a/lamp_generated.rs"),
+            out.join("diagrams/a/lamp.smcat"),
+            out.join(MODULE_INDEX)
+        ]
+    );
+    let index = fs::read_to_string(out.join(MODULE_INDEX)).unwrap();
+    assert!(index.contains("    pub mod lamp {\n        include!(\"a/lamp_generated.rs\");\n"));
+}
+
+/// Test that Frame files are found by the configured extensions.
+#[test]
+fn file_extensions() {
+    let dir = input_dir(
+        "file_extensions",
+        &[("a.frm", "A"), ("b.frame", "B"), ("c.txt", "C")],
+    );
+    let input_files = |build: FrameBuild| {
+        let mut files: Vec<_> = build
+            .input_files()
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    };
+    assert_eq!(input_files(build(&dir)), ["a.frm"]);
+    let extensions = ["frm", "frame"];
+    assert_eq!(
+        input_files(build(&dir).file_extensions(&extensions)),
+        ["a.frm", "b.frame"]
+    );
+
+    build(&dir).file_extensions(&extensions).run().unwrap();
+    assert!(dir.join("out/b.rs").exists());
+
+    fs::write(dir.join("src/a.frame"), SPEC).unwrap();
+    let err = build(&dir).file_extensions(&extensions).run().unwrap_err();
+    assert!(err.to_string().contains("would both be generated at"));
+}
+
+/// Test that the configuration is overridden by environment variables.
+#[test]
+fn env_overrides() {
+    let dir = input_dir("env_overrides", &[("lamp.frm", "Lamp")]);
+    fs::create_dir_all(dir.join("protocol")).unwrap();
+    fs::write(dir.join("protocol/peer.frm"), SPEC.replace("Lamp", "Peer")).unwrap();
+    fs::write(dir.join("protocol/broken.frm"), "#Broken\n    S\n##\n").unwrap();
+
+    std::env::set_var("FRAME_BUILD_TARGETS", "smcat, rust");
+    std::env::set_var("FRAME_BUILD_INPUT_DIR", dir.join("protocol"));
+    std::env::set_var("FRAME_BUILD_CONTINUE_ON_ERROR", "true");
+    let generated = build(&dir).apply_env_overrides().unwrap().run();
+    std::env::set_var("FRAME_BUILD_TARGETS", "rust,cobol");
+    let invalid_target = build(&dir).apply_env_overrides().err().unwrap();
+    std::env::remove_var("FRAME_BUILD_TARGETS");
+    std::env::remove_var("FRAME_BUILD_INPUT_DIR");
+    std::env::remove_var("FRAME_BUILD_CONTINUE_ON_ERROR");
+
+    let out = dir.join("out");
+    assert_eq!(
+        generated.unwrap(),
+        [out.join("peer.smcat"), out.join("peer.rs")]
+    );
+    assert_eq!(
+        format!("{:#}", invalid_target),
+        "invalid FRAME_BUILD_TARGETS: Unrecognized target language cobol"
+    );
+}
+
+/// Test that the input directories are traversed in the order of the paths.
+#[test]
+fn sort_by_path() {
+    let files = [
+        ("z.frm", "Z"),
+        ("b/y.frm", "Y"),
+        ("a.frm", "A"),
+        ("b/a.frm", "BA"),
+        ("b.frm", "B"),
+    ];
+    let dir = input_dir("sort_by_path", &files);
+    let input_dir = dir.join("src");
+    let input_files: Vec<_> = build(&dir)
+        .input_files()
+        .unwrap()
+        .iter()
+        .map(|path| path.strip_prefix(&input_dir).unwrap().to_path_buf())
+        .collect();
+    assert_eq!(
+        input_files,
+        ["a.frm", "b/a.frm", "b/y.frm", "b.frm", "z.frm"]
+            .iter()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        build(&dir).sort_by_path(false).input_files().unwrap().len(),
+        files.len()
+    );
+}
+
+/// Test that include stubs are created next to the Frame files, and that existing ones are checked
+/// but not overwritten.
+#[test]
+fn include_stubs() {
+    let dir = input_dir("include_stubs", &[("lamp.frm", "Lamp")]);
+    fs::create_dir_all(dir.join("src/a")).unwrap();
+    fs::write(
+        dir.join("src/a/switch.frm"),
+        SPEC.replace("Lamp", "Switch")
+            .replace("-> $Off ^", "-> $Off ^\n    -actions-\n    click [n:i32]"),
+    )
+    .unwrap();
+
+    build(&dir).generate_include_stubs().run().unwrap();
+    let stub = fs::read_to_string(dir.join("src/a/switch.rs")).unwrap();
+    assert!(stub.starts_with(
+        "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"a/switch.rs\"));\n\n\
+         #[cfg(not(test))]\nimpl Switch {\n    fn click(&self, _n: i32) {}\n}\n"
+    ));
+    assert!(stub.contains("mod tests {"));
+    assert!(dir.join("src/lamp.rs").exists());
+
+    let lamp_stub = "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"lamp.rs\"));\n// mine\n";
+    fs::write(dir.join("src/lamp.rs"), lamp_stub).unwrap();
+    fs::remove_file(dir.join("src/a/switch.rs")).unwrap();
+    build(&dir)
+        .generate_include_stubs()
+        .include_stub_template(|model| format!("// {} actions\n", model.actions.len()))
+        .run()
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.join("src/lamp.rs")).unwrap(),
+        lamp_stub
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("src/a/switch.rs")).unwrap(),
+        "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"a/switch.rs\"));\n// 1 actions\n"
+    );
+
+    fs::write(dir.join("src/lamp.rs"), "include!(\"old/lamp.rs\");\n").unwrap();
+    let err = build(&dir).generate_include_stubs().run().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("doesn't include \"lamp.rs\", generated from"));
+
+    // a stub that fails is collected like the other failures, and the other files are built
+    fs::remove_dir_all(dir.join("out")).unwrap();
+    let err = build(&dir)
+        .generate_include_stubs()
+        .collect_errors()
+        .run()
+        .unwrap_err();
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].input_path.ends_with("src/lamp.frm"));
+    assert!(matches!(errors[0].failure, BuildFailure::IncludeStub(_)));
+    assert_eq!(errors[0].target_name(), "include stub");
+    assert!(dir.join("out/a/switch.rs").exists());
+}
+
+/// Test that in-tree codegen only writes changed files, and that verifying it reports the stale
+/// files without writing them.
+#[test]
+fn in_tree_codegen() {
+    let dir = input_dir("in_tree_codegen", &[("lamp.frm", "Lamp"), ("a/b.frm", "B")]);
+    let build = |verify| build(&dir).codegen_mode(CodegenMode::InTree { verify });
+    let lamp = dir.join("out/lamp.rs");
+
+    let err = build(true).run().unwrap_err().to_string();
+    assert!(err.starts_with("2 generated file(s) are not up to date"));
+    assert!(err.contains("lamp.rs is missing"));
+    assert!(!lamp.exists());
+
+    build(false).run().unwrap();
+    let modified = fs::metadata(&lamp).unwrap().modified().unwrap();
+    build(false).run().unwrap();
+    assert_eq!(fs::metadata(&lamp).unwrap().modified().unwrap(), modified);
+    build(true).run().unwrap();
+
+    fs::write(&lamp, "// edited\n").unwrap();
+    let err = build(true).run().unwrap_err().to_string();
+    assert!(err.starts_with("1 generated file(s) are not up to date"));
+    assert!(err.contains("lamp.rs differs"));
+    assert_eq!(fs::read_to_string(&lamp).unwrap(), "// edited\n");
+}
+
+/// Test that custom targets are generated next to the built-in ones, and that their errors are
+/// handled like those of Framec.
+#[test]
+fn custom_targets() {
+    let dir = input_dir("custom_targets", &[("lamp.frm", "Lamp"), ("a/b.frm", "B")]);
+    let build = || {
+        build(&dir).add_custom_target("lines", "lines", |path, source| {
+            if path.ends_with("b.frm") {
+                anyhow::bail!("no lines for B");
+            }
+            Ok(format!("{}\n", source.lines().count()))
+        })
+    };
+
+    let err = build().collect_errors().run().unwrap_err();
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].input_path.ends_with("a/b.frm"));
+    assert!(matches!(
+        &errors[0].failure,
+        BuildFailure::CustomTarget { name, .. } if name == "lines"
+    ));
+    assert_eq!(
+        errors[0].to_string(),
+        format!(
+            "failed to generate lines from {}: no lines for B",
+            errors[0].input_path.display()
+        )
+    );
+
+    let generated = build().continue_on_error().run().unwrap();
+    assert_eq!(generated.len(), 3);
+    assert_eq!(
+        fs::read_to_string(dir.join("out/lamp.lines")).unwrap(),
+        format!("{}\n", SPEC.lines().count())
+    );
+    assert!(dir.join("out/a/b.rs").exists());
+    assert!(!dir.join("out/a/b.lines").exists());
+}
+
+/// Test that the progress of the build is told to the callback, in order.
+#[test]
+fn on_progress() {
+    let dir = input_dir(
+        "on_progress",
+        &[("draft.frm", "Draft"), ("lamp.frm", "Lamp")],
+    );
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let recorded = events.clone();
+    build(&dir)
+        .add_target(TargetLanguage::Smcat)
+        .include_only_if(|path| !path.ends_with("draft.frm"))
+        .on_progress(move |event| {
+            let event = match event {
+                BuildProgress::FileStarted { input_path } => {
+                    format!(
+                        "started {}",
+                        input_path.file_name().unwrap().to_string_lossy()
+                    )
+                }
+                BuildProgress::TargetFinished {
+                    target, succeeded, ..
+                } => format!("finished {} {}", target, succeeded),
+                BuildProgress::FileSkipped { input_path } => {
+                    format!(
+                        "skipped {}",
+                        input_path.file_name().unwrap().to_string_lossy()
+                    )
+                }
+                BuildProgress::Finished { .. } => String::from("finished"),
+            };
+            recorded.borrow_mut().push(event);
+        })
+        .run()
+        .unwrap();
+    assert_eq!(
+        *events.borrow(),
+        [
+            "skipped draft.frm",
+            "started lamp.frm",
+            "finished rust true",
+            "finished smcat true",
+            "finished"
+        ]
+    );
+}
+
+/// Test that Frame files are transformed before they are compiled, and that the errors of the
+/// transformation are handled like those of Framec.
+#[test]
+fn pre_process() {
+    let dir = input_dir("pre_process", &[]);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/lamp.frm"), SPEC.replace("toggle", "@EVENT")).unwrap();
+    fs::write(dir.join("src/broken.frm"), "@UNKNOWN\n").unwrap();
+
+    let err = build(&dir)
+        .add_custom_target("source", "frm.txt", |_, source| Ok(source.to_string()))
+        .collect_errors()
+        .pre_process(|path, source| {
+            if source.contains("@UNKNOWN") {
+                anyhow::bail!("unknown macro in {}", path.display());
+            }
+            Ok(source.replace("@EVENT", "toggle"))
+        })
+        .run()
+        .unwrap_err();
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].input_path.ends_with("broken.frm"));
+    assert!(matches!(errors[0].failure, BuildFailure::PreProcess(_)));
+
+    let lamp = fs::read_to_string(dir.join("out/lamp.rs")).unwrap();
+    assert!(lamp.contains("pub fn toggle(&mut self)"));
+    assert_eq!(
+        fs::read_to_string(dir.join("out/lamp.frm.txt")).unwrap(),
+        SPEC
+    );
+}
+
+/// Test that a single Frame file is compiled to a string, and that its errors can be inspected.
+#[test]
+fn compile_file() {
+    let dir = input_dir("compile_file", &[("lamp.frm", "Lamp")]);
+    fs::write(dir.join("src/broken.frm"), "#A\n    -machine-\n    S\n##\n").unwrap();
+
+    let code =
+        frame_build::compile_file(&dir.join("src/lamp.frm"), TargetLanguage::Rust, None).unwrap();
+    assert!(code.contains("struct Lamp"));
+    assert!(!dir.join("out/lamp.rs").exists());
+
+    let err = frame_build::compile_file(&dir.join("src/broken.frm"), TargetLanguage::Rust, None)
+        .unwrap_err();
+    let error = err.downcast_ref::<BuildError>().unwrap();
+    assert!(error.input_path.ends_with("broken.frm"));
+    assert_eq!(error.target, Some(TargetLanguage::Rust));
+    assert!(matches!(error.failure, BuildFailure::Errored(_)));
+}
+
+/// Test that the actions of a machine must be implemented by the Rust module next to its spec.
+#[test]
+fn verify_actions_implemented() {
+    let dir = input_dir("verify_actions_implemented", &[("lamp.frm", "Lamp")]);
+    fs::write(
+        dir.join("src/switch.frm"),
+        SPEC.replace("Lamp", "Switch").replace(
+            "-> $Off ^",
+            "-> $Off ^\n    -actions-\n    log [msg:String]\n    beep",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("src/switch.rs"),
+        "impl Switch {\n    // fn beep(&self) {}\n    fn log(&self, _msg: String) {}\n}\n",
+    )
+    .unwrap();
+
+    let err = build(&dir)
+        .verify_actions_implemented()
+        .collect_errors()
+        .run()
+        .unwrap_err();
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    match &errors[0].failure {
+        BuildFailure::MissingActions { module, actions } => {
+            assert!(module.ends_with("src/switch.rs"));
+            assert_eq!(actions, &["beep"]);
+        }
+        failure => panic!("unexpected failure {:?}", failure),
+    }
+    assert!(err.to_string().contains("no `fn beep` in"));
+
+    fs::write(
+        dir.join("src/switch.rs"),
+        "impl Switch {\n    fn beep(&self) {}\n    fn log(&self, _msg: String) {}\n}\n",
+    )
+    .unwrap();
+    build(&dir).verify_actions_implemented().run().unwrap();
+
+    // a module that can't be read is collected like the other failures, and the other files are
+    // built
+    fs::write(dir.join("src/switch.rs"), b"impl Switch {\xff}\n").unwrap();
+    fs::remove_dir_all(dir.join("out")).unwrap();
+    let err = build(&dir)
+        .verify_actions_implemented()
+        .collect_errors()
+        .run()
+        .unwrap_err();
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].input_path.ends_with("src/switch.frm"));
+    assert!(matches!(errors[0].failure, BuildFailure::VerifyActions(_)));
+    assert_eq!(errors[0].target_name(), "actions");
+    assert!(dir.join("out/lamp.rs").exists());
+}
+
+/// Test that the file headers are prepended to the generated files, with their variables
+/// replaced.
+#[test]
+fn file_header() {
+    let dir = input_dir("file_header", &[("lamp.frm", "Lamp")]);
+    std::env::set_var("SOURCE_DATE_EPOCH", "1706702400");
+    build(&dir)
+        .add_target(TargetLanguage::Smcat)
+        .add_custom_target("source", "txt", |_, source| Ok(source.to_string()))
+        .generate_module_index()
+        .file_header("// DO NOT EDIT: generated from {input_path} by framec {framec_version}")
+        .target_file_header(TargetLanguage::Smcat, "# {timestamp}\n")
+        .run()
+        .unwrap();
+
+    let lamp = fs::read_to_string(dir.join("out/lamp.rs")).unwrap();
+    let header = format!(
+        "// DO NOT EDIT: generated from {} by framec {}\n",
+        dir.join("src/lamp.frm").display(),
+        framec::frame_c::compiler::VERSION
+    );
+    assert!(lamp.starts_with(&header));
+    assert!(fs::read_to_string(dir.join("out/lamp.txt"))
+        .unwrap()
+        .starts_with(&header));
+    let smcat = fs::read_to_string(dir.join("out/lamp.smcat")).unwrap();
+    assert!(smcat.starts_with("# 2024-01-31T12:00:00Z\ninitial,"));
+    let index = fs::read_to_string(dir.join("out").join(MODULE_INDEX)).unwrap();
+    assert!(index.starts_with(&format!(
+        "// DO NOT EDIT: generated from {} by framec",
+        dir.join("src").display()
+    )));
+}
+
+/// Test that Cargo is told to rerun the build when the input directory, the Frame config file or an
+/// included file changes. The build runs in a child process of the test, whose output is not
+/// captured.
+#[test]
+fn cargo_directives() {
+    if let Some(dir) = std::env::var_os("FRAME_BUILD_DIRECTIVES_DIR") {
+        let dir = PathBuf::from(dir);
+        build(&dir)
+            .cargo_directives(true)
+            .frame_config(&dir.join("config.yaml"))
+            .run()
+            .unwrap();
+        build(&dir).cargo_directives(false).run().unwrap();
+        return;
+    }
+
+    let dir = input_dir("cargo_directives", &[]);
+    fs::write(dir.join("config.yaml"), "{}\n").unwrap();
+    // an included file outside of the input directory
+    let spec = SPEC.replace(
+        "    -interface-\n    toggle\n",
+        "#include \"../interface.frmi\"\n",
+    );
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/lamp.frm"), spec).unwrap();
+    fs::write(dir.join("interface.frmi"), "    -interface-\n    toggle\n").unwrap();
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["cargo_directives", "--exact", "--nocapture"])
+        .env("FRAME_BUILD_DIRECTIVES_DIR", &dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let directives: Vec<_> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        // the first directive follows the name of the test on its line
+        .filter_map(|line| line.find("cargo:").map(|start| line[start..].to_string()))
+        .collect();
+    assert_eq!(
+        directives,
+        [
+            format!("cargo:rerun-if-changed={}", dir.join("src").display()),
+            format!(
+                "cargo:rerun-if-changed={}",
+                dir.join("config.yaml").display()
+            ),
+            format!(
+                "cargo:rerun-if-changed={}",
+                dir.join("src/lamp.frm").display()
+            ),
+            format!(
+                "cargo:rerun-if-changed={}",
+                dir.join("src/../interface.frmi").display()
+            ),
+        ]
+    );
+}
+
+/// Test that the min depth of the traversal is set, and that invalid configurations are
+/// reported before building.
+#[test]
+fn validate() {
+    let dir = input_dir("validate", &[("lamp.frm", "Lamp"), ("a/b.frm", "B")]);
+    let input_dir = dir.join("src");
+    let input_files = build(&dir).min_depth(2).input_files().unwrap();
+    assert_eq!(input_files, [input_dir.join("a/b.frm")]);
+
+    build(&dir).validate().unwrap();
+    let invalid = [
+        (
+            build(&dir).min_depth(3).max_depth(2),
+            "the min depth 3 is greater than the max depth 2",
+        ),
+        (
+            build(&dir).add_input_dir(&dir.join("missing")),
+            "the input directory",
+        ),
+        (build(&dir).set_targets(&[]), "there are no targets"),
+        (build(&dir).output_dir(Path::new("")), "OUT_DIR is not set"),
+    ];
+    for (build, message) in invalid.iter() {
+        let err = build.validate().unwrap_err().to_string();
+        assert!(err.starts_with(message), "{}", err);
+        assert_eq!(build.run().unwrap_err().to_string(), err);
+    }
+}
+
+/// Test that the metadata of each machine is written next to its generated files.
+#[test]
+fn emit_metadata() {
+    let dir = input_dir("emit_metadata", &[("a/lamp.frm", "Lamp")]);
+    let generated = build(&dir)
+        .emit_metadata(MetadataFormat::Json)
+        .run()
+        .unwrap();
+    let metadata_path = dir.join("out/a/lamp.machine.json");
+    assert_eq!(
+        generated,
+        [dir.join("out/a/lamp.rs"), metadata_path.clone()]
+    );
+
+    let model = MachineModel::from_json(&fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    assert_eq!(model.name, "Lamp");
+    assert_eq!(model.initial_state.as_deref(), Some("Off"));
+    let states: Vec<_> = model
+        .states
+        .iter()
+        .map(|state| state.name.as_str())
+        .collect();
+    assert_eq!(states, ["Off", "On"]);
+    assert_eq!(model.interface[0].name, "toggle");
+}
+
+/// Test that Frame files included by other Frame files are compiled first, and that the files
+/// included by a Frame file are found, directly or not.
+#[test]
+fn includes() {
+    let dir = input_dir("includes", &[]);
+    fs::create_dir_all(dir.join("src/shared")).unwrap();
+    let spec = SPEC.replace(
+        "    -machine-",
+        "#include \"shared/interface.frmi\"\n    -machine-",
+    );
+    fs::write(
+        dir.join("src/a.frm"),
+        spec.replace("-interface-\n    toggle\n", ""),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("src/shared/interface.frmi"),
+        "    -interface-\n#include \"../z.frm\"\n",
+    )
+    .unwrap();
+    // a file that is only meant to be included is compiled on its own too with this extension
+    fs::write(dir.join("src/z.frm"), "    toggle\n").unwrap();
+
+    let started = Rc::new(RefCell::new(Vec::new()));
+    let recorded = started.clone();
+    let err = build(&dir)
+        .collect_errors()
+        .on_progress(move |event| {
+            if let BuildProgress::FileStarted { input_path } = event {
+                recorded.borrow_mut().push(input_path.to_path_buf());
+            }
+        })
+        .run()
+        .unwrap_err();
+    assert_eq!(
+        *started.borrow(),
+        [dir.join("src/z.frm"), dir.join("src/a.frm")]
+    );
+    let errors = &err.downcast_ref::<BuildErrors>().unwrap().errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].input_path.ends_with("z.frm"));
+    let lamp = fs::read_to_string(dir.join("out/a.rs")).unwrap();
+    assert!(lamp.contains("pub fn toggle(&mut self)"));
+}
+
+/// Test that each failure of the build is told to the callback with its diagnostics, located in
+/// the files.
+#[test]
+fn on_error() {
+    let dir = input_dir("on_error", &[("lamp.frm", "Lamp")]);
+    fs::write(dir.join("src/broken.frm"), "#A\n    -machine-\n    S\n##\n").unwrap();
+    let failures = Rc::new(RefCell::new(Vec::new()));
+    let recorded = failures.clone();
+    let err = build(&dir)
+        .add_custom_target("dsl", "dsl", |_, _| anyhow::bail!("no dsl"))
+        .continue_on_error()
+        .on_error(move |context| {
+            assert_eq!(context.error.input_path, context.input_path);
+            assert!(context
+                .diagnostics
+                .iter()
+                .all(|diagnostic| diagnostic.severity == Severity::Error));
+            for diagnostic in &context.diagnostics {
+                recorded.borrow_mut().push(format!(
+                    "{} {}:{:?}:{:?} {}",
+                    context.target,
+                    Path::new(diagnostic.file.as_ref().unwrap())
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy(),
+                    diagnostic.line,
+                    diagnostic.column,
+                    diagnostic.code
+                ));
+            }
+        })
+        .run();
+    assert!(err.is_ok());
+    assert_eq!(
+        *failures.borrow(),
+        [
+            "rust broken.frm:Some(3):Some(5) parse-error",
+            "dsl broken.frm:None:None build-error",
+            "dsl lamp.frm:None:None build-error",
+        ]
+    );
+}
+
+/// Test that the targets of the enabled Cargo features are added.
+#[test]
+fn targets_from_features() {
+    let dir = input_dir("targets_from_features", &[("lamp.frm", "Lamp")]);
+    std::env::set_var("CARGO_FEATURE_FRAME_BUILD_TEST_DIAGRAM", "1");
+    std::env::set_var("CARGO_FEATURE_FRAME_BUILD_TEST_RUST", "1");
+    let generated = build(&dir)
+        .targets_from_features(&[
+            ("frame-build-test-diagram", TargetLanguage::Smcat),
+            ("frame-build-test-rust", TargetLanguage::Rust),
+            ("frame-build-test-plantuml", TargetLanguage::PlantUml),
+        ])
+        .run()
+        .unwrap();
+    assert_eq!(
+        generated,
+        [dir.join("out/lamp.rs"), dir.join("out/lamp.smcat")]
+    );
+}
+
+/// Test that the source of each Frame file is copied next to the generated files, or embedded in
+/// the generated Rust code.
+#[test]
+fn embed_source() {
+    let dir = input_dir("embed_source", &[("a/lamp.frm", "Lamp")]);
+    // a quote followed by a `#` ends a raw string with one `#`
+    let spec = SPEC.replace("    -machine-", "    -machine-\n    --- \"#1\"");
+    fs::write(dir.join("src/a/lamp.frm"), &spec).unwrap();
+
+    let generated = build(&dir)
+        .embed_source(SourceEmbedding::Copy)
+        .run()
+        .unwrap();
+    assert_eq!(
+        generated,
+        [dir.join("out/a/lamp.rs"), dir.join("out/a/lamp.frm")]
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("out/a/lamp.frm")).unwrap(),
+        spec
+    );
+    let err = FrameBuild::new()
+        .input_dir(&dir.join("src"))
+        .output_dir(&dir.join("src/generated"))
+        .embed_source(SourceEmbedding::Copy)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().starts_with("the copies of the Frame files"));
+
+    build(&dir)
+        .embed_source(SourceEmbedding::Const)
+        .run()
+        .unwrap();
+    let code = fs::read_to_string(dir.join("out/a/lamp.rs")).unwrap();
+    assert!(code.ends_with(&format!(
+        "/// The Frame specification this code is generated from, `{}`.\n\
+         pub const FRAME_SOURCE: &str = r##\"{}\"##;\n",
+        Path::new("a/lamp.frm").display(),
+        spec
+    )));
+    let lib_path = dir.join("lib.rs");
+    fs::write(
+        &lib_path,
+        "pub mod lamp {\n    include!(\"out/a/lamp.rs\");\n}\n\
+         pub const SOURCE: &str = lamp::FRAME_SOURCE;\n",
+    )
+    .unwrap();
+    let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .args(["--crate-type", "lib", "--emit", "metadata"])
+        .args(["--edition", "2018", "--crate-name", "embed_source"])
+        .arg("--out-dir")
+        .arg(&dir)
+        .arg(&lib_path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Test that the files generated by the previous build from Frame files that were deleted or
+/// renamed are removed, but only when every Frame file builds, however failures are handled.
+#[test]
+fn prune_stale_outputs() {
+    let dir = input_dir(
+        "prune_stale_outputs",
+        &[("a/lamp.frm", "Lamp"), ("door.frm", "Door")],
+    );
+    fs::write(dir.join("out/other.rs"), "").unwrap();
+    build(&dir).prune_stale_outputs().run().unwrap();
+    let manifest_path = dir.join("out").join(OUTPUT_MANIFEST);
+    assert_eq!(
+        fs::read_to_string(&manifest_path).unwrap(),
+        "a/lamp.rs\ndoor.rs\n"
+    );
+
+    fs::remove_file(dir.join("src/door.frm")).unwrap();
+    fs::create_dir_all(dir.join("src/b")).unwrap();
+    fs::rename(dir.join("src/a/lamp.frm"), dir.join("src/b/lamp.frm")).unwrap();
+    fs::write(dir.join("src/broken.frm"), "#A\n    -machine-\n    S\n##\n").unwrap();
+    build(&dir)
+        .prune_stale_outputs()
+        .collect_errors()
+        .run()
+        .unwrap_err();
+    assert!(dir.join("out/door.rs").exists());
+    assert_eq!(
+        fs::read_to_string(&manifest_path).unwrap(),
+        "a/lamp.rs\nb/lamp.rs\ndoor.rs\n"
+    );
+
+    fs::remove_file(dir.join("src/broken.frm")).unwrap();
+    build(&dir).prune_stale_outputs().run().unwrap();
+    assert!(!dir.join("out/door.rs").exists());
+    assert!(!dir.join("out/a").exists());
+    assert!(dir.join("out/b/lamp.rs").exists());
+    assert!(dir.join("out/other.rs").exists());
+    assert_eq!(fs::read_to_string(&manifest_path).unwrap(), "b/lamp.rs\n");
+
+    // a failure that is only reported keeps the files too
+    fs::write(dir.join("src/b/lamp.frm"), "#A\n    -machine-\n    S\n##\n").unwrap();
+    build(&dir)
+        .prune_stale_outputs()
+        .continue_on_error()
+        .run()
+        .unwrap();
+    assert!(dir.join("out/b/lamp.rs").exists());
+    assert_eq!(fs::read_to_string(&manifest_path).unwrap(), "b/lamp.rs\n");
+}
+
+/// Test that a symbolic link to an ancestor directory fails the traversal rather than hanging it,
+/// and that a directory reached through two links is only traversed once.
+#[cfg(unix)]
+#[test]
+fn symlink_cycles() {
+    use std::os::unix::fs::symlink;
+
+    let dir = input_dir("symlink_cycles", &[("a/lamp.frm", "Lamp")]);
+    symlink(dir.join("src/a"), dir.join("src/b")).unwrap();
+    let input_files = build(&dir).follow_links().input_files().unwrap();
+    assert_eq!(input_files, [dir.join("src/a/lamp.frm")]);
+
+    symlink(dir.join("src"), dir.join("src/a/up")).unwrap();
+    let err = build(&dir).follow_links().run().unwrap_err().to_string();
+    assert!(err.starts_with("the symbolic link"), "{}", err);
+    assert!(err.ends_with("would be traversed forever"), "{}", err);
+}
+
+/// Test that a Frame file larger than the max file size fails the build before it is read.
+#[test]
+fn max_file_size() {
+    let dir = input_dir("max_file_size", &[("lamp.frm", "Lamp")]);
+    let size = SPEC.len() as u64;
+    build(&dir).max_file_size(size).run().unwrap();
+    let err = build(&dir).max_file_size(size - 1).run().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "the Frame file {:?} is {} bytes, more than the max file size of {} bytes",
+            dir.join("src/lamp.frm"),
+            size,
+            size - 1
+        )
+    );
+}
+
+/// Test that the generated files are kept in memory rather than written, with the contents that
+/// would be written.
+#[test]
+fn run_collect() {
+    let dir = input_dir("run_collect", &[("a/lamp.frm", "Lamp")]);
+    let configured = || {
+        build(&dir)
+            .add_target(TargetLanguage::Smcat)
+            .emit_metadata(MetadataFormat::Json)
+            .generate_module_index()
+    };
+    let artifacts = configured().run_collect().unwrap();
+    assert_eq!(fs::read_dir(dir.join("out")).unwrap().count(), 0);
+
+    let generated = configured().run().unwrap();
+    let input_path = dir.join("src/a/lamp.frm");
+    let artifact =
+        |input_path: Option<&Path>, target: &str, output_path: &Path| GeneratedArtifact {
+            input_path: input_path.map(Path::to_path_buf),
+            target: target.to_string(),
+            output_path: output_path.to_path_buf(),
+            contents: fs::read_to_string(output_path).unwrap(),
+        };
+    assert_eq!(
+        artifacts,
+        [
+            artifact(Some(&input_path), "rust", &generated[0]),
+            artifact(Some(&input_path), "smcat", &generated[1]),
+            artifact(Some(&input_path), "metadata", &generated[2]),
+            artifact(None, "rust", &generated[3]),
+        ]
+    );
+}
+
+/// Test that the messages of the build go to the logger up to the verbosity.
+#[test]
+fn verbosity() {
+    let dir = input_dir("verbosity", &[("draft.frm", "Draft"), ("lamp.frm", "Lamp")]);
+    fs::write(dir.join("src/lamp.rs"), "").unwrap();
+    let logged = |verbosity| {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let recorded = messages.clone();
+        build(&dir)
+            .include_only_if(|path| !path.ends_with("draft.frm"))
+            .verbosity(verbosity)
+            .logger(move |level, message| {
+                recorded.borrow_mut().push(format!("{} {}", level, message));
+            })
+            .run()
+            .unwrap();
+        let messages = messages.borrow().clone();
+        messages
+    };
+
+    let messages = logged(LevelFilter::Debug);
+    assert_eq!(messages.len(), 3);
+    assert_eq!(
+        messages[0],
+        format!(
+            "DEBUG {:?} is excluded by the filter",
+            dir.join("src/draft.frm")
+        )
+    );
+    assert_eq!(
+        messages[1],
+        format!(
+            "DEBUG {:?} generates {:?} for rust",
+            dir.join("src/lamp.frm"),
+            dir.join("out/lamp.rs")
+        )
+    );
+    assert!(messages[2].starts_with("INFO Generated 1 file(s) in "));
+    assert_eq!(logged(LevelFilter::Trace).len(), 4);
+    assert_eq!(logged(LevelFilter::Info).len(), 1);
+    assert!(logged(LevelFilter::Off).is_empty());
+}
+
+/// Test that the Rust code generated from all of the Frame files is written into one module tree,
+/// and that it compiles.
+#[test]
+fn aggregate_output() {
+    let dir = input_dir(
+        "aggregate_output",
+        &[("a/b/lamp.frm", "Lamp"), ("door.frm", "Door")],
+    );
+    let generated = build(&dir)
+        .add_target(TargetLanguage::Smcat)
+        .aggregate_output("frame_machines.rs")
+        .run()
+        .unwrap();
+    let aggregate_path = dir.join("out/frame_machines.rs");
+    assert_eq!(
+        generated,
+        [
+            dir.join("out/a/b/lamp.smcat"),
+            dir.join("out/door.smcat"),
+            aggregate_path.clone()
+        ]
+    );
+    assert!(!dir.join("out/a/b/lamp.rs").exists());
+    let code = fs::read_to_string(&aggregate_path).unwrap();
+    assert!(code.contains("pub mod a {\n    pub mod b {\n        pub mod lamp {\n"));
+
+    let lib_path = dir.join("lib.rs");
+    fs::write(
+        &lib_path,
+        "include!(\"out/frame_machines.rs\");\n\
+         pub fn machines() -> (a::b::lamp::Lamp, door::Door) {\n\
+         \x20   (a::b::lamp::Lamp::new(), door::Door::new())\n\
+         }\n",
+    )
+    .unwrap();
+    let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .args(["--crate-type", "lib", "--emit", "metadata"])
+        .args(["--edition", "2018", "--crate-name", "aggregate_output"])
+        .arg("--out-dir")
+        .arg(&dir)
+        .arg(&lib_path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let err = build(&dir)
+        .aggregate_output("frame_machines.rs")
+        .generate_module_index()
+        .validate()
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("the module index and include stubs"));
+}
@@ -0,0 +1,160 @@
+//! This crate provides the [`frame!`] macro, which compiles a Frame specification written inline
+//! in Rust code into Rust using the Frame transpiler, Framec, while the crate is compiled.
+//!
+//! # What does it do?
+//!
+//! Small machines, such as those of tests and examples, can be written next to the code that uses
+//! them, without a `build.rs` script or an `include!` of the generated file. The macro expands to
+//! the code Framec generates for the spec, in place.
+//!
+//! ```ignore
+//! use frame_macro::frame;
+//!
+//! frame! {
+//!     #Lamp
+//!         -interface-
+//!         toggle
+//!         -machine-
+//!         $Off
+//!             |toggle| -> $On ^
+//!         $On
+//!             |toggle| -> $Off ^
+//!     ##
+//! }
+//!
+//! let mut lamp = Lamp::new();
+//! lamp.toggle();
+//! ```
+//!
+//! # How to use it?
+//!
+//! Add the following to your `Cargo.toml` file, along with `frame_runtime` and `once_cell` if the
+//! spec enables the `runtime_support` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! frame_macro = "0.8"
+//! ```
+//!
+//! The spec can be written as Rust tokens, as above, or as a string literal, e.g.
+//! `frame!(r#"..."#)`. The tokens must be valid Rust tokens, so a spec with backticks or
+//! unbalanced quotes in its comments must be written as a string. The layout of the tokens is
+//! kept, so errors in the spec are reported at the line of the Rust code they refer to, while
+//! errors in a string are reported at the string, with their line and column in it. The layout is
+//! read from the locations of the tokens, so the macro needs Rust 1.88 or newer, unlike the other
+//! crates of Frame, which build with Rust 1.59.
+//!
+//! Framec is configured like `frame_build` configures it from a build script: the attributes of
+//! the spec are merged on top of the `config.yaml` file in the package directory, if there is
+//! one. Cargo doesn't know about the config file, so a change to it is only seen by the next
+//! build of the code using the macro.
+
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use proc_macro::{TokenStream, TokenTree};
+use proc_macro2::Span;
+use quote::quote_spanned;
+use std::path::PathBuf;
+
+/// Compile an inline Frame spec to Rust. See the [crate documentation](crate) for details.
+#[proc_macro]
+pub fn frame(input: TokenStream) -> TokenStream {
+    let (spec, spans) = match syn::parse::<syn::LitStr>(input.clone()) {
+        Ok(lit) => (lit.value(), SpecSpans::Literal(lit.span())),
+        Err(_) => spec_from_tokens(input),
+    };
+
+    let config_path = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(|dir| PathBuf::from(dir).join("config.yaml"))
+        .filter(|path| path.exists());
+    let exe = Exe::with_options(CompilerOptions {
+        load_local_config: false,
+        ..CompilerOptions::default()
+    });
+    match exe.run(&config_path, None, spec, Some(TargetLanguage::Rust)) {
+        Ok(code) => match code.parse() {
+            Ok(tokens) => tokens,
+            Err(err) => compile_error(
+                Span::call_site(),
+                &format!("Framec generated code that is not valid Rust: {}", err),
+            ),
+        },
+        Err(err) => err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| match spans {
+                // the location in the string is the only one there is
+                SpecSpans::Literal(span) => compile_error(span, &diagnostic.to_string()),
+                SpecSpans::Tokens(..) => compile_error(
+                    spans.line(diagnostic.line),
+                    &format!("{}: {}", diagnostic.code, diagnostic.message),
+                ),
+            })
+            .collect(),
+    }
+}
+
+/// Where the lines of the spec are in the Rust code, for reporting errors.
+enum SpecSpans {
+    /// The spec is a string literal.
+    Literal(Span),
+    /// The spec is Rust tokens starting on the given line, with the span of the first token of
+    /// each line.
+    Tokens(usize, Vec<(usize, Span)>),
+}
+
+impl SpecSpans {
+    /// The span to report an error on a line of the spec at.
+    fn line(&self, line: Option<usize>) -> Span {
+        match (self, line) {
+            (SpecSpans::Literal(span), _) => *span,
+            (SpecSpans::Tokens(first_line, spans), Some(line)) => spans
+                .iter()
+                .rev()
+                .find(|(l, _)| *l <= first_line + line - 1)
+                .map(|(_, span)| *span)
+                .unwrap_or_else(Span::call_site),
+            (SpecSpans::Tokens(..), None) => Span::call_site(),
+        }
+    }
+}
+
+/// Recover the text of a spec written as Rust tokens from their source, keeping their layout, so
+/// that the line numbers of the spec are those of the Rust code relative to its first line.
+fn spec_from_tokens(input: TokenStream) -> (String, SpecSpans) {
+    let mut spec = String::new();
+    let mut spans = Vec::new();
+    let mut first_line = None;
+    let mut position: Option<(usize, usize)> = None;
+    for token in input {
+        let span = token.span();
+        let (line, column) = (span.line(), span.column());
+        match position {
+            Some((last_line, _)) if line > last_line => {
+                spec.push_str(&"\n".repeat(line - last_line));
+                spec.push_str(&" ".repeat(column.saturating_sub(1)));
+            }
+            Some((_, last_column)) => {
+                spec.push_str(&" ".repeat(column.saturating_sub(last_column)))
+            }
+            None => first_line = Some(line),
+        }
+        if spans.last().map_or(true, |(l, _)| *l < line) {
+            spans.push((line, Span::from(span)));
+        }
+
+        // groups are taken verbatim, with their layout and any comments in them
+        let text = match &token {
+            TokenTree::Group(group) => group.span().source_text(),
+            _ => span.source_text(),
+        };
+        spec.push_str(&text.unwrap_or_else(|| token.to_string()));
+        let end = span.end();
+        position = Some((end.line(), end.column()));
+    }
+    spec.push('\n');
+    (spec, SpecSpans::Tokens(first_line.unwrap_or(1), spans))
+}
+
+fn compile_error(span: Span, msg: &str) -> TokenStream {
+    quote_spanned!(span=> compile_error!(#msg);).into()
+}
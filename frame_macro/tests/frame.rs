@@ -0,0 +1,63 @@
+//! Tests of machines compiled from inline specs.
+
+mod tokens {
+    use frame_macro::frame;
+
+    frame! {
+        #Lamp
+            -interface-
+            toggle
+            brightness : i32
+            -machine-
+            $Off
+                |>| level = 0 ^   --- entering turns the light off
+                |toggle| -> $On ^
+                |brightness| ^(level)
+            $On
+                |>| level = 10 ^
+                |toggle| -> $Off ^
+                |brightness| ^(level)
+            -domain-
+            var level:i32 = 0
+        ##
+    }
+
+    /// Test that a spec written as tokens compiles, with its comments and layout.
+    #[test]
+    fn lamp() {
+        let mut lamp = Lamp::new();
+        assert_eq!(lamp.brightness(), 0);
+        lamp.toggle();
+        assert_eq!(lamp.brightness(), 10);
+        lamp.toggle();
+        assert_eq!(lamp.brightness(), 0);
+    }
+}
+
+mod literal {
+    use frame_macro::frame;
+
+    frame!(
+        r#"#[codegen.rust.features.runtime_support:bool="true"]
+#Switch
+    -interface-
+    flip
+    -machine-
+    $Down
+        |flip| -> "up" $Up ^
+    $Up
+        |flip| -> "down" $Down ^
+##
+"#
+    );
+
+    /// Test that a spec written as a string literal compiles, with the attributes in it.
+    #[test]
+    fn switch() {
+        use frame_runtime::Machine;
+        let mut switch = Switch::new();
+        switch.flip();
+        assert_eq!(switch.state().info().name, "Up");
+        assert_eq!(Switch::machine_info().transitions[0].label, "up");
+    }
+}
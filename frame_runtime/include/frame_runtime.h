@@ -0,0 +1,63 @@
+/*
+ * C interface to state machines generated by Frame with the `runtime_support` and
+ * `runtime_dispatch` features, exposed by the `ffi` feature of the `frame_runtime` crate. See the
+ * documentation of `frame_runtime::ffi` for details.
+ *
+ * Strings returned by these functions are owned by the caller and must be released with
+ * `frame_string_free`. A machine must not be used from several threads at once.
+ */
+
+#ifndef FRAME_RUNTIME_H
+#define FRAME_RUNTIME_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define FRAME_OK 0
+#define FRAME_INVALID_ARGUMENT (-1)
+#define FRAME_UNKNOWN_METHOD (-2)
+#define FRAME_BAD_ARGUMENTS (-3)
+#define FRAME_PANIC (-4)
+
+/* A running state machine, created by a constructor exported by the embedding crate. */
+typedef struct FrameMachine FrameMachine;
+
+/* The name of the machine's system, e.g. "Account". */
+char *frame_machine_name(const FrameMachine *machine);
+
+/* The name of the current state, e.g. "Open". */
+char *frame_machine_state(const FrameMachine *machine);
+
+/* The domain variables as a JSON object, e.g. {"balance":5}. */
+char *frame_machine_variables(const FrameMachine *machine);
+
+/* The machine name, current state with its arguments and variables, and domain variables, as a
+ * JSON object. */
+char *frame_machine_snapshot(const FrameMachine *machine);
+
+/* Call an interface method by name with arguments given as text. On success, returns FRAME_OK
+ * and stores the return value as JSON in *result, unless result is NULL. On failure, returns one
+ * of the error codes above; frame_last_error describes the error. */
+int frame_machine_dispatch(FrameMachine *machine,
+                           const char *method,
+                           const char *const *arguments,
+                           size_t argument_count,
+                           char **result);
+
+/* Release a machine. */
+void frame_machine_free(FrameMachine *machine);
+
+/* The message of the most recent failed call on this thread, or NULL. */
+char *frame_last_error(void);
+
+/* Release a string returned by one of the functions above. */
+void frame_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FRAME_RUNTIME_H */
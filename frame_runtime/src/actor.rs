@@ -0,0 +1,191 @@
+//! This module hosts running state machines as actors on a [tokio](https://tokio.rs) runtime,
+//! so that a service can run many instances concurrently without writing a mailbox loop for
+//! each. It is enabled by the `actor` feature of this crate.
+//!
+//! An actor owns its machine, and handles the messages of its mailbox one at a time in a task of
+//! its own. Messages are sent through an [ActorHandle], which can be cloned and shared between
+//! tasks. Each message is a closure run with the machine, typically calling one of its
+//! interface methods, whose result is sent back through a reply channel:
+//!
+//! ```ignore
+//! let account = Actor::spawn(Account::new());
+//! account.tell(|account| account.deposit(10))?;
+//! let balance = account.ask(|account| account.get_balance()).await?;
+//! let account = account.stop().await?;
+//! ```
+//!
+//! Actors spawned with [Actor::spawn] run on any worker thread of the runtime, so the machine
+//! must be `Send`, which is the case for machines generated with the `thread_safe` feature.
+//! Other machines can be spawned on a [tokio::task::LocalSet] with [Actor::spawn_local].
+//!
+//! Machines generated with the `runtime_dispatch` feature can also be sent events by name,
+//! with [ActorHandle::dispatch].
+
+use crate::dispatch::{Dispatch, DispatchError};
+use std::any::Any;
+use std::fmt;
+use tokio::sync::{mpsc, oneshot};
+
+/// A message in the mailbox of an actor.
+enum Message<M> {
+    /// Run a closure with the machine.
+    Run(Box<dyn FnOnce(&mut M) + Send>),
+
+    /// Stop the actor and send back its machine.
+    Stop(oneshot::Sender<M>),
+}
+
+/// The ways a message to an actor may fail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ActorError {
+    /// The actor has stopped, either because it was asked to, or because a message panicked.
+    Stopped,
+
+    /// An event sent by name was rejected by the machine.
+    Dispatch(DispatchError),
+
+    /// The return value of an event sent by name isn't of the requested type.
+    ReturnType {
+        method: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ActorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActorError::Stopped => write!(f, "the actor has stopped"),
+            ActorError::Dispatch(err) => err.fmt(f),
+            ActorError::ReturnType { method, expected } => write!(
+                f,
+                "the return value of `{}` is not of type `{}`",
+                method, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ActorError {}
+
+impl From<DispatchError> for ActorError {
+    fn from(err: DispatchError) -> ActorError {
+        ActorError::Dispatch(err)
+    }
+}
+
+/// Functions spawning actors.
+pub struct Actor;
+
+impl Actor {
+    /// Spawn an actor owning the given machine on the current tokio runtime. The actor runs until
+    /// it is stopped or all of its handles are dropped.
+    pub fn spawn<M: Send + 'static>(machine: M) -> ActorHandle<M> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(machine, receiver));
+        ActorHandle { sender }
+    }
+
+    /// Spawn an actor owning the given machine on the current [tokio::task::LocalSet], for
+    /// machines that aren't `Send`.
+    pub fn spawn_local<M: 'static>(machine: M) -> ActorHandle<M> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::task::spawn_local(run(machine, receiver));
+        ActorHandle { sender }
+    }
+}
+
+/// Handle messages until the actor is stopped or its handles are dropped.
+async fn run<M>(mut machine: M, mut mailbox: mpsc::UnboundedReceiver<Message<M>>) {
+    while let Some(message) = mailbox.recv().await {
+        match message {
+            Message::Run(f) => f(&mut machine),
+            Message::Stop(reply) => {
+                let _ = reply.send(machine);
+                return;
+            }
+        }
+    }
+}
+
+/// A handle to send messages to an actor, which can be cloned to share the actor.
+pub struct ActorHandle<M> {
+    sender: mpsc::UnboundedSender<Message<M>>,
+}
+
+impl<M> Clone for ActorHandle<M> {
+    fn clone(&self) -> Self {
+        ActorHandle {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<M: 'static> ActorHandle<M> {
+    /// Queue a closure to run with the machine, without waiting for it.
+    pub fn tell<F>(&self, f: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&mut M) + Send + 'static,
+    {
+        self.sender
+            .send(Message::Run(Box::new(f)))
+            .map_err(|_| ActorError::Stopped)
+    }
+
+    /// Run a closure with the machine after the messages already queued, and return its result.
+    pub async fn ask<F, R>(&self, f: F) -> Result<R, ActorError>
+    where
+        F: FnOnce(&mut M) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, result) = oneshot::channel();
+        self.tell(move |machine| {
+            let _ = reply.send(f(machine));
+        })?;
+        result.await.map_err(|_| ActorError::Stopped)
+    }
+
+    /// Stop the actor after the messages already queued, and return its machine. Messages sent
+    /// by other handles afterwards fail with [ActorError::Stopped].
+    pub async fn stop(self) -> Result<M, ActorError> {
+        let (reply, machine) = oneshot::channel();
+        self.sender
+            .send(Message::Stop(reply))
+            .map_err(|_| ActorError::Stopped)?;
+        machine.await.map_err(|_| ActorError::Stopped)
+    }
+
+    /// Whether the actor has stopped, e.g. because a message panicked.
+    pub fn is_stopped(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+impl<M: Dispatch + 'static> ActorHandle<M> {
+    /// Call the interface method with the given name after the messages already queued, and
+    /// return its return value, which must be of type `R`, or `None` for methods without a
+    /// return type. See [Dispatch::dispatch].
+    pub async fn dispatch<R: Any + Send>(
+        &self,
+        method: &str,
+        arguments: Vec<Box<dyn Any + Send>>,
+    ) -> Result<Option<R>, ActorError> {
+        let method = method.to_string();
+        self.ask(move |machine| {
+            let arguments = arguments
+                .into_iter()
+                .map(|argument| argument as Box<dyn Any>)
+                .collect();
+            match machine.dispatch(&method, arguments)? {
+                Some(value) => match value.downcast::<R>() {
+                    Ok(value) => Ok(Some(*value)),
+                    Err(_) => Err(ActorError::ReturnType {
+                        method,
+                        expected: std::any::type_name::<R>(),
+                    }),
+                },
+                None => Ok(None),
+            }
+        })
+        .await?
+    }
+}
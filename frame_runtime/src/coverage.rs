@@ -0,0 +1,395 @@
+//! This module generates test cases from the transition graph of a state machine. From the static
+//! [MachineInfo] of a machine, it computes sequences of interface events that, sent to new
+//! instances of the machine, take every transition (or every pair of consecutive transitions) of
+//! the machine at least once. The sequences can be rendered as a Rust `tests` module to put next
+//! to the generated machine, or as a line-based script for driving the machine from a simulator.
+//!
+//! The sequences are computed from the structure of the machine alone, so they are a baseline to
+//! start from rather than finished tests:
+//!
+//!  * Guards are not evaluated. A transition in a guarded branch is assumed to be taken whenever
+//!    its event is handled, so the arguments or domain of a test may have to be adjusted to make
+//!    the intended branch taken.
+//!  * An event is assumed to be handled by the transitions of the nearest of the current state and
+//!    its ancestors that has a transition for it.
+//!  * Transitions in enter handlers are taken as soon as their state is entered by a transition,
+//!    but not by a change-state. Transitions in exit handlers are not covered.
+//!  * The target of a state stack pop is not known, so a sequence ends after popping the stack.
+//!
+//! Transitions that can't be reached under these assumptions are listed as uncovered.
+
+use crate::info::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+/// The coverage criterion that generated sequences satisfy.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Coverage {
+    /// Every transition is taken at least once.
+    AllTransitions,
+    /// Every transition is taken at least once, and every transition is followed at least once by
+    /// each transition that can be taken after it.
+    AllTransitionPairs,
+}
+
+/// A sequence of interface events to send to a new instance of a machine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestSequence {
+    /// The interface events to send, in order.
+    pub events: Vec<&'static MethodInfo>,
+
+    /// The transitions the events are expected to take, in order. This includes transitions taken
+    /// when the machine is created and transitions of enter handlers.
+    pub transitions: Vec<&'static TransitionInfo>,
+
+    /// The expected state at the end of the sequence, or `None` if the sequence ends by popping
+    /// the state stack.
+    pub final_state: Option<&'static StateInfo>,
+}
+
+/// The test sequences achieving a coverage criterion for a machine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestPlan {
+    /// The machine the sequences are for.
+    pub machine: &'static MachineInfo,
+
+    /// The sequences, each to be sent to a new instance of the machine.
+    pub sequences: Vec<TestSequence>,
+
+    /// The transitions that no sequence takes.
+    pub uncovered: Vec<&'static TransitionInfo>,
+}
+
+/// A position in the transition graph. Whether the current state was entered by the last
+/// transition determines if the transitions of its enter handler are taken next.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Node {
+    state: &'static str,
+    entered: bool,
+    last: Option<usize>,
+}
+
+/// An element of the machine to cover: a transition or a pair of consecutive transitions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Item {
+    Transition(usize),
+    Pair(usize, usize),
+}
+
+struct Planner {
+    machine: &'static MachineInfo,
+    coverage: Coverage,
+    covered: HashSet<Item>,
+}
+
+impl Planner {
+    /// The transitions that can be taken next from a node.
+    fn steps(&self, node: &Node) -> Vec<&'static TransitionInfo> {
+        let state = match self.machine.get_state(node.state) {
+            Some(state) if !state.is_stack_pop => state,
+            _ => return Vec::new(),
+        };
+        if node.entered {
+            let enter_event = format!("{}:>", state.name);
+            let on_enter = self.transitions_of(state, &enter_event);
+            if !on_enter.is_empty() {
+                return on_enter;
+            }
+        }
+        let mut steps = Vec::new();
+        for event in self.machine.interface {
+            let mut states = vec![state];
+            states.extend(state.ancestors());
+            if let Some(handled) = states
+                .iter()
+                .map(|state| self.transitions_of(state, event.name))
+                .find(|transitions| !transitions.is_empty())
+            {
+                steps.extend(handled);
+            }
+        }
+        steps
+    }
+
+    fn transitions_of(&self, state: &StateInfo, event: &str) -> Vec<&'static TransitionInfo> {
+        self.machine
+            .transitions
+            .iter()
+            .copied()
+            .filter(|transition| transition.source.name == state.name)
+            .filter(|transition| transition.event.name == event)
+            .collect()
+    }
+
+    fn take(&self, transition: &TransitionInfo) -> Node {
+        Node {
+            state: transition.target.name,
+            entered: transition.is_transition(),
+            last: match self.coverage {
+                Coverage::AllTransitions => None,
+                Coverage::AllTransitionPairs => Some(transition.id),
+            },
+        }
+    }
+
+    fn items(&self, node: &Node, transition: &TransitionInfo) -> Vec<Item> {
+        let mut items = vec![Item::Transition(transition.id)];
+        if let Some(last) = node.last {
+            items.push(Item::Pair(last, transition.id));
+        }
+        items
+    }
+
+    fn is_new(&self, node: &Node, transition: &TransitionInfo) -> bool {
+        self.items(node, transition)
+            .iter()
+            .any(|item| !self.covered.contains(item))
+    }
+
+    /// Find the shortest path from a node to a transition that covers a new item.
+    fn path_to_new(&self, start: Node) -> Option<Vec<&'static TransitionInfo>> {
+        let mut parents: HashMap<Node, (Node, &'static TransitionInfo)> = HashMap::new();
+        let mut queue = VecDeque::from(vec![start]);
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        while let Some(node) = queue.pop_front() {
+            for transition in self.steps(&node) {
+                let next = self.take(transition);
+                if self.is_new(&node, transition) {
+                    let mut path = vec![transition];
+                    let mut current = node;
+                    while let Some((parent, transition)) = parents.get(&current) {
+                        path.push(transition);
+                        current = *parent;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                if visited.insert(next) {
+                    parents.insert(next, (node, transition));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Take a transition in a sequence, covering its items.
+    fn extend(
+        &mut self,
+        sequence: &mut TestSequence,
+        node: &mut Node,
+        transition: &'static TransitionInfo,
+    ) {
+        for item in self.items(node, transition) {
+            self.covered.insert(item);
+        }
+        if !transition.event.name.contains(':') {
+            sequence.events.push(transition.event);
+        }
+        sequence.transitions.push(transition);
+        *node = self.take(transition);
+    }
+
+    fn plan(mut self) -> TestPlan {
+        let mut sequences = Vec::new();
+        let start = match self.machine.initial_state() {
+            Some(state) => Node {
+                state: state.name,
+                entered: true,
+                last: None,
+            },
+            None => {
+                return TestPlan {
+                    machine: self.machine,
+                    sequences,
+                    uncovered: self.machine.transitions.to_vec(),
+                }
+            }
+        };
+
+        while let Some(mut path) = self.path_to_new(start) {
+            let mut sequence = TestSequence {
+                events: Vec::new(),
+                transitions: Vec::new(),
+                final_state: None,
+            };
+            let mut node = start;
+            loop {
+                for transition in path {
+                    self.extend(&mut sequence, &mut node, transition);
+                }
+                path = match self.path_to_new(node) {
+                    Some(path) => path,
+                    None => break,
+                };
+            }
+
+            // the transitions of enter handlers are taken before the next event can be sent
+            let mut remaining = self.machine.transitions.len();
+            while remaining > 0 && node.entered {
+                let transition = match self.steps(&node).first() {
+                    Some(transition) if transition.event.name.ends_with(":>") => *transition,
+                    _ => break,
+                };
+                self.extend(&mut sequence, &mut node, transition);
+                remaining -= 1;
+            }
+            sequence.final_state = self
+                .machine
+                .get_state(node.state)
+                .filter(|state| !state.is_stack_pop);
+            sequences.push(sequence);
+        }
+
+        let uncovered = self
+            .machine
+            .transitions
+            .iter()
+            .copied()
+            .filter(|transition| !self.covered.contains(&Item::Transition(transition.id)))
+            .collect();
+        TestPlan {
+            machine: self.machine,
+            sequences,
+            uncovered,
+        }
+    }
+}
+
+impl TestPlan {
+    /// Compute test sequences achieving a coverage criterion for a machine.
+    pub fn new(machine: &'static MachineInfo, coverage: Coverage) -> TestPlan {
+        Planner {
+            machine,
+            coverage,
+            covered: HashSet::new(),
+        }
+        .plan()
+    }
+
+    /// Render the sequences as a Rust `tests` module to put next to the code generated for the
+    /// machine, with one test per sequence. Each test creates the machine with `new()`, calls the
+    /// interface methods of the sequence with default arguments, and checks the final state.
+    ///
+    /// Interface methods are called by their names in snake case, as generated by the Rust
+    /// backend with its default naming conventions. Checking the final state requires the
+    /// `runtime_support` feature.
+    pub fn render_rust_tests(&self) -> String {
+        let mut code = String::new();
+        code.push_str("#[cfg(test)]\nmod transition_coverage {\n");
+        code.push_str("    use super::*;\n    use frame_runtime::Machine;\n");
+        for transition in &self.uncovered {
+            let _ = writeln!(code, "\n    // not covered: {}", describe(transition));
+        }
+        for (i, sequence) in self.sequences.iter().enumerate() {
+            code.push('\n');
+            for transition in &sequence.transitions {
+                let _ = writeln!(code, "    // {}", describe(transition));
+            }
+            let _ = writeln!(code, "    #[test]\n    fn sequence_{}() {{", i + 1);
+            let _ = writeln!(code, "        let mut sm = {}::new();", self.machine.name);
+            for event in &sequence.events {
+                let args = vec!["Default::default()"; event.parameters.len()];
+                let _ = writeln!(
+                    code,
+                    "        sm.{}({});",
+                    snake_case(event.name),
+                    args.join(", ")
+                );
+            }
+            match sequence.final_state {
+                Some(state) => {
+                    let _ = writeln!(
+                        code,
+                        "        assert_eq!(sm.state().info().name, \"{}\");",
+                        state.name
+                    );
+                }
+                None => code.push_str("        // the state stack was popped\n"),
+            }
+            code.push_str("    }\n");
+        }
+        code.push_str("}\n");
+        code
+    }
+
+    /// Render the sequences as a line-based script. Each sequence starts with a `new` line, which
+    /// creates a new instance of the machine, followed by a line per event with its name and the
+    /// names and types of its parameters, and ends with the expected final state. Lines starting
+    /// with `#` list the transitions that are expected to be taken.
+    pub fn render_script(&self) -> String {
+        let mut script = String::new();
+        for transition in &self.uncovered {
+            let _ = writeln!(script, "# not covered: {}", describe(transition));
+        }
+        for sequence in &self.sequences {
+            let _ = writeln!(script, "new {}", self.machine.name);
+            for transition in &sequence.transitions {
+                let _ = writeln!(script, "# {}", describe(transition));
+            }
+            for event in &sequence.events {
+                let mut line = event.name.to_string();
+                for param in event.parameters {
+                    let _ = write!(line, " {}:{}", param.name, param.vtype);
+                }
+                let _ = writeln!(script, "{}", line);
+            }
+            match sequence.final_state {
+                Some(state) => {
+                    let _ = writeln!(script, "state {}", state.name);
+                }
+                None => script.push_str("state ^\n"),
+            }
+        }
+        script
+    }
+}
+
+/// Describe a transition and the event that takes it, e.g. `S0->S1 |transit|`.
+fn describe(transition: &TransitionInfo) -> String {
+    format!("{} |{}|", transition, transition.event.name)
+}
+
+/// Convert a name to snake case, splitting words like the Rust backend does for the names of
+/// interface methods, e.g. `getHTTPStatus2` to `get_http_status_2`.
+fn snake_case(name: &str) -> String {
+    let mut words = Vec::new();
+    for part in name.split(['_', '-', ' ']) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, c) in chars.iter().enumerate() {
+            let boundary = i > 0 && {
+                let previous = chars[i - 1];
+                let next = chars.get(i + 1);
+                (previous.is_lowercase() && c.is_uppercase())
+                    || (previous.is_ascii_digit() != c.is_ascii_digit())
+                    || (previous.is_uppercase()
+                        && c.is_uppercase()
+                        && next.map_or(false, |next| next.is_lowercase()))
+            };
+            if boundary {
+                words.push(word);
+                word = String::new();
+            }
+            word.extend(c.to_lowercase());
+        }
+        words.push(word);
+    }
+    words.retain(|word| !word.is_empty());
+    words.join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("transit"), "transit");
+        assert_eq!(snake_case("X"), "x");
+        assert_eq!(snake_case("toA"), "to_a");
+        assert_eq!(snake_case("to_B"), "to_b");
+        assert_eq!(snake_case("getHTTPStatus2Code"), "get_http_status_2_code");
+    }
+}
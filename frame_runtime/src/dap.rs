@@ -0,0 +1,845 @@
+//! This module provides a [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/)
+//! server for stepping running state machines from a debugger UI such as that of VS Code. It is
+//! enabled by the `dap` feature of this crate.
+//!
+//! Instances of machines generated with `runtime_support` are attached to the server under a
+//! name, and each one appears as a thread of the debuggee. The debugger connects over TCP, e.g.
+//! with `"debugServer": 4711` in a VS Code launch configuration, and can then:
+//!
+//!  * set breakpoints on the lines of states, event handlers and interface methods of a spec,
+//!    which are resolved through the source maps written by Framec for the Rust backend's
+//!    `generate_source_map` feature;
+//!  * set function breakpoints on a state (`Locked`), an event handler (`Locked:coin`, or
+//!    `Locked:>` for an enter handler) or an interface event (`coin`);
+//!  * step event by event: `next` stops at the next interface event the machine is sent, and
+//!    `stepIn` also stops at the enter and exit events of the transitions in between;
+//!  * inspect the arguments of the current event, the arguments and variables of the current
+//!    state, and the domain variables.
+//!
+//! ```ignore
+//! use frame_runtime::dap::DapServer;
+//!
+//! let server = DapServer::bind("127.0.0.1:4711")?;
+//! server.add_source_map(Turnstile::machine_info(), include_str!(concat!(env!("OUT_DIR"), "/turnstile.rs.map.json")))?;
+//! let mut turnstile = Turnstile::new();
+//! server.attach("turnstile", &mut turnstile);
+//! ```
+//!
+//! A machine stops before it handles an event, by blocking the thread that sent the event until
+//! the debugger resumes it. Machines don't stop while no debugger is connected. Callbacks don't
+//! have access to the domain variables, so those shown are the ones of the last call to
+//! [DapServer::update] for the instance, e.g. from its event loop, or of [DapServer::attach].
+
+use crate::callback::{Callback, CallbackSend};
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::{MachineInfo, NameInfo, StateInfo};
+use crate::machine::{Machine, State, ThreadSafeMachine, ThreadUnsafeMachine};
+use crate::transition::Transition;
+use crate::values;
+use serde_json::{json, Value as Json};
+use std::cell::RefCell;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::Deref;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+
+/// The name of the callbacks registered by the server.
+const CALLBACK_NAME: &str = "dap_server";
+
+/// The number of variable scopes of a stopped thread: the event, the state and the domain.
+const SCOPES: i64 = 4;
+
+/// A Debug Adapter Protocol server for the machines attached to it.
+pub struct DapServer {
+    shared: Arc<Shared>,
+    address: SocketAddr,
+}
+
+/// The state shared by the server, its client connection and the attached machines.
+struct Shared {
+    session: Mutex<Session>,
+    /// Notified when stopped threads are resumed.
+    resumed: Condvar,
+    client: Mutex<Option<Client>>,
+}
+
+/// The connected debugger.
+struct Client {
+    stream: TcpStream,
+    seq: i64,
+}
+
+#[derive(Default)]
+struct Session {
+    connected: bool,
+    threads: Vec<Thread>,
+    next_thread_id: i64,
+    source_maps: Vec<SourceMap>,
+    /// The lines of the breakpoints requested for each source file.
+    line_breakpoints: Vec<(String, Vec<usize>)>,
+    function_breakpoints: Vec<String>,
+    /// The breakpoints of both kinds, resolved to the items they stop at.
+    targets: Vec<Target>,
+}
+
+/// An attached instance.
+struct Thread {
+    id: i64,
+    instance: String,
+    machine: &'static MachineInfo,
+    variables: Vec<Variable>,
+    step: Option<Step>,
+    pause_requested: bool,
+    stopped: Option<Stop>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Step {
+    /// Stop at the next interface event.
+    Next,
+    /// Stop at the next event, including enter and exit events.
+    In,
+}
+
+/// Where and why a thread is stopped.
+struct Stop {
+    state: &'static StateInfo,
+    event: String,
+    event_arguments: Vec<Variable>,
+    state_variables: Vec<Variable>,
+}
+
+/// A variable shown by the debugger, with its value rendered as JSON.
+struct Variable {
+    name: String,
+    value: String,
+    vtype: &'static str,
+}
+
+/// An item of a spec that a breakpoint stops at.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Target {
+    /// The name of the machine, or `None` for function breakpoints, which apply to all machines.
+    machine: Option<&'static str>,
+    kind: TargetKind,
+    name: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TargetKind {
+    /// Stop at every event in the state.
+    State,
+    /// Stop at the events handled by the handler, named `State:event`.
+    Handler,
+    /// Stop at the interface event in any state.
+    Event,
+}
+
+/// The source map of a machine, as written by Framec.
+struct SourceMap {
+    machine: &'static MachineInfo,
+    mappings: Vec<Mapping>,
+}
+
+struct Mapping {
+    kind: TargetKind,
+    name: String,
+    source_file: Option<String>,
+    source_line: usize,
+}
+
+impl DapServer {
+    /// Start a server listening for a debugger at the given address, e.g. `127.0.0.1:4711`.
+    /// Port 0 picks a free port, see [DapServer::local_addr].
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<DapServer> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            session: Mutex::new(Session {
+                next_thread_id: 1,
+                ..Session::default()
+            }),
+            resumed: Condvar::new(),
+            client: Mutex::new(None),
+        });
+        let server = shared.clone();
+        thread::Builder::new()
+            .name("frame-dap-server".to_string())
+            .spawn(move || {
+                // a single debugger is served at a time
+                for stream in listener.incoming().flatten() {
+                    let _ = server.serve(stream);
+                    server.disconnect();
+                }
+            })?;
+        Ok(DapServer { shared, address })
+    }
+
+    /// The address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Add the source map of a machine, as written by Framec next to the generated code, so that
+    /// breakpoints can be set on the lines of its spec.
+    pub fn add_source_map(&self, machine: &'static MachineInfo, json: &str) -> Result<(), String> {
+        let map = SourceMap::parse(machine, json)?;
+        let mut session = self.shared.lock();
+        session
+            .source_maps
+            .retain(|map| map.machine.name != machine.name);
+        session.source_maps.push(map);
+        session.resolve();
+        Ok(())
+    }
+
+    /// Attach an instance of a machine generated with `thread_safe` disabled, which stops at the
+    /// breakpoints of the debugger from now on.
+    pub fn attach<M: ThreadUnsafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        let id = self.shared.add_thread(instance, machine);
+        let state = Rc::new(RefCell::new(machine.state()));
+        let (shared, current) = (self.shared.clone(), state.clone());
+        machine
+            .event_monitor_mut()
+            .add_event_sent_callback(Callback::new(CALLBACK_NAME, move |event: &M::EventPtr| {
+                let state = current.borrow().clone();
+                shared.event_sent::<M>(id, &**event, &*state)
+            }));
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(Callback::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    *state.borrow_mut() = transition.new_state.clone();
+                },
+            ));
+    }
+
+    /// Like [DapServer::attach], for a machine generated with `thread_safe` enabled.
+    pub fn attach_sync<M: ThreadSafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        let id = self.shared.add_thread(instance, machine);
+        let state = Arc::new(Mutex::new(machine.state()));
+        let (shared, current) = (self.shared.clone(), state.clone());
+        machine
+            .event_monitor_mut()
+            .add_event_sent_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| {
+                    let state = current.lock().unwrap().clone();
+                    shared.event_sent::<M>(id, &**event, &*state)
+                },
+            ));
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    *state.lock().unwrap() = transition.new_state.clone();
+                },
+            ));
+    }
+
+    /// Update the domain variables of an attached instance shown by the debugger.
+    pub fn update<M: Machine>(&self, instance: &str, machine: &M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let variables = variables(machine.variables(), machine.info().variables);
+        let mut session = self.shared.lock();
+        if let Some(thread) = session.threads.iter_mut().find(|t| t.instance == instance) {
+            thread.variables = variables;
+        }
+    }
+
+    /// Stop debugging an attached instance.
+    pub fn detach<M: Machine>(&self, instance: &str, machine: &mut M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let monitor = machine.event_monitor_mut();
+        monitor.remove_event_sent_callback(CALLBACK_NAME);
+        monitor.remove_transition_callback(CALLBACK_NAME);
+        let mut session = self.shared.lock();
+        if let Some(i) = session.threads.iter().position(|t| t.instance == instance) {
+            let id = session.threads.remove(i).id;
+            drop(session);
+            self.shared
+                .event("thread", json!({"reason": "exited", "threadId": id}));
+        }
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Session> {
+        self.session.lock().unwrap()
+    }
+
+    fn add_thread<M: Machine>(&self, instance: &str, machine: &M) -> i64
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let mut session = self.lock();
+        let id = session.next_thread_id;
+        session.next_thread_id += 1;
+        session.threads.push(Thread {
+            id,
+            instance: instance.to_string(),
+            machine: machine.info(),
+            variables: variables(machine.variables(), machine.info().variables),
+            step: None,
+            pause_requested: false,
+            stopped: None,
+        });
+        drop(session);
+        self.event("thread", json!({"reason": "started", "threadId": id}));
+        id
+    }
+
+    /// Stop the thread of an instance before it handles an event, if the debugger asks for it,
+    /// until the debugger resumes it.
+    fn event_sent<M: Machine + ?Sized>(&self, id: i64, event: &dyn Event<M>, state: &dyn State<M>)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let event_info = event.info();
+        let state_info = state.info();
+        let mut session = self.lock();
+        let reason = match session.stop_reason(id, event_info.name, state_info) {
+            Some(reason) => reason,
+            None => return,
+        };
+        let mut state_variables = variables(&*state.arguments(), state_info.parameters);
+        state_variables.extend(variables(&*state.variables(), state_info.variables));
+        let stop = Stop {
+            state: state_info,
+            event: event_info.name.to_string(),
+            event_arguments: variables(&*event.arguments(), event_info.parameters),
+            state_variables,
+        };
+        if let Some(thread) = session.thread_mut(id) {
+            thread.stopped = Some(stop);
+        }
+        drop(session);
+        self.event(
+            "stopped",
+            json!({"reason": reason, "threadId": id, "allThreadsStopped": false}),
+        );
+
+        let mut session = self.lock();
+        while session.connected && session.thread(id).map_or(false, |t| t.stopped.is_some()) {
+            session = self.resumed.wait(session).unwrap();
+        }
+    }
+
+    /// Handle the requests of a debugger until it disconnects.
+    fn serve(&self, stream: TcpStream) -> io::Result<()> {
+        // requests and responses are small, and the debugger waits for each response
+        stream.set_nodelay(true)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        *self.client.lock().unwrap() = Some(Client { stream, seq: 0 });
+        self.lock().connected = true;
+        while let Some(request) = read_message(&mut reader)? {
+            let command = request["command"].as_str().unwrap_or_default().to_string();
+            let result = self.handle(&command, &request["arguments"]);
+            let (success, body, message) = match result {
+                Ok(body) => (true, body, None),
+                Err(message) => (false, Json::Null, Some(message)),
+            };
+            let mut response = json!({
+                "type": "response",
+                "request_seq": request["seq"],
+                "success": success,
+                "command": command,
+                "body": body,
+            });
+            if let Some(message) = message {
+                response["message"] = Json::from(message);
+            }
+            self.send(response);
+            match command.as_str() {
+                "initialize" => self.event("initialized", json!({})),
+                "disconnect" => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget the breakpoints of the debugger, and resume the stopped threads.
+    fn disconnect(&self) {
+        *self.client.lock().unwrap() = None;
+        let mut session = self.lock();
+        session.connected = false;
+        session.line_breakpoints.clear();
+        session.function_breakpoints.clear();
+        session.targets.clear();
+        for thread in &mut session.threads {
+            thread.step = None;
+            thread.pause_requested = false;
+            thread.stopped = None;
+        }
+        self.resumed.notify_all();
+    }
+
+    /// Handle a request, returning the body of the response or an error message.
+    fn handle(&self, command: &str, arguments: &Json) -> Result<Json, String> {
+        let thread_id = arguments["threadId"].as_i64().unwrap_or_default();
+        let mut session = self.lock();
+        match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsFunctionBreakpoints": true,
+            })),
+            "launch" | "attach" | "configurationDone" | "setExceptionBreakpoints" => Ok(json!({})),
+            "disconnect" => Ok(json!({})),
+            "setBreakpoints" => {
+                let path = arguments["source"]["path"]
+                    .as_str()
+                    .ok_or("missing source path")?
+                    .to_string();
+                let lines: Vec<usize> = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|b| b["line"].as_u64())
+                            .map(|line| line as usize)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                session.line_breakpoints.retain(|(p, _)| *p != path);
+                session.line_breakpoints.push((path.clone(), lines.clone()));
+                session.resolve();
+                let breakpoints: Vec<Json> = lines
+                    .iter()
+                    .map(|line| match session.resolve_line(&path, *line) {
+                        Some((_, mapping)) => {
+                            json!({"verified": true, "line": mapping.source_line})
+                        }
+                        None => json!({
+                            "verified": false,
+                            "line": line,
+                            "message": "no state, handler or interface method at this line",
+                        }),
+                    })
+                    .collect();
+                Ok(json!({ "breakpoints": breakpoints }))
+            }
+            "setFunctionBreakpoints" => {
+                session.function_breakpoints = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|b| b["name"].as_str())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                session.resolve();
+                let breakpoints: Vec<Json> = session
+                    .function_breakpoints
+                    .iter()
+                    .map(|_| json!({"verified": true}))
+                    .collect();
+                Ok(json!({ "breakpoints": breakpoints }))
+            }
+            "threads" => {
+                let threads: Vec<Json> = session
+                    .threads
+                    .iter()
+                    .map(|t| json!({"id": t.id, "name": format!("{} ({})", t.instance, t.machine.name)}))
+                    .collect();
+                Ok(json!({ "threads": threads }))
+            }
+            "stackTrace" => {
+                let frames = match session.thread(thread_id) {
+                    Some(thread) => match &thread.stopped {
+                        Some(stop) => vec![session.frame(thread, stop)],
+                        None => Vec::new(),
+                    },
+                    None => return Err(format!("unknown thread {}", thread_id)),
+                };
+                Ok(json!({"stackFrames": frames, "totalFrames": frames.len()}))
+            }
+            "scopes" => {
+                // each stopped thread has a single frame, identified by the thread
+                let id = arguments["frameId"].as_i64().unwrap_or_default();
+                let scopes: Vec<Json> = ["Event", "State", "Domain"]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| {
+                        json!({
+                            "name": name,
+                            "variablesReference": id * SCOPES + i as i64 + 1,
+                            "expensive": false,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "scopes": scopes }))
+            }
+            "variables" => {
+                let reference = arguments["variablesReference"].as_i64().unwrap_or_default();
+                let thread = session
+                    .thread(reference / SCOPES)
+                    .ok_or_else(|| format!("unknown variables {}", reference))?;
+                let variables = match (reference % SCOPES, &thread.stopped) {
+                    (1, Some(stop)) => &stop.event_arguments,
+                    (2, Some(stop)) => &stop.state_variables,
+                    (3, _) => &thread.variables,
+                    _ => return Err(format!("unknown variables {}", reference)),
+                };
+                let variables: Vec<Json> = variables
+                    .iter()
+                    .map(|v| {
+                        json!({
+                            "name": v.name,
+                            "value": v.value,
+                            "type": v.vtype,
+                            "variablesReference": 0,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "variables": variables }))
+            }
+            "continue" => {
+                for thread in &mut session.threads {
+                    thread.step = None;
+                    thread.stopped = None;
+                }
+                self.resumed.notify_all();
+                Ok(json!({"allThreadsContinued": true}))
+            }
+            "next" | "stepIn" | "stepOut" => {
+                let thread = session
+                    .thread_mut(thread_id)
+                    .ok_or_else(|| format!("unknown thread {}", thread_id))?;
+                thread.step = Some(if command == "stepIn" {
+                    Step::In
+                } else {
+                    Step::Next
+                });
+                thread.stopped = None;
+                self.resumed.notify_all();
+                Ok(json!({}))
+            }
+            "pause" => {
+                let thread = session
+                    .thread_mut(thread_id)
+                    .ok_or_else(|| format!("unknown thread {}", thread_id))?;
+                thread.pause_requested = true;
+                Ok(json!({}))
+            }
+            _ => Err(format!("unsupported request `{}`", command)),
+        }
+    }
+
+    fn event(&self, event: &str, body: Json) {
+        self.send(json!({"type": "event", "event": event, "body": body}));
+    }
+
+    fn send(&self, mut message: Json) {
+        let mut client = self.client.lock().unwrap();
+        if let Some(client) = client.as_mut() {
+            client.seq += 1;
+            message["seq"] = Json::from(client.seq);
+            let _ = write_message(&mut client.stream, &message);
+        }
+    }
+}
+
+impl Session {
+    fn thread(&self, id: i64) -> Option<&Thread> {
+        self.threads.iter().find(|t| t.id == id)
+    }
+
+    fn thread_mut(&mut self, id: i64) -> Option<&mut Thread> {
+        self.threads.iter_mut().find(|t| t.id == id)
+    }
+
+    /// Whether a thread should stop before handling an event in a state, and why. The step or
+    /// pause that stops it is then done.
+    fn stop_reason(
+        &mut self,
+        id: i64,
+        event: &str,
+        state: &'static StateInfo,
+    ) -> Option<&'static str> {
+        if !self.connected {
+            return None;
+        }
+        let hit = self
+            .targets
+            .iter()
+            .any(|target| target.matches(event, state));
+        let thread = self.thread_mut(id)?;
+        let is_interface_event = !event.contains(':');
+        let reason = if thread.pause_requested {
+            "pause"
+        } else if thread.step == Some(Step::In)
+            || (thread.step == Some(Step::Next) && is_interface_event)
+        {
+            "step"
+        } else if hit {
+            "breakpoint"
+        } else {
+            return None;
+        };
+        thread.pause_requested = false;
+        thread.step = None;
+        Some(reason)
+    }
+
+    /// Resolve the line and function breakpoints to the items of the specs they stop at.
+    fn resolve(&mut self) {
+        let mut targets = Vec::new();
+        for (path, lines) in &self.line_breakpoints {
+            for line in lines {
+                if let Some((machine, mapping)) = self.resolve_line(path, *line) {
+                    targets.push(Target {
+                        machine: Some(machine.name),
+                        kind: mapping.kind,
+                        name: mapping.name.clone(),
+                    });
+                }
+            }
+        }
+        for name in &self.function_breakpoints {
+            let kind = if name.contains(':') {
+                TargetKind::Handler
+            } else if self
+                .threads
+                .iter()
+                .any(|t| t.machine.get_state(name).is_some())
+                || self
+                    .source_maps
+                    .iter()
+                    .any(|m| m.machine.get_state(name).is_some())
+            {
+                TargetKind::State
+            } else {
+                TargetKind::Event
+            };
+            targets.push(Target {
+                machine: None,
+                kind,
+                name: name.clone(),
+            });
+        }
+        self.targets = targets;
+    }
+
+    /// The innermost item of a spec at a line of its file: the state, handler or interface
+    /// method declared last on or before the line.
+    fn resolve_line(&self, path: &str, line: usize) -> Option<(&'static MachineInfo, &Mapping)> {
+        self.source_maps
+            .iter()
+            .flat_map(|map| map.mappings.iter().map(move |m| (map.machine, m)))
+            .filter(|(_, m)| {
+                m.source_line <= line
+                    && m.source_file
+                        .as_deref()
+                        .map_or(false, |file| same_file(file, path))
+            })
+            .max_by_key(|(_, m)| m.source_line)
+    }
+
+    /// The stack frame of a stopped thread: the handler of the event, or the state if there
+    /// isn't one.
+    fn frame(&self, thread: &Thread, stop: &Stop) -> Json {
+        let handler = handler_name(&stop.event, stop.state);
+        // enter and exit events are named after their state, e.g. `Locked:>`
+        let message = stop.event.rsplit(':').next().unwrap_or_default();
+        let name = format!("${} |{}|", stop.state.name, message);
+        let mapping = self
+            .source_maps
+            .iter()
+            .filter(|map| map.machine.name == thread.machine.name)
+            .flat_map(|map| map.mappings.iter())
+            .find(|m| m.name == handler && m.kind == TargetKind::Handler)
+            .or_else(|| {
+                self.source_maps
+                    .iter()
+                    .filter(|map| map.machine.name == thread.machine.name)
+                    .flat_map(|map| map.mappings.iter())
+                    .find(|m| m.name == stop.state.name && m.kind == TargetKind::State)
+            });
+        let mut frame = json!({"id": thread.id, "name": name, "line": 0, "column": 0});
+        let file = mapping
+            .and_then(|m| m.source_file.as_deref())
+            .or(thread.machine.path_str);
+        if let Some(file) = file {
+            let name = Path::new(file)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(file);
+            frame["source"] = json!({"name": name, "path": file});
+        }
+        if let Some(mapping) = mapping {
+            frame["line"] = Json::from(mapping.source_line);
+            frame["column"] = Json::from(1);
+        }
+        frame
+    }
+}
+
+impl Target {
+    fn matches(&self, event: &str, state: &'static StateInfo) -> bool {
+        if self
+            .machine
+            .map_or(false, |machine| machine != state.machine().name)
+        {
+            return false;
+        }
+        match self.kind {
+            TargetKind::State => state.name == self.name,
+            TargetKind::Handler => handler_name(event, state) == self.name,
+            TargetKind::Event => event == self.name,
+        }
+    }
+}
+
+impl SourceMap {
+    fn parse(machine: &'static MachineInfo, json: &str) -> Result<SourceMap, String> {
+        let map: Json =
+            serde_json::from_str(json).map_err(|err| format!("malformed source map: {}", err))?;
+        let mappings = map["mappings"]
+            .as_array()
+            .ok_or("malformed source map: missing mappings")?
+            .iter()
+            .map(|mapping| {
+                let kind = match mapping["kind"].as_str() {
+                    Some("state") => TargetKind::State,
+                    Some("event_handler") => TargetKind::Handler,
+                    Some("interface_method") => TargetKind::Event,
+                    _ => {
+                        return Err(format!(
+                            "malformed source map: unknown kind {}",
+                            mapping["kind"]
+                        ))
+                    }
+                };
+                Ok(Mapping {
+                    kind,
+                    name: mapping["name"].as_str().unwrap_or_default().to_string(),
+                    source_file: mapping["source_file"].as_str().map(str::to_string),
+                    source_line: mapping["source_line"].as_u64().unwrap_or_default() as usize,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(SourceMap { machine, mappings })
+    }
+}
+
+/// The name of the handler of an event in a state, in the style of source maps: `Locked:coin`
+/// for an interface event, or the name of an enter or exit event such as `Locked:>`. The handler
+/// is that of the nearest ancestor handling the event if the state doesn't.
+fn handler_name(event: &str, state: &'static StateInfo) -> String {
+    if event.contains(':') {
+        return event.to_string();
+    }
+    let handling = std::iter::once(state)
+        .chain(state.ancestors())
+        .find(|s| s.get_handler(event).is_some())
+        .unwrap_or(state);
+    format!("{}:{}", handling.name, event)
+}
+
+fn variables<E: Environment + ?Sized>(
+    environment: &E,
+    names: &'static [NameInfo],
+) -> Vec<Variable> {
+    names
+        .iter()
+        .map(|name| Variable {
+            name: name.name.to_string(),
+            value: environment
+                .lookup(name.name)
+                .map_or_else(|| "null".to_string(), |value| values::json(&*value)),
+            vtype: name.vtype,
+        })
+        .collect()
+}
+
+/// Whether two paths name the same file, e.g. a path in a source map and one sent by the
+/// debugger, which may differ in form.
+fn same_file(a: &str, b: &str) -> bool {
+    a == b
+        || match (Path::new(a).canonicalize(), Path::new(b).canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+}
+
+/// Read a message framed with a `Content-Length` header, or `None` at the end of the stream.
+fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Json>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Json) -> io::Result<()> {
+    let body = message.to_string();
+    let message = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    writer.write_all(message.as_bytes())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_messages() {
+        let mut buffer = Vec::new();
+        write_message(
+            &mut buffer,
+            &json!({"seq": 1, "type": "event", "event": "initialized"}),
+        )
+        .unwrap();
+        write_message(&mut buffer, &json!({"seq": 2, "command": "threads"})).unwrap();
+        assert!(buffer.starts_with(b"Content-Length: 46\r\n\r\n{"));
+        let mut reader = &buffer[..];
+        assert_eq!(
+            read_message(&mut reader).unwrap().unwrap()["event"],
+            "initialized"
+        );
+        assert_eq!(
+            read_message(&mut reader).unwrap().unwrap()["command"],
+            "threads"
+        );
+        assert!(read_message(&mut reader).unwrap().is_none());
+        let mut reader = &b"Content-Type: x\r\n\r\n{}"[..];
+        assert!(read_message(&mut reader).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! This module provides a server that streams the activity of running state machines to remote
+//! debugging tools over WebSocket, so that the machines of a running service can be inspected
+//! live. It is enabled by the `debug_server` feature of this crate.
+//!
+//! Instances of machines generated with `runtime_support` are attached to the server under a
+//! name, after which every event they handle and every transition they take is sent to all
+//! connected clients as a JSON text message:
+//!
+//! ```text
+//! {"type":"event","instance":"lamp","time":1700000000000,"event":"toggle","arguments":{},"return":null}
+//! {"type":"transition","instance":"lamp","time":1700000000000,"kind":"transition","event":"toggle",
+//!  "label":"","source":"Off","target":"On","state":{"name":"On","arguments":{},"variables":{}}}
+//! {"type":"snapshot","instance":"lamp","snapshot":{"machine":"Lamp","state":{...},"variables":{...}}}
+//! ```
+//!
+//! Times are in milliseconds since the Unix epoch. A client receives the latest snapshot of each
+//! attached instance when it connects.
+//!
+//! Clients can also send commands, as JSON text messages with an optional `id` that is copied to
+//! the reply:
+//!
+//! ```text
+//! {"id":1,"command":"send","instance":"lamp","event":"setLevel","arguments":["3"]}
+//! {"id":2,"command":"snapshot","instance":"lamp"}
+//! ```
+//!
+//! Machines are not shared with the server, so commands are queued until the thread that owns
+//! the instance calls [DebugServer::poll], e.g. from its event loop. Events are sent through the
+//! [Dispatch] trait, so the machine must also be generated with `runtime_dispatch`, and their
+//! arguments are parsed according to the declared types of the parameters. The reply to a
+//! command is `{"type":"reply","id":1,"ok":true,"return":null}`, or has an `error` message
+//! instead of the return value, and a fresh snapshot of the instance is sent to all clients
+//! after each event.
+//!
+//! ```ignore
+//! use frame_runtime::debug_server::DebugServer;
+//!
+//! let server = DebugServer::bind("127.0.0.1:9229")?;
+//! let mut lamp = Lamp::new();
+//! server.attach("lamp", &mut lamp);
+//! loop {
+//!     server.poll("lamp", &mut lamp);
+//!     // ...
+//! }
+//! ```
+
+use crate::callback::{Callback, CallbackSend};
+use crate::dispatch::Dispatch;
+use crate::env::Environment;
+use crate::event::Event;
+use crate::machine::{Machine, State, ThreadSafeMachine, ThreadUnsafeMachine};
+use crate::transition::Transition;
+use crate::values;
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+use std::io::{self, ErrorKind};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tungstenite::{Message, WebSocket};
+
+/// The name of the callbacks registered with the event monitors of attached instances.
+const CALLBACK_NAME: &str = "debug_server";
+
+/// How often the threads of the server check for new connections, messages and shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// A WebSocket server streaming the events, transitions and snapshots of the attached instances
+/// to its clients. The server stops when it is dropped.
+pub struct DebugServer {
+    shared: Arc<Shared>,
+    local_addr: SocketAddr,
+}
+
+/// The state shared by the server, its threads and the callbacks of the attached instances.
+#[derive(Default)]
+struct Shared {
+    // queues of the messages to send to each connected client
+    clients: Mutex<Vec<Sender<String>>>,
+    // latest snapshot of each attached instance, by name
+    snapshots: Mutex<BTreeMap<String, String>>,
+    // commands waiting for the thread of their instance to poll the server
+    commands: Mutex<Vec<Command>>,
+    stopped: AtomicBool,
+}
+
+/// A command from a client to an attached instance.
+struct Command {
+    id: Json,
+    instance: String,
+    request: Request,
+    // queue of the client that sent the command, for the reply
+    reply_to: Sender<String>,
+}
+
+enum Request {
+    Send {
+        event: String,
+        arguments: Vec<String>,
+    },
+    Snapshot,
+}
+
+impl DebugServer {
+    /// Start a server listening on the given address, e.g. `127.0.0.1:9229`. Use port 0 to let
+    /// the system choose a free port, and [DebugServer::local_addr] to find it.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<DebugServer> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared::default());
+        let server_shared = shared.clone();
+        thread::Builder::new()
+            .name("frame-debug-server".to_string())
+            .spawn(move || accept(listener, server_shared))?;
+        Ok(DebugServer { shared, local_addr })
+    }
+
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Receive the messages sent to clients in this process, e.g. for a monitor running in the
+    /// same service, starting with the latest snapshot of each attached instance.
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        self.shared.add_client(sender);
+        receiver
+    }
+
+    /// Stream the activity of an instance of a machine generated with `thread_safe` disabled
+    /// under the given name, which identifies it in messages and commands.
+    pub fn attach<M: ThreadUnsafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        self.shared.update_snapshot(instance, machine);
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_event_handled_callback(Callback::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| shared.broadcast(event_message::<M>(&name, &**event)),
+            ));
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(Callback::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    shared.broadcast(transition_message(&name, transition))
+                },
+            ));
+    }
+
+    /// Like [DebugServer::attach], for a machine generated with `thread_safe` enabled.
+    pub fn attach_sync<M: ThreadSafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        self.shared.update_snapshot(instance, machine);
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_event_handled_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| shared.broadcast(event_message::<M>(&name, &**event)),
+            ));
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    shared.broadcast(transition_message(&name, transition))
+                },
+            ));
+    }
+
+    /// Stop streaming the activity of an attached instance.
+    pub fn detach<M: Machine>(&self, instance: &str, machine: &mut M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let monitor = machine.event_monitor_mut();
+        monitor.remove_event_handled_callback(CALLBACK_NAME);
+        monitor.remove_transition_callback(CALLBACK_NAME);
+        self.shared.snapshots.lock().unwrap().remove(instance);
+    }
+
+    /// Handle the commands sent to an attached instance since the last call, from the thread
+    /// that owns it. Returns the number of commands handled.
+    pub fn poll<M: Machine + Dispatch>(&self, instance: &str, machine: &mut M) -> usize
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let commands: Vec<Command> = {
+            let mut pending = self.shared.commands.lock().unwrap();
+            let (commands, others) = pending.drain(..).partition(|c| c.instance == instance);
+            *pending = others;
+            commands
+        };
+        for command in &commands {
+            let reply = match &command.request {
+                Request::Send { event, arguments } => {
+                    let result = send(machine, event, arguments);
+                    self.shared
+                        .broadcast(self.shared.update_snapshot(instance, machine));
+                    result.map(|value| format!("\"return\":{}", value))
+                }
+                Request::Snapshot => Ok(format!("\"snapshot\":{}", values::snapshot_json(machine))),
+            };
+            let _ = command.reply_to.send(reply_message(&command.id, reply));
+        }
+        commands.len()
+    }
+}
+
+impl Drop for DebugServer {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Shared {
+    /// Send the latest snapshots to a new client and register it for the messages to come.
+    fn add_client(&self, client: Sender<String>) {
+        // register under the lock of the snapshots, so that no update is missed in between
+        let snapshots = self.snapshots.lock().unwrap();
+        for snapshot in snapshots.values() {
+            let _ = client.send(snapshot.clone());
+        }
+        self.clients.lock().unwrap().push(client);
+    }
+
+    /// Send a message to all connected clients, forgetting those that disconnected.
+    fn broadcast(&self, message: String) {
+        self.clients
+            .lock()
+            .unwrap()
+            .retain(|client| client.send(message.clone()).is_ok());
+    }
+
+    /// Take a snapshot of an instance for the clients that connect later, and return its
+    /// message.
+    fn update_snapshot<M: Machine>(&self, instance: &str, machine: &M) -> String
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let message = format!(
+            "{{\"type\":\"snapshot\",\"instance\":{},\"snapshot\":{}}}",
+            values::json_string(instance),
+            values::snapshot_json(machine)
+        );
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(instance.to_string(), message.clone());
+        message
+    }
+
+    /// Queue a command received from a client, or reply with an error if it is invalid.
+    fn queue(&self, text: &str, reply_to: &Sender<String>) {
+        let command: Json = match serde_json::from_str(text) {
+            Ok(command) => command,
+            Err(err) => {
+                let error = format!("invalid command: {}", err);
+                let _ = reply_to.send(reply_message(&Json::Null, Err(error)));
+                return;
+            }
+        };
+        let id = command.get("id").cloned().unwrap_or(Json::Null);
+        match self.parse_command(&command) {
+            Ok((instance, request)) => self.commands.lock().unwrap().push(Command {
+                id,
+                instance,
+                request,
+                reply_to: reply_to.clone(),
+            }),
+            Err(error) => {
+                let _ = reply_to.send(reply_message(&id, Err(error)));
+            }
+        }
+    }
+
+    fn parse_command(&self, command: &Json) -> Result<(String, Request), String> {
+        let instance = match command.get("instance").and_then(Json::as_str) {
+            Some(instance) if self.snapshots.lock().unwrap().contains_key(instance) => instance,
+            Some(instance) => return Err(format!("unknown instance `{}`", instance)),
+            None => return Err("the command has no instance".to_string()),
+        };
+        let request = match command.get("command").and_then(Json::as_str) {
+            Some("send") => {
+                let event = match command.get("event").and_then(Json::as_str) {
+                    Some(event) => event.to_string(),
+                    None => return Err("the send command has no event".to_string()),
+                };
+                let arguments = match command.get("arguments") {
+                    None => Vec::new(),
+                    Some(Json::Array(arguments)) => arguments
+                        .iter()
+                        .map(|argument| match argument {
+                            Json::String(text) => text.clone(),
+                            other => other.to_string(),
+                        })
+                        .collect(),
+                    Some(_) => return Err("the arguments must be an array".to_string()),
+                };
+                Request::Send { event, arguments }
+            }
+            Some("snapshot") => Request::Snapshot,
+            Some(other) => return Err(format!("unknown command `{}`", other)),
+            None => return Err("the message has no command".to_string()),
+        };
+        Ok((instance.to_string(), request))
+    }
+}
+
+/// Accept connections until the server is dropped, serving each client on its own thread.
+fn accept(listener: TcpListener, shared: Arc<Shared>) {
+    while !shared.stopped.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let shared = shared.clone();
+                let _ = thread::Builder::new()
+                    .name("frame-debug-client".to_string())
+                    .spawn(move || serve(stream, shared));
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(_) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Forward the messages for a client and queue its commands until it disconnects or the server
+/// is dropped.
+fn serve(stream: TcpStream, shared: Arc<Shared>) {
+    if stream.set_nonblocking(false).is_err() {
+        return;
+    }
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(_) => return,
+    };
+    if socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .is_err()
+    {
+        return;
+    }
+    let (sender, receiver) = mpsc::channel();
+    shared.add_client(sender.clone());
+
+    while !shared.stopped.load(Ordering::Relaxed) {
+        while let Ok(message) = receiver.try_recv() {
+            if socket.write(Message::Text(message)).is_err() {
+                return;
+            }
+        }
+        if !flush(&mut socket) {
+            return;
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => shared.queue(&text, &sender),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+    let _ = socket.close(None);
+    flush(&mut socket);
+}
+
+/// Send the buffered messages, returning whether the connection is still open.
+fn flush(socket: &mut WebSocket<TcpStream>) -> bool {
+    match socket.flush() {
+        Ok(()) => true,
+        Err(tungstenite::Error::Io(err)) => err.kind() == ErrorKind::WouldBlock,
+        Err(_) => false,
+    }
+}
+
+/// Send an event to an instance, given its name and the text of its arguments, and render the
+/// return value.
+fn send<M: Machine + Dispatch>(
+    machine: &mut M,
+    event: &str,
+    arguments: &[String],
+) -> Result<String, String>
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    let info = match machine.info().interface.iter().find(|m| m.name == event) {
+        Some(info) => info,
+        None => return Err(format!("unknown method `{}`", event)),
+    };
+    let mut values = Vec::new();
+    for (parameter, text) in info.parameters.iter().zip(arguments) {
+        match values::parse(text, parameter.vtype) {
+            Some(value) => values.push(value),
+            None => {
+                return Err(format!(
+                    "argument `{}` of `{}` is not a valid `{}`: {}",
+                    parameter.name, event, parameter.vtype, text
+                ))
+            }
+        }
+    }
+    // extra arguments are passed on, for the error of the dispatch
+    for _ in info.parameters.len()..arguments.len() {
+        values.push(Box::new(()));
+    }
+    match machine.dispatch(event, values) {
+        Ok(Some(value)) => Ok(values::json(&*value)),
+        Ok(None) => Ok("null".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn reply_message(id: &Json, result: Result<String, String>) -> String {
+    match result {
+        Ok(fields) => format!(
+            "{{\"type\":\"reply\",\"id\":{},\"ok\":true,{}}}",
+            id, fields
+        ),
+        Err(error) => format!(
+            "{{\"type\":\"reply\",\"id\":{},\"ok\":false,\"error\":{}}}",
+            id,
+            values::json_string(&error)
+        ),
+    }
+}
+
+fn event_message<M: Machine>(instance: &str, event: &<M::EventPtr as Deref>::Target) -> String
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    let info = event.info();
+    let return_value = event
+        .return_value()
+        .map_or_else(|| "null".to_string(), |value| values::json(&*value));
+    format!(
+        "{{\"type\":\"event\",\"instance\":{},\"time\":{},\"event\":{},\"arguments\":{},\"return\":{}}}",
+        values::json_string(instance),
+        now(),
+        values::json_string(info.name),
+        values::environment_json(&*event.arguments(), info.parameters),
+        return_value
+    )
+}
+
+fn transition_message<M: Machine>(instance: &str, transition: &Transition<M>) -> String
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    let info = transition.info;
+    format!(
+        "{{\"type\":\"transition\",\"instance\":{},\"time\":{},\"kind\":{},\"event\":{},\"label\":{},\"source\":{},\"target\":{},\"state\":{}}}",
+        values::json_string(instance),
+        now(),
+        values::json_string(if info.is_change_state() { "change_state" } else { "transition" }),
+        values::json_string(info.event.name),
+        values::json_string(info.label),
+        values::json_string(info.source.name),
+        values::json_string(info.target.name),
+        values::state_json::<M, _>(&*transition.new_state),
+    )
+}
+
+/// Milliseconds since the Unix epoch.
+fn now() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis())
+}
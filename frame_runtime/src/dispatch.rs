@@ -0,0 +1,191 @@
+//! This module defines a trait for calling the interface methods of a running state machine by
+//! name, for tools and host applications that drive a machine without knowing its Rust type.
+//!
+//! The trait is implemented by the code generated by Frame when both the `runtime_support` and
+//! `runtime_dispatch` features are enabled. The types of the parameters and return values of the
+//! interface must then be `'static`, since they are passed as `Box<dyn Any>`.
+
+use std::any::Any;
+use std::fmt;
+
+/// A state machine whose interface methods can be called by name.
+pub trait Dispatch {
+    /// Call the interface method with the given name, e.g. `deposit`, as it is declared in the
+    /// spec. There must be an argument of the declared type for each parameter, except for
+    /// trailing parameters with default values. The return value is `None` for methods without a
+    /// return type.
+    fn dispatch(
+        &mut self,
+        method: &str,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Option<Box<dyn Any>>, DispatchError>;
+}
+
+/// The reasons a call by name may fail. The machine is not changed by a failed call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DispatchError {
+    /// The machine has no interface method with the given name.
+    UnknownMethod(String),
+
+    /// The number of arguments doesn't match the parameters of the method. The expected number
+    /// is that of the parameters up to the first one missing an argument, or of all the
+    /// parameters if there are too many arguments.
+    ArgumentCount {
+        method: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// An argument is not of the declared type of its parameter.
+    ArgumentType {
+        method: String,
+        parameter: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DispatchError::UnknownMethod(method) => write!(f, "unknown method `{}`", method),
+            DispatchError::ArgumentCount {
+                method,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` takes {} argument(s) but {} were given",
+                method, expected, found
+            ),
+            DispatchError::ArgumentType {
+                method,
+                parameter,
+                expected,
+            } => write!(
+                f,
+                "argument `{}` of `{}` must be of type `{}`",
+                parameter, method, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DispatchError {}
+
+/// The arguments of a call by name, which are taken one by one in the order of the parameters.
+/// This is intended for use by generated code.
+pub struct DispatchArgs {
+    method: String,
+    arguments: std::vec::IntoIter<Box<dyn Any>>,
+    count: usize,
+    // number of parameters taken so far
+    taken: usize,
+}
+
+impl DispatchArgs {
+    pub fn new(method: &str, arguments: Vec<Box<dyn Any>>) -> DispatchArgs {
+        DispatchArgs {
+            method: method.to_string(),
+            count: arguments.len(),
+            arguments: arguments.into_iter(),
+            taken: 0,
+        }
+    }
+
+    /// Take the argument of a required parameter.
+    pub fn required<T: Any>(
+        &mut self,
+        parameter: &'static str,
+        vtype: &'static str,
+    ) -> Result<T, DispatchError> {
+        match self.optional(parameter, vtype)? {
+            Some(value) => Ok(value),
+            None => Err(self.count_error()),
+        }
+    }
+
+    /// Take the argument of a parameter with a default value, which may be missing.
+    pub fn optional<T: Any>(
+        &mut self,
+        parameter: &'static str,
+        vtype: &'static str,
+    ) -> Result<Option<T>, DispatchError> {
+        self.taken += 1;
+        match self.arguments.next() {
+            Some(argument) => match argument.downcast::<T>() {
+                Ok(value) => Ok(Some(*value)),
+                Err(_) => Err(DispatchError::ArgumentType {
+                    method: self.method.clone(),
+                    parameter,
+                    expected: vtype,
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Check that all the arguments were taken.
+    pub fn finish(self) -> Result<(), DispatchError> {
+        if self.count > self.taken {
+            return Err(self.count_error());
+        }
+        Ok(())
+    }
+
+    fn count_error(&self) -> DispatchError {
+        DispatchError::ArgumentCount {
+            method: self.method.clone(),
+            expected: self.taken,
+            found: self.count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(arguments: Vec<Box<dyn Any>>) -> DispatchArgs {
+        DispatchArgs::new("deposit", arguments)
+    }
+
+    #[test]
+    fn takes_arguments_in_order() {
+        let mut arguments = args(vec![Box::new(5), Box::new(String::from("cash"))]);
+        assert_eq!(arguments.required::<i32>("amount", "i32"), Ok(5));
+        assert_eq!(
+            arguments.optional::<String>("memo", "String"),
+            Ok(Some(String::from("cash")))
+        );
+        assert_eq!(arguments.optional::<bool>("notify", "bool"), Ok(None));
+        assert_eq!(arguments.finish(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_wrong_arguments() {
+        let mut arguments = args(vec![Box::new(5u8)]);
+        assert_eq!(
+            arguments.required::<i32>("amount", "i32"),
+            Err(DispatchError::ArgumentType {
+                method: String::from("deposit"),
+                parameter: "amount",
+                expected: "i32",
+            })
+        );
+
+        let mut arguments = args(vec![]);
+        let err = arguments.required::<i32>("amount", "i32").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`deposit` takes 1 argument(s) but 0 were given"
+        );
+
+        let mut arguments = args(vec![Box::new(5), Box::new(6)]);
+        arguments.required::<i32>("amount", "i32").unwrap();
+        let err = arguments.finish().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`deposit` takes 1 argument(s) but 2 were given"
+        );
+    }
+}
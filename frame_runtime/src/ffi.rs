@@ -0,0 +1,323 @@
+//! This module exposes running state machines through a C ABI, so that host applications written
+//! in other languages can inspect and drive the Rust state machines they embed. It is enabled by
+//! the `ffi` feature of this crate.
+//!
+//! A machine is passed to the host as an opaque `FrameMachine` pointer, created by a constructor
+//! the embedding crate exports for each of its machines:
+//!
+//! ```ignore
+//! use frame_runtime::ffi::FrameMachine;
+//!
+//! #[no_mangle]
+//! pub extern "C" fn account_new() -> *mut FrameMachine {
+//!     FrameMachine::into_raw(Account::new())
+//! }
+//! ```
+//!
+//! The functions below then work with machines of any type. The machine must be generated with
+//! the `runtime_support` and `runtime_dispatch` features, since events are sent to it through
+//! the [Dispatch] trait. The embedding crate is built as a `cdylib` or `staticlib`, and
+//! `include/frame_runtime.h` declares the functions for C and C++ hosts.
+//!
+//! Values cross the ABI as text: arguments are parsed according to the declared types of the
+//! parameters, which must be `bool`, `char`, a primitive integer or float type, or `String`, and
+//! the values of variables and the return values of events are rendered as JSON. Values of other
+//! types are rendered as `null`.
+//!
+//! Strings returned to the host are owned by it and must be released with `frame_string_free`.
+//! A `FrameMachine` is not thread safe; the host must not use one from several threads at once.
+
+use crate::dispatch::{Dispatch, DispatchError};
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::MachineInfo;
+use crate::machine::{Machine, State};
+use crate::values;
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::ops::Deref;
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// The call succeeded.
+pub const FRAME_OK: c_int = 0;
+/// A pointer argument was null, or a string wasn't valid UTF-8.
+pub const FRAME_INVALID_ARGUMENT: c_int = -1;
+/// The machine has no interface method with the given name.
+pub const FRAME_UNKNOWN_METHOD: c_int = -2;
+/// The arguments don't match the parameters of the method.
+pub const FRAME_BAD_ARGUMENTS: c_int = -3;
+/// The machine panicked while handling the event. It should not be used any further.
+pub const FRAME_PANIC: c_int = -4;
+
+thread_local! {
+    // message of the most recent failed call on this thread
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// A running state machine of any type, as seen by the host.
+pub struct FrameMachine {
+    machine: Box<dyn Introspect>,
+}
+
+impl FrameMachine {
+    /// Wrap a machine for the host. The pointer is released with `frame_machine_free`.
+    pub fn into_raw<M>(machine: M) -> *mut FrameMachine
+    where
+        M: Machine + Dispatch + 'static,
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        Box::into_raw(Box::new(FrameMachine {
+            machine: Box::new(machine),
+        }))
+    }
+}
+
+/// The parts of a machine used by the functions of the ABI, which can be used as a trait object
+/// unlike [Machine].
+trait Introspect {
+    fn info(&self) -> &'static MachineInfo;
+    fn state_name(&self) -> &'static str;
+    fn snapshot(&self) -> String;
+    fn variables(&self) -> String;
+    fn dispatch(
+        &mut self,
+        method: &str,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Option<Box<dyn Any>>, DispatchError>;
+}
+
+impl<M> Introspect for M
+where
+    M: Machine + Dispatch,
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    fn info(&self) -> &'static MachineInfo {
+        Machine::info(self)
+    }
+
+    fn state_name(&self) -> &'static str {
+        self.state().info().name
+    }
+
+    fn snapshot(&self) -> String {
+        values::snapshot_json(self)
+    }
+
+    fn variables(&self) -> String {
+        values::variables_json(self)
+    }
+
+    fn dispatch(
+        &mut self,
+        method: &str,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Option<Box<dyn Any>>, DispatchError> {
+        Dispatch::dispatch(self, method, arguments)
+    }
+}
+
+/// The name of the machine's system, e.g. `Account`.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_name(machine: *const FrameMachine) -> *mut c_char {
+    match machine.as_ref() {
+        Some(machine) => into_c_string(machine.machine.info().name.to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// The name of the current state of the machine, e.g. `Open`.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_state(machine: *const FrameMachine) -> *mut c_char {
+    match machine.as_ref() {
+        Some(machine) => into_c_string(machine.machine.state_name().to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// The values of the domain variables of the machine, as a JSON object, e.g. `{"balance":5}`.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_variables(machine: *const FrameMachine) -> *mut c_char {
+    match machine.as_ref() {
+        Some(machine) => into_c_string(machine.machine.variables()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// A snapshot of the machine as a JSON object, with the name of the machine, the name,
+/// arguments and variables of the current state, and the domain variables, e.g.
+/// `{"machine":"Account","state":{"name":"Open","arguments":{},"variables":{}},"variables":{"balance":5}}`.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_snapshot(machine: *const FrameMachine) -> *mut c_char {
+    match machine.as_ref() {
+        Some(machine) => into_c_string(machine.machine.snapshot()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Call the interface method with the given name, with `argument_count` arguments given as
+/// text. On success, the return value is rendered as JSON into `*result`, or `null` for methods
+/// without a return type, unless `result` is null. On failure, one of the error codes above is
+/// returned and `frame_last_error` describes the error.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed,
+/// `method` must be null or a nul-terminated string, and `arguments` must point to
+/// `argument_count` nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_dispatch(
+    machine: *mut FrameMachine,
+    method: *const c_char,
+    arguments: *const *const c_char,
+    argument_count: usize,
+    result: *mut *mut c_char,
+) -> c_int {
+    let machine = match machine.as_mut() {
+        Some(machine) => &mut machine.machine,
+        None => return fail(FRAME_INVALID_ARGUMENT, "the machine is null"),
+    };
+    let method = match c_str(method) {
+        Some(method) => method,
+        None => {
+            return fail(
+                FRAME_INVALID_ARGUMENT,
+                "the method name is not a valid string",
+            )
+        }
+    };
+    let info = match machine
+        .info()
+        .interface
+        .iter()
+        .find(|info| info.name == method)
+    {
+        Some(info) => info,
+        None => {
+            let err = DispatchError::UnknownMethod(method.to_string());
+            return fail(FRAME_UNKNOWN_METHOD, &err.to_string());
+        }
+    };
+    if argument_count > 0 && arguments.is_null() {
+        return fail(FRAME_INVALID_ARGUMENT, "the arguments are null");
+    }
+    if argument_count > info.parameters.len() {
+        let err = DispatchError::ArgumentCount {
+            method: method.to_string(),
+            expected: info.parameters.len(),
+            found: argument_count,
+        };
+        return fail(FRAME_BAD_ARGUMENTS, &err.to_string());
+    }
+
+    let mut values = Vec::new();
+    for (i, parameter) in info.parameters.iter().take(argument_count).enumerate() {
+        let text = match c_str(*arguments.add(i)) {
+            Some(text) => text,
+            None => {
+                let msg = format!("argument `{}` is not a valid string", parameter.name);
+                return fail(FRAME_INVALID_ARGUMENT, &msg);
+            }
+        };
+        match values::parse(text, parameter.vtype) {
+            Some(value) => values.push(value),
+            None => {
+                let msg = format!(
+                    "argument `{}` of `{}` is not a valid `{}`: {}",
+                    parameter.name, method, parameter.vtype, text
+                );
+                return fail(FRAME_BAD_ARGUMENTS, &msg);
+            }
+        }
+    }
+
+    match panic::catch_unwind(AssertUnwindSafe(|| machine.dispatch(method, values))) {
+        Ok(Ok(value)) => {
+            if !result.is_null() {
+                let json = value.map_or_else(|| "null".to_string(), |value| values::json(&*value));
+                *result = into_c_string(json);
+            }
+            FRAME_OK
+        }
+        Ok(Err(err @ DispatchError::UnknownMethod(_))) => {
+            fail(FRAME_UNKNOWN_METHOD, &err.to_string())
+        }
+        Ok(Err(err)) => fail(FRAME_BAD_ARGUMENTS, &err.to_string()),
+        Err(_) => fail(
+            FRAME_PANIC,
+            &format!("the machine panicked handling `{}`", method),
+        ),
+    }
+}
+
+/// Release a machine returned by a constructor.
+///
+/// # Safety
+///
+/// `machine` must be null or a pointer returned by `FrameMachine::into_raw` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_machine_free(machine: *mut FrameMachine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
+
+/// The message of the most recent failed call on this thread, or null if there is none.
+#[no_mangle]
+pub extern "C" fn frame_last_error() -> *mut c_char {
+    LAST_ERROR.with(|last_error| match &*last_error.borrow() {
+        Some(msg) => into_c_string(msg.clone()),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Release a string returned by one of the functions above.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by one of the functions above that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn frame_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+fn fail(code: c_int, msg: &str) -> c_int {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(msg.to_string()));
+    code
+}
+
+unsafe fn c_str<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    // names and JSON don't contain nul characters, which are escaped in JSON strings
+    CString::new(string).map_or(ptr::null_mut(), CString::into_raw)
+}
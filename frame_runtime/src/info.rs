@@ -230,10 +230,13 @@ impl StateInfo {
 }
 
 /// Is this a standard transition or a change-state transition (which bypasses enter/exit events)?
+/// Deep-history transitions are standard transitions whose actual target is the most recently
+/// active descendant of the `target` state, if there is one.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum TransitionKind {
     ChangeState,
     Transition,
+    DeepHistory,
 }
 
 impl fmt::Display for TransitionKind {
@@ -241,6 +244,7 @@ impl fmt::Display for TransitionKind {
         match self {
             TransitionKind::ChangeState => write!(f, "->>"),
             TransitionKind::Transition => write!(f, "->"),
+            TransitionKind::DeepHistory => write!(f, "->H*"),
         }
     }
 }
@@ -282,9 +286,15 @@ impl TransitionInfo {
         self.kind == TransitionKind::ChangeState
     }
 
-    /// Is this a standard transition (as opposed to a change-state)?
+    /// Is this a standard transition (as opposed to a change-state)? Deep-history transitions
+    /// are standard transitions.
     pub fn is_transition(&self) -> bool {
-        self.kind == TransitionKind::Transition
+        self.kind != TransitionKind::ChangeState
+    }
+
+    /// Is this a transition to the deep history of the target state?
+    pub fn is_deep_history(&self) -> bool {
+        self.kind == TransitionKind::DeepHistory
     }
 }
 
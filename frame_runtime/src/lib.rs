@@ -83,19 +83,92 @@
 //! registering callbacks for a state machine generated with `thread_safe=false`, and the
 //! `demo::sync` tests for examples for a machine with `thread_safe=true`.
 //!
+//! # Property testing
+//!
+//! With the `proptest` feature of this crate enabled, the [proptest](crate::proptest) module
+//! derives [proptest](https://docs.rs/proptest) strategies generating arbitrary sequences of calls
+//! to the interface of a machine from its [MachineInfo], for testing invariants of the machine.
+//!
+//! # Coverage
+//!
+//! The [coverage] module computes sequences of events that take every transition of a machine
+//! from its [MachineInfo], and the [recorder] module measures the states and transitions that a
+//! test suite actually exercises, aggregating the coverage recorded by each test into a report
+//! per machine.
+//!
+//! # Traces
+//!
+//! The [trace] module defines the `.frmtrace` file format for recorded runs of a machine. A
+//! [trace::TraceWriter] attached to a running machine records its events, transitions and
+//! snapshots, and a [trace::Trace] read back from a file can be replayed against another instance
+//! of the machine, or by the simulator of Framec.
+//!
+//! # Dispatch by name
+//!
+//! When the `runtime_dispatch` feature is enabled along with `runtime_support`, the generated
+//! machine implements the [Dispatch] trait, whose `dispatch` method calls an interface method
+//! given its name and its arguments as `Box<dyn Any>`, for tools that drive machines of types
+//! they don't know. With the `ffi` feature of this crate, the [ffi](crate::ffi) module builds on
+//! it to expose machines to host applications through a C ABI, and with the `python` feature,
+//! the [python](crate::python) module exposes them to Python through PyO3, along with their
+//! histories and coverage.
+//!
+//! # Actors
+//!
+//! With the `actor` feature of this crate, the [actor](crate::actor) module hosts machines as
+//! actors on a tokio runtime, each handling the events of its mailbox in a task of its own, with
+//! replies carrying the return values of the interface methods.
+//!
+//! # Live debugging
+//!
+//! With the `debug_server` feature of this crate, the [debug_server](crate::debug_server) module
+//! streams the events, transitions and snapshots of the attached machines as JSON over WebSocket,
+//! and queues commands from its clients that send events to them. With the `dap` feature, the
+//! [dap](crate::dap) module lets a debugger such as that of VS Code set breakpoints on the
+//! states and handlers of a spec, step the attached machines event by event, and inspect their
+//! variables, through the Debug Adapter Protocol.
+//!
+//! # Metrics
+//!
+//! With the `metrics` feature of this crate, the [metrics](crate::metrics) module collects the
+//! current states, transition counts and state dwell times of the attached machines, and exports
+//! them to Prometheus from a scrape endpoint.
+//!
 //! [bounds-rfc]: https://github.com/rust-lang/rust/issues/44491
 //! [smcat]: https://github.com/sverweij/state-machine-cat
 
+#[cfg(feature = "actor")]
+pub mod actor;
 pub mod callback;
+pub mod coverage;
+#[cfg(feature = "dap")]
+pub mod dap;
+#[cfg(feature = "debug_server")]
+pub mod debug_server;
+pub mod dispatch;
 pub mod env;
 pub mod event;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod history;
 pub mod info;
 pub mod machine;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod recorder;
 pub mod smcat;
+#[cfg(feature = "proptest")]
+pub mod stress;
+pub mod trace;
 pub mod transition;
+mod values;
 
 pub use crate::callback::*;
+pub use crate::dispatch::*;
 pub use crate::env::*;
 pub use crate::event::*;
 pub use crate::history::*;
@@ -0,0 +1,439 @@
+//! This module exports metrics of running state machines to [Prometheus](https://prometheus.io).
+//! It is enabled by the `metrics` feature of this crate.
+//!
+//! Instances of machines generated with `runtime_support` are attached to a [MetricsRegistry]
+//! under a name, after which the registry tracks the state each instance is in, counts the
+//! transitions it takes, and measures how long it stays in each state. The metrics of all
+//! attached instances are rendered in the Prometheus text format as three metric families,
+//! labeled with the machine and the instance:
+//!
+//! ```text
+//! # TYPE frame_machine_state gauge
+//! frame_machine_state{machine="Lamp",instance="hall",state="On"} 1
+//! # TYPE frame_machine_transitions_total counter
+//! frame_machine_transitions_total{machine="Lamp",instance="hall",event="toggle",source="Off",target="On"} 3
+//! # TYPE frame_machine_state_dwell_seconds histogram
+//! frame_machine_state_dwell_seconds_bucket{machine="Lamp",instance="hall",state="Off",le="0.005"} 1
+//! ```
+//!
+//! The state gauge has a series for every state of the machine, which is `1` for the state the
+//! instance is in and `0` for the others. A state's dwell time is observed when the instance
+//! leaves it, so the time spent in the current state is not part of the histogram yet.
+//!
+//! [MetricsRegistry::serve] starts a scrape endpoint serving the metrics at `/metrics` over
+//! HTTP, and [MetricsRegistry::render] returns them for an existing HTTP server.
+//!
+//! ```ignore
+//! use frame_runtime::metrics::MetricsRegistry;
+//!
+//! let registry = MetricsRegistry::new();
+//! let _server = registry.serve("0.0.0.0:9464")?;
+//! let mut hall = Lamp::new();
+//! registry.attach("hall", &mut hall);
+//! ```
+
+use crate::callback::{Callback, CallbackSend};
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::{MachineInfo, TransitionInfo};
+use crate::machine::{Machine, State, ThreadSafeMachine, ThreadUnsafeMachine};
+use crate::transition::Transition;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The name of the callbacks registered with the event monitors of attached instances.
+const CALLBACK_NAME: &str = "metrics_registry";
+
+/// The default upper bounds of the buckets of the dwell time histograms, in seconds. These are
+/// the default buckets of the Prometheus client libraries.
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// How often the thread of a [MetricsServer] checks for new connections and shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long a [MetricsServer] waits for the request of a client.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Collects the metrics of the attached instances. Clones of a registry share its instances, so
+/// one registry can collect the metrics of every machine of a service.
+#[derive(Clone)]
+pub struct MetricsRegistry {
+    shared: Arc<Mutex<Registry>>,
+}
+
+struct Registry {
+    buckets: Vec<f64>,
+    // metrics of each attached instance, by name
+    instances: BTreeMap<String, InstanceMetrics>,
+}
+
+struct InstanceMetrics {
+    machine: &'static MachineInfo,
+    state: &'static str,
+    entered: Instant,
+    // counts by event, source and target
+    transitions: BTreeMap<(&'static str, &'static str, &'static str), u64>,
+    // dwell times by state
+    dwell: BTreeMap<&'static str, Histogram>,
+}
+
+/// A histogram of observations, with the count of observations in each bucket, not including
+/// those of the lower buckets.
+struct Histogram {
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        MetricsRegistry::new()
+    }
+}
+
+impl MetricsRegistry {
+    /// Create a registry measuring dwell times with the [DEFAULT_BUCKETS].
+    pub fn new() -> MetricsRegistry {
+        MetricsRegistry::with_buckets(DEFAULT_BUCKETS)
+    }
+
+    /// Create a registry measuring dwell times with the given upper bounds of the buckets of the
+    /// histograms, in seconds and in increasing order. An implicit `+Inf` bucket follows them.
+    pub fn with_buckets(buckets: &[f64]) -> MetricsRegistry {
+        MetricsRegistry {
+            shared: Arc::new(Mutex::new(Registry {
+                buckets: buckets.to_vec(),
+                instances: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Collect the metrics of an instance of a machine generated with `thread_safe` disabled,
+    /// under the given name. Attaching another instance under the same name replaces its
+    /// metrics.
+    pub fn attach<M: ThreadUnsafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        self.start(instance, machine);
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(Callback::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    shared.lock().unwrap().take(&name, transition.info)
+                },
+            ));
+    }
+
+    /// Like [MetricsRegistry::attach], for a machine generated with `thread_safe` enabled.
+    pub fn attach_sync<M: ThreadSafeMachine + 'static>(&self, instance: &str, machine: &mut M) {
+        self.start(instance, machine);
+        let (shared, name) = (self.shared.clone(), instance.to_string());
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| {
+                    shared.lock().unwrap().take(&name, transition.info)
+                },
+            ));
+    }
+
+    /// Stop collecting the metrics of an attached instance, and remove them from the registry.
+    pub fn detach<M: Machine>(&self, instance: &str, machine: &mut M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        machine
+            .event_monitor_mut()
+            .remove_transition_callback(CALLBACK_NAME);
+        self.shared.lock().unwrap().instances.remove(instance);
+    }
+
+    /// Start measuring an instance in its current state.
+    fn start<M: Machine>(&self, instance: &str, machine: &M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let metrics = InstanceMetrics {
+            machine: machine.info(),
+            state: machine.state().info().name,
+            entered: Instant::now(),
+            transitions: BTreeMap::new(),
+            dwell: BTreeMap::new(),
+        };
+        self.shared
+            .lock()
+            .unwrap()
+            .instances
+            .insert(instance.to_string(), metrics);
+    }
+
+    /// Render the metrics of the attached instances in the Prometheus text format.
+    pub fn render(&self) -> String {
+        self.shared.lock().unwrap().render()
+    }
+
+    /// Start an HTTP server on the given address, e.g. `0.0.0.0:9464`, that serves the metrics
+    /// at `/metrics` for Prometheus to scrape. Use port 0 to let the system choose a free port,
+    /// and [MetricsServer::local_addr] to find it.
+    pub fn serve(&self, addr: impl ToSocketAddrs) -> io::Result<MetricsServer> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (registry, server_stopped) = (self.clone(), stopped.clone());
+        thread::Builder::new()
+            .name("frame-metrics-server".to_string())
+            .spawn(move || accept(listener, registry, server_stopped))?;
+        Ok(MetricsServer {
+            local_addr,
+            stopped,
+        })
+    }
+}
+
+impl Registry {
+    /// Count a transition of an instance, and the time it spent in the state it left.
+    fn take(&mut self, instance: &str, transition: &TransitionInfo) {
+        let buckets = &self.buckets;
+        let metrics = match self.instances.get_mut(instance) {
+            Some(metrics) => metrics,
+            None => return,
+        };
+        let now = Instant::now();
+        let dwell = now.duration_since(metrics.entered).as_secs_f64();
+        metrics
+            .dwell
+            .entry(metrics.state)
+            .or_insert_with(|| Histogram::new(buckets.len()))
+            .observe(buckets, dwell);
+        *metrics
+            .transitions
+            .entry((
+                transition.event.name,
+                transition.source.name,
+                transition.target.name,
+            ))
+            .or_insert(0) += 1;
+        metrics.state = transition.target.name;
+        metrics.entered = now;
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        text.push_str(
+            "# HELP frame_machine_state Whether an instance of a machine is in a state.\n",
+        );
+        text.push_str("# TYPE frame_machine_state gauge\n");
+        for (instance, metrics) in &self.instances {
+            for state in metrics.machine.states.iter() {
+                let _ = writeln!(
+                    text,
+                    "frame_machine_state{{{},state={}}} {}",
+                    metrics.labels(instance),
+                    label_value(state.name),
+                    (state.name == metrics.state) as u8
+                );
+            }
+        }
+
+        text.push_str(
+            "# HELP frame_machine_transitions_total Transitions taken by an instance of a machine.\n",
+        );
+        text.push_str("# TYPE frame_machine_transitions_total counter\n");
+        for (instance, metrics) in &self.instances {
+            for ((event, source, target), count) in &metrics.transitions {
+                let _ = writeln!(
+                    text,
+                    "frame_machine_transitions_total{{{},event={},source={},target={}}} {}",
+                    metrics.labels(instance),
+                    label_value(event),
+                    label_value(source),
+                    label_value(target),
+                    count
+                );
+            }
+        }
+
+        text.push_str(
+            "# HELP frame_machine_state_dwell_seconds Time an instance of a machine spent in a \
+             state before leaving it.\n",
+        );
+        text.push_str("# TYPE frame_machine_state_dwell_seconds histogram\n");
+        for (instance, metrics) in &self.instances {
+            for (state, histogram) in &metrics.dwell {
+                let labels = format!("{},state={}", metrics.labels(instance), label_value(state));
+                let mut cumulative = 0;
+                let bounds = self.buckets.iter().map(|bound| bound.to_string());
+                for (bound, count) in bounds
+                    .chain(std::iter::once("+Inf".to_string()))
+                    .zip(&histogram.counts)
+                {
+                    cumulative += count;
+                    let _ = writeln!(
+                        text,
+                        "frame_machine_state_dwell_seconds_bucket{{{},le=\"{}\"}} {}",
+                        labels, bound, cumulative
+                    );
+                }
+                let _ = writeln!(
+                    text,
+                    "frame_machine_state_dwell_seconds_sum{{{}}} {}",
+                    labels, histogram.sum
+                );
+                let _ = writeln!(
+                    text,
+                    "frame_machine_state_dwell_seconds_count{{{}}} {}",
+                    labels, histogram.count
+                );
+            }
+        }
+        text
+    }
+}
+
+impl InstanceMetrics {
+    /// The labels identifying the instance.
+    fn labels(&self, instance: &str) -> String {
+        format!(
+            "machine={},instance={}",
+            label_value(self.machine.name),
+            label_value(instance)
+        )
+    }
+}
+
+impl Histogram {
+    fn new(buckets: usize) -> Histogram {
+        Histogram {
+            // one more for the +Inf bucket
+            counts: vec![0; buckets + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, buckets: &[f64], value: f64) {
+        let bucket = buckets
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(buckets.len());
+        self.counts[bucket] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Quote and escape the value of a label.
+fn label_value(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// A server of the metrics of a [MetricsRegistry]. The server stops when it is dropped.
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MetricsServer {
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Answer scrapes until the server is dropped. Scrapes are rare and quick, so they are answered
+/// one at a time.
+fn accept(listener: TcpListener, registry: MetricsRegistry, stopped: Arc<AtomicBool>) {
+    while !stopped.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let _ = respond(stream, &registry);
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(_) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Read an HTTP request and answer it with the metrics if it asks for them.
+fn respond(mut stream: TcpStream, registry: &MetricsRegistry) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    // the request line and headers are enough, and a scrape has no body
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < 8192 {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+    let (method, path) = (request_line.next(), request_line.next());
+    let (status, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", registry.render()),
+        (Some("GET"), _) => ("404 Not Found", "not found, try /metrics\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "only GET is supported\n".to_string(),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histograms() {
+        let buckets = [0.1, 1.0];
+        let mut histogram = Histogram::new(buckets.len());
+        for value in [0.05, 0.1, 0.5, 3.0] {
+            histogram.observe(&buckets, value);
+        }
+        assert_eq!(histogram.counts, vec![2, 1, 1]);
+        assert_eq!(histogram.count, 4);
+        assert!((histogram.sum - 3.65).abs() < 1e-9);
+    }
+
+    #[test]
+    fn label_values() {
+        assert_eq!(label_value("On"), "\"On\"");
+        assert_eq!(label_value("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+}
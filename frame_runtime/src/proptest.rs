@@ -0,0 +1,432 @@
+//! This module defines [proptest] strategies that generate arbitrary sequences of calls to the
+//! interface of a state machine from its static [MachineInfo]. A property test can then send each
+//! call of a sequence to a new instance of the machine and check an invariant after each call, such
+//! as that the machine never panics or that a domain variable never becomes negative.
+//!
+//! This module is only available with the `proptest` feature of this crate enabled.
+//!
+//! Only events of the machine's `interface` block are generated, not enter or exit events. The
+//! argument of each parameter is generated by a strategy chosen by the parameter's type. Strategies
+//! for the primitive types and `String` (including `&str` and `&String` parameters) are provided,
+//! which can be overridden for a type or an individual parameter with the methods of
+//! [EventStrategies].
+//!
+//! Since the runtime interface cannot call the interface methods of a machine generically, the
+//! property test dispatches each [EventCall] on the name of its event. For example, for a machine
+//! `Account` with the interface methods `deposit [amount:i32]` and `withdraw [amount:i32]`:
+//!
+//! ```text
+//! use frame_runtime::proptest::{EventStrategies, Value};
+//! use frame_runtime::Machine;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn balance_never_negative(calls in EventStrategies::new()
+//!         .parameter("deposit", "amount", (0..100).prop_map(Value::I32))
+//!         .events(Account::machine_info(), 0..20))
+//!     {
+//!         let mut sm = Account::new();
+//!         for call in calls {
+//!             match call.name() {
+//!                 "deposit" => sm.deposit(call.arg("amount")),
+//!                 "withdraw" => sm.withdraw(call.arg("amount")),
+//!                 _ => unreachable!(),
+//!             }
+//!             prop_assert!(sm.balance >= 0);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::info::{MachineInfo, MethodInfo};
+use ::proptest::collection::{vec, SizeRange};
+use ::proptest::prelude::*;
+use ::proptest::strategy::Union;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A generated argument of an interface method.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+    String(String),
+}
+
+impl Value {
+    /// The wrapped value, for downcasting to its Rust type.
+    pub fn as_any(&self) -> &dyn Any {
+        match self {
+            Value::Bool(value) => value,
+            Value::Char(value) => value,
+            Value::I8(value) => value,
+            Value::I16(value) => value,
+            Value::I32(value) => value,
+            Value::I64(value) => value,
+            Value::Isize(value) => value,
+            Value::U8(value) => value,
+            Value::U16(value) => value,
+            Value::U32(value) => value,
+            Value::U64(value) => value,
+            Value::Usize(value) => value,
+            Value::F32(value) => value,
+            Value::F64(value) => value,
+            Value::String(value) => value,
+        }
+    }
+
+    /// The wrapped value, boxed for [Dispatch::dispatch](crate::dispatch::Dispatch::dispatch).
+    pub fn into_any(self) -> Box<dyn Any> {
+        match self {
+            Value::Bool(value) => Box::new(value),
+            Value::Char(value) => Box::new(value),
+            Value::I8(value) => Box::new(value),
+            Value::I16(value) => Box::new(value),
+            Value::I32(value) => Box::new(value),
+            Value::I64(value) => Box::new(value),
+            Value::Isize(value) => Box::new(value),
+            Value::U8(value) => Box::new(value),
+            Value::U16(value) => Box::new(value),
+            Value::U32(value) => Box::new(value),
+            Value::U64(value) => Box::new(value),
+            Value::Usize(value) => Box::new(value),
+            Value::F32(value) => Box::new(value),
+            Value::F64(value) => Box::new(value),
+            Value::String(value) => Box::new(value),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{:?}", value),
+            Value::Char(value) => write!(f, "{:?}", value),
+            Value::I8(value) => write!(f, "{:?}", value),
+            Value::I16(value) => write!(f, "{:?}", value),
+            Value::I32(value) => write!(f, "{:?}", value),
+            Value::I64(value) => write!(f, "{:?}", value),
+            Value::Isize(value) => write!(f, "{:?}", value),
+            Value::U8(value) => write!(f, "{:?}", value),
+            Value::U16(value) => write!(f, "{:?}", value),
+            Value::U32(value) => write!(f, "{:?}", value),
+            Value::U64(value) => write!(f, "{:?}", value),
+            Value::Usize(value) => write!(f, "{:?}", value),
+            Value::F32(value) => write!(f, "{:?}", value),
+            Value::F64(value) => write!(f, "{:?}", value),
+            Value::String(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+/// A generated call of an interface method, with an argument for each of its parameters.
+#[derive(Clone, Debug)]
+pub struct EventCall {
+    /// The signature of the called interface method.
+    pub event: &'static MethodInfo,
+
+    /// The arguments of the call, in the order of the method's parameters.
+    pub arguments: Vec<(&'static str, Value)>,
+}
+
+impl EventCall {
+    /// The name of the called interface method.
+    pub fn name(&self) -> &'static str {
+        self.event.name
+    }
+
+    /// The argument of the named parameter, as its Rust type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method has no such parameter or the argument is not of type `T`.
+    pub fn arg<T: Any + Clone>(&self, name: &str) -> T {
+        let (_, value) = self
+            .arguments
+            .iter()
+            .find(|(param, _)| *param == name)
+            .unwrap_or_else(|| panic!("Event {} has no parameter {}", self.event.name, name));
+        match value.as_any().downcast_ref::<T>() {
+            Some(arg) => arg.clone(),
+            None => panic!(
+                "Argument {} of event {} is {:?}, not a {}",
+                name,
+                self.event.name,
+                value,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+}
+
+/// Formats the call like a call expression with named arguments, e.g. `deposit(amount: 5)`.
+impl fmt::Display for EventCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.name())?;
+        for (i, (name, value)) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", name, value)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Configures the strategies generating the arguments of interface methods, and derives
+/// strategies for calls and sequences of calls of the interface of a machine from them.
+#[derive(Clone)]
+pub struct EventStrategies {
+    types: HashMap<String, BoxedStrategy<Value>>,
+    parameters: HashMap<(String, String), BoxedStrategy<Value>>,
+}
+
+impl Default for EventStrategies {
+    fn default() -> Self {
+        EventStrategies::new()
+    }
+}
+
+impl EventStrategies {
+    /// Construct the default strategies, which generate arbitrary values of the primitive types
+    /// and `String`.
+    pub fn new() -> Self {
+        let mut strategies = EventStrategies {
+            types: HashMap::new(),
+            parameters: HashMap::new(),
+        };
+        strategies = strategies
+            .type_strategy("bool", any::<bool>().prop_map(Value::Bool))
+            .type_strategy("char", any::<char>().prop_map(Value::Char))
+            .type_strategy("i8", any::<i8>().prop_map(Value::I8))
+            .type_strategy("i16", any::<i16>().prop_map(Value::I16))
+            .type_strategy("i32", any::<i32>().prop_map(Value::I32))
+            .type_strategy("i64", any::<i64>().prop_map(Value::I64))
+            .type_strategy("isize", any::<isize>().prop_map(Value::Isize))
+            .type_strategy("u8", any::<u8>().prop_map(Value::U8))
+            .type_strategy("u16", any::<u16>().prop_map(Value::U16))
+            .type_strategy("u32", any::<u32>().prop_map(Value::U32))
+            .type_strategy("u64", any::<u64>().prop_map(Value::U64))
+            .type_strategy("usize", any::<usize>().prop_map(Value::Usize))
+            .type_strategy("f32", any::<f32>().prop_map(Value::F32))
+            .type_strategy("f64", any::<f64>().prop_map(Value::F64));
+        for vtype in &["String", "&String", "&str"] {
+            strategies = strategies.type_strategy(vtype, any::<String>().prop_map(Value::String));
+        }
+        strategies
+    }
+
+    /// Set the strategy generating the arguments of parameters of a type, as written in the Frame
+    /// spec, e.g. `i32`. This also adds support for types without a default strategy, as long as
+    /// their values can be represented by a [Value].
+    pub fn type_strategy(
+        mut self,
+        vtype: &str,
+        strategy: impl Strategy<Value = Value> + 'static,
+    ) -> Self {
+        self.types.insert(vtype.to_string(), strategy.boxed());
+        self
+    }
+
+    /// Set the strategy generating the arguments of one parameter of an interface method,
+    /// overriding the strategy of its type.
+    pub fn parameter(
+        mut self,
+        event: &str,
+        parameter: &str,
+        strategy: impl Strategy<Value = Value> + 'static,
+    ) -> Self {
+        self.parameters
+            .insert((event.to_string(), parameter.to_string()), strategy.boxed());
+        self
+    }
+
+    /// A strategy generating a call of any of the interface methods of a machine.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the machine has no interface methods, or if there is no strategy for the type of
+    /// a parameter of one of them.
+    pub fn event(&self, machine: &'static MachineInfo) -> BoxedStrategy<EventCall> {
+        assert!(
+            !machine.interface.is_empty(),
+            "Machine {} has no interface methods",
+            machine.name
+        );
+        let calls = machine
+            .interface
+            .iter()
+            .map(|event| self.call(event))
+            .collect::<Vec<_>>();
+        Union::new(calls).boxed()
+    }
+
+    /// A strategy generating a sequence of calls of the interface methods of a machine, with a
+    /// length in the range of `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [EventStrategies::event].
+    pub fn events(
+        &self,
+        machine: &'static MachineInfo,
+        size: impl Into<SizeRange>,
+    ) -> BoxedStrategy<Vec<EventCall>> {
+        vec(self.event(machine), size).boxed()
+    }
+
+    /// A strategy generating calls of one interface method.
+    fn call(&self, event: &'static MethodInfo) -> BoxedStrategy<EventCall> {
+        let arguments = event
+            .parameters
+            .iter()
+            .map(|param| {
+                let key = (event.name.to_string(), param.name.to_string());
+                self.parameters
+                    .get(&key)
+                    .or_else(|| self.types.get(param.vtype))
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "No strategy for type {} of parameter {} of event {}",
+                            param.vtype, param.name, event.name
+                        )
+                    })
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+        arguments
+            .prop_map(move |values| EventCall {
+                event,
+                arguments: event
+                    .parameters
+                    .iter()
+                    .map(|param| param.name)
+                    .zip(values)
+                    .collect(),
+            })
+            .boxed()
+    }
+}
+
+/// A strategy generating a sequence of calls of the interface methods of a machine with the
+/// default strategies, with a length in the range of `size`.
+///
+/// # Panics
+///
+/// Panics in the same cases as [EventStrategies::event].
+pub fn events(
+    machine: &'static MachineInfo,
+    size: impl Into<SizeRange>,
+) -> BoxedStrategy<Vec<EventCall>> {
+    EventStrategies::new().events(machine, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::NameInfo;
+    use ::proptest::strategy::ValueTree;
+    use ::proptest::test_runner::TestRunner;
+
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: None,
+        sha256: None,
+        name: "Account",
+        variables: &[],
+        states: &[],
+        interface: &[DEPOSIT, CLOSE],
+        actions: &[],
+        events: &[DEPOSIT, CLOSE],
+        transitions: &[],
+    };
+    static DEPOSIT: &MethodInfo = &MethodInfo {
+        name: "deposit",
+        parameters: &[
+            NameInfo {
+                name: "amount",
+                vtype: "i32",
+            },
+            NameInfo {
+                name: "memo",
+                vtype: "&str",
+            },
+        ],
+        return_type: None,
+    };
+    static CLOSE: &MethodInfo = &MethodInfo {
+        name: "close",
+        parameters: &[],
+        return_type: Some("bool"),
+    };
+
+    fn sample<T: std::fmt::Debug>(strategy: &BoxedStrategy<T>, runner: &mut TestRunner) -> T {
+        strategy.new_tree(runner).unwrap().current()
+    }
+
+    #[test]
+    fn generates_interface_calls() {
+        let strategy = EventStrategies::new()
+            .parameter("deposit", "amount", (0..10).prop_map(Value::I32))
+            .events(MACHINE, 50);
+        let mut runner = TestRunner::deterministic();
+        let calls = sample(&strategy, &mut runner);
+        assert_eq!(calls.len(), 50);
+        assert!(calls.iter().any(|call| call.name() == "deposit"));
+        assert!(calls.iter().any(|call| call.name() == "close"));
+        for call in calls {
+            if call.name() == "deposit" {
+                assert_eq!(call.arguments.len(), 2);
+                assert!((0..10).contains(&call.arg::<i32>("amount")));
+                call.arg::<String>("memo");
+            } else {
+                assert!(call.arguments.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn type_strategies() {
+        let strategy = EventStrategies::new()
+            .type_strategy("&str", Just(Value::String("memo".to_string())))
+            .event(MACHINE);
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..20 {
+            let call = sample(&strategy, &mut runner);
+            if call.name() == "deposit" {
+                assert_eq!(call.arg::<String>("memo"), "memo");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "No strategy for type &str of parameter memo of event deposit")]
+    fn missing_type_strategy() {
+        let mut strategies = EventStrategies::new();
+        strategies.types.remove("&str");
+        let _ = strategies.event(MACHINE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Argument amount of event deposit is I32")]
+    fn wrong_argument_type() {
+        let call = EventCall {
+            event: DEPOSIT,
+            arguments: vec![("amount", Value::I32(1))],
+        };
+        call.arg::<u32>("amount");
+    }
+}
@@ -0,0 +1,486 @@
+//! This module exposes running state machines to Python through [PyO3](https://pyo3.rs), so that
+//! scenarios can be scripted against real generated machines, e.g. from a notebook. It is
+//! enabled by the `python` feature of this crate.
+//!
+//! A machine is passed to Python as a `Machine` object, created by a function of the Python
+//! extension module that embeds the machine:
+//!
+//! ```ignore
+//! use frame_runtime::python::{self, PyMachine};
+//! use pyo3::prelude::*;
+//!
+//! #[pyfunction]
+//! fn new_account() -> PyMachine {
+//!     PyMachine::new(Account::new())
+//! }
+//!
+//! #[pymodule]
+//! fn bank(module: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     python::add_classes(module)?;
+//!     module.add_function(wrap_pyfunction!(new_account, module)?)
+//! }
+//! ```
+//!
+//! The methods of `Machine` then work with machines of any type:
+//!
+//! ```text
+//! >>> account = bank.new_account()
+//! >>> account.send("deposit", 10)
+//! >>> account.state, account.variables()
+//! ('Open', {'balance': 10})
+//! >>> [t["target"] for t in account.transition_history()]
+//! ```
+//!
+//! The machine must be generated with the `runtime_support` and `runtime_dispatch` features,
+//! since events are sent to it through the [Dispatch] trait. Values of `bool`, `char`, the
+//! primitive integer and float types, and `String` are converted to and from the corresponding
+//! Python values; values of other types are `None`, and can't be passed as arguments.
+//!
+//! The histories of a machine are unlimited once it is wrapped, so that they and the coverage
+//! computed from them cover the whole scenario; see `Machine.set_history_capacity`. A `Machine`
+//! can only be used from the Python thread that created it.
+
+// the code generated by PyO3 for methods returning `PyResult` trips this lint
+#![allow(clippy::useless_conversion)]
+
+use crate::dispatch::{Dispatch, DispatchError};
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::{MachineInfo, NameInfo, StateInfo};
+use crate::machine::{Machine, State};
+use crate::recorder::MachineCoverage;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+use std::any::Any;
+use std::ops::Deref;
+
+/// A value of a variable, argument or return value, which is only known as `dyn Any`.
+type Value = Option<Box<dyn Any>>;
+
+/// The values of some variables or arguments, by name.
+type Values = Vec<(&'static str, Value)>;
+
+/// An event in the history of a machine, with its arguments and return value.
+struct EventRecord {
+    event: &'static str,
+    arguments: Values,
+    return_value: Value,
+}
+
+/// A transition in the history of a machine.
+struct TransitionRecord {
+    id: usize,
+    change_state: bool,
+    event: &'static str,
+    label: &'static str,
+    source: &'static str,
+    target: &'static str,
+    arguments: Values,
+}
+
+/// A running state machine of any type, as seen by Python.
+#[pyclass(name = "Machine", module = "frame_runtime", unsendable)]
+pub struct PyMachine {
+    machine: Box<dyn Inspect>,
+}
+
+impl PyMachine {
+    /// Wrap a machine for Python, making its histories unlimited.
+    pub fn new<M>(mut machine: M) -> PyMachine
+    where
+        M: Machine + Dispatch + 'static,
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let monitor = machine.event_monitor_mut();
+        monitor.set_event_history_capacity(None);
+        monitor.set_transition_history_capacity(None);
+        PyMachine {
+            machine: Box::new(machine),
+        }
+    }
+}
+
+/// The parts of a machine used by the methods of [PyMachine], which can be used as a trait
+/// object unlike [Machine].
+trait Inspect {
+    fn info(&self) -> &'static MachineInfo;
+    fn state_info(&self) -> &'static StateInfo;
+    fn state_values(&self) -> (Values, Values);
+    fn variables(&self) -> Values;
+    fn event_history(&self) -> Vec<EventRecord>;
+    fn transition_history(&self) -> Vec<TransitionRecord>;
+    fn set_history_capacity(&mut self, events: Option<usize>, transitions: Option<usize>);
+    fn clear_history(&mut self);
+    fn coverage(&self) -> MachineCoverage;
+    fn dispatch(
+        &mut self,
+        method: &str,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Value, DispatchError>;
+}
+
+impl<M> Inspect for M
+where
+    M: Machine + Dispatch,
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    fn info(&self) -> &'static MachineInfo {
+        Machine::info(self)
+    }
+
+    fn state_info(&self) -> &'static StateInfo {
+        self.state().info()
+    }
+
+    fn state_values(&self) -> (Values, Values) {
+        let state = self.state();
+        let info = state.info();
+        (
+            values(&*state.arguments(), info.parameters),
+            values(&*state.variables(), info.variables),
+        )
+    }
+
+    fn variables(&self) -> Values {
+        values(Machine::variables(self), Machine::info(self).variables)
+    }
+
+    fn event_history(&self) -> Vec<EventRecord> {
+        self.event_monitor()
+            .event_history()
+            .iter()
+            .map(|event| {
+                let info = event.info();
+                EventRecord {
+                    event: info.name,
+                    arguments: values(&*event.arguments(), info.parameters),
+                    return_value: event.return_value(),
+                }
+            })
+            .collect()
+    }
+
+    fn transition_history(&self) -> Vec<TransitionRecord> {
+        self.event_monitor()
+            .transition_history()
+            .iter()
+            .map(|transition| {
+                let info = transition.info;
+                TransitionRecord {
+                    id: info.id,
+                    change_state: info.is_change_state(),
+                    event: info.event.name,
+                    label: info.label,
+                    source: info.source.name,
+                    target: info.target.name,
+                    arguments: values(&*transition.enter_arguments(), info.target.parameters),
+                }
+            })
+            .collect()
+    }
+
+    fn set_history_capacity(&mut self, events: Option<usize>, transitions: Option<usize>) {
+        let monitor = self.event_monitor_mut();
+        monitor.set_event_history_capacity(events);
+        monitor.set_transition_history_capacity(transitions);
+    }
+
+    fn clear_history(&mut self) {
+        let monitor = self.event_monitor_mut();
+        monitor.clear_event_history();
+        monitor.clear_transition_history();
+    }
+
+    fn coverage(&self) -> MachineCoverage {
+        MachineCoverage::from_history(self)
+    }
+
+    fn dispatch(
+        &mut self,
+        method: &str,
+        arguments: Vec<Box<dyn Any>>,
+    ) -> Result<Value, DispatchError> {
+        Dispatch::dispatch(self, method, arguments)
+    }
+}
+
+#[pymethods]
+impl PyMachine {
+    /// The name of the machine's system, e.g. `Account`.
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.machine.info().name
+    }
+
+    /// The name of the current state, e.g. `Open`.
+    #[getter]
+    fn state(&self) -> &'static str {
+        self.machine.state_info().name
+    }
+
+    /// The static structure of the machine: its states, interface, domain variables and
+    /// transitions.
+    fn info(&self, py: Python<'_>) -> PyResult<PyObject> {
+        machine_info(py, self.machine.info())
+    }
+
+    /// The values of the domain variables, by name.
+    fn variables(&self, py: Python<'_>) -> PyResult<PyObject> {
+        dict(py, &self.machine.variables())
+    }
+
+    /// The name of the current state, the values of its arguments and variables, and the
+    /// values of the domain variables.
+    fn snapshot(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let (arguments, variables) = self.machine.state_values();
+        let state = PyDict::new_bound(py);
+        state.set_item("name", self.machine.state_info().name)?;
+        state.set_item("arguments", dict(py, &arguments)?)?;
+        state.set_item("variables", dict(py, &variables)?)?;
+        let snapshot = PyDict::new_bound(py);
+        snapshot.set_item("machine", self.machine.info().name)?;
+        snapshot.set_item("state", state)?;
+        snapshot.set_item("variables", dict(py, &self.machine.variables())?)?;
+        Ok(snapshot.into_py(py))
+    }
+
+    /// Call the interface method with the given name, with the given arguments, and return its
+    /// return value, or `None` for methods without a return type.
+    #[pyo3(signature = (method, *arguments))]
+    fn send(
+        &mut self,
+        py: Python<'_>,
+        method: &str,
+        arguments: &Bound<'_, PyTuple>,
+    ) -> PyResult<PyObject> {
+        let info = self
+            .machine
+            .info()
+            .interface
+            .iter()
+            .find(|info| info.name == method)
+            .ok_or_else(|| {
+                PyValueError::new_err(DispatchError::UnknownMethod(method.to_string()).to_string())
+            })?;
+        if arguments.len() > info.parameters.len() {
+            let err = DispatchError::ArgumentCount {
+                method: method.to_string(),
+                expected: info.parameters.len(),
+                found: arguments.len(),
+            };
+            return Err(PyTypeError::new_err(err.to_string()));
+        }
+        let mut values = Vec::new();
+        for (parameter, argument) in info.parameters.iter().zip(arguments.iter()) {
+            let value = from_python(&argument, parameter.vtype).map_err(|_| {
+                PyTypeError::new_err(format!(
+                    "argument `{}` of `{}` must be of type `{}`",
+                    parameter.name, method, parameter.vtype
+                ))
+            })?;
+            values.push(value);
+        }
+        match self.machine.dispatch(method, values) {
+            Ok(value) => Ok(to_python(py, &value)),
+            Err(err @ DispatchError::UnknownMethod(_)) => {
+                Err(PyValueError::new_err(err.to_string()))
+            }
+            Err(err) => Err(PyTypeError::new_err(err.to_string())),
+        }
+    }
+
+    /// The events sent to the machine, the oldest first, each with its `event` name, its
+    /// `arguments` and its `return` value. Enter and exit events are included, named after
+    /// their state, e.g. `Open:>`.
+    fn event_history(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let list = PyList::empty_bound(py);
+        for record in self.machine.event_history() {
+            let event = PyDict::new_bound(py);
+            event.set_item("event", record.event)?;
+            event.set_item("arguments", dict(py, &record.arguments)?)?;
+            event.set_item("return", to_python(py, &record.return_value))?;
+            list.append(event)?;
+        }
+        Ok(list.into_py(py))
+    }
+
+    /// The transitions the machine took, the oldest first, each with the `id` of the
+    /// transition, its `kind`, `event`, `label`, `source` and `target`, and the `arguments`
+    /// passed to the target.
+    fn transition_history(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let list = PyList::empty_bound(py);
+        for record in self.machine.transition_history() {
+            let transition = PyDict::new_bound(py);
+            transition.set_item("id", record.id)?;
+            let kind = if record.change_state {
+                "change_state"
+            } else {
+                "transition"
+            };
+            transition.set_item("kind", kind)?;
+            transition.set_item("event", record.event)?;
+            transition.set_item("label", record.label)?;
+            transition.set_item("source", record.source)?;
+            transition.set_item("target", record.target)?;
+            transition.set_item("arguments", dict(py, &record.arguments)?)?;
+            list.append(transition)?;
+        }
+        Ok(list.into_py(py))
+    }
+
+    /// Set the number of events and transitions kept in the histories, or `None` to keep them
+    /// all.
+    #[pyo3(signature = (events = None, transitions = None))]
+    fn set_history_capacity(&mut self, events: Option<usize>, transitions: Option<usize>) {
+        self.machine.set_history_capacity(events, transitions);
+    }
+
+    /// Forget the events and transitions in the histories, e.g. between scenarios.
+    fn clear_history(&mut self) {
+        self.machine.clear_history();
+    }
+
+    /// The number of times each state was entered and each transition taken, according to the
+    /// transition history, with the percentages of the states and transitions covered.
+    fn coverage(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let coverage = self.machine.coverage();
+        let states = PyDict::new_bound(py);
+        for state in &coverage.states {
+            states.set_item(&state.name, state.count)?;
+        }
+        let transitions = PyList::empty_bound(py);
+        for t in &coverage.transitions {
+            let transition = PyDict::new_bound(py);
+            transition.set_item("id", t.id)?;
+            transition.set_item("event", &t.event)?;
+            transition.set_item("transition", &t.transition)?;
+            transition.set_item("count", t.count)?;
+            transitions.append(transition)?;
+        }
+        let result = PyDict::new_bound(py);
+        result.set_item("machine", &coverage.machine)?;
+        result.set_item("states", states)?;
+        result.set_item("transitions", transitions)?;
+        result.set_item("state_percentage", coverage.state_percentage())?;
+        result.set_item("transition_percentage", coverage.transition_percentage())?;
+        Ok(result.into_py(py))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<{} machine in ${}>",
+            self.machine.info().name,
+            self.machine.state_info().name
+        )
+    }
+}
+
+/// Add the classes of this module to a Python module.
+pub fn add_classes(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMachine>()
+}
+
+fn values<E: Environment + ?Sized>(environment: &E, names: &'static [NameInfo]) -> Values {
+    names
+        .iter()
+        .map(|name| (name.name, environment.lookup(name.name)))
+        .collect()
+}
+
+fn dict(py: Python<'_>, values: &[(&'static str, Value)]) -> PyResult<PyObject> {
+    let dict = PyDict::new_bound(py);
+    for (name, value) in values {
+        dict.set_item(name, to_python(py, value))?;
+    }
+    Ok(dict.into_py(py))
+}
+
+fn machine_info(py: Python<'_>, info: &'static MachineInfo) -> PyResult<PyObject> {
+    let names = |names: &'static [NameInfo]| -> PyResult<PyObject> {
+        let list = PyList::empty_bound(py);
+        for name in names {
+            list.append((name.name, name.vtype))?;
+        }
+        Ok(list.into_py(py))
+    };
+
+    let states = PyList::empty_bound(py);
+    for state in info.states {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("name", state.name)?;
+        dict.set_item("parent", state.parent.map(|parent| parent.name))?;
+        dict.set_item("parameters", names(state.parameters)?)?;
+        dict.set_item("variables", names(state.variables)?)?;
+        states.append(dict)?;
+    }
+    let interface = PyList::empty_bound(py);
+    for method in info.interface {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("name", method.name)?;
+        dict.set_item("parameters", names(method.parameters)?)?;
+        dict.set_item("return_type", method.return_type)?;
+        interface.append(dict)?;
+    }
+    let transitions = PyList::empty_bound(py);
+    for transition in info.transitions {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("id", transition.id)?;
+        let kind = if transition.is_change_state() {
+            "change_state"
+        } else {
+            "transition"
+        };
+        dict.set_item("kind", kind)?;
+        dict.set_item("event", transition.event.name)?;
+        dict.set_item("label", transition.label)?;
+        dict.set_item("source", transition.source.name)?;
+        dict.set_item("target", transition.target.name)?;
+        transitions.append(dict)?;
+    }
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item("name", info.name)?;
+    dict.set_item("states", states)?;
+    dict.set_item("interface", interface)?;
+    dict.set_item("variables", names(info.variables)?)?;
+    dict.set_item("transitions", transitions)?;
+    Ok(dict.into_py(py))
+}
+
+/// Convert a value to Python, or to `None` if its type isn't supported.
+fn to_python(py: Python<'_>, value: &Value) -> PyObject {
+    let value = match value {
+        Some(value) => value,
+        None => return py.None(),
+    };
+    macro_rules! convert {
+        ($($t:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$t>() {
+                return value.clone().into_py(py);
+            })*
+        };
+    }
+    convert!(bool, char, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, String);
+    if let Some(value) = value.downcast_ref::<&str>() {
+        return value.into_py(py);
+    }
+    py.None()
+}
+
+/// Convert a Python value to a value of the declared type.
+fn from_python(value: &Bound<'_, PyAny>, vtype: &str) -> PyResult<Box<dyn Any>> {
+    macro_rules! convert {
+        ($($t:ty),*) => {
+            match vtype.trim() {
+                $(stringify!($t) => Ok(Box::new(value.extract::<$t>()?)),)*
+                _ => Err(PyTypeError::new_err(format!("unsupported type `{}`", vtype))),
+            }
+        };
+    }
+    convert!(bool, char, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, String)
+}
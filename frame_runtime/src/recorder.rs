@@ -0,0 +1,483 @@
+//! This module measures which states and transitions of a machine a test suite exercises. A
+//! [CoverageRecorder] attached to running instances of a machine counts the states they enter
+//! and the transitions they take, and writes the counts to a file per test. A [CoverageReport]
+//! aggregates the files written by a test run into the coverage of each machine, listing the
+//! states that were never entered and the transitions that were never taken.
+//!
+//! The files are only written by [CoverageRecorder::write] when the `FRAME_COVERAGE_DIR`
+//! environment variable names the directory to write them to, so tests can record coverage
+//! unconditionally and a CI job can collect it with, e.g.
+//!
+//! ```text
+//! FRAME_COVERAGE_DIR=target/frame-coverage cargo test
+//! cargo run -p frame_testing --bin frame-coverage -- target/frame-coverage --min-transitions 90
+//! ```
+//!
+//! The recorder counts the transitions reported to the event monitor of the machine, so the
+//! machine must be generated with the `runtime_support` feature. The state a machine is in when
+//! the recorder is attached counts as entered, as do the transitions it took before that as far
+//! as its transition history goes back, e.g. the transition of the enter handler of its initial
+//! state. Entering a state also counts as entering its ancestors.
+
+use crate::callback::{Callback, CallbackSend};
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::{MachineInfo, StateInfo, TransitionInfo};
+use crate::machine::{Machine, State, ThreadSafeMachine, ThreadUnsafeMachine};
+use crate::transition::Transition;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The environment variable naming the directory [CoverageRecorder::write] writes to.
+pub const COVERAGE_DIR_VAR: &str = "FRAME_COVERAGE_DIR";
+
+/// The extension of the files written by a [CoverageRecorder].
+pub const COVERAGE_FILE_EXTENSION: &str = "frmcov";
+
+/// The number of times a state was entered.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateCount {
+    /// The name of the state.
+    pub name: String,
+
+    /// The number of times the state was entered.
+    pub count: u64,
+}
+
+/// The number of times a transition was taken.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransitionCount {
+    /// The ID of the transition within its machine, see [TransitionInfo::id].
+    pub id: usize,
+
+    /// The name of the event that triggers the transition.
+    pub event: String,
+
+    /// The transition, rendered as by [TransitionInfo]'s `Display` impl, e.g. `Off->On`.
+    pub transition: String,
+
+    /// The number of times the transition was taken.
+    pub count: u64,
+}
+
+/// The number of times each state of a machine was entered and each of its transitions taken.
+///
+/// The coverage is written to and read from a line-based text format: a `machine <name>` line
+/// followed by a `state <name> <count>` line per state and a
+/// `transition <id> <event> <transition> <count>` line per transition, with the fields separated
+/// by tabs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MachineCoverage {
+    /// The name of the machine.
+    pub machine: String,
+
+    /// The states of the machine, in declaration order.
+    pub states: Vec<StateCount>,
+
+    /// The transitions of the machine, in order of their IDs.
+    pub transitions: Vec<TransitionCount>,
+}
+
+impl MachineCoverage {
+    /// The coverage of a machine before any state was entered.
+    pub fn new(machine: &MachineInfo) -> MachineCoverage {
+        MachineCoverage {
+            machine: machine.name.to_string(),
+            states: machine
+                .states
+                .iter()
+                .map(|state| StateCount {
+                    name: state.name.to_string(),
+                    count: 0,
+                })
+                .collect(),
+            transitions: machine
+                .transitions
+                .iter()
+                .map(|transition| TransitionCount {
+                    id: transition.id,
+                    event: transition.event.name.to_string(),
+                    transition: transition.to_string(),
+                    count: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// The coverage of the current state of a running machine, and of the transitions in its
+    /// transition history along with the states they left.
+    pub fn from_history<M: Machine>(machine: &M) -> MachineCoverage
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let mut coverage = MachineCoverage::new(machine.info());
+        for transition in machine.event_monitor().transition_history().iter() {
+            coverage.enter(transition.info.source);
+            coverage.count(transition.info);
+        }
+        coverage.enter(machine.state().info());
+        coverage
+    }
+
+    /// Parse coverage written in the format of this type's `Display` impl.
+    pub fn parse(text: &str) -> Result<MachineCoverage, String> {
+        let mut coverage = None;
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let error = || format!("line {}: malformed coverage `{}`", i + 1, line);
+            let count = |field: &str| field.parse::<u64>().map_err(|_| error());
+            match (fields.as_slice(), coverage.as_mut()) {
+                ([""], _) => {}
+                (["machine", name], None) => {
+                    coverage = Some(MachineCoverage {
+                        machine: name.to_string(),
+                        states: Vec::new(),
+                        transitions: Vec::new(),
+                    })
+                }
+                (["state", name, n], Some(coverage)) => coverage.states.push(StateCount {
+                    name: name.to_string(),
+                    count: count(n)?,
+                }),
+                (["transition", id, event, transition, n], Some(coverage)) => {
+                    coverage.transitions.push(TransitionCount {
+                        id: id.parse().map_err(|_| error())?,
+                        event: event.to_string(),
+                        transition: transition.to_string(),
+                        count: count(n)?,
+                    })
+                }
+                _ => return Err(error()),
+            }
+        }
+        coverage.ok_or_else(|| "missing machine name".to_string())
+    }
+
+    /// Add the counts of other coverage of the same machine to this coverage. States and
+    /// transitions are matched by name and ID; those this coverage doesn't have yet are added.
+    pub fn merge(&mut self, other: &MachineCoverage) {
+        for state in &other.states {
+            match self.states.iter_mut().find(|s| s.name == state.name) {
+                Some(s) => s.count += state.count,
+                None => self.states.push(state.clone()),
+            }
+        }
+        for transition in &other.transitions {
+            match self.transitions.iter_mut().find(|t| t.id == transition.id) {
+                Some(t) => t.count += transition.count,
+                None => self.transitions.push(transition.clone()),
+            }
+        }
+        self.transitions.sort_by_key(|t| t.id);
+    }
+
+    /// Count an entry to a state and to its ancestors.
+    fn enter(&mut self, state: &StateInfo) {
+        let names: Vec<&str> = std::iter::once(state)
+            .chain(state.ancestors())
+            .map(|s| s.name)
+            .collect();
+        for state in self.states.iter_mut() {
+            if names.contains(&state.name.as_str()) {
+                state.count += 1;
+            }
+        }
+    }
+
+    /// Count a transition.
+    fn count(&mut self, transition: &TransitionInfo) {
+        if let Some(t) = self.transitions.iter_mut().find(|t| t.id == transition.id) {
+            t.count += 1;
+        }
+    }
+
+    /// Count a transition and the entry to its target.
+    fn take(&mut self, transition: &TransitionInfo) {
+        self.count(transition);
+        self.enter(transition.target);
+    }
+
+    /// The states that were never entered.
+    pub fn states_not_entered(&self) -> Vec<&StateCount> {
+        self.states.iter().filter(|s| s.count == 0).collect()
+    }
+
+    /// The transitions that were never taken.
+    pub fn transitions_not_taken(&self) -> Vec<&TransitionCount> {
+        self.transitions.iter().filter(|t| t.count == 0).collect()
+    }
+
+    /// The percentage of the states that were entered, or 100 if the machine has no states.
+    pub fn state_percentage(&self) -> f64 {
+        percentage(self.states_not_entered().len(), self.states.len())
+    }
+
+    /// The percentage of the transitions that were taken, or 100 if the machine has no
+    /// transitions.
+    pub fn transition_percentage(&self) -> f64 {
+        percentage(self.transitions_not_taken().len(), self.transitions.len())
+    }
+}
+
+impl fmt::Display for MachineCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "machine\t{}", self.machine)?;
+        for state in &self.states {
+            writeln!(f, "state\t{}\t{}", state.name, state.count)?;
+        }
+        for t in &self.transitions {
+            writeln!(
+                f,
+                "transition\t{}\t{}\t{}\t{}",
+                t.id, t.event, t.transition, t.count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn percentage(missing: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        (total - missing) as f64 * 100.0 / total as f64
+    }
+}
+
+/// Records the coverage of a machine by the instances it is attached to. Clones of a recorder
+/// share its counts, so one recorder can be attached to every instance a test creates.
+#[derive(Clone)]
+pub struct CoverageRecorder {
+    coverage: Arc<Mutex<MachineCoverage>>,
+}
+
+impl CoverageRecorder {
+    /// Create a recorder for a machine, which can be attached to its instances.
+    pub fn new(machine: &'static MachineInfo) -> CoverageRecorder {
+        CoverageRecorder {
+            coverage: Arc::new(Mutex::new(MachineCoverage::new(machine))),
+        }
+    }
+
+    /// Count the current state of an instance of a machine generated with `thread_safe`
+    /// disabled, and the states and transitions it enters and takes from now on.
+    pub fn attach<M: ThreadUnsafeMachine + 'static>(&self, machine: &mut M) {
+        self.record_start(machine);
+        let coverage = self.coverage.clone();
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(Callback::new(
+                "coverage_recorder",
+                move |transition: &Transition<M>| {
+                    coverage.lock().unwrap().take(transition.info);
+                },
+            ));
+    }
+
+    /// Like [CoverageRecorder::attach], for a machine generated with `thread_safe` enabled.
+    pub fn attach_sync<M: ThreadSafeMachine + 'static>(&self, machine: &mut M) {
+        self.record_start(machine);
+        let coverage = self.coverage.clone();
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(CallbackSend::new(
+                "coverage_recorder",
+                move |transition: &Transition<M>| {
+                    coverage.lock().unwrap().take(transition.info);
+                },
+            ));
+    }
+
+    /// Count the transitions an instance took before the recorder was attached, as far as its
+    /// transition history goes back, along with the states they left, and its current state.
+    fn record_start<M: Machine>(&self, machine: &M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        self.lock().merge(&MachineCoverage::from_history(machine));
+    }
+
+    /// The coverage recorded so far.
+    pub fn coverage(&self) -> MachineCoverage {
+        self.lock().clone()
+    }
+
+    /// Write the coverage recorded so far to the directory named by the `FRAME_COVERAGE_DIR`
+    /// environment variable, if it is set, as by [CoverageRecorder::write_to]. Returns the path
+    /// of the file written, or `None` if the variable isn't set.
+    pub fn write(&self, test_name: &str) -> io::Result<Option<PathBuf>> {
+        match std::env::var_os(COVERAGE_DIR_VAR) {
+            Some(dir) => self.write_to(Path::new(&dir), test_name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Write the coverage recorded so far to a file in a directory, which is created if it
+    /// doesn't exist. The file is named after the test and the machine, so the coverage of each
+    /// test is kept in a file of its own, and returns its path.
+    pub fn write_to(&self, dir: &Path, test_name: &str) -> io::Result<PathBuf> {
+        let coverage = self.coverage();
+        let file_name: String = format!("{}.{}", test_name, coverage.machine)
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let path = dir.join(format!("{}.{}", file_name, COVERAGE_FILE_EXTENSION));
+        fs::create_dir_all(dir)?;
+        fs::write(&path, coverage.to_string())?;
+        Ok(path)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MachineCoverage> {
+        self.coverage.lock().unwrap()
+    }
+}
+
+/// The coverage of each machine, aggregated from the files of a test run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageReport {
+    /// The coverage of each machine, ordered by machine name.
+    pub machines: Vec<MachineCoverage>,
+}
+
+impl CoverageReport {
+    /// Create an empty report.
+    pub fn new() -> CoverageReport {
+        CoverageReport::default()
+    }
+
+    /// Read the coverage files in a directory, as written by [CoverageRecorder::write_to].
+    pub fn read_dir(dir: &Path) -> io::Result<CoverageReport> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<_>>()?;
+        paths.retain(|path| path.extension().unwrap_or_default() == COVERAGE_FILE_EXTENSION);
+        paths.sort();
+
+        let mut report = CoverageReport::new();
+        for path in paths {
+            let coverage = MachineCoverage::parse(&fs::read_to_string(&path)?).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            })?;
+            report.add(&coverage);
+        }
+        Ok(report)
+    }
+
+    /// Add the coverage of a machine to the report, merging it with the coverage of the same
+    /// machine that was added before.
+    pub fn add(&mut self, coverage: &MachineCoverage) {
+        match self
+            .machines
+            .binary_search_by(|m| m.machine.as_str().cmp(&coverage.machine))
+        {
+            Ok(i) => self.machines[i].merge(coverage),
+            Err(i) => self.machines.insert(i, coverage.clone()),
+        }
+    }
+
+    /// The machines less than the given percentage of whose transitions were taken.
+    pub fn below(&self, min_transition_percentage: f64) -> Vec<&MachineCoverage> {
+        self.machines
+            .iter()
+            .filter(|m| m.transition_percentage() < min_transition_percentage)
+            .collect()
+    }
+
+    /// Render the report as text for people, with a summary line per machine followed by the
+    /// states and transitions it didn't cover.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for machine in &self.machines {
+            let not_entered = machine.states_not_entered();
+            let not_taken = machine.transitions_not_taken();
+            out.push_str(&format!(
+                "{}: {}/{} states ({:.1}%), {}/{} transitions ({:.1}%)\n",
+                machine.machine,
+                machine.states.len() - not_entered.len(),
+                machine.states.len(),
+                machine.state_percentage(),
+                machine.transitions.len() - not_taken.len(),
+                machine.transitions.len(),
+                machine.transition_percentage(),
+            ));
+            for state in not_entered {
+                out.push_str(&format!("  never entered: ${}\n", state.name));
+            }
+            // grouped by event, as the same states may be connected by several events
+            let mut by_event: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            for t in not_taken {
+                by_event.entry(&t.event).or_default().push(&t.transition);
+            }
+            for (event, transitions) in by_event {
+                out.push_str(&format!(
+                    "  never taken on |{}|: {}\n",
+                    event,
+                    transitions.join(", ")
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAMP: &str = "machine\tLamp\n\
+                        state\tOff\t1\n\
+                        state\tOn\t0\n\
+                        transition\t0\tturnOn\tOff->On\t0\n\
+                        transition\t1\tturnOff\tOn->Off\t0\n";
+
+    #[test]
+    fn parse_and_merge() {
+        let mut coverage = MachineCoverage::parse(LAMP).unwrap();
+        assert_eq!(coverage.to_string(), LAMP);
+        assert_eq!(coverage.transition_percentage(), 0.0);
+
+        let taken = LAMP
+            .replace("On\t0\n", "On\t2\n")
+            .replace("Off->On\t0", "Off->On\t2");
+        coverage.merge(&MachineCoverage::parse(&taken).unwrap());
+        assert_eq!(coverage.states[1].count, 2);
+        assert_eq!(coverage.transitions[0].count, 2);
+        assert_eq!(coverage.state_percentage(), 100.0);
+        assert_eq!(coverage.transition_percentage(), 50.0);
+
+        let mut report = CoverageReport::new();
+        report.add(&coverage);
+        assert_eq!(
+            report.render(),
+            "Lamp: 2/2 states (100.0%), 1/2 transitions (50.0%)\n  never taken on |turnOff|: On->Off\n"
+        );
+        assert_eq!(report.below(50.0).len(), 0);
+        assert_eq!(report.below(75.0).len(), 1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(MachineCoverage::parse("").is_err());
+        assert!(MachineCoverage::parse("state\tOff\t1\n").is_err());
+        let err = MachineCoverage::parse("machine\tLamp\nstate\tOff\tmany\n").unwrap_err();
+        assert_eq!(err, "line 2: malformed coverage `state\tOff\tmany`");
+    }
+}
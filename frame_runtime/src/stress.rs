@@ -0,0 +1,396 @@
+//! This module runs randomized stress tests of state machines: it sends long random sequences of
+//! calls to the interface of new instances of a machine, checks registered invariants after each
+//! call, and watches for panics, including the panics of `RefCell` borrow errors. A failing
+//! sequence is shrunk to a minimal sequence reproducing the failure, which is reported along with
+//! the seed of the run, so that the run can be repeated.
+//!
+//! This module is only available with the `proptest` feature of this crate enabled. The sequences
+//! are generated by the strategies of the [proptest](crate::proptest) module, and the calls are
+//! sent by name, so the machine must be generated with the `runtime_support` and
+//! `runtime_dispatch` features, and its parameters must be of owned types such as `String`. For
+//! example, for a machine `Account` with a domain variable `balance`:
+//!
+//! ```text
+//! use frame_runtime::stress::StressTest;
+//! use frame_runtime::Machine;
+//!
+//! #[test]
+//! fn stress() {
+//!     StressTest::new(Account::machine_info(), Account::new)
+//!         .invariant("balance is never negative", |sm| sm.balance >= 0)
+//!         .run()
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! The seed of a run is taken from the `FRAME_STRESS_SEED` environment variable if it is set, so
+//! that a failure reported by a test can be reproduced by running it again with the reported
+//! seed. Otherwise a new seed is chosen for each run, unless one is set with [StressTest::seed].
+
+use crate::dispatch::{Dispatch, DispatchError};
+use crate::info::MachineInfo;
+use crate::proptest::{EventCall, EventStrategies};
+use ::proptest::test_runner::{
+    Config, RngAlgorithm, TestCaseError, TestError, TestRng, TestRunner,
+};
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The ways a sequence of calls may fail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Failure {
+    /// The named invariant doesn't hold.
+    Invariant(String),
+
+    /// A `RefCell` was borrowed while it was mutably borrowed, or mutably borrowed while it was
+    /// borrowed, e.g. by an action calling back into the machine.
+    BorrowError(String),
+
+    /// The construction of the machine or a call panicked with the given message.
+    Panic(String),
+
+    /// A call was rejected, e.g. because a parameter type is not supported by
+    /// [Dispatch::dispatch].
+    Dispatch(DispatchError),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Invariant(name) => write!(f, "invariant `{}` doesn't hold", name),
+            Failure::BorrowError(message) => write!(f, "borrow error: {}", message),
+            Failure::Panic(message) => write!(f, "panic: {}", message),
+            Failure::Dispatch(err) => write!(f, "call rejected: {}", err),
+        }
+    }
+}
+
+/// A failed stress test: a minimal sequence of calls, sent to a new machine, that fails.
+#[derive(Clone, Debug)]
+pub struct StressFailure {
+    /// The seed of the run that found the failure.
+    pub seed: u64,
+
+    /// The minimal sequence of calls found that reproduces the failure. The failure happens
+    /// during or after the last call, or when the machine is created if there are no calls.
+    pub calls: Vec<EventCall>,
+
+    /// How the calls fail.
+    pub failure: Failure,
+}
+
+impl fmt::Display for StressFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} after {} call(s), with FRAME_STRESS_SEED={}",
+            self.failure,
+            self.calls.len(),
+            self.seed
+        )?;
+        for call in &self.calls {
+            write!(f, "\n  {}", call)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StressFailure {}
+
+/// A named check of the state of a machine.
+type Invariant<M> = (String, Box<dyn Fn(&M) -> bool>);
+
+/// Configures and runs a stress test of a machine. The methods of this struct are designed to be
+/// chained to override the default configuration.
+pub struct StressTest<M> {
+    machine: &'static MachineInfo,
+    new_machine: Box<dyn Fn() -> M>,
+    strategies: EventStrategies,
+    invariants: Vec<Invariant<M>>,
+    seed: u64,
+    runs: u32,
+    length: usize,
+}
+
+impl<M: Dispatch> StressTest<M> {
+    /// Construct a stress test of the machine described by `machine`, whose instances are
+    /// created by `new_machine`. By default, 100 sequences of up to 100 calls are sent, with the
+    /// arguments generated by [EventStrategies::new].
+    pub fn new(machine: &'static MachineInfo, new_machine: impl Fn() -> M + 'static) -> Self {
+        let seed = match std::env::var("FRAME_STRESS_SEED") {
+            Ok(seed) => seed
+                .parse()
+                .unwrap_or_else(|_| panic!("FRAME_STRESS_SEED is not a number: {}", seed)),
+            Err(_) => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or_default(),
+        };
+        StressTest {
+            machine,
+            new_machine: Box::new(new_machine),
+            strategies: EventStrategies::new(),
+            invariants: Vec::new(),
+            seed,
+            runs: 100,
+            length: 100,
+        }
+    }
+
+    /// Set the strategies generating the arguments of the calls.
+    pub fn strategies(mut self, strategies: EventStrategies) -> Self {
+        self.strategies = strategies;
+        self
+    }
+
+    /// Add an invariant, checked when the machine is created and after each call.
+    pub fn invariant(mut self, name: &str, check: impl Fn(&M) -> bool + 'static) -> Self {
+        self.invariants.push((name.to_string(), Box::new(check)));
+        self
+    }
+
+    /// Set the seed of the random sequences, to repeat a run.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the number of sequences to send, each to a new machine.
+    pub fn runs(mut self, runs: u32) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Set the maximum length of the sequences.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Run the stress test, and return the minimal failing sequence found, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [EventStrategies::event].
+    pub fn run(&self) -> Result<(), StressFailure> {
+        let strategy = self.strategies.events(self.machine, 0..=self.length);
+        let config = Config {
+            cases: self.runs,
+            failure_persistence: None,
+            ..Config::default()
+        };
+        let mut seed = [0; 32];
+        seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed);
+        let mut runner = TestRunner::new_with_rng(config, rng);
+
+        let result = silently(|| {
+            runner.run(&strategy, |calls| match self.execute(&calls) {
+                Some(failure) => Err(TestCaseError::fail(failure.to_string())),
+                None => Ok(()),
+            })
+        });
+        match result {
+            Ok(()) => Ok(()),
+            Err(TestError::Fail(_, calls)) => {
+                let failure = silently(|| self.execute(&calls))
+                    .expect("a shrunk sequence of calls stopped failing");
+                Err(StressFailure {
+                    seed: self.seed,
+                    calls,
+                    failure,
+                })
+            }
+            Err(TestError::Abort(reason)) => panic!("The stress test was aborted: {}", reason),
+        }
+    }
+
+    /// Send a sequence of calls to a new machine, e.g. the calls of a [StressFailure], and
+    /// return how it fails, if it does.
+    pub fn replay(&self, calls: &[EventCall]) -> Option<Failure> {
+        silently(|| self.execute(calls))
+    }
+
+    /// Send a sequence of calls to a new machine, checking the invariants after each call.
+    fn execute(&self, calls: &[EventCall]) -> Option<Failure> {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut machine = (self.new_machine)();
+            self.check_invariants(&machine)?;
+            for call in calls {
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|(_, value)| value.clone().into_any())
+                    .collect();
+                machine
+                    .dispatch(call.name(), arguments)
+                    .map_err(Failure::Dispatch)?;
+                self.check_invariants(&machine)?;
+            }
+            Ok(())
+        }));
+        match result {
+            Ok(result) => result.err(),
+            Err(payload) => Some(panic_failure(&*payload)),
+        }
+    }
+
+    fn check_invariants(&self, machine: &M) -> Result<(), Failure> {
+        for (name, check) in &self.invariants {
+            if !check(machine) {
+                return Err(Failure::Invariant(name.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run a function without printing the panics it catches.
+fn silently<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(default_hook);
+    result
+}
+
+/// The failure of a panic, from its payload.
+fn panic_failure(payload: &(dyn Any + Send)) -> Failure {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    if message.contains("already borrowed") || message.contains("already mutably borrowed") {
+        Failure::BorrowError(message)
+    } else {
+        Failure::Panic(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::{MethodInfo, NameInfo};
+    use crate::proptest::Value;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: None,
+        sha256: None,
+        name: "Counter",
+        variables: &[],
+        states: &[],
+        interface: &[ADD, RESET],
+        actions: &[],
+        events: &[ADD, RESET],
+        transitions: &[],
+    };
+    static ADD: &MethodInfo = &MethodInfo {
+        name: "add",
+        parameters: &[NameInfo {
+            name: "step",
+            vtype: "u8",
+        }],
+        return_type: None,
+    };
+    static RESET: &MethodInfo = &MethodInfo {
+        name: "reset",
+        parameters: &[],
+        return_type: None,
+    };
+
+    /// A machine counting up to 1000 before it panics, whose `reset` borrows its count while it
+    /// is borrowed after 3 calls of `add` in a row.
+    #[derive(Default)]
+    struct Counter {
+        count: Rc<RefCell<u32>>,
+        adds: u32,
+    }
+
+    impl Dispatch for Counter {
+        fn dispatch(
+            &mut self,
+            method: &str,
+            arguments: Vec<Box<dyn Any>>,
+        ) -> Result<Option<Box<dyn Any>>, DispatchError> {
+            match method {
+                "add" => {
+                    let step = *arguments[0].downcast_ref::<u8>().unwrap();
+                    let mut count = self.count.borrow_mut();
+                    *count += step as u32;
+                    assert!(*count < 1000, "count overflow");
+                    self.adds += 1;
+                }
+                _ => {
+                    let _count = self.count.borrow();
+                    if self.adds >= 3 {
+                        *self.count.borrow_mut() = 0;
+                    }
+                    self.adds = 0;
+                }
+            }
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn passes() {
+        let result = StressTest::new(MACHINE, Counter::default)
+            .invariant("count is small", |sm| *sm.count.borrow() < 1000)
+            .length(2)
+            .seed(1)
+            .run();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn shrinks_invariant_failures() {
+        let failure = StressTest::new(MACHINE, Counter::default)
+            .invariant("count is below 10", |sm| *sm.count.borrow() < 10)
+            .seed(1)
+            .run()
+            .unwrap_err();
+        assert_eq!(failure.seed, 1);
+        assert_eq!(
+            failure.failure,
+            Failure::Invariant("count is below 10".to_string())
+        );
+        assert_eq!(failure.calls.len(), 1);
+        assert!(failure.to_string().starts_with(
+            "invariant `count is below 10` doesn't hold after 1 call(s), with FRAME_STRESS_SEED=1\n  add(step: "
+        ));
+    }
+
+    #[test]
+    fn finds_borrow_errors() {
+        let failure = StressTest::new(MACHINE, Counter::default)
+            .seed(2)
+            .run()
+            .unwrap_err();
+        assert!(matches!(failure.failure, Failure::BorrowError(_)));
+        let names: Vec<&str> = failure.calls.iter().map(|call| call.name()).collect();
+        assert_eq!(names, vec!["add", "add", "add", "reset"]);
+
+        let test = StressTest::new(MACHINE, Counter::default);
+        assert_eq!(test.replay(&failure.calls), Some(failure.failure));
+        assert_eq!(test.replay(&[]), None);
+    }
+
+    #[test]
+    fn finds_panics() {
+        let add = |step: u8| EventCall {
+            event: ADD,
+            arguments: vec![("step", Value::U8(step))],
+        };
+        let calls = vec![add(255), add(255), add(255), add(255)];
+        let test = StressTest::new(MACHINE, Counter::default);
+        assert_eq!(
+            test.replay(&calls),
+            Some(Failure::Panic("count overflow".to_string()))
+        );
+    }
+}
@@ -0,0 +1,662 @@
+//! This module defines a file format for traces of running state machines, so that a run can be
+//! recorded in one place, e.g. a failing test or a bug report, and replayed in another, e.g. a
+//! test of a fixed machine or the simulator of Framec.
+//!
+//! A [TraceWriter] attached to an instance of a machine records the interface events it is sent,
+//! with their arguments and return values, the transitions it takes, and snapshots of its state
+//! and variables. A [Trace] is written to and read from a line-based text format, with the fields
+//! of each line separated by tabs (shown as spaces below), in files with the `.frmtrace`
+//! extension:
+//!
+//! ```text
+//! frmtrace    1
+//! machine     Lamp
+//! snapshot    1700000000000  Off      brightness=0
+//! event       1700000000005  turnOn   level=3
+//! transition  1700000000005  transition  turnOn  Off  On
+//! return      1700000000005  turnOn   true
+//! ```
+//!
+//! The first line gives the version of the format, which is [TRACE_VERSION] for the traces
+//! written by this version of the crate; readers reject traces of later versions. Times are in
+//! milliseconds since the Unix epoch. Values are rendered as JSON, so strings are quoted and
+//! their tabs and line breaks escaped, and values of types other than `bool`, `char`, the
+//! primitive integer and float types, and `String` are `null`. The variables of a snapshot are
+//! the domain variables, and the arguments and variables of the current state prefixed with its
+//! name, e.g. `$On.level=3`. The kind of a transition is `transition` or `change_state`, and the
+//! last field is its label, which may be empty.
+//!
+//! [Trace::replay] sends the recorded events to a new instance of the machine and checks that it
+//! takes the same path, which requires the `runtime_dispatch` feature.
+
+use crate::callback::{Callback, CallbackSend};
+use crate::dispatch::Dispatch;
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::NameInfo;
+use crate::machine::{Machine, State, ThreadSafeMachine, ThreadUnsafeMachine};
+use crate::transition::Transition;
+use crate::values;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The version of the trace format written by this crate.
+pub const TRACE_VERSION: u32 = 1;
+
+/// The extension of trace files.
+pub const TRACE_FILE_EXTENSION: &str = "frmtrace";
+
+/// The name of the callbacks a [TraceWriter] registers with the event monitor of an instance.
+const CALLBACK_NAME: &str = "trace_writer";
+
+/// A recorded run of a machine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    /// The name of the machine.
+    pub machine: String,
+
+    /// The records of the run, in the order they occurred.
+    pub records: Vec<TraceRecord>,
+}
+
+/// Something that happened in a run of a machine. Times are in milliseconds since the Unix
+/// epoch, and values are rendered as JSON.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceRecord {
+    /// An interface event was sent to the machine with the given arguments, by parameter name.
+    Event {
+        time: u64,
+        event: String,
+        arguments: Vec<(String, String)>,
+    },
+
+    /// An interface event with a return type was handled, returning the given value.
+    Return {
+        time: u64,
+        event: String,
+        value: String,
+    },
+
+    /// The machine took a transition, or changed state if `change_state` is set.
+    Transition {
+        time: u64,
+        change_state: bool,
+        event: String,
+        source: String,
+        target: String,
+        label: String,
+    },
+
+    /// The machine was in the given state, with the given values of its variables.
+    Snapshot {
+        time: u64,
+        state: String,
+        variables: Vec<(String, String)>,
+    },
+}
+
+/// The reason a replayed machine did not take the recorded path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReplayError {
+    /// The index of the record the machine diverged at.
+    pub record: usize,
+
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "record {}: {}", self.record + 1, self.message)
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Trace {
+    /// Create an empty trace of a machine.
+    pub fn new(machine: &str) -> Trace {
+        Trace {
+            machine: machine.to_string(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Parse a trace written in the format of this type's `Display` impl.
+    pub fn parse(text: &str) -> Result<Trace, String> {
+        let mut lines = text.lines().enumerate();
+        match lines
+            .next()
+            .map(|(_, line)| line.split('\t').collect::<Vec<_>>())
+        {
+            Some(header) if header.len() == 2 && header[0] == "frmtrace" => {
+                match header[1].parse::<u32>() {
+                    Ok(version) if version <= TRACE_VERSION => {}
+                    Ok(version) => {
+                        return Err(format!(
+                            "unsupported trace version {}, expected at most {}",
+                            version, TRACE_VERSION
+                        ))
+                    }
+                    Err(_) => return Err(format!("malformed trace version `{}`", header[1])),
+                }
+            }
+            _ => return Err("missing `frmtrace` header".to_string()),
+        }
+
+        let mut trace: Option<Trace> = None;
+        for (i, line) in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let error = || format!("line {}: malformed trace record `{}`", i + 1, line);
+            let time = |field: &str| field.parse::<u64>().map_err(|_| error());
+            let record = match (fields.as_slice(), trace.as_mut()) {
+                ([""], _) => continue,
+                (["machine", name], None) => {
+                    trace = Some(Trace::new(name));
+                    continue;
+                }
+                (["event", t, event, arguments @ ..], Some(_)) => TraceRecord::Event {
+                    time: time(t)?,
+                    event: event.to_string(),
+                    arguments: parse_fields(arguments).ok_or_else(error)?,
+                },
+                (["return", t, event, value], Some(_)) => TraceRecord::Return {
+                    time: time(t)?,
+                    event: event.to_string(),
+                    value: value.to_string(),
+                },
+                (["transition", t, kind, event, source, target, label], Some(_)) => {
+                    TraceRecord::Transition {
+                        time: time(t)?,
+                        change_state: match *kind {
+                            "transition" => false,
+                            "change_state" => true,
+                            _ => return Err(error()),
+                        },
+                        event: event.to_string(),
+                        source: source.to_string(),
+                        target: target.to_string(),
+                        label: label.to_string(),
+                    }
+                }
+                (["snapshot", t, state, variables @ ..], Some(_)) => TraceRecord::Snapshot {
+                    time: time(t)?,
+                    state: state.to_string(),
+                    variables: parse_fields(variables).ok_or_else(error)?,
+                },
+                _ => return Err(error()),
+            };
+            // the machine line comes first, so the trace exists by now
+            if let Some(trace) = trace.as_mut() {
+                trace.records.push(record);
+            }
+        }
+        trace.ok_or_else(|| "missing machine name".to_string())
+    }
+
+    /// Read a trace from a file.
+    pub fn read(path: &Path) -> io::Result<Trace> {
+        Trace::parse(&fs::read_to_string(path)?).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), err),
+            )
+        })
+    }
+
+    /// Write the trace to a file.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Send the recorded events to an instance of the machine, e.g. a new one, and check that
+    /// it takes the recorded transitions and returns the recorded values. Snapshots are checked
+    /// against its current state and the values of its domain variables. Returns the number of
+    /// events sent.
+    pub fn replay<M: Machine + Dispatch>(&self, machine: &mut M) -> Result<usize, ReplayError>
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let info = machine.info();
+        if info.name != self.machine {
+            return Err(ReplayError {
+                record: 0,
+                message: format!(
+                    "the trace is of machine `{}`, not `{}`",
+                    self.machine, info.name
+                ),
+            });
+        }
+
+        let mut events = 0;
+        // the expected current state, after the transitions recorded since the last check
+        let mut expected: Option<&str> = None;
+        let mut last_return = None;
+        for (record, entry) in self.records.iter().enumerate() {
+            let fail = |message: String| ReplayError { record, message };
+            match entry {
+                TraceRecord::Event {
+                    event, arguments, ..
+                } => {
+                    check_state(machine, expected.take()).map_err(fail)?;
+                    let method = info
+                        .interface
+                        .iter()
+                        .find(|method| method.name == event)
+                        .ok_or_else(|| fail(format!("unknown event `{}`", event)))?;
+                    let mut values = Vec::new();
+                    for parameter in method.parameters {
+                        let argument = arguments.iter().find(|(name, _)| name == parameter.name);
+                        let (_, text) = match argument {
+                            Some(argument) => argument,
+                            // parameters with default values may be left out
+                            None => break,
+                        };
+                        let value = values::parse_json(text, parameter.vtype).ok_or_else(|| {
+                            fail(format!(
+                                "cannot replay argument `{}` of type `{}`: {}",
+                                parameter.name, parameter.vtype, text
+                            ))
+                        })?;
+                        values.push(value);
+                    }
+                    let value = machine
+                        .dispatch(event, values)
+                        .map_err(|err| fail(err.to_string()))?;
+                    last_return = value.map(|value| values::json(&*value));
+                    events += 1;
+                }
+                TraceRecord::Return { event, value, .. } => {
+                    if last_return.as_deref() != Some(value.as_str()) {
+                        return Err(fail(format!(
+                            "|{}| returned {}, not {}",
+                            event,
+                            last_return.as_deref().unwrap_or("nothing"),
+                            value
+                        )));
+                    }
+                }
+                TraceRecord::Transition { target, .. } => expected = Some(target),
+                TraceRecord::Snapshot {
+                    state, variables, ..
+                } => {
+                    expected = None;
+                    check_state(machine, Some(state)).map_err(fail)?;
+                    for (name, value) in variables {
+                        if name.starts_with('$') {
+                            continue;
+                        }
+                        let actual = machine
+                            .variables()
+                            .lookup(name)
+                            .map_or_else(|| "null".to_string(), |actual| values::json(&*actual));
+                        if &actual != value {
+                            return Err(fail(format!(
+                                "variable `{}` is {}, not {}",
+                                name, actual, value
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        check_state(machine, expected).map_err(|message| ReplayError {
+            record: self.records.len(),
+            message,
+        })?;
+        Ok(events)
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "frmtrace\t{}", TRACE_VERSION)?;
+        writeln!(f, "machine\t{}", self.machine)?;
+        for record in &self.records {
+            match record {
+                TraceRecord::Event {
+                    time,
+                    event,
+                    arguments,
+                } => writeln!(f, "event\t{}\t{}{}", time, event, fields(arguments))?,
+                TraceRecord::Return { time, event, value } => {
+                    writeln!(f, "return\t{}\t{}\t{}", time, event, value)?
+                }
+                TraceRecord::Transition {
+                    time,
+                    change_state,
+                    event,
+                    source,
+                    target,
+                    label,
+                } => {
+                    let kind = if *change_state {
+                        "change_state"
+                    } else {
+                        "transition"
+                    };
+                    writeln!(
+                        f,
+                        "transition\t{}\t{}\t{}\t{}\t{}\t{}",
+                        time, kind, event, source, target, label
+                    )?
+                }
+                TraceRecord::Snapshot {
+                    time,
+                    state,
+                    variables,
+                } => writeln!(f, "snapshot\t{}\t{}{}", time, state, fields(variables))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records a run of an instance of a machine into a [Trace]. Clones of a writer share its trace.
+#[derive(Clone)]
+pub struct TraceWriter {
+    trace: Arc<Mutex<Trace>>,
+}
+
+impl TraceWriter {
+    /// Create a writer for a machine with the given name, which can be attached to an instance.
+    pub fn new(machine: &str) -> TraceWriter {
+        TraceWriter {
+            trace: Arc::new(Mutex::new(Trace::new(machine))),
+        }
+    }
+
+    /// Record a snapshot of an instance of a machine generated with `thread_safe` disabled,
+    /// and the events it is sent and the transitions it takes from now on.
+    pub fn attach<M: ThreadUnsafeMachine + 'static>(&self, machine: &mut M) {
+        self.snapshot(machine);
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_event_sent_callback(Callback::new(CALLBACK_NAME, move |event: &M::EventPtr| {
+                writer.record_event::<M>(&**event)
+            }));
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_event_handled_callback(Callback::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| writer.record_return::<M>(&**event),
+            ));
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(Callback::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| writer.record_transition(transition),
+            ));
+    }
+
+    /// Like [TraceWriter::attach], for a machine generated with `thread_safe` enabled.
+    pub fn attach_sync<M: ThreadSafeMachine + 'static>(&self, machine: &mut M) {
+        self.snapshot(machine);
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_event_sent_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| writer.record_event::<M>(&**event),
+            ));
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_event_handled_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |event: &M::EventPtr| writer.record_return::<M>(&**event),
+            ));
+        let writer = self.clone();
+        machine
+            .event_monitor_mut()
+            .add_transition_callback(CallbackSend::new(
+                CALLBACK_NAME,
+                move |transition: &Transition<M>| writer.record_transition(transition),
+            ));
+    }
+
+    /// Stop recording the run of an instance.
+    pub fn detach<M: Machine>(&self, machine: &mut M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let monitor = machine.event_monitor_mut();
+        monitor.remove_event_sent_callback(CALLBACK_NAME);
+        monitor.remove_event_handled_callback(CALLBACK_NAME);
+        monitor.remove_transition_callback(CALLBACK_NAME);
+    }
+
+    /// Record a snapshot of the current state and variables of an instance.
+    pub fn snapshot<M: Machine>(&self, machine: &M)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let state = machine.state();
+        let info = state.info();
+        let mut variables = environment(machine.variables(), machine.info().variables, "");
+        let prefix = format!("${}.", info.name);
+        variables.extend(environment(&*state.arguments(), info.parameters, &prefix));
+        variables.extend(environment(&*state.variables(), info.variables, &prefix));
+        self.lock().records.push(TraceRecord::Snapshot {
+            time: now(),
+            state: info.name.to_string(),
+            variables,
+        });
+    }
+
+    /// The trace recorded so far.
+    pub fn trace(&self) -> Trace {
+        self.lock().clone()
+    }
+
+    /// Write the trace recorded so far to a file.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        self.lock().write(path)
+    }
+
+    fn record_event<M: Machine>(&self, event: &<M::EventPtr as Deref>::Target)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let info = event.info();
+        if is_interface_event::<M>(info.name) {
+            self.lock().records.push(TraceRecord::Event {
+                time: now(),
+                event: info.name.to_string(),
+                arguments: environment(&*event.arguments(), info.parameters, ""),
+            });
+        }
+    }
+
+    fn record_return<M: Machine>(&self, event: &<M::EventPtr as Deref>::Target)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let info = event.info();
+        if info.return_type.is_none() || !is_interface_event::<M>(info.name) {
+            return;
+        }
+        if let Some(value) = event.return_value() {
+            self.lock().records.push(TraceRecord::Return {
+                time: now(),
+                event: info.name.to_string(),
+                value: values::json(&*value),
+            });
+        }
+    }
+
+    fn record_transition<M: Machine>(&self, transition: &Transition<M>)
+    where
+        <M::EnvironmentPtr as Deref>::Target: Environment,
+        <M::EventPtr as Deref>::Target: Event<M>,
+        <M::StatePtr as Deref>::Target: State<M>,
+    {
+        let info = transition.info;
+        self.lock().records.push(TraceRecord::Transition {
+            time: now(),
+            change_state: info.is_change_state(),
+            event: info.event.name.to_string(),
+            source: info.source.name.to_string(),
+            target: info.target.name.to_string(),
+            label: info.label.to_string(),
+        });
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Trace> {
+        self.trace.lock().unwrap()
+    }
+}
+
+/// Whether an event is declared in the interface of a machine, rather than e.g. an enter event.
+fn is_interface_event<M: Machine>(name: &str) -> bool
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    M::machine_info()
+        .interface
+        .iter()
+        .any(|method| method.name == name)
+}
+
+/// Check that a machine is in the expected state, if any.
+fn check_state<M: Machine>(machine: &M, expected: Option<&str>) -> Result<(), String>
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    let actual = machine.state().info().name;
+    match expected {
+        Some(expected) if expected != actual => {
+            Err(format!("the machine is in ${}, not ${}", actual, expected))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The values of the given names in an environment, rendered as JSON, with the names prefixed.
+fn environment<E: Environment + ?Sized>(
+    environment: &E,
+    names: &[NameInfo],
+    prefix: &str,
+) -> Vec<(String, String)> {
+    names
+        .iter()
+        .map(|name| {
+            let value = environment
+                .lookup(name.name)
+                .map_or_else(|| "null".to_string(), |value| values::json(&*value));
+            (format!("{}{}", prefix, name.name), value)
+        })
+        .collect()
+}
+
+/// Render named values as tab-separated `name=value` fields, each preceded by a tab.
+fn fields(values: &[(String, String)]) -> String {
+    values
+        .iter()
+        .map(|(name, value)| format!("\t{}={}", name, value))
+        .collect()
+}
+
+fn parse_fields(fields: &[&str]) -> Option<Vec<(String, String)>> {
+    fields
+        .iter()
+        .map(|field| {
+            let (name, value) = field.split_once('=')?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Milliseconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAMP: &str = "frmtrace\t1\n\
+                        machine\tLamp\n\
+                        snapshot\t100\tOff\tbrightness=0\n\
+                        event\t105\tturnOn\tlevel=3\tnote=\"a\\tb\"\n\
+                        transition\t105\ttransition\tturnOn\tOff\tOn\t\n\
+                        return\t105\tturnOn\ttrue\n\
+                        transition\t110\tchange_state\treset\tOn\tOff\tlights out\n";
+
+    #[test]
+    fn parse_and_render() {
+        let trace = Trace::parse(LAMP).unwrap();
+        assert_eq!(trace.machine, "Lamp");
+        assert_eq!(trace.records.len(), 5);
+        assert_eq!(
+            trace.records[1],
+            TraceRecord::Event {
+                time: 105,
+                event: "turnOn".to_string(),
+                arguments: vec![
+                    ("level".to_string(), "3".to_string()),
+                    ("note".to_string(), "\"a\\tb\"".to_string()),
+                ],
+            }
+        );
+        assert_eq!(
+            trace.records[4],
+            TraceRecord::Transition {
+                time: 110,
+                change_state: true,
+                event: "reset".to_string(),
+                source: "On".to_string(),
+                target: "Off".to_string(),
+                label: "lights out".to_string(),
+            }
+        );
+        assert_eq!(trace.to_string(), LAMP);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Trace::parse("machine\tLamp\n").unwrap_err(),
+            "missing `frmtrace` header"
+        );
+        assert_eq!(
+            Trace::parse("frmtrace\t2\nmachine\tLamp\n").unwrap_err(),
+            "unsupported trace version 2, expected at most 1"
+        );
+        assert_eq!(
+            Trace::parse("frmtrace\t1\n").unwrap_err(),
+            "missing machine name"
+        );
+        assert_eq!(
+            Trace::parse("frmtrace\t1\nmachine\tLamp\nevent\tsoon\tturnOn\n").unwrap_err(),
+            "line 3: malformed trace record `event\tsoon\tturnOn`"
+        );
+        assert!(Trace::parse("frmtrace\t1\nevent\t1\tturnOn\n").is_err());
+        assert!(Trace::parse("frmtrace\t1\nmachine\tLamp\nevent\t1\tturnOn\tlevel\n").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Conversions of the values of variables, arguments and return values, which are only known as
+//! `dyn Any`, to and from text, for traces and the tools that expose running machines to other
+//! programs. Values of `bool`, `char`, the primitive integer and float types, and `String` are
+//! supported.
+
+// the JSON snapshots are only used by the tools, which are optional
+#![cfg_attr(not(any(feature = "debug_server", feature = "ffi")), allow(dead_code))]
+
+use crate::env::Environment;
+use crate::event::Event;
+use crate::info::NameInfo;
+use crate::machine::{Machine, State};
+use std::any::Any;
+use std::ops::Deref;
+
+/// Render a snapshot of a machine as a JSON object, with the name of the machine, the name,
+/// arguments and variables of the current state, and the domain variables.
+pub fn snapshot_json<M: Machine + ?Sized>(machine: &M) -> String
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    format!(
+        "{{\"machine\":{},\"state\":{},\"variables\":{}}}",
+        json_string(machine.info().name),
+        state_json(&*machine.state()),
+        variables_json(machine),
+    )
+}
+
+/// Render the values of the domain variables of a machine as a JSON object.
+pub fn variables_json<M: Machine + ?Sized>(machine: &M) -> String
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    environment_json(machine.variables(), machine.info().variables)
+}
+
+/// Render a state as a JSON object with its name, arguments and variables.
+pub fn state_json<M: Machine + ?Sized, S: State<M> + ?Sized>(state: &S) -> String
+where
+    <M::EnvironmentPtr as Deref>::Target: Environment,
+    <M::EventPtr as Deref>::Target: Event<M>,
+    <M::StatePtr as Deref>::Target: State<M>,
+{
+    let info = state.info();
+    format!(
+        "{{\"name\":{},\"arguments\":{},\"variables\":{}}}",
+        json_string(info.name),
+        environment_json(&*state.arguments(), info.parameters),
+        environment_json(&*state.variables(), info.variables),
+    )
+}
+
+/// Parse the text of an argument as a value of the declared type.
+pub fn parse(text: &str, vtype: &str) -> Option<Box<dyn Any>> {
+    fn parse<T: std::str::FromStr + Any>(text: &str) -> Option<Box<dyn Any>> {
+        text.trim()
+            .parse::<T>()
+            .ok()
+            .map(|value| Box::new(value) as Box<dyn Any>)
+    }
+    match vtype.trim() {
+        "bool" => parse::<bool>(text),
+        "char" => parse::<char>(text),
+        "i8" => parse::<i8>(text),
+        "i16" => parse::<i16>(text),
+        "i32" => parse::<i32>(text),
+        "i64" => parse::<i64>(text),
+        "isize" => parse::<isize>(text),
+        "u8" => parse::<u8>(text),
+        "u16" => parse::<u16>(text),
+        "u32" => parse::<u32>(text),
+        "u64" => parse::<u64>(text),
+        "usize" => parse::<usize>(text),
+        "f32" => parse::<f32>(text),
+        "f64" => parse::<f64>(text),
+        "String" => Some(Box::new(text.to_string())),
+        _ => None,
+    }
+}
+
+/// Parse a value rendered as JSON, e.g. by [json], as a value of the declared type. Strings and
+/// characters are JSON strings, other values are parsed as by [parse].
+pub fn parse_json(text: &str, vtype: &str) -> Option<Box<dyn Any>> {
+    match vtype.trim() {
+        "String" | "char" => parse(&unquote(text.trim())?, vtype),
+        _ => parse(text, vtype),
+    }
+}
+
+/// Decode a JSON string, e.g. rendered by [json_string].
+pub fn unquote(json: &str) -> Option<String> {
+    let mut chars = json.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut string = String::with_capacity(json.len());
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => string.push('\n'),
+            'r' => string.push('\r'),
+            't' => string.push('\t'),
+            'b' => string.push('\u{8}'),
+            'f' => string.push('\u{c}'),
+            'u' => {
+                let code: String = chars.by_ref().take(4).collect();
+                string.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            c => string.push(c),
+        }
+    }
+    Some(string)
+}
+
+/// Render the values of the given names in an environment as a JSON object.
+pub fn environment_json<E: Environment + ?Sized>(environment: &E, names: &[NameInfo]) -> String {
+    let fields: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let value = environment
+                .lookup(name.name)
+                .map_or_else(|| "null".to_string(), |value| json(&*value));
+            format!("{}:{}", json_string(name.name), value)
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Render a value as JSON, or as `null` if its type isn't supported.
+pub fn json(value: &dyn Any) -> String {
+    macro_rules! render {
+        ($($t:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$t>() {
+                return value.to_string();
+            })*
+        };
+    }
+    render!(bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    if let Some(value) = value.downcast_ref::<f32>() {
+        return float_json(f64::from(*value));
+    }
+    if let Some(value) = value.downcast_ref::<f64>() {
+        return float_json(*value);
+    }
+    if let Some(value) = value.downcast_ref::<char>() {
+        return json_string(&value.to_string());
+    }
+    if let Some(value) = value.downcast_ref::<String>() {
+        return json_string(value);
+    }
+    if let Some(value) = value.downcast_ref::<&str>() {
+        return json_string(value);
+    }
+    "null".to_string()
+}
+
+fn float_json(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+pub fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_values() {
+        let value = parse(" 42", "i32").unwrap();
+        assert_eq!(value.downcast_ref::<i32>(), Some(&42));
+        let value = parse("true", "bool").unwrap();
+        assert_eq!(value.downcast_ref::<bool>(), Some(&true));
+        let value = parse(" spaced ", "String").unwrap();
+        assert_eq!(value.downcast_ref::<String>().unwrap(), " spaced ");
+        assert!(parse("-1", "u8").is_none());
+        assert!(parse("1", "Vec<u8>").is_none());
+
+        let value = parse_json("\"a\\tb\\u0001\"", "String").unwrap();
+        assert_eq!(value.downcast_ref::<String>().unwrap(), "a\tb\u{1}");
+        let value = parse_json(&json(&'"'), "char").unwrap();
+        assert_eq!(value.downcast_ref::<char>(), Some(&'"'));
+        let value = parse_json("-7", "i64").unwrap();
+        assert_eq!(value.downcast_ref::<i64>(), Some(&-7));
+        assert!(parse_json("unquoted", "String").is_none());
+    }
+
+    #[test]
+    fn renders_values() {
+        assert_eq!(json(&-3i64), "-3");
+        assert_eq!(json(&1.5f32), "1.5");
+        assert_eq!(json(&f64::NAN), "null");
+        assert_eq!(json(&'"'), "\"\\\"\"");
+        assert_eq!(json(&String::from("a\nb\u{1}")), "\"a\\nb\\u0001\"");
+        assert_eq!(json(&vec![1]), "null");
+    }
+}
@@ -323,6 +323,7 @@ mod tests {
                     match t.info.kind {
                         TransitionKind::ChangeState => "->>",
                         TransitionKind::Transition => "->",
+                        TransitionKind::DeepHistory => "->H*",
                     },
                     t.new_state.info().name
                 ));
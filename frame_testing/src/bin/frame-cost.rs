@@ -0,0 +1,66 @@
+//! Report the lines of code generated from the Frame files of a directory, and the time `rustc`
+//! takes to compile the generated Rust code and the size of the object file, for each variant of
+//! the configuration, so the cost of codegen options can be compared.
+//!
+//! ```text
+//! frame-cost [DIR] [--variant <NAME[:KEY=VALUE,...]>]... [--targets <LANG,...>] [--deps <DIR>]
+//!            [--extern <NAME=PATH>]... [--opt-level <LEVEL>] [--no-compile] [--csv]
+//! ```
+
+use anyhow::{bail, Context, Result};
+use frame_testing::cost::{parse_targets, FrameCost, Variant};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: frame-cost [DIR] [--variant <NAME[:KEY=VALUE,...]>]... \
+                     [--targets <LANG,...>] [--deps <DIR>] [--extern <NAME=PATH>]... \
+                     [--opt-level <LEVEL>] [--no-compile] [--csv]";
+
+fn main() -> Result<()> {
+    let mut cost = FrameCost::new();
+    let mut dir = None;
+    let mut csv = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--variant" => {
+                let variant = args.next().context(USAGE)?;
+                cost = cost.add_variant(Variant::parse(&variant)?);
+            }
+            "--targets" => {
+                let targets = args.next().context(USAGE)?;
+                cost = cost.set_targets(&parse_targets(&targets)?);
+            }
+            "--deps" => cost = cost.deps_dir(&PathBuf::from(args.next().context(USAGE)?)),
+            "--extern" => {
+                let library = args.next().context(USAGE)?;
+                let (name, path) = library
+                    .split_once('=')
+                    .with_context(|| format!("invalid library `{}`\n{}", library, USAGE))?;
+                cost = cost.extern_crate(name, &PathBuf::from(path));
+            }
+            "--opt-level" => cost = cost.opt_level(&args.next().context(USAGE)?),
+            "--no-compile" => cost = cost.compile(false),
+            "--csv" => csv = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if dir.is_none() && !arg.starts_with('-') => dir = Some(PathBuf::from(arg)),
+            _ => bail!("unexpected argument `{}`\n{}", arg, USAGE),
+        }
+    }
+    if let Some(dir) = &dir {
+        cost = cost.input_dir(dir);
+    }
+
+    let report = cost.run()?;
+    if report.files.is_empty() {
+        bail!("no Frame files found");
+    }
+    if csv {
+        print!("{}", report.render_csv());
+    } else {
+        print!("{}", report.render());
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Report the states and transitions of each machine that a test run didn't exercise, from the
+//! coverage files written by `frame_runtime::recorder::CoverageRecorder`, and fail if the
+//! transition coverage of a machine is below a threshold.
+//!
+//! ```text
+//! frame-coverage <DIR> [--min-transitions <PERCENT>]
+//! ```
+
+use anyhow::{bail, Context, Result};
+use frame_runtime::recorder::CoverageReport;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: frame-coverage <DIR> [--min-transitions <PERCENT>]";
+
+fn main() -> Result<()> {
+    let mut dir = None;
+    let mut min_transitions = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-transitions" => {
+                let percent = args.next().context(USAGE)?;
+                let percent: f64 = percent
+                    .parse()
+                    .with_context(|| format!("invalid percentage `{}`", percent))?;
+                min_transitions = Some(percent);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if dir.is_none() && !arg.starts_with('-') => dir = Some(PathBuf::from(arg)),
+            _ => bail!("unexpected argument `{}`\n{}", arg, USAGE),
+        }
+    }
+    let dir = dir.context(USAGE)?;
+
+    let report = CoverageReport::read_dir(&dir)
+        .with_context(|| format!("failed to read the coverage in {}", dir.display()))?;
+    if report.machines.is_empty() {
+        bail!("no coverage files in {}", dir.display());
+    }
+    print!("{}", report.render());
+
+    if let Some(min) = min_transitions {
+        let below = report.below(min);
+        if !below.is_empty() {
+            let names: Vec<&str> = below.iter().map(|m| m.machine.as_str()).collect();
+            bail!(
+                "transition coverage is below {}% for {}",
+                min,
+                names.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
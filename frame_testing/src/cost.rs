@@ -0,0 +1,575 @@
+//! Measure the cost of codegen options: the lines of code generated from a corpus of Frame files
+//! for each target language, and the time `rustc` takes to compile the generated Rust code and the
+//! size of the resulting object file, for each of a set of variants of the configuration.
+
+use anyhow::{bail, Context, Error, Result};
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use framec::frame_c::config::FrameConfig;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+use walkdir::WalkDir;
+
+/// A named set of configuration settings whose cost is measured.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+
+    /// The overridden settings, by the path of their keys, with values written in YAML.
+    pub settings: Vec<(String, String)>,
+}
+
+impl Variant {
+    /// Parse a variant written as its name, optionally followed by a colon and comma-separated
+    /// settings, e.g. `sync:runtime_support=true,thread_safe=true`. Keys without a dot are
+    /// features of the Rust backend, e.g. `codegen.rust.features.thread_safe`.
+    pub fn parse(variant: &str) -> Result<Variant> {
+        let (name, settings) = match variant.split_once(':') {
+            Some((name, settings)) => (name, settings),
+            None => (variant, ""),
+        };
+        if name.is_empty() {
+            bail!("variant `{}` has no name", variant);
+        }
+        let settings = settings
+            .split(',')
+            .filter(|setting| !setting.is_empty())
+            .map(|setting| match setting.split_once('=') {
+                Some((key, value)) if key.contains('.') => Ok((key.to_string(), value.to_string())),
+                Some((key, value)) => {
+                    Ok((format!("codegen.rust.features.{}", key), value.to_string()))
+                }
+                None => bail!("setting `{}` of variant `{}` has no value", setting, name),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Variant {
+            name: name.to_string(),
+            settings,
+        })
+    }
+
+    /// The variants measured by default: the default configuration, and the runtime support of
+    /// the Rust backend without and with thread safety.
+    pub fn defaults() -> Vec<Variant> {
+        [
+            "default",
+            "runtime:runtime_support=true",
+            "sync:runtime_support=true,thread_safe=true",
+        ]
+        .iter()
+        .map(|variant| Variant::parse(variant).unwrap())
+        .collect()
+    }
+
+    /// The configuration of this variant, based on the given one.
+    fn config(&self, base: &FrameConfig) -> Result<FrameConfig> {
+        let mut config = base.clone();
+        for (key, value) in &self.settings {
+            config = config
+                .with_setting(key, value)
+                .with_context(|| format!("invalid setting `{}` of variant `{}`", key, self.name))?;
+        }
+        Ok(config)
+    }
+}
+
+/// The cost of a Frame file in one variant.
+#[derive(Clone, Debug)]
+pub struct Cost {
+    /// The number of non-blank lines generated for each target, or the first error of Framec.
+    pub lines: Vec<Result<usize, String>>,
+
+    /// The time `rustc` took to compile the generated Rust code and the size of the object file,
+    /// or the error of `rustc`, if the Rust code was compiled.
+    pub compiled: Option<Result<(Duration, u64), String>>,
+}
+
+/// The costs of a corpus of Frame files, for each variant.
+#[derive(Clone, Debug)]
+pub struct CostReport {
+    pub targets: Vec<TargetLanguage>,
+    pub variants: Vec<Variant>,
+
+    /// The costs of each Frame file, in the order of the variants.
+    pub files: Vec<(PathBuf, Vec<Cost>)>,
+}
+
+/// Create, configure, and run a measurement of the cost of codegen options. The methods of this
+/// struct are designed to be chained like those of [`FrameSnapshots`](crate::FrameSnapshots).
+pub struct FrameCost {
+    input_dir: PathBuf,
+    targets: Vec<TargetLanguage>,
+    variants: Vec<Variant>,
+    compile: bool,
+    deps_dir: Option<PathBuf>,
+    externs: Vec<(String, PathBuf)>,
+    opt_level: String,
+}
+
+impl Default for FrameCost {
+    fn default() -> Self {
+        FrameCost::new()
+    }
+}
+
+impl FrameCost {
+    /// Construct a new default configuration, which measures the Frame files of the `src`
+    /// directory, compiled to Rust in the default variants, with `rustc -C opt-level=2`.
+    pub fn new() -> Self {
+        FrameCost {
+            input_dir: PathBuf::from("src"),
+            targets: vec![TargetLanguage::Rust],
+            variants: Vec::new(),
+            compile: true,
+            deps_dir: None,
+            externs: Vec::new(),
+            opt_level: "2".to_string(),
+        }
+    }
+
+    /// Set the directory searched for `.frm` files.
+    pub fn input_dir(mut self, path: &Path) -> Self {
+        self.input_dir = path.to_path_buf();
+        self
+    }
+
+    /// Set the target languages whose generated lines are counted.
+    pub fn set_targets(mut self, targets: &[TargetLanguage]) -> Self {
+        self.targets = targets.to_vec();
+        self
+    }
+
+    /// Add a variant to measure. The first variant is the baseline of the others. If no variant
+    /// is added, [`Variant::defaults`] are measured.
+    pub fn add_variant(mut self, variant: Variant) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    /// Set whether to compile the generated Rust code with `rustc`.
+    pub fn compile(mut self, compile: bool) -> Self {
+        self.compile = compile;
+        self
+    }
+
+    /// Set the directory of compiled dependencies of the generated code, e.g. `target/debug/deps`
+    /// after `cargo build -p frame_runtime`. The generated code of variants with runtime support
+    /// can only be compiled if it contains the `frame_runtime` and `once_cell` libraries. Unless
+    /// they are set by [`FrameCost::extern_crate`], the most recently built `frame_runtime` is
+    /// used, with the `once_cell` it was built against.
+    pub fn deps_dir(mut self, path: &Path) -> Self {
+        self.deps_dir = Some(path.to_path_buf());
+        self
+    }
+
+    /// Set the compiled library of a crate used by the generated code, as `rustc --extern`.
+    pub fn extern_crate(mut self, name: &str, path: &Path) -> Self {
+        self.externs.push((name.to_string(), path.to_path_buf()));
+        self
+    }
+
+    /// Set the optimization level passed to `rustc`.
+    pub fn opt_level(mut self, opt_level: &str) -> Self {
+        self.opt_level = opt_level.to_string();
+        self
+    }
+
+    /// Measure the cost of each Frame file in each variant. Errors of Framec and `rustc` are part
+    /// of the report; only errors reading the files or in the variants are returned.
+    pub fn run(&self) -> Result<CostReport> {
+        let variants = if self.variants.is_empty() {
+            Variant::defaults()
+        } else {
+            self.variants.clone()
+        };
+        let exes = variants
+            .iter()
+            .map(|variant| {
+                Ok(Exe::with_options(CompilerOptions {
+                    config: variant.config(&FrameConfig::default())?,
+                    load_local_config: false,
+                    ..CompilerOptions::default()
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut input_paths = Vec::new();
+        for entry in WalkDir::new(&self.input_dir).sort_by_file_name() {
+            let entry = entry?;
+            if entry.path().extension().unwrap_or_default() == "frm" {
+                input_paths.push(entry.path().to_path_buf());
+            }
+        }
+
+        let externs = self.resolve_externs();
+        let scratch_dir = env::temp_dir().join(format!("frame-cost-{}", std::process::id()));
+        fs::create_dir_all(&scratch_dir)?;
+        let mut files = Vec::new();
+        for input_path in input_paths {
+            let mut costs = Vec::new();
+            for exe in &exes {
+                let mut lines = Vec::new();
+                let mut compiled = None;
+                for target in &self.targets {
+                    match exe.run_file(&None, &input_path, Some(*target)) {
+                        Ok(code) => {
+                            lines.push(Ok(code.lines().filter(|l| !l.trim().is_empty()).count()));
+                            if self.compile && *target == TargetLanguage::Rust {
+                                compiled = Some(self.rustc(&code, &externs, &scratch_dir));
+                            }
+                        }
+                        Err(err) => {
+                            // the path is already part of the report
+                            let mut diagnostic = err.diagnostics[0].clone();
+                            diagnostic.file = None;
+                            lines.push(Err(diagnostic.to_string()))
+                        }
+                    }
+                }
+                costs.push(Cost { lines, compiled });
+            }
+            files.push((input_path, costs));
+        }
+        let _ = fs::remove_dir_all(&scratch_dir);
+
+        Ok(CostReport {
+            targets: self.targets.clone(),
+            variants,
+            files,
+        })
+    }
+
+    /// Compile the generated Rust code to an object file, and return the time it took and its
+    /// size.
+    fn rustc(
+        &self,
+        code: &str,
+        externs: &[(String, PathBuf)],
+        scratch_dir: &Path,
+    ) -> Result<(Duration, u64), String> {
+        let source_path = scratch_dir.join("machine.rs");
+        let object_path = scratch_dir.join("machine.o");
+        fs::write(&source_path, code).map_err(|err| err.to_string())?;
+        let mut command = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()));
+        command
+            .args(["--edition", "2018", "--crate-type", "lib", "--crate-name"])
+            .arg("machine")
+            .args(["--emit", "obj", "--cap-lints", "allow", "-C"])
+            .arg(format!("opt-level={}", self.opt_level))
+            .arg("-o")
+            .arg(&object_path)
+            .arg(&source_path);
+        if let Some(deps_dir) = &self.deps_dir {
+            command
+                .arg("-L")
+                .arg(format!("dependency={}", deps_dir.display()));
+        }
+        for (name, path) in externs {
+            command
+                .arg("--extern")
+                .arg(format!("{}={}", name, path.display()));
+        }
+
+        let start = Instant::now();
+        let output = command.output().map_err(|err| err.to_string())?;
+        let time = start.elapsed();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // the first error is enough to tell why, e.g. a missing dependency
+            let error = stderr
+                .lines()
+                .find(|line| line.starts_with("error"))
+                .unwrap_or("rustc failed");
+            return Err(error.to_string());
+        }
+        let size = fs::metadata(&object_path)
+            .map_err(|err| err.to_string())?
+            .len();
+        Ok((time, size))
+    }
+
+    /// The libraries passed to `rustc --extern`: the ones set explicitly, and the dependencies of
+    /// the generated code found in the dependencies directory.
+    fn resolve_externs(&self) -> Vec<(String, PathBuf)> {
+        let mut externs = self.externs.clone();
+        let deps_dir = match &self.deps_dir {
+            Some(deps_dir) => deps_dir,
+            None => return externs,
+        };
+        let is_set = |name: &str| self.externs.iter().any(|(set, _)| set == name);
+        let runtime = match self
+            .externs
+            .iter()
+            .find(|(name, _)| name == "frame_runtime")
+        {
+            Some((_, path)) => Some(path.clone()),
+            None => libraries(deps_dir, "frame_runtime")
+                .into_iter()
+                .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok()),
+        };
+        if let Some(runtime) = runtime {
+            if !is_set("once_cell") {
+                // a target directory often holds several builds of a dependency, and only the one
+                // `frame_runtime` was built against links with it; its metadata names that build
+                let metadata = fs::read(&runtime).unwrap_or_default();
+                let once_cell = libraries(deps_dir, "once_cell").into_iter().find(|path| {
+                    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                    let suffix = stem.trim_start_matches("libonce_cell").as_bytes();
+                    metadata
+                        .windows(suffix.len())
+                        .any(|window| window == suffix)
+                });
+                if let Some(once_cell) = once_cell {
+                    externs.push(("once_cell".to_string(), once_cell));
+                }
+            }
+            if !is_set("frame_runtime") {
+                externs.push(("frame_runtime".to_string(), runtime));
+            }
+        }
+        externs
+    }
+}
+
+/// The `.rlib` files of a library in a directory of dependencies.
+fn libraries(deps_dir: &Path, library: &str) -> Vec<PathBuf> {
+    let prefix = format!("lib{}-", library);
+    fs::read_dir(deps_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    name.starts_with(&prefix) && name.ends_with(".rlib")
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl CostReport {
+    /// The column headers of the report.
+    fn headers(&self) -> Vec<String> {
+        let mut headers = vec!["file".to_string(), "variant".to_string()];
+        for target in &self.targets {
+            headers.push(format!("{} lines", target.name()));
+        }
+        if self.is_compiled() {
+            headers.push("rustc ms".to_string());
+            headers.push("object bytes".to_string());
+        }
+        headers
+    }
+
+    fn is_compiled(&self) -> bool {
+        self.files
+            .iter()
+            .flat_map(|(_, costs)| costs)
+            .any(|cost| cost.compiled.is_some())
+    }
+
+    /// The measurements of a cost, in the order of the columns after the file and variant, or
+    /// `None` for the errors.
+    fn values(&self, cost: &Cost) -> Vec<Option<u64>> {
+        let mut values: Vec<Option<u64>> = cost
+            .lines
+            .iter()
+            .map(|lines| lines.as_ref().ok().map(|lines| *lines as u64))
+            .collect();
+        if self.is_compiled() {
+            match &cost.compiled {
+                Some(Ok((time, size))) => {
+                    values.push(Some(time.as_millis() as u64));
+                    values.push(Some(*size));
+                }
+                _ => values.extend([None, None]),
+            }
+        }
+        values
+    }
+
+    /// The total measurements of each variant over the files, summing only the files measured in
+    /// every variant, or `None` for the columns without such files.
+    fn totals(&self) -> Vec<Vec<Option<u64>>> {
+        let columns = self.headers().len() - 2;
+        let mut totals = vec![vec![None; columns]; self.variants.len()];
+        for column in 0..columns {
+            for (_, costs) in &self.files {
+                let values: Vec<Option<u64>> =
+                    costs.iter().map(|cost| self.values(cost)[column]).collect();
+                if values.iter().all(Option::is_some) {
+                    for (total, value) in totals.iter_mut().zip(values) {
+                        total[column] = Some(total[column].unwrap_or(0) + value.unwrap());
+                    }
+                }
+            }
+        }
+        totals
+    }
+
+    /// Render the report as a table, with the difference of each measurement from the first
+    /// variant, and the errors after the table.
+    pub fn render(&self) -> String {
+        let mut rows = vec![self.headers()];
+        let mut errors = Vec::new();
+        let mut add_rows = |file: &str, measurements: &[Vec<Option<u64>>], missing: &str| {
+            for (i, values) in measurements.iter().enumerate() {
+                let mut row = vec![
+                    if i == 0 {
+                        file.to_string()
+                    } else {
+                        String::new()
+                    },
+                    self.variants[i].name.clone(),
+                ];
+                for (column, value) in values.iter().enumerate() {
+                    let baseline = measurements[0][column];
+                    row.push(match (value, baseline) {
+                        (None, _) => missing.to_string(),
+                        (Some(value), Some(baseline)) if i > 0 => {
+                            format!("{} ({})", value, difference(*value, baseline))
+                        }
+                        (Some(value), _) => value.to_string(),
+                    });
+                }
+                rows.push(row);
+            }
+        };
+        for (path, costs) in &self.files {
+            let measurements: Vec<Vec<Option<u64>>> =
+                costs.iter().map(|cost| self.values(cost)).collect();
+            add_rows(&path.display().to_string(), &measurements, "error");
+            for (variant, cost) in self.variants.iter().zip(costs) {
+                for (target, lines) in self.targets.iter().zip(&cost.lines) {
+                    if let Err(err) = lines {
+                        errors.push(format!(
+                            "{} ({}, {}): {}",
+                            path.display(),
+                            variant.name,
+                            target.name(),
+                            err
+                        ));
+                    }
+                }
+                if let Some(Err(err)) = &cost.compiled {
+                    errors.push(format!(
+                        "{} ({}, rustc): {}",
+                        path.display(),
+                        variant.name,
+                        err
+                    ));
+                }
+            }
+        }
+        if self.files.len() > 1 {
+            add_rows("total", &self.totals(), "-");
+        }
+
+        let mut widths = vec![0; rows[0].len()];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let mut table = String::new();
+        for row in &rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            table.push_str(cells.join("  ").trim_end());
+            table.push('\n');
+        }
+        for error in errors {
+            table.push_str(&format!("\n{}", error));
+        }
+        if !table.ends_with('\n') {
+            table.push('\n');
+        }
+        table
+    }
+
+    /// Render the measurements as CSV, with empty cells for the errors.
+    pub fn render_csv(&self) -> String {
+        let mut csv = self.headers().join(",");
+        csv.push('\n');
+        for (path, costs) in &self.files {
+            for (variant, cost) in self.variants.iter().zip(costs) {
+                let mut row = vec![path.display().to_string(), variant.name.clone()];
+                row.extend(
+                    self.values(cost)
+                        .iter()
+                        .map(|value| value.map(|value| value.to_string()).unwrap_or_default()),
+                );
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+            }
+        }
+        csv
+    }
+}
+
+/// The relative difference of a measurement from its baseline, e.g. `+25%`.
+fn difference(value: u64, baseline: u64) -> String {
+    if baseline == 0 {
+        return if value == 0 { "+0%" } else { "new" }.to_string();
+    }
+    let percent = ((value as f64 - baseline as f64) * 100.0 / baseline as f64).round();
+    // avoid `-0%` for small decreases
+    format!("{:+}%", percent as i64)
+}
+
+/// Parse target language names separated by commas, e.g. `rust,smcat`.
+pub fn parse_targets(targets: &str) -> Result<Vec<TargetLanguage>> {
+    targets
+        .split(',')
+        .map(|target| TargetLanguage::try_from(target.trim()).map_err(Error::msg))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variants() {
+        let variant =
+            Variant::parse("sync:runtime_support=true,codegen.rust.code.state_var_name=s").unwrap();
+        assert_eq!(variant.name, "sync");
+        assert_eq!(
+            variant.settings,
+            vec![
+                (
+                    "codegen.rust.features.runtime_support".to_string(),
+                    "true".to_string()
+                ),
+                (
+                    "codegen.rust.code.state_var_name".to_string(),
+                    "s".to_string()
+                ),
+            ]
+        );
+        let config = variant.config(&FrameConfig::default()).unwrap();
+        assert!(config.codegen.rust.features.runtime_support);
+        assert_eq!(Variant::parse("default").unwrap().settings, vec![]);
+        assert!(Variant::parse("sync:thread_safe").is_err());
+        assert!(Variant::parse(":thread_safe=true").is_err());
+        assert!(Variant::parse("typo:thread_sfe=true")
+            .unwrap()
+            .config(&FrameConfig::default())
+            .is_err());
+    }
+
+    #[test]
+    fn differences() {
+        assert_eq!(difference(150, 100), "+50%");
+        assert_eq!(difference(75, 100), "-25%");
+        assert_eq!(difference(0, 0), "+0%");
+        assert_eq!(difference(3, 0), "new");
+        assert_eq!(difference(442, 443), "+0%");
+    }
+}
@@ -0,0 +1,396 @@
+//! This crate tests the code Framec generates from a directory of Frame files against snapshots
+//! of it checked in next to the tests.
+//!
+//! # What does it do?
+//!
+//! This crate is intended to be used from a test of your package.
+//!
+//! By default, it will traverse your package's `src` directory, searching for Frame (`.frm`) files
+//! to compile into Rust using the Frame transpiler, Framec, and compare the generated code with
+//! its snapshot in the `snapshots` directory. The snapshots of a Frame file are stored in a
+//! directory named after it, at the relative position where the file was found, with one file
+//! per target language named after it. For example, the Rust code generated from the Frame file
+//! `src/a/b/sm.frm` is compared with `snapshots/a/b/sm/rust.rs`. When Framec fails, its error is
+//! the snapshot instead, e.g. `snapshots/a/b/sm/rust.error`.
+//!
+//! A changed snapshot fails the test, so the changes to the generated code of every Frame file
+//! show up in review, not only the changes to its behavior checked by runtime assertions.
+//!
+//!
+//! # How to use it?
+//!
+//! First, add the following to your `Cargo.toml` file:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! frame_testing = "0.8"
+//! ```
+//!
+//! Then add a test that checks the snapshots, e.g. in `tests/snapshots.rs`.
+//!
+//! ```no_run
+//! use frame_testing::FrameSnapshots;
+//!
+//! #[test]
+//! fn snapshots() {
+//!     FrameSnapshots::new().run().unwrap();
+//! }
+//! ```
+//!
+//! The process can be configured by various methods on the [`FrameSnapshots`] struct, which are
+//! designed to be chained like those of `frame_build`. For example, the following test checks
+//! the Rust and [smcat](https://state-machine-cat.js.org/) code generated from the Frame files of
+//! the package's `specs` directory.
+//!
+//! ```no_run
+//! use frame_testing::{FrameSnapshots, TargetLanguage};
+//! use std::path::Path;
+//!
+//! #[test]
+//! fn snapshots() {
+//!     FrameSnapshots::new()
+//!         .input_dir(Path::new("specs"))
+//!         .add_target(TargetLanguage::Smcat)
+//!         .run()
+//!         .unwrap();
+//! }
+//! ```
+//!
+//!
+//! # Updating snapshots
+//!
+//! Missing snapshots fail the test too. To create or update the snapshots after an intended
+//! change, run the tests with the `FRAMEC_BLESS` environment variable set, e.g.
+//!
+//! ```text
+//! FRAMEC_BLESS=1 cargo test --test snapshots
+//! ```
+//!
+//! and review the changes to the snapshots before checking them in. Snapshots of target
+//! languages that are no longer generated, e.g. the error of a Frame file that compiles now, are
+//! removed.
+//!
+//!
+//! # Inline specs
+//!
+//! Small machines can also live next to the tests that use them. The [`compile_frame_str!`]
+//! macro compiles a spec given as a string when the test runs and returns the generated code, or
+//! fails the test with Framec's error, so a test can check the code generated for a feature
+//! without a Frame file or `build.rs` plumbing.
+//!
+//! ```
+//! use frame_testing::{compile_frame_str, TargetLanguage};
+//!
+//! let code = compile_frame_str!("#Lamp\n    -machine-\n    $Off\n##\n");
+//! assert!(code.contains("pub struct Lamp"));
+//! let smcat = compile_frame_str!("#Lamp\n    -machine-\n    $Off\n##\n", TargetLanguage::Smcat);
+//! assert!(smcat.contains("Off"));
+//! ```
+//!
+//! [`compile_str`] returns the error instead, for tests of specs that should fail to compile.
+//!
+//!
+//! # Cost of codegen options
+//!
+//! The [`cost`] module measures what codegen options such as `runtime_support` cost: the lines
+//! generated from each Frame file, and the time `rustc` takes to compile the generated code and
+//! the size of the object file, for each variant of the configuration. The `frame-cost` binary
+//! prints the report, e.g.
+//!
+//! ```text
+//! cargo build -p frame_runtime
+//! frame-cost src --deps target/debug/deps --variant default --variant sync:runtime_support=true,thread_safe=true
+//! ```
+
+use anyhow::{Error, Result};
+use framec::frame_c::compiler::Exe;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use walkdir::WalkDir;
+
+pub mod cost;
+
+// re-export types here that are part of the `frame_testing` interface
+pub use framec::frame_c::compiler::{CompilerOptions, TargetLanguage};
+pub use framec::frame_c::config::FrameConfig;
+
+/// Create, configure, and run a snapshot test. The methods associated with this struct are
+/// designed to be chained to override the default configuration. After the test has been
+/// configured, the [`FrameSnapshots::run`] method compiles the Frame files and compares the
+/// generated code with the snapshots.
+pub struct FrameSnapshots {
+    compiler_options: CompilerOptions,
+    frame_config: Option<PathBuf>,
+    input_dir: PathBuf,
+    snapshot_dir: PathBuf,
+    targets: Vec<TargetLanguage>,
+    input_filter: Box<dyn Fn(&Path) -> bool>,
+    bless: bool,
+}
+
+impl Default for FrameSnapshots {
+    fn default() -> Self {
+        FrameSnapshots::new()
+    }
+}
+
+impl FrameSnapshots {
+    /// Construct a new default configuration.
+    pub fn new() -> Self {
+        FrameSnapshots {
+            compiler_options: CompilerOptions::default(),
+            frame_config: None,
+            input_dir: PathBuf::from("src"),
+            snapshot_dir: PathBuf::from("snapshots"),
+            targets: vec![TargetLanguage::Rust],
+            input_filter: Box::new(|_| true),
+            bless: env::var_os("FRAMEC_BLESS").is_some(),
+        }
+    }
+
+    /// Add a Frame `config.yaml` file to pass to Framec.
+    ///
+    /// If unset, Framec will look for the file in the current working directory.
+    pub fn frame_config(mut self, path: &Path) -> Self {
+        self.frame_config = Some(path.to_path_buf());
+        self
+    }
+
+    /// Configure Framec in code. The configuration in the options takes the place of the default
+    /// configuration; a `config.yaml` file and the attributes in each Frame spec are merged on top
+    /// of it.
+    pub fn compiler_options(mut self, options: CompilerOptions) -> Self {
+        self.compiler_options = options;
+        self
+    }
+
+    /// Set the root input directory to traverse, searching for `.frm` files. The path of each
+    /// Frame file is passed to Framec as found, so a relative directory keeps the paths that end
+    /// up in the generated code independent of where the package is checked out.
+    ///
+    /// If unset, we will search the project's `src` directory.
+    pub fn input_dir(mut self, path: &Path) -> Self {
+        self.input_dir = path.to_path_buf();
+        self
+    }
+
+    /// Set the root directory of the snapshots. The snapshots of each Frame file are stored in a
+    /// directory at a relative path within this directory corresponding to the file's location in
+    /// the input directory, named after the file. This may be the input directory itself, to
+    /// keep the snapshots next to the Frame files.
+    ///
+    /// If unset, the snapshots are stored in the project's `snapshots` directory.
+    pub fn snapshot_dir(mut self, path: &Path) -> Self {
+        self.snapshot_dir = path.to_path_buf();
+        self
+    }
+
+    /// Set the list of target languages to compile each Frame file to using Framec.
+    ///
+    /// By default, each Frame file is compiled to only Rust. Use this method if Rust output is not
+    /// desired. Otherwise, additional targets can be added with [`FrameSnapshots::add_target`].
+    pub fn set_targets(mut self, targets: &[TargetLanguage]) -> Self {
+        self.targets = targets.to_vec();
+        self
+    }
+
+    /// Add an additional target language to compile to.
+    pub fn add_target(mut self, target: TargetLanguage) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Set a function that filters the Frame files found in the input directory based on their
+    /// paths. For the path of each Frame file found, if this function returns `true`, its
+    /// snapshots are checked. If the function returns `false`, it is skipped.
+    pub fn include_only_if(mut self, filter: impl Fn(&Path) -> bool + 'static) -> Self {
+        self.input_filter = Box::new(filter);
+        self
+    }
+
+    /// Set whether to write the generated code to the snapshots instead of comparing them.
+    ///
+    /// By default, the snapshots are written if the `FRAMEC_BLESS` environment variable is set.
+    pub fn bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Run the snapshot test. Every Frame file is compiled to every target language, even if
+    /// some of the snapshots don't match.
+    ///
+    /// On success, this function returns a vector of paths to each of the snapshots. Otherwise,
+    /// the error lists the snapshots that don't match, with the first difference of each.
+    ///
+    /// Only the code of the generated file is part of the snapshots, not the files of its state
+    /// modules or its code map.
+    pub fn run(&self) -> Result<Vec<PathBuf>> {
+        let mut snapshots = Vec::new();
+        let mut failures = Vec::new();
+        // one compiler for all files, so that the configuration file is only read once
+        let exe = Exe::with_options(self.compiler_options.clone());
+
+        let mut input_paths = Vec::new();
+        for entry in WalkDir::new(&self.input_dir).sort_by_file_name() {
+            let entry = entry?;
+            let input_path = entry.path();
+            if input_path.extension().unwrap_or_default() == "frm"
+                && (&self.input_filter)(input_path)
+            {
+                input_paths.push(input_path.to_path_buf());
+            }
+        }
+
+        // silence the panics of Framec, they are part of the snapshots
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+
+        for input_path in &input_paths {
+            let local_path = input_path.strip_prefix(&self.input_dir)?;
+            let fixture_dir = self.snapshot_dir.join(local_path.with_extension(""));
+            if self.bless {
+                fs::create_dir_all(&fixture_dir)?;
+            }
+
+            let mut expected_paths = Vec::new();
+            for target in &self.targets {
+                let (output, extension) = self.generate(&exe, input_path, *target);
+                let snapshot_path = fixture_dir.join(format!("{}.{}", target.name(), extension));
+                expected_paths.push(snapshot_path.clone());
+                if self.bless {
+                    fs::write(&snapshot_path, output)?;
+                    continue;
+                }
+                match fs::read_to_string(&snapshot_path) {
+                    Ok(snapshot) if snapshot == output => {}
+                    Ok(snapshot) => failures.push(format!(
+                        "{} differs at {}",
+                        snapshot_path.display(),
+                        first_difference(&snapshot, &output)
+                    )),
+                    Err(_) => failures.push(format!("{} is missing", snapshot_path.display())),
+                }
+            }
+
+            // snapshots of the targets that are no longer generated, e.g. because Framec stopped
+            // failing; other files of the directory are left alone
+            if let Ok(entries) = fs::read_dir(&fixture_dir) {
+                for entry in entries {
+                    let path = entry?.path();
+                    let is_snapshot = self.targets.iter().any(|target| {
+                        path.file_stem() == Some(target.name().as_ref()) && path.is_file()
+                    });
+                    if !is_snapshot || expected_paths.contains(&path) {
+                        continue;
+                    }
+                    if self.bless {
+                        fs::remove_file(&path)?;
+                    } else {
+                        failures.push(format!("{} is not generated", path.display()));
+                    }
+                }
+            }
+            snapshots.extend(expected_paths);
+        }
+
+        panic::set_hook(default_hook);
+        if failures.is_empty() {
+            Ok(snapshots)
+        } else {
+            Err(Error::msg(format!(
+                "{} snapshot(s) don't match, run with FRAMEC_BLESS=1 to update them:\n{}",
+                failures.len(),
+                failures.join("\n")
+            )))
+        }
+    }
+
+    /// Compile a Frame file to a target and return the generated code or the error, along with
+    /// the extension of the snapshot.
+    fn generate(&self, exe: &Exe, input_path: &Path, target: TargetLanguage) -> (String, String) {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            exe.run_file(&self.frame_config, input_path, Some(target))
+        }));
+        match result {
+            Ok(Ok(code)) => (code, exe.file_extension(target)),
+            Ok(Err(err)) => (
+                format!("error {}: {}\n", err.code, err.error),
+                "error".to_string(),
+            ),
+            Err(payload) => (
+                format!("panic: {}\n", panic_message(&*payload)),
+                "error".to_string(),
+            ),
+        }
+    }
+}
+
+/// Compile a Frame spec given as a string to a target language, and return the generated code.
+///
+/// The spec is compiled without a `config.yaml` file, so that the result doesn't depend on the
+/// directory the test runs in; configure it with attributes in the spec instead. A panic of
+/// Framec is returned as an error like the errors it reports.
+pub fn compile_str(spec: &str, target: TargetLanguage) -> Result<String> {
+    let exe = Exe::with_options(CompilerOptions {
+        load_local_config: false,
+        ..CompilerOptions::default()
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        exe.run(&None, None, spec.to_string(), Some(target))
+    }));
+    match result {
+        Ok(Ok(code)) => Ok(code),
+        Ok(Err(err)) => Err(Error::msg(format!("error {}: {}", err.code, err.error))),
+        Err(payload) => Err(Error::msg(format!("panic: {}", panic_message(&*payload)))),
+    }
+}
+
+/// Compile a Frame spec given as a string when the test runs, and return the generated code.
+/// The target language is Rust unless given as a second argument. If Framec fails, the test
+/// fails with its error.
+///
+/// See [`compile_str`] for how the spec is compiled.
+#[macro_export]
+macro_rules! compile_frame_str {
+    ($spec:expr) => {
+        $crate::compile_frame_str!($spec, $crate::TargetLanguage::Rust)
+    };
+    ($spec:expr, $target:expr) => {
+        match $crate::compile_str($spec, $target) {
+            Ok(code) => code,
+            Err(err) => panic!("failed to compile the Frame spec: {}", err),
+        }
+    };
+}
+
+/// The message of a panic payload, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// Describe the first difference between the snapshot and the generated code.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) => return "line endings differ".to_string(),
+            (e, a) => {
+                return format!(
+                    "line {}:\n  - {}\n  + {}",
+                    line,
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests of measuring the cost of codegen options.
+
+use frame_testing::cost::{FrameCost, Variant};
+use frame_testing::TargetLanguage;
+use std::{env, fs};
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+#[test]
+fn cost_report() {
+    let dir = env::temp_dir().join(format!("frame-cost-test-{}", std::process::id()));
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    fs::write(dir.join("lamp.frm"), SPEC).unwrap();
+    fs::write(
+        dir.join("nested/broken.frm"),
+        "#Broken\n-interface-\nfoo [\n##\n",
+    )
+    .unwrap();
+
+    let report = FrameCost::new()
+        .input_dir(&dir)
+        .set_targets(&[TargetLanguage::Rust, TargetLanguage::Smcat])
+        .add_variant(Variant::parse("default").unwrap())
+        .add_variant(Variant::parse("runtime:runtime_support=true").unwrap())
+        .run()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.files.len(), 2);
+    let (path, costs) = &report.files[0];
+    assert!(path.ends_with("lamp.frm"));
+    let default_lines = *costs[0].lines[0].as_ref().unwrap();
+    let runtime_lines = *costs[1].lines[0].as_ref().unwrap();
+    assert!(runtime_lines > default_lines);
+    assert_eq!(costs[0].lines[1], costs[1].lines[1]);
+    let (_, size) = costs[0].compiled.as_ref().unwrap().as_ref().unwrap();
+    assert!(*size > 0);
+    // the runtime library isn't available without a dependencies directory
+    assert!(costs[1].compiled.as_ref().unwrap().is_err());
+    assert!(report.files[1].1[0].lines[0].is_err());
+
+    let table = report.render();
+    assert!(table.starts_with("file"));
+    assert!(table.contains(&format!("runtime  {} (+", runtime_lines)));
+    assert!(table
+        .contains("broken.frm (default, rust): 4:1: error[parse-error]: Expected parameter name."));
+    let csv = report.render_csv();
+    assert!(csv.starts_with("file,variant,rust lines,smcat lines,rustc ms,object bytes\n"));
+    assert_eq!(csv.lines().count(), 5);
+}
@@ -0,0 +1,36 @@
+//! Tests of compiling inline specs.
+
+use frame_testing::{compile_frame_str, compile_str, TargetLanguage};
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+#[test]
+fn compile_inline_spec() {
+    let code = compile_frame_str!(SPEC);
+    assert!(code.contains("pub fn toggle(&mut self)"));
+    assert_eq!(code, compile_str(SPEC, TargetLanguage::Rust).unwrap());
+
+    let smcat = compile_frame_str!(SPEC, TargetLanguage::Smcat);
+    assert!(smcat.contains("Off -> On"));
+}
+
+#[test]
+fn inline_spec_errors() {
+    let err = compile_str("#Lamp\n-interface-\nfoo [\n##\n", TargetLanguage::Rust).unwrap_err();
+    assert!(err.to_string().starts_with("error 1: "));
+}
+
+#[test]
+#[should_panic(expected = "failed to compile the Frame spec: error 1: ")]
+fn inline_spec_fails_test() {
+    compile_frame_str!("#Lamp\n-interface-\nfoo [\n##\n");
+}
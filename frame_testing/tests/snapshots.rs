@@ -0,0 +1,73 @@
+//! Tests of the snapshot workflow: blessing, mismatches and snapshots that are no longer
+//! generated.
+
+use frame_testing::{CompilerOptions, FrameSnapshots, TargetLanguage};
+use std::fs;
+use std::path::Path;
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+fn snapshots(dir: &Path, bless: bool) -> FrameSnapshots {
+    FrameSnapshots::new()
+        .compiler_options(CompilerOptions {
+            load_local_config: false,
+            ..CompilerOptions::default()
+        })
+        .input_dir(&dir.join("specs"))
+        .snapshot_dir(&dir.join("snapshots"))
+        .add_target(TargetLanguage::Smcat)
+        .bless(bless)
+}
+
+#[test]
+fn bless_and_compare() {
+    let dir = std::env::temp_dir().join(format!("frame_testing_{}", std::process::id()));
+    fs::create_dir_all(dir.join("specs/sub")).unwrap();
+    fs::write(dir.join("specs/sub/lamp.frm"), SPEC).unwrap();
+    fs::write(dir.join("specs/notes.txt"), "not a spec").unwrap();
+
+    // missing snapshots fail until they are blessed
+    let err = snapshots(&dir, false).run().unwrap_err();
+    assert!(err.to_string().contains("sub/lamp/rust.rs is missing"));
+    let paths = snapshots(&dir, true).run().unwrap();
+    assert_eq!(
+        paths,
+        [
+            dir.join("snapshots/sub/lamp/rust.rs"),
+            dir.join("snapshots/sub/lamp/smcat.smcat")
+        ]
+    );
+    assert!(snapshots(&dir, false).run().is_ok());
+
+    // a change to the generated code is reported with the first difference
+    fs::write(dir.join("specs/sub/lamp.frm"), SPEC.replace("$On", "$Lit")).unwrap();
+    let err = snapshots(&dir, false).run().unwrap_err().to_string();
+    assert!(err.starts_with("2 snapshot(s) don't match"));
+    assert!(err.contains("rust.rs differs at line"));
+
+    // the error of a failing spec replaces its code, other files are left alone
+    fs::write(
+        dir.join("specs/sub/lamp.frm"),
+        "#Lamp\n-interface-\nfoo [\n##\n",
+    )
+    .unwrap();
+    fs::write(dir.join("snapshots/sub/lamp/README"), "notes").unwrap();
+    let err = snapshots(&dir, false).run().unwrap_err().to_string();
+    assert!(err.contains("rust.rs is not generated"));
+    snapshots(&dir, true).run().unwrap();
+    let error = fs::read_to_string(dir.join("snapshots/sub/lamp/rust.error")).unwrap();
+    assert!(error.starts_with("error 1: "));
+    assert!(!dir.join("snapshots/sub/lamp/rust.rs").exists());
+    assert!(dir.join("snapshots/sub/lamp/README").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
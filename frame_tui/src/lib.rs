@@ -0,0 +1,177 @@
+//! A terminal dashboard showing the state machines of a running program live: the current state
+//! of each instance, its recent transitions, the values of its variables and the rate of the
+//! events it handles. This is handy when debugging a service with several machines locally.
+//!
+//! The dashboard is fed by the messages of a `frame_runtime` debug server (see the
+//! `debug_server` module of `frame_runtime`), either over WebSocket from another process, with
+//! the `frame-tui` command:
+//!
+//! ```text
+//! frame-tui ws://127.0.0.1:9229
+//! ```
+//!
+//! or in the process running the machines, e.g. from a debug build of a command line tool:
+//!
+//! ```ignore
+//! let server = DebugServer::bind("127.0.0.1:0")?;
+//! server.attach("lamp", &mut lamp);
+//! let monitor = frame_tui::monitor(&server);
+//! std::thread::spawn(move || frame_tui::run(monitor, "lamp"));
+//! ```
+//!
+//! Events and transitions don't carry the domain variables of the machines, so over WebSocket
+//! the dashboard asks for a snapshot of each instance every second, which is answered when the
+//! thread owning the instance polls the server. In process, the variables are updated when a
+//! snapshot is sent, e.g. after each command of another client.
+
+pub mod model;
+pub mod ui;
+
+use anyhow::Context;
+use frame_runtime::debug_server::DebugServer;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+use ui::App;
+
+/// How often the dashboard is redrawn, and the connection checked for messages and commands.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the dashboard asks a remote debug server for snapshots.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The source of the messages of a debug server, and where to send commands to it if possible.
+pub struct Connection {
+    messages: Receiver<String>,
+    commands: Option<Sender<String>>,
+}
+
+/// Connect to a debug server over WebSocket, e.g. at `ws://127.0.0.1:9229`.
+pub fn connect(url: &str) -> anyhow::Result<Connection> {
+    let (mut socket, _) =
+        tungstenite::connect(url).with_context(|| format!("cannot connect to {}", url))?;
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        stream.set_read_timeout(Some(REFRESH_INTERVAL))?;
+    }
+    let (message_sender, messages) = mpsc::channel();
+    let (commands, command_receiver) = mpsc::channel::<String>();
+    thread::Builder::new()
+        .name("frame-tui-connection".to_string())
+        .spawn(move || loop {
+            match socket.read() {
+                Ok(Message::Text(text)) => {
+                    if message_sender.send(text).is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err)) if is_timeout(&err) => {}
+                // the closed connection is noticed by the dashboard when the channel is closed
+                Err(_) => return,
+            }
+            for command in command_receiver.try_iter() {
+                if socket.send(Message::Text(command)).is_err() {
+                    return;
+                }
+            }
+        })?;
+    Ok(Connection {
+        messages,
+        commands: Some(commands),
+    })
+}
+
+/// Receive the messages of a debug server running in this process.
+pub fn monitor(server: &DebugServer) -> Connection {
+    Connection {
+        messages: server.subscribe(),
+        commands: None,
+    }
+}
+
+/// Show the dashboard in the terminal until the user quits, with `q` or `Esc`. The source is
+/// shown in the status line, e.g. the URL of the debug server.
+pub fn run(connection: Connection, source: &str) -> anyhow::Result<()> {
+    use crossterm::{execute, terminal};
+
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), terminal::EnterAlternateScreen)?;
+    let result = Terminal::new(CrosstermBackend::new(io::stdout()))
+        .map_err(anyhow::Error::from)
+        .and_then(|mut terminal| show(&mut terminal, connection, source));
+    // restore the terminal even if drawing failed
+    let _ = execute!(io::stdout(), terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+    result
+}
+
+fn show(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    connection: Connection,
+    source: &str,
+) -> anyhow::Result<()> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+
+    let mut app = App::new(source);
+    let mut last_snapshots: Option<Instant> = None;
+    loop {
+        loop {
+            match connection.messages.try_recv() {
+                Ok(message) => app.dashboard.handle(&message),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    app.dashboard.connected = false;
+                    break;
+                }
+            }
+        }
+        if let Some(commands) = &connection.commands {
+            if last_snapshots.map_or(true, |time| time.elapsed() >= SNAPSHOT_INTERVAL) {
+                for instance in app.dashboard.instances.keys() {
+                    let _ = commands.send(snapshot_command(instance));
+                }
+                last_snapshots = Some(Instant::now());
+            }
+        }
+
+        terminal.draw(|frame| ui::draw(frame, &app, now()))?;
+
+        if event::poll(REFRESH_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => app.select_next(),
+                    KeyCode::Up | KeyCode::Char('k') => app.select_previous(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// A command asking for a snapshot of an instance, with the instance as the id of the reply.
+fn snapshot_command(instance: &str) -> String {
+    serde_json::json!({"id": instance, "command": "snapshot", "instance": instance}).to_string()
+}
+
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+/// Milliseconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as u64)
+}
@@ -0,0 +1,28 @@
+//! `frame-tui [URL]` shows the state machines streamed by a debug server in the terminal. The
+//! URL defaults to `ws://127.0.0.1:9229`.
+
+use std::env;
+use std::process;
+
+const DEFAULT_URL: &str = "ws://127.0.0.1:9229";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let url = match args.as_slice() {
+        [] => DEFAULT_URL.to_string(),
+        [arg] if arg == "-h" || arg == "--help" => {
+            println!("Usage: frame-tui [URL]\n\nShow the state machines streamed by a Frame debug server, at {} by default.", DEFAULT_URL);
+            return;
+        }
+        [url] => url.clone(),
+        _ => {
+            eprintln!("Usage: frame-tui [URL]");
+            process::exit(exitcode::USAGE);
+        }
+    };
+    let result = frame_tui::connect(&url).and_then(|connection| frame_tui::run(connection, &url));
+    if let Err(err) = result {
+        eprintln!("error: {:#}", err);
+        process::exit(1);
+    }
+}
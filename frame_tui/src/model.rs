@@ -0,0 +1,296 @@
+//! The state of the dashboard, built from the messages of a `frame_runtime` debug server.
+
+use serde_json::Value as Json;
+use std::collections::{BTreeMap, VecDeque};
+
+/// The number of recent transitions kept for each instance.
+pub const TRANSITION_HISTORY: usize = 50;
+
+/// The period over which event rates are computed, in milliseconds.
+pub const RATE_WINDOW: u64 = 10_000;
+
+/// The period over which the times of events are kept, in milliseconds.
+const EVENT_HISTORY: u64 = 60_000;
+
+/// Everything known about the instances streamed by a debug server.
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    /// The instances, by name.
+    pub instances: BTreeMap<String, Instance>,
+    /// Whether the dashboard still receives messages.
+    pub connected: bool,
+    /// The number of messages that could not be understood.
+    pub invalid_messages: usize,
+}
+
+/// The latest known state of an instance, and its recent activity.
+#[derive(Debug, Default)]
+pub struct Instance {
+    /// The name of the system of the instance, e.g. `Lamp`.
+    pub machine: String,
+    /// The current state.
+    pub state: StateView,
+    /// The domain variables, by name, rendered as JSON. They are only updated by snapshots,
+    /// since events and transitions don't carry them.
+    pub variables: Vec<(String, String)>,
+    /// The most recent transitions, the latest last.
+    pub transitions: VecDeque<TransitionEntry>,
+    /// The times of the interface events handled in the last minute.
+    pub event_times: VecDeque<u64>,
+    /// The number of interface events handled since the dashboard started.
+    pub event_count: u64,
+    /// The name of the last interface event handled.
+    pub last_event: Option<String>,
+}
+
+/// A state of an instance, with the values of its parameters and variables rendered as JSON.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct StateView {
+    pub name: String,
+    pub arguments: Vec<(String, String)>,
+    pub variables: Vec<(String, String)>,
+}
+
+/// A transition taken by an instance.
+#[derive(Debug, Eq, PartialEq)]
+pub struct TransitionEntry {
+    /// Milliseconds since the Unix epoch.
+    pub time: u64,
+    pub change_state: bool,
+    pub event: String,
+    pub label: String,
+    pub source: String,
+    pub target: String,
+}
+
+impl Dashboard {
+    pub fn new() -> Dashboard {
+        Dashboard {
+            connected: true,
+            ..Dashboard::default()
+        }
+    }
+
+    /// Update the dashboard with a message of the debug server.
+    pub fn handle(&mut self, message: &str) {
+        let message: Json = match serde_json::from_str(message) {
+            Ok(message) => message,
+            Err(_) => {
+                self.invalid_messages += 1;
+                return;
+            }
+        };
+        if message["type"] == "reply" {
+            // the replies to the snapshot commands of the dashboard are identified by instance
+            if let (Some(name), true) = (message["id"].as_str(), message["snapshot"].is_object()) {
+                self.instance(name.to_string()).update(&message["snapshot"]);
+            }
+            return;
+        }
+        let name = match message["instance"].as_str() {
+            Some(name) => name.to_string(),
+            None => {
+                self.invalid_messages += 1;
+                return;
+            }
+        };
+        match message["type"].as_str() {
+            Some("snapshot") => self.instance(name).update(&message["snapshot"]),
+            Some("event") => {
+                let event = message["event"].as_str().unwrap_or_default();
+                // enter and exit events are named after their state, e.g. `On:>`
+                if !event.contains(':') {
+                    let time = message["time"].as_u64().unwrap_or_default();
+                    self.instance(name).record_event(event, time);
+                }
+            }
+            Some("transition") => {
+                let instance = self.instance(name);
+                instance.state = state_view(&message["state"]);
+                instance.transitions.push_back(TransitionEntry {
+                    time: message["time"].as_u64().unwrap_or_default(),
+                    change_state: message["kind"] == "change_state",
+                    event: text(&message["event"]),
+                    label: text(&message["label"]),
+                    source: text(&message["source"]),
+                    target: text(&message["target"]),
+                });
+                if instance.transitions.len() > TRANSITION_HISTORY {
+                    instance.transitions.pop_front();
+                }
+            }
+            _ => self.invalid_messages += 1,
+        }
+    }
+
+    fn instance(&mut self, name: String) -> &mut Instance {
+        self.instances.entry(name).or_default()
+    }
+}
+
+impl Instance {
+    /// The number of interface events per second handled over the last [RATE_WINDOW].
+    pub fn event_rate(&self, now: u64) -> f64 {
+        let since = now.saturating_sub(RATE_WINDOW);
+        let count = self
+            .event_times
+            .iter()
+            .filter(|time| **time > since)
+            .count();
+        count as f64 * 1000.0 / RATE_WINDOW as f64
+    }
+
+    /// The number of interface events handled in each of the last `seconds` seconds, the
+    /// latest last.
+    pub fn events_per_second(&self, now: u64, seconds: usize) -> Vec<u64> {
+        let mut counts = vec![0; seconds];
+        for time in &self.event_times {
+            let age = (now.saturating_sub(*time) / 1000) as usize;
+            if age < seconds {
+                counts[seconds - 1 - age] += 1;
+            }
+        }
+        counts
+    }
+
+    fn update(&mut self, snapshot: &Json) {
+        self.machine = text(&snapshot["machine"]);
+        self.state = state_view(&snapshot["state"]);
+        self.variables = values(&snapshot["variables"]);
+    }
+
+    fn record_event(&mut self, event: &str, time: u64) {
+        self.event_times.push_back(time);
+        let since = time.saturating_sub(EVENT_HISTORY);
+        while self
+            .event_times
+            .front()
+            .map_or(false, |first| *first <= since)
+        {
+            self.event_times.pop_front();
+        }
+        self.event_count += 1;
+        self.last_event = Some(event.to_string());
+    }
+}
+
+fn state_view(state: &Json) -> StateView {
+    StateView {
+        name: text(&state["name"]),
+        arguments: values(&state["arguments"]),
+        variables: values(&state["variables"]),
+    }
+}
+
+fn values(values: &Json) -> Vec<(String, String)> {
+    match values.as_object() {
+        Some(values) => values
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_string()))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn text(value: &Json) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = r#"{"type":"snapshot","instance":"dimmer","snapshot":{"machine":"Dimmer","state":{"name":"Off","arguments":{},"variables":{}},"variables":{"brightness":1}}}"#;
+
+    fn event(name: &str, time: u64) -> String {
+        format!(
+            r#"{{"type":"event","instance":"dimmer","time":{},"event":"{}","arguments":{{}},"return":null}}"#,
+            time, name
+        )
+    }
+
+    fn transition(source: &str, target: &str, time: u64) -> String {
+        format!(
+            r#"{{"type":"transition","instance":"dimmer","time":{},"kind":"transition","event":"toggle","label":"","source":"{}","target":"{}","state":{{"name":"{}","arguments":{{}},"variables":{{"count":0}}}}}}"#,
+            time, source, target, target
+        )
+    }
+
+    #[test]
+    fn tracks_state_and_variables() {
+        let mut dashboard = Dashboard::new();
+        dashboard.handle(SNAPSHOT);
+        let dimmer = &dashboard.instances["dimmer"];
+        assert_eq!(dimmer.machine, "Dimmer");
+        assert_eq!(dimmer.state.name, "Off");
+        assert_eq!(
+            dimmer.variables,
+            vec![("brightness".to_string(), "1".to_string())]
+        );
+
+        dashboard.handle(&transition("Off", "On", 1000));
+        let dimmer = &dashboard.instances["dimmer"];
+        assert_eq!(dimmer.state.name, "On");
+        assert_eq!(
+            dimmer.state.variables,
+            vec![("count".to_string(), "0".to_string())]
+        );
+        assert_eq!(
+            dimmer.transitions.back(),
+            Some(&TransitionEntry {
+                time: 1000,
+                change_state: false,
+                event: "toggle".to_string(),
+                label: String::new(),
+                source: "Off".to_string(),
+                target: "On".to_string(),
+            })
+        );
+
+        let reply = SNAPSHOT
+            .replace(
+                r#""type":"snapshot","instance":"dimmer""#,
+                r#""type":"reply","id":"dimmer","ok":true"#,
+            )
+            .replace(r#""brightness":1"#, r#""brightness":7"#);
+        dashboard.handle(&reply);
+        assert_eq!(dashboard.instances["dimmer"].variables[0].1, "7");
+    }
+
+    #[test]
+    fn keeps_recent_transitions() {
+        let mut dashboard = Dashboard::new();
+        for i in 0..TRANSITION_HISTORY as u64 + 5 {
+            dashboard.handle(&transition("Off", "On", i));
+        }
+        let transitions = &dashboard.instances["dimmer"].transitions;
+        assert_eq!(transitions.len(), TRANSITION_HISTORY);
+        assert_eq!(transitions.front().unwrap().time, 5);
+    }
+
+    #[test]
+    fn computes_event_rates() {
+        let mut dashboard = Dashboard::new();
+        for time in &[1_000, 15_000, 16_500, 17_000, 20_000] {
+            dashboard.handle(&event("toggle", *time));
+        }
+        dashboard.handle(&event("On:>", 20_000));
+        let dimmer = &dashboard.instances["dimmer"];
+        assert_eq!(dimmer.event_count, 5);
+        assert_eq!(dimmer.last_event.as_deref(), Some("toggle"));
+        assert!((dimmer.event_rate(20_000) - 0.4).abs() < 1e-9);
+        assert_eq!(dimmer.events_per_second(20_500, 5), vec![1, 1, 0, 0, 1]);
+
+        dashboard.handle(&event("toggle", 90_000));
+        assert_eq!(dashboard.instances["dimmer"].event_times.len(), 1);
+    }
+
+    #[test]
+    fn counts_invalid_messages() {
+        let mut dashboard = Dashboard::new();
+        dashboard.handle("not json");
+        dashboard.handle(r#"{"type":"event"}"#);
+        dashboard.handle(r#"{"type":"unknown","instance":"dimmer"}"#);
+        assert_eq!(dashboard.invalid_messages, 3);
+    }
+}
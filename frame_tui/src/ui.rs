@@ -0,0 +1,263 @@
+//! The layout of the dashboard: the list of instances on the left, and the state, variables,
+//! recent transitions and event rate of the selected instance on the right.
+
+use crate::model::{Dashboard, Instance};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, List, ListItem, ListState, Paragraph, Row, Sparkline, Table,
+};
+use ratatui::Frame;
+
+/// The number of seconds shown by the sparkline of events.
+const SPARKLINE_SECONDS: usize = 60;
+
+/// The dashboard and the choices of the user.
+pub struct App {
+    pub dashboard: Dashboard,
+    /// The index of the selected instance, in the order of their names.
+    pub selected: usize,
+    /// Where the messages come from, e.g. the URL of the debug server.
+    pub source: String,
+}
+
+impl App {
+    pub fn new(source: &str) -> App {
+        App {
+            dashboard: Dashboard::new(),
+            selected: 0,
+            source: source.to_string(),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.dashboard.instances.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    fn selected_instance(&self) -> Option<(&String, &Instance)> {
+        self.dashboard.instances.iter().nth(self.selected)
+    }
+}
+
+/// Draw the dashboard at the given time, in milliseconds since the Unix epoch.
+pub fn draw(frame: &mut Frame, app: &App, now: u64) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(frame.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(32), Constraint::Min(0)])
+        .split(rows[0]);
+
+    draw_instances(frame, app, now, columns[0]);
+    match app.selected_instance() {
+        Some((name, instance)) => draw_instance(frame, name, instance, now, columns[1]),
+        None => frame.render_widget(
+            Paragraph::new("Waiting for an instance to be attached...").block(block("Instance")),
+            columns[1],
+        ),
+    }
+
+    let status = if app.dashboard.connected {
+        Span::styled("connected", Style::default().fg(Color::Green))
+    } else {
+        Span::styled("disconnected", Style::default().fg(Color::Red))
+    };
+    let mut footer = vec![Span::raw(format!(" {} ", app.source)), status];
+    if app.dashboard.invalid_messages > 0 {
+        footer.push(Span::raw(format!(
+            " | {} invalid message(s)",
+            app.dashboard.invalid_messages
+        )));
+    }
+    footer.push(Span::raw(" | ↑/↓ select, q quit"));
+    frame.render_widget(Paragraph::new(Line::from(footer)), rows[1]);
+}
+
+fn draw_instances(frame: &mut Frame, app: &App, now: u64, area: Rect) {
+    let items: Vec<ListItem> = app
+        .dashboard
+        .instances
+        .iter()
+        .map(|(name, instance)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(
+                    " {} {:.1}/s",
+                    instance.state.name,
+                    instance.event_rate(now)
+                )),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block("Instances"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default();
+    if !app.dashboard.instances.is_empty() {
+        state.select(Some(app.selected));
+    }
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_instance(frame: &mut Frame, name: &str, instance: &Instance, now: u64, area: Rect) {
+    let state_height = 3 + instance.state.arguments.len() + instance.state.variables.len();
+    let variables_height = 2 + instance.variables.len().max(1);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(state_height as u16),
+            Constraint::Length(variables_height as u16),
+            Constraint::Min(3),
+            Constraint::Length(5),
+        ])
+        .split(area);
+
+    let mut state = vec![Line::from(vec![
+        Span::raw("$"),
+        Span::styled(
+            instance.state.name.clone(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    for (name, value) in &instance.state.arguments {
+        state.push(Line::from(format!("  param {} = {}", name, value)));
+    }
+    for (name, value) in &instance.state.variables {
+        state.push(Line::from(format!("  var {} = {}", name, value)));
+    }
+    let title = format!("{} ({})", name, instance.machine);
+    frame.render_widget(Paragraph::new(state).block(block(&title)), rows[0]);
+
+    let variables = instance
+        .variables
+        .iter()
+        .map(|(name, value)| Row::new(vec![name.clone(), value.clone()]));
+    let table = Table::new(variables, [Constraint::Length(24), Constraint::Min(0)])
+        .block(block("Domain variables"));
+    frame.render_widget(table, rows[1]);
+
+    let transitions: Vec<ListItem> = instance
+        .transitions
+        .iter()
+        .rev()
+        .map(|transition| {
+            let arrow = if transition.change_state { "->>" } else { "->" };
+            let mut line = format!(
+                "{} {} {} {} ({})",
+                clock(transition.time),
+                transition.source,
+                arrow,
+                transition.target,
+                transition.event
+            );
+            if !transition.label.is_empty() {
+                line.push_str(&format!(" \"{}\"", transition.label));
+            }
+            ListItem::new(line)
+        })
+        .collect();
+    frame.render_widget(
+        List::new(transitions).block(block("Recent transitions")),
+        rows[2],
+    );
+
+    let counts = instance.events_per_second(now, SPARKLINE_SECONDS);
+    let title = format!(
+        "Events: {:.1}/s, {} total{}",
+        instance.event_rate(now),
+        instance.event_count,
+        instance
+            .last_event
+            .as_ref()
+            .map_or_else(String::new, |event| format!(", last |{}|", event))
+    );
+    let sparkline = Sparkline::default()
+        .block(block(&title))
+        .data(&counts)
+        .style(Style::default().fg(Color::Cyan));
+    frame.render_widget(sparkline, rows[3]);
+}
+
+fn block(title: &str) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+}
+
+/// The time of day of a time in milliseconds since the Unix epoch, in UTC, e.g. `13:45:02.125`.
+fn clock(time: u64) -> String {
+    let seconds = time / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+        time % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render(app: &App, now: u64) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, app, now)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                text.push_str(buffer.get(x, y).symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn draws_the_selected_instance() {
+        let mut app = App::new("ws://127.0.0.1:9229");
+        assert!(render(&app, 0).contains("Waiting for an instance"));
+
+        app.dashboard.handle(r#"{"type":"snapshot","instance":"dimmer","snapshot":{"machine":"Dimmer","state":{"name":"Off","arguments":{},"variables":{}},"variables":{"brightness":1}}}"#);
+        app.dashboard.handle(r#"{"type":"event","instance":"dimmer","time":45002000,"event":"toggle","arguments":{},"return":null}"#);
+        app.dashboard.handle(r#"{"type":"transition","instance":"dimmer","time":45002125,"kind":"transition","event":"toggle","label":"","source":"Off","target":"On","state":{"name":"On","arguments":{"level":3},"variables":{}}}"#);
+        app.dashboard.handle(r#"{"type":"snapshot","instance":"lamp","snapshot":{"machine":"Lamp","state":{"name":"Dark","arguments":{},"variables":{}},"variables":{}}}"#);
+
+        let screen = render(&app, 45_003_000);
+        assert!(screen.contains("dimmer (Dimmer)"));
+        assert!(screen.contains("$On"));
+        assert!(screen.contains("param level = 3"));
+        assert!(screen.contains("brightness"));
+        assert!(screen.contains("12:30:02.125 Off -> On (toggle)"));
+        assert!(screen.contains("Events: 0.1/s, 1 total, last |toggle|"));
+        assert!(screen.contains("connected"));
+
+        app.select_next();
+        app.select_next();
+        app.dashboard.connected = false;
+        let screen = render(&app, 45_003_000);
+        assert!(screen.contains("lamp (Lamp)"));
+        assert!(screen.contains("$Dark"));
+        assert!(screen.contains("disconnected"));
+    }
+
+    #[test]
+    fn formats_times_of_day() {
+        assert_eq!(clock(0), "00:00:00.000");
+        assert_eq!(clock(1_700_000_000_042), "22:13:20.042");
+    }
+}
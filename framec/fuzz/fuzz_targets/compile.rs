@@ -0,0 +1,16 @@
+//! Feed arbitrary input through the whole compiler, from parsing through linting and
+//! optimization to the Rust backend, which should fail with an error rather than panic.
+
+#![no_main]
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(spec) = std::str::from_utf8(data) {
+        let exe = Exe::with_options(CompilerOptions {
+            load_local_config: false,
+            ..CompilerOptions::default()
+        });
+        let _ = exe.run(&None, None, spec.to_string(), Some(TargetLanguage::Rust));
+    }
+});
@@ -0,0 +1,85 @@
+//! Mutate valid specs and compile them, to reach the code behind the parser that arbitrary bytes
+//! rarely get to. The specs are those of the golden tests; each mutation removes, duplicates or
+//! swaps lines, or inserts a Frame token, so most mutants are nearly valid specs.
+
+#![no_main]
+use arbitrary::Arbitrary;
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use libfuzzer_sys::fuzz_target;
+
+const SPECS: &[&str] = &[
+    include_str!("../../tests/golden/annotated.frm"),
+    include_str!("../../tests/golden/hierarchy.frm"),
+    include_str!("../../tests/golden/lamp.frm"),
+    include_str!("../../tests/golden/optimized.frm"),
+    include_str!("../../tests/golden/stack.frm"),
+];
+
+const TOKENS: &[&str] = &[
+    "#", "##", "$", "$S", "$$[+]", "$$[-]", "|>|", "|<|", "|e|", "||*", "->", "->>", "=>", ":>",
+    "^", "^(x)", "?", "?!", "?~", "?:", ":", "::", "[", "]", "(", ")", "{", "}", "-interface-",
+    "-machine-", "-actions-", "-domain-", "var x:i32 = 0", "const", "#.", "$.", "||.", "\"s\"",
+    "`code`", "---", "0", "true", "nil", "\n",
+];
+
+#[derive(Arbitrary, Debug)]
+enum Mutation {
+    RemoveLine(u16),
+    DuplicateLine(u16),
+    SwapLines(u16, u16),
+    InsertToken { offset: u16, token: u8 },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    spec: u8,
+    mutations: Vec<Mutation>,
+}
+
+fn mutate(spec: &str, mutations: &[Mutation]) -> String {
+    let mut lines: Vec<String> = spec.lines().map(String::from).collect();
+    for mutation in mutations {
+        if lines.is_empty() {
+            break;
+        }
+        let line = |i: &u16| usize::from(*i) % lines.len();
+        match mutation {
+            Mutation::RemoveLine(i) => {
+                let i = line(i);
+                lines.remove(i);
+            }
+            Mutation::DuplicateLine(i) => {
+                let i = line(i);
+                lines.insert(i, lines[i].clone());
+            }
+            Mutation::SwapLines(i, j) => {
+                let (i, j) = (line(i), line(j));
+                lines.swap(i, j);
+            }
+            Mutation::InsertToken { offset, token } => {
+                let i = line(offset);
+                let text = &mut lines[i];
+                let mut at = usize::from(*offset) % (text.len() + 1);
+                while !text.is_char_boundary(at) {
+                    at -= 1;
+                }
+                text.insert_str(at, TOKENS[usize::from(*token) % TOKENS.len()]);
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fuzz_target!(|input: Input| {
+    let spec = SPECS[usize::from(input.spec) % SPECS.len()];
+    let exe = Exe::with_options(CompilerOptions {
+        load_local_config: false,
+        ..CompilerOptions::default()
+    });
+    let _ = exe.run(
+        &None,
+        None,
+        mutate(spec, &input.mutations),
+        Some(TargetLanguage::Rust),
+    );
+});
@@ -0,0 +1,16 @@
+//! Feed arbitrary input to the scanner, parser and semantic analysis of framec, which should
+//! reject invalid specs with an error rather than panic.
+
+#![no_main]
+use framec::frame_c::compiler::{CompilerOptions, Exe};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(spec) = std::str::from_utf8(data) {
+        let exe = Exe::with_options(CompilerOptions {
+            load_local_config: false,
+            ..CompilerOptions::default()
+        });
+        let _ = exe.parse(None, spec.to_string());
+    }
+});
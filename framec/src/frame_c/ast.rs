@@ -1,3 +1,48 @@
+//! The abstract syntax tree of a Frame specification.
+//!
+//! The AST is produced by [`Exe::parse`](crate::frame_c::compiler::Exe::parse) and is the same
+//! tree the code generators run on, so external tools such as linters, migration scripts and
+//! custom generators can analyze specifications without reimplementing the parser.
+//!
+//! The root of the tree is a [`SystemNode`], which holds the optional interface, machine, actions
+//! and domain blocks. A [`MachineBlockNode`] holds the [`StateNode`]s in the order they are
+//! declared, each state holds its [`EventHandlerNode`]s, and each event handler holds its
+//! statements and an optional [`TerminatorExpr`]. Nodes that are shared between parts of the tree
+//! are wrapped in `Rc<RefCell<_>>`.
+//!
+//! Nodes can be inspected directly through their public fields, or with an [`AstVisitor`]. Every
+//! node implements [`NodeElement`], whose `accept` method calls the matching `visit_*` method of
+//! the visitor. The `visit_*` methods do nothing by default and don't descend into child nodes,
+//! so a visitor implements the methods for the nodes it is interested in and calls `accept` on
+//! the children it wants to visit.
+//!
+//! ```
+//! use framec::frame_c::ast::{AstVisitor, NodeElement, StateNode, SystemNode};
+//! use framec::frame_c::compiler::Exe;
+//!
+//! #[derive(Default)]
+//! struct StateNames(Vec<String>);
+//!
+//! impl AstVisitor for StateNames {
+//!     fn visit_system_node(&mut self, node: &SystemNode) {
+//!         if let Some(machine_block_node) = &node.machine_block_node_opt {
+//!             for state_node_rcref in &machine_block_node.states {
+//!                 state_node_rcref.borrow().accept(self);
+//!             }
+//!         }
+//!     }
+//!     fn visit_state_node(&mut self, node: &StateNode) {
+//!         self.0.push(node.name.clone());
+//!     }
+//! }
+//!
+//! let spec = "#Lamp\n-machine-\n$Off\n|toggle| -> $On ^\n$On\n|toggle| -> $Off ^\n##\n";
+//! let system_node = Exe::new().parse(None, spec.to_string()).unwrap();
+//! let mut state_names = StateNames::default();
+//! system_node.accept(&mut state_names);
+//! assert_eq!(state_names.0, vec!["Off", "On"]);
+//! ```
+
 #![allow(clippy::enum_variant_names)]
 #![allow(non_snake_case)]
 
@@ -7,8 +52,9 @@ use super::symbol_table::{ActionDeclSymbol, EventSymbol, SymbolType};
 use crate::frame_c::ast::OperatorType::{
     Divide, Greater, GreaterEqual, LessEqual, Minus, Multiply, Plus,
 };
+use crate::frame_c::diagnostics::Span;
 use crate::frame_c::symbol_table::InterfaceMethodSymbol;
-use crate::frame_c::visitors::*;
+pub use crate::frame_c::visitors::AstVisitor;
 use downcast_rs::__std::cell::RefCell;
 use downcast_rs::*;
 use std::collections::VecDeque;
@@ -152,6 +198,8 @@ pub struct SystemNode {
     pub machine_block_node_opt: Option<MachineBlockNode>,
     pub actions_block_node_opt: Option<ActionsBlockNode>,
     pub domain_block_node_opt: Option<DomainBlockNode>,
+    /// The line the declaration of the system starts on, with its attributes if any.
+    pub start_line: usize,
     pub line: usize,
 }
 
@@ -167,6 +215,7 @@ impl SystemNode {
         machine_block_node_opt: Option<MachineBlockNode>,
         actions_block_node_opt: Option<ActionsBlockNode>,
         domain_block_node_opt: Option<DomainBlockNode>,
+        start_line: usize,
         line: usize,
     ) -> SystemNode {
         SystemNode {
@@ -180,6 +229,7 @@ impl SystemNode {
             machine_block_node_opt,
             actions_block_node_opt,
             domain_block_node_opt,
+            start_line,
             line,
         }
     }
@@ -230,6 +280,8 @@ pub struct InterfaceMethodNode {
     pub params: Option<Vec<ParameterNode>>,
     pub return_type_opt: Option<TypeNode>,
     pub alias: Option<MessageNode>,
+    pub line: usize,
+    pub name_span: Span,
 }
 
 impl InterfaceMethodNode {
@@ -238,12 +290,16 @@ impl InterfaceMethodNode {
         params: Option<Vec<ParameterNode>>,
         return_type: Option<TypeNode>,
         alias: Option<MessageNode>,
+        line: usize,
+        name_span: Span,
     ) -> InterfaceMethodNode {
         InterfaceMethodNode {
             name,
             params,
             return_type_opt: return_type,
             alias,
+            line,
+            name_span,
         }
     }
 }
@@ -261,6 +317,10 @@ pub struct ParameterNode {
     pub param_name: String,
     pub param_type_opt: Option<TypeNode>,
     pub scope: IdentifierDeclScope,
+    /// The value of the parameter when no argument is passed for it, e.g. `5` in
+    /// `[level:i32 = 5]`. Only parameters of interface methods and of enter and exit handlers
+    /// have defaults.
+    pub default_opt: Option<LiteralExprNode>,
 }
 
 impl ParameterNode {
@@ -273,6 +333,7 @@ impl ParameterNode {
             param_name,
             param_type_opt,
             scope,
+            default_opt: None,
             //           param_context,
         }
     }
@@ -384,6 +445,49 @@ impl VariableNode {
             symbol_type_rcref_opt,
         }
     }
+
+    /// The declared type of the variable or parameter, if it has one.
+    pub fn get_type_opt(&self) -> Option<TypeNode> {
+        let symbol_type_rcref = self.symbol_type_rcref_opt.as_ref()?;
+        let symbol_type = symbol_type_rcref.borrow();
+        match &*symbol_type {
+            SymbolType::DomainVariable {
+                domain_variable_symbol_rcref,
+            } => domain_variable_symbol_rcref.borrow().var_type.clone(),
+            SymbolType::StateVariable {
+                state_variable_symbol_rcref,
+            } => state_variable_symbol_rcref.borrow().var_type.clone(),
+            SymbolType::EventHandlerVariable {
+                event_handler_variable_symbol_rcref,
+            } => event_handler_variable_symbol_rcref
+                .borrow()
+                .var_type
+                .clone(),
+            SymbolType::StateParam {
+                state_param_symbol_rcref,
+            } => state_param_symbol_rcref.borrow().param_type_opt.clone(),
+            SymbolType::EventHandlerParam {
+                event_handler_param_symbol_rcref,
+            } => event_handler_param_symbol_rcref
+                .borrow()
+                .param_type_opt
+                .clone(),
+            _ => None,
+        }
+    }
+
+    /// True if the variable refers to a `const` declared in the domain.
+    pub fn is_constant(&self) -> bool {
+        match &self.symbol_type_rcref_opt {
+            Some(symbol_type_rcref) => match &*symbol_type_rcref.borrow() {
+                SymbolType::DomainVariable {
+                    domain_variable_symbol_rcref,
+                } => domain_variable_symbol_rcref.borrow().is_constant,
+                _ => false,
+            },
+            None => false,
+        }
+    }
 }
 
 impl NodeElement for VariableNode {
@@ -445,11 +549,18 @@ impl NodeElement for ActionsBlockNode {
 
 pub struct DomainBlockNode {
     pub member_variables: Vec<Rc<RefCell<VariableDeclNode>>>,
+    pub enums: Vec<Rc<RefCell<EnumDeclNode>>>,
 }
 
 impl DomainBlockNode {
-    pub fn new(member_variables: Vec<Rc<RefCell<VariableDeclNode>>>) -> DomainBlockNode {
-        DomainBlockNode { member_variables }
+    pub fn new(
+        member_variables: Vec<Rc<RefCell<VariableDeclNode>>>,
+        enums: Vec<Rc<RefCell<EnumDeclNode>>>,
+    ) -> DomainBlockNode {
+        DomainBlockNode {
+            member_variables,
+            enums,
+        }
     }
 }
 
@@ -461,6 +572,44 @@ impl NodeElement for DomainBlockNode {
 
 //-----------------------------------------------------//
 
+// enum Color { Red Green Blue }
+
+pub struct EnumDeclNode {
+    pub name: String,
+    pub enums: Vec<Rc<EnumeratorDeclNode>>,
+    pub line: usize,
+}
+
+impl EnumDeclNode {
+    pub fn new(name: String, enums: Vec<Rc<EnumeratorDeclNode>>, line: usize) -> EnumDeclNode {
+        EnumDeclNode { name, enums, line }
+    }
+
+    pub fn has_enumerator(&self, name: &str) -> bool {
+        self.enums.iter().any(|e| e.name == name)
+    }
+}
+
+impl NodeElement for EnumDeclNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enum_decl_node(self);
+    }
+}
+
+//-----------------------------------------------------//
+
+pub struct EnumeratorDeclNode {
+    pub name: String,
+}
+
+impl EnumeratorDeclNode {
+    pub fn new(name: String) -> EnumeratorDeclNode {
+        EnumeratorDeclNode { name }
+    }
+}
+
+//-----------------------------------------------------//
+
 pub struct StateNode {
     pub name: String,
     pub params_opt: Option<Vec<ParameterNode>>,
@@ -472,6 +621,7 @@ pub struct StateNode {
     // pub transitions:Vec<Rc<RefCell<TransitionStatementNode>>>,
     pub dispatch_opt: Option<DispatchNode>,
     pub line: usize,
+    pub name_span: Span,
 }
 
 impl StateNode {
@@ -485,6 +635,7 @@ impl StateNode {
         exit_event_handler_opt: Option<Rc<RefCell<EventHandlerNode>>>,
         dispatch_opt: Option<DispatchNode>,
         line: usize,
+        name_span: Span,
     ) -> StateNode {
         StateNode {
             name,
@@ -497,6 +648,7 @@ impl StateNode {
             // transitions:Vec::new(),
             dispatch_opt,
             line,
+            name_span,
         }
     }
 }
@@ -523,6 +675,8 @@ pub struct StateContextNode {
     pub state_ref_node: StateRefNode,
     pub state_ref_args_opt: Option<ExprListNode>,
     pub enter_args_opt: Option<ExprListNode>,
+    // $S[H*] - restore the most recently active descendant of $S
+    pub deep_history: bool,
 }
 
 impl StateContextNode {
@@ -530,11 +684,13 @@ impl StateContextNode {
         state_ref_node: StateRefNode,
         state_ref_args_opt: Option<ExprListNode>,
         enter_args_opt: Option<ExprListNode>,
+        deep_history: bool,
     ) -> StateContextNode {
         StateContextNode {
             state_ref_node,
             state_ref_args_opt,
             enter_args_opt,
+            deep_history,
         }
     }
 }
@@ -567,13 +723,21 @@ impl NodeElement for StateRefNode {
 
 pub struct DispatchNode {
     pub target_state_ref: StateRefNode,
+    /// The region of the parent state the state belongs to, e.g. `motor` in
+    /// `=> $Running.motor`. The regions of a parallel state are active at the same time.
+    pub region_opt: Option<String>,
     pub line: usize,
 }
 
 impl DispatchNode {
-    pub fn new(target_state_ref: StateRefNode, line: usize) -> DispatchNode {
+    pub fn new(
+        target_state_ref: StateRefNode,
+        region_opt: Option<String>,
+        line: usize,
+    ) -> DispatchNode {
         DispatchNode {
             target_state_ref,
+            region_opt,
             line,
         }
     }
@@ -649,11 +813,12 @@ pub enum MessageType {
 pub struct MessageNode {
     pub name: String,
     pub line: usize,
+    pub span: Span,
 }
 
 impl MessageNode {
-    pub(crate) fn new(name: String, line: usize) -> MessageNode {
-        MessageNode { name, line }
+    pub(crate) fn new(name: String, line: usize, span: Span) -> MessageNode {
+        MessageNode { name, line, span }
     }
 }
 
@@ -774,6 +939,9 @@ pub enum ExprType {
     BinaryExprT {
         binary_expr_node: BinaryExprNode,
     },
+    EnumeratorExprT {
+        enum_expr_node: EnumeratorExprNode,
+    },
 }
 
 impl ExprType {
@@ -792,6 +960,7 @@ impl ExprType {
             ExprType::FrameEventExprT { .. } => "FrameEventExprT",
             ExprType::UnaryExprT { .. } => "UnaryExprT",
             ExprType::BinaryExprT { .. } => "BinaryExprT",
+            ExprType::EnumeratorExprT { .. } => "EnumeratorExprT",
         }
     }
 }
@@ -845,6 +1014,9 @@ impl NodeElement for ExprType {
             ExprType::BinaryExprT { binary_expr_node } => {
                 ast_visitor.visit_binary_expr_node(binary_expr_node);
             }
+            ExprType::EnumeratorExprT { enum_expr_node } => {
+                ast_visitor.visit_enumerator_expr_node(enum_expr_node);
+            }
         }
     }
 
@@ -896,6 +1068,9 @@ impl NodeElement for ExprType {
             ExprType::BinaryExprT { binary_expr_node } => {
                 ast_visitor.visit_binary_expr_node_to_string(binary_expr_node, output);
             }
+            ExprType::EnumeratorExprT { enum_expr_node } => {
+                ast_visitor.visit_enumerator_expr_node_to_string(enum_expr_node, output);
+            }
             ExprType::UnaryExprT { unary_expr_node } => {
                 ast_visitor.visit_unary_expr_node_to_string(unary_expr_node, output);
             }
@@ -1098,6 +1273,9 @@ pub struct TransitionStatementNode {
     pub exit_args_opt: Option<ExprListNode>,
     pub label_opt: Option<String>,
     pub forward_event: bool,
+    /// Calls made after the exit event is sent and before the enter event, e.g. `openDoor()` in
+    /// `-> { openDoor() } $Open`.
+    pub actions: Vec<ExprStmtType>,
 }
 
 // TODO - why is new() commented out?
@@ -1570,6 +1748,35 @@ impl NodeElement for LiteralExprNode {
 
 //-----------------------------------------------------//
 
+// Color.Red
+
+#[derive(Clone)]
+pub struct EnumeratorExprNode {
+    pub enum_type: String,
+    pub enumerator: String,
+}
+
+impl EnumeratorExprNode {
+    pub fn new(enum_type: String, enumerator: String) -> EnumeratorExprNode {
+        EnumeratorExprNode {
+            enum_type,
+            enumerator,
+        }
+    }
+}
+
+impl NodeElement for EnumeratorExprNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enumerator_expr_node(self);
+    }
+
+    fn accept_to_string(&self, ast_visitor: &mut dyn AstVisitor, output: &mut String) {
+        ast_visitor.visit_enumerator_expr_node_to_string(self, output);
+    }
+}
+
+//-----------------------------------------------------//
+
 // &String | &str | Widget<int> | `& mut String` | &`mut String` | *x
 
 #[derive(Clone)]
@@ -1624,6 +1831,9 @@ pub enum TestType {
     NumberMatchTest {
         number_match_test_node: NumberMatchTestNode,
     },
+    EnumMatchTest {
+        enum_match_test_node: EnumMatchTestNode,
+    },
 }
 
 pub struct BoolTestNode {
@@ -1909,6 +2119,113 @@ impl NodeElement for NumberMatchTestPatternNode {
 
 //-----------------------------------------------------//
 
+pub struct EnumMatchTestNode {
+    pub expr_t: ExprType,
+    pub enum_type: String,
+    pub match_branch_nodes: Vec<EnumMatchTestMatchBranchNode>,
+    pub else_branch_node_opt: Option<EnumMatchTestElseBranchNode>,
+}
+
+impl EnumMatchTestNode {
+    pub fn new(
+        expr_t: ExprType,
+        enum_type: String,
+        match_branch_nodes: Vec<EnumMatchTestMatchBranchNode>,
+        else_branch_node_opt: Option<EnumMatchTestElseBranchNode>,
+    ) -> EnumMatchTestNode {
+        EnumMatchTestNode {
+            expr_t,
+            enum_type,
+            match_branch_nodes,
+            else_branch_node_opt,
+        }
+    }
+}
+
+impl NodeElement for EnumMatchTestNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enum_match_test_node(self);
+    }
+}
+
+//-----------------------------------------------------//
+
+pub struct EnumMatchTestMatchBranchNode {
+    pub enum_match_pattern_nodes: Vec<EnumMatchTestPatternNode>,
+    pub statements: Vec<DeclOrStmtType>,
+    pub branch_terminator_expr_opt: Option<TerminatorExpr>,
+}
+
+impl EnumMatchTestMatchBranchNode {
+    pub fn new(
+        enum_match_pattern_nodes: Vec<EnumMatchTestPatternNode>,
+        statements: Vec<DeclOrStmtType>,
+        branch_terminator_t_opt: Option<TerminatorExpr>,
+    ) -> EnumMatchTestMatchBranchNode {
+        EnumMatchTestMatchBranchNode {
+            enum_match_pattern_nodes,
+            statements,
+            branch_terminator_expr_opt: branch_terminator_t_opt,
+        }
+    }
+}
+
+impl NodeElement for EnumMatchTestMatchBranchNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enum_match_test_match_branch_node(self);
+    }
+}
+
+//-----------------------------------------------------//
+
+pub struct EnumMatchTestElseBranchNode {
+    pub statements: Vec<DeclOrStmtType>,
+    pub branch_terminator_expr_opt: Option<TerminatorExpr>,
+}
+
+impl EnumMatchTestElseBranchNode {
+    pub fn new(
+        statements: Vec<DeclOrStmtType>,
+        branch_terminator_t_opt: Option<TerminatorExpr>,
+    ) -> EnumMatchTestElseBranchNode {
+        EnumMatchTestElseBranchNode {
+            statements,
+            branch_terminator_expr_opt: branch_terminator_t_opt,
+        }
+    }
+}
+
+impl NodeElement for EnumMatchTestElseBranchNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enum_match_test_else_branch_node(self);
+    }
+}
+
+//-----------------------------------------------------//
+
+// The enumerator is stored as a full enumerator expression so each
+// backend can format it the same way as `Color.Red` in an expression.
+
+pub struct EnumMatchTestPatternNode {
+    pub enumerator_expr_node: EnumeratorExprNode,
+}
+
+impl EnumMatchTestPatternNode {
+    pub fn new(enumerator_expr_node: EnumeratorExprNode) -> EnumMatchTestPatternNode {
+        EnumMatchTestPatternNode {
+            enumerator_expr_node,
+        }
+    }
+}
+
+impl NodeElement for EnumMatchTestPatternNode {
+    fn accept(&self, ast_visitor: &mut dyn AstVisitor) {
+        ast_visitor.visit_enum_match_test_pattern_node(self);
+    }
+}
+
+//-----------------------------------------------------//
+
 #[derive(Clone)]
 pub enum StateStackOperationType {
     Push,
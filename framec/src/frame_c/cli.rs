@@ -1,7 +1,16 @@
-use crate::frame_c::compiler::{Exe, TargetLanguage};
-use crate::frame_c::config::FrameConfig;
+use crate::frame_c::compat;
+use crate::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use crate::frame_c::config::{FrameConfig, LintLevel, LintLevels};
+use crate::frame_c::diagnostics::{Diagnostic, MessageFormat};
+use crate::frame_c::diff;
+use crate::frame_c::formatter;
+use crate::frame_c::glob;
+use crate::frame_c::html;
+use crate::frame_c::simulator;
+use crate::frame_c::utils::RunError;
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
 // use structopt::StructOpt;
 use clap::Arg;
 
@@ -14,14 +23,90 @@ pub struct Cli {
     /// Generate a default config.yaml file and exit.
     generate_config: bool,
 
-    /// Stdin flag. Mutually exclusive with path
+    /// Stdin flag. Mutually exclusive with paths
     stdin_flag: bool,
 
-    /// Path to frame specification file.
-    path: Option<PathBuf>,
+    /// Paths, directories or patterns of Frame specification files.
+    paths: Vec<String>,
 
-    /// Target language.
+    /// Directory to write the generated files to, replicating the layout of the inputs.
+    out_dir: Option<PathBuf>,
+
+    /// Path the spec read from stdin is known by, for error messages and includes.
+    stdin_name: Option<String>,
+
+    /// Target languages, separated by commas.
     language: Option<String>,
+
+    /// Format of error messages.
+    message_format: MessageFormat,
+
+    /// Path to write the code map of the generated code to.
+    source_map: Option<PathBuf>,
+
+    /// Levels of lint rules given with `-A`, `-W` and `-D`, in command line order.
+    lint_levels: Vec<(String, LintLevel)>,
+
+    /// Arguments of the `fmt` subcommand, if it was given.
+    fmt: Option<FmtArgs>,
+
+    /// Arguments of the `compat` subcommand, if it was given.
+    compat: Option<CompatArgs>,
+
+    /// Spec to generate a test skeleton for with the `test-skeleton` subcommand, if it was given.
+    test_skeleton: Option<PathBuf>,
+
+    /// Spec or IR of the machine to simulate with the `simulate` subcommand, if it was given.
+    simulate: Option<PathBuf>,
+
+    /// Arguments of the `doc` subcommand, if it was given.
+    doc: Option<DocArgs>,
+
+    /// Arguments of the `diff` subcommand, if it was given.
+    diff: Option<DiffArgs>,
+}
+
+/// Arguments of the `framec fmt` subcommand.
+pub struct FmtArgs {
+    /// Files to format in place. Stdin is formatted to stdout when empty.
+    paths: Vec<PathBuf>,
+
+    /// Report unformatted input instead of formatting it.
+    check: bool,
+}
+
+/// Arguments of the `framec compat` subcommand.
+pub struct CompatArgs {
+    /// The old version of the spec.
+    old_path: PathBuf,
+
+    /// The new version of the spec.
+    new_path: PathBuf,
+
+    /// Treat the machine as persisted even if it doesn't handle the save and restore messages.
+    persisted: bool,
+}
+
+/// Arguments of the `framec doc` subcommand.
+pub struct DocArgs {
+    /// Specs or IR of the machines to document.
+    paths: Vec<PathBuf>,
+
+    /// Directory to write a page per machine and an index to. The page of a single machine is
+    /// written to stdout when not given.
+    out_dir: Option<PathBuf>,
+}
+
+/// Arguments of the `framec diff` subcommand.
+pub struct DiffArgs {
+    /// The old version of the spec or IR.
+    old_path: PathBuf,
+
+    /// The new version of the spec or IR.
+    new_path: PathBuf,
+
+    /// The format of the report: `text`, `json` or `dot`.
+    format: String,
 }
 
 impl Cli {
@@ -42,45 +127,238 @@ impl Cli {
                     .help("Config path")
                     .required(false),
             )
-            .arg(Arg::new("FILE-PATH").help("File path"))
+            .arg(Arg::new("FILE-PATH").multiple_values(true).help(
+                "Files, directories or patterns like `specs/**/*.frm`. Reads stdin if omitted or `-`",
+            ))
+            .arg(
+                Arg::new("OUT-DIR")
+                    .long("out-dir")
+                    .takes_value(true)
+                    .help("Write generated files here instead of stdout, mirroring the input layout"),
+            )
+            .arg(
+                Arg::new("STDIN-NAME")
+                    .long("stdin-name")
+                    .takes_value(true)
+                    .help(
+                        "Path of the spec read from stdin, used in errors and to resolve includes",
+                    ),
+            )
             .arg(
                 Arg::new("language")
                     .takes_value(true)
                     .long("language")
                     .short('l')
-                    .help("Target language"),
+                    .help("Target language, or several separated by commas with --out-dir"),
                 //                    .required_unless_present("GENERATE-CONFIG"),
             )
+            .arg(
+                Arg::new("MESSAGE-FORMAT")
+                    .long("message-format")
+                    .takes_value(true)
+                    .possible_values(["human", "json"])
+                    .default_value("human")
+                    .global(true)
+                    .help("Error format: human-readable text, or one JSON diagnostic per line"),
+            )
+            .arg(
+                Arg::new("SOURCE-MAP")
+                    .long("source-map")
+                    .takes_value(true)
+                    .help("Write the map from generated code to the spec to this file"),
+            )
+            .arg(
+                Arg::new("ALLOW")
+                    .short('A')
+                    .long("allow")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .value_name("RULE")
+                    .help("Allow a lint rule, or `warnings` to hide all warnings"),
+            )
+            .arg(
+                Arg::new("WARN")
+                    .short('W')
+                    .long("warn")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .value_name("RULE")
+                    .help("Report the findings of a lint rule as warnings"),
+            )
+            .arg(
+                Arg::new("DENY")
+                    .short('D')
+                    .long("deny")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .value_name("RULE")
+                    .help("Make the findings of a lint rule errors, or `warnings` to deny all warnings"),
+            )
+            .subcommand(
+                clap::Command::new("fmt")
+                    .about("Format Frame specifications in the canonical style")
+                    .arg(
+                        Arg::new("FILE-PATHS")
+                            .multiple_values(true)
+                            .help("Files to format in place. Formats stdin to stdout if omitted"),
+                    )
+                    .arg(Arg::new("CHECK").long("check").help(
+                        "Exit with an error if any input is not formatted, without changing it",
+                    )),
+            )
+            .subcommand(
+                clap::Command::new("compat")
+                    .about("Report the changes between two versions of a Frame specification that break compatibility")
+                    .arg(Arg::new("OLD").required(true).help("The old version of the spec"))
+                    .arg(Arg::new("NEW").required(true).help("The new version of the spec"))
+                    .arg(Arg::new("PERSISTED").long("persisted").help(
+                        "Treat changes to states and domain variables as breaking, for machines persisted without the save and restore messages",
+                    )),
+            )
+            .subcommand(
+                clap::Command::new("test-skeleton")
+                    .about("Generate a Rust test module with recording actions and a stub test per interface method")
+                    .arg(Arg::new("FILE-PATH").required(true).help("The Frame specification")),
+            )
+            .subcommand(
+                clap::Command::new("simulate")
+                    .about("Send events to a machine interactively, reading commands from stdin")
+                    .arg(Arg::new("FILE-PATH").required(true).help(
+                        "The Frame specification, or the JSON emitted for it by the `ir` target",
+                    )),
+            )
+            .subcommand(
+                clap::Command::new("doc")
+                    .about("Generate HTML documentation of machines, with their comments and diagrams")
+                    .arg(Arg::new("FILE-PATHS").required(true).multiple_values(true).help(
+                        "Frame specifications, or the JSON emitted for them by the `ir` target",
+                    ))
+                    .arg(
+                        Arg::new("OUT-DIR")
+                            .long("out-dir")
+                            .takes_value(true)
+                            .help("Write a page per machine and an index here instead of stdout"),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("diff")
+                    .about("Report the states, handlers and transitions changed between two versions of a machine")
+                    .arg(Arg::new("OLD").required(true).help("The old version of the spec or its IR"))
+                    .arg(Arg::new("NEW").required(true).help("The new version of the spec or its IR"))
+                    .arg(
+                        Arg::new("FORMAT")
+                            .long("format")
+                            .takes_value(true)
+                            .possible_values(["text", "json", "dot"])
+                            .default_value("text")
+                            .help("Report the changes as text, as JSON, or as a Graphviz diagram of the new version"),
+                    ),
+            )
             .get_matches();
 
+        let fmt = matches
+            .subcommand_matches("fmt")
+            .map(|fmt_matches| FmtArgs {
+                paths: fmt_matches
+                    .values_of("FILE-PATHS")
+                    .map(|paths| paths.map(PathBuf::from).collect())
+                    .unwrap_or_default(),
+                check: fmt_matches.is_present("CHECK"),
+            });
+
+        let compat = matches
+            .subcommand_matches("compat")
+            .map(|compat_matches| CompatArgs {
+                old_path: PathBuf::from(compat_matches.value_of("OLD").unwrap()),
+                new_path: PathBuf::from(compat_matches.value_of("NEW").unwrap()),
+                persisted: compat_matches.is_present("PERSISTED"),
+            });
+
+        let test_skeleton =
+            matches
+                .subcommand_matches("test-skeleton")
+                .map(|test_skeleton_matches| {
+                    PathBuf::from(test_skeleton_matches.value_of("FILE-PATH").unwrap())
+                });
+
+        let simulate = matches
+            .subcommand_matches("simulate")
+            .map(|simulate_matches| PathBuf::from(simulate_matches.value_of("FILE-PATH").unwrap()));
+
+        let doc = matches
+            .subcommand_matches("doc")
+            .map(|doc_matches| DocArgs {
+                paths: doc_matches
+                    .values_of("FILE-PATHS")
+                    .map(|paths| paths.map(PathBuf::from).collect())
+                    .unwrap_or_default(),
+                out_dir: doc_matches.value_of("OUT-DIR").map(PathBuf::from),
+            });
+
+        let diff = matches
+            .subcommand_matches("diff")
+            .map(|diff_matches| DiffArgs {
+                old_path: PathBuf::from(diff_matches.value_of("OLD").unwrap()),
+                new_path: PathBuf::from(diff_matches.value_of("NEW").unwrap()),
+                format: diff_matches.value_of("FORMAT").unwrap().to_string(),
+            });
+
         let generate_config = matches.is_present("GENERATE-CONFIG");
 
         let config_path_str_opt = matches.value_of("CONFIG-PATH");
         let config_path_pathbuf_opt = config_path_str_opt.map(PathBuf::from);
 
-        let mut stdin = false;
-        let mut path_opt = None;
-        if matches.is_present("FILE-PATH") {
-            let file_path = matches.value_of("FILE-PATH");
-            // path_opt = match file_path {
-            //     Some(file_path) => Some(PathBuf::from(file_path.to_string())),
-            //     None => None,
-            // };
-            path_opt = file_path.map(|file_path| PathBuf::from(file_path.to_string()));
-        } else {
-            stdin = true;
-        }
+        let paths: Vec<String> = matches
+            .values_of("FILE-PATH")
+            .map(|paths| paths.map(String::from).collect())
+            .unwrap_or_default();
+        let stdin = paths.is_empty() || paths == ["-"];
 
         let language = matches.value_of("language");
 
         let language_opt = language.map(|lang| lang.to_string());
 
+        let message_format = matches
+            .value_of("MESSAGE-FORMAT")
+            .and_then(|format| MessageFormat::try_from(format).ok())
+            .unwrap_or_default();
+
+        // later options override earlier ones, as in rustc
+        let mut lint_levels = Vec::new();
+        for (arg, level) in [
+            ("ALLOW", LintLevel::Allow),
+            ("WARN", LintLevel::Warn),
+            ("DENY", LintLevel::Deny),
+        ] {
+            if let (Some(indices), Some(names)) = (matches.indices_of(arg), matches.values_of(arg))
+            {
+                for (index, name) in indices.zip(names) {
+                    lint_levels.push((index, name.to_string(), level));
+                }
+            }
+        }
+        lint_levels.sort_by_key(|(index, _, _)| *index);
+
         Cli {
             stdin_flag: stdin,
             config: config_path_pathbuf_opt,
             generate_config,
-            path: path_opt,
+            paths,
+            out_dir: matches.value_of("OUT-DIR").map(PathBuf::from),
+            stdin_name: matches.value_of("STDIN-NAME").map(String::from),
             language: language_opt,
+            message_format,
+            source_map: matches.value_of("SOURCE-MAP").map(PathBuf::from),
+            lint_levels: lint_levels
+                .into_iter()
+                .map(|(_, name, level)| (name, level))
+                .collect(),
+            fmt,
+            compat,
+            test_skeleton,
+            simulate,
+            doc,
+            diff,
         }
     }
 }
@@ -98,7 +376,73 @@ pub fn run() {
 
 /// Run `framec` with the given CLI options.
 pub fn run_with(args: Cli) {
-    let exe = Exe::new();
+    for (name, _) in &args.lint_levels {
+        if !LintLevels::NAMES.contains(&name.as_str()) {
+            eprintln!(
+                "Unknown lint rule `{}`, expected one of: {}.",
+                name,
+                LintLevels::NAMES.join(", ")
+            );
+            std::process::exit(exitcode::USAGE);
+        }
+    }
+    let exe = Exe::with_options(CompilerOptions {
+        lint_levels: args.lint_levels.clone(),
+        ..CompilerOptions::default()
+    });
+
+    // format specifications, if requested, then exit
+    if let Some(fmt_args) = &args.fmt {
+        run_fmt(fmt_args);
+        return;
+    }
+
+    // compare two versions of a spec, if requested, then exit
+    if let Some(compat_args) = &args.compat {
+        run_compat(&exe, compat_args, args.message_format);
+        return;
+    }
+
+    // generate a test skeleton, if requested, then exit
+    if let Some(input_path) = &args.test_skeleton {
+        match exe.test_skeleton_file(&args.config, input_path) {
+            Ok(code) => write_code(&code),
+            Err(err) => report_error_and_exit(&err, args.message_format),
+        }
+        return;
+    }
+
+    // simulate a machine, if requested, then exit
+    if let Some(input_path) = &args.simulate {
+        let model = match exe.machine_model_file(input_path) {
+            Ok(model) => model,
+            Err(err) => report_error_and_exit(&err, args.message_format),
+        };
+        let stdin = std::io::stdin();
+        let interactive = atty::is(atty::Stream::Stdin);
+        let result = simulator::run(
+            model,
+            &mut stdin.lock(),
+            &mut std::io::stdout(),
+            interactive,
+        );
+        if let Err(err) = result {
+            report_error_and_exit(&err, args.message_format);
+        }
+        return;
+    }
+
+    // generate documentation, if requested, then exit
+    if let Some(doc_args) = &args.doc {
+        run_doc(&exe, doc_args, args.message_format);
+        return;
+    }
+
+    // compare two versions of a machine, if requested, then exit
+    if let Some(diff_args) = &args.diff {
+        run_diff(&exe, diff_args, args.message_format);
+        return;
+    }
 
     // generate config file, if requested, then exit
     if args.generate_config {
@@ -112,42 +456,368 @@ pub fn run_with(args: Cli) {
         return;
     }
 
-    let target_language = match args.language {
-        Some(lang_str) => match TargetLanguage::try_from(lang_str) {
-            Ok(lang) => Some(lang),
+    let mut target_languages = Vec::new();
+    for lang_str in args
+        .language
+        .iter()
+        .flat_map(|language| language.split(','))
+    {
+        match TargetLanguage::try_from(lang_str.trim()) {
+            Ok(lang) if !target_languages.contains(&lang) => target_languages.push(lang),
+            Ok(_) => {}
             Err(err) => {
                 eprintln!("{}", err);
                 std::process::exit(exitcode::USAGE);
             }
-        },
-        None => {
-            // eprintln!("No target language specified.");
-            // std::process::exit(exitcode::USAGE);
-            None
         }
-    };
+    }
+    if target_languages.len() > 1 && args.out_dir.is_none() {
+        eprintln!("Compiling to several target languages requires --out-dir.");
+        std::process::exit(exitcode::USAGE);
+    }
+    let target_language = target_languages.first().copied();
 
     // run the compiler and print output to stdout
     if args.stdin_flag {
-        match exe.run_stdin(&args.config, target_language) {
+        if atty::is(atty::Stream::Stdin) {
+            eprintln!("No input: pass the path of a Frame spec, or pipe a spec to stdin.");
+            std::process::exit(exitcode::USAGE);
+        }
+        match exe.run_stdin(&args.config, args.stdin_name.as_deref(), target_language) {
             // match exe.run_stdin(&args.config, args.language.unwrap()) {
             Ok(code) => {
-                println!("{}", code);
-            }
-            Err(err) => {
-                eprintln!("Framec failed with an error:\n{}", err.error);
-                std::process::exit(err.code);
+                report_diagnostics(&exe.warnings(), args.message_format);
+                exit_if_split(&exe);
+                write_code_map(&exe, &args.source_map);
+                write_code(&code);
             }
+            Err(err) => report_error_and_exit(&err, args.message_format),
         }
     } else {
-        match exe.run_file(&args.config, &args.path.unwrap(), target_language) {
+        let inputs = expand_inputs(&args.paths);
+        if let Some(out_dir) = &args.out_dir {
+            if target_languages.is_empty() {
+                eprintln!("--out-dir requires a target language, given with -l.");
+                std::process::exit(exitcode::USAGE);
+            }
+            if args.source_map.is_some() {
+                eprintln!(
+                    "--source-map can't be combined with --out-dir, code maps are written next \
+                     to the generated files."
+                );
+                std::process::exit(exitcode::USAGE);
+            }
+            let message_format = args.message_format;
+            run_batch(
+                &exe,
+                &args.config,
+                &inputs,
+                out_dir,
+                &target_languages,
+                message_format,
+            );
+            return;
+        }
+        if inputs.len() != 1 {
+            eprintln!(
+                "The inputs match {} specs, use --out-dir to compile several specs.",
+                inputs.len()
+            );
+            std::process::exit(exitcode::USAGE);
+        }
+        match exe.run_file(&args.config, &inputs[0].path, target_language) {
             Ok(code) => {
-                println!("{}", code);
+                report_diagnostics(&exe.warnings(), args.message_format);
+                exit_if_split(&exe);
+                write_code_map(&exe, &args.source_map);
+                write_code(&code);
             }
+            Err(err) => report_error_and_exit(&err, args.message_format),
+        }
+    }
+}
+
+/// Expand the input paths and patterns into the specs to compile, or exit if a pattern doesn't
+/// match any.
+fn expand_inputs(paths: &[String]) -> Vec<glob::Input> {
+    let mut inputs = Vec::new();
+    for path in paths {
+        match glob::expand(path) {
+            Ok(expanded) => inputs.extend(expanded),
             Err(err) => {
-                eprintln!("Framec failed with an error:\n{}", err.error);
-                std::process::exit(err.code);
+                eprintln!("{}", err);
+                std::process::exit(exitcode::NOINPUT);
+            }
+        }
+    }
+    inputs
+}
+
+/// Compile each input to a file per target language at its relative path in the output
+/// directory, along with its code map if one is generated. Each input is parsed once for all of
+/// the target languages. All inputs are compiled even if some fail; the exit code is the one of
+/// the first failure.
+fn run_batch(
+    exe: &Exe,
+    config_path: &Option<PathBuf>,
+    inputs: &[glob::Input],
+    out_dir: &Path,
+    target_languages: &[TargetLanguage],
+    message_format: MessageFormat,
+) {
+    let mut exit_code = exitcode::OK;
+    for input in inputs {
+        match exe.run_file_targets(config_path, &input.path, target_languages) {
+            Ok(outputs) => {
+                report_diagnostics(&exe.warnings(), message_format);
+                let mut files = Vec::new();
+                for output in outputs {
+                    let mut output_path = out_dir.join(&input.relative_path);
+                    output_path.set_extension(&output.file_extension);
+                    let output_dir = output_path.parent().unwrap_or(out_dir).to_path_buf();
+                    for (path, state_code) in output.state_files {
+                        files.push((output_dir.join(path), state_code));
+                    }
+                    if let Some(code_map) = output.code_map {
+                        let mut map_path = output_path.clone().into_os_string();
+                        map_path.push(".map.json");
+                        files.push((PathBuf::from(map_path), code_map.to_json()));
+                    }
+                    files.push((output_path, output.code));
+                }
+                for (i, (path, _)) in files.iter().enumerate() {
+                    if files[..i].iter().any(|(other_path, _)| other_path == path) {
+                        eprintln!(
+                            "Several target languages generate {}, compile them separately.",
+                            path.display()
+                        );
+                        std::process::exit(exitcode::USAGE);
+                    }
+                }
+                for (path, content) in files {
+                    let written = path
+                        .parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .and_then(|_| std::fs::write(&path, content));
+                    if let Err(err) = written {
+                        eprintln!("Error writing {}: {}", path.display(), err);
+                        std::process::exit(exitcode::IOERR);
+                    }
+                }
+            }
+            Err(err) => {
+                if message_format == MessageFormat::Human {
+                    eprintln!("Framec failed to compile {}:", input.path.display());
+                }
+                report_error(&err, message_format);
+                if exit_code == exitcode::OK {
+                    exit_code = err.code;
+                }
+            }
+        }
+    }
+    if exit_code != exitcode::OK {
+        std::process::exit(exit_code);
+    }
+}
+
+/// Report the changes between two versions of a spec, and exit with an error if any of them
+/// breaks compatibility.
+fn run_compat(exe: &Exe, args: &CompatArgs, message_format: MessageFormat) {
+    let changes = match exe.compare_files(&args.old_path, &args.new_path, args.persisted) {
+        Ok(changes) => changes,
+        Err(err) => report_error_and_exit(&err, message_format),
+    };
+    report_diagnostics(&changes, message_format);
+    let breaking_changes = compat::breaking_changes(&changes);
+    if breaking_changes > 0 {
+        if message_format == MessageFormat::Human {
+            eprintln!("{} breaking change(s).", breaking_changes);
+        }
+        std::process::exit(exitcode::DATAERR);
+    }
+}
+
+/// Write the documentation page of each machine and an index to the output directory, or the
+/// page of a single machine to stdout.
+fn run_doc(exe: &Exe, args: &DocArgs, message_format: MessageFormat) {
+    let mut models = Vec::new();
+    for path in &args.paths {
+        match exe.machine_model_file(path) {
+            Ok(model) => models.push(model),
+            Err(err) => report_error_and_exit(&err, message_format),
+        }
+    }
+    let out_dir = match &args.out_dir {
+        Some(out_dir) => out_dir,
+        None if models.len() == 1 => {
+            write_code(&html::render(&models[0]));
+            return;
+        }
+        None => {
+            eprintln!("Documenting several machines requires --out-dir.");
+            std::process::exit(exitcode::USAGE);
+        }
+    };
+    let mut files = vec![(out_dir.join("index.html"), html::render_index(&models))];
+    for model in &models {
+        let path = out_dir.join(html::page_file_name(model));
+        if files.iter().any(|(other_path, _)| *other_path == path) {
+            eprintln!("Several machines are named {}.", model.name);
+            std::process::exit(exitcode::USAGE);
+        }
+        files.push((path, html::render(model)));
+    }
+    for (path, content) in files {
+        let written = std::fs::create_dir_all(out_dir).and_then(|_| std::fs::write(&path, content));
+        if let Err(err) = written {
+            eprintln!("Error writing {}: {}", path.display(), err);
+            std::process::exit(exitcode::IOERR);
+        }
+    }
+}
+
+/// Print the differences between two versions of a machine in the requested format.
+fn run_diff(exe: &Exe, args: &DiffArgs, message_format: MessageFormat) {
+    let mut models = Vec::new();
+    for path in [&args.old_path, &args.new_path] {
+        match exe.machine_model_file(path) {
+            Ok(model) => models.push(model),
+            Err(err) => report_error_and_exit(&err, message_format),
+        }
+    }
+    let (old, new) = (&models[0], &models[1]);
+    let report = match args.format.as_str() {
+        "json" => serde_json::to_string_pretty(&diff::diff(old, new)).unwrap(),
+        "dot" => diff::diagram(old, new),
+        _ => diff::diff(old, new)
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    if !report.is_empty() {
+        write_code(report.trim_end());
+    }
+}
+
+/// Format the given files in place, or stdin to stdout. With `--check`, list the inputs that
+/// are not formatted and exit with an error if there are any.
+fn run_fmt(args: &FmtArgs) {
+    if args.paths.is_empty() {
+        let mut source = String::new();
+        if let Err(err) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut source) {
+            eprintln!("Error reading stdin: {}", err);
+            std::process::exit(exitcode::IOERR);
+        }
+        if args.check {
+            if !formatter::is_formatted(&source) {
+                eprintln!("<stdin> is not formatted");
+                std::process::exit(exitcode::DATAERR);
+            }
+        } else {
+            print!("{}", formatter::format_source(&source));
+        }
+        return;
+    }
+
+    let mut unformatted = false;
+    for path in &args.paths {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) => {
+                eprintln!("Error reading {}: {}", path.display(), err);
+                std::process::exit(exitcode::NOINPUT);
+            }
+        };
+        let formatted = formatter::format_source(&source);
+        if formatted == source {
+            continue;
+        }
+        if args.check {
+            eprintln!("{} is not formatted", path.display());
+            unformatted = true;
+        } else if let Err(err) = std::fs::write(path, formatted) {
+            eprintln!("Error writing {}: {}", path.display(), err);
+            std::process::exit(exitcode::IOERR);
+        }
+    }
+    if unformatted {
+        std::process::exit(exitcode::DATAERR);
+    }
+}
+
+/// Print diagnostics that are not part of an error, such as the warnings of a successful
+/// compilation, to stderr in the requested format.
+fn report_diagnostics(diagnostics: &[Diagnostic], message_format: MessageFormat) {
+    for diagnostic in diagnostics {
+        match message_format {
+            MessageFormat::Human => eprintln!("{}", diagnostic),
+            MessageFormat::Json => eprintln!("{}", diagnostic.to_json()),
+        }
+    }
+}
+
+/// Write the generated code to stdout. A reader that stops reading early, e.g. `head` at the end
+/// of a pipeline, is not an error.
+fn write_code(code: &str) {
+    let mut stdout = std::io::stdout().lock();
+    if let Err(err) = writeln!(stdout, "{}", code).and_then(|_| stdout.flush()) {
+        if err.kind() == ErrorKind::BrokenPipe {
+            return;
+        }
+        eprintln!("Error writing stdout: {}", err);
+        std::process::exit(exitcode::IOERR);
+    }
+}
+
+/// Exit if the generated code includes state files, which can't be written to stdout.
+fn exit_if_split(exe: &Exe) {
+    if !exe.state_files().is_empty() {
+        eprintln!(
+            "The generated code is split into files by the split_states feature, use --out-dir \
+             to write them."
+        );
+        std::process::exit(exitcode::USAGE);
+    }
+}
+
+/// Write the code map of a successful compilation to the path given with `--source-map`.
+fn write_code_map(exe: &Exe, path_opt: &Option<PathBuf>) {
+    let path = match path_opt {
+        Some(path) => path,
+        None => return,
+    };
+    match exe.code_map() {
+        Some(code_map) => {
+            if let Err(err) = std::fs::write(path, code_map.to_json()) {
+                eprintln!("Error writing {}: {}", path.display(), err);
+                std::process::exit(exitcode::IOERR);
             }
         }
+        None => {
+            eprintln!(
+                "No source map was generated. Source maps require the Rust backend with the \
+                 codegen.rust.features.generate_source_map feature enabled."
+            );
+            std::process::exit(exitcode::USAGE);
+        }
+    }
+}
+
+/// Print a compilation error to stderr in the requested format and exit.
+fn report_error_and_exit(err: &RunError, message_format: MessageFormat) -> ! {
+    if message_format == MessageFormat::Human {
+        eprintln!("Framec failed with an error:");
+    }
+    report_error(err, message_format);
+    std::process::exit(err.code);
+}
+
+/// Print a compilation error to stderr in the requested format.
+fn report_error(err: &RunError, message_format: MessageFormat) {
+    match message_format {
+        MessageFormat::Human => eprintln!("{}", err.error),
+        MessageFormat::Json => eprint!("{}", err.render(MessageFormat::Json)),
     }
 }
@@ -0,0 +1,141 @@
+//! Maps from generated code back to the Frame specification it was generated from.
+//!
+//! When the `generate_source_map` feature of a backend is enabled, the backend records the
+//! originating location of each generated item: state handler functions, event handler match
+//! arms and interface methods. The compiler resolves the locations to files, which matters for
+//! specifications that `#include` other files, and the result is available from
+//! [`Exe::code_map`](crate::frame_c::compiler::Exe::code_map). It is usually written next to the
+//! generated code as a `.map.json` file, for example `traffic.rs.map.json` for `traffic.rs`.
+
+use crate::frame_c::diagnostics::Span;
+use serde::Serialize;
+
+/// The version of the JSON format of code maps.
+pub const CODE_MAP_VERSION: u32 = 1;
+
+/// The kind of a generated item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeMapKind {
+    State,
+    EventHandler,
+    InterfaceMethod,
+}
+
+/// The originating location of one generated item.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CodeMapping {
+    /// 1-based line of the generated code the item starts on.
+    pub generated_line: usize,
+    pub kind: CodeMapKind,
+    /// Name of the item in the specification: the state or interface method name, or the state
+    /// and message of an event handler, e.g. `Idle:>` for the enter handler of `$Idle`.
+    pub name: String,
+    /// Path of the file the item was declared in, if the specification was loaded from a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// 1-based line of the item in `source_file`.
+    pub source_line: usize,
+    /// The name of the item in `source_file`.
+    pub source_span: Span,
+}
+
+/// The code map of one generated file.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CodeMap {
+    pub version: u32,
+    /// Mappings in the order of `generated_line`.
+    pub mappings: Vec<CodeMapping>,
+}
+
+impl CodeMap {
+    pub fn new(mappings: Vec<CodeMapping>) -> CodeMap {
+        CodeMap {
+            version: CODE_MAP_VERSION,
+            mappings,
+        }
+    }
+
+    /// The mapping of the innermost item containing the given line of generated code.
+    pub fn lookup(&self, generated_line: usize) -> Option<&CodeMapping> {
+        self.mappings
+            .iter()
+            .take_while(|mapping| mapping.generated_line <= generated_line)
+            .last()
+    }
+
+    /// Render the code map as JSON.
+    pub fn to_json(&self) -> String {
+        // Serializing a plain struct of strings and numbers cannot fail.
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const SPEC: &str = "#[codegen.rust.features.generate_source_map:bool=\"true\"]
+#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+    /// Test that each generated item maps back to the line and span of its name in the spec.
+    #[test]
+    fn rust_code_map() {
+        let exe = Exe::new();
+        let code = exe
+            .run(
+                &None,
+                Some("lamp.frm"),
+                SPEC.to_string(),
+                Some(TargetLanguage::Rust),
+            )
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        let code_map = exe.code_map().expect("expected a code map");
+        let names: Vec<_> = code_map.mappings.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["toggle", "Off", "Off:toggle", "On", "On:toggle"]
+        );
+
+        let lines: Vec<&str> = code.lines().collect();
+        for mapping in &code_map.mappings {
+            assert_eq!(mapping.source_file.as_deref(), Some("lamp.frm"));
+            let span = mapping.source_span;
+            let spec_name = &SPEC[span.start..span.start + span.length];
+            assert!(mapping.name.ends_with(spec_name));
+            assert_eq!(
+                SPEC.lines()
+                    .nth(mapping.source_line - 1)
+                    .map(|line| line.contains(spec_name)),
+                Some(true)
+            );
+            assert!(lines[mapping.generated_line - 1].contains('{'));
+        }
+
+        let on_toggle = &code_map.mappings[4];
+        assert_eq!(
+            code_map.lookup(on_toggle.generated_line + 1),
+            Some(on_toggle)
+        );
+    }
+
+    /// Test that no code map is recorded unless the feature is enabled.
+    #[test]
+    fn disabled_by_default() {
+        let spec = SPEC.replacen("true", "false", 1);
+        let exe = Exe::new();
+        assert!(exe
+            .run(&None, None, spec, Some(TargetLanguage::Rust))
+            .is_ok());
+        assert_eq!(exe.code_map(), None);
+    }
+}
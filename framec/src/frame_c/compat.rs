@@ -0,0 +1,469 @@
+//! Compatibility checking between two versions of a Frame specification.
+//!
+//! Teams that evolve long-lived machines need to know when a new version of a spec breaks the
+//! code that drives the machine, or machines that were persisted with the old version. The
+//! changes are reported as diagnostics:
+//!
+//! * errors for breaking changes: removed interface methods (`removed_event`), changed
+//!   parameters (`changed_parameters`) or return types (`changed_return_type`) of interface
+//!   methods, and, for persisted machines, removed states (`removed_state`), changed state
+//!   parameters or variables (`changed_state_data`) and removed or retyped domain variables
+//!   (`changed_domain_variable`),
+//! * warnings for domain variables added to a persisted machine (`added_domain_variable`),
+//!   which data saved by the old version doesn't hold,
+//! * notes for compatible changes, such as added interface methods and states.
+//!
+//! A machine is persisted if it handles the save (`>>>`) or restore (`<<<`) messages, or if the
+//! caller says so because it persists machines by other means.
+
+use crate::frame_c::ast::{
+    InterfaceMethodNode, MessageType, ParameterNode, StateNode, SystemNode, TypeNode,
+    VariableDeclNode,
+};
+use crate::frame_c::diagnostics::{Diagnostic, Severity};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The version of the spec a change is located in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Version {
+    Old,
+    New,
+}
+
+/// Does the machine handle the save or restore messages?
+pub fn is_persisted(system_node: &SystemNode) -> bool {
+    states(system_node).iter().any(|state_rcref| {
+        state_rcref
+            .borrow()
+            .evt_handlers_rcref
+            .iter()
+            .any(|handler_rcref| match &handler_rcref.borrow().msg_t {
+                MessageType::CustomMessage { message_node } => {
+                    message_node.name == ">>>" || message_node.name == "<<<"
+                }
+                MessageType::AnyMessage { .. } => false,
+            })
+    })
+}
+
+/// Compare two versions of a spec and report the changes between them, in the order of the
+/// interface, the machine and the domain. Changes to states and domain variables are breaking
+/// if `persisted` is set.
+pub fn compare(old: &SystemNode, new: &SystemNode, persisted: bool) -> Vec<(Version, Diagnostic)> {
+    let mut changes = Vec::new();
+    compare_interfaces(old, new, &mut changes);
+    compare_states(old, new, persisted, &mut changes);
+    compare_domains(old, new, persisted, &mut changes);
+    changes
+}
+
+/// The number of breaking changes among the reported changes.
+pub fn breaking_changes(changes: &[Diagnostic]) -> usize {
+    changes
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count()
+}
+
+/* --------------------------------------------------------------------- */
+
+fn compare_interfaces(
+    old: &SystemNode,
+    new: &SystemNode,
+    changes: &mut Vec<(Version, Diagnostic)>,
+) {
+    let old_methods = interface_methods(old);
+    let new_methods = interface_methods(new);
+    for old_rcref in &old_methods {
+        let old_method = old_rcref.borrow();
+        let new_rcref = match new_methods
+            .iter()
+            .find(|new_rcref| new_rcref.borrow().name == old_method.name)
+        {
+            Some(new_rcref) => new_rcref,
+            None => {
+                let message = format!("Interface method {} was removed.", old_method.name);
+                changes.push((
+                    Version::Old,
+                    at_method(Severity::Error, "removed_event", &message, &old_method),
+                ));
+                continue;
+            }
+        };
+        let new_method = new_rcref.borrow();
+
+        let old_params = format_params(&old_method.params);
+        let new_params = format_params(&new_method.params);
+        if old_params != new_params {
+            let message = format!(
+                "Parameters of interface method {} changed from [{}] to [{}].",
+                new_method.name, old_params, new_params
+            );
+            changes.push((
+                Version::New,
+                at_method(Severity::Error, "changed_parameters", &message, &new_method),
+            ));
+        }
+
+        let old_return_type = format_type(&old_method.return_type_opt);
+        let new_return_type = format_type(&new_method.return_type_opt);
+        if old_return_type != new_return_type {
+            let message = format!(
+                "Return type of interface method {} changed from {} to {}.",
+                new_method.name, old_return_type, new_return_type
+            );
+            changes.push((
+                Version::New,
+                at_method(
+                    Severity::Error,
+                    "changed_return_type",
+                    &message,
+                    &new_method,
+                ),
+            ));
+        }
+    }
+
+    for new_rcref in &new_methods {
+        let new_method = new_rcref.borrow();
+        if !old_methods
+            .iter()
+            .any(|old_rcref| old_rcref.borrow().name == new_method.name)
+        {
+            let message = format!("Interface method {} was added.", new_method.name);
+            changes.push((
+                Version::New,
+                at_method(Severity::Note, "added_event", &message, &new_method),
+            ));
+        }
+    }
+}
+
+fn compare_states(
+    old: &SystemNode,
+    new: &SystemNode,
+    persisted: bool,
+    changes: &mut Vec<(Version, Diagnostic)>,
+) {
+    let severity = if persisted {
+        Severity::Error
+    } else {
+        Severity::Note
+    };
+    let old_states = states(old);
+    let new_states = states(new);
+    for old_rcref in &old_states {
+        let old_state = old_rcref.borrow();
+        let new_rcref = match new_states
+            .iter()
+            .find(|new_rcref| new_rcref.borrow().name == old_state.name)
+        {
+            Some(new_rcref) => new_rcref,
+            None => {
+                let message = if persisted {
+                    format!(
+                        "State ${} was removed, so persisted machines in it can't be restored.",
+                        old_state.name
+                    )
+                } else {
+                    format!("State ${} was removed.", old_state.name)
+                };
+                changes.push((
+                    Version::Old,
+                    at_state(severity, "removed_state", &message, &old_state),
+                ));
+                continue;
+            }
+        };
+        let new_state = new_rcref.borrow();
+
+        let old_data = format_state_data(&old_state);
+        let new_data = format_state_data(&new_state);
+        if old_data != new_data {
+            let message = format!(
+                "Parameters and variables of state ${} changed from [{}] to [{}].",
+                new_state.name, old_data, new_data
+            );
+            changes.push((
+                Version::New,
+                at_state(severity, "changed_state_data", &message, &new_state),
+            ));
+        }
+    }
+
+    for new_rcref in &new_states {
+        let new_state = new_rcref.borrow();
+        if !old_states
+            .iter()
+            .any(|old_rcref| old_rcref.borrow().name == new_state.name)
+        {
+            let message = format!("State ${} was added.", new_state.name);
+            changes.push((
+                Version::New,
+                at_state(Severity::Note, "added_state", &message, &new_state),
+            ));
+        }
+    }
+}
+
+fn compare_domains(
+    old: &SystemNode,
+    new: &SystemNode,
+    persisted: bool,
+    changes: &mut Vec<(Version, Diagnostic)>,
+) {
+    if !persisted {
+        return;
+    }
+    let old_vars = domain_variables(old);
+    let new_vars = domain_variables(new);
+    for old_rcref in &old_vars {
+        let old_var = old_rcref.borrow();
+        let (version, message) = match new_vars
+            .iter()
+            .find(|new_rcref| new_rcref.borrow().name == old_var.name)
+        {
+            None => (
+                Version::Old,
+                format!("Domain variable {} was removed.", old_var.name),
+            ),
+            Some(new_rcref) => {
+                let old_type = format_type(&old_var.type_opt);
+                let new_type = format_type(&new_rcref.borrow().type_opt);
+                if old_type == new_type {
+                    continue;
+                }
+                let message = format!(
+                    "Type of domain variable {} changed from {} to {}.",
+                    old_var.name, old_type, new_type
+                );
+                (Version::New, message)
+            }
+        };
+        let diagnostic = Diagnostic::error("changed_domain_variable", &message);
+        changes.push((version, diagnostic));
+    }
+
+    for new_rcref in &new_vars {
+        let new_var = new_rcref.borrow();
+        if !old_vars
+            .iter()
+            .any(|old_rcref| old_rcref.borrow().name == new_var.name)
+        {
+            let message = format!(
+                "Domain variable {} was added, data saved by the old version doesn't hold it.",
+                new_var.name
+            );
+            let diagnostic = Diagnostic::warning("added_domain_variable", &message);
+            changes.push((Version::New, diagnostic));
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+fn interface_methods(system_node: &SystemNode) -> Vec<Rc<RefCell<InterfaceMethodNode>>> {
+    system_node
+        .interface_block_node_opt
+        .as_ref()
+        .map(|interface_block_node| interface_block_node.interface_methods.clone())
+        .unwrap_or_default()
+}
+
+fn states(system_node: &SystemNode) -> Vec<Rc<RefCell<StateNode>>> {
+    system_node
+        .machine_block_node_opt
+        .as_ref()
+        .map(|machine_block_node| machine_block_node.states.clone())
+        .unwrap_or_default()
+}
+
+/// The domain variables, without constants which are not persisted.
+fn domain_variables(system_node: &SystemNode) -> Vec<Rc<RefCell<VariableDeclNode>>> {
+    system_node
+        .domain_block_node_opt
+        .as_ref()
+        .map(|domain_block_node| {
+            domain_block_node
+                .member_variables
+                .iter()
+                .filter(|var_rcref| !var_rcref.borrow().is_constant)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn format_type(type_opt: &Option<TypeNode>) -> String {
+    match type_opt {
+        Some(type_node) => type_node.get_type_str(),
+        None => String::from("no type"),
+    }
+}
+
+fn format_params(params_opt: &Option<Vec<ParameterNode>>) -> String {
+    params_opt
+        .iter()
+        .flatten()
+        .map(|param| match &param.param_type_opt {
+            Some(type_node) => format!("{}:{}", param.param_name, type_node.get_type_str()),
+            None => param.param_name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn format_state_data(state_node: &StateNode) -> String {
+    let mut data = vec![format_params(&state_node.params_opt)];
+    for var_rcref in state_node.vars_opt.iter().flatten() {
+        let var = var_rcref.borrow();
+        match &var.type_opt {
+            Some(type_node) => data.push(format!("var {}:{}", var.name, type_node.get_type_str())),
+            None => data.push(format!("var {}", var.name)),
+        }
+    }
+    data.retain(|item| !item.is_empty());
+    data.join(" ")
+}
+
+fn at_method(
+    severity: Severity,
+    code: &str,
+    message: &str,
+    method: &InterfaceMethodNode,
+) -> Diagnostic {
+    Diagnostic::new(severity, code, message)
+        .with_line(method.line)
+        .with_span(method.name_span.start, method.name_span.length)
+        .with_lexeme(&method.name)
+}
+
+fn at_state(severity: Severity, code: &str, message: &str, state_node: &StateNode) -> Diagnostic {
+    Diagnostic::new(severity, code, message)
+        .with_line(state_node.line)
+        .with_span(state_node.name_span.start, state_node.name_span.length)
+        .with_lexeme(&state_node.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+
+    const OLD: &str = "#Door
+    -interface-
+    open [force:bool]
+    close
+    lock : bool
+    -machine-
+    $Closed
+        |open| [force:bool] -> $Opened ^
+    $Opened
+        var count:int = 0
+        |close| -> $Closed ^
+    $Locked
+    -domain-
+    var code:int = 0
+##
+";
+
+    const NEW: &str = "#Door
+    -interface-
+    open [force:bool speed:int]
+    lock : int
+    unlock
+    -machine-
+    $Closed
+        |open| [force:bool speed:int] -> $Opened ^
+    $Opened
+        var count:string = \"\"
+        |unlock| -> $Closed ^
+    $Ajar
+    -domain-
+    var code:string = \"\"
+    var extra:int = 0
+##
+";
+
+    fn compare_specs(old: &str, new: &str, persisted: bool) -> Vec<(Version, Severity, String)> {
+        let exe = Exe::new();
+        let old = exe.parse(None, old.to_string()).unwrap();
+        let new = exe.parse(None, new.to_string()).unwrap();
+        compare(&old, &new, persisted)
+            .into_iter()
+            .map(|(version, diagnostic)| (version, diagnostic.severity, diagnostic.code))
+            .collect()
+    }
+
+    /// Test that changes to the interface are breaking, and that changes to states and domain
+    /// variables are only breaking for persisted machines.
+    #[test]
+    fn breaking_changes() {
+        let interface_changes = [
+            (
+                Version::New,
+                Severity::Error,
+                "changed_parameters".to_string(),
+            ),
+            (Version::Old, Severity::Error, "removed_event".to_string()),
+            (
+                Version::New,
+                Severity::Error,
+                "changed_return_type".to_string(),
+            ),
+            (Version::New, Severity::Note, "added_event".to_string()),
+        ];
+
+        let changes = compare_specs(OLD, NEW, false);
+        assert_eq!(changes[..4], interface_changes);
+        assert_eq!(
+            changes[4..],
+            [
+                (
+                    Version::New,
+                    Severity::Note,
+                    "changed_state_data".to_string()
+                ),
+                (Version::Old, Severity::Note, "removed_state".to_string()),
+                (Version::New, Severity::Note, "added_state".to_string()),
+            ]
+        );
+
+        let changes = compare_specs(OLD, NEW, true);
+        assert_eq!(changes[..4], interface_changes);
+        assert_eq!(
+            changes[4..],
+            [
+                (
+                    Version::New,
+                    Severity::Error,
+                    "changed_state_data".to_string()
+                ),
+                (Version::Old, Severity::Error, "removed_state".to_string()),
+                (Version::New, Severity::Note, "added_state".to_string()),
+                (
+                    Version::New,
+                    Severity::Error,
+                    "changed_domain_variable".to_string()
+                ),
+                (
+                    Version::New,
+                    Severity::Warning,
+                    "added_domain_variable".to_string()
+                ),
+            ]
+        );
+
+        assert!(compare_specs(OLD, OLD, true).is_empty());
+    }
+
+    /// Test that machines that handle the save or restore messages are persisted.
+    #[test]
+    fn persisted_machines() {
+        let exe = Exe::new();
+        assert!(!is_persisted(&exe.parse(None, OLD.to_string()).unwrap()));
+        let persisted = OLD.replace("    $Locked\n", "    $Locked\n        |>>>| ^\n");
+        assert!(is_persisted(&exe.parse(None, persisted).unwrap()));
+    }
+}
@@ -1,8 +1,20 @@
-use crate::frame_c::config::FrameConfig;
+use crate::frame_c::ast::SystemNode;
+use crate::frame_c::codemap::CodeMap;
+use crate::frame_c::compat::{self, Version};
+use crate::frame_c::config::{FrameConfig, LintLevel};
+use crate::frame_c::diagnostics::Diagnostic;
+use crate::frame_c::includes::{IncludeExpander, SourceMap};
+use crate::frame_c::ir::MachineModel;
+use crate::frame_c::lint;
+use crate::frame_c::model_checker;
+use crate::frame_c::optimizer;
 use crate::frame_c::parser::*;
+use crate::frame_c::scaffold;
 use crate::frame_c::scanner::*;
 use crate::frame_c::symbol_table::*;
-use crate::frame_c::utils::{frame_exitcode, RunError};
+use crate::frame_c::template::{self, FragmentTemplates};
+use crate::frame_c::test_skeleton;
+use crate::frame_c::utils::{frame_exitcode, RunError, SystemHierarchy};
 use crate::frame_c::visitors::cpp_visitor::CppVisitor;
 use crate::frame_c::visitors::cs_visitor::CsVisitor;
 use crate::frame_c::visitors::cs_visitor_for_bob::CsVisitorForBob;
@@ -12,14 +24,18 @@ use crate::frame_c::visitors::java_8_visitor::Java8Visitor;
 use crate::frame_c::visitors::javascript_visitor::JavaScriptVisitor;
 use crate::frame_c::visitors::plantuml_visitor::PlantUmlVisitor;
 use crate::frame_c::visitors::python_visitor::PythonVisitor;
-use crate::frame_c::visitors::rust_visitor::RustVisitor;
+use crate::frame_c::visitors::rust_visitor::{self, RustVisitor};
 use crate::frame_c::visitors::smcat_visitor::SmcatVisitor;
 use exitcode::USAGE;
+use figment::Figment;
 use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 // Re-export this enum here since it's part of the interface for the run functions. The definition
 // lives with visitors since adding a new visitor requires extending the enum and its trait impls.
@@ -31,15 +47,202 @@ pub use crate::frame_c::visitors::TargetLanguage;
 static IS_DEBUG: bool = false;
 static FRAMEC_VERSION: &str = "emitted from framec_v0.8.0";
 
+/// The version of framec, e.g. `0.8.0`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /* --------------------------------------------------------------------- */
 
-pub struct Exe {}
+/// Options for configuring the compiler in code, for embedders that don't want to write a
+/// `config.yaml` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompilerOptions {
+    /// The base configuration, which takes the place of the default configuration. A
+    /// configuration file and the configuration attributes in the spec are merged on top of it.
+    pub config: FrameConfig,
+
+    /// When enabled, a `config.yaml` file in the current working directory is loaded if no
+    /// configuration file is passed to `run`.
+    ///
+    /// Default is `true`.
+    pub load_local_config: bool,
+
+    /// Levels of lint rules, or of `warnings`, that override the configuration, applied in
+    /// order. These are the `-A`, `-W` and `-D` options of the command line.
+    pub lint_levels: Vec<(String, LintLevel)>,
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        CompilerOptions {
+            config: FrameConfig::default(),
+            load_local_config: true,
+            lint_levels: Vec::new(),
+        }
+    }
+}
+
+impl From<FrameConfig> for CompilerOptions {
+    fn from(config: FrameConfig) -> Self {
+        CompilerOptions {
+            config,
+            ..CompilerOptions::default()
+        }
+    }
+}
+
+/// The code generated for one of the target languages of `Exe::run_targets`.
+#[derive(Clone, Debug)]
+pub struct TargetOutput {
+    /// The target language the code was generated for.
+    pub target_language: TargetLanguage,
+
+    /// The generated code.
+    pub code: String,
+
+    /// The map from the generated code back to the Frame specification, if the backend
+    /// generated one. See `Exe::code_map`.
+    pub code_map: Option<CodeMap>,
+
+    /// The files of the state modules, by path relative to the directory of the generated code.
+    /// See `Exe::state_files`.
+    pub state_files: Vec<(PathBuf, String)>,
+
+    /// The extension of the file of the generated code. See `Exe::file_extension`.
+    pub file_extension: String,
+}
+
+// The path of a specification and of its configuration file.
+type AnalysisKey = (Option<String>, Option<PathBuf>);
+
+// The result of analyzing a specification, from which the backends generate code.
+struct Analysis {
+    // hash of the contents of the specification
+    sha256: String,
+    generate_enter_args: bool,
+    generate_exit_args: bool,
+    generate_state_context: bool,
+    generate_state_stack: bool,
+    generate_change_state: bool,
+    generate_transition_state: bool,
+    generate_deep_history: bool,
+    generate_regions: bool,
+    generate_transition_actions: bool,
+    source_map: SourceMap,
+    // the optimized AST
+    system_node: SystemNode,
+    arcanum: Arcanum,
+    system_hierarchy: SystemHierarchy,
+    comments: Vec<Token>,
+    removed_states: Vec<String>,
+    // findings of the lint rules at level `warn` and notes of the optimizer
+    warnings: Vec<Diagnostic>,
+}
+
+/// The Frame compiler.
+///
+/// A single `Exe` can compile any number of specs to any targets, and reusing one is the
+/// supported way to compile many files: each configuration file is read once and its contents
+/// are reused by later runs, while the per-run state (warnings and the code map) is reset at the
+/// start of each run. Changes to a configuration file after it was first read are not seen by
+/// the same `Exe`.
+///
+/// The parsed, linted and optimized spec of each path is kept until the next run on that path,
+/// which generates code from it directly if neither the spec nor the files it includes have
+/// changed, so recompiling unchanged specs, e.g. in an editor or to another target, is cheap.
+///
+/// ```no_run
+/// use framec::frame_c::compiler::{Exe, TargetLanguage};
+/// use std::path::Path;
+///
+/// let exe = Exe::new();
+/// for spec in ["a.frm", "b.frm"] {
+///     let code = exe.run_file(&None, Path::new(spec), Some(TargetLanguage::Rust));
+///     let warnings = exe.warnings();
+///     // ...
+/// }
+/// ```
+pub struct Exe {
+    options: CompilerOptions,
+    // base configuration merged with each configuration file that has been read, by path
+    config_files: RefCell<HashMap<Option<PathBuf>, Figment>>,
+    // warnings reported by the most recent run
+    warnings: RefCell<Vec<Diagnostic>>,
+    // code map of the most recent run, if the backend generated one
+    code_map: RefCell<Option<CodeMap>>,
+    // files of the state modules generated by the most recent run
+    state_files: RefCell<Vec<(PathBuf, String)>>,
+    // extension of the file of the code generated by the most recent run
+    file_extension: RefCell<Option<String>>,
+    // analysis of the most recent run by path of the spec and of the configuration file
+    analyses: RefCell<HashMap<AnalysisKey, Rc<Analysis>>>,
+}
 
 impl Exe {
     /* --------------------------------------------------------------------- */
 
     pub fn new() -> Exe {
-        Exe {}
+        Exe::with_options(CompilerOptions::default())
+    }
+
+    /// Create a compiler configured by the given options instead of the defaults.
+    ///
+    /// ```
+    /// use framec::frame_c::compiler::{CompilerOptions, Exe};
+    /// use framec::frame_c::config::FrameConfig;
+    ///
+    /// let mut config = FrameConfig::default();
+    /// config.codegen.rust.features.runtime_support = true;
+    /// let exe = Exe::with_options(CompilerOptions {
+    ///     config,
+    ///     load_local_config: false,
+    ///     ..CompilerOptions::default()
+    /// });
+    /// ```
+    pub fn with_options(options: CompilerOptions) -> Exe {
+        Exe {
+            options,
+            config_files: RefCell::new(HashMap::new()),
+            warnings: RefCell::new(Vec::new()),
+            code_map: RefCell::new(None),
+            state_files: RefCell::new(Vec::new()),
+            file_extension: RefCell::new(None),
+            analyses: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The options this compiler was created with.
+    pub fn options(&self) -> &CompilerOptions {
+        &self.options
+    }
+
+    /// The warnings reported by the most recent run, such as findings of lint rules at level
+    /// `warn`, along with the notes of the optimizer. Warnings don't fail the compilation, so
+    /// they are not part of the `RunError`.
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        self.warnings.borrow().clone()
+    }
+
+    /// The map from the code generated by the most recent run back to the Frame specification.
+    /// This is only available from backends whose `generate_source_map` feature is enabled.
+    pub fn code_map(&self) -> Option<CodeMap> {
+        self.code_map.borrow().clone()
+    }
+
+    /// The files of the state modules generated by the most recent run, by path relative to the
+    /// directory of the generated code, which includes them. These are only generated by the
+    /// Rust backend when its `split_states` feature is enabled.
+    pub fn state_files(&self) -> Vec<(PathBuf, String)> {
+        self.state_files.borrow().clone()
+    }
+
+    /// The extension of the file of the code generated by the most recent run. This is the
+    /// extension of the target language, unless set by the configuration of the template
+    /// backend.
+    pub fn file_extension(&self, target_language: TargetLanguage) -> String {
+        match &*self.file_extension.borrow() {
+            Some(file_extension) => file_extension.clone(),
+            None => target_language.file_extension().to_string(),
+        }
     }
 
     pub fn debug_print(msg: &str) {
@@ -81,11 +284,278 @@ impl Exe {
         }
     }
 
+    /// Run the Frame compiler on a Frame specification loaded from a file, generating code for
+    /// each of the target languages. See `run_targets`.
+    pub fn run_file_targets(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path: &Path,
+        target_languages: &[TargetLanguage],
+    ) -> Result<Vec<TargetOutput>, RunError> {
+        match fs::read_to_string(input_path) {
+            Ok(content) => {
+                self.run_targets(config_path, input_path.to_str(), content, target_languages)
+            }
+            Err(err) => {
+                let error_msg = format!("Error reading input file: {}", err);
+                let run_error = RunError::new(exitcode::NOINPUT, &error_msg);
+                Err(run_error)
+            }
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    /// Parse a Frame specification loaded from a file, without generating code. See `parse`.
+    pub fn parse_file(&self, input_path: &Path) -> Result<SystemNode, RunError> {
+        match fs::read_to_string(input_path) {
+            Ok(content) => self.parse(input_path.to_str(), content),
+            Err(err) => {
+                let error_msg = format!("Error reading input file: {}", err);
+                let run_error = RunError::new(exitcode::NOINPUT, &error_msg);
+                Err(run_error)
+            }
+        }
+    }
+
+    /// Parse a Frame specification passed as a `String`, without generating code. The returned
+    /// AST is the one the backends generate code from; see the `ast` module for how to inspect
+    /// it.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_path_str` - Path to the file containing the Frame specification, used to report
+    ///   errors and to resolve `#include` directives. This may be `None`.
+    ///
+    /// * `content` - The Frame specification.
+    pub fn parse(
+        &self,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<SystemNode, RunError> {
+        let (system_node, _) = Exe::parse_with_source_map(input_path_str, content)?;
+        Ok(system_node)
+    }
+
+    fn parse_with_source_map(
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<(SystemNode, SourceMap), RunError> {
+        let (system_node, source_map, _) = Exe::parse_with_comments(input_path_str, content)?;
+        Ok((system_node, source_map))
+    }
+
+    fn parse_with_comments(
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<(SystemNode, SourceMap, Vec<Token>), RunError> {
+        let mut source_map = SourceMap::new(input_path_str, &content);
+        let tokens = Exe::scan(&mut source_map, input_path_str, content)?;
+
+        let mut comments = Vec::new();
+        let mut semantic_comments = Vec::new();
+        let (_, system_node) =
+            Exe::parse_tokens(&source_map, &tokens, &mut comments, &mut semantic_comments)?;
+        Ok((system_node, source_map, comments))
+    }
+
+    /// Compare two versions of a Frame specification loaded from files and report the changes
+    /// between them; see the `compat` module for the changes that are reported. Changes to
+    /// states and domain variables are breaking if the old version handles the save or restore
+    /// messages, or if `persisted` is set for machines that are persisted by other means.
+    pub fn compare_files(
+        &self,
+        old_path: &Path,
+        new_path: &Path,
+        persisted: bool,
+    ) -> Result<Vec<Diagnostic>, RunError> {
+        let mut versions = Vec::new();
+        for path in [old_path, new_path] {
+            let content = fs::read_to_string(path).map_err(|err| {
+                let error_msg = format!("Error reading input file {}: {}", path.display(), err);
+                RunError::new(exitcode::NOINPUT, &error_msg)
+            })?;
+            versions.push(Exe::parse_with_source_map(path.to_str(), content)?);
+        }
+        let (new, new_source_map) = versions.pop().unwrap();
+        let (old, old_source_map) = versions.pop().unwrap();
+
+        let persisted = persisted || compat::is_persisted(&old);
+        let mut diagnostics = Vec::new();
+        for (version, mut diagnostic) in compat::compare(&old, &new, persisted) {
+            match version {
+                Version::Old => old_source_map.locate(&mut diagnostic),
+                Version::New => new_source_map.locate(&mut diagnostic),
+            }
+            diagnostics.push(diagnostic);
+        }
+        Ok(diagnostics)
+    }
+
+    /// Generate the test skeleton of a Frame specification loaded from a file, for the code the
+    /// Rust backend generates from it; see the `test_skeleton` module.
+    pub fn test_skeleton_file(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path: &Path,
+    ) -> Result<String, RunError> {
+        let system_node = self.parse_file(input_path)?;
+        let config = self.load_config(config_path, &system_node, Some(TargetLanguage::Rust))?;
+        Ok(test_skeleton::generate(&system_node, &config.codegen.rust))
+    }
+
+    /// Generate the Rust file to put next to a Frame specification loaded from a file, which
+    /// includes the code generated for it from `include_path` in `OUT_DIR`; see the `scaffold`
+    /// module.
+    pub fn scaffold_wrapper_file(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path: &Path,
+        include_path: &str,
+    ) -> Result<String, RunError> {
+        let system_node = self.parse_file(input_path)?;
+        self.scaffold_wrapper_of(config_path, &system_node, include_path)
+    }
+
+    /// Generate the Rust file to put next to a Frame specification passed as a `String`; see
+    /// `scaffold_wrapper_file`.
+    pub fn scaffold_wrapper(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path_str: Option<&str>,
+        content: String,
+        include_path: &str,
+    ) -> Result<String, RunError> {
+        let system_node = self.parse(input_path_str, content)?;
+        self.scaffold_wrapper_of(config_path, &system_node, include_path)
+    }
+
+    /// The names of the methods the Rust file next to a Frame specification passed as a `String`
+    /// implements for the actions of the machine without a body; see the `scaffold` module.
+    pub fn scaffold_action_methods(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<Vec<String>, RunError> {
+        let system_node = self.parse(input_path_str, content)?;
+        let config = self.load_config(config_path, &system_node, Some(TargetLanguage::Rust))?;
+        Ok(scaffold::action_methods(&system_node, &config.codegen.rust))
+    }
+
+    fn scaffold_wrapper_of(
+        &self,
+        config_path: &Option<PathBuf>,
+        system_node: &SystemNode,
+        include_path: &str,
+    ) -> Result<String, RunError> {
+        let config = self.load_config(config_path, system_node, Some(TargetLanguage::Rust))?;
+        Ok(scaffold::wrapper(
+            system_node,
+            &config.codegen.rust,
+            include_path,
+        ))
+    }
+
+    /// Load the intermediate representation of a machine from a Frame specification, or from the
+    /// JSON emitted by the `ir` target if the file has a `.json` extension.
+    pub fn machine_model_file(&self, input_path: &Path) -> Result<MachineModel, RunError> {
+        let content = fs::read_to_string(input_path).map_err(|err| {
+            RunError::new(
+                exitcode::NOINPUT,
+                &format!("Error reading input file: {}", err),
+            )
+        })?;
+        if input_path.extension() != Some("json".as_ref()) {
+            return self.machine_model(input_path.to_str(), content);
+        }
+        MachineModel::from_json(&content).map_err(|err| RunError::new(exitcode::DATAERR, &err))
+    }
+
+    /// Load the intermediate representation of a machine from a Frame specification passed as a
+    /// `String`.
+    pub fn machine_model(
+        &self,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<MachineModel, RunError> {
+        let (system_node, _, comments) = Exe::parse_with_comments(input_path_str, content)?;
+        Ok(MachineModel::with_comments(
+            &system_node,
+            &comments,
+            input_path_str,
+            FRAMEC_VERSION,
+        ))
+    }
+
+    /// The paths of the files included by a Frame specification passed as a `String`, directly or
+    /// through other included files, in the order they are included; see the `includes` module.
+    pub fn include_dependencies(
+        &self,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<Vec<PathBuf>, RunError> {
+        let mut source_map = SourceMap::new(input_path_str, &content);
+        Exe::scan(&mut source_map, input_path_str, content)?;
+        Ok(source_map.included_files())
+    }
+
+    /// The configuration file to load: the given one, or a `config.yaml` file in the current
+    /// working directory if no path is given.
+    fn config_file_path(&self, config_path: &Option<PathBuf>) -> Option<PathBuf> {
+        // check for local config.yaml if no path specified
+        let config_yaml = PathBuf::from("config.yaml");
+        if config_path.is_none() && self.options.load_local_config && config_yaml.exists() {
+            return Some(config_yaml);
+        }
+        config_path.clone()
+    }
+
+    /// Load the configuration for a spec from the configuration file, or from a `config.yaml`
+    /// file in the current working directory if no path is given, and the spec's attributes.
+    fn load_config(
+        &self,
+        config_path: &Option<PathBuf>,
+        system_node: &SystemNode,
+        target_language: Option<TargetLanguage>,
+    ) -> Result<FrameConfig, RunError> {
+        let local_config_path = self.config_file_path(config_path);
+
+        // load configuration
+        let figment = self
+            .config_files
+            .borrow_mut()
+            .entry(local_config_path.clone())
+            .or_insert_with(|| FrameConfig::merge_file(&self.options.config, &local_config_path))
+            .clone();
+        match FrameConfig::load(figment, system_node, target_language) {
+            Ok(cfg) => Ok(cfg),
+            Err(err) => {
+                let msg = format!("Invalid configuration: {}", err);
+                let run_error = RunError::new(frame_exitcode::CONFIG_ERR, &msg);
+                Err(run_error)
+            }
+        }
+    }
+
     /* --------------------------------------------------------------------- */
 
+    /// Run the Frame compiler on a Frame specification read from standard input.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - Optional path to a configuration YAML file.
+    ///
+    /// * `input_path_str` - Optional path the specification is known by, e.g. the file open in
+    ///   an editor that pipes its contents to `framec`. It is used in error messages and to
+    ///   resolve `#include` directives, but the file is not read.
+    ///
+    /// * `target_language` - The target language to compile the specification to. This may be
+    ///   `None` if the `language` attribute is defined in the specification itself.
     pub fn run_stdin(
         &self,
         config_path: &Option<PathBuf>,
+        input_path_str: Option<&str>,
         target_language: Option<TargetLanguage>,
     ) -> Result<String, RunError> {
         let mut buffer = String::new();
@@ -93,7 +563,7 @@ impl Exe {
         match stdin.read_to_string(&mut buffer) {
             Ok(_size) => {
                 Exe::debug_print(&(&buffer).to_string());
-                self.run(config_path, None, buffer, target_language)
+                self.run(config_path, input_path_str, buffer, target_language)
             }
             Err(err) => {
                 let error_msg = format!("Error reading input file: {}", err);
@@ -128,6 +598,42 @@ impl Exe {
         content: String,
         target_language: Option<TargetLanguage>,
     ) -> Result<String, RunError> {
+        let target_languages: Vec<TargetLanguage> = target_language.into_iter().collect();
+        let mut outputs =
+            self.run_targets(config_path, input_path_str, content, &target_languages)?;
+        let output = match outputs.pop() {
+            Some(output) => output,
+            None => {
+                let run_error = RunError::new(USAGE, "No target language specified.");
+                return Err(run_error);
+            }
+        };
+        *self.code_map.borrow_mut() = output.code_map;
+        *self.state_files.borrow_mut() = output.state_files;
+        *self.file_extension.borrow_mut() = Some(output.file_extension);
+        Ok(output.code)
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    /// Run the Frame compiler on a Frame specification passed as a `String`, generating code
+    /// for each of the target languages. The specification is scanned, parsed, linted and
+    /// optimized once, and the backends then generate code from the shared AST, so this is
+    /// faster than a run per target. The arguments are those of `run`.
+    ///
+    /// The outputs are in the order of `target_languages`, each with its code map and state
+    /// files, while the warnings are available from `warnings` as for `run`. The compilation
+    /// fails as a whole if the specification is invalid for any of the targets.
+    ///
+    /// The backends run one after the other: the AST is made of reference-counted nodes, which
+    /// can't be shared between threads.
+    pub fn run_targets(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path_str: Option<&str>,
+        content: String,
+        target_languages: &[TargetLanguage],
+    ) -> Result<Vec<TargetOutput>, RunError> {
         // NOTE!!! There is a bug w/ the CLion debugger when a variable (maybe just String type)
         // isn't initialized under some circumstances. Basically the debugger
         // stops debugging or doesn't step and it looks like it hangs. To avoid
@@ -137,73 +643,77 @@ impl Exe {
         // debugging here, just uncomment the next line and then comment it back
         // when checking in.
 
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        let sha256 = &format!("{:x}", hasher.finalize());
-
-        let output;
-        //        let mut output= String::new(); ^^^^ See above! ^^^^
+        self.warnings.borrow_mut().clear();
+        self.code_map.borrow_mut().take();
+        self.state_files.borrow_mut().clear();
+        self.file_extension.borrow_mut().take();
 
-        let scanner = Scanner::new(content);
-
-        let (has_errors, errors, tokens) = scanner.scan_tokens();
-        if has_errors {
-            let run_error = RunError::new(frame_exitcode::PARSE_ERR, &*errors);
-            return Err(run_error);
-        }
+        let analysis = self.analyze(config_path, input_path_str, content)?;
+        *self.warnings.borrow_mut() = analysis.warnings.clone();
+        let Analysis {
+            sha256,
+            source_map,
+            system_node,
+            arcanum,
+            system_hierarchy,
+            comments,
+            removed_states,
+            ..
+        } = &*analysis;
 
-        for token in &tokens {
-            Exe::debug_print(&format!("{:?}", token));
-        }
+        let generate_enter_args = analysis.generate_enter_args;
+        let generate_exit_args = analysis.generate_exit_args;
+        let generate_state_context = analysis.generate_state_context;
+        let generate_state_stack = analysis.generate_state_stack;
+        let generate_change_state = analysis.generate_change_state;
+        let generate_transition_state = analysis.generate_transition_state;
+        let generate_deep_history = analysis.generate_deep_history;
+        let generate_transition_actions = analysis.generate_transition_actions;
 
-        let mut arcanum = Arcanum::new();
-        let mut comments = Vec::new();
-        // NOTE: This block is to remove references to symbol_table and comments
-        {
-            let mut syntactic_parser = Parser::new(&tokens, &mut comments, true, arcanum);
-            syntactic_parser.parse();
-            if syntactic_parser.had_error() {
-                let mut errors = "Terminating with errors.\n".to_string();
-                errors.push_str(&syntactic_parser.get_errors());
-                let run_error = RunError::new(frame_exitcode::PARSE_ERR, &errors);
+        for target_language in target_languages {
+            let supported = matches!(
+                target_language,
+                TargetLanguage::Rust | TargetLanguage::PlantUml | TargetLanguage::Smcat
+            );
+            if generate_deep_history && !supported {
+                let run_error = RunError::new(
+                    frame_exitcode::PARSE_ERR,
+                    "Deep history transitions are currently only supported for Rust.",
+                );
+                return Err(run_error);
+            }
+            if generate_transition_actions && !supported {
+                let run_error = RunError::new(
+                    frame_exitcode::PARSE_ERR,
+                    "Transition actions are currently only supported for Rust.",
+                );
+                return Err(run_error);
+            }
+            if analysis.generate_regions && !supported {
+                let run_error = RunError::new(
+                    frame_exitcode::PARSE_ERR,
+                    "Orthogonal regions are currently only supported for Rust.",
+                );
                 return Err(run_error);
             }
-            arcanum = syntactic_parser.get_arcanum();
-        }
-
-        let mut comments2 = comments.clone();
-        let mut semantic_parser = Parser::new(&tokens, &mut comments2, false, arcanum);
-        let system_node = semantic_parser.parse();
-        if semantic_parser.had_error() {
-            let mut errors = "Terminating with errors.\n".to_string();
-            errors.push_str(&semantic_parser.get_errors());
-            let run_error = RunError::new(frame_exitcode::PARSE_ERR, &errors);
-            return Err(run_error);
-        }
-
-        let generate_enter_args = semantic_parser.generate_enter_args;
-        let generate_exit_args = semantic_parser.generate_exit_args;
-        let generate_state_context = semantic_parser.generate_state_context;
-        let generate_state_stack = semantic_parser.generate_state_stack;
-        let generate_change_state = semantic_parser.generate_change_state;
-        let generate_transition_state = semantic_parser.generate_transition_state;
-
-        // check for local config.yaml if no path specified
-        let mut local_config_path = config_path;
-        let config_yaml = PathBuf::from("config.yaml");
-        let some_config_yaml = Some(config_yaml.clone());
-        if local_config_path.is_none() && config_yaml.exists() {
-            local_config_path = &some_config_yaml;
         }
 
-        // load configuration
-        let config = match FrameConfig::load(local_config_path, &system_node) {
-            Ok(cfg) => cfg,
-            Err(err) => {
-                let run_error = RunError::new(frame_exitcode::CONFIG_ERR, &err.to_string());
+        // the configuration is checked for each backend, but only their settings differ
+        let mut configs = Vec::new();
+        for &target_language in target_languages {
+            let config = self.load_config(config_path, system_node, Some(target_language))?;
+            if analysis.generate_regions
+                && target_language == TargetLanguage::Rust
+                && config.codegen.rust.features.runtime_support
+            {
+                let run_error = RunError::new(
+                    frame_exitcode::CONFIG_ERR,
+                    "Invalid configuration: orthogonal regions can't be combined yet with `runtime_support`",
+                );
                 return Err(run_error);
             }
-        };
+            configs.push(config);
+        }
 
         // check for language attribute specifying target language
         // match &system_node.attributes_opt {
@@ -230,15 +740,18 @@ impl Exe {
         //     None => {}
         // }
 
-        match target_language {
-            None => {
-                let run_error = RunError::new(USAGE, "No target language specified.");
-                return Err(run_error);
-            }
-            Some(lang) => match lang {
+        let mut outputs = Vec::new();
+        for (&target_language, config) in target_languages.iter().zip(configs) {
+            let output;
+            //        let mut output= String::new(); ^^^^ See above! ^^^^
+            let mut code_map = None;
+            let mut state_files = Vec::new();
+            let mut file_extension = target_language.file_extension().to_string();
+
+            match target_language {
                 TargetLanguage::Cpp => {
                     let mut visitor = CppVisitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         config,
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
@@ -246,56 +759,56 @@ impl Exe {
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::CSharp => {
                     let mut visitor = CsVisitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::CSharpForBob => {
                     let mut visitor = CsVisitorForBob::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::GdScript => {
                     let mut visitor = GdScript32Visitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::GoLang => {
                     let mut visitor = GolangVisitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         config,
     //                    generate_exit_args,
    //                     generate_enter_args || generate_state_context,
@@ -303,98 +816,165 @@ impl Exe {
                         generate_change_state,
     //                    generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
+                TargetLanguage::Ir => {
+                    let model = MachineModel::with_comments(
+                        system_node,
+                        comments,
+                        input_path_str,
+                        FRAMEC_VERSION,
+                    );
+                    output = model.to_json();
+                }
                 TargetLanguage::Java8 => {
                     let mut visitor = Java8Visitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::JavaScript => {
                     let mut visitor = JavaScriptVisitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::PlantUml => {
-                    let (arcanum, system_hierarchy) = semantic_parser.get_all();
+                    let mut system_hierarchy = system_hierarchy.clone();
+                    for state_name in removed_states {
+                        system_hierarchy.remove_node(state_name);
+                    }
                     let mut visitor = PlantUmlVisitor::new(
-                        arcanum,
+                        arcanum.clone(),
                         system_hierarchy,
                         generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
+                        config,
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::Python3 => {
                     let mut visitor = PythonVisitor::new(
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_exit_args,
                         generate_enter_args || generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
                         FRAMEC_VERSION,
-                        comments,
+                        comments.clone(),
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
                     output = visitor.get_code();
                 }
                 TargetLanguage::Rust => {
+                    let override_template_dir = &config.codegen.rust.code.override_template_dir;
+                    let fragment_templates = if override_template_dir.is_empty() {
+                        None
+                    } else {
+                        Some(FragmentTemplates::load(
+                            Path::new(override_template_dir),
+                            &rust_visitor::FRAGMENTS,
+                        )?)
+                    };
                     let mut visitor = RustVisitor::new(
                         FRAMEC_VERSION,
                         config,
                         input_path_str,
                         sha256,
-                        semantic_parser.get_arcanum(),
+                        arcanum.clone(),
                         generate_enter_args,
                         generate_exit_args,
                         generate_state_context,
                         generate_state_stack,
                         generate_change_state,
                         generate_transition_state,
-                        comments,
+                        generate_deep_history,
+                        generate_transition_actions,
+                        comments.clone(),
+                        fragment_templates,
                     );
-                    visitor.run(&system_node);
+                    visitor.run(system_node);
+                    if let Some(error) = visitor.get_template_error() {
+                        return Err(RunError::new(frame_exitcode::CONFIG_ERR, &error));
+                    }
                     output = visitor.get_code();
+                    state_files = visitor
+                        .get_state_files()
+                        .into_iter()
+                        .map(|(path, code)| (PathBuf::from(path), code))
+                        .collect();
+                    if let Some(mut visitor_code_map) = visitor.get_code_map() {
+                        for mapping in &mut visitor_code_map.mappings {
+                            let (file, span) = source_map.locate_span(mapping.source_span);
+                            mapping.source_file = file;
+                            mapping.source_span = span;
+                        }
+                        code_map = Some(visitor_code_map);
+                    }
                 }
                 TargetLanguage::Smcat => {
-                    let mut visitor = SmcatVisitor::new(
+                    let mut system_hierarchy = system_hierarchy.clone();
+                    for state_name in removed_states {
+                        system_hierarchy.remove_node(state_name);
+                    }
+                    let mut visitor = SmcatVisitor::new(FRAMEC_VERSION, config, system_hierarchy);
+                    visitor.run(system_node);
+                    output = visitor.get_code();
+                }
+                TargetLanguage::Template => {
+                    let model = MachineModel::with_comments(
+                        system_node,
+                        comments,
+                        input_path_str,
                         FRAMEC_VERSION,
-                        config,
-                        semantic_parser.get_system_hierarchy(),
                     );
-                    visitor.run(&system_node);
-                    output = visitor.get_code();
+                    output = template::render(&config.codegen.template, &model)?;
+                    file_extension = config.codegen.template.code.file_extension;
+                }
+                TargetLanguage::NuSmv | TargetLanguage::Tla => {
+                    let model = MachineModel::new(system_node, input_path_str, FRAMEC_VERSION);
+                    let (code, diagnostics) = if target_language == TargetLanguage::Tla {
+                        model_checker::tla(&model)
+                    } else {
+                        model_checker::nusmv(&model)
+                    };
+                    output = code;
+                    let mut warnings = self.warnings.borrow_mut();
+                    for diagnostic in source_map.locate_all(locate_states(system_node, diagnostics))
+                    {
+                        if !warnings.contains(&diagnostic) {
+                            warnings.push(diagnostic);
+                        }
+                    }
                 }
                 // TargetLanguage::XState => {
                 //     let mut visitor = XStateVisitor::new(
-                //         semantic_parser.get_arcanum(),
+                //         arcanum.clone(),
                 //         generate_exit_args,
                 //         generate_state_context,
                 //         generate_state_stack,
@@ -406,15 +986,240 @@ impl Exe {
                 //     visitor.run(&system_node);
                 //     output = visitor.get_code();
                 // },
-            },
+            }
+
+            outputs.push(TargetOutput {
+                target_language,
+                code: output,
+                code_map,
+                state_files,
+                file_extension,
+            });
         }
 
-        Ok(output)
+        Ok(outputs)
 
         // let mut graphviz_visitor = GraphVizVisitor::new(semantic_parser.get_arcanum(), comments);
         // graphviz_visitor.run(&system_node);
         // println!("{}", graphviz_visitor.code);
     }
+
+    /* --------------------------------------------------------------------- */
+
+    // Scan, parse, lint and optimize the specification, or reuse the analysis of the previous
+    // run on the same path with the same configuration file if the specification and the files
+    // it includes haven't changed since.
+    fn analyze(
+        &self,
+        config_path: &Option<PathBuf>,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<Rc<Analysis>, RunError> {
+        let mut hasher = Sha256::new();
+        hasher.update(&content);
+        let sha256 = format!("{:x}", hasher.finalize());
+
+        let key = (
+            input_path_str.map(String::from),
+            self.config_file_path(config_path),
+        );
+        if let Some(analysis) = self.analyses.borrow().get(&key) {
+            if analysis.sha256 == sha256 && analysis.source_map.includes_unchanged() {
+                return Ok(Rc::clone(analysis));
+            }
+        }
+
+        let mut source_map = SourceMap::new(input_path_str, &content);
+        let tokens = Exe::scan(&mut source_map, input_path_str, content)?;
+
+        let mut comments = Vec::new();
+        let mut comments2 = Vec::new();
+        let (semantic_parser, mut system_node) =
+            Exe::parse_tokens(&source_map, &tokens, &mut comments, &mut comments2)?;
+
+        if semantic_parser.generate_regions
+            && (semantic_parser.generate_state_context
+                || semantic_parser.generate_enter_args
+                || semantic_parser.generate_exit_args
+                || semantic_parser.generate_state_stack
+                || semantic_parser.generate_change_state
+                || semantic_parser.generate_deep_history
+                || semantic_parser.generate_transition_actions)
+        {
+            let run_error = RunError::new(
+                frame_exitcode::PARSE_ERR,
+                "Orthogonal regions can't be combined yet with state parameters, state variables, enter/exit parameters, change-state, the state stack, deep history or transition actions.",
+            );
+            return Err(run_error);
+        }
+
+        let mut config = self.load_config(config_path, &system_node, None)?;
+
+        for (name, level) in &self.options.lint_levels {
+            if !config.lint.levels.set(name, *level) {
+                let msg = format!("Invalid configuration: unknown lint rule `{}`", name);
+                return Err(RunError::new(frame_exitcode::CONFIG_ERR, &msg));
+            }
+        }
+
+        let mut lint_diagnostics = source_map.locate_all(lint::lint(&system_node, &config.lint));
+        lint::apply_warnings_level(&mut lint_diagnostics, config.lint.levels.warnings);
+        if lint::has_errors(&lint_diagnostics) {
+            let mut errors = "Terminating with lint errors.\n".to_string();
+            for diagnostic in &lint_diagnostics {
+                errors.push_str(&format!("{}\n", diagnostic));
+            }
+            let run_error =
+                RunError::with_diagnostics(frame_exitcode::LINT_ERR, &errors, lint_diagnostics);
+            return Err(run_error);
+        }
+        let mut warnings = lint_diagnostics;
+
+        let optimization = optimizer::optimize(&mut system_node, &config.optimize);
+        warnings.extend(source_map.locate_all(optimization.notes));
+
+        let generate_enter_args = semantic_parser.generate_enter_args;
+        let generate_exit_args = semantic_parser.generate_exit_args;
+        let generate_state_context = semantic_parser.generate_state_context;
+        let generate_state_stack = semantic_parser.generate_state_stack;
+        let generate_change_state = semantic_parser.generate_change_state;
+        let generate_transition_state = semantic_parser.generate_transition_state;
+        let generate_deep_history = semantic_parser.generate_deep_history;
+        let generate_regions = semantic_parser.generate_regions;
+        let generate_transition_actions = semantic_parser.generate_transition_actions;
+        let (arcanum, system_hierarchy) = semantic_parser.get_all();
+
+        let analysis = Rc::new(Analysis {
+            sha256,
+            generate_enter_args,
+            generate_exit_args,
+            generate_state_context,
+            generate_state_stack,
+            generate_change_state,
+            generate_transition_state,
+            generate_deep_history,
+            generate_regions,
+            generate_transition_actions,
+            source_map,
+            system_node,
+            arcanum,
+            system_hierarchy,
+            comments,
+            removed_states: optimization.removed_states,
+            warnings,
+        });
+        self.analyses.borrow_mut().insert(key, Rc::clone(&analysis));
+        Ok(analysis)
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // Scan the specification and splice in the tokens of any #include'd files.
+    fn scan(
+        source_map: &mut SourceMap,
+        input_path_str: Option<&str>,
+        content: String,
+    ) -> Result<Vec<Token>, RunError> {
+        let scanner = Scanner::new(content);
+
+        let (has_errors, errors, diagnostics, tokens) = scanner.scan_tokens();
+        if has_errors {
+            let diagnostics = source_map.locate_all(diagnostics);
+            let run_error =
+                RunError::with_diagnostics(frame_exitcode::PARSE_ERR, &*errors, diagnostics);
+            return Err(run_error);
+        }
+
+        // splice in the tokens of any #include'd files
+        let tokens =
+            match IncludeExpander::new(source_map).expand(tokens, input_path_str.map(Path::new)) {
+                Ok(tokens) => tokens,
+                Err(diagnostics) => {
+                    let diagnostics = source_map.locate_all(diagnostics);
+                    let mut errors = String::new();
+                    for diagnostic in &diagnostics {
+                        errors.push_str(&format!("{}\n", diagnostic));
+                    }
+                    let run_error =
+                        RunError::with_diagnostics(frame_exitcode::PARSE_ERR, &errors, diagnostics);
+                    return Err(run_error);
+                }
+            };
+
+        for token in &tokens {
+            Exe::debug_print(&format!("{:?}", token));
+        }
+
+        Ok(tokens)
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // Run the syntactic and semantic passes of the parser. The comments found by the syntactic
+    // pass are left in `comments` for the backends.
+    fn parse_tokens<'a>(
+        source_map: &SourceMap,
+        tokens: &'a [Token],
+        comments: &mut Vec<Token>,
+        semantic_comments: &'a mut Vec<Token>,
+    ) -> Result<(Parser<'a>, SystemNode), RunError> {
+        let mut arcanum = Arcanum::new();
+        // NOTE: This block is to remove references to symbol_table and comments
+        {
+            let mut syntactic_parser = Parser::new(tokens, comments, true, arcanum);
+            syntactic_parser.parse();
+            if syntactic_parser.had_error() {
+                let mut errors = "Terminating with errors.\n".to_string();
+                errors.push_str(&syntactic_parser.get_errors());
+                let diagnostics = source_map.locate_all(syntactic_parser.get_diagnostics());
+                let run_error =
+                    RunError::with_diagnostics(frame_exitcode::PARSE_ERR, &errors, diagnostics);
+                return Err(run_error);
+            }
+            arcanum = syntactic_parser.get_arcanum();
+        }
+
+        *semantic_comments = comments.clone();
+        let mut semantic_parser = Parser::new(tokens, semantic_comments, false, arcanum);
+        let system_node = semantic_parser.parse();
+        if semantic_parser.had_error() {
+            let mut errors = "Terminating with errors.\n".to_string();
+            errors.push_str(&semantic_parser.get_errors());
+            let diagnostics = source_map.locate_all(semantic_parser.get_diagnostics());
+            let run_error =
+                RunError::with_diagnostics(frame_exitcode::PARSE_ERR, &errors, diagnostics);
+            return Err(run_error);
+        }
+
+        Ok((semantic_parser, system_node))
+    }
+}
+
+/// Locate the diagnostics whose lexeme is the name of a state at the name of the state.
+fn locate_states(system_node: &SystemNode, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let states = match &system_node.machine_block_node_opt {
+        Some(machine_block_node) => &machine_block_node.states,
+        None => return diagnostics,
+    };
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let state_node_rcref = diagnostic.lexeme.as_ref().and_then(|lexeme| {
+                states
+                    .iter()
+                    .find(|state_node_rcref| &state_node_rcref.borrow().name == lexeme)
+            });
+            match state_node_rcref {
+                Some(state_node_rcref) => {
+                    let state_node = state_node_rcref.borrow();
+                    diagnostic
+                        .with_line(state_node.line)
+                        .with_span(state_node.name_span.start, state_node.name_span.length)
+                }
+                None => diagnostic,
+            }
+        })
+        .collect()
 }
 
 impl Default for Exe {
@@ -422,3 +1227,497 @@ impl Default for Exe {
         Exe::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that compiling a spec repeatedly generates identical code. The symbol tables are
+    /// hash maps whose iteration order differs between instances, so generated code that
+    /// depends on it changes from run to run.
+    #[test]
+    fn deterministic_output() {
+        let specs_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../framec_tests/src");
+        let mut spec_paths: Vec<PathBuf> = fs::read_dir(&specs_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "frm"))
+            .collect();
+        spec_paths.sort();
+        assert!(!spec_paths.is_empty());
+
+        for spec_path in &spec_paths {
+            let first = Exe::new().run_file(&None, spec_path, Some(TargetLanguage::Rust));
+            let first = first.unwrap_or_else(|err| panic!("{}", err.error));
+            for _ in 0..4 {
+                let again = Exe::new()
+                    .run_file(&None, spec_path, Some(TargetLanguage::Rust))
+                    .unwrap_or_else(|err| panic!("{}", err.error));
+                assert!(
+                    first == again,
+                    "output for {} differs between runs",
+                    spec_path.display()
+                );
+            }
+        }
+    }
+
+    /// Test that the lightweight feature can't be combined with features that need shared state.
+    #[test]
+    fn lightweight_conflicts() {
+        let spec = "#[codegen.rust.features.lightweight:bool=\"true\"]
+#[codegen.rust.features.runtime_support:bool=\"true\"]
+#Lamp
+    -machine-
+    $Off
+##
+";
+        let err = Exe::new()
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+
+        let spec = spec.replacen("runtime_support", "thread_safe", 1);
+        assert!(Exe::new()
+            .run(&None, None, spec.clone(), Some(TargetLanguage::Rust))
+            .is_err());
+        assert!(Exe::new()
+            .run(&None, None, spec, Some(TargetLanguage::Python3))
+            .is_ok());
+    }
+
+    /// Test that the event monitor can't be generated without the parts of the runtime
+    /// interface it depends on, and that lightweight code can include the other parts.
+    #[test]
+    fn runtime_feature_dependencies() {
+        let spec = "#[codegen.rust.features.runtime_support:bool=\"true\"]
+#[codegen.rust.features.runtime_environments:bool=\"false\"]
+#Lamp
+    -machine-
+    $Off
+##
+";
+        let err = Exe::new()
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+
+        let spec = spec.replacen(
+            "runtime_environments:bool=\"false\"",
+            "runtime_event_monitor:bool=\"false\"",
+            1,
+        );
+        let code = Exe::new()
+            .run(&None, None, spec.clone(), Some(TargetLanguage::Rust))
+            .unwrap();
+        assert!(code.contains("runtime::Environment for Lamp"));
+        assert!(!code.contains("EventMonitor"));
+
+        let spec = format!(
+            "#[codegen.rust.features.lightweight:bool=\"true\"]\n{}",
+            spec
+        );
+        assert!(Exe::new()
+            .run(&None, None, spec, Some(TargetLanguage::Rust))
+            .is_ok());
+    }
+
+    /// Test that interface parameters with default values are optional in the generated code, and
+    /// that defaults are rejected where arguments can't be left out.
+    #[test]
+    fn parameter_defaults() {
+        let spec = "#Counter
+    -interface-
+    add [step:i32 = 1]
+    next
+    -machine-
+    $A
+        |add| [step:i32] ^
+        |next| -> $B ^
+    $B
+        |>| [val:i32 = -1] ^
+##
+";
+        let run =
+            |spec: &str, language| Exe::new().run(&None, None, spec.to_string(), Some(language));
+        let code = run(spec, TargetLanguage::Rust).unwrap();
+        assert!(code.contains("pub fn add(&mut self, step: Option<i32>)"));
+        assert!(code.contains("let step = step.unwrap_or(1);"));
+        let code = run(spec, TargetLanguage::Python3).unwrap();
+        assert!(code.contains("def add(self,step=1):"));
+        assert!(code.contains("stateContext.addEnterArg(\"val\",-1)"));
+
+        assert!(run(
+            &spec.replace("[step:i32]", "[step:i32 = 2]"),
+            TargetLanguage::Rust
+        )
+        .is_err());
+        let unordered = spec.replace("[val:i32 = -1]", "[val:i32 = -1 other:i32]");
+        assert!(run(&unordered, TargetLanguage::Rust).is_err());
+    }
+
+    /// Test that the substates of a parallel state must all be in a region without substates of
+    /// their own, and that regions are rejected by the backends that don't support them.
+    #[test]
+    fn regions() {
+        let spec = "#Robot
+    -interface-
+    start
+    -machine-
+    $Idle
+        |start| -> $Running ^
+    $Running
+    $MotorOff => $Running.motor
+    $LightOff => $Running.light
+##
+";
+        let run =
+            |spec: &str, language| Exe::new().run(&None, None, spec.to_string(), Some(language));
+        let code = run(spec, TargetLanguage::Rust).unwrap();
+        assert!(code.contains("running_motor_region: RobotState,"));
+        assert!(code.contains("RobotState::Running => self.running_regions_handler("));
+
+        let err = run(spec, TargetLanguage::Python3).unwrap_err();
+        assert_eq!(err.code, frame_exitcode::PARSE_ERR);
+        let with_runtime = format!(
+            "#[codegen.rust.features.runtime_support:bool=\"true\"]\n{}",
+            spec
+        );
+        let err = run(&with_runtime, TargetLanguage::Rust).unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+
+        let outside_region = spec.replace("$Running.light", "$Running");
+        let err = run(&outside_region, TargetLanguage::Rust).unwrap_err();
+        assert!(err.error.contains("its substate $LightOff must be in one"));
+        let nested = format!(
+            "{}    $Dim => $LightOff\n##\n",
+            spec.trim_end_matches("##\n")
+        );
+        let err = run(&nested, TargetLanguage::Rust).unwrap_err();
+        assert!(err
+            .error
+            .contains("States of a region can't have substates"));
+    }
+
+    /// Test that the actions of a transition are called between the halves of the transition
+    /// method, and that they are rejected by the backends that don't support them.
+    #[test]
+    fn transition_actions() {
+        let spec = "#Door
+    -interface-
+    open
+    -machine-
+    $Closed
+        |open| -> { unlock() } $Open ^
+    $Open
+    -actions-
+    unlock
+##
+";
+        let run =
+            |spec: &str, language| Exe::new().run(&None, None, spec.to_string(), Some(language));
+        let code = run(spec, TargetLanguage::Rust).unwrap();
+        assert!(code.contains(
+            "self.transition_exit();\n                self.unlock();\n                self.transition_enter(DoorState::Open);"
+        ));
+        assert!(code.contains("fn transition_exit(&mut self) {"));
+
+        let err = run(spec, TargetLanguage::Python3).unwrap_err();
+        assert_eq!(err.code, frame_exitcode::PARSE_ERR);
+        let not_a_call = spec.replace("unlock()", "42");
+        let err = run(&not_a_call, TargetLanguage::Rust).unwrap_err();
+        assert!(err.error.contains("Expected an action call."));
+    }
+
+    /// Test that templates override fragments of the Rust code, with the fragment generated by
+    /// default in `default`, and that templates not named after a fragment are rejected.
+    #[test]
+    fn override_templates() {
+        let spec = "#Lamp
+    -interface-
+    turnOn [level:u8]
+    -machine-
+    $Off
+        |turnOn| [level:u8] -> $On ^
+    $On
+##
+";
+        let dir = std::env::temp_dir().join(format!("framec_overrides_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("file_header.hbs"),
+            "// Generated for {{name}}\n{{default}}",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("interface_method.hbs"),
+            "#[doc = \"Send {{name}}({{#each params}}{{name}}: {{type}}{{/each}})\"]\n    {{default}}",
+        )
+        .unwrap();
+        let mut config = FrameConfig::default();
+        config.codegen.rust.code.override_template_dir = dir.to_string_lossy().into_owned();
+        let exe = Exe::with_options(CompilerOptions {
+            config: config.clone(),
+            load_local_config: false,
+            ..CompilerOptions::default()
+        });
+
+        let code = exe
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap();
+        assert!(code.starts_with("// Generated for Lamp\n// emitted from framec_v"));
+        assert!(code.contains(
+            "    #[doc = \"Send turnOn(level: u8)\"]\n    pub fn turn_on(&mut self, level: u8) {"
+        ));
+        assert!(code.contains("    fn off_handler(&mut self, frame_event: Rc<FrameEvent>) {"));
+
+        fs::write(dir.join("state_handler.hbs"), "{{handler}}").unwrap();
+        let err = exe
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+        assert!(err.error.contains("state_handler.hbs"));
+
+        fs::write(dir.join("states.hbs"), "").unwrap();
+        let err = exe
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(err.error.contains("Template states.hbs"));
+    }
+
+    /// Test that the configuration in the compiler options takes the place of the defaults, and
+    /// that attributes in the spec still override it.
+    #[test]
+    fn compiler_options() {
+        let spec = "#Lamp
+    -machine-
+    $Off
+##
+";
+        let mut config = FrameConfig::default();
+        config.codegen.rust.features.runtime_support = true;
+        config.codegen.rust.code.state_var_name = "current".to_string();
+        let exe = Exe::with_options(CompilerOptions {
+            config,
+            load_local_config: false,
+            ..CompilerOptions::default()
+        });
+        assert!(!exe.options().load_local_config);
+
+        let code = exe
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+            .unwrap();
+        assert!(code.contains("impl runtime::Machine"));
+        assert!(code.contains("current: LampState"));
+
+        let spec = format!(
+            "#[codegen.rust.features.runtime_support:bool=\"false\"]\n{}",
+            spec
+        );
+        let code = exe
+            .run(&None, None, spec, Some(TargetLanguage::Rust))
+            .unwrap();
+        assert!(!code.contains("impl runtime::Machine"));
+        assert!(code.contains("current: LampState"));
+    }
+
+    /// Test that an `Exe` can be reused for many runs: the configuration file is read once, and
+    /// the warnings of one run don't leak into the next.
+    #[test]
+    fn reuse_exe() {
+        let config_path =
+            std::env::temp_dir().join(format!("framec_reuse_exe_{}.yaml", std::process::id()));
+        fs::write(&config_path, "lint:\n  levels:\n    state_naming: warn\n").unwrap();
+        let config_path = Some(config_path);
+
+        let exe = Exe::new();
+        let bad_name = "#Lamp\n    -machine-\n    $off\n##\n";
+        let good_name = "#Lamp\n    -machine-\n    $Off\n##\n";
+        assert!(exe
+            .run(
+                &config_path,
+                None,
+                bad_name.to_string(),
+                Some(TargetLanguage::Rust)
+            )
+            .is_ok());
+        assert_eq!(exe.warnings().len(), 1);
+        assert!(exe
+            .run(
+                &config_path,
+                None,
+                good_name.to_string(),
+                Some(TargetLanguage::Python3)
+            )
+            .is_ok());
+        assert!(exe.warnings().is_empty());
+
+        // the file was read by the first run, so later changes are only seen by a new `Exe`
+        let path = config_path.as_ref().unwrap();
+        fs::write(path, "lint:\n  levels:\n    state_naming: deny\n").unwrap();
+        assert!(exe
+            .run(
+                &config_path,
+                None,
+                bad_name.to_string(),
+                Some(TargetLanguage::Rust)
+            )
+            .is_ok());
+        let err = Exe::new()
+            .run(
+                &config_path,
+                None,
+                bad_name.to_string(),
+                Some(TargetLanguage::Rust),
+            )
+            .unwrap_err();
+        assert_eq!(err.code, frame_exitcode::LINT_ERR);
+        fs::remove_file(path).unwrap();
+    }
+
+    /// Test that compiling to several targets at once generates the code of separate runs, and
+    /// that the compilation fails if the spec isn't supported by one of the targets.
+    #[test]
+    fn run_targets() {
+        let spec = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+        let targets = [
+            TargetLanguage::Rust,
+            TargetLanguage::Python3,
+            TargetLanguage::Smcat,
+        ];
+        let run =
+            |spec: &str, language| Exe::new().run(&None, None, spec.to_string(), Some(language));
+        let exe = Exe::new();
+        let outputs = exe
+            .run_targets(&None, None, spec.to_string(), &targets)
+            .unwrap();
+        assert_eq!(outputs.len(), targets.len());
+        for (output, target) in outputs.iter().zip(targets) {
+            assert_eq!(output.target_language, target);
+            assert_eq!(output.file_extension, target.file_extension());
+            assert_eq!(output.code, run(spec, target).unwrap());
+        }
+
+        let actions = spec.replace("|toggle| -> $On", "|toggle| -> { log() } $On");
+        let actions = actions.replace("##", "    -actions-\n    log\n##");
+        let err = exe
+            .run_targets(
+                &None,
+                None,
+                actions,
+                &[TargetLanguage::Rust, TargetLanguage::Python3],
+            )
+            .unwrap_err();
+        assert!(err.error.contains("Transition actions"));
+    }
+    /// Test that the analysis of a spec is reused while neither the spec nor the files it
+    /// includes change.
+    #[test]
+    fn reuse_analysis() {
+        let dir =
+            std::env::temp_dir().join(format!("framec_reuse_analysis_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let included = dir.join("actions.frm");
+        fs::write(&included, "    -actions-\n    log\n").unwrap();
+        let spec = "#Lamp\n    -machine-\n    $Off\n#include \"actions.frm\"\n##\n";
+        let spec_path = dir.join("lamp.frm");
+        let spec_path = spec_path.to_str();
+
+        let exe = Exe::new();
+        let analyze = |spec: &str| exe.analyze(&None, spec_path, spec.to_string()).unwrap();
+        let first = analyze(spec);
+        assert!(Rc::ptr_eq(&first, &analyze(spec)));
+        let code = exe
+            .run(
+                &None,
+                spec_path,
+                spec.to_string(),
+                Some(TargetLanguage::Rust),
+            )
+            .unwrap();
+        assert!(code.contains("fn log(&self)"));
+        assert!(Rc::ptr_eq(&first, &analyze(spec)));
+
+        let changed = spec.replace("$Off", "$On");
+        assert!(!Rc::ptr_eq(&first, &analyze(&changed)));
+        let second = analyze(spec);
+        assert!(!Rc::ptr_eq(&first, &second));
+
+        fs::write(&included, "    -actions-\n    log\n    beep\n").unwrap();
+        assert!(!Rc::ptr_eq(&second, &analyze(spec)));
+        let code = exe
+            .run(
+                &None,
+                spec_path,
+                spec.to_string(),
+                Some(TargetLanguage::Rust),
+            )
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(code.contains("fn beep(&self)"));
+    }
+
+    /// Test that the files included by a spec are listed, including those included by them.
+    #[test]
+    fn include_dependencies() {
+        let dir = std::env::temp_dir().join(format!(
+            "framec_include_dependencies_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(dir.join("shared")).unwrap();
+        fs::write(
+            dir.join("shared/actions.frmi"),
+            "    -actions-\n    log\n#include \"domain.frmi\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("shared/domain.frmi"),
+            "    -domain-\n    var n:i32 = 0\n",
+        )
+        .unwrap();
+        let spec = "#Lamp\n    -machine-\n    $Off\n#include \"shared/actions.frmi\"\n##\n";
+        let spec_path = dir.join("lamp.frm");
+
+        let dependencies = Exe::new()
+            .include_dependencies(spec_path.to_str(), spec.to_string())
+            .unwrap();
+        let missing =
+            Exe::new().include_dependencies(spec_path.to_str(), spec.replace("shared/", ""));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            dependencies,
+            [
+                dir.join("shared/actions.frmi"),
+                dir.join("shared/domain.frmi")
+            ]
+        );
+        assert!(missing.is_err());
+    }
+
+    /// Test that malformed specs that used to panic the scanner are rejected with a parse error.
+    #[test]
+    fn scan_errors() {
+        let specs = [
+            "#Lamp {-x\n##\n",
+            "#Lamp\n    -machine-\n    $Off\n        |e| x ?# /1|2",
+            "#Lamp\n    -machine-\n    $Off\n        |e| \"unterminated",
+            "#Lämp\n    -machine-\n    $Öff\n##\n",
+        ];
+        for spec in specs {
+            let err = Exe::new()
+                .run(&None, None, spec.to_string(), Some(TargetLanguage::Rust))
+                .unwrap_err();
+            assert_eq!(err.code, frame_exitcode::PARSE_ERR, "{}", spec);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::frame_c::ast::{AttributeNode, SystemNode};
 use crate::frame_c::utils::{frame_exitcode, RunError};
+use crate::frame_c::visitors::TargetLanguage;
+use convert_case::{Case, Casing};
 use figment::providers::{Format, Yaml};
 use figment::value::{Dict, Map, Value};
 use figment::{Error, Figment, Metadata, Profile, Provider};
@@ -10,8 +12,11 @@ use std::path::PathBuf;
 
 /// The root struct of a frame configuration.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FrameConfig {
     pub codegen: CodeGenConfig,
+    pub lint: LintConfig,
+    pub optimize: OptimizeConfig,
 }
 
 impl FrameConfig {
@@ -33,31 +38,108 @@ impl FrameConfig {
         }
     }
 
-    /// Load a configuration by merging the default configuration with an optional local
-    /// configuration file, then overriding any configuration attributes defined in the Frame spec.
+    /// Merge a base configuration, usually the default one, with an optional local configuration
+    /// file. The file is read once, so the result can be reused to load the configuration of many
+    /// specs with `load`.
+    pub fn merge_file(base: &FrameConfig, local_config: &Option<PathBuf>) -> Figment {
+        let mut figment = base.figment();
+        if let Some(path) = local_config {
+            figment = figment.merge(Yaml::file(path));
+        }
+        figment
+    }
+
+    /// Load the configuration of a Frame spec by overriding the configuration returned by
+    /// `merge_file` with the configuration attributes defined in the spec.
+    ///
+    /// The configuration is validated while loading: unknown keys and values of the wrong type are
+    /// errors, and if a target language is given, the settings of its backend are checked, e.g.
+    /// for features that can't be combined. Errors name the offending key and the file or
+    /// attribute it was set in.
     pub fn load(
-        local_config: &Option<PathBuf>,
+        mut figment: Figment,
         system_node: &SystemNode,
+        target_language: Option<TargetLanguage>,
     ) -> Result<FrameConfig, Error> {
-        let mut figment = FrameConfig::default().figment();
-        if let Some(path) = local_config {
-            figment = figment.merge(Yaml::file(path));
+        // merge the attributes individually to keep track of where each setting came from
+        if let Some(attributes) = &system_node.attributes_opt {
+            for attr in attributes.values() {
+                figment = figment.merge(attr);
+            }
+        }
+        let config: FrameConfig = figment.extract()?;
+        if let Some(target_language) = target_language {
+            if let Err(invalid) = config.codegen.check(target_language) {
+                let mut err = Error::from(invalid.message);
+                err.metadata = figment.find_metadata(&invalid.key).cloned();
+                err.profile = Some(Profile::Default);
+                err.path = invalid.key.split('.').map(String::from).collect();
+                return Err(err);
+            }
         }
-        figment.merge(Figment::from(system_node)).extract()
+        Ok(config)
     }
 }
 
 /// Configuration options related to code generation.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CodeGenConfig {
     pub common: CommonConfig,
     pub rust: RustConfig,
     pub golang: GolangConfig,
+    pub plantuml: PlantUmlConfig,
     pub smcat: SmcatConfig,
+    pub template: TemplateConfig,
+}
+
+impl CodeGenConfig {
+    /// Check the settings of the backend for the given target language. Settings of other
+    /// backends are not checked, so one configuration can hold settings for all backends.
+    pub fn check(&self, target_language: TargetLanguage) -> Result<(), InvalidSetting> {
+        match target_language {
+            TargetLanguage::Rust => {
+                self.rust.features.check()?;
+                self.rust.code.check()?;
+                if !self.rust.code.override_template_dir.is_empty()
+                    && self.rust.features.generate_source_map
+                {
+                    return Err(InvalidSetting::new(
+                        "codegen.rust.code.override_template_dir",
+                        "override templates cannot be combined with generate_source_map"
+                            .to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            TargetLanguage::PlantUml => self.plantuml.code.check(),
+            TargetLanguage::Template => self.template.code.check(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Check that a setting is a comma-separated list of trait paths, such as `Clone, serde::Serialize`.
+fn check_trait_list(key: &str, traits: &str) -> Result<(), InvalidSetting> {
+    if traits.trim().is_empty() {
+        return Ok(());
+    }
+    for name in traits.split(',').map(str::trim) {
+        let valid = !name.is_empty()
+            && name.split("::").all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+            });
+        if !valid {
+            let message = format!("\"{}\" is not a comma-separated list of traits", traits);
+            return Err(InvalidSetting::new(key, message));
+        }
+    }
+    Ok(())
 }
 
 /// Code generation options shared among all backends.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CommonConfig {
     pub features: CommonFeatures,
     pub code: CommonCode,
@@ -65,6 +147,7 @@ pub struct CommonConfig {
 
 /// Code generation options specific to the Rust backend.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GolangConfig {
     //    pub features: GolangFeatures,
     pub code: GolangCode,
@@ -77,6 +160,7 @@ pub struct GolangConfig {
 /// other than the defaults. Unless you have some strong reason to do otherwise, it's probably best
 /// to leave them be. :-)
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GolangCode {
     pub action_prefix: String,
     pub action_suffix: String,
@@ -227,22 +311,84 @@ impl Default for GolangCode {
 }
 /// Code generation features shared among all backends.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CommonFeatures {}
 
 /// Naming options for generated code shared among all backends.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CommonCode {}
 
 /// Code generation options specific to the Golang backend.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RustConfig {
     pub features: RustFeatures,
     pub code: RustCode,
     pub runtime: RustRuntime,
 }
 
+impl RustConfig {
+    /// Format a "type-level" name, e.g. a type, trait, or enum variant, in the case configured
+    /// by `type_name_case` if Rust naming conventions are followed.
+    pub fn format_type_name(&self, name: &str) -> String {
+        if self.features.follow_rust_naming {
+            self.code.type_name_case.convert(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Format a "value-level" name, e.g. a function, method, variable, in the case configured
+    /// by `value_name_case` if Rust naming conventions are followed.
+    pub fn format_value_name(&self, name: &str) -> String {
+        if self.features.follow_rust_naming {
+            self.code.value_name_case.convert(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Are generated names in the cases of the Rust naming conventions?
+    pub fn follows_rust_naming(&self) -> bool {
+        self.features.follow_rust_naming
+            && self.code.type_name_case == NameCase::UpperCamel
+            && self.code.value_name_case == NameCase::Snake
+    }
+}
+
+/// The case generated names are converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameCase {
+    /// Names are used as written in the spec.
+    Preserve,
+    /// `UpperCamelCase`
+    UpperCamel,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+}
+
+impl NameCase {
+    /// Convert a name to this case.
+    pub fn convert(self, name: &str) -> String {
+        match self {
+            NameCase::Preserve => name.to_string(),
+            NameCase::UpperCamel => name.to_case(Case::UpperCamel),
+            NameCase::Camel => name.to_case(Case::Camel),
+            NameCase::Snake => name.to_case(Case::Snake),
+            NameCase::ScreamingSnake => name.to_case(Case::ScreamingSnake),
+        }
+    }
+}
+
 /// Code generation features specific to the Rust backend.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RustFeatures {
     /// When enabled, generated code will attempt to conform to standard Rust naming conventions.
     /// However, options in `RustCode` are in general not overridden by this feature.
@@ -257,6 +403,43 @@ pub struct RustFeatures {
     /// Default is `true`.
     pub generate_action_impl: bool,
 
+    /// When enabled along with `generate_action_impl`, the generated implementation of the
+    /// `Action` trait records each call of an action, e.g. `log("on")`, and returns the default
+    /// value of actions with a return type and no body. The calls are returned by the
+    /// `take_action_calls` method of the state machine, for assertions in tests. The types of
+    /// action parameters must implement `Debug`.
+    ///
+    /// Default is `false`.
+    pub generate_mock_actions: bool,
+
+    /// When enabled, implements `Display` and `FromStr` for the state enum, which format and
+    /// parse the names of states in the spec, and `Display` for the state machine, which
+    /// writes the current state followed by the values of the domain variables, e.g.
+    /// `On { brightness: 3 }`. The types of domain variables must implement `Debug`.
+    ///
+    /// Default is `false`.
+    pub generate_display: bool,
+
+    /// When enabled, generates a struct holding the initial values of the domain variables,
+    /// e.g. `LampDomain`, whose `Default` implementation uses the initializers in the spec, and
+    /// a `new_with` constructor taking such a struct and whether to enter the initial state.
+    /// This makes it possible to start a state machine from given values in tests, or to
+    /// restore one from persisted values without running the enter event handler again.
+    ///
+    /// Default is `false`.
+    pub generate_new_with: bool,
+
+    /// When enabled, generates a trait of the interface methods of the state machine and of a
+    /// method returning its current state, e.g. `LampInterface`, implemented by the state machine
+    /// and by a fake, e.g. `FakeLamp`. The interface methods of the fake return scripted values
+    /// and enter scripted states instead of running the machine, so that code using the machine
+    /// through the trait can be tested with the fake. The fake records the calls of its methods,
+    /// so the types of their parameters must implement `Debug`, and their return types must
+    /// implement `Default`.
+    ///
+    /// Default is `false`.
+    pub generate_fake: bool,
+
     /// When enabled, generates "hook" methods that will be invoked on every transition or
     /// change-state. These hook methods are added to the `Action` trait and must be implemented.
     ///
@@ -273,14 +456,138 @@ pub struct RustFeatures {
     ///  * `frame_runtime::unsync` if the `thread_safe` feature is disabled (default)
     ///  * `frame_runtime::sync` if the `thread_safe` feature is enabled
     ///
+    /// The parts of the runtime support can be excluded individually with the `runtime_*`
+    /// features below to reduce the size and compile time of the generated code.
+    ///
     /// By default, the `runtime_support` feature is `false`.
     pub runtime_support: bool,
 
+    /// When enabled along with `runtime_support`, generates the implementation of the runtime
+    /// interface (`Machine`, `State` and `Event`) and the event monitor integration that invokes
+    /// callbacks and records the event and transition history. Requires `runtime_machine_info`
+    /// and `runtime_environments`.
+    ///
+    /// Default is `true`.
+    pub runtime_event_monitor: bool,
+
+    /// When enabled along with `runtime_support`, generates the static tables describing the
+    /// states, events, actions, variables and transitions of the state machine.
+    ///
+    /// Default is `true`.
+    pub runtime_machine_info: bool,
+
+    /// When enabled along with `runtime_support`, implements the `Environment` trait for the
+    /// state machine and the structs holding arguments and state variables, which enables
+    /// looking up their values by name.
+    ///
+    /// Default is `true`.
+    pub runtime_environments: bool,
+
+    /// When enabled along with `runtime_support`, implements the `Dispatch` trait for the state
+    /// machine, whose `dispatch` method calls an interface method given its name and its
+    /// arguments as `Box<dyn Any>`. This is used by tools and by the `ffi` module of
+    /// `frame_runtime` to drive a machine without knowing its type. The types of the parameters
+    /// and return values of the interface must be `'static`.
+    ///
+    /// Default is `false`.
+    pub runtime_dispatch: bool,
+
     /// When enabled, generates a state machine that implements the `Send` trait, and so can be
     /// safely passed acrosss thread boundries.
     ///
     /// Default is `false`.
     pub thread_safe: bool,
+
+    /// When enabled, generates a state machine without reference counting or interior
+    /// mutability. Events are passed to handlers by mutable reference and the current state
+    /// context is owned by the state machine, so handlers access state parameters and state
+    /// variables through `self`. Contexts saved on the state stack or for deep history are
+    /// clones, so the types of state variables must implement `Clone` when those features are
+    /// used.
+    ///
+    /// This feature cannot be combined with `thread_safe` or with `runtime_event_monitor` when
+    /// `runtime_support` is enabled.
+    ///
+    /// Default is `false`.
+    pub lightweight: bool,
+
+    /// When enabled, records the line and span in the Frame spec of each generated state
+    /// handler, event handler and interface method. The map is available from
+    /// `Exe::code_map` and is written next to the generated code by `frame_build` and by the
+    /// `--source-map` option of `framec`.
+    ///
+    /// Default is `false`.
+    pub generate_source_map: bool,
+
+    /// When enabled, the handler of each state is generated into the file of its own module,
+    /// e.g. `lamp/off.rs` for the state `$Off` of `lamp.frm`, which the generated code includes
+    /// relative to its own path. This keeps the generated files of large machines small. The
+    /// state files are available from `Exe::state_files` and are written next to the generated
+    /// code by `frame_build` and by the `--out-dir` option of `framec`.
+    ///
+    /// This feature cannot be combined with `generate_source_map`.
+    ///
+    /// Default is `false`.
+    pub split_states: bool,
+}
+
+/// A setting that is invalid for the backend it configures, found by the `check` methods of the
+/// backend configurations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidSetting {
+    /// The path of the setting, e.g. `codegen.rust.features.lightweight`.
+    pub key: String,
+    pub message: String,
+}
+
+impl InvalidSetting {
+    fn new(key: &str, message: String) -> InvalidSetting {
+        InvalidSetting {
+            key: key.to_string(),
+            message,
+        }
+    }
+}
+
+impl RustFeatures {
+    /// Check that the enabled features can be combined.
+    pub fn check(&self) -> Result<(), InvalidSetting> {
+        if self.runtime_support && self.runtime_event_monitor {
+            for (feature, enabled) in [
+                ("runtime_machine_info", self.runtime_machine_info),
+                ("runtime_environments", self.runtime_environments),
+            ] {
+                if !enabled {
+                    let message =
+                        format!("runtime_event_monitor requires {} to be enabled", feature);
+                    let key = format!("codegen.rust.features.{}", feature);
+                    return Err(InvalidSetting::new(&key, message));
+                }
+            }
+        }
+        if self.generate_mock_actions && !self.generate_action_impl {
+            return Err(InvalidSetting::new(
+                "codegen.rust.features.generate_mock_actions",
+                "generate_mock_actions requires generate_action_impl to be enabled".to_string(),
+            ));
+        }
+        if self.split_states && self.generate_source_map {
+            return Err(InvalidSetting::new(
+                "codegen.rust.features.split_states",
+                "split_states cannot be combined with generate_source_map".to_string(),
+            ));
+        }
+        if self.lightweight
+            && (self.thread_safe || self.runtime_support && self.runtime_event_monitor)
+        {
+            return Err(InvalidSetting::new(
+                "codegen.rust.features.lightweight",
+                "lightweight cannot be combined with thread_safe or runtime_event_monitor"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Naming options for generated code specific to the Rust backend. These options can be used to
@@ -290,11 +597,25 @@ pub struct RustFeatures {
 /// other than the defaults. Unless you have some strong reason to do otherwise, it's probably best
 /// to leave them be. :-)
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RustCode {
+    /// The case of type-level names derived from the spec, such as the system type, state enum
+    /// variants and message enum variants, when `follow_rust_naming` is enabled.
+    pub type_name_case: NameCase,
+    /// The case of value-level names derived from the spec, such as interface methods, actions
+    /// and variables, when `follow_rust_naming` is enabled.
+    pub value_name_case: NameCase,
+
     pub action_prefix: String,
     pub action_suffix: String,
     pub actions_prefix: String,
     pub actions_suffix: String,
+    pub action_calls_var_name: String,
+    pub take_action_calls_method_name: String,
+    pub domain_type_suffix: String,
+    pub new_with_method_name: String,
+    pub interface_trait_suffix: String,
+    pub fake_type_prefix: String,
 
     pub enter_token: String,
     pub exit_token: String,
@@ -318,10 +639,17 @@ pub struct RustCode {
     pub handle_event_method_name: String,
     pub change_state_method_name: String,
     pub transition_method_name: String,
+    /// The names of the halves of the transition method, sending the exit event to the state left
+    /// and the enter event to the new state, which transitions with actions call around them.
+    pub transition_exit_method_name: String,
+    pub transition_enter_method_name: String,
 
     pub assignment_temp_var_name: String,
     pub state_handler_name_prefix: String,
     pub state_handler_name_suffix: String,
+    /// Appended to the names of a parallel state and one of its regions to name the field
+    /// holding the current state of the region, and the methods dispatching events to it.
+    pub region_suffix: String,
 
     pub state_var_name: String,
     pub state_args_suffix: String,
@@ -335,8 +663,11 @@ pub struct RustCode {
     pub state_context_method_suffix: String,
     pub this_state_context_var_name: String,
 
+    pub state_enum_prefix: String,
     pub state_enum_suffix: String,
     pub state_enum_traits: String,
+    /// Traits derived by enums declared in the domain block.
+    pub enum_traits: String,
 
     pub change_state_hook_method_name: String,
     pub transition_hook_method_name: String,
@@ -345,17 +676,28 @@ pub struct RustCode {
     pub state_stack_push_method_name: String,
     pub state_stack_pop_method_name: String,
 
+    pub state_history_var_name: String,
+    pub state_history_record_method_name: String,
+    pub state_history_restore_method_name: String,
+
     pub runtime_info_module_name: String,
     pub runtime_module_use_as_name: String,
     pub machine_info_function_name: String,
     pub pop_state_info_name: String,
     pub event_monitor_var_name: String,
     pub transition_info_arg_name: String,
+
+    /// Directory of Handlebars templates overriding fragments of the generated code, relative
+    /// to the working directory: `file_header.hbs`, `interface_method.hbs` and
+    /// `state_handler.hbs`. Fragments without a template are generated as usual. No fragment is
+    /// overridden if empty.
+    pub override_template_dir: String,
 }
 
 /// Initial settings for the Rust runtime system. These options are only relevant if
 /// [RustFeatures.runtime_support] is enabled. These options can be changed at runtime later.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RustRuntime {
     /// The number of handled events to save in the event history. A value of `0` disables the
     /// event history feature, while a negative value allows the history to grow to unbounded size
@@ -372,6 +714,32 @@ pub struct RustRuntime {
     pub transition_history_capacity: i32,
 }
 
+impl RustCode {
+    /// Replace `{system}` in the names of the types shared by the events and states of a
+    /// machine with the type name of the system, so that the types of several machines
+    /// generated into the same module don't collide.
+    pub fn expand_system_name(&mut self, system_type_name: &str) {
+        for name in [
+            &mut self.frame_event_type_name,
+            &mut self.frame_event_args_type_name,
+            &mut self.frame_event_message_type_name,
+            &mut self.frame_event_return_type_name,
+            &mut self.state_context_type_name,
+        ] {
+            *name = name.replace("{system}", system_type_name);
+        }
+    }
+
+    /// Check that the derived traits are lists of traits.
+    pub fn check(&self) -> Result<(), InvalidSetting> {
+        check_trait_list(
+            "codegen.rust.code.state_enum_traits",
+            &self.state_enum_traits,
+        )?;
+        check_trait_list("codegen.rust.code.enum_traits", &self.enum_traits)
+    }
+}
+
 impl RustRuntime {
     /// Get the event history capacity as a value suitable for the event monitor.
     pub fn event_history_capacity(&self) -> Option<usize> {
@@ -384,8 +752,73 @@ impl RustRuntime {
     }
 }
 
+/// Code generation options specific to the PlantUML backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlantUmlConfig {
+    pub features: PlantUmlFeatures,
+    pub code: PlantUmlCode,
+}
+
+/// Code generation features specific to the PlantUML backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlantUmlFeatures {
+    /// When enabled, the edge of a transition shows the parameters of its event, e.g.
+    /// `open(speed)`.
+    ///
+    /// Default is `false`.
+    pub edge_params: bool,
+
+    /// When enabled, the edge of a transition in a branch of a test shows the condition of the
+    /// branch, e.g. `[speed > 0]`, with the conditions of nested branches joined by `&&`.
+    ///
+    /// Default is `false`.
+    pub edge_guards: bool,
+
+    /// When enabled, the edge of a transition shows the actions called by the event handler
+    /// before the transition and the actions of the transition, e.g. `/ unlock(); log()`.
+    ///
+    /// Default is `false`.
+    pub edge_actions: bool,
+}
+
+/// Style options for diagrams generated by the PlantUML backend.
+///
+/// See the PlantUML documentation of themes and skin parameters:
+/// <https://plantuml.com/theme> and <https://plantuml.com/skinparam>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlantUmlCode {
+    /// Name of the theme of the diagram, e.g. `cerulean`. No theme is used if empty.
+    pub theme: String,
+    /// Skin parameters of the diagram, one per entry, e.g. `backgroundColor white`.
+    pub skinparams: Vec<String>,
+}
+
+impl PlantUmlCode {
+    /// Check that the theme and skin parameters fit on a line of the diagram.
+    pub fn check(&self) -> Result<(), InvalidSetting> {
+        if self.theme.split_whitespace().count() > 1 {
+            let message = format!("\"{}\" is not a theme name", self.theme);
+            return Err(InvalidSetting::new("codegen.plantuml.code.theme", message));
+        }
+        for skinparam in &self.skinparams {
+            if skinparam.trim().is_empty() || skinparam.contains('\n') {
+                let message = format!("{:?} is not a skin parameter", skinparam);
+                return Err(InvalidSetting::new(
+                    "codegen.plantuml.code.skinparams",
+                    message,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Code generation options specific to the Smcat backend.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SmcatConfig {
     pub features: SmcatFeatures,
     pub code: SmcatCode,
@@ -393,13 +826,33 @@ pub struct SmcatConfig {
 
 /// Code generation features specific to the Smcat backend.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
-pub struct SmcatFeatures {}
+#[serde(deny_unknown_fields)]
+pub struct SmcatFeatures {
+    /// When enabled, the edge of a transition shows the parameters of its event, e.g.
+    /// `open(speed)`.
+    ///
+    /// Default is `false`.
+    pub edge_params: bool,
+
+    /// When enabled, the edge of a transition in a branch of a test shows the condition of the
+    /// branch, e.g. `[speed > 0]`, with the conditions of nested branches joined by `&&`.
+    ///
+    /// Default is `false`.
+    pub edge_guards: bool,
+
+    /// When enabled, the edge of a transition shows the actions called by the event handler
+    /// before the transition and the actions of the transition, e.g. `/ unlock(); log()`.
+    ///
+    /// Default is `false`.
+    pub edge_actions: bool,
+}
 
 /// Style options for generated code specific to the Smcat backend.
 ///
 /// See the sections "colors and line width", "classes", and "overriding the type of a state" in
 /// the smcat README: <https://github.com/sverweij/state-machine-cat/blob/develop/README.md>
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SmcatCode {
     /// Style settings for nodes that do not have any children.
     pub simple_state_node_style: String,
@@ -411,6 +864,187 @@ pub struct SmcatCode {
     pub transition_edge_style: String,
 }
 
+/// Code generation options specific to the template backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateConfig {
+    pub features: TemplateFeatures,
+    pub code: TemplateCode,
+}
+
+/// Code generation features specific to the template backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateFeatures {}
+
+/// Options locating the templates of the template backend.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateCode {
+    /// Directory of the Handlebars templates, relative to the working directory. The machine is
+    /// rendered with `machine.hbs`, and the other `.hbs` files of the directory can be used as
+    /// partials, e.g. `{{> state}}` for `state.hbs`.
+    pub template_dir: String,
+    /// Extension of the files generated from specs, e.g. `kt`.
+    pub file_extension: String,
+}
+
+impl TemplateCode {
+    /// Check that a template directory is set and the extension is a single path component.
+    pub fn check(&self) -> Result<(), InvalidSetting> {
+        if self.template_dir.is_empty() {
+            let message = "a template directory is required by the template backend".to_string();
+            return Err(InvalidSetting::new(
+                "codegen.template.code.template_dir",
+                message,
+            ));
+        }
+        if self.file_extension.is_empty() || self.file_extension.contains(['.', '/', '\\']) {
+            let message = format!("\"{}\" is not a file extension", self.file_extension);
+            return Err(InvalidSetting::new(
+                "codegen.template.code.file_extension",
+                message,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of the lint pass, which checks a Frame spec for style and design issues.
+///
+/// Each rule is configured with a level in `levels`. For example, to make state names that are
+/// not in `UpperCamelCase` an error, the following attribute statement would be used.
+///
+/// ```text
+/// #[lint.levels.state_naming:str="deny"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    pub levels: LintLevels,
+
+    /// The largest number of states a machine may define before `max_states` is reported.
+    ///
+    /// Default is `50`.
+    pub max_states: usize,
+
+    /// The naming style checked by `state_naming`.
+    ///
+    /// Default is `UpperCamelCase`.
+    pub state_naming_style: NamingStyle,
+
+    /// The naming style checked by `event_naming`.
+    ///
+    /// Default is `lowerCamelCase`.
+    pub event_naming_style: NamingStyle,
+}
+
+/// The level of each lint rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintLevels {
+    /// State names follow `state_naming_style`.
+    ///
+    /// Default is `warn`.
+    pub state_naming: LintLevel,
+
+    /// Interface method names follow `event_naming_style`.
+    ///
+    /// Default is `allow`.
+    pub event_naming: LintLevel,
+
+    /// A machine defines no more than `max_states` states.
+    ///
+    /// Default is `allow`.
+    pub max_states: LintLevel,
+
+    /// A state that holds state variables defines an exit handler to release them.
+    ///
+    /// Default is `allow`.
+    pub exit_handler_for_state_vars: LintLevel,
+
+    /// The types of expressions match the declared types of the variables, parameters and
+    /// return values they are used for, see [`typeck`](crate::frame_c::typeck).
+    ///
+    /// Default is `warn`.
+    pub type_mismatch: LintLevel,
+
+    /// Applies to every finding reported as a warning: `deny` turns all warnings into errors,
+    /// which is useful in CI, and `allow` hides them.
+    ///
+    /// Default is `warn`.
+    pub warnings: LintLevel,
+}
+
+impl LintLevels {
+    /// The names of the levels that can be set, which are the lint rules and `warnings`.
+    pub const NAMES: [&'static str; 6] = [
+        "state_naming",
+        "event_naming",
+        "max_states",
+        "exit_handler_for_state_vars",
+        "type_mismatch",
+        "warnings",
+    ];
+
+    /// Set the level of a lint rule, or of all warnings when `name` is `warnings`. Returns
+    /// `false` if there is no such rule.
+    pub fn set(&mut self, name: &str, level: LintLevel) -> bool {
+        let field = match name {
+            "state_naming" => &mut self.state_naming,
+            "event_naming" => &mut self.event_naming,
+            "max_states" => &mut self.max_states,
+            "exit_handler_for_state_vars" => &mut self.exit_handler_for_state_vars,
+            "type_mismatch" => &mut self.type_mismatch,
+            "warnings" => &mut self.warnings,
+            _ => return false,
+        };
+        *field = level;
+        true
+    }
+}
+
+/// How findings of a lint rule are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The rule is not checked.
+    Allow,
+    /// Findings are reported as warnings.
+    Warn,
+    /// Findings are reported as errors and fail the compilation.
+    Deny,
+}
+
+/// Configuration of the optimizer, which simplifies the machine before code is generated. Each
+/// simplification is reported as a note.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OptimizeConfig {
+    /// Merge states that handle the same events with the same transitions into the state
+    /// declared first.
+    ///
+    /// Default is `false`.
+    pub merge_equivalent_states: bool,
+
+    /// Send transitions to a state whose enter handler only transitions to another state
+    /// directly to that state, and remove the skipped state once nothing refers to it.
+    ///
+    /// Default is `false`.
+    pub collapse_transition_chains: bool,
+}
+
+/// A naming convention checked by the lint pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NamingStyle {
+    #[serde(rename = "UpperCamelCase")]
+    UpperCamelCase,
+    #[serde(rename = "lowerCamelCase")]
+    LowerCamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+}
+
 impl FrameConfig {
     /// Generate a configuration from any `Provider`.
     pub fn from<T: Provider>(provider: T) -> Result<FrameConfig, Error> {
@@ -421,6 +1055,16 @@ impl FrameConfig {
     pub fn figment(&self) -> Figment {
         Figment::from(self)
     }
+
+    /// Override the setting at a path of keys, e.g. `codegen.rust.features.thread_safe`, with a
+    /// value written in YAML, e.g. `true`.
+    pub fn with_setting(&self, key: &str, value: &str) -> Result<FrameConfig, Error> {
+        let value: Value =
+            serde_yaml::from_str(value).map_err(|err| Error::from(err.to_string()))?;
+        self.figment()
+            .merge(figment::providers::Serialized::default(key, value))
+            .extract()
+    }
 }
 
 /// Make `FrameConfig` a `Provider` for composability.
@@ -433,6 +1077,10 @@ impl Provider for FrameConfig {
     }
 }
 
+/// The top-level keys of `FrameConfig`. Attributes whose name starts with one of these are
+/// configuration attributes.
+const CONFIG_SECTIONS: [&str; 3] = ["codegen", "lint", "optimize"];
+
 /// Make `AttributeNode` a `Provider`. An attribute may contain zero or one configuration settings.
 ///
 /// The format of an attribute name is `[full.path.to.attribute]:[type]`, where the path is the
@@ -451,7 +1099,7 @@ impl Provider for FrameConfig {
 /// ```
 impl Provider for AttributeNode {
     fn metadata(&self) -> Metadata {
-        Metadata::named("AttributeNode")
+        Metadata::named(format!("attribute `#[{}]`", self.get_name()))
     }
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
         let mut map = Map::new();
@@ -474,10 +1122,10 @@ impl Provider for AttributeNode {
                 Ok(value) => {
                     config_value = Value::from(value);
                 }
-                Err(err) => {
+                Err(_) => {
                     return Err(Error::from(format!(
-                        "Error parsing boolean feature option: {:?}",
-                        err
+                        "invalid value \"{}\", expected `true` or `false`",
+                        value
                     )));
                 }
             }
@@ -488,10 +1136,10 @@ impl Provider for AttributeNode {
                 Ok(value) => {
                     config_value = Value::from(value);
                 }
-                Err(err) => {
+                Err(_) => {
                     return Err(Error::from(format!(
-                        "Error parsing integer feature option: {:?}",
-                        err
+                        "invalid value \"{}\", expected an integer",
+                        value
                     )));
                 }
             }
@@ -499,6 +1147,14 @@ impl Provider for AttributeNode {
             // this attribute is a string config option
             config_path = path;
             config_value = Value::from(value);
+        } else if CONFIG_SECTIONS
+            .iter()
+            .any(|section| attr_name.starts_with(&format!("{}.", section)))
+        {
+            return Err(Error::from(
+                "missing type of configuration attribute, expected `:bool`, `:int` or `:str`"
+                    .to_string(),
+            ));
         } else {
             return Ok(map);
         }
@@ -542,9 +1198,20 @@ impl Default for RustFeatures {
         RustFeatures {
             follow_rust_naming: true,
             generate_action_impl: true,
+            generate_mock_actions: false,
+            generate_display: false,
+            generate_new_with: false,
+            generate_fake: false,
             generate_hook_methods: false,
             runtime_support: false,
+            runtime_event_monitor: true,
+            runtime_machine_info: true,
+            runtime_environments: true,
+            runtime_dispatch: false,
             thread_safe: false,
+            lightweight: false,
+            generate_source_map: false,
+            split_states: false,
         }
     }
 }
@@ -552,10 +1219,19 @@ impl Default for RustFeatures {
 impl Default for RustCode {
     fn default() -> Self {
         RustCode {
+            type_name_case: NameCase::UpperCamel,
+            value_name_case: NameCase::Snake,
+
             action_prefix: String::from(""),
             action_suffix: String::from(""),
             actions_prefix: String::from(""),
             actions_suffix: String::from("Actions"),
+            action_calls_var_name: String::from("action_calls"),
+            take_action_calls_method_name: String::from("take_action_calls"),
+            domain_type_suffix: String::from("Domain"),
+            new_with_method_name: String::from("new_with"),
+            interface_trait_suffix: String::from("Interface"),
+            fake_type_prefix: String::from("Fake"),
 
             enter_token: String::from(">"),
             exit_token: String::from("<"),
@@ -579,10 +1255,13 @@ impl Default for RustCode {
             handle_event_method_name: String::from("handle_event"),
             change_state_method_name: String::from("change_state"),
             transition_method_name: String::from("transition"),
+            transition_exit_method_name: String::from("transition_exit"),
+            transition_enter_method_name: String::from("transition_enter"),
 
             assignment_temp_var_name: String::from("assign_temp"),
             state_handler_name_prefix: String::from(""),
             state_handler_name_suffix: String::from("_handler"),
+            region_suffix: String::from("_region"),
 
             state_var_name: String::from("state"),
             state_args_suffix: String::from("StateArgs"),
@@ -596,10 +1275,12 @@ impl Default for RustCode {
             state_context_method_suffix: String::from("_context"),
             this_state_context_var_name: String::from("this_state_context"),
 
+            state_enum_prefix: String::from(""),
             state_enum_suffix: String::from("State"),
             state_enum_traits: String::from(
                 "Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord",
             ),
+            enum_traits: String::from("Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd"),
 
             change_state_hook_method_name: String::from("change_state_hook"),
             transition_hook_method_name: String::from("transition_hook"),
@@ -608,12 +1289,42 @@ impl Default for RustCode {
             state_stack_push_method_name: String::from("state_stack_push"),
             state_stack_pop_method_name: String::from("state_stack_pop"),
 
+            state_history_var_name: String::from("state_history"),
+            state_history_record_method_name: String::from("state_history_record"),
+            state_history_restore_method_name: String::from("state_history_restore"),
+
             runtime_info_module_name: String::from("runtime_info"),
             runtime_module_use_as_name: String::from("runtime"),
             machine_info_function_name: String::from("machine_info"),
             pop_state_info_name: String::from("$$[-]"),
             event_monitor_var_name: String::from("event_monitor"),
             transition_info_arg_name: String::from("transition_info"),
+
+            override_template_dir: String::new(),
+        }
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            levels: LintLevels::default(),
+            max_states: 50,
+            state_naming_style: NamingStyle::UpperCamelCase,
+            event_naming_style: NamingStyle::LowerCamelCase,
+        }
+    }
+}
+
+impl Default for LintLevels {
+    fn default() -> Self {
+        LintLevels {
+            state_naming: LintLevel::Warn,
+            event_naming: LintLevel::Allow,
+            max_states: LintLevel::Allow,
+            exit_handler_for_state_vars: LintLevel::Allow,
+            type_mismatch: LintLevel::Warn,
+            warnings: LintLevel::Warn,
         }
     }
 }
@@ -637,3 +1348,194 @@ impl Default for SmcatCode {
         }
     }
 }
+
+impl Default for TemplateCode {
+    fn default() -> Self {
+        TemplateCode {
+            template_dir: String::new(),
+            file_extension: String::from("txt"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameConfig;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+    use crate::frame_c::utils::{frame_exitcode, RunError};
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_CONFIG_FILE: AtomicUsize = AtomicUsize::new(0);
+
+    const SPEC: &str = "#Lamp
+    -machine-
+    $Off
+##
+";
+
+    fn run(config: Option<&str>, attribute: &str) -> Result<String, RunError> {
+        run_target(config, attribute, TargetLanguage::Rust)
+    }
+
+    fn run_target(
+        config: Option<&str>,
+        attribute: &str,
+        target_language: TargetLanguage,
+    ) -> Result<String, RunError> {
+        let config_path = config.map(|yaml| {
+            let path = std::env::temp_dir().join(format!(
+                "framec_config_test_{}_{}.yaml",
+                std::process::id(),
+                NEXT_CONFIG_FILE.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::write(&path, yaml).unwrap();
+            path
+        });
+        let spec = format!("{}\n{}", attribute, SPEC);
+        let result = Exe::new().run(&config_path, None, spec, Some(target_language));
+        if let Some(path) = config_path {
+            let _ = fs::remove_file(path);
+        }
+        result
+    }
+
+    fn config_error(config: Option<&str>, attribute: &str) -> String {
+        let err = run(config, attribute).unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+        err.error
+    }
+
+    /// Test that misspelled keys are reported along with the attribute or file they're in.
+    #[test]
+    fn unknown_keys() {
+        let err = config_error(
+            None,
+            "#[codegen.rust.features.runtime_suport:bool=\"true\"]",
+        );
+        assert!(err.contains("unknown field: found `runtime_suport`"));
+        assert!(err.contains("`runtime_support`"));
+        assert!(err.contains("attribute `#[codegen.rust.features.runtime_suport:bool]`"));
+
+        let err = config_error(
+            Some("codegen:\n  rust:\n    feature:\n      thread_safe: true\n"),
+            "",
+        );
+        assert!(err.contains("unknown field: found `feature`"));
+        assert!(err.contains("\"default.codegen.rust.feature\""));
+        assert!(err.contains("YAML file"));
+    }
+
+    /// Test that values of the wrong type are reported with the offending key.
+    #[test]
+    fn type_mismatches() {
+        let err = config_error(None, "#[codegen.rust.features.thread_safe:bool=\"yes\"]");
+        assert!(err.contains("invalid value \"yes\", expected `true` or `false`"));
+
+        let err = config_error(
+            None,
+            "#[codegen.rust.features.thread_safe:boolean=\"true\"]",
+        );
+        assert!(err.contains("missing type of configuration attribute"));
+
+        let err = config_error(Some("lint:\n  max_states: many\n"), "");
+        assert!(err.contains("expected usize for key \"default.lint.max_states\""));
+
+        let err = config_error(Some("lint:\n  levels:\n    state_naming: error\n"), "");
+        assert!(err.contains("unknown variant: found `error`"));
+
+        let err = config_error(None, "#[codegen.rust.code.value_name_case:str=\"kebab\"]");
+        assert!(err.contains("unknown variant: found `kebab`"));
+    }
+
+    /// Test that conflicting features are reported against the feature that was set.
+    #[test]
+    fn invalid_combinations() {
+        let err = config_error(
+            Some("codegen:\n  rust:\n    features:\n      thread_safe: true\n"),
+            "#[codegen.rust.features.lightweight:bool=\"true\"]",
+        );
+        assert!(err.contains("lightweight cannot be combined with thread_safe"));
+        assert!(err.contains("attribute `#[codegen.rust.features.lightweight:bool]`"));
+
+        let err = config_error(
+            Some("codegen:\n  rust:\n    features:\n      runtime_machine_info: false\n"),
+            "#[codegen.rust.features.runtime_support:bool=\"true\"]",
+        );
+        assert!(err.contains("\"default.codegen.rust.features.runtime_machine_info\""));
+        assert!(err.contains("YAML file"));
+
+        let err = config_error(
+            Some("codegen:\n  rust:\n    features:\n      generate_action_impl: false\n"),
+            "#[codegen.rust.features.generate_mock_actions:bool=\"true\"]",
+        );
+        assert!(err.contains("generate_mock_actions requires generate_action_impl"));
+
+        let err = config_error(
+            Some("codegen:\n  rust:\n    features:\n      generate_source_map: true\n"),
+            "#[codegen.rust.features.split_states:bool=\"true\"]",
+        );
+        assert!(err.contains("split_states cannot be combined with generate_source_map"));
+    }
+
+    /// Test that a complete configuration file and attributes unrelated to configuration are
+    /// accepted.
+    #[test]
+    fn valid_config() {
+        let yaml = serde_yaml::to_string(&super::FrameConfig::default()).unwrap();
+        assert!(run(Some(&yaml), "#[derive(Debug)]").is_ok());
+    }
+
+    /// Test that one configuration can hold settings for several backends, and that the settings
+    /// of a backend are only checked when generating code for it.
+    #[test]
+    fn per_target_sections() {
+        let yaml = "codegen:
+  rust:
+    code:
+      enum_traits: \"Clone, serde::Serialize\"
+  plantuml:
+    code:
+      theme: cerulean
+      skinparams:
+        - backgroundColor white
+";
+        let spec = "#[codegen.rust.features.runtime_support:bool=\"false\"]";
+        let uml = run_target(Some(yaml), spec, TargetLanguage::PlantUml).unwrap();
+        assert!(uml.starts_with("@startuml\n!theme cerulean\nskinparam backgroundColor white\n"));
+
+        let yaml = yaml.replace("cerulean", "\"two words\"");
+        let err = run_target(Some(&yaml), spec, TargetLanguage::PlantUml).unwrap_err();
+        assert!(err.error.contains("\"two words\" is not a theme name"));
+        assert!(err
+            .error
+            .contains("\"default.codegen.plantuml.code.theme\""));
+        assert!(run_target(Some(&yaml), spec, TargetLanguage::Rust).is_ok());
+
+        let yaml = yaml.replace("serde::Serialize", "serde::");
+        let err = config_error(Some(&yaml), "");
+        assert!(err.contains("is not a comma-separated list of traits"));
+        assert!(err.contains("\"default.codegen.rust.code.enum_traits\""));
+    }
+
+    /// Test that settings are overridden by their path, with values parsed as YAML.
+    #[test]
+    fn with_setting() {
+        let config = FrameConfig::default()
+            .with_setting("codegen.rust.features.thread_safe", "true")
+            .unwrap()
+            .with_setting("codegen.rust.code.state_enum_suffix", "Mode")
+            .unwrap();
+        assert!(config.codegen.rust.features.thread_safe);
+        assert_eq!(config.codegen.rust.code.state_enum_suffix, "Mode");
+        assert!(!config.codegen.rust.features.runtime_support);
+
+        let err = FrameConfig::default()
+            .with_setting("codegen.rust.features.thread_safe", "maybe")
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"));
+        assert!(FrameConfig::default()
+            .with_setting("codegen.rust.features.thread_sfe", "true")
+            .is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Structured diagnostics reported by the Frame compiler.
+//!
+//! Every error reported by the scanner, parser, or the compiler driver is recorded as a
+//! [`Diagnostic`] alongside the traditional human-readable error text. Tools such as CI
+//! annotators and editor plugins can consume the diagnostics directly, either through the
+//! library API (see [`RunError::diagnostics`](crate::frame_c::utils::RunError)) or by running
+//! `framec --message-format=json`, which prints one JSON object per line.
+
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The severity of a diagnostic.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A region of the Frame specification, as a byte offset and length.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// A single message reported by the compiler.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// A short, stable identifier for the kind of diagnostic, e.g. `"parse-error"`.
+    pub code: String,
+
+    pub message: String,
+
+    /// Path of the Frame specification, if it was loaded from a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// 1-based line number, if the diagnostic refers to a location in the specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /// 1-based column number, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+
+    /// The source text the diagnostic points at, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lexeme: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &str, message: &str) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: code.to_string(),
+            message: message.to_string(),
+            file: None,
+            line: None,
+            column: None,
+            span: None,
+            lexeme: None,
+        }
+    }
+
+    pub fn error(code: &str, message: &str) -> Diagnostic {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: &str) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    pub fn with_line(mut self, line: usize) -> Diagnostic {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_span(mut self, start: usize, length: usize) -> Diagnostic {
+        self.span = Some(Span { start, length });
+        self
+    }
+
+    pub fn with_lexeme(mut self, lexeme: &str) -> Diagnostic {
+        self.lexeme = Some(lexeme.to_string());
+        self
+    }
+
+    pub fn with_file(mut self, file: Option<&str>) -> Diagnostic {
+        self.file = file.map(|f| f.to_string());
+        self
+    }
+
+    /// Fill in the column number from the span, given the source text the span refers to.
+    pub(crate) fn resolve_column(&mut self, source: &str) {
+        if let Some(span) = self.span {
+            if span.start <= source.len() {
+                let line_start = source.as_bytes()[..span.start]
+                    .iter()
+                    .rposition(|b| *b == b'\n')
+                    .map_or(0, |idx| idx + 1);
+                self.column = Some(span.start - line_start + 1);
+            }
+        }
+    }
+
+    /// Render the diagnostic as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        // Serializing a plain struct of strings and numbers cannot fail.
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+            if let Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+/// How the compiler reports errors and warnings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageFormat {
+    /// Free-form text intended for people.
+    Human,
+    /// One JSON object per diagnostic, one diagnostic per line.
+    Json,
+}
+
+impl Default for MessageFormat {
+    fn default() -> Self {
+        MessageFormat::Human
+    }
+}
+
+impl TryFrom<&str> for MessageFormat {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value == "human" {
+            Ok(MessageFormat::Human)
+        } else if value == "json" {
+            Ok(MessageFormat::Json)
+        } else {
+            Err(format!("Unrecognized message format {}", value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    /// Test that a parse error is reported as a structured diagnostic with a resolved location.
+    #[test]
+    fn parse_error_diagnostic() {
+        let spec = "#Broken\n-interface-\nfoo [\n##\n".to_string();
+        let err = Exe::new()
+            .run(&None, Some("broken.frm"), spec, Some(TargetLanguage::Rust))
+            .unwrap_err();
+        let diag = err.diagnostics.first().expect("expected a diagnostic");
+        assert_eq!(diag.severity, Severity::Error);
+        assert_eq!(diag.code, "parse-error");
+        assert_eq!(diag.file.as_deref(), Some("broken.frm"));
+        assert_eq!(diag.line, Some(4));
+        assert_eq!(diag.column, Some(1));
+        assert_eq!(diag.lexeme.as_deref(), Some("##"));
+    }
+
+    /// Test that the parser recovers from errors so independent errors in different parts of a
+    /// spec are reported by a single run, each of them once.
+    #[test]
+    fn independent_errors() {
+        let spec = "#Broken
+    -machine-
+    $S1
+        |a| x = = 3 ^
+    $S2
+        |b| y = 3
+    $S3
+        |c| z = = 4 ^
+    -actions-
+    foo : {`x`}
+    bar
+    bar
+    -domain-
+    var a:int =
+    var b:int = 1
+##
+"
+        .to_string();
+        let err = Exe::new()
+            .run(&None, Some("broken.frm"), spec, Some(TargetLanguage::Rust))
+            .unwrap_err();
+        let lines: Vec<usize> = err
+            .diagnostics
+            .iter()
+            .map(|diag| diag.line.unwrap())
+            .collect();
+        assert_eq!(lines, [4, 7, 8, 10, 12, 15]);
+        assert_eq!(err.diagnostics[4].message, "Duplicate action name bar.");
+    }
+
+    /// Test that JSON output puts one diagnostic on each line.
+    #[test]
+    fn json_message_format() {
+        let err = Exe::new()
+            .run(&None, None, "#Empty\n##\n".to_string(), None)
+            .unwrap_err();
+        let rendered = err.render(MessageFormat::Json);
+        assert_eq!(rendered.lines().count(), err.diagnostics.len());
+        for line in rendered.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["severity"], "error");
+        }
+    }
+}
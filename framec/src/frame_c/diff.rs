@@ -0,0 +1,833 @@
+//! Semantic differences between two versions of a machine.
+//!
+//! Where a text diff shows the lines that changed, this compares the intermediate
+//! representations of two versions of a spec and reports the interface methods, states, event
+//! handlers, transitions and domain variables that were added, removed or changed, for reviewing
+//! changes to specs. Unlike the `compat` module, it doesn't judge whether a change breaks
+//! anything.
+//!
+//! A state removed in the new version is considered renamed to an added state if both have the
+//! same parameters and handle mostly the same events, so the rename is reported instead of the
+//! removal and addition, and the transitions to the state aren't reported as changed. The differences can
+//! also be drawn as a Graphviz diagram of the new version, annotated with the added, removed and
+//! changed states and transitions.
+
+use crate::frame_c::ir::{HandlerModel, MachineModel, ParamModel, StateModel, TransitionModel};
+use serde::Serialize;
+use std::fmt;
+
+/// A difference between two versions of a machine. States are named by their name in the new
+/// version, except for removed states.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    AddedMethod {
+        method: String,
+    },
+    RemovedMethod {
+        method: String,
+    },
+    /// The parameters or return type of an interface method changed.
+    ChangedMethod {
+        method: String,
+        old: String,
+        new: String,
+    },
+    AddedState {
+        state: String,
+    },
+    RemovedState {
+        state: String,
+    },
+    RenamedState {
+        old: String,
+        new: String,
+    },
+    /// The parent, parameters or variables of a state changed, as given by `aspect`.
+    ChangedState {
+        state: String,
+        aspect: String,
+        old: String,
+        new: String,
+    },
+    AddedHandler {
+        state: String,
+        event: String,
+    },
+    RemovedHandler {
+        state: String,
+        event: String,
+    },
+    /// The parameters or return type of an event handler changed.
+    ChangedHandler {
+        state: String,
+        event: String,
+        old: String,
+        new: String,
+    },
+    AddedTransition {
+        state: String,
+        event: String,
+        transition: String,
+    },
+    RemovedTransition {
+        state: String,
+        event: String,
+        transition: String,
+    },
+    AddedVariable {
+        variable: String,
+    },
+    RemovedVariable {
+        variable: String,
+    },
+    /// The type, initial value or constness of a domain variable changed.
+    ChangedVariable {
+        variable: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::AddedMethod { method } => write!(f, "+ method {}", method),
+            Change::RemovedMethod { method } => write!(f, "- method {}", method),
+            Change::ChangedMethod { method, old, new } => {
+                write!(f, "~ method {}: {} => {}", method, old, new)
+            }
+            Change::AddedState { state } => write!(f, "+ state ${}", state),
+            Change::RemovedState { state } => write!(f, "- state ${}", state),
+            Change::RenamedState { old, new } => write!(f, "~ state ${} renamed to ${}", old, new),
+            Change::ChangedState {
+                state,
+                aspect,
+                old,
+                new,
+            } => write!(f, "~ state ${} {}: {} => {}", state, aspect, old, new),
+            Change::AddedHandler { state, event } => write!(f, "+ handler ${} |{}|", state, event),
+            Change::RemovedHandler { state, event } => {
+                write!(f, "- handler ${} |{}|", state, event)
+            }
+            Change::ChangedHandler {
+                state,
+                event,
+                old,
+                new,
+            } => write!(f, "~ handler ${} |{}|: {} => {}", state, event, old, new),
+            Change::AddedTransition {
+                state,
+                event,
+                transition,
+            } => write!(f, "+ transition ${} |{}| {}", state, event, transition),
+            Change::RemovedTransition {
+                state,
+                event,
+                transition,
+            } => write!(f, "- transition ${} |{}| {}", state, event, transition),
+            Change::AddedVariable { variable } => write!(f, "+ variable {}", variable),
+            Change::RemovedVariable { variable } => write!(f, "- variable {}", variable),
+            Change::ChangedVariable { variable, old, new } => {
+                write!(f, "~ variable {}: {} => {}", variable, old, new)
+            }
+        }
+    }
+}
+
+/// Compare two versions of a machine and report the differences between them, in the order of
+/// the interface, the states of the new version followed by the removed states, and the domain.
+pub fn diff(old: &MachineModel, new: &MachineModel) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for new_method in &new.interface {
+        match old
+            .interface
+            .iter()
+            .find(|old_method| old_method.name == new_method.name)
+        {
+            Some(old_method) => {
+                let old_signature = format_signature(&old_method.params, &old_method.return_type);
+                let new_signature = format_signature(&new_method.params, &new_method.return_type);
+                if old_signature != new_signature {
+                    changes.push(Change::ChangedMethod {
+                        method: new_method.name.clone(),
+                        old: old_signature,
+                        new: new_signature,
+                    });
+                }
+            }
+            None => changes.push(Change::AddedMethod {
+                method: new_method.name.clone(),
+            }),
+        }
+    }
+    for old_method in &old.interface {
+        if !new
+            .interface
+            .iter()
+            .any(|new_method| new_method.name == old_method.name)
+        {
+            changes.push(Change::RemovedMethod {
+                method: old_method.name.clone(),
+            });
+        }
+    }
+
+    let renames = Renames::new(old, new);
+    for new_state in &new.states {
+        let old_state = match renames.old_state(old, &new_state.name) {
+            Some(old_state) => old_state,
+            None => {
+                changes.push(Change::AddedState {
+                    state: new_state.name.clone(),
+                });
+                continue;
+            }
+        };
+        if old_state.name != new_state.name {
+            changes.push(Change::RenamedState {
+                old: old_state.name.clone(),
+                new: new_state.name.clone(),
+            });
+        }
+        compare_state(old_state, new_state, &renames, &mut changes);
+    }
+    for old_state in &old.states {
+        if renames.new_name(&old_state.name).is_none() {
+            changes.push(Change::RemovedState {
+                state: old_state.name.clone(),
+            });
+        }
+    }
+
+    for new_var in &new.domain {
+        match old
+            .domain
+            .iter()
+            .find(|old_var| old_var.name == new_var.name)
+        {
+            Some(old_var) => {
+                let old_decl = format_var(&old_var.type_opt, &old_var.value, old_var.constant);
+                let new_decl = format_var(&new_var.type_opt, &new_var.value, new_var.constant);
+                if old_decl != new_decl {
+                    changes.push(Change::ChangedVariable {
+                        variable: new_var.name.clone(),
+                        old: old_decl,
+                        new: new_decl,
+                    });
+                }
+            }
+            None => changes.push(Change::AddedVariable {
+                variable: new_var.name.clone(),
+            }),
+        }
+    }
+    for old_var in &old.domain {
+        if !new
+            .domain
+            .iter()
+            .any(|new_var| new_var.name == old_var.name)
+        {
+            changes.push(Change::RemovedVariable {
+                variable: old_var.name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Draw the states and transitions of the new version of a machine as a Graphviz graph, with
+/// the states and transitions removed since the old version. Added states and transitions are
+/// green, removed ones are red and dashed, and renamed or changed states are orange, with the
+/// old name of renamed states.
+pub fn diagram(old: &MachineModel, new: &MachineModel) -> String {
+    let renames = Renames::new(old, new);
+    let changes = diff(old, new);
+    let mut graph = String::from("digraph {\n");
+    graph.push_str("  node [shape=box, style=rounded, fontname=\"sans-serif\"];\n");
+    graph.push_str("  edge [fontname=\"sans-serif\", fontsize=10];\n");
+
+    for new_state in &new.states {
+        let mark = match renames.old_state(old, &new_state.name) {
+            None => Mark::Added,
+            Some(old_state) if old_state.name != new_state.name => {
+                let label = format!("{}\n(was {})", new_state.name, old_state.name);
+                graph.push_str(&format!(
+                    "  {} [label={}, {}];\n",
+                    dot_id(&new_state.name),
+                    dot_id(&label),
+                    Mark::Changed.attributes()
+                ));
+                continue;
+            }
+            Some(_)
+                if changes
+                    .iter()
+                    .any(|change| is_change_of(change, &new_state.name)) =>
+            {
+                Mark::Changed
+            }
+            Some(_) => Mark::Unchanged,
+        };
+        push_node(&mut graph, &new_state.name, mark);
+    }
+    for old_state in &old.states {
+        if renames.new_name(&old_state.name).is_none() {
+            push_node(&mut graph, &old_state.name, Mark::Removed);
+        }
+    }
+    if let Some(initial_state) = &new.initial_state {
+        graph.push_str("  \"$start\" [shape=point, label=\"\"];\n");
+        graph.push_str(&format!("  \"$start\" -> {};\n", dot_id(initial_state)));
+    }
+
+    for new_state in &new.states {
+        let old_state = renames.old_state(old, &new_state.name);
+        for new_handler in &new_state.handlers {
+            let old_transitions = old_state
+                .and_then(|old_state| find_handler(old_state, &new_handler.event))
+                .map(|old_handler| renamed_transitions(old_handler, &renames))
+                .unwrap_or_default();
+            for transition in &new_handler.transitions {
+                let mark = if old_transitions.contains(transition) {
+                    Mark::Unchanged
+                } else {
+                    Mark::Added
+                };
+                push_edge(
+                    &mut graph,
+                    &new_state.name,
+                    &new_handler.event,
+                    transition,
+                    mark,
+                );
+            }
+            for transition in &old_transitions {
+                if !new_handler.transitions.contains(transition) {
+                    push_edge(
+                        &mut graph,
+                        &new_state.name,
+                        &new_handler.event,
+                        transition,
+                        Mark::Removed,
+                    );
+                }
+            }
+        }
+        // the transitions of removed handlers
+        for old_handler in old_state.iter().flat_map(|old_state| &old_state.handlers) {
+            if find_handler(new_state, &old_handler.event).is_none() {
+                for transition in &renamed_transitions(old_handler, &renames) {
+                    push_edge(
+                        &mut graph,
+                        &new_state.name,
+                        &old_handler.event,
+                        transition,
+                        Mark::Removed,
+                    );
+                }
+            }
+        }
+    }
+    for old_state in &old.states {
+        if renames.new_name(&old_state.name).is_some() {
+            continue;
+        }
+        for old_handler in &old_state.handlers {
+            for transition in &renamed_transitions(old_handler, &renames) {
+                push_edge(
+                    &mut graph,
+                    &old_state.name,
+                    &old_handler.event,
+                    transition,
+                    Mark::Removed,
+                );
+            }
+        }
+    }
+
+    let pops = old.states.iter().chain(&new.states).any(|state| {
+        state
+            .handlers
+            .iter()
+            .flat_map(|handler| &handler.transitions)
+            .any(|transition| transition.target.is_none())
+    });
+    if pops {
+        graph.push_str("  \"$history\" [shape=circle, label=\"H\"];\n");
+    }
+    graph.push_str("}\n");
+    graph
+}
+
+/* --------------------------------------------------------------------- */
+
+/// The names of the states kept in the new version, by their name in the old version.
+struct Renames {
+    names: Vec<(String, String)>,
+}
+
+impl Renames {
+    fn new(old: &MachineModel, new: &MachineModel) -> Renames {
+        let is_new = |name: &str| new.states.iter().any(|new_state| new_state.name == name);
+        let is_old = |name: &str| old.states.iter().any(|old_state| old_state.name == name);
+        let mut names: Vec<(String, String)> = old
+            .states
+            .iter()
+            .filter(|old_state| is_new(&old_state.name))
+            .map(|old_state| (old_state.name.clone(), old_state.name.clone()))
+            .collect();
+
+        // pair the removed and added states with the same parameters sharing at least half of
+        // the events either handles, the most similar first
+        let mut candidates = Vec::new();
+        for old_state in old.states.iter().filter(|state| !is_new(&state.name)) {
+            for new_state in new.states.iter().filter(|state| !is_old(&state.name)) {
+                let similarity = similarity(&events(old_state), &events(new_state));
+                if new_state.params == old_state.params && similarity >= 0.5 {
+                    candidates.push((similarity, &old_state.name, &new_state.name));
+                }
+            }
+        }
+        // the sort is stable, so ties are broken by the order of the specs
+        candidates.sort_by(|(a, _, _), (b, _, _)| b.partial_cmp(a).unwrap());
+        let mut renamed: Vec<(String, String)> = Vec::new();
+        for (_, old_name, new_name) in candidates {
+            if !renamed
+                .iter()
+                .any(|(old, new)| old == old_name || new == new_name)
+            {
+                renamed.push((old_name.clone(), new_name.clone()));
+            }
+        }
+        names.extend(renamed);
+        Renames { names }
+    }
+
+    /// The name in the new version of a state of the old version, if it's still there.
+    fn new_name(&self, old_name: &str) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|(old, _)| old == old_name)
+            .map(|(_, new)| new.as_str())
+    }
+
+    /// The state of the old version that a state of the new version was, if any.
+    fn old_state<'a>(&self, old: &'a MachineModel, new_name: &str) -> Option<&'a StateModel> {
+        let (old_name, _) = self.names.iter().find(|(_, new)| new == new_name)?;
+        old.states.iter().find(|state| state.name == *old_name)
+    }
+
+    /// The name of a state of the old version in the new version if it's still there, so that
+    /// references to renamed states compare equal.
+    fn rename(&self, old_name: &str) -> String {
+        self.new_name(old_name).unwrap_or(old_name).to_string()
+    }
+}
+
+fn compare_state(
+    old_state: &StateModel,
+    new_state: &StateModel,
+    renames: &Renames,
+    changes: &mut Vec<Change>,
+) {
+    let state = &new_state.name;
+    let old_parent = old_state
+        .parent
+        .as_deref()
+        .map(|parent| renames.rename(parent));
+    let aspects = [
+        (
+            "parent",
+            format_parent(&old_parent),
+            format_parent(&new_state.parent),
+        ),
+        (
+            "parameters",
+            format_params(&old_state.params),
+            format_params(&new_state.params),
+        ),
+        ("variables", format_vars(old_state), format_vars(new_state)),
+    ];
+    for (aspect, old, new) in aspects {
+        if old != new {
+            changes.push(Change::ChangedState {
+                state: state.clone(),
+                aspect: aspect.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+
+    for new_handler in &new_state.handlers {
+        let event = &new_handler.event;
+        let old_handler = match find_handler(old_state, event) {
+            Some(old_handler) => old_handler,
+            None => {
+                changes.push(Change::AddedHandler {
+                    state: state.clone(),
+                    event: event.clone(),
+                });
+                continue;
+            }
+        };
+        let old_signature = format_signature(&old_handler.params, &old_handler.return_type);
+        let new_signature = format_signature(&new_handler.params, &new_handler.return_type);
+        if old_signature != new_signature {
+            changes.push(Change::ChangedHandler {
+                state: state.clone(),
+                event: event.clone(),
+                old: old_signature,
+                new: new_signature,
+            });
+        }
+        let old_transitions = renamed_transitions(old_handler, renames);
+        for transition in &new_handler.transitions {
+            if !old_transitions.contains(transition) {
+                changes.push(Change::AddedTransition {
+                    state: state.clone(),
+                    event: event.clone(),
+                    transition: format_transition(transition),
+                });
+            }
+        }
+        for transition in &old_transitions {
+            if !new_handler.transitions.contains(transition) {
+                changes.push(Change::RemovedTransition {
+                    state: state.clone(),
+                    event: event.clone(),
+                    transition: format_transition(transition),
+                });
+            }
+        }
+    }
+    for old_handler in &old_state.handlers {
+        if find_handler(new_state, &old_handler.event).is_none() {
+            changes.push(Change::RemovedHandler {
+                state: state.clone(),
+                event: old_handler.event.clone(),
+            });
+        }
+    }
+}
+
+/// Is the change one to the handlers or transitions of the given state?
+fn is_change_of(change: &Change, name: &str) -> bool {
+    match change {
+        Change::ChangedState { state, .. }
+        | Change::AddedHandler { state, .. }
+        | Change::RemovedHandler { state, .. }
+        | Change::ChangedHandler { state, .. }
+        | Change::AddedTransition { state, .. }
+        | Change::RemovedTransition { state, .. } => state == name,
+        _ => false,
+    }
+}
+
+fn find_handler<'a>(state: &'a StateModel, event: &str) -> Option<&'a HandlerModel> {
+    state.handlers.iter().find(|handler| handler.event == event)
+}
+
+/// The number of shared events over the number of events handled by either state.
+fn similarity(old_events: &[&str], new_events: &[&str]) -> f64 {
+    let shared = old_events
+        .iter()
+        .filter(|event| new_events.contains(event))
+        .count();
+    let total = old_events.len() + new_events.len() - shared;
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f64 / total as f64
+}
+
+fn events(state: &StateModel) -> Vec<&str> {
+    let mut events: Vec<&str> = state
+        .handlers
+        .iter()
+        .map(|handler| handler.event.as_str())
+        .collect();
+    events.sort_unstable();
+    events
+}
+
+/// The transitions of a handler of the old version, with the targets renamed as in the new
+/// version.
+fn renamed_transitions(handler: &HandlerModel, renames: &Renames) -> Vec<TransitionModel> {
+    handler
+        .transitions
+        .iter()
+        .map(|transition| TransitionModel {
+            target: transition
+                .target
+                .as_deref()
+                .map(|target| renames.rename(target)),
+            ..transition.clone()
+        })
+        .collect()
+}
+
+fn format_parent(parent: &Option<String>) -> String {
+    match parent {
+        Some(parent) => format!("${}", parent),
+        None => String::from("none"),
+    }
+}
+
+/// Parameters in the syntax of the spec, e.g. `[level:u8 = 1]`, or `[]` if there are none.
+fn format_params(params: &[ParamModel]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|param| format_decl(&param.name, &param.type_opt, &param.default))
+        .collect();
+    format!("[{}]", params.join(", "))
+}
+
+fn format_signature(params: &[ParamModel], return_type: &Option<String>) -> String {
+    match return_type {
+        Some(return_type) => format!("{}:{}", format_params(params), return_type),
+        None => format_params(params),
+    }
+}
+
+fn format_vars(state: &StateModel) -> String {
+    let vars: Vec<String> = state
+        .vars
+        .iter()
+        .map(|var| format_decl(&var.name, &var.type_opt, &var.value))
+        .collect();
+    format!("[{}]", vars.join(", "))
+}
+
+fn format_var(type_opt: &Option<String>, value: &Option<String>, constant: bool) -> String {
+    let mut decl = String::from(if constant { "const" } else { "var" });
+    if let Some(type_name) = type_opt {
+        decl.push(':');
+        decl.push_str(type_name);
+    }
+    if let Some(value) = value {
+        decl.push_str(" = ");
+        decl.push_str(value);
+    }
+    decl
+}
+
+fn format_decl(name: &str, type_opt: &Option<String>, value: &Option<String>) -> String {
+    let mut decl = name.to_string();
+    if let Some(type_name) = type_opt {
+        decl.push(':');
+        decl.push_str(type_name);
+    }
+    if let Some(value) = value {
+        decl.push_str(" = ");
+        decl.push_str(value);
+    }
+    decl
+}
+
+/// A transition in the syntax of the spec, e.g. `-> "on" $On(level)`, followed by its guard and
+/// the actions called before it, if any.
+fn format_transition(transition: &TransitionModel) -> String {
+    let mut text = String::new();
+    if !transition.exit_args.is_empty() {
+        text.push_str(&format!("({}) ", transition.exit_args.join(", ")));
+    }
+    text.push_str(if transition.change_state { "->>" } else { "->" });
+    if transition.forward_event {
+        text.push_str(" =>");
+    }
+    if !transition.enter_args.is_empty() {
+        text.push_str(&format!(" ({})", transition.enter_args.join(", ")));
+    }
+    if let Some(label) = &transition.label {
+        text.push_str(&format!(" \"{}\"", label));
+    }
+    match &transition.target {
+        Some(target) => text.push_str(&format!(" ${}", target)),
+        None => text.push_str(" $$[-]"),
+    }
+    if !transition.state_args.is_empty() {
+        text.push_str(&format!("({})", transition.state_args.join(", ")));
+    }
+    if let Some(guard) = &transition.guard {
+        text.push_str(&format!(" if {}", guard));
+    }
+    if !transition.actions.is_empty() {
+        text.push_str(&format!(" after {}", transition.actions.join("; ")));
+    }
+    text
+}
+
+/// How a state or transition is drawn in the diagram of the differences.
+#[derive(Clone, Copy)]
+enum Mark {
+    Unchanged,
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Mark {
+    /// The Graphviz attributes of the mark, e.g. `color=red`.
+    fn attributes(self) -> &'static str {
+        match self {
+            Mark::Unchanged => "",
+            Mark::Added => "color=darkgreen, fontcolor=darkgreen",
+            Mark::Removed => "style=dashed, color=red, fontcolor=red",
+            Mark::Changed => "color=darkorange, fontcolor=darkorange",
+        }
+    }
+}
+
+fn push_node(graph: &mut String, name: &str, mark: Mark) {
+    match mark {
+        Mark::Unchanged => graph.push_str(&format!("  {};\n", dot_id(name))),
+        // states are rounded boxes, which removed states keep
+        Mark::Removed => graph.push_str(&format!(
+            "  {} [style=\"rounded,dashed\", color=red, fontcolor=red];\n",
+            dot_id(name)
+        )),
+        _ => graph.push_str(&format!("  {} [{}];\n", dot_id(name), mark.attributes())),
+    }
+}
+
+fn push_edge(
+    graph: &mut String,
+    source: &str,
+    event: &str,
+    transition: &TransitionModel,
+    mark: Mark,
+) {
+    let target = match &transition.target {
+        Some(target) => dot_id(target),
+        None => String::from("\"$history\""),
+    };
+    let mut attributes = format!("label={}", dot_id(event));
+    if !matches!(mark, Mark::Unchanged) {
+        attributes.push_str(", ");
+        attributes.push_str(mark.attributes());
+    }
+    graph.push_str(&format!(
+        "  {} -> {} [{}];\n",
+        dot_id(source),
+        target,
+        attributes
+    ));
+}
+
+fn dot_id(name: &str) -> String {
+    format!(
+        "\"{}\"",
+        name.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const OLD: &str = "\
+#Lamp
+    -interface-
+    toggle
+    dim [level:u8]
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+        |dim| [level:u8] ^
+    $Broken
+        |toggle| ^
+    -domain-
+    var brightness:u8 = 1
+##
+";
+
+    const NEW: &str = "\
+#Lamp
+    -interface-
+    toggle
+    dim [level:u16]
+    reset
+    -machine-
+    $Dark
+        |toggle| -> $Lit ^
+        |reset| ^
+    $Lit
+        |toggle| -> \"off\" $Dark ^
+        |dim| [level:u16] ^
+    -domain-
+    var brightness:u8 = 2
+    var color = `0`
+##
+";
+
+    fn model(spec: &str) -> MachineModel {
+        let ir = Exe::new()
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Ir))
+            .unwrap();
+        MachineModel::from_json(&ir).unwrap()
+    }
+
+    /// Test that renamed states are recognized, and that the changes to the handlers and
+    /// transitions of the states they are renamed to are reported.
+    #[test]
+    fn reports_changes() {
+        let changes: Vec<String> = diff(&model(OLD), &model(NEW))
+            .iter()
+            .map(Change::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "~ method dim: [level:u8] => [level:u16]",
+                "+ method reset",
+                "~ state $Off renamed to $Dark",
+                "+ handler $Dark |reset|",
+                "~ state $On renamed to $Lit",
+                "+ transition $Lit |toggle| -> \"off\" $Dark",
+                "- transition $Lit |toggle| -> $Dark",
+                "~ handler $Lit |dim|: [level:u8] => [level:u16]",
+                "- state $Broken",
+                "~ variable brightness: var:u8 = 1 => var:u8 = 2",
+                "+ variable color",
+            ]
+        );
+        assert!(diff(&model(NEW), &model(NEW)).is_empty());
+    }
+
+    #[test]
+    fn serializes_changes() {
+        let changes = diff(&model(OLD), &model(NEW));
+        assert_eq!(
+            serde_json::to_string(&changes[2]).unwrap(),
+            r#"{"change":"renamed_state","old":"Off","new":"Dark"}"#
+        );
+    }
+
+    /// Test that the diagram marks added, removed and renamed states and transitions.
+    #[test]
+    fn draws_changes() {
+        let graph = diagram(&model(OLD), &model(NEW));
+        assert!(graph.contains(
+            "  \"Dark\" [label=\"Dark\\n(was Off)\", color=darkorange, fontcolor=darkorange];\n"
+        ));
+        assert!(
+            graph.contains("  \"Broken\" [style=\"rounded,dashed\", color=red, fontcolor=red];\n")
+        );
+        assert!(graph.contains(
+            "  \"Lit\" -> \"Dark\" [label=\"toggle\", color=darkgreen, fontcolor=darkgreen];\n"
+        ));
+        assert!(graph.contains(
+            "  \"Lit\" -> \"Dark\" [label=\"toggle\", style=dashed, color=red, fontcolor=red];\n"
+        ));
+        assert!(graph.contains("  \"Dark\" -> \"Lit\" [label=\"toggle\"];\n"));
+    }
+}
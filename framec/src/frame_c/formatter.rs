@@ -0,0 +1,401 @@
+//! Canonical formatting of Frame specifications.
+//!
+//! The formatter works on lines rather than on the AST, so comments and the exact spelling of
+//! statements are preserved and specifications that don't parse can still be formatted. It:
+//!
+//! * normalizes line endings, expands tabs and strips trailing whitespace,
+//! * collapses runs of blank lines and removes blank lines at the start and end of a system,
+//! * indents system headers and attributes at column 0, blocks, interface methods, actions,
+//!   domain variables and states one level in, and event handlers two levels in,
+//! * indents the statements of an event handler one level below the handler, keeping their
+//!   nesting relative to each other.
+//!
+//! Comment-only lines, `{-- --}` comments and multi-line superstrings are left as written.
+//! Formatting is idempotent: formatting already formatted source returns it unchanged.
+
+const INDENT: usize = 4;
+
+const BLOCK_HEADERS: [&str; 4] = ["-interface-", "-machine-", "-actions-", "-domain-"];
+
+/// Format a Frame specification in the canonical style.
+pub fn format_source(source: &str) -> String {
+    let lines = classify(source);
+    let indents = layout(&lines);
+
+    let mut output = String::new();
+    let mut pending_blank = false;
+    for (line, indent) in lines.iter().zip(indents) {
+        match line {
+            Line::Blank => {
+                pending_blank = !output.is_empty();
+            }
+            Line::Verbatim { text } => {
+                flush_blank(&mut output, &mut pending_blank, false);
+                output.push_str(text);
+                output.push('\n');
+            }
+            Line::Code { kind, text, .. } => {
+                let suppress_blank = matches!(kind, LineKind::SystemEnd)
+                    || output.ends_with("##\n")
+                    || last_line_is_system_start(&output);
+                flush_blank(&mut output, &mut pending_blank, suppress_blank);
+                output.push_str(&" ".repeat(indent));
+                output.push_str(text);
+                output.push('\n');
+            }
+        }
+    }
+    output
+}
+
+/// Is the given source already in the canonical style?
+pub fn is_formatted(source: &str) -> bool {
+    format_source(source) == source
+}
+
+/* --------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, PartialEq)]
+enum LineKind {
+    /// `#System` or `#[attribute]`.
+    Directive,
+    /// `#include`, which may appear at the top level or inside a block.
+    Include,
+    SystemEnd,
+    BlockHeader,
+    State,
+    EventHandler,
+    /// A statement of an event handler, or any other line in a block.
+    Other,
+}
+
+enum Line {
+    Blank,
+    /// A line that is emitted as written, apart from trailing whitespace.
+    Verbatim {
+        text: String,
+    },
+    Code {
+        kind: LineKind,
+        /// Indentation in the original source.
+        indent: usize,
+        text: String,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    None,
+    Machine,
+    Other,
+}
+
+fn classify(source: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut in_superstring = false;
+    let mut in_block_comment = false;
+
+    for raw_line in source.lines() {
+        let expanded = raw_line.replace('\t', &" ".repeat(INDENT));
+        let line = expanded.trim_end();
+        let text = line.trim_start();
+
+        if in_superstring || in_block_comment {
+            lines.push(Line::Verbatim {
+                text: line.to_string(),
+            });
+        } else if text.is_empty() {
+            lines.push(Line::Blank);
+        } else if text.starts_with("---") || text.starts_with("{--") {
+            lines.push(Line::Verbatim {
+                text: line.to_string(),
+            });
+        } else {
+            let kind = if text.starts_with("##") {
+                LineKind::SystemEnd
+            } else if text.starts_with("#include") {
+                LineKind::Include
+            } else if text.starts_with('#') {
+                LineKind::Directive
+            } else if BLOCK_HEADERS.iter().any(|header| text.starts_with(header)) {
+                LineKind::BlockHeader
+            } else if text.starts_with('$') && !text.starts_with("$$") && !text.starts_with("$.") {
+                LineKind::State
+            } else if text.starts_with('|') {
+                LineKind::EventHandler
+            } else {
+                LineKind::Other
+            };
+            lines.push(Line::Code {
+                kind,
+                indent: line.len() - text.len(),
+                text: text.to_string(),
+            });
+        }
+
+        scan_line(line, &mut in_superstring, &mut in_block_comment);
+    }
+    lines
+}
+
+// Track superstrings and block comments that continue past the end of the line.
+fn scan_line(line: &str, in_superstring: &mut bool, in_block_comment: &mut bool) {
+    let mut in_string = false;
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if *in_block_comment {
+            if rest.starts_with("--}") {
+                *in_block_comment = false;
+                rest = &rest[3..];
+                continue;
+            }
+        } else if *in_superstring {
+            if c == '`' {
+                *in_superstring = false;
+            }
+        } else if in_string {
+            if c == '\\' {
+                rest = &rest[c.len_utf8()..];
+                if let Some(escaped) = rest.chars().next() {
+                    rest = &rest[escaped.len_utf8()..];
+                }
+                continue;
+            }
+            if c == '"' {
+                in_string = false;
+            }
+        } else if rest.starts_with("---") {
+            return;
+        } else if rest.starts_with("{--") {
+            *in_block_comment = true;
+            rest = &rest[3..];
+            continue;
+        } else if c == '`' {
+            *in_superstring = true;
+        } else if c == '"' {
+            in_string = true;
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+// Compute the canonical indentation of every line. Statements are grouped by the state or
+// event handler they follow, and keep their indentation relative to the least indented
+// statement of the group.
+fn layout(lines: &[Line]) -> Vec<usize> {
+    let mut indents = vec![0; lines.len()];
+    let mut block = Block::None;
+    let mut in_system = false;
+    let mut brace_depth: usize = 0;
+
+    let mut i = 0;
+    while i < lines.len() {
+        let (kind, indent) = match &lines[i] {
+            Line::Code { kind, indent, .. } => (*kind, *indent),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        match kind {
+            LineKind::Directive => {
+                indents[i] = 0;
+                in_system = true;
+                block = Block::None;
+            }
+            LineKind::SystemEnd => {
+                indents[i] = 0;
+                in_system = false;
+                block = Block::None;
+            }
+            LineKind::BlockHeader => {
+                indents[i] = INDENT;
+                brace_depth = 0;
+                block = match &lines[i] {
+                    Line::Code { text, .. } if text.starts_with("-machine-") => Block::Machine,
+                    _ => Block::Other,
+                };
+            }
+            LineKind::Include => {
+                indents[i] = if in_system { INDENT } else { 0 };
+            }
+            LineKind::State if block == Block::Machine => {
+                indents[i] = INDENT;
+                i = layout_statements(lines, &mut indents, i + 1, INDENT * 2);
+                continue;
+            }
+            LineKind::EventHandler if block == Block::Machine => {
+                indents[i] = INDENT * 2;
+                i = layout_statements(lines, &mut indents, i + 1, INDENT * 3);
+                continue;
+            }
+            _ if in_system => {
+                // enum bodies and other braced declarations are indented one more level
+                let text = match &lines[i] {
+                    Line::Code { text, .. } => text,
+                    _ => unreachable!(),
+                };
+                let rest = match text.strip_prefix('}') {
+                    Some(rest) => {
+                        brace_depth = brace_depth.saturating_sub(1);
+                        rest
+                    }
+                    None => text,
+                };
+                indents[i] = INDENT * (brace_depth + 1);
+                brace_depth += net_braces(rest);
+            }
+            _ => {
+                indents[i] = indent;
+            }
+        }
+        i += 1;
+    }
+    indents
+}
+
+// Indent the statements starting at `start` and return the index of the first line that
+// isn't a statement.
+fn layout_statements(lines: &[Line], indents: &mut [usize], start: usize, base: usize) -> usize {
+    let mut end = start;
+    let mut min_indent = usize::MAX;
+    while end < lines.len() {
+        match &lines[end] {
+            Line::Code {
+                kind: LineKind::Other,
+                indent,
+                ..
+            } => min_indent = min_indent.min(*indent),
+            Line::Code { .. } => break,
+            _ => {}
+        }
+        end += 1;
+    }
+    for i in start..end {
+        if let Line::Code { indent, .. } = &lines[i] {
+            indents[i] = base + indent - min_indent;
+        }
+    }
+    end
+}
+
+// The number of braces a line opens, less the number it closes, ignoring braces in strings.
+fn net_braces(text: &str) -> usize {
+    let mut depth: isize = 0;
+    let mut in_string = false;
+    for c in text.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    depth.max(0) as usize
+}
+
+fn flush_blank(output: &mut String, pending_blank: &mut bool, suppress: bool) {
+    if *pending_blank && !suppress {
+        output.push('\n');
+    }
+    *pending_blank = false;
+}
+
+fn last_line_is_system_start(output: &str) -> bool {
+    output.lines().last().map_or(false, |line| {
+        line.starts_with('#') && !line.starts_with("#[")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str = "#[codegen.rust.features.runtime_support:bool=\"true\"]\r
+  #Player\r
+\r
+-interface-\r
+        play [speed:u32]   \r
+\tstop\r
+\r
+\r
+  -machine-\r
+$Idle\r
+  |play| [speed:u32]\r
+      speed > 1 ?\r
+          log(\"fast\")\r
+      :\r
+          log(\"slow\")\r
+      :: -> $Playing ^\r
+        --- comments are left alone\r
+  $Playing\r
+|stop| -> $Idle ^\r
+\r
+-actions-\r
+  log [msg:String]\r
+\r
+-domain-\r
+    var tape:Log = `vec![\r
+  \"multi-line\".to_string(),\r
+]`\r
+\r
+##\r
+";
+
+    const FORMATTED: &str = "#[codegen.rust.features.runtime_support:bool=\"true\"]
+#Player
+    -interface-
+    play [speed:u32]
+    stop
+
+    -machine-
+    $Idle
+        |play| [speed:u32]
+            speed > 1 ?
+                log(\"fast\")
+            :
+                log(\"slow\")
+            :: -> $Playing ^
+        --- comments are left alone
+    $Playing
+        |stop| -> $Idle ^
+
+    -actions-
+    log [msg:String]
+
+    -domain-
+    var tape:Log = `vec![
+  \"multi-line\".to_string(),
+]`
+##
+";
+
+    /// Test that a messy specification is formatted in the canonical style.
+    #[test]
+    fn canonical_style() {
+        assert_eq!(format_source(MESSY), FORMATTED);
+    }
+
+    /// Test that formatting already formatted source doesn't change it.
+    #[test]
+    fn idempotent() {
+        assert!(is_formatted(FORMATTED));
+        assert_eq!(format_source(&format_source(MESSY)), FORMATTED);
+    }
+
+    /// Test that the bodies of enum declarations are indented one more level.
+    #[test]
+    fn braced_declarations() {
+        let source =
+            "#M\n-domain-\nenum Color {\nRed\n  Green\n    }\nvar c:Color = Color.Red\n##\n";
+        let formatted = "#M\n    -domain-\n    enum Color {\n        Red\n        Green\n    }\n    var c:Color = Color.Red\n##\n";
+        assert_eq!(format_source(source), formatted);
+    }
+
+    /// Test that block comments are left as written.
+    #[test]
+    fn block_comments() {
+        let source = "#M\n    -machine-\n    $S\n{-- a\n  b --}\n        |e| ^\n##\n";
+        assert_eq!(format_source(source), source);
+    }
+}
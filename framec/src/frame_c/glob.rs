@@ -0,0 +1,227 @@
+//! Expansion of the input paths given to the `framec` command line.
+//!
+//! An input is a file, a directory, which stands for all of the `.frm` files below it, or a
+//! pattern. Patterns support `*` and `?` within a path component and `**` for any number of
+//! directories, e.g. `specs/**/*.frm`, so they also work in shells that don't expand them.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A Frame spec to compile, with its path relative to the directory the input started from.
+/// Generated files are written to the same relative path in the output directory.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Input {
+    pub path: PathBuf,
+    pub relative_path: PathBuf,
+}
+
+/// Expand an input path into the specs it stands for, in order of their paths.
+pub fn expand(input: &str) -> Result<Vec<Input>, String> {
+    let mut inputs = Vec::new();
+    let path = Path::new(input);
+    if !is_pattern(input) {
+        if path.is_dir() {
+            find_specs(path, PathBuf::new(), &mut inputs)?;
+        } else {
+            let relative_path = path.file_name().map(PathBuf::from).unwrap_or_default();
+            inputs.push(Input {
+                path: path.to_path_buf(),
+                relative_path,
+            });
+        }
+        return Ok(inputs);
+    }
+
+    // split the pattern into the directory it starts from and the components to match
+    let mut base = PathBuf::new();
+    let mut patterns = Vec::new();
+    for component in path.components() {
+        let component_str = component.as_os_str().to_string_lossy().to_string();
+        if patterns.is_empty() && !is_pattern(&component_str) {
+            base.push(component);
+        } else if let Component::Normal(_) = component {
+            patterns.push(component_str);
+        } else {
+            return Err(format!("Unsupported pattern `{}`", input));
+        }
+    }
+    let dir = if base.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        base.clone()
+    };
+    match_patterns(&dir, &base, PathBuf::new(), &patterns, &mut inputs)?;
+
+    if inputs.is_empty() {
+        return Err(format!("No files match `{}`", input));
+    }
+    inputs.sort();
+    inputs.dedup();
+    Ok(inputs)
+}
+
+fn is_pattern(s: &str) -> bool {
+    s.contains('*') || s.contains('?')
+}
+
+/// Match the entries of `dir` against the remaining pattern components. `path` is the path of
+/// `dir` as it should be reported, and `relative_path` the part of it matched by patterns.
+fn match_patterns(
+    dir: &Path,
+    path: &Path,
+    relative_path: PathBuf,
+    patterns: &[String],
+    inputs: &mut Vec<Input>,
+) -> Result<(), String> {
+    let (pattern, rest) = match patterns.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+
+    if pattern == "**" {
+        // match zero directories, then any subdirectory with the same pattern
+        match_patterns(dir, path, relative_path.clone(), rest, inputs)?;
+        for (name, entry_path) in read_dir(dir)? {
+            if entry_path.is_dir() {
+                match_patterns(
+                    &entry_path,
+                    &path.join(&name),
+                    relative_path.join(&name),
+                    patterns,
+                    inputs,
+                )?;
+            }
+        }
+        return Ok(());
+    }
+
+    for (name, entry_path) in read_dir(dir)? {
+        if !wildcard_match(pattern, &name) {
+            continue;
+        }
+        if rest.is_empty() {
+            if entry_path.is_file() {
+                inputs.push(Input {
+                    path: path.join(&name),
+                    relative_path: relative_path.join(&name),
+                });
+            }
+        } else if entry_path.is_dir() {
+            match_patterns(
+                &entry_path,
+                &path.join(&name),
+                relative_path.join(&name),
+                rest,
+                inputs,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Collect all `.frm` files below `dir`.
+fn find_specs(dir: &Path, relative_path: PathBuf, inputs: &mut Vec<Input>) -> Result<(), String> {
+    for (name, entry_path) in read_dir(dir)? {
+        if entry_path.is_dir() {
+            find_specs(&entry_path, relative_path.join(&name), inputs)?;
+        } else if entry_path.extension().unwrap_or_default() == "frm" {
+            inputs.push(Input {
+                path: entry_path,
+                relative_path: relative_path.join(&name),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The names and paths of the entries of a directory, sorted by name.
+fn read_dir(dir: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("Error reading {:?}: {}", dir, err))?;
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Error reading {:?}: {}", dir, err))?;
+        names.push((
+            entry.file_name().to_string_lossy().to_string(),
+            entry.path(),
+        ));
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Match a name against a pattern where `*` matches any sequence of characters and `?` matches
+/// any one character.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` in the pattern, and the position in the name it matched up to
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // let the last `*` match one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.frm", "lamp.frm"));
+        assert!(wildcard_match("l?mp*", "lamp.frm"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("*.frm", "lamp.frmi"));
+        assert!(!wildcard_match("?", ""));
+    }
+
+    /// Test that files, directories and patterns expand to the specs they stand for, with their
+    /// paths relative to where the input started.
+    #[test]
+    fn expand_inputs() {
+        let dir = std::env::temp_dir().join(format!("framec_glob_{}", std::process::id()));
+        for file in ["a.frm", "b.txt", "sub/c.frm", "sub/deep/d.frm"] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let relative_paths = |input: &Path| -> Vec<String> {
+            expand(input.to_str().unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|input| input.relative_path.to_string_lossy().replace('\\', "/"))
+                .collect()
+        };
+
+        assert_eq!(relative_paths(&dir.join("sub/c.frm")), ["c.frm"]);
+        assert_eq!(
+            relative_paths(&dir),
+            ["a.frm", "sub/c.frm", "sub/deep/d.frm"]
+        );
+        assert_eq!(relative_paths(&dir.join("*.frm")), ["a.frm"]);
+        assert_eq!(
+            relative_paths(&dir.join("**/*.frm")),
+            ["a.frm", "sub/c.frm", "sub/deep/d.frm"]
+        );
+        assert_eq!(relative_paths(&dir.join("s*/*/?.frm")), ["sub/deep/d.frm"]);
+        let inputs = expand(dir.join("sub/**/d.frm").to_str().unwrap()).unwrap();
+        assert_eq!(inputs[0].path, dir.join("sub/deep/d.frm"));
+        assert!(expand(dir.join("*.cpp").to_str().unwrap()).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,512 @@
+//! HTML documentation of machines, generated from their intermediate representation.
+//!
+//! The page of a machine documents its interface, states and event handlers, actions, domain and
+//! enums, with the comments of the spec carried by the IR as descriptions. It has a table of the
+//! events handled by each state, and a diagram of the states and transitions. The diagram is a
+//! Graphviz graph drawn in the browser by viz.js; its source is shown instead if the script can't
+//! be loaded, e.g. offline. An index page links the pages of several machines.
+//!
+//! Pages are self-contained and named after their machine, see [page_file_name], so that they
+//! can be published next to the API documentation of the generated code.
+
+use crate::frame_c::ir::{HandlerModel, MachineModel, ParamModel, TransitionModel};
+
+/// The script drawing the diagrams.
+const VIZ_JS: &str = "https://cdn.jsdelivr.net/npm/@viz-js/viz@3.2.4/lib/viz-standalone.js";
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f4f4f4; }
+code, pre { font-family: monospace; }
+.meta { color: #777; font-size: 0.9em; }
+.diagram pre { background: #f8f8f8; padding: 1em; overflow: auto; }
+";
+
+/// The name of the file of the page of a machine, e.g. `Lamp.html`.
+pub fn page_file_name(model: &MachineModel) -> String {
+    format!("{}.html", model.name)
+}
+
+/// Render the documentation page of a machine.
+pub fn render(model: &MachineModel) -> String {
+    let mut html = String::new();
+    open_page(&mut html, &model.name);
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&model.name)));
+    push_doc(&mut html, &model.doc);
+    let mut meta = escape(&model.framec_version);
+    if let Some(source) = &model.source {
+        meta.push_str(&format!(" &middot; <code>{}</code>", escape(source)));
+    }
+    html.push_str(&format!("<p class=\"meta\">{}</p>\n", meta));
+
+    if !model.states.is_empty() {
+        html.push_str("<h2 id=\"diagram\">Diagram</h2>\n");
+        html.push_str(&format!(
+            "<div class=\"diagram\"><pre>{}</pre></div>\n",
+            escape(&dot(model))
+        ));
+    }
+
+    if !model.interface.is_empty() {
+        html.push_str("<h2 id=\"interface\">Interface</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Method</th><th>Parameters</th><th>Returns</th><th>Description</th></tr>\n",
+        );
+        for method in &model.interface {
+            html.push_str(&format!(
+                "<tr id=\"method-{}\"><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&method.name),
+                escape(&method.name),
+                params(&method.params),
+                code_opt(&method.return_type),
+                doc_cell(&method.doc)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !model.states.is_empty() {
+        html.push_str("<h2 id=\"states\">States</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>State</th><th>Parent</th><th>Parameters</th><th>Variables</th><th>Description</th></tr>\n",
+        );
+        for state in &model.states {
+            let vars: Vec<String> = state
+                .vars
+                .iter()
+                .map(|var| variable(&var.name, &var.type_opt, &var.value))
+                .collect();
+            html.push_str(&format!(
+                "<tr><td>{}{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                state_link(&state.name),
+                if model.initial_state.as_ref() == Some(&state.name) {
+                    " (initial)"
+                } else {
+                    ""
+                },
+                state.parent.as_deref().map(state_link).unwrap_or_default(),
+                params(&state.params),
+                vars.join("<br>"),
+                doc_cell(&state.doc)
+            ));
+        }
+        html.push_str("</table>\n");
+        push_event_table(&mut html, model);
+        for state in &model.states {
+            push_state(&mut html, &state.name, &state.doc, &state.handlers);
+        }
+    }
+
+    if !model.actions.is_empty() {
+        html.push_str("<h2 id=\"actions\">Actions</h2>\n<table>\n");
+        html.push_str("<tr><th>Action</th><th>Parameters</th><th>Returns</th></tr>\n");
+        for action in &model.actions {
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                escape(&action.name),
+                params(&action.params),
+                code_opt(&action.return_type)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !model.domain.is_empty() {
+        html.push_str("<h2 id=\"domain\">Domain</h2>\n<table>\n");
+        html.push_str("<tr><th>Variable</th><th>Type</th><th>Initial value</th></tr>\n");
+        for var in &model.domain {
+            html.push_str(&format!(
+                "<tr><td><code>{}{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                if var.constant { "const " } else { "" },
+                escape(&var.name),
+                code_opt(&var.type_opt),
+                code_opt(&var.value)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !model.enums.is_empty() {
+        html.push_str("<h2 id=\"enums\">Enums</h2>\n<table>\n");
+        html.push_str("<tr><th>Enum</th><th>Enumerators</th></tr>\n");
+        for enum_model in &model.enums {
+            let enumerators: Vec<String> = enum_model
+                .enumerators
+                .iter()
+                .map(|enumerator| format!("<code>{}</code>", escape(enumerator)))
+                .collect();
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td></tr>\n",
+                escape(&enum_model.name),
+                enumerators.join(", ")
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !model.states.is_empty() {
+        html.push_str(&format!("<script src=\"{}\"></script>\n", VIZ_JS));
+        html.push_str(
+            "<script>\n\
+             if (typeof Viz !== \"undefined\") {\n\
+             \x20 Viz.instance().then(function (viz) {\n\
+             \x20   document.querySelectorAll(\".diagram\").forEach(function (diagram) {\n\
+             \x20     var svg = viz.renderSVGElement(diagram.textContent);\n\
+             \x20     diagram.replaceChildren(svg);\n\
+             \x20   });\n\
+             \x20 });\n\
+             }\n\
+             </script>\n",
+        );
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Render an index page linking the pages of the given machines, with the first paragraph of
+/// their doc.
+pub fn render_index(models: &[MachineModel]) -> String {
+    let mut html = String::new();
+    open_page(&mut html, "State machines");
+    html.push_str("<h1>State machines</h1>\n<table>\n");
+    html.push_str("<tr><th>Machine</th><th>Description</th></tr>\n");
+    for model in models {
+        let summary = model
+            .doc
+            .as_deref()
+            .and_then(|doc| doc.split("\n\n").next())
+            .map(escape)
+            .unwrap_or_default();
+        html.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td></tr>\n",
+            escape(&page_file_name(model)),
+            escape(&model.name),
+            summary
+        ));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+fn open_page(html: &mut String, title: &str) {
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(title)));
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+}
+
+/// The table of the events handled by each state, with the targets of their transitions. Events
+/// handled without a transition are checked. Events are those of the interface, followed by the
+/// others handled by the states in the order of the spec.
+fn push_event_table(html: &mut String, model: &MachineModel) {
+    let mut events: Vec<&str> = model
+        .interface
+        .iter()
+        .map(|method| method.name.as_str())
+        .collect();
+    for handler in model.states.iter().flat_map(|state| &state.handlers) {
+        if !events.contains(&handler.event.as_str()) {
+            events.push(&handler.event);
+        }
+    }
+    if events.is_empty() {
+        return;
+    }
+
+    html.push_str("<h2 id=\"events\">Events</h2>\n<table>\n<tr><th>State</th>");
+    for event in &events {
+        html.push_str(&format!("<th><code>|{}|</code></th>", escape(event)));
+    }
+    html.push_str("</tr>\n");
+    for state in &model.states {
+        html.push_str(&format!("<tr><td>{}</td>", state_link(&state.name)));
+        for event in &events {
+            let cell = match state
+                .handlers
+                .iter()
+                .find(|handler| handler.event == *event)
+            {
+                Some(handler) if handler.transitions.is_empty() => String::from("&check;"),
+                Some(handler) => {
+                    let targets: Vec<String> =
+                        handler.transitions.iter().map(transition_target).collect();
+                    targets.join("<br>")
+                }
+                None => String::new(),
+            };
+            html.push_str(&format!("<td>{}</td>", cell));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+/// The section of a state, with a table of its event handlers.
+fn push_state(html: &mut String, name: &str, doc: &Option<String>, handlers: &[HandlerModel]) {
+    html.push_str(&format!(
+        "<h3 id=\"state-{}\"><code>${}</code></h3>\n",
+        escape(name),
+        escape(name)
+    ));
+    push_doc(html, doc);
+    if handlers.is_empty() {
+        return;
+    }
+    html.push_str("<table>\n<tr><th>Event</th><th>Parameters</th><th>Returns</th><th>Transitions</th><th>Description</th></tr>\n");
+    for handler in handlers {
+        let transitions: Vec<String> = handler
+            .transitions
+            .iter()
+            .map(|transition| {
+                let mut text = transition_target(transition);
+                if let Some(label) = &transition.label {
+                    text.push_str(&format!(" &ldquo;{}&rdquo;", escape(label)));
+                }
+                if let Some(guard) = &transition.guard {
+                    text.push_str(&format!(" when <code>{}</code>", escape(guard)));
+                }
+                text
+            })
+            .collect();
+        html.push_str(&format!(
+            "<tr><td><code>|{}|</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&handler.event),
+            params(&handler.params),
+            code_opt(&handler.return_type),
+            transitions.join("<br>"),
+            doc_cell(&handler.doc)
+        ));
+    }
+    html.push_str("</table>\n");
+}
+
+/// The Graphviz graph of the states and transitions of a machine. The children of a state are
+/// drawn in a cluster with it. This is also the source of the diagrams rendered by `frame_build`.
+pub fn dot(model: &MachineModel) -> String {
+    let mut graph = String::from("digraph {\n");
+    graph.push_str("  node [shape=box, style=rounded, fontname=\"sans-serif\"];\n");
+    graph.push_str("  edge [fontname=\"sans-serif\", fontsize=10];\n");
+    if let Some(initial_state) = &model.initial_state {
+        graph.push_str("  \"$start\" [shape=point, label=\"\"];\n");
+        graph.push_str(&format!("  \"$start\" -> {};\n", dot_id(initial_state)));
+    }
+    for state in model.states.iter().filter(|state| state.parent.is_none()) {
+        push_dot_state(&mut graph, model, &state.name, 1);
+    }
+    let mut history = false;
+    for state in &model.states {
+        for handler in &state.handlers {
+            for transition in &handler.transitions {
+                let target = match &transition.target {
+                    Some(target) => dot_id(target),
+                    None => {
+                        history = true;
+                        String::from("\"$history\"")
+                    }
+                };
+                let mut label = handler.event.clone();
+                if let Some(guard) = &transition.guard {
+                    label.push_str(&format!(" [{}]", guard));
+                }
+                if let Some(transition_label) = &transition.label {
+                    label.push_str(&format!(" / {}", transition_label));
+                }
+                graph.push_str(&format!(
+                    "  {} -> {} [label={}{}];\n",
+                    dot_id(&state.name),
+                    target,
+                    dot_id(&label),
+                    if transition.change_state {
+                        ", style=dashed"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+        }
+    }
+    if history {
+        graph.push_str("  \"$history\" [shape=circle, label=\"H\"];\n");
+    }
+    graph.push_str("}\n");
+    graph
+}
+
+fn push_dot_state(graph: &mut String, model: &MachineModel, name: &str, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let children: Vec<&str> = model
+        .states
+        .iter()
+        .filter(|state| state.parent.as_deref() == Some(name))
+        .map(|state| state.name.as_str())
+        .collect();
+    if children.is_empty() {
+        graph.push_str(&format!("{}{};\n", indent, dot_id(name)));
+        return;
+    }
+    graph.push_str(&format!(
+        "{}subgraph {} {{\n{}  label={};\n{}  {};\n",
+        indent,
+        dot_id(&format!("cluster_{}", name)),
+        indent,
+        dot_id(name),
+        indent,
+        dot_id(name)
+    ));
+    for child in children {
+        push_dot_state(graph, model, child, depth + 1);
+    }
+    graph.push_str(&format!("{}}}\n", indent));
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn transition_target(transition: &TransitionModel) -> String {
+    let arrow = if transition.change_state {
+        "-&gt;&gt;"
+    } else {
+        "-&gt;"
+    };
+    match &transition.target {
+        Some(target) => format!("{} {}", arrow, state_link(target)),
+        None => format!("{} <code>$$[-]</code>", arrow),
+    }
+}
+
+fn state_link(name: &str) -> String {
+    format!(
+        "<a href=\"#state-{}\"><code>${}</code></a>",
+        escape(name),
+        escape(name)
+    )
+}
+
+fn params(params: &[ParamModel]) -> String {
+    params
+        .iter()
+        .map(|param| variable(&param.name, &param.type_opt, &param.default))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// A parameter or variable, e.g. `level: u8 = 1`.
+fn variable(name: &str, type_opt: &Option<String>, value: &Option<String>) -> String {
+    let mut text = escape(name);
+    if let Some(type_name) = type_opt {
+        text.push_str(&format!(": {}", escape(type_name)));
+    }
+    if let Some(value) = value {
+        text.push_str(&format!(" = {}", escape(value)));
+    }
+    format!("<code>{}</code>", text)
+}
+
+fn code_opt(text: &Option<String>) -> String {
+    text.as_deref()
+        .map(|text| format!("<code>{}</code>", escape(text)))
+        .unwrap_or_default()
+}
+
+/// A doc in a table cell, with its paragraphs on separate lines.
+fn doc_cell(doc: &Option<String>) -> String {
+    doc.as_deref()
+        .map(|doc| {
+            doc.split("\n\n")
+                .map(escape)
+                .collect::<Vec<_>>()
+                .join("<br><br>")
+        })
+        .unwrap_or_default()
+}
+
+/// A doc as paragraphs, separated by blank lines.
+fn push_doc(html: &mut String, doc: &Option<String>) {
+    for paragraph in doc.iter().flat_map(|doc| doc.split("\n\n")) {
+        html.push_str(&format!("<p>{}</p>\n", escape(paragraph)));
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const SPEC: &str = "\
+--- A lamp.
+#Lamp
+    -interface-
+    --- Turns the lamp on, if <off>.
+    turnOn [level:u8 = 1]
+    turnOff
+    -machine-
+    $Off
+        |turnOn| [level:u8] -> \"on\" $On(level) ^
+    --- Lit.
+    $On [level:u8]
+        |>| ^
+        |turnOff| -> $Off ^
+    -domain-
+    var count:u32 = 0
+##
+";
+
+    fn model() -> MachineModel {
+        let ir = Exe::new()
+            .run(&None, None, SPEC.to_string(), Some(TargetLanguage::Ir))
+            .unwrap();
+        MachineModel::from_json(&ir).unwrap()
+    }
+
+    /// Test that the page documents the interface, states, handlers and domain, with the
+    /// comments of the spec escaped.
+    #[test]
+    fn documents_machines() {
+        let html = render(&model());
+        assert!(html.contains("<title>Lamp</title>"));
+        assert!(html.contains("<p>A lamp.</p>"));
+        assert!(html.contains(
+            "<td><code>turnOn</code></td><td><code>level: u8 = 1</code></td><td></td><td>Turns the lamp on, if &lt;off&gt;.</td>"
+        ));
+        assert!(html.contains("<h3 id=\"state-On\"><code>$On</code></h3>\n<p>Lit.</p>"));
+        assert!(html.contains("-&gt; <a href=\"#state-On\"><code>$On</code></a> &ldquo;on&rdquo;"));
+        assert!(html.contains(
+            "<td><code>count</code></td><td><code>u32</code></td><td><code>0</code></td>"
+        ));
+        // the events of the interface come first in the table of events
+        assert!(html.contains("<th><code>|turnOn|</code></th><th><code>|turnOff|</code></th><th><code>|&gt;|</code></th>"));
+    }
+
+    /// Test the graph of the diagram, which is escaped in the page.
+    #[test]
+    fn draws_diagrams() {
+        let graph = dot(&model());
+        assert!(graph.contains("  \"$start\" -> \"Off\";\n"));
+        assert!(graph.contains("  \"Off\" -> \"On\" [label=\"turnOn / on\"];\n"));
+        assert!(graph.contains("  \"On\" -> \"Off\" [label=\"turnOff\"];\n"));
+        assert!(render(&model()).contains("&quot;Off&quot; -&gt; &quot;On&quot;"));
+    }
+
+    #[test]
+    fn indexes_machines() {
+        let html = render_index(&[model()]);
+        assert!(html.contains("<tr><td><a href=\"Lamp.html\">Lamp</a></td><td>A lamp.</td></tr>"));
+    }
+}
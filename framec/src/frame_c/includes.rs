@@ -0,0 +1,239 @@
+//! Expansion of `#include "path"` directives.
+//!
+//! Includes are resolved on the token stream, before parsing: the tokens of the included file
+//! are spliced in place of the directive, so every backend sees a single specification. Paths
+//! are resolved relative to the directory of the including file, or to the current directory
+//! when the specification was read from stdin. Included files may themselves include other
+//! files; cycles are reported as errors.
+//!
+//! Token offsets of included files are shifted into a single virtual address space, which the
+//! [`SourceMap`] translates back into a file and a file-local offset when reporting diagnostics.
+
+use crate::frame_c::diagnostics::{Diagnostic, Span};
+use crate::frame_c::scanner::{Scanner, Token, TokenType};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A source file that contributed tokens to the specification.
+pub(crate) struct SourceFile {
+    pub path: Option<String>,
+    pub content: String,
+    /// Offset of the first byte of this file in the virtual address space.
+    pub base: usize,
+}
+
+/// Maps token offsets back to the source file they came from.
+pub(crate) struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new(path: Option<&str>, content: &str) -> SourceMap {
+        SourceMap {
+            files: vec![SourceFile {
+                path: path.map(|p| p.to_string()),
+                content: content.to_string(),
+                base: 0,
+            }],
+        }
+    }
+
+    fn add_file(&mut self, path: &Path, content: &str) -> usize {
+        let last = self.files.last().unwrap();
+        // leave a gap so an offset at the very end of a file is not mistaken for the next one
+        let base = last.base + last.content.len() + 1;
+        self.files.push(SourceFile {
+            path: Some(path.to_string_lossy().to_string()),
+            content: content.to_string(),
+            base,
+        });
+        base
+    }
+
+    /// Attach the file path and a file-local span and column to the diagnostic.
+    pub fn locate(&self, diagnostic: &mut Diagnostic) {
+        let file = match diagnostic.span {
+            Some(span) => self.file_of(span),
+            None => &self.files[0],
+        };
+        if let Some(span) = &mut diagnostic.span {
+            span.start -= file.base;
+        }
+        diagnostic.file = file.path.clone();
+        diagnostic.resolve_column(&file.content);
+    }
+
+    /// The path of the file a span lies in, and the span relative to the start of that file.
+    pub fn locate_span(&self, span: Span) -> (Option<String>, Span) {
+        let file = self.file_of(span);
+        let local_span = Span {
+            start: span.start - file.base,
+            length: span.length,
+        };
+        (file.path.clone(), local_span)
+    }
+
+    fn file_of(&self, span: Span) -> &SourceFile {
+        self.files
+            .iter()
+            .rev()
+            .find(|file| file.base <= span.start)
+            .unwrap_or(&self.files[0])
+    }
+
+    pub fn locate_all(&self, mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        for diagnostic in &mut diagnostics {
+            self.locate(diagnostic);
+        }
+        diagnostics
+    }
+
+    /// The paths of the included files, in the order they were included.
+    pub fn included_files(&self) -> Vec<PathBuf> {
+        self.files[1..]
+            .iter()
+            .map(|file| PathBuf::from(file.path.as_ref().unwrap()))
+            .collect()
+    }
+
+    /// Whether the included files still have the contents they had when they were included.
+    pub fn includes_unchanged(&self) -> bool {
+        self.files[1..].iter().all(|file| {
+            let path = file.path.as_ref().unwrap();
+            fs::read_to_string(path).map_or(false, |content| content == file.content)
+        })
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+pub(crate) struct IncludeExpander<'a> {
+    source_map: &'a mut SourceMap,
+    diagnostics: Vec<Diagnostic>,
+    // canonical paths of the files currently being expanded, used to detect cycles
+    include_stack: Vec<PathBuf>,
+}
+
+impl<'a> IncludeExpander<'a> {
+    pub fn new(source_map: &'a mut SourceMap) -> IncludeExpander<'a> {
+        IncludeExpander {
+            source_map,
+            diagnostics: Vec::new(),
+            include_stack: Vec::new(),
+        }
+    }
+
+    /// Replace every `#include "path"` directive in `tokens` with the tokens of the named file.
+    /// `root_path` is the path of the specification the tokens were scanned from, if any.
+    pub fn expand(
+        mut self,
+        tokens: Vec<Token>,
+        root_path: Option<&Path>,
+    ) -> Result<Vec<Token>, Vec<Diagnostic>> {
+        if let Some(canonical) = root_path.and_then(|path| fs::canonicalize(path).ok()) {
+            self.include_stack.push(canonical);
+        }
+        let tokens = self.expand_tokens(tokens, root_path);
+        if self.diagnostics.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn expand_tokens(&mut self, tokens: Vec<Token>, including_path: Option<&Path>) -> Vec<Token> {
+        let mut expanded = Vec::with_capacity(tokens.len());
+        let mut iter = tokens.into_iter().peekable();
+        while let Some(token) = iter.next() {
+            if token.token_type != TokenType::Include {
+                expanded.push(token);
+                continue;
+            }
+            match iter.peek() {
+                Some(path_token) if path_token.token_type == TokenType::String => {
+                    let path_token = iter.next().unwrap();
+                    let included = self.include_file(&path_token, including_path);
+                    expanded.extend(included);
+                }
+                _ => {
+                    self.error(&token, "Expected a quoted file path after #include.");
+                }
+            }
+        }
+        expanded
+    }
+
+    fn include_file(&mut self, path_token: &Token, including_path: Option<&Path>) -> Vec<Token> {
+        let path = IncludeExpander::resolve_path(&path_token.lexeme, including_path);
+        let canonical = match fs::canonicalize(&path) {
+            Ok(canonical) => canonical,
+            Err(err) => {
+                let msg = format!("Could not include \"{}\": {}.", path.display(), err);
+                self.error(path_token, &msg);
+                return Vec::new();
+            }
+        };
+        if self.include_stack.contains(&canonical) {
+            let mut cycle: Vec<String> = self
+                .include_stack
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            cycle.push(canonical.display().to_string());
+            let msg = format!("Include cycle detected: {}.", cycle.join(" -> "));
+            self.error(path_token, &msg);
+            return Vec::new();
+        }
+        let content = match fs::read_to_string(&canonical) {
+            Ok(content) => content,
+            Err(err) => {
+                let msg = format!("Could not read \"{}\": {}.", path.display(), err);
+                self.error(path_token, &msg);
+                return Vec::new();
+            }
+        };
+
+        let base = self.source_map.add_file(&path, &content);
+        let (has_errors, _errors, diagnostics, mut tokens) = Scanner::new(content).scan_tokens();
+        if has_errors {
+            for mut diagnostic in diagnostics {
+                if let Some(span) = &mut diagnostic.span {
+                    span.start += base;
+                }
+                self.diagnostics.push(diagnostic);
+            }
+            return Vec::new();
+        }
+
+        // drop the Eof token of the included file and move offsets into the shared space
+        tokens.pop();
+        for token in &mut tokens {
+            token.start += base;
+        }
+
+        self.include_stack.push(canonical);
+        let tokens = self.expand_tokens(tokens, Some(&path));
+        self.include_stack.pop();
+        tokens
+    }
+
+    fn resolve_path(path_str: &str, including_path: Option<&Path>) -> PathBuf {
+        let path = Path::new(path_str);
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        match including_path.and_then(|including| including.parent()) {
+            Some(dir) => dir.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+
+    fn error(&mut self, token: &Token, message: &str) {
+        self.diagnostics.push(
+            Diagnostic::error("include-error", message)
+                .with_line(token.line)
+                .with_span(token.start, token.length)
+                .with_lexeme(&token.lexeme),
+        );
+    }
+}
@@ -0,0 +1,605 @@
+//! The intermediate representation (IR) of machines, for code generators and analyzers outside
+//! of framec.
+//!
+//! The IR is the model of a machine that the template backend renders, and the `ir` target
+//! emits it as JSON, e.g. with `framec -l ir lamp.frm`. Unlike the AST, it is a stable
+//! interface: it is versioned by `IR_VERSION`, which is part of the IR as `ir_version`, and
+//! tools written against a version keep working with later releases of framec that generate
+//! the same version.
+//!
+//! Within a version, fields are only added, so readers should ignore the fields they don't
+//! know, as `MachineModel::from_json` does. Removing or renaming a field, or changing its
+//! meaning, makes a new version.
+//!
+//! Names are those of the spec. Types, default values and expressions are the text of the
+//! spec, in Frame syntax.
+//!
+//! The machine, its interface methods, states and event handlers carry the comments written on
+//! the lines just before them in the spec as their `doc`, e.g. `Waits for a coin.` for
+//! `--- Waits for a coin.`, for tools that document machines.
+
+use crate::frame_c::ast::{
+    ActionNode, ChangeStateStatementNode, EventHandlerNode, MessageType, ParameterNode,
+    StateContextType, StateNode, SystemNode, TerminatorType, TransitionStatementNode,
+    VariableDeclNode,
+};
+use crate::frame_c::scanner::{Token, TokenType};
+use crate::frame_c::visitors::edge_labels::{self, EdgeStatement};
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+/// The version of the IR generated by this version of framec.
+pub const IR_VERSION: u32 = 1;
+
+/// The intermediate representation of a machine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineModel {
+    /// The version of the IR, `IR_VERSION` when generated by this version of framec.
+    pub ir_version: u32,
+    /// The version of framec, as in the first line of generated code, e.g.
+    /// `emitted from framec_v0.8.0`.
+    pub framec_version: String,
+    /// The path of the spec, if it was read from a file.
+    pub source: Option<String>,
+    /// The name of the machine, e.g. `Lamp`.
+    pub name: String,
+    /// The comment before the machine, if any.
+    pub doc: Option<String>,
+    /// The methods of the interface of the machine.
+    pub interface: Vec<MethodModel>,
+    /// The states of the machine, in the order of the spec.
+    pub states: Vec<StateModel>,
+    /// The name of the initial state, the first state of the spec.
+    pub initial_state: Option<String>,
+    /// The actions of the machine.
+    pub actions: Vec<ActionModel>,
+    /// The domain variables of the machine.
+    pub domain: Vec<VariableModel>,
+    /// The enums declared in the domain of the machine.
+    pub enums: Vec<EnumModel>,
+}
+
+/// An interface method.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodModel {
+    pub name: String,
+    pub params: Vec<ParamModel>,
+    /// The return type, if the method returns a value.
+    pub return_type: Option<String>,
+    /// The comment before the method, if any.
+    pub doc: Option<String>,
+}
+
+/// An action, with the code of its body if it has one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionModel {
+    pub name: String,
+    pub params: Vec<ParamModel>,
+    /// The return type, if the action returns a value.
+    pub return_type: Option<String>,
+    pub code: Option<String>,
+}
+
+/// A parameter of a method, action, state or event handler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamModel {
+    pub name: String,
+    /// The declared type, if any.
+    #[serde(rename = "type")]
+    pub type_opt: Option<String>,
+    /// The default value, if any, e.g. `1`.
+    pub default: Option<String>,
+}
+
+/// A domain or state variable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariableModel {
+    pub name: String,
+    /// The declared type, if any.
+    #[serde(rename = "type")]
+    pub type_opt: Option<String>,
+    /// The initial value, if any, e.g. `"white"`.
+    pub value: Option<String>,
+    /// Whether the variable is declared with `const`.
+    pub constant: bool,
+}
+
+/// An enum of the domain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumModel {
+    pub name: String,
+    pub enumerators: Vec<String>,
+}
+
+/// A state of the machine.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateModel {
+    pub name: String,
+    /// The parent state events not handled by the state are dispatched to, if any.
+    pub parent: Option<String>,
+    pub params: Vec<ParamModel>,
+    pub vars: Vec<VariableModel>,
+    /// The event handlers of the state, starting with the enter and exit handlers if any.
+    pub handlers: Vec<HandlerModel>,
+    /// The comment before the state, if any.
+    pub doc: Option<String>,
+}
+
+/// An event handler of a state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandlerModel {
+    /// The event handled: a message name, `>` for the enter event, `<` for the exit event or
+    /// `||*` for any event.
+    pub event: String,
+    pub params: Vec<ParamModel>,
+    /// The return type of the event, if it returns a value.
+    pub return_type: Option<String>,
+    /// The transitions and changes of state of the handler, in the order of the spec.
+    pub transitions: Vec<TransitionModel>,
+    /// Whether the handler ends with `:>`, which dispatches the event to the parent state.
+    #[serde(default)]
+    pub continues_to_parent: bool,
+    /// The comment before the handler, if any.
+    pub doc: Option<String>,
+}
+
+/// A transition or change of state of an event handler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionModel {
+    /// Whether this is a change of state (`->>`), which doesn't send exit and enter events.
+    pub change_state: bool,
+    /// The target state, or `None` for the state popped off the state stack.
+    pub target: Option<String>,
+    /// The label of the transition, if any.
+    pub label: Option<String>,
+    /// The conditions of the branches the transition is in, joined by `&&`, if any.
+    pub guard: Option<String>,
+    /// The actions called by the handler before the transition and the actions of the
+    /// transition, e.g. `unlock()`.
+    pub actions: Vec<String>,
+    /// The arguments of the exit event of the current state.
+    pub exit_args: Vec<String>,
+    /// The arguments of the parameters of the target state.
+    pub state_args: Vec<String>,
+    /// The arguments of the enter event of the target state.
+    pub enter_args: Vec<String>,
+    /// Whether the event is forwarded to the target state (`-> =>`).
+    pub forward_event: bool,
+}
+
+impl MachineModel {
+    /// Build the model of the machine of a spec.
+    pub fn new(
+        system_node: &SystemNode,
+        source: Option<&str>,
+        framec_version: &str,
+    ) -> MachineModel {
+        MachineModel::with_comments(system_node, &[], source, framec_version)
+    }
+
+    /// Build the model of the machine of a spec, documented with the comments of the spec.
+    pub fn with_comments(
+        system_node: &SystemNode,
+        comments: &[Token],
+        source: Option<&str>,
+        framec_version: &str,
+    ) -> MachineModel {
+        let interface = match &system_node.interface_block_node_opt {
+            Some(interface_block_node) => interface_block_node
+                .interface_methods
+                .iter()
+                .map(|interface_method_node_rcref| {
+                    let interface_method_node = interface_method_node_rcref.borrow();
+                    MethodModel {
+                        name: interface_method_node.name.clone(),
+                        params: param_models(&interface_method_node.params),
+                        return_type: interface_method_node
+                            .return_type_opt
+                            .as_ref()
+                            .map(|type_node| type_node.get_type_str()),
+                        doc: doc_comment(comments, interface_method_node.line),
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let states: Vec<StateModel> = match &system_node.machine_block_node_opt {
+            Some(machine_block_node) => machine_block_node
+                .states
+                .iter()
+                .map(|state_node_rcref| StateModel::new(&state_node_rcref.borrow(), comments))
+                .collect(),
+            None => Vec::new(),
+        };
+        let actions = match &system_node.actions_block_node_opt {
+            Some(actions_block_node) => actions_block_node
+                .actions
+                .iter()
+                .map(|action_node_rcref| ActionModel::new(&action_node_rcref.borrow()))
+                .collect(),
+            None => Vec::new(),
+        };
+        let (domain, enums) = match &system_node.domain_block_node_opt {
+            Some(domain_block_node) => (
+                domain_block_node
+                    .member_variables
+                    .iter()
+                    .map(|var_decl_node_rcref| VariableModel::new(&var_decl_node_rcref.borrow()))
+                    .collect(),
+                domain_block_node
+                    .enums
+                    .iter()
+                    .map(|enum_decl_node_rcref| {
+                        let enum_decl_node = enum_decl_node_rcref.borrow();
+                        EnumModel {
+                            name: enum_decl_node.name.clone(),
+                            enumerators: enum_decl_node
+                                .enums
+                                .iter()
+                                .map(|enumerator| enumerator.name.clone())
+                                .collect(),
+                        }
+                    })
+                    .collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        MachineModel {
+            ir_version: IR_VERSION,
+            framec_version: framec_version.to_string(),
+            source: source.map(String::from),
+            name: system_node.name.clone(),
+            doc: doc_comment(comments, system_node.start_line),
+            interface,
+            initial_state: states.first().map(|state| state.name.clone()),
+            states,
+            actions,
+            domain,
+            enums,
+        }
+    }
+
+    /// Serialize the IR as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).unwrap();
+        json.push('\n');
+        json
+    }
+
+    /// Deserialize IR serialized as JSON, which must be of version `IR_VERSION`. Fields added
+    /// by later releases of framec are ignored.
+    pub fn from_json(json: &str) -> Result<MachineModel, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| format!("Invalid IR: {}", err))?;
+        match value.get("ir_version").and_then(|version| version.as_u64()) {
+            Some(version) if version == u64::from(IR_VERSION) => {}
+            Some(version) => {
+                return Err(format!(
+                    "IR version {} is not supported, expected version {}",
+                    version, IR_VERSION
+                ))
+            }
+            None => return Err(String::from("Invalid IR: missing ir_version")),
+        }
+        serde_json::from_value(value).map_err(|err| format!("Invalid IR: {}", err))
+    }
+}
+
+impl ActionModel {
+    fn new(action_node: &ActionNode) -> ActionModel {
+        ActionModel {
+            name: action_node.name.clone(),
+            params: param_models(&action_node.params),
+            return_type: action_node
+                .type_opt
+                .as_ref()
+                .map(|type_node| type_node.get_type_str()),
+            code: action_node.code_opt.clone(),
+        }
+    }
+}
+
+impl VariableModel {
+    fn new(var_decl_node: &VariableDeclNode) -> VariableModel {
+        VariableModel {
+            name: var_decl_node.name.clone(),
+            type_opt: var_decl_node
+                .type_opt
+                .as_ref()
+                .map(|type_node| type_node.get_type_str()),
+            value: var_decl_node
+                .initializer_expr_t_opt
+                .as_ref()
+                .map(edge_labels::format_expr),
+            constant: var_decl_node.is_constant,
+        }
+    }
+}
+
+impl StateModel {
+    fn new(state_node: &StateNode, comments: &[Token]) -> StateModel {
+        let handlers = state_node
+            .enter_event_handler_opt
+            .iter()
+            .chain(state_node.exit_event_handler_opt.iter())
+            .chain(
+                state_node
+                    .evt_handlers_rcref
+                    .iter()
+                    .filter(|evt_handler_node_rcref| {
+                        // the enter and exit handlers are also in the list of all handlers
+                        !state_node
+                            .enter_event_handler_opt
+                            .iter()
+                            .chain(state_node.exit_event_handler_opt.iter())
+                            .any(|handler| Rc::ptr_eq(handler, evt_handler_node_rcref))
+                    }),
+            )
+            .map(|evt_handler_node_rcref| {
+                HandlerModel::new(&evt_handler_node_rcref.borrow(), comments)
+            })
+            .collect();
+        StateModel {
+            name: state_node.name.clone(),
+            parent: state_node
+                .dispatch_opt
+                .as_ref()
+                .map(|dispatch_node| dispatch_node.target_state_ref.name.clone()),
+            params: param_models(&state_node.params_opt),
+            vars: state_node
+                .vars_opt
+                .iter()
+                .flatten()
+                .map(|var_decl_node_rcref| VariableModel::new(&var_decl_node_rcref.borrow()))
+                .collect(),
+            handlers,
+            doc: doc_comment(comments, state_node.line),
+        }
+    }
+}
+
+impl HandlerModel {
+    fn new(evt_handler_node: &EventHandlerNode, comments: &[Token]) -> HandlerModel {
+        let event = match &evt_handler_node.msg_t {
+            MessageType::CustomMessage { message_node } => message_node.name.clone(),
+            MessageType::AnyMessage { .. } => String::from("||*"),
+        };
+        let event_symbol = evt_handler_node.event_symbol_rcref.borrow();
+        let params = event_symbol
+            .params_opt
+            .iter()
+            .flatten()
+            .map(|param| ParamModel {
+                name: param.name.clone(),
+                type_opt: param
+                    .param_type_opt
+                    .as_ref()
+                    .map(|type_node| type_node.get_type_str()),
+                default: param.default_opt.as_ref().map(edge_labels::format_literal),
+            })
+            .collect();
+        let return_type = event_symbol
+            .ret_type_opt
+            .as_ref()
+            .map(|type_node| type_node.get_type_str());
+        let transitions = edge_labels::edges(evt_handler_node)
+            .into_iter()
+            .map(|edge| {
+                let mut transition = match edge.statement {
+                    EdgeStatement::Transition(transition_stmt) => {
+                        TransitionModel::transition(transition_stmt)
+                    }
+                    EdgeStatement::ChangeState(change_state_stmt) => {
+                        TransitionModel::change_state(change_state_stmt)
+                    }
+                };
+                transition.guard = edge.annotation.format_guard();
+                transition.actions = edge.annotation.actions;
+                transition
+            })
+            .collect();
+        HandlerModel {
+            event,
+            params,
+            return_type,
+            transitions,
+            continues_to_parent: matches!(
+                evt_handler_node.terminator_node.terminator_type,
+                TerminatorType::Continue
+            ),
+            doc: doc_comment(comments, evt_handler_node.line),
+        }
+    }
+}
+
+impl TransitionModel {
+    fn transition(transition_stmt: &TransitionStatementNode) -> TransitionModel {
+        let mut transition = TransitionModel::to_state(&transition_stmt.target_state_context_t);
+        transition.label = transition_stmt.label_opt.clone();
+        transition.exit_args = format_args(&transition_stmt.exit_args_opt);
+        transition.forward_event = transition_stmt.forward_event;
+        transition
+    }
+
+    fn change_state(change_state_stmt: &ChangeStateStatementNode) -> TransitionModel {
+        let mut transition = TransitionModel::to_state(&change_state_stmt.state_context_t);
+        transition.change_state = true;
+        transition.label = change_state_stmt.label_opt.clone();
+        transition
+    }
+
+    fn to_state(state_context_t: &StateContextType) -> TransitionModel {
+        let mut transition = TransitionModel {
+            change_state: false,
+            target: None,
+            label: None,
+            guard: None,
+            actions: Vec::new(),
+            exit_args: Vec::new(),
+            state_args: Vec::new(),
+            enter_args: Vec::new(),
+            forward_event: false,
+        };
+        if let StateContextType::StateRef { state_context_node } = state_context_t {
+            transition.target = Some(state_context_node.state_ref_node.name.clone());
+            transition.state_args = format_args(&state_context_node.state_ref_args_opt);
+            transition.enter_args = format_args(&state_context_node.enter_args_opt);
+        }
+        transition
+    }
+}
+
+fn param_models(params_opt: &Option<Vec<ParameterNode>>) -> Vec<ParamModel> {
+    params_opt
+        .iter()
+        .flatten()
+        .map(|param| ParamModel {
+            name: param.param_name.clone(),
+            type_opt: param
+                .param_type_opt
+                .as_ref()
+                .map(|type_node| type_node.get_type_str()),
+            default: param.default_opt.as_ref().map(edge_labels::format_literal),
+        })
+        .collect()
+}
+
+/// The comments on the lines just before the given line, joined by newlines, without their
+/// delimiters. Comments are tokens ending on their line, and are in the order of the spec.
+fn doc_comment(comments: &[Token], line: usize) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut expected = line.checked_sub(1)?;
+    for comment in comments
+        .iter()
+        .rev()
+        .skip_while(|comment| comment.line >= line)
+    {
+        if comment.line != expected {
+            break;
+        }
+        let text = match comment.token_type {
+            TokenType::MultiLineComment => comment
+                .lexeme
+                .trim_start_matches("{--")
+                .trim_end_matches("--}"),
+            _ => comment.lexeme.trim_start_matches('-'),
+        };
+        // the lines of the comment are collected in reverse
+        lines.extend(text.lines().rev().map(str::trim));
+        expected = comment.line - comment.lexeme.matches('\n').count() - 1;
+    }
+    let lines: Vec<&str> = lines.into_iter().rev().collect();
+    let start = lines.iter().position(|line| !line.is_empty())?;
+    let end = lines.iter().rposition(|line| !line.is_empty())?;
+    Some(lines[start..=end].join("\n"))
+}
+
+fn format_args(args_opt: &Option<crate::frame_c::ast::ExprListNode>) -> Vec<String> {
+    args_opt
+        .iter()
+        .flat_map(|args| args.exprs_t.iter().map(edge_labels::format_expr))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const SPEC: &str = "\
+#Lamp
+    -interface-
+    turnOn [level:u8 = 1]
+    -machine-
+    $Off
+        |turnOn| [level:u8] -> \"on\" $On(level) ^
+    $On [level:u8]
+##
+";
+
+    fn ir() -> String {
+        Exe::new()
+            .run(&None, None, SPEC.to_string(), Some(TargetLanguage::Ir))
+            .unwrap()
+    }
+
+    /// Test that the IR emitted by the `ir` target reads back into the model of the machine.
+    #[test]
+    fn round_trip() {
+        let model = MachineModel::from_json(&ir()).unwrap();
+        assert_eq!(model.ir_version, IR_VERSION);
+        assert_eq!(model.name, "Lamp");
+        assert_eq!(model.interface[0].params[0].default.as_deref(), Some("1"));
+        let transition = &model.states[0].handlers[0].transitions[0];
+        assert_eq!(transition.target.as_deref(), Some("On"));
+        assert_eq!(transition.label.as_deref(), Some("on"));
+        assert_eq!(transition.state_args, ["level"]);
+        assert_eq!(model.to_json(), ir());
+    }
+
+    /// Test that fields unknown to this version are ignored, and that other versions are
+    /// rejected.
+    #[test]
+    fn versions() {
+        let added_field = ir().replacen("\"name\"", "\"added\": [1, 2],\n  \"name\"", 1);
+        assert_eq!(
+            MachineModel::from_json(&added_field).unwrap(),
+            MachineModel::from_json(&ir()).unwrap()
+        );
+
+        let next_version = ir().replacen(
+            &format!("\"ir_version\": {}", IR_VERSION),
+            &format!("\"ir_version\": {}", IR_VERSION + 1),
+            1,
+        );
+        let err = MachineModel::from_json(&next_version).unwrap_err();
+        assert!(err.contains("is not supported"));
+        assert!(MachineModel::from_json("{}").is_err());
+    }
+
+    /// Test that the comments just before the machine, its methods, states and handlers are
+    /// carried over as their doc.
+    #[test]
+    fn doc_comments() {
+        let spec = "\
+--- A lamp.
+--- It dims.
+#[codegen.rust.features.runtime_support:bool=\"true\"]
+#Lamp
+    -interface-
+    {-- Turns the lamp on
+        at a level. --}
+    turnOn [level:u8 = 1]
+
+    --- Not a doc, as a line separates it from the method.
+
+    turnOff
+    -machine-
+    --- Dark.
+    $Off
+        --- Lights up.
+        |turnOn| [level:u8] -> $On(level) ^
+    $On [level:u8]
+        |turnOff| -> $Off ^
+##
+";
+        let ir = Exe::new()
+            .run(&None, None, spec.to_string(), Some(TargetLanguage::Ir))
+            .unwrap();
+        let model = MachineModel::from_json(&ir).unwrap();
+        assert_eq!(model.doc.as_deref(), Some("A lamp.\nIt dims."));
+        assert_eq!(
+            model.interface[0].doc.as_deref(),
+            Some("Turns the lamp on\nat a level.")
+        );
+        assert_eq!(model.interface[1].doc, None);
+        assert_eq!(model.states[0].doc.as_deref(), Some("Dark."));
+        assert_eq!(
+            model.states[0].handlers[0].doc.as_deref(),
+            Some("Lights up.")
+        );
+        assert_eq!(model.states[1].doc, None);
+        assert_eq!(model.states[1].handlers[0].doc, None);
+    }
+}
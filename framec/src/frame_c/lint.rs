@@ -0,0 +1,366 @@
+//! Configurable style and design checks for Frame specifications.
+//!
+//! The lint pass runs on the AST after a specification has parsed successfully. Each rule has a
+//! name, which is also the `code` of the diagnostics it reports, and a level in
+//! [`LintLevels`](crate::frame_c::config::LintLevels): rules at level `allow` are skipped, rules
+//! at level `warn` report warnings, and rules at level `deny` report errors that fail the
+//! compilation. The rules are:
+//!
+//! * `state_naming` - state names follow the configured naming style,
+//! * `event_naming` - interface method names follow the configured naming style,
+//! * `max_states` - a machine defines no more than the configured number of states,
+//! * `exit_handler_for_state_vars` - a state that holds state variables defines an exit handler,
+//! * `type_mismatch` - expressions match the declared types they are used for, see
+//!   [`typeck`](crate::frame_c::typeck).
+//!
+//! The level of `warnings` then applies to all warnings: `deny` turns them into errors and
+//! `allow` hides them.
+
+use crate::frame_c::ast::{InterfaceMethodNode, StateNode, SystemNode};
+use crate::frame_c::config::{LintConfig, LintLevel, NamingStyle};
+use crate::frame_c::diagnostics::{Diagnostic, Severity};
+use crate::frame_c::typeck;
+use std::fmt;
+
+/// Check a parsed specification against the configured lint rules.
+pub fn lint(system_node: &SystemNode, config: &LintConfig) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        config,
+        diagnostics: Vec::new(),
+    };
+    if let Some(machine_block_node) = &system_node.machine_block_node_opt {
+        for state_node_rcref in &machine_block_node.states {
+            let state_node = state_node_rcref.borrow();
+            linter.check_state_naming(&state_node);
+            linter.check_exit_handler_for_state_vars(&state_node);
+        }
+        linter.check_max_states(&machine_block_node.states);
+    }
+    if let Some(interface_block_node) = &system_node.interface_block_node_opt {
+        for interface_method_rcref in &interface_block_node.interface_methods {
+            linter.check_event_naming(&interface_method_rcref.borrow());
+        }
+    }
+    linter.check_types(system_node);
+    linter.diagnostics
+}
+
+/// Apply the level of `warnings` to the warnings among the diagnostics.
+pub fn apply_warnings_level(diagnostics: &mut Vec<Diagnostic>, level: LintLevel) {
+    match level {
+        LintLevel::Allow => {
+            diagnostics.retain(|diagnostic| diagnostic.severity != Severity::Warning)
+        }
+        LintLevel::Warn => {}
+        LintLevel::Deny => {
+            for diagnostic in diagnostics.iter_mut() {
+                if diagnostic.severity == Severity::Warning {
+                    diagnostic.severity = Severity::Error;
+                }
+            }
+        }
+    }
+}
+
+/// Does any diagnostic fail the compilation?
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+/* --------------------------------------------------------------------- */
+
+struct Linter<'a> {
+    config: &'a LintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn check_state_naming(&mut self, state_node: &StateNode) {
+        let style = self.config.state_naming_style;
+        if !style.matches(&state_node.name) {
+            let message = format!("State ${} is not {}.", state_node.name, style);
+            self.report(
+                self.config.levels.state_naming,
+                "state_naming",
+                &message,
+                state_node,
+            );
+        }
+    }
+
+    fn check_event_naming(&mut self, interface_method_node: &InterfaceMethodNode) {
+        let style = self.config.event_naming_style;
+        if !style.matches(&interface_method_node.name) {
+            let message = format!(
+                "Interface method {} is not {}.",
+                interface_method_node.name, style
+            );
+            if let Some(diagnostic) =
+                self.diagnostic(self.config.levels.event_naming, "event_naming", &message)
+            {
+                let span = interface_method_node.name_span;
+                self.diagnostics.push(
+                    diagnostic
+                        .with_line(interface_method_node.line)
+                        .with_span(span.start, span.length)
+                        .with_lexeme(&interface_method_node.name),
+                );
+            }
+        }
+    }
+
+    fn check_exit_handler_for_state_vars(&mut self, state_node: &StateNode) {
+        let has_state_vars = state_node
+            .vars_opt
+            .as_ref()
+            .map_or(false, |vars| !vars.is_empty());
+        if has_state_vars && state_node.exit_event_handler_opt.is_none() {
+            let message = format!(
+                "State ${} holds state variables but has no exit handler.",
+                state_node.name
+            );
+            self.report(
+                self.config.levels.exit_handler_for_state_vars,
+                "exit_handler_for_state_vars",
+                &message,
+                state_node,
+            );
+        }
+    }
+
+    fn check_max_states(&mut self, states: &[std::rc::Rc<std::cell::RefCell<StateNode>>]) {
+        let max_states = self.config.max_states;
+        if let Some(first_extra_state) = states.get(max_states) {
+            let message = format!(
+                "Machine defines {} states, more than the maximum of {}.",
+                states.len(),
+                max_states
+            );
+            self.report(
+                self.config.levels.max_states,
+                "max_states",
+                &message,
+                &first_extra_state.borrow(),
+            );
+        }
+    }
+
+    fn check_types(&mut self, system_node: &SystemNode) {
+        let severity = match self.config.levels.type_mismatch {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        for mut diagnostic in typeck::check(system_node) {
+            diagnostic.severity = severity;
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // Report a finding located at the name of a state.
+    fn report(&mut self, level: LintLevel, rule: &str, message: &str, state_node: &StateNode) {
+        if let Some(diagnostic) = self.diagnostic(level, rule, message) {
+            let span = state_node.name_span;
+            self.diagnostics.push(
+                diagnostic
+                    .with_line(state_node.line)
+                    .with_span(span.start, span.length)
+                    .with_lexeme(&state_node.name),
+            );
+        }
+    }
+
+    fn diagnostic(&self, level: LintLevel, rule: &str, message: &str) -> Option<Diagnostic> {
+        match level {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Diagnostic::warning(rule, message)),
+            LintLevel::Deny => Some(Diagnostic::error(rule, message)),
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+impl NamingStyle {
+    /// Does the name follow this naming style? Digits are allowed after the first character.
+    pub fn matches(&self, name: &str) -> bool {
+        let first = match name.chars().next() {
+            Some(first) => first,
+            None => return false,
+        };
+        match self {
+            NamingStyle::UpperCamelCase => {
+                first.is_ascii_uppercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            NamingStyle::LowerCamelCase => {
+                first.is_ascii_lowercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            NamingStyle::SnakeCase => {
+                first.is_ascii_lowercase()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+        }
+    }
+}
+
+impl fmt::Display for NamingStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NamingStyle::UpperCamelCase => write!(f, "UpperCamelCase"),
+            NamingStyle::LowerCamelCase => write!(f, "lowerCamelCase"),
+            NamingStyle::SnakeCase => write!(f, "snake_case"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+
+    const SPEC: &str = "#[lint.levels.exit_handler_for_state_vars:str=\"deny\"]
+#Lint
+    -interface-
+    Start
+    -machine-
+    $Idle
+        |Start| -> $running ^
+    $running
+        var count:i32 = 0
+##
+";
+
+    /// Test which names each naming style accepts.
+    #[test]
+    fn naming_conventions() {
+        assert!(NamingStyle::UpperCamelCase.matches("Running2"));
+        assert!(!NamingStyle::UpperCamelCase.matches("running"));
+        assert!(NamingStyle::LowerCamelCase.matches("startNow"));
+        assert!(!NamingStyle::LowerCamelCase.matches("start_now"));
+        assert!(NamingStyle::SnakeCase.matches("start_now"));
+        assert!(!NamingStyle::SnakeCase.matches("StartNow"));
+    }
+
+    /// Test that the warnings of a successful compilation are kept on the compiler.
+    #[test]
+    fn warnings_are_reported() {
+        let spec = SPEC.replacen("deny", "allow", 1);
+        let exe = Exe::new();
+        exe.run(&None, Some("lint.frm"), spec, Some(TargetLanguage::Rust))
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        let warnings = exe.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert_eq!(warnings[0].code, "state_naming");
+        assert_eq!(warnings[0].file.as_deref(), Some("lint.frm"));
+        assert_eq!(warnings[0].line, Some(8));
+        assert_eq!(warnings[0].column, Some(6));
+        assert_eq!(warnings[0].lexeme.as_deref(), Some("running"));
+    }
+
+    /// Test that a denied rule fails the compilation and reports every finding.
+    #[test]
+    fn denied_rules_are_errors() {
+        let err = Exe::new()
+            .run(&None, None, SPEC.to_string(), Some(TargetLanguage::Rust))
+            .unwrap_err();
+        let codes: Vec<_> = err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.code.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                (Severity::Warning, "state_naming"),
+                (Severity::Error, "exit_handler_for_state_vars"),
+            ]
+        );
+    }
+
+    /// Test that rules configured through attributes use their configured limits and styles.
+    #[test]
+    fn configured_rules() {
+        let spec = SPEC
+            .replacen("deny", "allow", 1)
+            .replace(
+                "#Lint",
+                "#[lint.levels.max_states:str=\"warn\"]\n#[lint.max_states:int=\"1\"]\n#[lint.levels.event_naming:str=\"warn\"]\n#Lint",
+            );
+        let exe = Exe::new();
+        exe.run(&None, None, spec, Some(TargetLanguage::Rust))
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        let codes: Vec<_> = exe
+            .warnings()
+            .iter()
+            .map(|diagnostic| diagnostic.code.clone())
+            .collect();
+        assert_eq!(codes, vec!["state_naming", "max_states", "event_naming"]);
+    }
+
+    /// Test that the level of `warnings` applies to the findings of every rule at level `warn`.
+    #[test]
+    fn warnings_level() {
+        let spec = SPEC.replacen("deny", "allow", 1);
+        let denied = format!("#[lint.levels.warnings:str=\"deny\"]\n{}", spec);
+        let err = Exe::new()
+            .run(&None, None, denied, Some(TargetLanguage::Rust))
+            .unwrap_err();
+        assert_eq!(err.diagnostics.len(), 1);
+        assert_eq!(err.diagnostics[0].severity, Severity::Error);
+        assert_eq!(err.diagnostics[0].code, "state_naming");
+
+        let allowed = format!("#[lint.levels.warnings:str=\"allow\"]\n{}", spec);
+        let exe = Exe::new();
+        exe.run(&None, None, allowed, Some(TargetLanguage::Rust))
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        assert!(exe.warnings().is_empty());
+    }
+
+    /// Test that the levels in the compiler options override the attributes, in order.
+    #[test]
+    fn option_levels() {
+        let lint_levels = |levels: &[(&str, LintLevel)]| CompilerOptions {
+            lint_levels: levels
+                .iter()
+                .map(|(name, level)| (name.to_string(), *level))
+                .collect(),
+            ..CompilerOptions::default()
+        };
+        let run = |options: CompilerOptions| {
+            let exe = Exe::with_options(options);
+            let result = exe.run(&None, None, SPEC.to_string(), Some(TargetLanguage::Rust));
+            (result, exe.warnings())
+        };
+
+        let (result, warnings) = run(lint_levels(&[
+            ("exit_handler_for_state_vars", LintLevel::Warn),
+            ("state_naming", LintLevel::Deny),
+            ("state_naming", LintLevel::Allow),
+        ]));
+        assert!(result.is_ok());
+        let codes: Vec<_> = warnings
+            .iter()
+            .map(|warning| warning.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["exit_handler_for_state_vars"]);
+
+        let (result, _) = run(lint_levels(&[
+            ("exit_handler_for_state_vars", LintLevel::Allow),
+            ("warnings", LintLevel::Deny),
+        ]));
+        assert_eq!(result.unwrap_err().diagnostics.len(), 1);
+
+        let (result, _) = run(lint_levels(&[("state_names", LintLevel::Allow)]));
+        assert!(result
+            .unwrap_err()
+            .error
+            .contains("unknown lint rule `state_names`"));
+    }
+}
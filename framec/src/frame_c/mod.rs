@@ -1,9 +1,27 @@
-mod ast;
+pub mod ast;
 pub mod cli;
+pub mod codemap;
+pub mod compat;
 pub mod compiler;
 pub mod config;
+pub mod diagnostics;
+pub mod diff;
+pub mod formatter;
+mod glob;
+pub mod html;
+mod includes;
+pub mod ir;
+pub mod lint;
+pub mod model_checker;
+pub mod optimizer;
 mod parser;
+pub mod scaffold;
 mod scanner;
+pub mod semantic;
+pub mod simulator;
 mod symbol_table;
+pub mod template;
+pub mod test_skeleton;
+pub mod typeck;
 pub mod utils;
 mod visitors;
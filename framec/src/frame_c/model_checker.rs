@@ -0,0 +1,662 @@
+//! The model checker backends, which translate a machine into the input of a model checker so
+//! that properties of the machine, e.g. that a locked door is never opened, can be verified for
+//! every sequence of events. The `tla` target generates a TLA+ specification for TLC or Apalache,
+//! and the `nusmv` target an SMV model for NuSMV or nuXmv.
+//!
+//! The model is an abstraction of the machine, built from its `MachineModel`. Its variables are
+//! the current state of the machine and the last event sent, and every step of the model sends
+//! one of the events of the machine, dispatched to the parent states and followed by the enter
+//! events of the states entered, as the generated code does. Data is not modeled: the branches
+//! of guards are nondeterministic choices, with the conditions as comments, and parameters,
+//! variables, actions and return values are left out.
+//!
+//! The abstractions are reported as diagnostics located at the state they apply to, rather than
+//! silently made. They are notes, as the model may do more than the machine but never less: every
+//! branch of a guard may be taken, transitions to the state popped off the state stack may go to
+//! any state, and a handler with guarded transitions may also leave the state unchanged. A
+//! counterexample found by the model checker may then not be a run of the machine, while a
+//! safety property that holds for the model holds for the machine.
+
+use crate::frame_c::diagnostics::{Diagnostic, Severity};
+use crate::frame_c::ir::{HandlerModel, MachineModel, StateModel};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// The value of the `event` variable before the first event is sent.
+const NO_EVENT: &str = "none";
+
+/// The reserved words of the SMV language, which can't be the names of states or events.
+#[rustfmt::skip]
+const SMV_KEYWORDS: &[&str] = &[
+    "A", "ABF", "ABG", "AF", "AG", "ASSIGN", "AX", "BU", "COMPASSION", "COMPUTE", "CONSTANTS",
+    "CONSTRAINT", "CTLSPEC", "CTLWFF", "DEFINE", "E", "EBF", "EBG", "EF", "EG", "EX", "F",
+    "FAIRNESS", "FALSE", "FROZENVAR", "G", "H", "IN", "INIT", "INVAR", "INVARSPEC", "ISA", "IVAR",
+    "JUSTICE", "LTLSPEC", "LTLWFF", "MAX", "MDEFINE", "MIN", "MIRROR", "MODULE", "NAME", "O",
+    "PRED", "PREDICATES", "PSLSPEC", "PSLWFF", "S", "SIMPWFF", "SPEC", "T", "TRANS", "TRUE", "U",
+    "V", "VAR", "X", "Y", "Z", "abs", "array", "bool", "boolean", "case", "count", "esac",
+    "extend", "in", "init", "integer", "max", "min", "mod", "next", "of", "process", "real",
+    "resize", "self", "signed", "sizeof", "swconst", "union", "unsigned", "uwconst", "word",
+    "word1", "xnor", "xor",
+];
+
+/// Translate a machine into a TLA+ specification, along with the diagnostics of the
+/// abstractions made.
+///
+/// The module is named after the file of the spec, as TLC requires the module of a file to have
+/// the name of the file, or after the machine if the spec wasn't read from a file. `Spec` is the
+/// specification of the machine, and `TypeOK` its type invariant; liveness properties need a
+/// fairness condition on top of `Spec`, e.g. `Spec /\ WF_vars(Next)`.
+pub fn tla(model: &MachineModel) -> (String, Vec<Diagnostic>) {
+    let abstraction = Abstraction::new(model, |name| {
+        if name == NO_EVENT {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    });
+    let quote = |name: &String| format!("\"{}\"", name);
+    let set = |names: &[String]| {
+        let names: Vec<String> = names.iter().map(quote).collect();
+        format!("{{{}}}", names.join(", "))
+    };
+    let module_name = model
+        .source
+        .as_ref()
+        .and_then(|source| Path::new(source).file_stem())
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .filter(|stem| is_identifier(stem))
+        .unwrap_or_else(|| model.name.clone());
+
+    let mut code = String::new();
+    code.push_str(&format!("\\* {}\n", model.framec_version));
+    code.push_str(&format!("---- MODULE {} ----\n", module_name));
+    code.push_str(&format!(
+        "\\* The states of the machine {} and the events sent to it.\n\n",
+        model.name
+    ));
+    code.push_str("VARIABLES state, event\n\n");
+    code.push_str("vars == <<state, event>>\n\n");
+    code.push_str(&format!("States == {}\n\n", set(&abstraction.states)));
+    code.push_str(&format!("Events == {}\n\n", set(&abstraction.events)));
+    code.push_str("TypeOK ==\n");
+    code.push_str("    /\\ state \\in States\n");
+    code.push_str(&format!(
+        "    /\\ event \\in Events \\cup {{\"{}\"}}\n\n",
+        NO_EVENT
+    ));
+    code.push_str("Init ==\n");
+    if let [initial] = abstraction.initial.as_slice() {
+        code.push_str(&format!("    /\\ state = {}\n", quote(initial)));
+    } else {
+        code.push_str(&format!(
+            "    /\\ state \\in {}\n",
+            set(&abstraction.initial)
+        ));
+    }
+    code.push_str(&format!("    /\\ event = \"{}\"\n\n", NO_EVENT));
+
+    for (event, steps) in &abstraction.steps {
+        code.push_str(&format!("Send_{} ==\n", event));
+        code.push_str(&format!("    /\\ event' = {}\n", quote(event)));
+        if steps.is_empty() {
+            code.push_str("    /\\ UNCHANGED state\n\n");
+            continue;
+        }
+        for (i, step) in steps.iter().enumerate() {
+            let next_state = if let [target] = step.targets.as_slice() {
+                format!("state' = {}", quote(target))
+            } else {
+                format!("state' \\in {}", set(&step.targets))
+            };
+            let arm = if i == 0 {
+                "    /\\ CASE"
+            } else {
+                "         []"
+            };
+            code.push_str(&format!(
+                "{} state = {} -> {}{}\n",
+                arm,
+                quote(&step.state),
+                next_state,
+                guards_comment("\\*", &step.guards)
+            ));
+        }
+        code.push_str("         [] OTHER -> UNCHANGED state\n\n");
+    }
+
+    if abstraction.steps.is_empty() {
+        code.push_str("Next == UNCHANGED vars\n\n");
+    } else {
+        code.push_str("Next ==\n");
+        for (event, _) in &abstraction.steps {
+            code.push_str(&format!("    \\/ Send_{}\n", event));
+        }
+        code.push('\n');
+    }
+    code.push_str("Spec == Init /\\ [][Next]_vars\n\n");
+    code.push_str("====\n");
+    (code, abstraction.diagnostics)
+}
+
+/// Translate a machine into an SMV model, along with the diagnostics of the abstractions made.
+///
+/// States and events named after a reserved word of SMV, e.g. `A`, are renamed with a trailing
+/// `_`, e.g. `A_`.
+pub fn nusmv(model: &MachineModel) -> (String, Vec<Diagnostic>) {
+    let abstraction = Abstraction::new(model, |name| {
+        if name == NO_EVENT || SMV_KEYWORDS.contains(&name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    });
+    let value = |names: &[String]| match names {
+        [name] => name.clone(),
+        _ => format!("{{{}}}", names.join(", ")),
+    };
+    let mut events = vec![NO_EVENT.to_string()];
+    events.extend(abstraction.events.iter().cloned());
+
+    let mut code = String::new();
+    code.push_str(&format!("-- {}\n", model.framec_version));
+    code.push_str(&format!(
+        "-- The states of the machine {} and the events sent to it.\n",
+        model.name
+    ));
+    code.push_str("MODULE main\n");
+    code.push_str("VAR\n");
+    code.push_str(&format!(
+        "    state : {{{}}};\n",
+        abstraction.states.join(", ")
+    ));
+    code.push_str(&format!("    event : {{{}}};\n", events.join(", ")));
+    code.push_str("ASSIGN\n");
+    code.push_str(&format!(
+        "    init(state) := {};\n",
+        value(&abstraction.initial)
+    ));
+    code.push_str(&format!("    init(event) := {};\n", NO_EVENT));
+    if abstraction.events.is_empty() {
+        code.push_str(&format!("    next(event) := {};\n", NO_EVENT));
+    } else {
+        code.push_str(&format!(
+            "    next(event) := {};\n",
+            value(&abstraction.events)
+        ));
+    }
+    code.push_str("    next(state) :=\n");
+    code.push_str("        case\n");
+    for (event, steps) in &abstraction.steps {
+        for step in steps {
+            code.push_str(&format!(
+                "            next(event) = {} & state = {} : {};{}\n",
+                event,
+                step.state,
+                value(&step.targets),
+                guards_comment("--", &step.guards)
+            ));
+        }
+    }
+    code.push_str("            TRUE : state;\n");
+    code.push_str("        esac;\n");
+    (code, abstraction.diagnostics)
+}
+
+/// A comment listing the guards of a step, if any.
+fn guards_comment(comment: &str, guards: &[String]) -> String {
+    if guards.is_empty() {
+        String::new()
+    } else {
+        format!(" {} {}", comment, guards.join("; "))
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/* --------------------------------------------------------------------- */
+
+/// The machine as states, events and the steps between them.
+struct Abstraction {
+    states: Vec<String>,
+    events: Vec<String>,
+    /// The states the machine may be in once the enter event of its first state is handled.
+    initial: Vec<String>,
+    /// The steps of each event that may change the state, in the order of the states.
+    steps: Vec<(String, Vec<Step>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The states a machine may be in after an event is sent to it in a state.
+struct Step {
+    state: String,
+    targets: Vec<String>,
+    /// The guards of the handlers the event was dispatched to.
+    guards: Vec<String>,
+}
+
+impl Abstraction {
+    /// Build the abstraction of a machine, with the names of states and events mapped by
+    /// `name`, e.g. to avoid reserved words of the target.
+    fn new(model: &MachineModel, name: impl Fn(&str) -> String) -> Abstraction {
+        let mut diagnostics = Vec::new();
+        let mut states: Vec<String> = model.states.iter().map(|state| name(&state.name)).collect();
+        let mut initial = Vec::new();
+        if let Some(initial_state) = &model.initial_state {
+            initial = Dispatcher::new(model)
+                .enter(initial_state, &mut Vec::new())
+                .iter()
+                .map(|state| name(state))
+                .collect();
+        }
+        if states.is_empty() {
+            diagnostics.push(Diagnostic::warning(
+                "model-empty",
+                &format!(
+                    "The machine {} has no states, so it is modeled with a single `{}` state.",
+                    model.name, NO_EVENT
+                ),
+            ));
+            states.push(NO_EVENT.to_string());
+            initial.push(NO_EVENT.to_string());
+        }
+
+        let mut events: Vec<String> = Vec::new();
+        let handled = model
+            .states
+            .iter()
+            .flat_map(|state| state.handlers.iter().map(|handler| &handler.event));
+        for event in model
+            .interface
+            .iter()
+            .map(|method| &method.name)
+            .chain(handled)
+        {
+            if !matches!(event.as_str(), ">" | "<" | "||*") && !events.contains(event) {
+                events.push(event.clone());
+            }
+        }
+
+        let steps = events
+            .iter()
+            .map(|event| {
+                let steps = model
+                    .states
+                    .iter()
+                    .filter_map(|state| {
+                        let mut guards = Vec::new();
+                        let targets = Dispatcher::new(model).handle(
+                            &state.name,
+                            &state.name,
+                            event,
+                            &mut guards,
+                        );
+                        if targets == [state.name.clone()] {
+                            return None;
+                        }
+                        Some(Step {
+                            state: name(&state.name),
+                            targets: targets.iter().map(|target| name(target)).collect(),
+                            guards,
+                        })
+                    })
+                    .collect();
+                (name(event), steps)
+            })
+            .collect();
+
+        for state in &model.states {
+            diagnostics.extend(state_diagnostics(state));
+        }
+        let mut data = Vec::new();
+        if !model.domain.is_empty() {
+            data.push("domain variables");
+        }
+        if model
+            .states
+            .iter()
+            .any(|state| !state.params.is_empty() || !state.vars.is_empty())
+        {
+            data.push("state parameters and variables");
+        }
+        let events_have_data = model
+            .interface
+            .iter()
+            .any(|method| !method.params.is_empty() || method.return_type.is_some())
+            || model.states.iter().any(|state| {
+                state
+                    .handlers
+                    .iter()
+                    .any(|handler| !handler.params.is_empty() || handler.return_type.is_some())
+            });
+        if events_have_data {
+            data.push("event parameters and return values");
+        }
+        if !model.actions.is_empty() {
+            data.push("actions");
+        }
+        if !data.is_empty() {
+            diagnostics.push(Diagnostic::new(
+                Severity::Note,
+                "model-data",
+                &format!(
+                    "The {} of the machine {} are not modeled.",
+                    join_words(&data),
+                    model.name
+                ),
+            ));
+        }
+
+        Abstraction {
+            states,
+            events: events.iter().map(|event| name(event)).collect(),
+            initial,
+            steps,
+            diagnostics,
+        }
+    }
+}
+
+/// The diagnostics of the abstractions made of the handlers of a state.
+fn state_diagnostics(state: &StateModel) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let located = |diagnostic: Diagnostic| diagnostic.with_lexeme(&state.name);
+    for handler in &state.handlers {
+        let event = match handler.event.as_str() {
+            ">" => String::from("the enter event"),
+            "||*" => String::from("any event"),
+            event => format!("|{}|", event),
+        };
+        let guards: Vec<&String> = handler
+            .transitions
+            .iter()
+            .filter_map(|transition| transition.guard.as_ref())
+            .collect();
+        if !guards.is_empty() {
+            diagnostics.push(located(Diagnostic::new(
+                Severity::Note,
+                "model-guard",
+                &format!(
+                    "The guards of {} of ${} are modeled as a nondeterministic choice.",
+                    event, state.name
+                ),
+            )));
+        }
+        if handler
+            .transitions
+            .iter()
+            .any(|transition| transition.target.is_none())
+        {
+            diagnostics.push(located(Diagnostic::new(
+                Severity::Note,
+                "model-history",
+                &format!(
+                    "The state popped off the state stack by {} of ${} is modeled as any state.",
+                    event, state.name
+                ),
+            )));
+        }
+    }
+    diagnostics
+}
+
+/// Join words into an enumeration, e.g. `a, b and c`.
+fn join_words(words: &[&str]) -> String {
+    match words {
+        [] => String::new(),
+        [word] => word.to_string(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+/// Dispatches events to the handlers of states, as the generated code does.
+struct Dispatcher<'a> {
+    model: &'a MachineModel,
+    /// The states and events being handled, to stop at loops of enter events or forwarded
+    /// events.
+    active: HashSet<(String, String)>,
+}
+
+impl<'a> Dispatcher<'a> {
+    fn new(model: &'a MachineModel) -> Dispatcher<'a> {
+        Dispatcher {
+            model,
+            active: HashSet::new(),
+        }
+    }
+
+    /// The states the machine may be in after it enters a state.
+    fn enter(&mut self, target: &str, guards: &mut Vec<String>) -> Vec<String> {
+        self.handle(target, target, ">", guards)
+    }
+
+    /// The states the machine may be in after an event is dispatched to the handler of `state`
+    /// or of its parent states, when the machine is in `current`.
+    fn handle(
+        &mut self,
+        current: &str,
+        state: &str,
+        event: &str,
+        guards: &mut Vec<String>,
+    ) -> Vec<String> {
+        let key = (state.to_string(), event.to_string());
+        if !self.active.insert(key.clone()) {
+            return vec![current.to_string()];
+        }
+        let targets = match self.find_handler(state, event) {
+            Some((handler_state, handler)) => {
+                self.handle_with(current, handler_state, handler, event, guards)
+            }
+            None => vec![current.to_string()],
+        };
+        self.active.remove(&key);
+        targets
+    }
+
+    /// The state whose handler an event sent to `state` is dispatched to, and the handler.
+    fn find_handler(&self, state: &str, event: &str) -> Option<(&'a StateModel, &'a HandlerModel)> {
+        let mut state_model = self.state(state);
+        while let Some(state) = state_model {
+            let handler = state
+                .handlers
+                .iter()
+                .find(|handler| handler.event == event)
+                .or_else(|| {
+                    if matches!(event, ">" | "<") {
+                        None
+                    } else {
+                        state.handlers.iter().find(|handler| handler.event == "||*")
+                    }
+                });
+            if let Some(handler) = handler {
+                return Some((state, handler));
+            }
+            state_model = state
+                .parent
+                .as_deref()
+                .and_then(|parent| self.state(parent));
+        }
+        None
+    }
+
+    fn handle_with(
+        &mut self,
+        current: &str,
+        handler_state: &StateModel,
+        handler: &HandlerModel,
+        event: &str,
+        guards: &mut Vec<String>,
+    ) -> Vec<String> {
+        let mut targets = Vec::new();
+        let mut unconditional = false;
+        for transition in &handler.transitions {
+            if let Some(guard) = &transition.guard {
+                if !guards.contains(guard) {
+                    guards.push(guard.clone());
+                }
+            } else {
+                unconditional = true;
+            }
+            let states: Vec<String> = match &transition.target {
+                Some(target) => vec![target.clone()],
+                None => self
+                    .model
+                    .states
+                    .iter()
+                    .map(|state| state.name.clone())
+                    .collect(),
+            };
+            for target in states {
+                let entered = if transition.change_state {
+                    vec![target]
+                } else {
+                    self.enter(&target, guards)
+                };
+                if transition.forward_event {
+                    for entered in entered {
+                        add_all(&mut targets, self.handle(&entered, &entered, event, guards));
+                    }
+                } else {
+                    add_all(&mut targets, entered);
+                }
+            }
+            if unconditional {
+                break;
+            }
+        }
+        if !unconditional {
+            let parent = handler_state.parent.as_deref();
+            match parent {
+                Some(parent) if handler.continues_to_parent => {
+                    add_all(&mut targets, self.handle(current, parent, event, guards));
+                }
+                _ => add_all(&mut targets, vec![current.to_string()]),
+            }
+        }
+        targets
+    }
+
+    fn state(&self, name: &str) -> Option<&'a StateModel> {
+        self.model.states.iter().find(|state| state.name == name)
+    }
+}
+
+/// Add states to a list of states, keeping the order of their first occurrence.
+fn add_all(states: &mut Vec<String>, new_states: Vec<String>) {
+    for state in new_states {
+        if !states.contains(&state) {
+            states.push(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const SPEC: &str = "\
+#Door
+    -interface-
+    open [speed:int]
+    close
+    lock
+    unlock
+    -machine-
+    $Closed
+        |open| [speed:int]
+            speed > 0 ?
+                -> $Opened
+            :
+                -> $Closed
+            :: ^
+        |lock| -> $Locked ^
+    $Opened
+        |close| ->> $Closed ^
+    $Locked => $Closed
+        |open| [speed:int] :>
+        |unlock| -> $Checking ^
+    $Checking
+        |>| -> $Closed ^
+##
+";
+
+    fn model(spec: &str) -> MachineModel {
+        let ir = Exe::new()
+            .run(
+                &None,
+                Some("door.frm"),
+                spec.to_string(),
+                Some(TargetLanguage::Ir),
+            )
+            .unwrap();
+        MachineModel::from_json(&ir).unwrap()
+    }
+
+    #[test]
+    fn tla_steps() {
+        let (code, _) = tla(&model(SPEC));
+        assert!(code.contains("---- MODULE door ----\n"));
+        assert!(code.contains("Events == {\"open\", \"close\", \"lock\", \"unlock\"}\n"));
+        assert!(code.contains(
+            "    /\\ CASE state = \"Closed\" -> state' \\in {\"Opened\", \"Closed\"} \\* speed > 0; else\n"
+        ));
+        // the handler of $Locked continues to its parent, whose transitions are guarded
+        assert!(code.contains(
+            "         [] state = \"Locked\" -> state' \\in {\"Opened\", \"Closed\", \"Locked\"} \\* speed > 0; else\n"
+        ));
+        // $Checking transitions to $Closed as soon as it is entered
+        assert!(code.contains("    /\\ CASE state = \"Locked\" -> state' = \"Closed\"\n"));
+        assert!(code.ends_with("Spec == Init /\\ [][Next]_vars\n\n====\n"));
+    }
+
+    #[test]
+    fn nusmv_steps() {
+        let (code, _) = nusmv(&model(SPEC));
+        assert!(code.contains("    event : {none, open, close, lock, unlock};\n"));
+        assert!(code.contains("    init(state) := Closed;\n"));
+        assert!(code.contains("            next(event) = close & state = Opened : Closed;\n"));
+        assert!(code.ends_with("            TRUE : state;\n        esac;\n"));
+    }
+
+    #[test]
+    fn nusmv_keywords() {
+        let spec = "#Stack\n    -machine-\n    $A\n        |next| -> $B ^\n    $B\n##\n";
+        let (code, _) = nusmv(&model(spec));
+        assert!(code.contains("    state : {A_, B};\n"));
+        assert!(code.contains("    event : {none, next_};\n"));
+        assert!(code.contains("            next(event) = next_ & state = A_ : B;\n"));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let (_, diagnostics) = tla(&model(SPEC));
+        let codes: Vec<(&str, Option<&str>)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.code.as_str(), diagnostic.lexeme.as_deref()))
+            .collect();
+        assert_eq!(
+            codes,
+            [("model-guard", Some("Closed")), ("model-data", None),]
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "The event parameters and return values of the machine Door are not modeled."
+        );
+
+        let spec = "#Stack\n    -machine-\n    $A\n        |push| $$[+] -> $B ^\n    \
+                    $B\n        |pop| -> $$[-] ^\n##\n";
+        let (code, diagnostics) = tla(&model(spec));
+        assert!(code.contains("    /\\ CASE state = \"B\" -> state' \\in {\"A\", \"B\"}\n"));
+        let codes: Vec<&str> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_str())
+            .collect();
+        assert_eq!(codes, ["model-history"]);
+    }
+}
@@ -0,0 +1,622 @@
+//! Optional simplifications of a machine, applied to the AST before code is generated.
+//!
+//! Machine-generated specs often contain states that can't be told apart from each other, or
+//! states that are only passed through on the way to another state. The optimizer removes them
+//! to reduce the size of the generated code, when enabled in
+//! [`OptimizeConfig`](crate::frame_c::config::OptimizeConfig):
+//!
+//! * `collapse_transition_chains` - transitions to a state whose enter handler does nothing but
+//!   transition to another state go to that state directly, and the skipped state is removed if
+//!   nothing else refers to it,
+//! * `merge_equivalent_states` - states that handle the same events with the same transitions to
+//!   equivalent states are merged into the state declared first.
+//!
+//! Only states without parameters, variables, parents or children whose handlers consist of
+//! plain transitions and change-states are considered, and the start state is never removed.
+//! Each simplification is reported as a note located at the state that was removed or skipped.
+
+use crate::frame_c::ast::{
+    ChangeStateStatementNode, DeclOrStmtType, EventHandlerNode, MessageType, StateContextType,
+    StateNode, StatementType, SystemNode, TerminatorType, TestType, TransitionStatementNode,
+};
+use crate::frame_c::config::OptimizeConfig;
+use crate::frame_c::diagnostics::{Diagnostic, Severity};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// The result of optimizing a machine.
+#[derive(Debug, Default)]
+pub struct Optimization {
+    /// The names of the states removed from the machine.
+    pub removed_states: Vec<String>,
+    /// A note for each simplification.
+    pub notes: Vec<Diagnostic>,
+}
+
+/// Simplify the machine of a parsed specification as configured.
+pub fn optimize(system_node: &mut SystemNode, config: &OptimizeConfig) -> Optimization {
+    let mut optimization = Optimization::default();
+    if let Some(machine_block_node) = &mut system_node.machine_block_node_opt {
+        if config.collapse_transition_chains {
+            collapse_transition_chains(&mut machine_block_node.states, &mut optimization);
+        }
+        if config.merge_equivalent_states {
+            merge_equivalent_states(&mut machine_block_node.states, &mut optimization);
+        }
+    }
+    optimization
+}
+
+/* --------------------------------------------------------------------- */
+
+/// A transition or change-state without arguments, as found in the handlers of the states the
+/// optimizer considers. `target` is the index of the target state.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PlainStatement {
+    is_transition: bool,
+    label_opt: Option<String>,
+    forward_event: bool,
+    target: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PlainHandler {
+    message: String,
+    is_continue: bool,
+    statements: Vec<PlainStatement>,
+}
+
+/// The handlers of a state, sorted by message, if the state may be removed or merged.
+fn plain_handlers(
+    state_node: &StateNode,
+    state_indices: &HashMap<String, usize>,
+    parents: &HashSet<String>,
+) -> Option<Vec<PlainHandler>> {
+    let has_vars = state_node
+        .vars_opt
+        .as_ref()
+        .map_or(false, |vars| !vars.is_empty());
+    if state_node.params_opt.is_some()
+        || has_vars
+        || state_node.calls_opt.is_some()
+        || state_node.dispatch_opt.is_some()
+        || parents.contains(&state_node.name)
+    {
+        return None;
+    }
+
+    let mut handlers = Vec::new();
+    for handler_rcref in &state_node.evt_handlers_rcref {
+        handlers.push(plain_handler(&handler_rcref.borrow(), state_indices)?);
+    }
+    handlers.sort_by(|a, b| a.message.cmp(&b.message));
+    Some(handlers)
+}
+
+fn plain_handler(
+    handler: &EventHandlerNode,
+    state_indices: &HashMap<String, usize>,
+) -> Option<PlainHandler> {
+    if handler.terminator_node.return_expr_t_opt.is_some() {
+        return None;
+    }
+    let message = match &handler.msg_t {
+        MessageType::CustomMessage { message_node } => message_node.name.clone(),
+        MessageType::AnyMessage { .. } => String::from("||*"),
+    };
+
+    let mut statements = Vec::new();
+    for decl_or_stmt in &handler.statements {
+        let statement = match decl_or_stmt {
+            DeclOrStmtType::StmtT {
+                stmt_t:
+                    StatementType::TransitionStmt {
+                        transition_statement,
+                    },
+            } => {
+                if transition_statement.exit_args_opt.is_some()
+                    || !transition_statement.actions.is_empty()
+                {
+                    return None;
+                }
+                PlainStatement {
+                    is_transition: true,
+                    label_opt: transition_statement.label_opt.clone(),
+                    forward_event: transition_statement.forward_event,
+                    target: plain_target(
+                        &transition_statement.target_state_context_t,
+                        state_indices,
+                    )?,
+                }
+            }
+            DeclOrStmtType::StmtT {
+                stmt_t: StatementType::ChangeStateStmt { change_state_stmt },
+            } => PlainStatement {
+                is_transition: false,
+                label_opt: change_state_stmt.label_opt.clone(),
+                forward_event: false,
+                target: plain_target(&change_state_stmt.state_context_t, state_indices)?,
+            },
+            _ => return None,
+        };
+        statements.push(statement);
+    }
+
+    Some(PlainHandler {
+        message,
+        is_continue: matches!(
+            handler.terminator_node.terminator_type,
+            TerminatorType::Continue
+        ),
+        statements,
+    })
+}
+
+/// The index of the target state of a transition without arguments or history.
+fn plain_target(
+    state_context_t: &StateContextType,
+    state_indices: &HashMap<String, usize>,
+) -> Option<usize> {
+    match state_context_t {
+        StateContextType::StateRef { state_context_node }
+            if state_context_node.state_ref_args_opt.is_none()
+                && state_context_node.enter_args_opt.is_none()
+                && !state_context_node.deep_history =>
+        {
+            state_indices
+                .get(&state_context_node.state_ref_node.name)
+                .copied()
+        }
+        _ => None,
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+/// A statement that refers to a state.
+enum StateTarget<'a> {
+    Transition(&'a mut TransitionStatementNode),
+    ChangeState(&'a mut ChangeStateStatementNode),
+}
+
+impl StateTarget<'_> {
+    /// The name of the target state, if the target is not popped from the state stack.
+    fn name(&mut self) -> Option<&mut String> {
+        let state_context_t = match self {
+            StateTarget::Transition(transition) => &mut transition.target_state_context_t,
+            StateTarget::ChangeState(change_state) => &mut change_state.state_context_t,
+        };
+        match state_context_t {
+            StateContextType::StateRef { state_context_node } => {
+                Some(&mut state_context_node.state_ref_node.name)
+            }
+            StateContextType::StateStackPop {} => None,
+        }
+    }
+}
+
+/// Call `f` for every transition and change-state in the handlers of a state, including the
+/// ones in the branches of tests.
+fn for_each_target(state_node: &StateNode, f: &mut dyn FnMut(StateTarget)) {
+    for handler_rcref in &state_node.evt_handlers_rcref {
+        for_each_target_in(&mut handler_rcref.borrow_mut().statements, f);
+    }
+}
+
+fn for_each_target_in(statements: &mut [DeclOrStmtType], f: &mut dyn FnMut(StateTarget)) {
+    for decl_or_stmt in statements {
+        let stmt_t = match decl_or_stmt {
+            DeclOrStmtType::StmtT { stmt_t } => stmt_t,
+            DeclOrStmtType::VarDeclT { .. } => continue,
+        };
+        match stmt_t {
+            StatementType::TransitionStmt {
+                transition_statement,
+            } => f(StateTarget::Transition(transition_statement)),
+            StatementType::ChangeStateStmt { change_state_stmt } => {
+                f(StateTarget::ChangeState(change_state_stmt))
+            }
+            StatementType::TestStmt { test_stmt_node } => match &mut test_stmt_node.test_t {
+                TestType::BoolTest { bool_test_node } => {
+                    for branch in &mut bool_test_node.conditional_branch_nodes {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                    if let Some(branch) = &mut bool_test_node.else_branch_node_opt {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                }
+                TestType::StringMatchTest {
+                    string_match_test_node,
+                } => {
+                    for branch in &mut string_match_test_node.match_branch_nodes {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                    if let Some(branch) = &mut string_match_test_node.else_branch_node_opt {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                }
+                TestType::NumberMatchTest {
+                    number_match_test_node,
+                } => {
+                    for branch in &mut number_match_test_node.match_branch_nodes {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                    if let Some(branch) = &mut number_match_test_node.else_branch_node_opt {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                }
+                TestType::EnumMatchTest {
+                    enum_match_test_node,
+                } => {
+                    for branch in &mut enum_match_test_node.match_branch_nodes {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                    if let Some(branch) = &mut enum_match_test_node.else_branch_node_opt {
+                        for_each_target_in(&mut branch.statements, f);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+/// The names of the states that are parents of other states.
+fn parent_names(states: &[Rc<RefCell<StateNode>>]) -> HashSet<String> {
+    states
+        .iter()
+        .filter_map(|state_rcref| {
+            let state_node = state_rcref.borrow();
+            let dispatch_node = state_node.dispatch_opt.as_ref()?;
+            Some(dispatch_node.target_state_ref.name.clone())
+        })
+        .collect()
+}
+
+fn state_indices(states: &[Rc<RefCell<StateNode>>]) -> HashMap<String, usize> {
+    states
+        .iter()
+        .enumerate()
+        .map(|(idx, state_rcref)| (state_rcref.borrow().name.clone(), idx))
+        .collect()
+}
+
+fn note(code: &str, message: &str, state_node: &StateNode) -> Diagnostic {
+    Diagnostic::new(Severity::Note, code, message)
+        .with_line(state_node.line)
+        .with_span(state_node.name_span.start, state_node.name_span.length)
+        .with_lexeme(&state_node.name)
+}
+
+/* --------------------------------------------------------------------- */
+
+fn collapse_transition_chains(
+    states: &mut Vec<Rc<RefCell<StateNode>>>,
+    optimization: &mut Optimization,
+) {
+    let state_indices = state_indices(states);
+    let parents = parent_names(states);
+
+    // the states that are left for another state as soon as they are entered
+    let mut next_states: HashMap<String, String> = HashMap::new();
+    for state_rcref in states.iter() {
+        let state_node = state_rcref.borrow();
+        if state_node.exit_event_handler_opt.is_some()
+            || plain_handlers(&state_node, &state_indices, &parents).is_none()
+        {
+            continue;
+        }
+        let enter_handler = match &state_node.enter_event_handler_opt {
+            Some(enter_handler) => plain_handler(&enter_handler.borrow(), &state_indices),
+            None => None,
+        };
+        if let Some(enter_handler) = enter_handler {
+            if let [statement] = enter_handler.statements.as_slice() {
+                let next_state = states[statement.target].borrow().name.clone();
+                if statement.is_transition
+                    && !statement.forward_event
+                    && next_state != state_node.name
+                {
+                    next_states.insert(state_node.name.clone(), next_state);
+                }
+            }
+        }
+    }
+
+    // follow each chain to the state that is not left on enter, unless the chain is a loop
+    let final_state = |name: &str| -> Option<String> {
+        let mut visited = HashSet::new();
+        let mut name = name.to_string();
+        while let Some(next_state) = next_states.get(&name) {
+            if !visited.insert(name.clone()) {
+                return None;
+            }
+            name = next_state.clone();
+        }
+        Some(name)
+    };
+
+    let mut skipped_states: Vec<(String, String)> = Vec::new();
+    for state_rcref in states.iter() {
+        for_each_target(&state_rcref.borrow(), &mut |mut target| {
+            let is_plain = match &target {
+                StateTarget::Transition(transition) => {
+                    !transition.forward_event
+                        && plain_target(&transition.target_state_context_t, &state_indices)
+                            .is_some()
+                }
+                StateTarget::ChangeState(_) => false,
+            };
+            if !is_plain {
+                return;
+            }
+            if let Some(name) = target.name() {
+                if !next_states.contains_key(name.as_str()) {
+                    return;
+                }
+                if let Some(final_state) = final_state(name) {
+                    if !skipped_states.iter().any(|(skipped, _)| skipped == name) {
+                        skipped_states.push((name.clone(), final_state.clone()));
+                    }
+                    *name = final_state;
+                }
+            }
+        });
+    }
+
+    // remove the skipped states nothing refers to anymore
+    let mut referenced: HashSet<String> = parents;
+    for state_rcref in states.iter() {
+        let state_name = state_rcref.borrow().name.clone();
+        for_each_target(&state_rcref.borrow(), &mut |mut target| {
+            if let Some(name) = target.name() {
+                if *name != state_name {
+                    referenced.insert(name.clone());
+                }
+            }
+        });
+    }
+    let start_state_name = states
+        .first()
+        .map(|state_rcref| state_rcref.borrow().name.clone());
+    for (skipped, final_state) in &skipped_states {
+        let idx = state_indices[skipped];
+        let removed = !referenced.contains(skipped) && start_state_name.as_ref() != Some(skipped);
+        let mut message = format!(
+            "Transitions to ${} now go directly to ${}, as ${} transitions to ${} on enter",
+            skipped, final_state, skipped, next_states[skipped]
+        );
+        if removed {
+            message.push_str(&format!(", and ${} was removed", skipped));
+            optimization.removed_states.push(skipped.clone());
+        }
+        message.push('.');
+        optimization.notes.push(note(
+            "collapsed_transition",
+            &message,
+            &states[idx].borrow(),
+        ));
+    }
+    states.retain(|state_rcref| {
+        !optimization
+            .removed_states
+            .contains(&state_rcref.borrow().name)
+    });
+}
+
+/* --------------------------------------------------------------------- */
+
+fn merge_equivalent_states(
+    states: &mut Vec<Rc<RefCell<StateNode>>>,
+    optimization: &mut Optimization,
+) {
+    let state_indices = state_indices(states);
+    let parents = parent_names(states);
+    let handlers: Vec<Option<Vec<PlainHandler>>> = states
+        .iter()
+        .map(|state_rcref| plain_handlers(&state_rcref.borrow(), &state_indices, &parents))
+        .collect();
+
+    // Partition the states into classes of equivalent states, starting with all states that
+    // may be merged in one class and refining the classes by the classes of the transition
+    // targets until they don't change.
+    let mut classes: Vec<usize> = handlers
+        .iter()
+        .enumerate()
+        .map(|(idx, handlers)| match handlers {
+            Some(_) => 0,
+            None => idx + 1,
+        })
+        .collect();
+    let mut class_count = 0;
+    loop {
+        let mut keys: HashMap<(usize, Vec<PlainHandler>), usize> = HashMap::new();
+        let mut next_classes = Vec::new();
+        for (idx, handlers) in handlers.iter().enumerate() {
+            let mut key_handlers = handlers.clone().unwrap_or_default();
+            for handler in &mut key_handlers {
+                for statement in &mut handler.statements {
+                    statement.target = classes[statement.target];
+                }
+            }
+            let next_class = keys.len();
+            next_classes.push(
+                *keys
+                    .entry((classes[idx], key_handlers))
+                    .or_insert(next_class),
+            );
+        }
+        classes = next_classes;
+        if keys.len() == class_count {
+            break;
+        }
+        class_count = keys.len();
+    }
+
+    // merge each state into the first state of its class, so the start state is kept
+    let mut merged_into: HashMap<String, String> = HashMap::new();
+    let mut first_states: HashMap<usize, usize> = HashMap::new();
+    for (idx, class) in classes.iter().enumerate() {
+        let first_idx = *first_states.entry(*class).or_insert(idx);
+        if first_idx != idx {
+            let state_node = states[idx].borrow();
+            let first_state_name = states[first_idx].borrow().name.clone();
+            let message = format!(
+                "State ${} is equivalent to ${} and was merged into it.",
+                state_node.name, first_state_name
+            );
+            optimization
+                .notes
+                .push(note("merged_state", &message, &state_node));
+            optimization.removed_states.push(state_node.name.clone());
+            merged_into.insert(state_node.name.clone(), first_state_name);
+        }
+    }
+    if merged_into.is_empty() {
+        return;
+    }
+
+    states.retain(|state_rcref| !merged_into.contains_key(&state_rcref.borrow().name));
+    for state_rcref in states.iter() {
+        for_each_target(&state_rcref.borrow(), &mut |mut target| {
+            if let Some(name) = target.name() {
+                if let Some(first_state_name) = merged_into.get(name.as_str()) {
+                    *name = first_state_name.clone();
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+
+    const SPEC: &str = "#Turnstile
+    -interface-
+    coin
+    push
+    -machine-
+    $Locked
+        |coin| -> $Unlocking ^
+        |push| ^
+    $Unlocking
+        |>| -> $Unlocked ^
+    $Unlocked
+        |push| -> $Locked2 ^
+        |coin| ^
+    $Locked2
+        |coin| -> $Unlocking ^
+        |push| ^
+    $Unlocked2
+        |coin| ^
+        |push| -> $Locked ^
+    $Counting
+        var count:int = 0
+        |coin| -> $Unlocked2 ^
+        |push| ^
+##
+";
+
+    /// Optimize `spec` and return the notes along with each remaining state and the targets of
+    /// its transitions.
+    fn optimize_spec(spec: &str, config: OptimizeConfig) -> (Vec<String>, Vec<String>) {
+        let mut system_node = Exe::new().parse(None, spec.to_string()).unwrap();
+        let optimization = optimize(&mut system_node, &config);
+        let mut states = Vec::new();
+        for state_rcref in &system_node.machine_block_node_opt.unwrap().states {
+            let mut targets = Vec::new();
+            for_each_target(&state_rcref.borrow(), &mut |mut target| {
+                targets.push(target.name().cloned().unwrap_or_default());
+            });
+            states.push(format!(
+                "{} -> {}",
+                state_rcref.borrow().name,
+                targets.join(", ")
+            ));
+        }
+        let notes = optimization
+            .notes
+            .iter()
+            .map(|note| format!("{}: {}", note.code, note.message))
+            .collect();
+        (notes, states)
+    }
+
+    /// Test that states with the same handlers and equivalent targets are merged into the state
+    /// declared first, and that states with variables are kept.
+    #[test]
+    fn merge_equivalent_states() {
+        let config = OptimizeConfig {
+            merge_equivalent_states: true,
+            ..OptimizeConfig::default()
+        };
+        let (notes, states) = optimize_spec(SPEC, config);
+        assert_eq!(
+            notes,
+            [
+                "merged_state: State $Locked2 is equivalent to $Locked and was merged into it.",
+                "merged_state: State $Unlocked2 is equivalent to $Unlocked and was merged into it.",
+            ]
+        );
+        assert_eq!(
+            states,
+            [
+                "Locked -> Unlocking",
+                "Unlocking -> Unlocked",
+                "Unlocked -> Locked",
+                "Counting -> Unlocked",
+            ]
+        );
+    }
+
+    /// Test that transitions to states that are left on enter go to the end of the chain, that
+    /// skipped states are removed once nothing refers to them, and that the optimizations
+    /// combine.
+    #[test]
+    fn collapse_transition_chains() {
+        let spec = SPEC.replace(
+            "$Unlocking\n        |>| -> $Unlocked ^",
+            "$Unlocking\n        |>| -> $Opening ^\n    $Opening\n        |>| -> $Unlocked ^",
+        );
+        let config = OptimizeConfig {
+            collapse_transition_chains: true,
+            ..OptimizeConfig::default()
+        };
+        let (notes, states) = optimize_spec(&spec, config.clone());
+        assert_eq!(notes.len(), 2);
+        assert_eq!(
+            notes[0],
+            "collapsed_transition: Transitions to $Unlocking now go directly to $Unlocked, as \
+             $Unlocking transitions to $Opening on enter, and $Unlocking was removed."
+        );
+        assert_eq!(states[0], "Locked -> Unlocked");
+        assert_eq!(states[1], "Unlocked -> Locked2");
+
+        // a change-state doesn't enter the state, so the state is kept
+        let spec = spec.replace(
+            "|push| ^\n    $Unlocking",
+            "|push| ->> $Unlocking ^\n    $Unlocking",
+        );
+        let (notes, states) = optimize_spec(&spec, config);
+        assert!(notes[0].ends_with("on enter."));
+        assert_eq!(states[0], "Locked -> Unlocked, Unlocking");
+        assert_eq!(states[1], "Unlocking -> Unlocked");
+
+        let config = OptimizeConfig {
+            merge_equivalent_states: true,
+            collapse_transition_chains: true,
+        };
+        let (_, states) = optimize_spec(SPEC, config);
+        assert_eq!(
+            states,
+            [
+                "Locked -> Unlocked",
+                "Unlocked -> Locked",
+                "Counting -> Unlocked",
+            ]
+        );
+    }
+}
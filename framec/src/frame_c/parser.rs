@@ -10,6 +10,7 @@ use super::ast::TerminatorType::{Continue, Return};
 use super::ast::*;
 use super::scanner::*;
 use super::symbol_table::*;
+use crate::frame_c::diagnostics::{Diagnostic, Span};
 use crate::frame_c::utils::SystemHierarchy;
 use downcast_rs::__std::cell::RefCell;
 use std::collections::HashMap;
@@ -96,16 +97,24 @@ pub struct Parser<'a> {
     had_error: bool,
     panic_mode: bool,
     errors: String,
+    diagnostics: Vec<Diagnostic>,
+    // start of the token the last error was reported at
+    last_error_start_opt: Option<usize>,
     last_sync_token_idx: usize,
     system_hierarchy_opt: Option<SystemHierarchy>,
     is_parsing_rhs: bool,
     event_handler_has_transition: bool,
+    // the index of the parent state token, name, parent and region of each substate
+    substates: Vec<(usize, String, String, Option<String>)>,
     pub generate_enter_args: bool,
     pub generate_exit_args: bool,
     pub generate_state_context: bool,
     pub generate_state_stack: bool,
     pub generate_change_state: bool,
     pub generate_transition_state: bool,
+    pub generate_deep_history: bool,
+    pub generate_regions: bool,
+    pub generate_transition_actions: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -129,16 +138,22 @@ impl<'a> Parser<'a> {
             had_error: false,
             panic_mode: false,
             errors: String::new(),
+            diagnostics: Vec::new(),
+            last_error_start_opt: None,
             current_tok_ref: &tokens[0],
             system_hierarchy_opt: None,
             is_parsing_rhs: false,
             event_handler_has_transition: false,
+            substates: Vec::new(),
             generate_enter_args: false,
             generate_exit_args: false,
             generate_state_context: false,
             generate_state_stack: false,
             generate_change_state: false,
             generate_transition_state: false,
+            generate_deep_history: false,
+            generate_regions: false,
+            generate_transition_actions: false,
         }
     }
 
@@ -156,12 +171,6 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
-    pub fn get_system_hierarchy(self) -> SystemHierarchy {
-        self.system_hierarchy_opt.unwrap()
-    }
-
-    /* --------------------------------------------------------------------- */
-
     pub fn get_all(self) -> (Arcanum, SystemHierarchy) {
         (self.arcanum, self.system_hierarchy_opt.unwrap())
     }
@@ -180,6 +189,12 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    pub fn get_diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.clone()
+    }
+
+    /* --------------------------------------------------------------------- */
+
     // Helper functions
 
     fn match_token(&mut self, token_types: &[TokenType]) -> bool {
@@ -266,6 +281,15 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    fn token_span(token: &Token) -> Span {
+        Span {
+            start: token.start,
+            length: token.length,
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
     fn error_at_current(&mut self, message: &str) {
         self.error_at(&self.tokens[self.current], message);
     }
@@ -287,6 +311,13 @@ impl<'a> Parser<'a> {
         self.panic_mode = true;
         self.had_error = true;
 
+        // After recovering, the parser may fail again at the token that caused the last error.
+        // That is the same error, so only the first message for a token is reported.
+        if self.last_error_start_opt == Some(token.start) {
+            return;
+        }
+        self.last_error_start_opt = Some(token.start);
+
         let mut error_msg = format!("[line {}] Error", token.line);
 
         match token.token_type {
@@ -298,6 +329,14 @@ impl<'a> Parser<'a> {
         self.errors
             .push_str(&format!("{} : {}\n", error_msg, message));
 
+        let mut diagnostic = Diagnostic::error("parse-error", message)
+            .with_line(token.line)
+            .with_span(token.start, token.length);
+        if token.token_type != TokenType::Eof {
+            diagnostic = diagnostic.with_lexeme(&token.lexeme);
+        }
+        self.diagnostics.push(diagnostic);
+
         //        println!("{} : {}", error_msg, message);
         // TODO:?
         //       ParseError::new( /* error_msg */ )
@@ -305,6 +344,26 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    // Report an error if `name` is already declared in the current scope. Definitions
+    // may come from #include'd files, so this is the only place duplicates are caught.
+    // A duplicate is not a syntax error, so the parser is not put into panic mode.
+    fn check_duplicate_definition(&mut self, name: &str, kind: &str) {
+        let is_duplicate = self
+            .arcanum
+            .current_symtab
+            .borrow()
+            .lookup_local(name)
+            .is_some();
+        if is_duplicate {
+            let panic_mode = self.panic_mode;
+            self.panic_mode = false;
+            self.error_at_previous(&format!("Duplicate {} name {}.", kind, name));
+            self.panic_mode = panic_mode;
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
     fn synchronize(&mut self, sync_tokens: &[TokenType]) -> bool {
         self.panic_mode = false;
 
@@ -374,6 +433,7 @@ impl<'a> Parser<'a> {
                 None,
                 None,
                 0,
+                0,
             );
         }
 
@@ -393,6 +453,7 @@ impl<'a> Parser<'a> {
             }
         }
 
+        let start_line = self.peek().line;
         let attributes_opt = match self.attributes() {
             Ok(attributes_opt) => attributes_opt,
             Err(_parse_error) => None,
@@ -451,7 +512,10 @@ impl<'a> Parser<'a> {
                 self.synchronize(sync_tokens);
             }
             match self.parameters() {
-                Ok(Some(parameters)) => start_state_state_params_opt = Some(parameters),
+                Ok(Some(parameters)) => {
+                    self.disallow_defaults(&parameters);
+                    start_state_state_params_opt = Some(parameters)
+                }
                 Ok(None) => {}
                 Err(_) => {}
             }
@@ -472,7 +536,10 @@ impl<'a> Parser<'a> {
                 self.synchronize(sync_tokens);
             }
             match self.parameters() {
-                Ok(Some(parameters)) => start_state_enter_params_opt = Some(parameters),
+                Ok(Some(parameters)) => {
+                    self.disallow_defaults(&parameters);
+                    start_state_enter_params_opt = Some(parameters)
+                }
                 Ok(None) => {}
                 Err(_) => {}
             }
@@ -482,7 +549,10 @@ impl<'a> Parser<'a> {
 
         if self.match_token(&[TokenType::LBracket]) {
             match self.parameters() {
-                Ok(Some(parameters)) => domain_params_opt = Some(parameters),
+                Ok(Some(parameters)) => {
+                    self.disallow_defaults(&parameters);
+                    domain_params_opt = Some(parameters)
+                }
                 Ok(None) => {}
                 Err(_) => {}
             }
@@ -536,6 +606,7 @@ impl<'a> Parser<'a> {
             machine_block_node_opt,
             actions_block_node_opt,
             domain_block_node_opt,
+            start_line,
             line,
         )
     }
@@ -700,6 +771,11 @@ impl<'a> Parser<'a> {
 
     fn interface_method(&mut self) -> Result<Rc<RefCell<InterfaceMethodNode>>, ParseError> {
         let name = self.previous().lexeme.clone();
+        let line = self.previous().line;
+        let name_span = Parser::token_span(self.previous());
+        if self.is_building_symbol_table {
+            self.check_duplicate_definition(&name, "interface method");
+        }
 
         let mut params_opt: Option<Vec<ParameterNode>> = Option::None;
         let mut return_type_opt: Option<TypeNode> = Option::None;
@@ -766,7 +842,8 @@ impl<'a> Parser<'a> {
                         param_node.param_name.clone(),
                         param_node.param_type_opt.clone(),
                         IdentifierDeclScope::None,
-                    );
+                    )
+                    .with_default(param_node.default_opt.clone());
                     vec.push(param_symbol);
                 }
                 param_symbols_opt = Some(vec);
@@ -808,8 +885,14 @@ impl<'a> Parser<'a> {
             }
         }
 
-        let interface_method_node =
-            InterfaceMethodNode::new(name.clone(), params_opt, return_type_opt, alias_opt);
+        let interface_method_node = InterfaceMethodNode::new(
+            name.clone(),
+            params_opt,
+            return_type_opt,
+            alias_opt,
+            line,
+            name_span,
+        );
         let interface_method_rcref = Rc::new(RefCell::new(interface_method_node));
 
         if self.is_building_symbol_table {
@@ -911,7 +994,7 @@ impl<'a> Parser<'a> {
         let id = self.previous();
         let name = id.lexeme.clone();
 
-        MessageNode::new(name, id.line)
+        MessageNode::new(name, id.line, Parser::token_span(id))
     }
 
     /* --------------------------------------------------------------------- */
@@ -954,6 +1037,20 @@ impl<'a> Parser<'a> {
             }
         }
 
+        let first_default_opt = parameters
+            .iter()
+            .position(|param| param.default_opt.is_some());
+        if let Some(first_default) = first_default_opt {
+            if parameters[first_default..]
+                .iter()
+                .any(|param| param.default_opt.is_none())
+            {
+                self.error_at_previous(
+                    "Parameters with default values must follow the parameters without.",
+                );
+            }
+        }
+
         if !parameters.is_empty() {
             return Ok(Some(parameters));
         }
@@ -989,7 +1086,44 @@ impl<'a> Parser<'a> {
         }
 
         let scope = self.arcanum.get_current_identifier_scope();
-        Ok(Some(ParameterNode::new(param_name, param_type_opt, scope)))
+        let mut parameter_node = ParameterNode::new(param_name, param_type_opt, scope);
+
+        // default value e.g. '= 5'
+        if self.match_token(&[TokenType::Equals]) {
+            let negative = self.match_token(&[TokenType::Dash]);
+            match self.literal_expr() {
+                Ok(Some(mut literal_expr_node)) => {
+                    if negative {
+                        if literal_expr_node.token_t != TokenType::Number {
+                            self.error_at_previous("Expected a number after '-'.");
+                            return Err(ParseError::new("TODO"));
+                        }
+                        literal_expr_node.value = format!("-{}", literal_expr_node.value);
+                    }
+                    parameter_node.default_opt = Some(literal_expr_node);
+                }
+                Ok(None) => {
+                    self.error_at_current("Expected a literal as the default value.");
+                    return Err(ParseError::new("TODO"));
+                }
+                Err(parse_error) => return Err(parse_error),
+            }
+        }
+
+        Ok(Some(parameter_node))
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // Defaults are only supported where arguments may be left out: in calls of interface
+    // methods and in the enter and exit arguments of transitions.
+
+    fn disallow_defaults(&mut self, parameters: &[ParameterNode]) {
+        if parameters.iter().any(|param| param.default_opt.is_some()) {
+            self.error_at_previous(
+                "Default values are only supported for parameters of interface methods and enter and exit handlers.",
+            );
+        }
     }
 
     /* --------------------------------------------------------------------- */
@@ -1033,11 +1167,56 @@ impl<'a> Parser<'a> {
 
         self.arcanum.exit_parse_scope();
 
+        if !self.is_building_symbol_table {
+            let start_state_name_opt = states.first().map(|state| state.borrow().name.clone());
+            self.check_regions(start_state_name_opt);
+        }
+
         MachineBlockNode::new(states)
     }
 
     /* --------------------------------------------------------------------- */
 
+    // The substates of a parallel state must all be in one of its regions, and can't have
+    // substates of their own.
+
+    fn check_regions(&mut self, start_state_name_opt: Option<String>) {
+        let substates = std::mem::take(&mut self.substates);
+        for (id_idx, name, parent, region_opt) in &substates {
+            let parent_has_regions =
+                substates
+                    .iter()
+                    .any(|(_, _, other_parent, other_region_opt)| {
+                        other_parent == parent && other_region_opt.is_some()
+                    });
+            let parent_in_region = substates
+                .iter()
+                .any(|(_, other_name, _, other_region_opt)| {
+                    other_name == parent && other_region_opt.is_some()
+                });
+            let message = if parent_in_region {
+                format!(
+                    "States of a region can't have substates, as ${} does.",
+                    parent
+                )
+            } else if parent_has_regions && region_opt.is_none() {
+                format!(
+                    "${} has regions, so its substate ${} must be in one, e.g. '=> ${}.region'.",
+                    parent, name, parent
+                )
+            } else if region_opt.is_some() && start_state_name_opt.as_ref() == Some(name) {
+                "The start state can't be in a region.".to_string()
+            } else {
+                continue;
+            };
+            // report each error, as they are found after parsing the machine
+            self.panic_mode = false;
+            self.error_at(&self.tokens[*id_idx], &message);
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
     // TODO: Return result
     fn actions_block(&mut self) -> ActionsBlockNode {
         if self.is_building_symbol_table {
@@ -1050,8 +1229,18 @@ impl<'a> Parser<'a> {
         let mut actions = Vec::new();
 
         while self.match_token(&[TokenType::Identifier]) {
-            if let Ok(action_decl_node) = self.action_decl() {
-                actions.push(action_decl_node);
+            match self.action_decl() {
+                Ok(action_decl_node) => actions.push(action_decl_node),
+                Err(_parse_error) => {
+                    let sync_tokens = &[
+                        TokenType::Identifier,
+                        TokenType::InterfaceBlock,
+                        TokenType::MachineBlock,
+                        TokenType::DomainBlock,
+                        TokenType::SystemEnd,
+                    ];
+                    self.synchronize(sync_tokens);
+                }
             }
         }
 
@@ -1066,12 +1255,18 @@ impl<'a> Parser<'a> {
 
     fn action_decl(&mut self) -> Result<Rc<RefCell<ActionNode>>, ParseError> {
         let action_name = self.previous().lexeme.clone();
+        if self.is_building_symbol_table {
+            self.check_duplicate_definition(&action_name, "action");
+        }
 
         let mut params: Option<Vec<ParameterNode>> = Option::None;
 
         if self.match_token(&[TokenType::LBracket]) {
             params = match self.parameters() {
-                Ok(Some(parameters)) => Some(parameters),
+                Ok(Some(parameters)) => {
+                    self.disallow_defaults(&parameters);
+                    Some(parameters)
+                }
                 Ok(None) => None,
                 Err(parse_error) => return Err(parse_error),
             }
@@ -1142,12 +1337,33 @@ impl<'a> Parser<'a> {
         }
 
         let mut domain_variables = Vec::new();
-
-        while self.match_token(&[TokenType::Var, TokenType::Const]) {
+        let mut enums = Vec::new();
+
+        while self.match_token(&[TokenType::Var, TokenType::Const, TokenType::Enum]) {
+            if self.previous().token_type == TokenType::Enum {
+                match self.enum_decl() {
+                    Ok(enum_decl_node) => enums.push(enum_decl_node),
+                    Err(_parse_err) => {
+                        let sync_tokens = &[
+                            TokenType::Var,
+                            TokenType::Const,
+                            TokenType::Enum,
+                            TokenType::SystemEnd,
+                        ];
+                        self.synchronize(sync_tokens);
+                    }
+                }
+                continue;
+            }
             match self.variable_decl(IdentifierDeclScope::DomainBlock) {
                 Ok(domain_variable_node) => domain_variables.push(domain_variable_node),
                 Err(_parse_err) => {
-                    let sync_tokens = &vec![TokenType::Var, TokenType::Const, TokenType::SystemEnd];
+                    let sync_tokens = &[
+                        TokenType::Var,
+                        TokenType::Const,
+                        TokenType::Enum,
+                        TokenType::SystemEnd,
+                    ];
                     self.synchronize(sync_tokens);
                 }
             }
@@ -1157,7 +1373,58 @@ impl<'a> Parser<'a> {
             .debug_print_current_symbols(self.arcanum.get_current_symtab());
         self.arcanum.exit_parse_scope();
 
-        DomainBlockNode::new(domain_variables)
+        DomainBlockNode::new(domain_variables, enums)
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // enum_decl -> 'enum' identifier '{' identifier* '}'
+
+    fn enum_decl(&mut self) -> Result<Rc<RefCell<EnumDeclNode>>, ParseError> {
+        if !self.match_token(&[TokenType::Identifier]) {
+            self.error_at_current("Expected enum name.");
+            return Err(ParseError::new("TODO"));
+        }
+        let name = self.previous().lexeme.clone();
+        let line = self.previous().line;
+        if self.is_building_symbol_table {
+            self.check_duplicate_definition(&name, "enum");
+        }
+
+        self.consume(TokenType::OpenBrace, "Expected '{'.")?;
+
+        let mut enumerators: Vec<Rc<EnumeratorDeclNode>> = Vec::new();
+        while self.match_token(&[TokenType::Identifier]) {
+            let enumerator_name = self.previous().lexeme.clone();
+            if enumerators.iter().any(|e| e.name == enumerator_name) {
+                self.error_at_previous(&format!(
+                    "Duplicate enumerator {} in enum {}.",
+                    enumerator_name, name
+                ));
+            }
+            enumerators.push(Rc::new(EnumeratorDeclNode::new(enumerator_name)));
+        }
+
+        self.consume(TokenType::CloseBrace, "Expected '}'.")?;
+
+        let enum_decl_node_rcref = Rc::new(RefCell::new(EnumDeclNode::new(
+            name.clone(),
+            enumerators,
+            line,
+        )));
+
+        if self.is_building_symbol_table {
+            let enum_symbol = EnumDeclSymbol::new(name, Rc::clone(&enum_decl_node_rcref));
+            let enum_symbol_t = SymbolType::EnumDecl {
+                enum_symbol_rcref: Rc::new(RefCell::new(enum_symbol)),
+            };
+            self.arcanum
+                .current_symtab
+                .borrow_mut()
+                .insert_symbol(&enum_symbol_t);
+        }
+
+        Ok(enum_decl_node_rcref)
     }
 
     //* --------------------------------------------------------------------- *//
@@ -1179,6 +1446,11 @@ impl<'a> Parser<'a> {
             }
             true => self.previous().lexeme.clone(),
         };
+        if self.is_building_symbol_table
+            && identifier_decl_scope == IdentifierDeclScope::DomainBlock
+        {
+            self.check_duplicate_definition(&name, "domain variable");
+        }
 
         let mut type_node_opt: Option<TypeNode> = None;
 
@@ -1187,6 +1459,9 @@ impl<'a> Parser<'a> {
                 Ok(type_node) => type_node_opt = Some(type_node),
                 Err(parse_error) => return Err(parse_error),
             }
+        } else if is_constant {
+            self.error_at_previous(&format!("Constant {} must declare a type.", name));
+            return Err(ParseError::new("TODO"));
         }
 
         let initializer_expr_t_opt;
@@ -1210,6 +1485,8 @@ impl<'a> Parser<'a> {
                     => initializer_expr_t_opt = Some(BinaryExprT {binary_expr_node}),
                 Ok(Some(FrameEventExprT { frame_event_part }))
                     => initializer_expr_t_opt = Some(FrameEventExprT {frame_event_part}),
+                Ok(Some(EnumeratorExprT { enum_expr_node }))
+                    => initializer_expr_t_opt = Some(EnumeratorExprT {enum_expr_node}),
                 _ => {
                     self.error_at_current("Unexpected assignment expression value.");
                     return Err(ParseError::new("TODO"))
@@ -1234,7 +1511,7 @@ impl<'a> Parser<'a> {
             // syntactic pass
             // add variable to current symbol table
             let scope = self.arcanum.get_current_identifier_scope();
-            let variable_symbol = VariableSymbol::new(name, type_node_opt, scope);
+            let variable_symbol = VariableSymbol::new(name, type_node_opt, scope, is_constant);
             let variable_symbol_rcref = Rc::new(RefCell::new(variable_symbol));
             let variable_symbol_t = match identifier_decl_scope {
                 IdentifierDeclScope::DomainBlock => SymbolType::DomainVariable {
@@ -1324,12 +1601,17 @@ impl<'a> Parser<'a> {
                 Option::None,
                 None,
                 0,
+                Span {
+                    start: 0,
+                    length: 0,
+                },
             );
             let state_node_rcref = Rc::new(RefCell::new(state_node));
             return Ok(state_node_rcref);
         }
         let id = self.previous();
         let state_name = id.lexeme.clone();
+        let name_span = Parser::token_span(id);
 
         self.state_name_opt = Some(state_name.clone());
 
@@ -1357,6 +1639,7 @@ impl<'a> Parser<'a> {
             self.generate_state_context = true;
             match self.parameters() {
                 Ok(Some(parameters)) => {
+                    self.disallow_defaults(&parameters);
                     pop_state_params_scope = true;
                     if self.is_building_symbol_table {
                         match self.arcanum.get_state(&state_name) {
@@ -1398,16 +1681,34 @@ impl<'a> Parser<'a> {
         let mut dispatch_opt: Option<DispatchNode> = None;
 
         // Dispatch clause.
-        // '=>' '$' state_id
+        // '=>' '$' state_id ('.' region_id)?
         if self.match_token(&[TokenType::Dispatch]) {
             match self.consume(TokenType::State, "Expected '$'") {
                 Ok(_) => {
                     if self.match_token(&[TokenType::Identifier]) {
+                        let id_idx = self.current - 1;
                         let id = self.previous();
                         let target_state_name = id.lexeme.clone();
+                        let line = id.line;
+
+                        let mut region_opt = None;
+                        if self.match_token(&[TokenType::Dot]) {
+                            if self.match_token(&[TokenType::Identifier]) {
+                                region_opt = Some(self.previous().lexeme.clone());
+                                self.generate_regions = true;
+                            } else {
+                                self.error_at_current("Expected region identifier.");
+                            }
+                        }
+                        self.substates.push((
+                            id_idx,
+                            state_name.clone(),
+                            target_state_name.clone(),
+                            region_opt.clone(),
+                        ));
 
                         let target_state_ref = StateRefNode::new(target_state_name);
-                        dispatch_opt = Some(DispatchNode::new(target_state_ref, id.line));
+                        dispatch_opt = Some(DispatchNode::new(target_state_ref, region_opt, line));
                     } else {
                         self.error_at_current("Expected dispatch target state identifier.");
                         let sync_tokens = &vec![
@@ -1639,6 +1940,7 @@ impl<'a> Parser<'a> {
             exit_event_handler,
             dispatch_opt,
             line,
+            name_span,
         );
         let state_node_rcref = Rc::new(RefCell::new(state_node));
 
@@ -1746,6 +2048,11 @@ impl<'a> Parser<'a> {
 
             match self.parameters() {
                 Ok(Some(parameters)) => {
+                    if msg != self.arcanum.symbol_config.enter_msg_symbol
+                        && msg != self.arcanum.symbol_config.exit_msg_symbol
+                    {
+                        self.disallow_defaults(&parameters);
+                    }
                     // have parsed params - make sure they match w/ symbol
                     // pop scope at end.
                     pop_params_scope = true;
@@ -1767,7 +2074,8 @@ impl<'a> Parser<'a> {
                                     param_node.param_name.clone(),
                                     param_node.param_type_opt.clone(),
                                     IdentifierDeclScope::None,
-                                );
+                                )
+                                .with_default(param_node.default_opt.clone());
                                 vec.push(param_symbol);
                             }
                             event_symbol_rcref.borrow_mut().params_opt = Some(vec);
@@ -1867,7 +2175,8 @@ impl<'a> Parser<'a> {
                                             param_name.clone(),
                                             param_type_opt.clone(),
                                             scope,
-                                        );
+                                        )
+                                        .with_default(param.default_opt.clone());
                                         // add to Arcanum event symbol
                                         event_symbol_params.push(b);
 
@@ -2204,6 +2513,18 @@ impl<'a> Parser<'a> {
                             Err(parse_error)
                         }
                     };
+                } else if self.is_enum_match_test() {
+                    if !self.is_testable_expression(&expr_t) {
+                        self.error_at_current("Not a testable expression.");
+                        return Err(ParseError::new("TODO"));
+                    }
+                    let enum_match_test_node = self.enum_match_test(expr_t)?;
+                    let match_test_t = TestType::EnumMatchTest {
+                        enum_match_test_node,
+                    };
+                    let test_stmt_node = TestStatementNode::new(match_test_t);
+                    let test_stmt_t = StatementType::TestStmt { test_stmt_node };
+                    return Ok(Some(test_stmt_t));
                 }
 
                 match expr_t {
@@ -2293,6 +2614,10 @@ impl<'a> Parser<'a> {
                         self.error_at_previous("Binary expression statements not allowed.");
                         return Err(ParseError::new("TODO"));
                     }
+                    EnumeratorExprT { .. } => {
+                        self.error_at_previous("Enumerator statements not allowed.");
+                        return Err(ParseError::new("TODO"));
+                    }
                 }
             }
             None => {
@@ -2363,6 +2688,12 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    fn is_enum_match_test(&self) -> bool {
+        self.peek().token_type == TokenType::EnumTest
+    }
+
+    /* --------------------------------------------------------------------- */
+
     // TODO
     // fn is_regex_test(&self) -> bool {
     //
@@ -2698,6 +3029,12 @@ impl<'a> Parser<'a> {
             // like (a) and (a b c)
             self.is_parsing_rhs = true;
 
+            if !self.is_building_symbol_table {
+                if let Some(name) = Parser::constant_lvalue_name(&l_value) {
+                    self.error_at_previous(&format!("Cannot assign to constant {}.", name));
+                }
+            }
+
             let line = self.previous().line;
             let r_value = match self.equality() {
                 Ok(Some(expr_type)) => {
@@ -2725,6 +3062,30 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    // Returns the name of the domain constant an assignment targets, if any.
+
+    fn constant_lvalue_name(l_value: &ExprType) -> Option<String> {
+        let var_node = match l_value {
+            VariableExprT { var_node } => var_node,
+            CallChainLiteralExprT {
+                call_chain_expr_node,
+            } if call_chain_expr_node.call_chain.len() == 1 => {
+                match call_chain_expr_node.call_chain.front() {
+                    Some(CallChainLiteralNodeType::VariableNodeT { var_node }) => var_node,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        if var_node.is_constant() {
+            Some(var_node.id_node.name.lexeme.clone())
+        } else {
+            None
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
     fn equality(&mut self) -> Result<Option<ExprType>, ParseError> {
         let mut l_value = match self.comparison() {
             Ok(Some(expr_type)) => expr_type,
@@ -3104,6 +3465,9 @@ impl<'a> Parser<'a> {
                         call_chain_expr_node,
                     }));
                 }
+                Ok(Some(EnumeratorExprT { enum_expr_node })) => {
+                    return Ok(Some(EnumeratorExprT { enum_expr_node }))
+                }
                 Ok(Some(_)) => return Err(ParseError::new("TODO")),
                 Err(parse_error) => return Err(parse_error),
                 Ok(None) => {} // continue
@@ -3247,6 +3611,14 @@ impl<'a> Parser<'a> {
             false,
             self.previous().line,
         );
+        // Color.Red - enums are declared in the domain, so references that
+        // precede the declaration are only resolved in the semantic pass.
+        if explicit_scope == IdentifierDeclScope::None {
+            if let Some(enum_symbol_rcref) = self.arcanum.lookup_enum(&id_node.name.lexeme) {
+                return self.enumerator_expr(&enum_symbol_rcref);
+            }
+        }
+
         let mut call_chain: std::collections::VecDeque<CallChainLiteralNodeType> =
             std::collections::VecDeque::new();
 
@@ -3363,6 +3735,32 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
+    // enumerator_expr -> enum_name '.' identifier
+
+    fn enumerator_expr(
+        &mut self,
+        enum_symbol_rcref: &Rc<RefCell<EnumDeclSymbol>>,
+    ) -> Result<Option<ExprType>, ParseError> {
+        let enum_symbol = enum_symbol_rcref.borrow();
+        self.consume(TokenType::Dot, "Expected '.' after enum name.")?;
+        if !self.match_token(&[TokenType::Identifier]) {
+            self.error_at_current("Expected enumerator.");
+            return Err(ParseError::new("TODO"));
+        }
+        let enumerator = self.previous().lexeme.clone();
+        if !enum_symbol.ast_node.borrow().has_enumerator(&enumerator) {
+            self.error_at_previous(&format!(
+                "Enum {} has no enumerator {}.",
+                enum_symbol.name, enumerator
+            ));
+            return Err(ParseError::new("TODO"));
+        }
+        let enum_expr_node = EnumeratorExprNode::new(enum_symbol.name.clone(), enumerator);
+        Ok(Some(EnumeratorExprT { enum_expr_node }))
+    }
+
+    /* --------------------------------------------------------------------- */
+
     fn get_identifier_scope(
         &mut self,
         identifier_node: &IdentifierNode,
@@ -3499,6 +3897,24 @@ impl<'a> Parser<'a> {
             let state_id = self.previous();
             let name = state_id.lexeme.clone();
 
+            // parse optional deep history marker '[H*]'
+            let mut deep_history = false;
+            if self.match_token(&[TokenType::LBracket]) {
+                let is_history_marker = self.match_token(&[TokenType::Identifier])
+                    && self.previous().lexeme == "H"
+                    && self.match_token(&[TokenType::Star])
+                    && self.match_token(&[TokenType::RBracket]);
+                if !is_history_marker {
+                    self.error_at_current("Expected deep history marker '[H*]'.");
+                    return Err(ParseError::new("TODO"));
+                }
+                if enter_args_opt.is_some() {
+                    self.error_at_previous("Deep history transitions can't pass enter arguments.");
+                }
+                deep_history = true;
+                self.generate_deep_history = true;
+            }
+
             // parse optional state ref expression list
             // '(' ')' | '(' expr ')'
             let mut state_ref_args_opt = None;
@@ -3513,8 +3929,16 @@ impl<'a> Parser<'a> {
                 }
             }
 
-            let state_context_node =
-                StateContextNode::new(StateRefNode::new(name), state_ref_args_opt, enter_args_opt);
+            if deep_history && state_ref_args_opt.is_some() {
+                self.error_at_previous("Deep history transitions can't pass state arguments.");
+            }
+
+            let state_context_node = StateContextNode::new(
+                StateRefNode::new(name),
+                state_ref_args_opt,
+                enter_args_opt,
+                deep_history,
+            );
 
             Ok(Some(StateContextType::StateRef { state_context_node }))
         }
@@ -3522,14 +3946,22 @@ impl<'a> Parser<'a> {
 
     /* --------------------------------------------------------------------- */
 
-    // transition : exitArgs '->' enterArgs transitionLabel stateRef stateArgs
+    // transition : exitArgs '->' enterArgs transitionLabel transitionActions stateRef stateArgs
 
     fn transition(
         &mut self,
-        exit_args_opt: Option<ExprListNode>,
+        mut exit_args_opt: Option<ExprListNode>,
     ) -> Result<Option<StatementType>, ParseError> {
         self.generate_transition_state = true;
 
+        // pass the defaults of exit parameters that have no argument
+        if let Some(state_name) = self.state_name_opt.clone() {
+            let exit_msg = self.arcanum.symbol_config.exit_msg_symbol.clone();
+            if self.default_args(&mut exit_args_opt, &exit_msg, &state_name) {
+                self.generate_exit_args = true;
+            }
+        }
+
         let eh_rc_refcell = self.current_event_symbol_opt.as_ref().unwrap().clone();
         let evt_symbol = eh_rc_refcell.borrow();
 
@@ -3566,6 +3998,13 @@ impl<'a> Parser<'a> {
             transition_label = Some(self.previous().lexeme.clone());
         }
 
+        // transitionActions: '{' call* '}'
+        let mut actions = Vec::new();
+        if self.match_token(&[TokenType::OpenBrace]) {
+            actions = self.transition_actions()?;
+            self.generate_transition_actions = true;
+        }
+
         // Transition dispatch
         // -> => $Next
         let mut forward_event = false;
@@ -3578,13 +4017,28 @@ impl<'a> Parser<'a> {
             }
         }
 
-        let state_context_t;
+        let mut state_context_t;
         match self.state_context(enter_args_opt) {
             Ok(Some(scn)) => state_context_t = scn,
             Ok(None) => return Err(ParseError::new("TODO")),
             Err(parse_error) => return Err(parse_error),
         }
 
+        // pass the defaults of enter parameters of the target state that have no argument
+        if let StateContextType::StateRef { state_context_node } = &mut state_context_t {
+            if !state_context_node.deep_history {
+                let enter_msg = self.arcanum.symbol_config.enter_msg_symbol.clone();
+                let target_state_name = state_context_node.state_ref_node.name.clone();
+                if self.default_args(
+                    &mut state_context_node.enter_args_opt,
+                    &enter_msg,
+                    &target_state_name,
+                ) {
+                    self.generate_enter_args = true;
+                }
+            }
+        }
+
         // this is so we can know to declare a StateContext at the
         // top of the event handler.
         self.event_handler_has_transition = true;
@@ -3595,12 +4049,93 @@ impl<'a> Parser<'a> {
                 exit_args_opt,
                 label_opt: transition_label,
                 forward_event,
+                actions,
             },
         }))
     }
 
     /* --------------------------------------------------------------------- */
 
+    // transitionActions: '{' call* '}'
+
+    fn transition_actions(&mut self) -> Result<Vec<ExprStmtType>, ParseError> {
+        let mut actions = Vec::new();
+        while !self.check(TokenType::CloseBrace) && !self.check(TokenType::Eof) {
+            let expr_stmt_t = match self.expression()? {
+                Some(CallExprT { call_expr_node }) => CallStmtT {
+                    call_stmt_node: CallStmtNode::new(call_expr_node),
+                },
+                Some(ActionCallExprT {
+                    action_call_expr_node,
+                }) => ActionCallStmtT {
+                    action_call_stmt_node: ActionCallStmtNode::new(action_call_expr_node),
+                },
+                Some(CallChainLiteralExprT {
+                    call_chain_expr_node,
+                }) => ExprStmtType::CallChainLiteralStmtT {
+                    call_chain_literal_stmt_node: CallChainLiteralStmtNode::new(
+                        call_chain_expr_node,
+                    ),
+                },
+                _ => {
+                    self.error_at_previous("Expected an action call.");
+                    return Err(ParseError::new("TODO"));
+                }
+            };
+            actions.push(expr_stmt_t);
+        }
+        self.consume(TokenType::CloseBrace, "Expected '}'.")?;
+        Ok(actions)
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // Append the default values of the parameters of the enter or exit handler of a state that
+    // are left without arguments. Returns true if any default was appended.
+
+    fn default_args(
+        &mut self,
+        args_opt: &mut Option<ExprListNode>,
+        msg: &str,
+        state_name: &str,
+    ) -> bool {
+        if self.is_building_symbol_table {
+            return false;
+        }
+        let event_symbol_rcref = match self.arcanum.get_event(msg, &Some(state_name.to_string())) {
+            Some(event_symbol_rcref) => event_symbol_rcref,
+            None => return false,
+        };
+        let event_symbol = event_symbol_rcref.borrow();
+        let params = match &event_symbol.params_opt {
+            Some(params) => params,
+            None => return false,
+        };
+        let arg_count = args_opt
+            .as_ref()
+            .map_or(0, |expr_list_node| expr_list_node.exprs_t.len());
+        if arg_count >= params.len() {
+            return false;
+        }
+        let mut defaults = Vec::new();
+        for param in &params[arg_count..] {
+            match &param.default_opt {
+                Some(literal_expr_node) => defaults.push(ExprType::LiteralExprT {
+                    literal_expr_node: literal_expr_node.clone(),
+                }),
+                // missing arguments are reported by the type checker
+                None => return false,
+            }
+        }
+        args_opt
+            .get_or_insert_with(|| ExprListNode::new(Vec::new()))
+            .exprs_t
+            .extend(defaults);
+        true
+    }
+
+    /* --------------------------------------------------------------------- */
+
     // change_state : '->>' change_state_label state_ref
 
     fn change_state(&mut self) -> Result<Option<StatementType>, ParseError> {
@@ -3620,6 +4155,12 @@ impl<'a> Parser<'a> {
             Err(parse_error) => return Err(parse_error),
         }
 
+        if let StateContextType::StateRef { state_context_node } = &state_context_t {
+            if state_context_node.deep_history {
+                self.error_at_previous("Deep history is not supported by change-state.");
+            }
+        }
+
         Ok(Some(StatementType::ChangeStateStmt {
             change_state_stmt: ChangeStateStatementNode {
                 state_context_t,
@@ -3745,4 +4286,172 @@ impl<'a> Parser<'a> {
             Err(parse_error) => Err(parse_error),
         }
     }
+
+    /* --------------------------------------------------------------------- */
+
+    // The enum of a match test is the declared type of the tested variable
+    // or parameter. Anything else can't be matched on enumerators.
+
+    fn enum_type_of_expr(&self, expr_t: &ExprType) -> Option<Rc<RefCell<EnumDeclSymbol>>> {
+        let type_node = match expr_t {
+            VariableExprT { var_node } => var_node.get_type_opt()?,
+            CallChainLiteralExprT {
+                call_chain_expr_node,
+            } => {
+                if call_chain_expr_node.call_chain.len() != 1 {
+                    return None;
+                }
+                match call_chain_expr_node.call_chain.front()? {
+                    CallChainLiteralNodeType::VariableNodeT { var_node } => {
+                        var_node.get_type_opt()?
+                    }
+                    _ => return None,
+                }
+            }
+            ExprListT { expr_list_node } => {
+                if expr_list_node.exprs_t.len() != 1 {
+                    return None;
+                }
+                return self.enum_type_of_expr(expr_list_node.exprs_t.first()?);
+            }
+            _ => return None,
+        };
+        self.arcanum.lookup_enum(&type_node.get_type_str())
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // enum_match_test -> '?:'  ('/' match_enumerator  ('|' match_enumerator)* '/' (statement* branch_terminator?) ':>')+ ':' (statement* branch_terminator?) '::'
+
+    fn enum_match_test(&mut self, expr_t: ExprType) -> Result<EnumMatchTestNode, ParseError> {
+        // Enums are declared in the domain, after the machine, so the
+        // enumerators are only checked in the semantic pass.
+        let mut enum_symbol_rcref_opt = None;
+        if !self.is_building_symbol_table {
+            enum_symbol_rcref_opt = self.enum_type_of_expr(&expr_t);
+            if enum_symbol_rcref_opt.is_none() {
+                self.error_at_current("Enum match test requires a variable of an enum type.");
+            }
+        }
+
+        self.consume(TokenType::EnumTest, "Expected '?:'.")?;
+
+        let mut conditional_branches: Vec<EnumMatchTestMatchBranchNode> = Vec::new();
+
+        let first_branch_node = self.enum_match_test_match_branch(&enum_symbol_rcref_opt)?;
+        conditional_branches.push(first_branch_node);
+
+        while self.match_token(&[TokenType::ElseContinue]) {
+            let branch_node = self.enum_match_test_match_branch(&enum_symbol_rcref_opt)?;
+            conditional_branches.push(branch_node);
+        }
+
+        // (':' match_test_else_branch)?
+        let mut else_branch_opt: Option<EnumMatchTestElseBranchNode> = None;
+        if self.match_token(&[TokenType::Colon]) {
+            else_branch_opt = Some(self.enum_match_test_else_branch()?);
+        }
+
+        // '::'
+        self.consume(TokenType::TestTerminator, "Expected TestTerminator.")?;
+
+        let mut enum_type = String::new();
+        if let Some(enum_symbol_rcref) = &enum_symbol_rcref_opt {
+            let enum_symbol = enum_symbol_rcref.borrow();
+            enum_type = enum_symbol.name.clone();
+
+            // Without an else branch every enumerator must be matched.
+            if else_branch_opt.is_none() {
+                let missing: Vec<String> = enum_symbol
+                    .ast_node
+                    .borrow()
+                    .enums
+                    .iter()
+                    .filter(|enumerator| {
+                        !conditional_branches.iter().any(|branch| {
+                            branch.enum_match_pattern_nodes.iter().any(|pattern| {
+                                pattern.enumerator_expr_node.enumerator == enumerator.name
+                            })
+                        })
+                    })
+                    .map(|enumerator| enumerator.name.clone())
+                    .collect();
+                if !missing.is_empty() {
+                    self.error_at_previous(&format!(
+                        "Enum match test on {} is not exhaustive. Missing: {}.",
+                        enum_symbol.name,
+                        missing.join(", ")
+                    ));
+                }
+            }
+        }
+
+        Ok(EnumMatchTestNode::new(
+            expr_t,
+            enum_type,
+            conditional_branches,
+            else_branch_opt,
+        ))
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // enum_match_test_match_branch ->  '/' match_enumerator ('|' match_enumerator)* '/' (statement* branch_terminator?)
+
+    fn enum_match_test_match_branch(
+        &mut self,
+        enum_symbol_rcref_opt: &Option<Rc<RefCell<EnumDeclSymbol>>>,
+    ) -> Result<EnumMatchTestMatchBranchNode, ParseError> {
+        self.consume(TokenType::ForwardSlash, "Expected '/'.")?;
+
+        let mut match_enumerators = Vec::new();
+        loop {
+            if !self.match_token(&[TokenType::Identifier]) {
+                self.error_at_current("Expected enumerator.");
+                return Err(ParseError::new("TODO"));
+            }
+            let enumerator = self.previous().lexeme.clone();
+            let mut enum_type = String::new();
+            if let Some(enum_symbol_rcref) = enum_symbol_rcref_opt {
+                let enum_symbol = enum_symbol_rcref.borrow();
+                if !enum_symbol.ast_node.borrow().has_enumerator(&enumerator) {
+                    self.error_at_previous(&format!(
+                        "Enum {} has no enumerator {}.",
+                        enum_symbol.name, enumerator
+                    ));
+                    return Err(ParseError::new("TODO"));
+                }
+                enum_type = enum_symbol.name.clone();
+            }
+            let enumerator_expr_node = EnumeratorExprNode::new(enum_type, enumerator);
+            match_enumerators.push(EnumMatchTestPatternNode::new(enumerator_expr_node));
+
+            if !self.match_token(&[TokenType::Pipe]) {
+                break;
+            }
+        }
+
+        self.consume(TokenType::ForwardSlash, "Expected '/'.")?;
+
+        let statements = self.statements();
+        let branch_terminator_t_opt = self.branch_terminator()?;
+        Ok(EnumMatchTestMatchBranchNode::new(
+            match_enumerators,
+            statements,
+            branch_terminator_t_opt,
+        ))
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    // enum_match_test_else_branch -> statements* branch_terminator?
+
+    fn enum_match_test_else_branch(&mut self) -> Result<EnumMatchTestElseBranchNode, ParseError> {
+        let statements = self.statements();
+        let branch_terminator_opt = self.branch_terminator()?;
+        Ok(EnumMatchTestElseBranchNode::new(
+            statements,
+            branch_terminator_opt,
+        ))
+    }
 }
@@ -0,0 +1,153 @@
+//! Starter files for a new machine in a Rust package built with `frame_build`.
+//!
+//! A new machine is a spec, and a Rust file next to it that includes the code generated for the
+//! spec, as recommended by `frame_build`. The Rust file implements the actions of the machine as
+//! inherent methods, which take precedence over the generated empty implementation of the actions
+//! trait, and ends with the test skeleton of the machine; see the `test_skeleton` module. The
+//! recording actions of the skeleton replace those of the Rust file in test builds.
+
+use crate::frame_c::ast::SystemNode;
+use crate::frame_c::config::{NameCase, RustConfig};
+use crate::frame_c::formatter;
+use crate::frame_c::test_skeleton;
+
+/// The name of the files of a machine, e.g. `traffic_light` for `TrafficLight`.
+pub fn file_stem(name: &str) -> String {
+    NameCase::Snake.convert(name)
+}
+
+/// The name of the system of a machine, e.g. `TrafficLight` for `traffic_light`.
+pub fn system_name(name: &str) -> String {
+    NameCase::UpperCamel.convert(name)
+}
+
+/// A starter spec for a machine with the given name: two states, the events switching between
+/// them, and an action called when entering one of them.
+pub fn spec(name: &str) -> String {
+    let spec = format!(
+        "#{system}
+    -interface-
+    start
+    stop
+
+    -machine-
+    $Idle
+        |start| -> $Running ^
+
+    $Running
+        |>| log(\"running\") ^
+        |stop| -> $Idle ^
+
+    -actions-
+    log [msg:String]
+##
+",
+        system = system_name(name)
+    );
+    formatter::format_source(&spec)
+}
+
+/// The names of the methods the Rust file next to a spec implements for the actions of the
+/// machine that have no body in the spec, e.g. `log` for `log [msg:String]`.
+pub fn action_methods(system_node: &SystemNode, config: &RustConfig) -> Vec<String> {
+    let actions_block_node = match &system_node.actions_block_node_opt {
+        Some(actions_block_node) => actions_block_node,
+        None => return Vec::new(),
+    };
+    actions_block_node
+        .actions
+        .iter()
+        .map(|action_node_rcref| action_node_rcref.borrow())
+        .filter(|action_node| action_node.code_opt.is_none())
+        .map(|action_node| {
+            format!(
+                "{}{}{}",
+                config.code.action_prefix,
+                config.format_value_name(&action_node.name),
+                config.code.action_suffix
+            )
+        })
+        .collect()
+}
+
+/// The Rust file to put next to a spec, which includes the code generated for it from
+/// `include_path` in `OUT_DIR`, implements its actions with empty methods returning default
+/// values outside of tests, and has its test skeleton.
+pub fn wrapper(system_node: &SystemNode, config: &RustConfig, include_path: &str) -> String {
+    let system_type_name = config.format_type_name(&system_node.name);
+    let mut code = format!(
+        "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"{}\"));\n",
+        include_path
+    );
+
+    if let Some(actions_block_node) = &system_node.actions_block_node_opt {
+        code.push_str(&format!(
+            "\n#[cfg(not(test))]\nimpl {} {{\n",
+            system_type_name
+        ));
+        for (i, action_node_rcref) in actions_block_node.actions.iter().enumerate() {
+            let action_node = action_node_rcref.borrow();
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str(&format!(
+                "    fn {}{}{}(&self",
+                config.code.action_prefix,
+                config.format_value_name(&action_node.name),
+                config.code.action_suffix
+            ));
+            for param in action_node.params.iter().flatten() {
+                let param_type = match &param.param_type_opt {
+                    Some(type_node) => type_node.get_type_str(),
+                    None => String::from("<?>"),
+                };
+                code.push_str(&format!(
+                    ", _{}: {}",
+                    config.format_value_name(&param.param_name),
+                    param_type
+                ));
+            }
+            code.push(')');
+            match &action_node.type_opt {
+                Some(type_node) => code.push_str(&format!(
+                    " -> {} {{\n        Default::default()\n    }}\n",
+                    type_node.get_type_str()
+                )),
+                None => code.push_str(" {}\n"),
+            }
+        }
+        code.push_str("}\n");
+    }
+
+    code.push('\n');
+    code.push_str(&test_skeleton::generate(system_node, config));
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+    use crate::frame_c::config::FrameConfig;
+
+    /// Test that the starter spec is formatted, and that its Rust file implements its actions.
+    #[test]
+    fn starter_files() {
+        assert_eq!(file_stem("TrafficLight"), "traffic_light");
+        assert_eq!(system_name("traffic-light"), "TrafficLight");
+
+        let spec = spec("traffic_light");
+        assert!(formatter::is_formatted(&spec));
+        assert!(spec.contains("#TrafficLight\n"));
+
+        let system_node = Exe::new().parse(None, spec).unwrap();
+        let config = FrameConfig::default().codegen.rust;
+        let code = wrapper(&system_node, &config, "machines/traffic_light.rs");
+        assert!(code.starts_with(
+            "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"machines/traffic_light.rs\"));\n\n\
+             #[cfg(not(test))]\nimpl TrafficLight {\n    fn log(&self, _msg: String) {}\n}\n"
+        ));
+        assert!(code.contains("#[cfg(test)]\nmod tests {\n"));
+        assert_eq!(action_methods(&system_node, &config), ["log"]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::compiler::Exe;
+use crate::frame_c::diagnostics::Diagnostic;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
@@ -7,6 +8,7 @@ enum MatchType {
     Bool,
     String,
     Number,
+    Enum,
     //    None,
 }
 
@@ -18,6 +20,7 @@ pub(crate) struct Scanner {
     token_str: String,
     pub has_errors: bool,
     pub errors: String,
+    pub diagnostics: Vec<Diagnostic>,
     // The test_t_stack stack is to parse nested tests.  It is necessary
     // because the tokenizer should change how is scans the matches based
     // on the test type. Therefore we have to remember that
@@ -38,6 +41,7 @@ impl Scanner {
             ("false".to_string(), TokenType::False),
             ("var".to_string(), TokenType::Var),
             ("const".to_string(), TokenType::Const),
+            ("enum".to_string(), TokenType::Enum),
             ("-interface-".to_string(), TokenType::InterfaceBlock),
             ("-machine-".to_string(), TokenType::MachineBlock),
             ("-actions-".to_string(), TokenType::ActionsBlock),
@@ -55,6 +59,7 @@ impl Scanner {
             token_str: String::new(),
             has_errors: false,
             errors: String::new(),
+            diagnostics: Vec::new(),
             test_t_stack: Vec::new(),
             line: 1,
             keywords,
@@ -65,7 +70,7 @@ impl Scanner {
     // NOTE! The self param is NOT &self. That is how
     // the member variable token can move ownership to the
     // caller.
-    pub fn scan_tokens(mut self) -> (bool, String, Vec<Token>) {
+    pub fn scan_tokens(mut self) -> (bool, String, Vec<Diagnostic>, Vec<Token>) {
         // Scan header
         while self.is_whitespace() {
             self.advance();
@@ -73,7 +78,12 @@ impl Scanner {
         if self.peek() == '`' {
             self.sync_start();
             if !self.match_first_header_token() {
-                return (self.has_errors, self.errors.clone(), self.tokens);
+                return (
+                    self.has_errors,
+                    self.errors.clone(),
+                    self.diagnostics,
+                    self.tokens,
+                );
             }
             self.sync_start();
             while !self.is_at_end() {
@@ -103,7 +113,12 @@ impl Scanner {
             self.start,
             len,
         ));
-        (self.has_errors, self.errors.clone(), self.tokens)
+        (
+            self.has_errors,
+            self.errors.clone(),
+            self.diagnostics,
+            self.tokens,
+        )
     }
 
     fn is_whitespace(&self) -> bool {
@@ -260,6 +275,10 @@ impl Scanner {
                     self.add_token(TokenType::NumberTest);
                     // Store the context for the parse
                     self.test_t_stack.push(MatchType::Number);
+                } else if self.match_char(':') {
+                    self.add_token(TokenType::EnumTest);
+                    // Store the context for the parse
+                    self.test_t_stack.push(MatchType::Enum);
                 } else {
                     self.add_token(TokenType::BoolTestTrue);
                     // Store the context for the parse
@@ -303,7 +322,8 @@ impl Scanner {
                     if self.match_char('-') {
                         self.multi_line_comment();
                     } else {
-                        panic!("Unexpected character.");
+                        self.error(self.line, "Found unexpected character '-', expected '{--'.");
+                        self.add_token(TokenType::Error);
                     }
                 } else {
                     self.add_token(TokenType::OpenBrace);
@@ -328,6 +348,8 @@ impl Scanner {
             '#' => {
                 if self.match_char('#') {
                     self.add_token(TokenType::SystemEnd);
+                } else if self.directive_keyword("include") {
+                    self.add_token(TokenType::Include); // #include
                 } else if self.match_char('[') {
                     self.add_token(TokenType::OuterAttribute) // #[
                 } else if self.match_char('!') {
@@ -388,15 +410,21 @@ impl Scanner {
             return false;
         }
         self.current += 1;
-        self.token_str = String::from(&self.source[self.start..self.current]);
+        self.token_str = self.lexeme(self.start, self.current);
 
         true
     }
 
+    /// The text of the source between two byte offsets. The scanner works on bytes, so the
+    /// offsets may split a multi-byte character, which is replaced rather than panicking.
+    fn lexeme(&self, start: usize, end: usize) -> String {
+        String::from_utf8_lossy(&self.source.as_bytes()[start..end]).into_owned()
+    }
+
     // TODO: beware - mixing UTF-8 strings and chars here
     fn advance(&mut self) -> char {
         self.current += 1;
-        self.token_str = String::from(&self.source[self.start..self.current]);
+        self.token_str = self.lexeme(self.start, self.current);
         let c: char = self.source.as_bytes()[self.current - 1] as char;
         if c == '\n' {
             self.line += 1;
@@ -445,7 +473,7 @@ impl Scanner {
             self.advance();
         }
         // See if the identifier is a reserved word.
-        let text = &self.source[self.start..self.current].to_owned();
+        let text = &self.lexeme(self.start, self.current);
 
         let kw = &self.keywords.get(text);
         if let Some(keyword) = kw {
@@ -517,7 +545,7 @@ impl Scanner {
     // match_number_test -> '/' match_number_pattern ('|' match_number_pattern)* '/'
 
     fn scan_number_match(&mut self) {
-        while self.peek() != '/' {
+        while !self.is_at_end() && self.peek() != '/' {
             if self.peek() == '|' {
                 self.number();
                 self.advance();
@@ -529,8 +557,8 @@ impl Scanner {
 
         self.sync_start();
         if !self.match_char('/') {
-            // TODO
-            panic!("todo");
+            self.error(self.line, "Unterminated number pattern, expected '/'.");
+            return;
         }
         self.add_token_sync_start(TokenType::ForwardSlash);
     }
@@ -571,6 +599,23 @@ impl Scanner {
         false
     }
 
+    // Match a directive name such as `include` following a '#'. The name must not
+    // be followed by an identifier character, so `#includes` is still a system name.
+    fn directive_keyword(&mut self, name: &str) -> bool {
+        let start_pos = self.current;
+        for c in name.chars() {
+            if !self.match_char(c) {
+                self.current = start_pos;
+                return false;
+            }
+        }
+        if self.is_alpha_numeric(self.peek()) {
+            self.current = start_pos;
+            return false;
+        }
+        true
+    }
+
     fn is_alpha(&self, c: char) -> bool {
         ('a'..='z').contains(&c) || ('A'..='Z').contains(&c) || c == '_'
     }
@@ -590,7 +635,7 @@ impl Scanner {
     }
 
     fn add_token_literal(&mut self, tok_type: TokenType, literal: TokenLiteral) {
-        let lex = self.source[self.start..self.current].to_owned();
+        let lex = self.lexeme(self.start, self.current);
         let len = self.current - self.start;
         self.tokens.push(Token::new(
             tok_type, lex, literal, self.line, self.start, len,
@@ -598,7 +643,7 @@ impl Scanner {
     }
 
     fn add_string_token_literal(&mut self, tok_type: TokenType, literal: TokenLiteral) {
-        let lex = self.source[self.start + 1..self.current - 1].to_owned();
+        let lex = self.lexeme(self.start + 1, self.current - 1);
         let len = self.current - self.start;
         self.tokens.push(Token::new(
             tok_type, lex, literal, self.line, self.start, len,
@@ -609,9 +654,16 @@ impl Scanner {
         let error = &format!("Line {} : Error: {}\n", line, error_msg);
         self.has_errors = true;
         self.errors.push_str(error);
+        let end = self.current.max(self.start);
+        self.diagnostics.push(
+            Diagnostic::error("scan-error", error_msg)
+                .with_line(line)
+                .with_span(self.start, end - self.start),
+        );
     }
 
     fn string(&mut self) {
+        let start_line = self.line;
         while !self.is_at_end() {
             let c = self.peek();
             if c == '\\' {
@@ -629,7 +681,8 @@ impl Scanner {
 
         // Unterminated string.
         if self.is_at_end() {
-            self.error(self.line, "Unterminated string.");
+            self.error(start_line, "Unterminated string.");
+            return;
         }
 
         self.advance();
@@ -694,6 +747,7 @@ pub enum TokenType {
     SystemEnd,      // ##
     OuterAttribute, // #[
     InnerAttribute, // #![
+    Include,        // #include
     InterfaceBlock, // -interface-
     MachineBlock,   // -machine-
     ActionsBlock,   // -actions-
@@ -710,6 +764,7 @@ pub enum TokenType {
     Number,                  // 1, 1.01
     Var,                     // let
     Const,                   // const
+    Enum,                    // enum
     SingleLineComment,       // --- comment
     MultiLineComment,        // {-- comments --}
     OpenBrace,               // {
@@ -727,6 +782,7 @@ pub enum TokenType {
     BoolTestFalse,           // ?!
     StringTest,              // ?~
     NumberTest,              // ?#
+    EnumTest,                // ?:
     ElseContinue,            // :>
     TestTerminator,          // ::
     ForwardSlash,            // /
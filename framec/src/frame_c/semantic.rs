@@ -0,0 +1,678 @@
+//! The resolved semantic model of a Frame specification.
+//!
+//! Where the [`ast`](crate::frame_c::ast) mirrors the syntax of a specification, the semantic
+//! model lists its symbols - states, interface methods, actions and variables, with their
+//! types - and every place in an event handler that refers to one of them. Identifiers in the
+//! AST are already resolved to their declaring scope by the parser, so tooling can answer
+//! questions like "which handlers reference domain variable `x`" without re-implementing Frame's
+//! scoping rules.
+//!
+//! ```
+//! use framec::frame_c::compiler::Exe;
+//! use framec::frame_c::semantic::{SemanticModel, SymbolKind};
+//!
+//! let spec = "#Counter\n-interface-\ninc\n-machine-\n$Counting\n|inc| count = count + 1 ^\n\
+//!             -domain-\nvar count:i32 = 0\n##\n";
+//! let system_node = Exe::new().parse(None, spec.to_string()).unwrap();
+//! let model = SemanticModel::new(&system_node);
+//! let handlers = model.handlers_referencing(SymbolKind::DomainVariable, "count");
+//! assert_eq!(handlers, vec![("Counting", "inc")]);
+//! ```
+
+use crate::frame_c::ast::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The kind of a symbol declared in a specification.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SymbolKind {
+    State,
+    InterfaceMethod,
+    Action,
+    DomainVariable,
+    StateParam,
+    StateVariable,
+    EventHandlerParam,
+    EventHandlerVariable,
+}
+
+/// A variable or parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The declared type, if any.
+    pub type_opt: Option<String>,
+}
+
+/// A state of the machine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    pub name: String,
+    /// The state that events not handled by this state are dispatched to, if any.
+    pub parent_opt: Option<String>,
+    pub params: Vec<Variable>,
+    pub variables: Vec<Variable>,
+    pub handlers: Vec<Handler>,
+    pub line: usize,
+}
+
+/// An event handler of a state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Handler {
+    /// The message the handler responds to, e.g. `>` for the enter event.
+    pub message: String,
+    pub params: Vec<Variable>,
+    /// The variables declared in the body of the handler.
+    pub variables: Vec<Variable>,
+    pub line: usize,
+}
+
+/// A method of the interface block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterfaceMethod {
+    pub name: String,
+    pub params: Vec<Variable>,
+    pub return_type_opt: Option<String>,
+    pub line: usize,
+}
+
+/// An action declared in the actions block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    pub name: String,
+    pub params: Vec<Variable>,
+    pub return_type_opt: Option<String>,
+}
+
+/// A use of a symbol in an event handler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reference {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// The state whose handler contains the reference.
+    pub state: String,
+    /// The message of the handler that contains the reference, or empty for references in the
+    /// initializers of state variables.
+    pub message: String,
+    /// The line of the reference, or of its handler for transitions and state changes.
+    pub line: usize,
+}
+
+/// The symbols of a specification and the references to them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SemanticModel {
+    pub system_name: String,
+    pub states: Vec<State>,
+    pub interface_methods: Vec<InterfaceMethod>,
+    pub actions: Vec<Action>,
+    pub domain_variables: Vec<Variable>,
+    /// References in the order they appear in the specification.
+    pub references: Vec<Reference>,
+}
+
+impl SemanticModel {
+    /// Build the semantic model of a parsed specification.
+    pub fn new(system_node: &SystemNode) -> SemanticModel {
+        let mut model = SemanticModel {
+            system_name: system_node.name.clone(),
+            ..SemanticModel::default()
+        };
+
+        if let Some(interface_block_node) = &system_node.interface_block_node_opt {
+            for interface_method_rcref in &interface_block_node.interface_methods {
+                let interface_method_node = interface_method_rcref.borrow();
+                model.interface_methods.push(InterfaceMethod {
+                    name: interface_method_node.name.clone(),
+                    params: parameters(
+                        &interface_method_node.params,
+                        SymbolKind::EventHandlerParam,
+                    ),
+                    return_type_opt: type_name(&interface_method_node.return_type_opt),
+                    line: interface_method_node.line,
+                });
+            }
+        }
+
+        if let Some(actions_block_node) = &system_node.actions_block_node_opt {
+            for action_rcref in &actions_block_node.actions {
+                let action_node = action_rcref.borrow();
+                model.actions.push(Action {
+                    name: action_node.name.clone(),
+                    params: parameters(&action_node.params, SymbolKind::EventHandlerParam),
+                    return_type_opt: type_name(&action_node.type_opt),
+                });
+            }
+        }
+
+        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+            for variable_decl_rcref in &domain_block_node.member_variables {
+                model.domain_variables.push(variable(
+                    &variable_decl_rcref.borrow(),
+                    SymbolKind::DomainVariable,
+                ));
+            }
+        }
+
+        if let Some(machine_block_node) = &system_node.machine_block_node_opt {
+            for state_node_rcref in &machine_block_node.states {
+                let state = model.state_model(&state_node_rcref.borrow());
+                model.states.push(state);
+            }
+        }
+
+        model
+    }
+
+    /// Look up a state by name.
+    pub fn state(&self, name: &str) -> Option<&State> {
+        self.states.iter().find(|state| state.name == name)
+    }
+
+    /// The references to the symbol with the given kind and name. Names of state and handler
+    /// scoped variables are only unique within their state or handler, see
+    /// [`Reference::state`] and [`Reference::message`].
+    pub fn references_to<'a>(
+        &'a self,
+        kind: SymbolKind,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Reference> + 'a {
+        self.references
+            .iter()
+            .filter(move |reference| reference.kind == kind && reference.name == name)
+    }
+
+    /// The handlers that refer to the symbol with the given kind and name, as `(state, message)`
+    /// pairs in the order they appear in the specification.
+    pub fn handlers_referencing(&self, kind: SymbolKind, name: &str) -> Vec<(&str, &str)> {
+        let mut handlers: Vec<(&str, &str)> = Vec::new();
+        for reference in &self.references {
+            if reference.kind != kind || reference.name != name {
+                continue;
+            }
+            let handler = (reference.state.as_str(), reference.message.as_str());
+            if !handlers.contains(&handler) {
+                handlers.push(handler);
+            }
+        }
+        handlers
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    fn state_model(&mut self, state_node: &StateNode) -> State {
+        let mut state = State {
+            name: state_node.name.clone(),
+            parent_opt: state_node
+                .dispatch_opt
+                .as_ref()
+                .map(|dispatch_node| dispatch_node.target_state_ref.name.clone()),
+            params: parameters(&state_node.params_opt, SymbolKind::StateParam),
+            variables: Vec::new(),
+            handlers: Vec::new(),
+            line: state_node.line,
+        };
+        if let Some(vars) = &state_node.vars_opt {
+            for variable_decl_rcref in vars {
+                let variable_decl_node = variable_decl_rcref.borrow();
+                state
+                    .variables
+                    .push(variable(&variable_decl_node, SymbolKind::StateVariable));
+                if let Some(expr_t) = &variable_decl_node.initializer_expr_t_opt {
+                    let mut walker = ReferenceWalker::new(&state_node.name, "", state_node.line);
+                    walker.expr(expr_t);
+                    self.references.append(&mut walker.references);
+                }
+            }
+        }
+
+        let mut handler_nodes: Vec<&Rc<RefCell<EventHandlerNode>>> = Vec::new();
+        handler_nodes.extend(&state_node.enter_event_handler_opt);
+        handler_nodes.extend(&state_node.evt_handlers_rcref);
+        handler_nodes.extend(&state_node.exit_event_handler_opt);
+        // the enter and exit handlers are also in the list of all handlers
+        let mut seen: Vec<*const RefCell<EventHandlerNode>> = Vec::new();
+        for handler_rcref in handler_nodes {
+            if seen.contains(&Rc::as_ptr(handler_rcref)) {
+                continue;
+            }
+            seen.push(Rc::as_ptr(handler_rcref));
+            let handler = self.handler_model(&state_node.name, &handler_rcref.borrow());
+            state.handlers.push(handler);
+        }
+        state
+    }
+
+    fn handler_model(
+        &mut self,
+        state_name: &str,
+        event_handler_node: &EventHandlerNode,
+    ) -> Handler {
+        let message = match &event_handler_node.msg_t {
+            MessageType::CustomMessage { message_node } => message_node.name.clone(),
+            MessageType::AnyMessage { .. } => String::from("||*"),
+        };
+        let params = match &event_handler_node.event_symbol_rcref.borrow().params_opt {
+            Some(params) => params
+                .iter()
+                .map(|param| Variable {
+                    name: param.name.clone(),
+                    kind: SymbolKind::EventHandlerParam,
+                    type_opt: type_name(&param.param_type_opt),
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let mut walker = ReferenceWalker::new(state_name, &message, event_handler_node.line);
+        walker.statements(&event_handler_node.statements);
+        walker.terminator(&event_handler_node.terminator_node);
+        self.references.append(&mut walker.references);
+        let variables = walker.variables;
+
+        Handler {
+            message,
+            params,
+            variables,
+            line: event_handler_node.line,
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+fn type_name(type_opt: &Option<TypeNode>) -> Option<String> {
+    type_opt.as_ref().map(|type_node| type_node.get_type_str())
+}
+
+fn parameters(params_opt: &Option<Vec<ParameterNode>>, kind: SymbolKind) -> Vec<Variable> {
+    match params_opt {
+        Some(params) => params
+            .iter()
+            .map(|param| Variable {
+                name: param.param_name.clone(),
+                kind,
+                type_opt: type_name(&param.param_type_opt),
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn variable(variable_decl_node: &VariableDeclNode, kind: SymbolKind) -> Variable {
+    Variable {
+        name: variable_decl_node.name.clone(),
+        kind,
+        type_opt: type_name(&variable_decl_node.type_opt),
+    }
+}
+
+fn variable_kind(scope: &IdentifierDeclScope) -> Option<SymbolKind> {
+    match scope {
+        IdentifierDeclScope::DomainBlock => Some(SymbolKind::DomainVariable),
+        IdentifierDeclScope::StateParam => Some(SymbolKind::StateParam),
+        IdentifierDeclScope::StateVar => Some(SymbolKind::StateVariable),
+        IdentifierDeclScope::EventHandlerParam => Some(SymbolKind::EventHandlerParam),
+        IdentifierDeclScope::EventHandlerVar => Some(SymbolKind::EventHandlerVariable),
+        _ => None,
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+// Collects the references and variable declarations of one event handler.
+struct ReferenceWalker<'a> {
+    state: &'a str,
+    message: &'a str,
+    handler_line: usize,
+    references: Vec<Reference>,
+    variables: Vec<Variable>,
+}
+
+impl<'a> ReferenceWalker<'a> {
+    fn new(state: &'a str, message: &'a str, handler_line: usize) -> ReferenceWalker<'a> {
+        ReferenceWalker {
+            state,
+            message,
+            handler_line,
+            references: Vec::new(),
+            variables: Vec::new(),
+        }
+    }
+
+    fn reference(&mut self, kind: SymbolKind, name: &str, line: usize) {
+        self.references.push(Reference {
+            kind,
+            name: name.to_string(),
+            state: self.state.to_string(),
+            message: self.message.to_string(),
+            line,
+        });
+    }
+
+    fn statements(&mut self, statements: &[DeclOrStmtType]) {
+        for decl_or_stmt_t in statements {
+            match decl_or_stmt_t {
+                DeclOrStmtType::VarDeclT { var_decl_t_rc_ref } => {
+                    let variable_decl_node = var_decl_t_rc_ref.borrow();
+                    if let Some(expr_t) = &variable_decl_node.initializer_expr_t_opt {
+                        self.expr(expr_t);
+                    }
+                    self.variables.push(variable(
+                        &variable_decl_node,
+                        SymbolKind::EventHandlerVariable,
+                    ));
+                }
+                DeclOrStmtType::StmtT { stmt_t } => self.statement(stmt_t),
+            }
+        }
+    }
+
+    fn statement(&mut self, stmt_t: &StatementType) {
+        match stmt_t {
+            StatementType::ExpressionStmt { expr_stmt_t } => self.expr_stmt(expr_stmt_t),
+            StatementType::TransitionStmt {
+                transition_statement,
+            } => {
+                if let Some(expr_list_node) = &transition_statement.exit_args_opt {
+                    self.exprs(&expr_list_node.exprs_t);
+                }
+                for expr_stmt_t in &transition_statement.actions {
+                    self.expr_stmt(expr_stmt_t);
+                }
+                self.state_context(&transition_statement.target_state_context_t);
+            }
+            StatementType::ChangeStateStmt { change_state_stmt } => {
+                self.state_context(&change_state_stmt.state_context_t)
+            }
+            StatementType::TestStmt { test_stmt_node } => self.test(&test_stmt_node.test_t),
+            StatementType::StateStackStmt { .. } | StatementType::NoStmt => {}
+        }
+    }
+
+    fn expr_stmt(&mut self, expr_stmt_t: &ExprStmtType) {
+        match expr_stmt_t {
+            ExprStmtType::CallStmtT { call_stmt_node } => self.call(&call_stmt_node.call_expr_node),
+            ExprStmtType::ActionCallStmtT {
+                action_call_stmt_node,
+            } => self.action_call(&action_call_stmt_node.action_call_expr_node),
+            ExprStmtType::CallChainLiteralStmtT {
+                call_chain_literal_stmt_node,
+            } => self.call_chain(&call_chain_literal_stmt_node.call_chain_literal_expr_node),
+            ExprStmtType::AssignmentStmtT {
+                assignment_stmt_node,
+            } => self.assignment(&assignment_stmt_node.assignment_expr_node),
+            ExprStmtType::VariableStmtT { variable_stmt_node } => {
+                self.variable(&variable_stmt_node.var_node)
+            }
+        }
+    }
+
+    fn test(&mut self, test_t: &TestType) {
+        match test_t {
+            TestType::BoolTest { bool_test_node } => {
+                for branch_node in &bool_test_node.conditional_branch_nodes {
+                    self.expr(&branch_node.expr_t);
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &bool_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::StringMatchTest {
+                string_match_test_node,
+            } => {
+                self.expr(&string_match_test_node.expr_t);
+                for branch_node in &string_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &string_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::NumberMatchTest {
+                number_match_test_node,
+            } => {
+                self.expr(&number_match_test_node.expr_t);
+                for branch_node in &number_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &number_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                self.expr(&enum_match_test_node.expr_t);
+                for branch_node in &enum_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &enum_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+        }
+    }
+
+    fn branch(&mut self, statements: &[DeclOrStmtType], terminator_opt: &Option<TerminatorExpr>) {
+        self.statements(statements);
+        if let Some(terminator_expr) = terminator_opt {
+            self.terminator(terminator_expr);
+        }
+    }
+
+    fn terminator(&mut self, terminator_expr: &TerminatorExpr) {
+        if let Some(expr_t) = &terminator_expr.return_expr_t_opt {
+            self.expr(expr_t);
+        }
+    }
+
+    fn state_context(&mut self, state_context_t: &StateContextType) {
+        if let StateContextType::StateRef { state_context_node } = state_context_t {
+            let state_name = &state_context_node.state_ref_node.name;
+            self.reference(SymbolKind::State, state_name, self.handler_line);
+            if let Some(expr_list_node) = &state_context_node.state_ref_args_opt {
+                self.exprs(&expr_list_node.exprs_t);
+            }
+            if let Some(expr_list_node) = &state_context_node.enter_args_opt {
+                self.exprs(&expr_list_node.exprs_t);
+            }
+        }
+    }
+
+    fn exprs(&mut self, exprs_t: &[ExprType]) {
+        for expr_t in exprs_t {
+            self.expr(expr_t);
+        }
+    }
+
+    fn expr(&mut self, expr_t: &ExprType) {
+        match expr_t {
+            ExprType::AssignmentExprT {
+                assignment_expr_node,
+            } => self.assignment(assignment_expr_node),
+            ExprType::ActionCallExprT {
+                action_call_expr_node,
+            } => self.action_call(action_call_expr_node),
+            ExprType::CallChainLiteralExprT {
+                call_chain_expr_node,
+            } => self.call_chain(call_chain_expr_node),
+            ExprType::CallExprT { call_expr_node } => self.call(call_expr_node),
+            ExprType::CallExprListT {
+                call_expr_list_node,
+            } => self.exprs(&call_expr_list_node.exprs_t),
+            ExprType::ExprListT { expr_list_node } => self.exprs(&expr_list_node.exprs_t),
+            ExprType::VariableExprT { var_node } => self.variable(var_node),
+            ExprType::UnaryExprT { unary_expr_node } => {
+                self.expr(&unary_expr_node.right_rcref.borrow())
+            }
+            ExprType::BinaryExprT { binary_expr_node } => {
+                self.expr(&binary_expr_node.left_rcref.borrow());
+                self.expr(&binary_expr_node.right_rcref.borrow());
+            }
+            ExprType::LiteralExprT { .. }
+            | ExprType::StateStackOperationExprT { .. }
+            | ExprType::FrameEventExprT { .. }
+            | ExprType::EnumeratorExprT { .. } => {}
+        }
+    }
+
+    fn assignment(&mut self, assignment_expr_node: &AssignmentExprNode) {
+        self.expr(&assignment_expr_node.l_value_box);
+        self.expr(&assignment_expr_node.r_value_box);
+    }
+
+    fn variable(&mut self, var_node: &VariableNode) {
+        if let Some(kind) = variable_kind(&var_node.scope) {
+            self.reference(kind, &var_node.id_node.name.lexeme, var_node.id_node.line);
+        }
+    }
+
+    fn action_call(&mut self, action_call_expr_node: &ActionCallExprNode) {
+        let identifier = &action_call_expr_node.identifier;
+        self.reference(SymbolKind::Action, &identifier.name.lexeme, identifier.line);
+        self.exprs(&action_call_expr_node.call_expr_list.exprs_t);
+    }
+
+    fn call(&mut self, call_expr_node: &CallExprNode) {
+        self.exprs(&call_expr_node.call_expr_list.exprs_t);
+    }
+
+    fn call_chain(&mut self, call_chain_expr_node: &CallChainLiteralExprNode) {
+        for call_chain_node_t in &call_chain_expr_node.call_chain {
+            match call_chain_node_t {
+                CallChainLiteralNodeType::VariableNodeT { var_node } => self.variable(var_node),
+                CallChainLiteralNodeType::IdentifierNodeT { .. } => {}
+                CallChainLiteralNodeType::CallT { call } => self.call(call),
+                CallChainLiteralNodeType::InterfaceMethodCallT {
+                    interface_method_call_expr_node,
+                } => {
+                    let identifier = &interface_method_call_expr_node.identifier;
+                    self.reference(
+                        SymbolKind::InterfaceMethod,
+                        &identifier.name.lexeme,
+                        identifier.line,
+                    );
+                    self.exprs(&interface_method_call_expr_node.call_expr_list.exprs_t);
+                }
+                CallChainLiteralNodeType::ActionCallT {
+                    action_call_expr_node,
+                } => self.action_call(action_call_expr_node),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+
+    const SPEC: &str = "#Door
+    -interface-
+    open [force:bool]
+    close
+    -machine-
+    $Closed
+        |open| [force:bool]
+            force ? -> $Open ^ ::
+            var tries:i32 = attempts + 1
+            attempts = tries
+            log(\"locked\") ^
+    $Open => $Base
+        var since:i32 = attempts
+        |close| -> $Closed ^
+    $Base
+        |>| log(\"base\") ^
+    -actions-
+    log [msg:String]
+    -domain-
+    var attempts:i32 = 0
+##
+";
+
+    fn model() -> SemanticModel {
+        let system_node = Exe::new()
+            .parse(None, SPEC.to_string())
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        SemanticModel::new(&system_node)
+    }
+
+    /// Test that the declared symbols and their types are collected.
+    #[test]
+    fn symbols() {
+        let model = model();
+        assert_eq!(model.system_name, "Door");
+        let names: Vec<_> = model
+            .states
+            .iter()
+            .map(|state| state.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Closed", "Open", "Base"]);
+        let open = model.state("Open").unwrap();
+        assert_eq!(open.parent_opt.as_deref(), Some("Base"));
+        assert_eq!(open.variables[0].name, "since");
+        assert_eq!(open.variables[0].kind, SymbolKind::StateVariable);
+        let handler = &model.state("Closed").unwrap().handlers[0];
+        assert_eq!(handler.message, "open");
+        assert_eq!(handler.params[0].type_opt.as_deref(), Some("bool"));
+        assert_eq!(handler.variables[0].name, "tries");
+        assert_eq!(model.domain_variables[0].type_opt.as_deref(), Some("i32"));
+        assert_eq!(model.actions[0].params[0].name, "msg");
+        assert_eq!(model.interface_methods.len(), 2);
+    }
+
+    /// Test that references are resolved to the kind of symbol they refer to.
+    #[test]
+    fn references() {
+        let model = model();
+        assert_eq!(
+            model.handlers_referencing(SymbolKind::DomainVariable, "attempts"),
+            vec![("Closed", "open"), ("Open", "")]
+        );
+        assert_eq!(
+            model.handlers_referencing(SymbolKind::Action, "log"),
+            vec![("Closed", "open"), ("Base", ">")]
+        );
+        assert_eq!(
+            model.handlers_referencing(SymbolKind::State, "Closed"),
+            vec![("Open", "close")]
+        );
+        let tries: Vec<_> = model
+            .references_to(SymbolKind::EventHandlerVariable, "tries")
+            .map(|reference| reference.line)
+            .collect();
+        assert_eq!(tries, vec![10]);
+        assert_eq!(
+            model
+                .references_to(SymbolKind::EventHandlerParam, "force")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,883 @@
+//! A simulator of machines, for trying out a spec before its actions are written.
+//!
+//! The simulator interprets the intermediate representation of a machine (see the `ir` module),
+//! loaded from a spec or from the JSON emitted by the `ir` target. Events are sent by name and
+//! take the transitions of the handler of the current state or, if it has none for the event,
+//! of the nearest ancestor that has. Guards, actions and other code of the spec are not run:
+//! when a handler has guarded transitions, the simulator asks which branch to take, and the
+//! values of variables are the text of their initializers unless set by hand.
+//!
+//! `framec simulate lamp.frm` reads commands from stdin, one per line; see `HELP` for the
+//! commands. Lines in the format of the scripts of `frame_runtime::coverage` are commands too, so
+//! the simulator can replay them. So can traces of running machines recorded by
+//! `frame_runtime::trace`, with `replay <file>`, which takes the recorded branches instead of
+//! asking. Without a terminal, e.g. when a script is piped in, the first failing command stops the
+//! simulator with an error.
+
+use crate::frame_c::ir::{MachineModel, StateModel, TransitionModel, VariableModel};
+use crate::frame_c::utils::RunError;
+use frame_runtime::trace::{Trace, TraceRecord};
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// The commands of the simulator.
+pub const HELP: &str = "\
+Commands:
+  <event> [args]      send an interface event, e.g. `deposit 10`; same as `send <event> [args]`
+  <n>                 take the n-th branch when asked to choose one
+  new                 restart the machine in its initial state
+  state [name]        show the current state, or fail unless it is the named state
+  states | events     list the states or the interface events
+  vars                show the domain variables and the variables of the current state
+  set <var> <value>   set the value of a variable
+  break <name>        stop scripts when the state is entered or before the event is sent
+  unbreak <name>      remove a breakpoint; `breaks` lists them
+  run <file>          run the commands of a file, up to the first breakpoint
+  replay <file>       replay a .frmtrace file recorded from a running machine
+  trace [file]        show the trace of the simulation, or write it to a file
+  help | quit
+Lines starting with # are comments.
+";
+
+/// A branch the simulator asks the user to choose between.
+#[derive(Clone, Debug)]
+enum Choice {
+    /// Take a transition to the given target.
+    Transition(TransitionModel, String),
+    /// Take no transition, if all transitions of the handler are guarded.
+    Stay,
+}
+
+impl Choice {
+    fn describe(&self, state: &str) -> String {
+        match self {
+            Choice::Transition(transition, target) => {
+                let mut description = describe_transition(state, transition, target);
+                if let Some(guard) = &transition.guard {
+                    description.push_str(&format!(" if {}", guard));
+                }
+                description
+            }
+            Choice::Stay => format!("stay in ${}", state),
+        }
+    }
+}
+
+/// The state of a simulated machine.
+pub struct Simulator {
+    model: MachineModel,
+    state: Option<String>,
+    domain: Vec<(String, String)>,
+    state_vars: Vec<(String, String)>,
+    /// The branches to choose between before the next event can be sent.
+    choices: Vec<Choice>,
+    /// The states entered since the last command.
+    entered: Vec<String>,
+    trace: Vec<String>,
+}
+
+impl Simulator {
+    /// Start simulating a machine in its initial state.
+    pub fn new(model: MachineModel) -> Simulator {
+        let mut simulator = Simulator {
+            model,
+            state: None,
+            domain: Vec::new(),
+            state_vars: Vec::new(),
+            choices: Vec::new(),
+            entered: Vec::new(),
+            trace: Vec::new(),
+        };
+        simulator.reset();
+        simulator
+    }
+
+    /// Restart the machine in its initial state, taking the transitions of its enter handler.
+    pub fn reset(&mut self) {
+        self.domain = initial_values(&self.model.domain);
+        self.choices.clear();
+        self.entered.clear();
+        self.trace.push(format!("new {}", self.model.name));
+        self.state = None;
+        if let Some(initial_state) = self.model.initial_state.clone() {
+            self.enter(&initial_state);
+        }
+    }
+
+    /// The current state, if the machine has states.
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// The trace of events, transitions and choices of the simulation.
+    pub fn trace(&self) -> &[String] {
+        &self.trace
+    }
+
+    /// Send an interface event with the given arguments, which are only recorded in the trace.
+    pub fn send(&mut self, event: &str, args: &[&str]) -> Result<(), String> {
+        self.entered.clear();
+        if !self.choices.is_empty() {
+            return Err("Choose a branch first.".to_string());
+        }
+        if !self
+            .model
+            .interface
+            .iter()
+            .any(|method| method.name == event)
+        {
+            return Err(format!("Unknown event `{}`, see `events`.", event));
+        }
+        self.trace.push(
+            format!("|{}| {}", event, args.join(" "))
+                .trim_end()
+                .to_string(),
+        );
+        let state = match &self.state {
+            Some(state) => state.clone(),
+            None => return Ok(()),
+        };
+
+        let mut handled_by = None;
+        for state_model in self.ancestry(&state) {
+            let handlers = state_model
+                .handlers
+                .iter()
+                .filter(|handler| handler.event == event || handler.event == "||*");
+            for handler in handlers {
+                handled_by.get_or_insert_with(|| state_model.name.clone());
+                if !handler.transitions.is_empty() {
+                    let transitions = handler.transitions.clone();
+                    self.branch(&transitions);
+                    return Ok(());
+                }
+            }
+        }
+        match handled_by {
+            Some(handler_state) => self.trace.push(format!("  handled by ${}", handler_state)),
+            None => self.trace.push("  ignored".to_string()),
+        }
+        Ok(())
+    }
+
+    /// The branches to choose between, as descriptions numbered from 1.
+    pub fn choices(&self) -> Vec<String> {
+        let state = self.state.clone().unwrap_or_default();
+        self.choices
+            .iter()
+            .map(|choice| choice.describe(&state))
+            .collect()
+    }
+
+    /// Take the n-th branch, counting from 1.
+    pub fn choose(&mut self, n: usize) -> Result<(), String> {
+        self.entered.clear();
+        if n == 0 || n > self.choices.len() {
+            return Err(format!("Choose a branch from 1 to {}.", self.choices.len()));
+        }
+        let choice = self.choices[n - 1].clone();
+        self.choices.clear();
+        let state = self.state.clone().unwrap_or_default();
+        self.trace
+            .push(format!("  chose {}: {}", n, choice.describe(&state)));
+        self.take(choice);
+        Ok(())
+    }
+
+    /// The states entered by the last event or choice.
+    pub fn entered(&self) -> &[String] {
+        &self.entered
+    }
+
+    /// The domain variables and the variables of the current state, with their values.
+    pub fn variables(&self) -> Vec<(String, String)> {
+        let mut variables = self.domain.clone();
+        if let Some(state) = &self.state {
+            for (name, value) in &self.state_vars {
+                variables.push((format!("${}.{}", state, name), value.clone()));
+            }
+        }
+        variables
+    }
+
+    /// Set the value of a domain variable or, prefixed with the state as shown by `variables`
+    /// or not, of a variable of the current state.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let state_prefix = self.state.as_ref().map(|state| format!("${}.", state));
+        let state_var_name = state_prefix
+            .as_ref()
+            .and_then(|prefix| name.strip_prefix(prefix.as_str()))
+            .unwrap_or(name);
+        let domain = &mut self.domain;
+        let variable = self
+            .state_vars
+            .iter_mut()
+            .find(|(var_name, _)| var_name == state_var_name)
+            .or_else(|| domain.iter_mut().find(|(var_name, _)| var_name == name));
+        match variable {
+            Some((_, var_value)) => {
+                *var_value = value.to_string();
+                self.trace.push(format!("  set {} = {}", name, value));
+                Ok(())
+            }
+            None => Err(format!("Unknown variable `{}`, see `vars`.", name)),
+        }
+    }
+
+    /// Restart the machine and send it the events of a trace recorded from a running machine,
+    /// taking the branches it took. The domain variables are set to the values of the snapshots
+    /// in the trace. Fails if the machine can't take the recorded path, e.g. because the spec
+    /// changed since the trace was recorded. Returns the number of events sent.
+    pub fn replay(&mut self, trace: &Trace) -> Result<usize, String> {
+        if trace.machine != self.model.name {
+            return Err(format!(
+                "The trace is of machine `{}`, not `{}`.",
+                trace.machine, self.model.name
+            ));
+        }
+        self.reset();
+        let mut events = 0;
+        // the state the machine is expected to be in, after the transitions recorded last
+        let mut expected: Option<&str> = None;
+        for (i, record) in trace.records.iter().enumerate() {
+            let location = format!("record {} of the trace", i + 1);
+            match record {
+                TraceRecord::Event {
+                    event, arguments, ..
+                } => {
+                    self.check_replayed(expected.take(), &location)?;
+                    let args: Vec<&str> =
+                        arguments.iter().map(|(_, value)| value.as_str()).collect();
+                    self.send(event, &args)
+                        .map_err(|err| format!("{} ({})", err, location))?;
+                    events += 1;
+                }
+                TraceRecord::Transition {
+                    change_state,
+                    target,
+                    ..
+                } => {
+                    if !self.choices.is_empty() {
+                        let n = self
+                            .choices
+                            .iter()
+                            .position(|choice| match choice {
+                                Choice::Transition(transition, choice_target) => {
+                                    choice_target == target
+                                        && transition.change_state == *change_state
+                                }
+                                Choice::Stay => false,
+                            })
+                            .ok_or_else(|| {
+                                format!("No branch leads to ${} ({}).", target, location)
+                            })?;
+                        self.choose(n + 1)?;
+                    }
+                    expected = Some(target);
+                }
+                TraceRecord::Snapshot {
+                    state, variables, ..
+                } => {
+                    self.check_replayed(expected.take(), &location)?;
+                    self.check_replayed(Some(state), &location)?;
+                    for (name, value) in variables {
+                        // the simulator doesn't keep the arguments of states
+                        let _ = self.set(name, value);
+                    }
+                }
+                TraceRecord::Return { .. } => {}
+            }
+        }
+        self.check_replayed(expected, "the end of the trace")?;
+        Ok(events)
+    }
+
+    /// Check that the machine is in the state the trace expects, if any, after staying in the
+    /// current state where the trace recorded no transition.
+    fn check_replayed(&mut self, expected: Option<&str>, location: &str) -> Result<(), String> {
+        if self
+            .choices
+            .iter()
+            .any(|choice| matches!(choice, Choice::Stay))
+        {
+            let n = self.choices.len();
+            self.choose(n)?;
+        }
+        if !self.choices.is_empty() {
+            return Err(format!("The trace took no branch ({}).", location));
+        }
+        let state = self.state.as_deref().unwrap_or_default();
+        match expected {
+            Some(expected) if expected != state => Err(format!(
+                "Expected state ${}, but in ${} ({}).",
+                expected, state, location
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The machine being simulated.
+    pub fn model(&self) -> &MachineModel {
+        &self.model
+    }
+
+    fn state_model(&self, name: &str) -> Option<&StateModel> {
+        self.model.states.iter().find(|state| state.name == name)
+    }
+
+    /// The state and its ancestors, starting with the state.
+    fn ancestry(&self, name: &str) -> Vec<StateModel> {
+        let mut ancestry: Vec<StateModel> = Vec::new();
+        let mut next = Some(name.to_string());
+        while let Some(name) = next {
+            match self.state_model(&name) {
+                Some(state) if !ancestry.iter().any(|other| other.name == state.name) => {
+                    next = state.parent.clone();
+                    ancestry.push(state.clone());
+                }
+                _ => break,
+            }
+        }
+        ancestry
+    }
+
+    /// Take the transitions of a handler, or ask which one to take if there is a choice.
+    fn branch(&mut self, transitions: &[TransitionModel]) {
+        let mut choices = Vec::new();
+        for transition in transitions {
+            match &transition.target {
+                Some(target) => {
+                    choices.push(Choice::Transition(transition.clone(), target.clone()))
+                }
+                // the simulator doesn't track the state stack, so any state may be popped
+                None => {
+                    for state in &self.model.states {
+                        choices.push(Choice::Transition(transition.clone(), state.name.clone()));
+                    }
+                }
+            }
+        }
+        if transitions
+            .iter()
+            .all(|transition| transition.guard.is_some())
+        {
+            choices.push(Choice::Stay);
+        }
+        if choices.len() == 1 {
+            self.take(choices.remove(0));
+        } else {
+            self.choices = choices;
+        }
+    }
+
+    fn take(&mut self, choice: Choice) {
+        let (transition, target) = match choice {
+            Choice::Transition(transition, target) => (transition, target),
+            Choice::Stay => return,
+        };
+        let source = self.state.clone().unwrap_or_default();
+        self.trace.push(format!(
+            "  {}",
+            describe_transition(&source, &transition, &target)
+        ));
+        if transition.change_state {
+            self.change_state(&target);
+        } else {
+            self.enter(&target);
+        }
+    }
+
+    /// Make a state the current state, without taking the transitions of its enter handler.
+    fn change_state(&mut self, name: &str) {
+        self.state = Some(name.to_string());
+        self.state_vars = self
+            .state_model(name)
+            .map(|state| initial_values(&state.vars))
+            .unwrap_or_default();
+        self.entered.push(name.to_string());
+    }
+
+    /// Enter a state and take the transitions of its enter handler.
+    fn enter(&mut self, name: &str) {
+        self.change_state(name);
+        let on_enter = self.state_model(name).and_then(|state| {
+            state
+                .handlers
+                .iter()
+                .find(|handler| handler.event == ">" && !handler.transitions.is_empty())
+                .map(|handler| handler.transitions.clone())
+        });
+        if let Some(transitions) = on_enter {
+            // a cycle of enter transitions would never stop
+            if self.entered.len() <= self.model.states.len() {
+                self.branch(&transitions);
+            }
+        }
+    }
+}
+
+fn initial_values(variables: &[VariableModel]) -> Vec<(String, String)> {
+    variables
+        .iter()
+        .map(|variable| {
+            (
+                variable.name.clone(),
+                variable.value.clone().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn describe_transition(source: &str, transition: &TransitionModel, target: &str) -> String {
+    let arrow = if transition.change_state { "->>" } else { "->" };
+    let mut description = format!("${} {} ${}", source, arrow, target);
+    if transition.target.is_none() {
+        description.push_str(" (popped)");
+    }
+    if let Some(label) = &transition.label {
+        description.push_str(&format!(" \"{}\"", label));
+    }
+    description
+}
+
+/// What to do after a command.
+enum Control {
+    Continue,
+    /// Stop running a script at a breakpoint.
+    Break(String),
+    Quit,
+}
+
+/// The command loop of the simulator.
+struct Repl<'a> {
+    simulator: Simulator,
+    breakpoints: Vec<String>,
+    output: &'a mut dyn Write,
+}
+
+impl Repl<'_> {
+    fn print(&mut self, text: &str) -> Result<(), String> {
+        writeln!(self.output, "{}", text).map_err(|err| format!("Error writing output: {}", err))
+    }
+
+    /// Print the trace of a command and the branches to choose from, if any.
+    fn report(&mut self, trace_start: usize) -> Result<Control, String> {
+        let trace = self.simulator.trace()[trace_start..].to_vec();
+        for line in trace {
+            self.print(&line)?;
+        }
+        let choices = self.simulator.choices();
+        if !choices.is_empty() {
+            self.print("Choose a branch:")?;
+            for (i, choice) in choices.iter().enumerate() {
+                self.print(&format!("  {}: {}", i + 1, choice))?;
+            }
+        }
+        let hit = self
+            .simulator
+            .entered()
+            .iter()
+            .find(|state| self.breakpoints.contains(state))
+            .cloned();
+        match hit {
+            Some(state) => Ok(Control::Break(format!("Breakpoint: entered ${}", state))),
+            None => Ok(Control::Continue),
+        }
+    }
+
+    fn execute(&mut self, line: &str) -> Result<Control, String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(Control::Continue);
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let trace_start = self.simulator.trace().len();
+        if let Ok(n) = words[0].parse::<usize>() {
+            self.simulator.choose(n)?;
+            return self.report(trace_start);
+        }
+        match (words[0], &words[1..]) {
+            ("help", _) => self.print(HELP.trim_end())?,
+            ("quit", _) | ("exit", _) => return Ok(Control::Quit),
+            ("new", _) => {
+                self.simulator.reset();
+                return self.report(trace_start);
+            }
+            ("state", []) => {
+                let state = self.simulator.state().unwrap_or_default().to_string();
+                let ancestry: Vec<String> = self
+                    .simulator
+                    .ancestry(&state)
+                    .iter()
+                    .map(|state| format!("${}", state.name))
+                    .collect();
+                self.print(&ancestry.join(" => "))?;
+            }
+            ("state", [expected]) => {
+                let state = self.simulator.state().unwrap_or_default();
+                let expected = expected.trim_start_matches('$');
+                if state != expected {
+                    return Err(format!("Expected state ${}, but in ${}.", expected, state));
+                }
+            }
+            ("states", _) => {
+                let states: Vec<String> = self
+                    .simulator
+                    .model()
+                    .states
+                    .iter()
+                    .map(|state| match &state.parent {
+                        Some(parent) => format!("${} => ${}", state.name, parent),
+                        None => format!("${}", state.name),
+                    })
+                    .collect();
+                for state in states {
+                    self.print(&state)?;
+                }
+            }
+            ("events", _) => {
+                let events: Vec<String> = self
+                    .simulator
+                    .model()
+                    .interface
+                    .iter()
+                    .map(|method| {
+                        let params: Vec<String> = method
+                            .params
+                            .iter()
+                            .map(|param| match &param.type_opt {
+                                Some(param_type) => format!("{}:{}", param.name, param_type),
+                                None => param.name.clone(),
+                            })
+                            .collect();
+                        format!("{} {}", method.name, params.join(" "))
+                            .trim_end()
+                            .to_string()
+                    })
+                    .collect();
+                for event in events {
+                    self.print(&event)?;
+                }
+            }
+            ("vars", _) => {
+                for (name, value) in self.simulator.variables() {
+                    self.print(&format!("{} = {}", name, value))?;
+                }
+            }
+            ("set", [name, value @ ..]) if !value.is_empty() => {
+                self.simulator.set(name, &value.join(" "))?;
+                return self.report(trace_start);
+            }
+            ("break", [name]) => {
+                let name = name.trim_start_matches('$');
+                let model = self.simulator.model();
+                if !model.states.iter().any(|state| state.name == name)
+                    && !model.interface.iter().any(|method| method.name == name)
+                {
+                    return Err(format!("No state or event `{}`.", name));
+                }
+                if !self.breakpoints.iter().any(|breakpoint| breakpoint == name) {
+                    self.breakpoints.push(name.to_string());
+                }
+            }
+            ("unbreak", [name]) => {
+                let name = name.trim_start_matches('$');
+                self.breakpoints.retain(|breakpoint| breakpoint != name);
+            }
+            ("breaks", _) => {
+                let breakpoints = self.breakpoints.join(" ");
+                self.print(&breakpoints)?;
+            }
+            ("trace", []) => {
+                let trace = self.simulator.trace().join("\n");
+                self.print(&trace)?;
+            }
+            ("trace", [path]) => {
+                let mut trace = self.simulator.trace().join("\n");
+                trace.push('\n');
+                std::fs::write(path, trace)
+                    .map_err(|err| format!("Error writing {}: {}", path, err))?;
+            }
+            ("run", [path]) => return self.run_file(Path::new(path)),
+            ("replay", [path]) => {
+                let text = std::fs::read_to_string(path)
+                    .map_err(|err| format!("Error reading {}: {}", path, err))?;
+                let trace = Trace::parse(&text).map_err(|err| format!("{}: {}", path, err))?;
+                self.simulator.replay(&trace)?;
+                return self.report(trace_start);
+            }
+            ("send", [event, args @ ..]) => return self.send(event, args, trace_start),
+            (event, args) => return self.send(event, args, trace_start),
+        }
+        Ok(Control::Continue)
+    }
+
+    fn send(&mut self, event: &str, args: &[&str], trace_start: usize) -> Result<Control, String> {
+        self.simulator.send(event, args)?;
+        self.report(trace_start)
+    }
+
+    /// Run the commands of a file until the end, a breakpoint or an error.
+    fn run_file(&mut self, path: &Path) -> Result<Control, String> {
+        let script = std::fs::read_to_string(path)
+            .map_err(|err| format!("Error reading {}: {}", path.display(), err))?;
+        for (i, line) in script.lines().enumerate() {
+            let location = format!("{}:{}", path.display(), i + 1);
+            let event = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                ["send", event, ..] | [event, ..] => event.to_string(),
+                [] => String::new(),
+            };
+            if self.breakpoints.contains(&event) {
+                self.print(&format!("Breakpoint: |{}| at {}", event, location))?;
+                return Ok(Control::Continue);
+            }
+            match self.execute(line) {
+                Ok(Control::Continue) => {}
+                Ok(Control::Break(message)) => {
+                    self.print(&format!("{} at {}", message, location))?;
+                    return Ok(Control::Continue);
+                }
+                Ok(Control::Quit) => return Ok(Control::Quit),
+                Err(err) => return Err(format!("{} ({})", err, location)),
+            }
+        }
+        Ok(Control::Continue)
+    }
+}
+
+/// Simulate a machine with the commands read from `input`, one per line. When `interactive`,
+/// a prompt is shown and failing commands are reported on `output`; otherwise the first failing
+/// command stops the simulation with an error.
+pub fn run(
+    model: MachineModel,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    interactive: bool,
+) -> Result<(), RunError> {
+    let io_error = |err: std::io::Error| RunError::new(exitcode::IOERR, &err.to_string());
+    let mut repl = Repl {
+        simulator: Simulator::new(model),
+        breakpoints: Vec::new(),
+        output,
+    };
+    if interactive {
+        writeln!(
+            repl.output,
+            "Simulating {}, type `help` for the commands.",
+            repl.simulator.model().name
+        )
+        .map_err(io_error)?;
+    }
+    let trace = repl.simulator.trace().join("\n");
+    writeln!(repl.output, "{}", trace).map_err(io_error)?;
+
+    let mut line_number = 0;
+    loop {
+        if interactive {
+            let prompt = if repl.simulator.choices().is_empty() {
+                "> "
+            } else {
+                "? "
+            };
+            write!(repl.output, "{}", prompt).map_err(io_error)?;
+            repl.output.flush().map_err(io_error)?;
+        }
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(io_error)? == 0 {
+            return Ok(());
+        }
+        line_number += 1;
+        match repl.execute(&line) {
+            Ok(Control::Continue) => {}
+            Ok(Control::Break(message)) => {
+                writeln!(repl.output, "{}", message).map_err(io_error)?;
+            }
+            Ok(Control::Quit) => return Ok(()),
+            Err(err) if interactive => {
+                writeln!(repl.output, "error: {}", err).map_err(io_error)?;
+            }
+            Err(err) => {
+                return Err(RunError::new(
+                    exitcode::DATAERR,
+                    &format!("{} (line {})", err, line_number),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+
+    const SPEC: &str = "\
+#Account
+    -interface-
+    deposit [amount:i32]
+    withdraw [amount:i32]
+    close
+    -machine-
+    $Init
+        |>| -> $Open ^
+    $Open => $Active
+        var withdrawals:i32 = 0
+        |withdraw| [amount:i32]
+            amount <= #.balance ? -> \"paid\" $Open :: ^
+    $Active
+        |deposit| [amount:i32] ^
+        |close| ->> $Closed ^
+    $Closed
+    -domain-
+    var balance:i32 = 0
+##
+";
+
+    fn model() -> MachineModel {
+        let system_node = Exe::new().parse(None, SPEC.to_string()).unwrap();
+        MachineModel::new(&system_node, None, "test")
+    }
+
+    fn simulate(commands: &str, interactive: bool) -> (Result<(), RunError>, String) {
+        let mut output = Vec::new();
+        let result = run(model(), &mut commands.as_bytes(), &mut output, interactive);
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    /// Test sending events through inherited handlers, choosing between guarded branches and
+    /// the transitions of enter handlers.
+    #[test]
+    fn events_and_choices() {
+        let (result, output) = simulate(
+            "# a comment\n\
+             deposit 10\n\
+             withdraw 5\n\
+             1\n\
+             withdraw 50\n\
+             2\n\
+             state Open\n\
+             send close\n\
+             state\n\
+             close\n",
+            false,
+        );
+        assert!(result.is_ok(), "{:?}", result.err().map(|err| err.error));
+        assert_eq!(
+            output,
+            "new Account\n  $Init -> $Open\n\
+             |deposit| 10\n  handled by $Active\n\
+             |withdraw| 5\n\
+             Choose a branch:\n  1: $Open -> $Open \"paid\" if amount <= balance\n  2: stay in $Open\n\
+             \x20 chose 1: $Open -> $Open \"paid\" if amount <= balance\n  $Open -> $Open \"paid\"\n\
+             |withdraw| 50\n\
+             Choose a branch:\n  1: $Open -> $Open \"paid\" if amount <= balance\n  2: stay in $Open\n\
+             \x20 chose 2: stay in $Open\n\
+             |close|\n  $Open ->> $Closed\n\
+             $Closed\n\
+             |close|\n  ignored\n"
+        );
+    }
+
+    /// Test inspecting and setting variables, and that state variables are reset when their
+    /// state is entered.
+    #[test]
+    fn variables() {
+        let mut simulator = Simulator::new(model());
+        assert_eq!(
+            simulator.variables(),
+            vec![
+                ("balance".to_string(), "0".to_string()),
+                ("$Open.withdrawals".to_string(), "0".to_string()),
+            ]
+        );
+        simulator.set("balance", "100").unwrap();
+        simulator.set("withdrawals", "1").unwrap();
+        assert!(simulator.set("missing", "1").is_err());
+        assert_eq!(simulator.variables()[1].1, "1");
+        simulator.send("withdraw", &["5"]).unwrap();
+        assert!(simulator.send("deposit", &["5"]).is_err());
+        simulator.choose(1).unwrap();
+        assert_eq!(
+            simulator.variables(),
+            vec![
+                ("balance".to_string(), "100".to_string()),
+                ("$Open.withdrawals".to_string(), "0".to_string()),
+            ]
+        );
+    }
+
+    /// Test replaying traces recorded from running machines, taking the recorded branches.
+    #[test]
+    fn replay_traces() {
+        let trace = Trace::parse(
+            "frmtrace\t1\n\
+             machine\tAccount\n\
+             snapshot\t1\tOpen\tbalance=20\t$Open.withdrawals=0\n\
+             event\t2\twithdraw\tamount=50\n\
+             event\t3\twithdraw\tamount=5\n\
+             transition\t3\ttransition\twithdraw\tOpen\tOpen\tpaid\n\
+             event\t4\tclose\n\
+             transition\t4\tchange_state\tclose\tOpen\tClosed\t\n",
+        )
+        .unwrap();
+        let mut simulator = Simulator::new(model());
+        assert_eq!(simulator.replay(&trace), Ok(3));
+        assert_eq!(simulator.state(), Some("Closed"));
+        assert_eq!(simulator.variables()[0].1, "20");
+        assert!(simulator
+            .trace()
+            .contains(&"  chose 2: stay in $Open".to_string()));
+
+        let mut diverging = trace.clone();
+        if let TraceRecord::Transition { target, .. } = &mut diverging.records[3] {
+            *target = "Closed".to_string();
+        }
+        assert_eq!(
+            simulator.replay(&diverging).unwrap_err(),
+            "No branch leads to $Closed (record 4 of the trace)."
+        );
+        diverging.machine = "Lamp".to_string();
+        assert!(simulator.replay(&diverging).is_err());
+    }
+
+    /// Test that scripts stop at breakpoints on events and states, and that errors stop the
+    /// simulation unless it is interactive.
+    #[test]
+    fn breakpoints_and_errors() {
+        let dir = std::env::temp_dir().join(format!("framec_simulator_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("script.txt");
+        std::fs::write(&script, "deposit 1\nclose\ndeposit 2\n").unwrap();
+        let trace = dir.join("trace.txt");
+
+        let commands = format!(
+            "break close\nrun {0}\nunbreak close\nbreak Closed\nrun {0}\ntrace {1}\n",
+            script.display(),
+            trace.display()
+        );
+        let (result, output) = simulate(&commands, false);
+        assert!(result.is_ok());
+        assert!(output.contains(&format!(
+            "|deposit| 1\n  handled by $Active\nBreakpoint: |close| at {}:2\n",
+            script.display()
+        )));
+        assert!(output.contains(&format!(
+            "|close|\n  $Open ->> $Closed\nBreakpoint: entered $Closed at {}:2\n",
+            script.display()
+        )));
+        assert!(!output.contains("|deposit| 2"));
+        let trace = std::fs::read_to_string(&trace).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(trace.starts_with("new Account\n  $Init -> $Open\n|deposit| 1\n"));
+        assert!(trace.ends_with("|close|\n  $Open ->> $Closed\n"));
+
+        let (result, output) = simulate("open\nstate Open\n", true);
+        assert!(result.is_ok());
+        assert!(output.contains("error: Unknown event `open`, see `events`.\n"));
+
+        let (result, _) = simulate("close\nstate Open\nclose\n", false);
+        let err = result.unwrap_err();
+        assert_eq!(err.code, exitcode::DATAERR);
+        assert_eq!(err.error, "Expected state $Open, but in $Closed. (line 2)");
+    }
+}
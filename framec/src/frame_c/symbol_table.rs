@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 // TODO: init from file
+#[derive(Clone)]
 pub struct SymbolConfig {
     pub start_msg_symbol: String,
     pub stop_msg_symbol: String,
@@ -141,6 +142,9 @@ pub enum SymbolType {
     EventHandlerVariable {
         event_handler_variable_symbol_rcref: Rc<RefCell<VariableSymbol>>,
     },
+    EnumDecl {
+        enum_symbol_rcref: Rc<RefCell<EnumDeclSymbol>>,
+    },
 }
 
 impl Symbol for SymbolType {
@@ -196,6 +200,7 @@ impl Symbol for SymbolType {
             SymbolType::EventHandlerLocalScope {
                 event_handler_local_scope_rcref,
             } => event_handler_local_scope_rcref.borrow().get_name(),
+            SymbolType::EnumDecl { enum_symbol_rcref } => enum_symbol_rcref.borrow().get_name(),
         }
     }
 }
@@ -509,6 +514,13 @@ impl SymbolTable {
                 }));
                 self.symbols.insert(name, symbol_type_rcref);
             }
+            SymbolType::EnumDecl { enum_symbol_rcref } => {
+                let name = enum_symbol_rcref.borrow().name.clone();
+                let symbol_type_rcref = Rc::new(RefCell::new(SymbolType::EnumDecl {
+                    enum_symbol_rcref: Rc::clone(enum_symbol_rcref),
+                }));
+                self.symbols.insert(name, symbol_type_rcref);
+            }
             _ => panic!("Fatal error - missing symbol type"),
         }
     }
@@ -580,6 +592,8 @@ impl fmt::Display for SymbolTable {
     }
 }
 
+// Clones share the symbol tables.
+#[derive(Clone)]
 pub struct Arcanum {
     pub root_symtab: Rc<RefCell<SymbolTable>>,
     pub current_symtab: Rc<RefCell<SymbolTable>>,
@@ -700,6 +714,19 @@ impl Arcanum {
 
     /* --------------------------------------------------------------------- */
 
+    // Enums are declared in the -domain- block and visible from every scope
+    // that does not declare a variable of the same name.
+    pub fn lookup_enum(&self, name: &str) -> Option<Rc<RefCell<EnumDeclSymbol>>> {
+        let symbol_type_rcref = self.lookup(name, &IdentifierDeclScope::None)?;
+        let symbol_type = symbol_type_rcref.borrow();
+        match &*symbol_type {
+            SymbolType::EnumDecl { enum_symbol_rcref } => Some(Rc::clone(enum_symbol_rcref)),
+            _ => None,
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
     pub fn enter_scope(&mut self, scope_t: ParseScopeType) {
         // do scope specific actions
         match &scope_t {
@@ -1043,23 +1070,25 @@ impl Arcanum {
         }
         let a = self.system_symbol_opt.as_ref().unwrap();
         let mut b = a.borrow_mut();
+        if !b.events.contains_key(&msg) {
+            b.event_names.push(msg.clone());
+        }
         b.events.insert(msg, Rc::clone(&event_symbol_rcref));
     }
 
     /* --------------------------------------------------------------------- */
 
+    /// Get all event names in the order the events were declared: interface methods first, in
+    /// the order of the interface block, then the messages of event handlers that are not
+    /// interface methods, in the order of the machine block. Generated code must not depend on
+    /// the iteration order of `events`, which differs between runs.
     pub fn get_event_names(&self) -> Vec<String> {
         let system_symbol_rcref = self.system_symbol_opt.as_ref().unwrap();
         let system_symbol = system_symbol_rcref.borrow();
-        //        let ret = system_symbol.events.iter().map(f).collect();
-        let mut ret = Vec::new();
-        for (k, _v) in system_symbol.events.iter() {
-            ret.push(k.clone());
-        }
-        ret
+        system_symbol.event_names.clone()
     }
 
-    /// Get all action names from the action block.
+    /// Get all action names from the action block, sorted by name.
     pub fn get_action_names(&self) -> Vec<String> {
         let system_symbol_rcref = self.system_symbol_opt.as_ref().unwrap();
         let system_symbol = system_symbol_rcref.borrow();
@@ -1071,6 +1100,7 @@ impl Arcanum {
                 result.push(action.clone());
             }
         }
+        result.sort();
         result
     }
 
@@ -1196,6 +1226,8 @@ pub struct SystemSymbol {
     pub name: String,
     pub symtab_rcref: Rc<RefCell<SymbolTable>>,
     pub events: HashMap<String, Rc<RefCell<EventSymbol>>>,
+    // keys of `events` in the order they were declared
+    pub event_names: Vec<String>,
     pub interface_block_symbol_opt: Option<Rc<RefCell<InterfaceBlockScopeSymbol>>>,
     pub machine_block_symbol_opt: Option<Rc<RefCell<MachineBlockScopeSymbol>>>,
     pub actions_block_symbol_opt: Option<Rc<RefCell<ActionsBlockScopeSymbol>>>,
@@ -1215,6 +1247,7 @@ impl SystemSymbol {
                 true,
             ))),
             events: HashMap::new(),
+            event_names: Vec::new(),
             interface_block_symbol_opt: None,
             machine_block_symbol_opt: None,
             actions_block_symbol_opt: None,
@@ -1658,6 +1691,7 @@ pub struct ParameterSymbol {
     pub name: String,
     pub param_type_opt: Option<TypeNode>,
     pub scope: IdentifierDeclScope,
+    pub default_opt: Option<LiteralExprNode>,
 }
 
 impl ParameterSymbol {
@@ -1670,9 +1704,15 @@ impl ParameterSymbol {
             name,
             param_type_opt: param_type,
             scope,
+            default_opt: None,
         }
     }
 
+    pub fn with_default(mut self, default_opt: Option<LiteralExprNode>) -> ParameterSymbol {
+        self.default_opt = default_opt;
+        self
+    }
+
     pub fn is_eq(&self, other: &ParameterNode) -> bool {
         if self.name != other.param_name {
             return false;
@@ -2199,6 +2239,25 @@ impl Symbol for ActionDeclSymbol {
 
 // ----------------------- //
 
+pub struct EnumDeclSymbol {
+    pub name: String,
+    pub ast_node: Rc<RefCell<EnumDeclNode>>,
+}
+
+impl EnumDeclSymbol {
+    pub fn new(name: String, ast_node: Rc<RefCell<EnumDeclNode>>) -> EnumDeclSymbol {
+        EnumDeclSymbol { name, ast_node }
+    }
+}
+
+impl Symbol for EnumDeclSymbol {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+// ----------------------- //
+
 pub struct ActionCallSymbol {
     pub name: String,
     pub ast_node: Option<Rc<RefCell<ActionCallExprNode>>>,
@@ -2229,6 +2288,7 @@ pub struct VariableSymbol {
     pub name: String,
     pub var_type: Option<TypeNode>,
     pub scope: IdentifierDeclScope,
+    pub is_constant: bool,
     pub ast_node: Option<Rc<RefCell<VariableDeclNode>>>,
 }
 
@@ -2237,11 +2297,13 @@ impl VariableSymbol {
         name: String,
         var_type: Option<TypeNode>,
         scope: IdentifierDeclScope,
+        is_constant: bool,
     ) -> VariableSymbol {
         VariableSymbol {
             name,
             var_type,
             scope,
+            is_constant,
             ast_node: None,
         }
     }
@@ -0,0 +1,261 @@
+//! The template backend, which renders machines with user-supplied Handlebars templates.
+//!
+//! Templates are loaded from the directory set by `codegen.template.code.template_dir`. The
+//! machine is rendered with `machine.hbs`, and the other templates of the directory are available
+//! as partials by the name of their file, e.g. `{{> state}}` for `state.hbs`. Templates are
+//! rendered in strict mode, so a misspelled field of the model fails the compilation, and values
+//! are not HTML-escaped.
+//!
+//! The templates are rendered with the intermediate representation of the machine, a
+//! `MachineModel` of the `ir` module. Names are those of the spec; the `snake_case`,
+//! `camel_case`, `pascal_case` and `upper_case` helpers convert them, e.g. `{{snake_case name}}`.
+//!
+//! Built-in backends may also let templates override fragments of the code they generate, such
+//! as the header of the file, with `FragmentTemplates`.
+
+use crate::frame_c::config::TemplateConfig;
+use crate::frame_c::ir::MachineModel;
+use crate::frame_c::utils::{frame_exitcode, RunError};
+use convert_case::{Case, Casing};
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+use std::fs;
+use std::path::Path;
+
+handlebars_helper!(snake_case: |name: str| name.to_case(Case::Snake));
+handlebars_helper!(camel_case: |name: str| name.to_case(Case::Camel));
+handlebars_helper!(pascal_case: |name: str| name.to_case(Case::Pascal));
+handlebars_helper!(upper_case: |name: str| name.to_case(Case::UpperSnake));
+
+fn config_error(msg: String) -> RunError {
+    RunError::new(frame_exitcode::CONFIG_ERR, &msg)
+}
+
+/// Create a registry of the `.hbs` templates of a directory, by the name of their file, with
+/// the naming helpers.
+fn load_templates(template_dir: &Path) -> Result<Handlebars<'static>, RunError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(no_escape);
+    handlebars.register_helper("snake_case", Box::new(snake_case));
+    handlebars.register_helper("camel_case", Box::new(camel_case));
+    handlebars.register_helper("pascal_case", Box::new(pascal_case));
+    handlebars.register_helper("upper_case", Box::new(upper_case));
+
+    let entries = fs::read_dir(template_dir).map_err(|err| {
+        config_error(format!(
+            "Error reading template directory {}: {}",
+            template_dir.display(),
+            err
+        ))
+    })?;
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                return Err(config_error(format!(
+                    "Error reading template directory {}: {}",
+                    template_dir.display(),
+                    err
+                )))
+            }
+        };
+        if path.extension() != Some("hbs".as_ref()) {
+            continue;
+        }
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let template = fs::read_to_string(&path).map_err(|err| {
+            config_error(format!(
+                "Error reading template {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        handlebars
+            .register_template_string(&name, template)
+            .map_err(|err| {
+                config_error(format!("Error in template {}: {}", path.display(), err))
+            })?;
+    }
+    Ok(handlebars)
+}
+
+/// Render the model of a machine with the templates of the configuration.
+pub fn render(config: &TemplateConfig, model: &MachineModel) -> Result<String, RunError> {
+    let template_dir = Path::new(&config.code.template_dir);
+    let handlebars = load_templates(template_dir)?;
+    if !handlebars.has_template("machine") {
+        return Err(config_error(format!(
+            "No machine.hbs template in {}",
+            template_dir.display()
+        )));
+    }
+
+    handlebars
+        .render("machine", model)
+        .map_err(|err| config_error(format!("Error rendering template: {}", err)))
+}
+
+/// Templates overriding fragments of the code generated by a built-in backend, such as the
+/// header of the file or the method of an interface method.
+///
+/// Each template is named after the fragment it overrides, e.g. `file_header.hbs`, and is
+/// rendered with the fields of the fragment, along with `default`, the code the backend would
+/// have generated. Fragments without a template are generated as usual.
+pub struct FragmentTemplates {
+    handlebars: Handlebars<'static>,
+    // the first error rendering a fragment
+    error: Option<String>,
+}
+
+impl FragmentTemplates {
+    /// Load the templates of a directory, which must each be named after one of `fragments`.
+    pub fn load(template_dir: &Path, fragments: &[&str]) -> Result<FragmentTemplates, RunError> {
+        let handlebars = load_templates(template_dir)?;
+        let mut names: Vec<&String> = handlebars.get_templates().keys().collect();
+        names.sort();
+        for name in names {
+            if !fragments.contains(&name.as_str()) {
+                return Err(config_error(format!(
+                    "Template {}.hbs in {} doesn't override a fragment, expected one of {}",
+                    name,
+                    template_dir.display(),
+                    fragments.join(", ")
+                )));
+            }
+        }
+        Ok(FragmentTemplates {
+            handlebars,
+            error: None,
+        })
+    }
+
+    /// Whether the fragment is overridden.
+    pub fn has(&self, fragment: &str) -> bool {
+        self.handlebars.has_template(fragment)
+    }
+
+    /// Render the template of a fragment. If rendering fails, the error is kept to fail the
+    /// compilation with and `None` is returned.
+    pub fn render(&mut self, fragment: &str, context: &serde_json::Value) -> Option<String> {
+        match self.handlebars.render(fragment, context) {
+            Ok(code) => Some(code),
+            Err(err) => {
+                self.error.get_or_insert_with(|| {
+                    format!("Error rendering template {}.hbs: {}", fragment, err)
+                });
+                None
+            }
+        }
+    }
+
+    /// The first error rendering a fragment, if any.
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+
+    const SPEC: &str = "\
+#Door
+    -interface-
+    open [speed:i32 = 1]
+    close
+    -machine-
+    $Closed
+        |open| [speed:i32]
+            speed > 0 ? unlock() -> \"push\" $Opened :: ^
+    $Opened => $Base
+        |close| ->> $Closed ^
+    $Base
+    -actions-
+    unlock
+    -domain-
+    var locked:bool = true
+##
+";
+
+    /// Write the templates to a new directory and return its path.
+    fn template_dir(name: &str, templates: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("framec_template_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file_name, template) in templates {
+            std::fs::write(dir.join(file_name), template).unwrap();
+        }
+        dir
+    }
+
+    fn run(dir: &Path) -> Result<String, RunError> {
+        let spec = format!(
+            "#[codegen.template.code.template_dir:str=\"{}\"]\n{}",
+            dir.display(),
+            SPEC
+        );
+        Exe::new().run(&None, None, spec, Some(TargetLanguage::Template))
+    }
+
+    /// Test that the machine is rendered with `machine.hbs` and the other templates as partials.
+    #[test]
+    fn render_machine() {
+        let dir = template_dir(
+            "render",
+            &[
+                (
+                    "machine.hbs",
+                    "class {{pascal_case name}} {{initial_state}}\n\
+                     {{#each interface}}fn {{snake_case name}}({{#each params}}{{name}}: {{type}} = {{default}}{{/each}})\n{{/each}}\
+                     {{#each states}}{{> state}}{{/each}}\
+                     {{#each domain}}var {{name}} = {{value}}\n{{/each}}",
+                ),
+                (
+                    "state.hbs",
+                    "state {{name}}{{#if parent}} < {{parent}}{{/if}}\n\
+                     {{#each handlers}}{{#each transitions}}  {{../event}} -> {{target}} [{{guard}}] {{label}} {{#each actions}}{{this}}{{/each}}{{#if change_state}} change{{/if}}\n{{/each}}{{/each}}",
+                ),
+                ("notes.txt", "{{not a template"),
+            ],
+        );
+        let code = run(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            code,
+            "class Door Closed\n\
+             fn open(speed: i32 = 1)\n\
+             fn close()\n\
+             state Closed\n  open -> Opened [speed > 0] push unlock()\n\
+             state Opened < Base\n  close -> Closed []   change\n\
+             state Base\n\
+             var locked = true\n"
+        );
+    }
+
+    /// Test that a missing template directory, a missing `machine.hbs` and fields that are not
+    /// in the model are reported as configuration errors.
+    #[test]
+    fn template_errors() {
+        let err = Exe::new()
+            .run(
+                &None,
+                None,
+                SPEC.to_string(),
+                Some(TargetLanguage::Template),
+            )
+            .unwrap_err();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+        assert!(err.error.contains("codegen.template.code.template_dir"));
+
+        let dir = template_dir("missing", &[("state.hbs", "{{name}}")]);
+        let err = run(&dir).unwrap_err();
+        assert!(err.error.contains("No machine.hbs template"));
+
+        std::fs::write(dir.join("machine.hbs"), "{{nmae}}").unwrap();
+        let err = run(&dir).unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(err.code, frame_exitcode::CONFIG_ERR);
+        assert!(err.error.contains("nmae"));
+    }
+}
@@ -0,0 +1,210 @@
+//! Test skeletons for machines generated by the Rust backend.
+//!
+//! A skeleton is a `tests` module to put next to the generated code of a machine. It shadows the
+//! actions of the machine in test builds with methods that record their calls, and has a stub test
+//! per interface method that sends the event to a new machine and fails with the actions called,
+//! to be turned into the assertions of the test.
+//!
+//! Actions declared as inherent methods of the machine type take precedence over the methods of
+//! the actions trait, so the recording actions replace both the generated empty implementation and
+//! a hand-written implementation of the trait.
+
+use crate::frame_c::ast::{ParameterNode, SystemNode};
+use crate::frame_c::config::RustConfig;
+
+/// Generate the test skeleton of the machine, with names formatted as by the Rust backend.
+pub fn generate(system_node: &SystemNode, config: &RustConfig) -> String {
+    let system_type_name = config.format_type_name(&system_node.name);
+    let actions = match &system_node.actions_block_node_opt {
+        Some(actions_block_node) => actions_block_node.actions.as_slice(),
+        None => &[],
+    };
+    let interface_methods = match &system_node.interface_block_node_opt {
+        Some(interface_block_node) => interface_block_node.interface_methods.as_slice(),
+        None => &[],
+    };
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "// Test skeleton for the {} machine. Each test fails until its expectations are written.\n",
+        system_type_name
+    ));
+    code.push_str("#[cfg(test)]\nmod tests {\n    use super::*;\n");
+
+    if !actions.is_empty() {
+        code.push_str("    use std::cell::RefCell;\n\n");
+        code.push_str("    thread_local! {\n");
+        code.push_str(
+            "        static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };\n",
+        );
+        code.push_str("    }\n\n");
+        code.push_str("    /// Take the names of the actions called since the last call.\n");
+        code.push_str("    fn take_calls() -> Vec<String> {\n");
+        code.push_str("        CALLS.with(|calls| calls.take())\n");
+        code.push_str("    }\n\n");
+        code.push_str(
+            "    /// Actions recording their calls, in place of the machine's actions.\n",
+        );
+        code.push_str("    #[allow(clippy::ptr_arg)]\n");
+        code.push_str(&format!("    impl {} {{\n", system_type_name));
+        for (i, action_node_rcref) in actions.iter().enumerate() {
+            let action_node = action_node_rcref.borrow();
+            let action_name = format!(
+                "{}{}{}",
+                config.code.action_prefix,
+                config.format_value_name(&action_node.name),
+                config.code.action_suffix
+            );
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str(&format!(
+                "        pub(super) fn {}(&self{})",
+                action_name,
+                format_parameters(config, &action_node.params, "_")
+            ));
+            if let Some(type_node) = &action_node.type_opt {
+                code.push_str(&format!(" -> {}", type_node.get_type_str()));
+            }
+            code.push_str(" {\n");
+            code.push_str(&format!(
+                "            CALLS.with(|calls| calls.borrow_mut().push(\"{}\".to_string()));\n",
+                action_name
+            ));
+            if action_node.type_opt.is_some() {
+                code.push_str("            Default::default()\n");
+            }
+            code.push_str("        }\n");
+        }
+        code.push_str("    }\n");
+    }
+
+    for interface_method_node_rcref in interface_methods {
+        let interface_method_node = interface_method_node_rcref.borrow();
+        let method_name = config.format_value_name(&interface_method_node.name);
+        let args = interface_method_node
+            .params
+            .iter()
+            .flatten()
+            .map(|param| match &param.param_type_opt {
+                _ if param.default_opt.is_some() => "None",
+                Some(type_node) if type_node.get_type_str().starts_with('&') => {
+                    "&Default::default()"
+                }
+                _ => "Default::default()",
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let result = if interface_method_node.return_type_opt.is_some() {
+            "let _result = "
+        } else {
+            ""
+        };
+
+        code.push_str("\n    #[test]\n");
+        code.push_str(&format!("    fn {}() {{\n", method_name));
+        code.push_str(&format!(
+            "        let mut sm = {}::new();\n",
+            system_type_name
+        ));
+        if actions.is_empty() {
+            code.push_str(&format!(
+                "        {}sm.{}({});\n",
+                result, method_name, args
+            ));
+            code.push_str("        todo!(\"check the state of the machine\");\n");
+        } else {
+            code.push_str("        take_calls();\n");
+            code.push_str(&format!(
+                "        {}sm.{}({});\n",
+                result, method_name, args
+            ));
+            code.push_str("        let calls = take_calls();\n");
+            code.push_str(
+                "        todo!(\"check the state of the machine and the actions called: {:?}\", calls);\n",
+            );
+        }
+        code.push_str("    }\n");
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+/// Format parameters as a list following `self`, with names starting with `name_prefix`.
+fn format_parameters(
+    config: &RustConfig,
+    params_opt: &Option<Vec<ParameterNode>>,
+    name_prefix: &str,
+) -> String {
+    let mut code = String::new();
+    for param in params_opt.iter().flatten() {
+        let param_type = match &param.param_type_opt {
+            Some(type_node) => type_node.get_type_str(),
+            None => String::from("<?>"),
+        };
+        code.push_str(&format!(
+            ", {}{}: {}",
+            name_prefix,
+            config.format_value_name(&param.param_name),
+            param_type
+        ));
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::Exe;
+    use crate::frame_c::config::FrameConfig;
+
+    const SPEC: &str = "\
+#Lamp
+    -interface-
+    turnOn
+    setColor [color:&String]
+    isOn : bool
+    -machine-
+    $Off
+        |turnOn| openSwitch() -> $On ^
+        |isOn| ^(false)
+    $On
+        |setColor| [color:&String] log(color) ^
+        |isOn| ^(true)
+    -actions-
+    openSwitch
+    log [msg:&String] : bool
+##
+";
+
+    fn skeleton(config: &RustConfig) -> String {
+        let system_node = Exe::new().parse(None, SPEC.to_string()).unwrap();
+        generate(&system_node, config)
+    }
+
+    /// Test that the skeleton records the calls of the actions and has a test per event.
+    #[test]
+    fn skeleton_per_machine() {
+        let code = skeleton(&FrameConfig::default().codegen.rust);
+        assert!(code.contains("    impl Lamp {\n"));
+        assert!(code.contains("        pub(super) fn open_switch(&self) {\n"));
+        assert!(code.contains("        pub(super) fn log(&self, _msg: &String) -> bool {\n"));
+        assert!(code.contains("calls.borrow_mut().push(\"log\".to_string())"));
+        assert!(code.contains("    fn turn_on() {\n        let mut sm = Lamp::new();\n"));
+        assert!(code.contains("        sm.set_color(&Default::default());\n"));
+        assert!(code.contains("        let _result = sm.is_on();\n"));
+        assert_eq!(code.matches("#[test]").count(), 3);
+    }
+
+    /// Test that the skeleton follows the naming configuration of the Rust backend.
+    #[test]
+    fn naming() {
+        let mut config = FrameConfig::default().codegen.rust;
+        config.features.follow_rust_naming = false;
+        config.code.action_prefix = "do_".to_string();
+        let code = skeleton(&config);
+        assert!(code.contains("        pub(super) fn do_openSwitch(&self) {\n"));
+        assert!(code.contains("    fn turnOn() {\n"));
+    }
+}
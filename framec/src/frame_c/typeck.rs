@@ -0,0 +1,828 @@
+//! Type checking of the expressions in a Frame specification.
+//!
+//! Frame passes the types in a specification through to the generated code as strings, so a
+//! mismatch would otherwise only be found by the compiler of the target language, in generated
+//! code. This pass infers the types of expressions from the declared types of variables,
+//! parameters and actions and from literals, and checks them against the declared types where
+//! they meet:
+//!
+//! * initializers of domain, state and handler variables,
+//! * assignments to variables,
+//! * return values of interface method handlers,
+//! * arguments of transitions, both state arguments and enter and exit arguments, which are
+//!   also checked for their number,
+//! * conditions of tests.
+//!
+//! Calls of actions are not checked, and their values are of unknown type, since the generated
+//! actions may be shadowed by methods of the state machine with other signatures.
+//!
+//! Types declared in a specification may be aliases or types of the target language that the
+//! pass knows nothing about, so only mismatches between the primitive types of Rust - `bool`,
+//! `char`, the integer and floating point types and `String` - and literals are reported.
+//! Expressions whose type can't be inferred are never reported.
+
+use crate::frame_c::ast::*;
+use crate::frame_c::diagnostics::Diagnostic;
+use crate::frame_c::scanner::{Token, TokenType};
+use crate::frame_c::symbol_table::SymbolType;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// Check the types of the expressions in a parsed specification. Every mismatch is reported as
+/// an error with the code `type_mismatch`, whose severity the `type_mismatch` lint level then
+/// sets; it is a warning by default.
+pub fn check(system_node: &SystemNode) -> Vec<Diagnostic> {
+    let mut checker = TypeChecker::new(system_node);
+
+    if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+        for variable_decl_node_rcref in &domain_block_node.member_variables {
+            checker.variable_decl(&variable_decl_node_rcref.borrow(), system_node.line);
+        }
+    }
+    if let Some(machine_block_node) = &system_node.machine_block_node_opt {
+        for state_node_rcref in &machine_block_node.states {
+            checker.state(&state_node_rcref.borrow());
+        }
+    }
+    checker.diagnostics
+}
+
+/* --------------------------------------------------------------------- */
+
+/// The type of an expression, as far as it can be inferred.
+#[derive(Clone, Debug, PartialEq)]
+enum Type {
+    Unknown,
+    Named(String),
+    IntegerLiteral,
+    FloatLiteral,
+}
+
+impl Type {
+    fn of(type_opt: &Option<TypeNode>) -> Type {
+        match type_opt {
+            Some(type_node) => Type::Named(type_node.get_type_str().trim().to_string()),
+            None => Type::Unknown,
+        }
+    }
+
+    /// The primitive type this type is known to be, if any.
+    fn primitive(&self) -> Option<&str> {
+        match self {
+            Type::Named(name) if is_primitive(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Can a value of type `found` be used where this type is expected? Only types known to
+    /// differ are incompatible.
+    fn accepts(&self, found: &Type) -> bool {
+        let expected = match self.primitive() {
+            Some(expected) => expected,
+            None => return true,
+        };
+        match found {
+            Type::Named(_) => found.primitive().map_or(true, |found| found == expected),
+            Type::IntegerLiteral => is_integer(expected),
+            Type::FloatLiteral => is_float(expected),
+            Type::Unknown => true,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Unknown => write!(f, "an unknown type"),
+            Type::Named(name) => write!(f, "`{}`", name),
+            Type::IntegerLiteral => write!(f, "an integer literal"),
+            Type::FloatLiteral => write!(f, "a float literal"),
+        }
+    }
+}
+
+fn is_integer(name: &str) -> bool {
+    matches!(
+        name,
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+    )
+}
+
+fn is_float(name: &str) -> bool {
+    matches!(name, "f32" | "f64")
+}
+
+fn is_primitive(name: &str) -> bool {
+    is_integer(name) || is_float(name) || matches!(name, "bool" | "char" | "String")
+}
+
+/* --------------------------------------------------------------------- */
+
+/// The declared parameters of a state and of its enter and exit handlers.
+struct StateSignature {
+    params: Vec<Type>,
+    enter_params: Vec<Type>,
+    exit_params: Vec<Type>,
+}
+
+fn handler_params(handler_opt: &Option<Rc<RefCell<EventHandlerNode>>>) -> Vec<Type> {
+    match handler_opt {
+        Some(handler_rcref) => handler_rcref
+            .borrow()
+            .event_symbol_rcref
+            .borrow()
+            .params_opt
+            .iter()
+            .flatten()
+            .map(|param| Type::of(&param.param_type_opt))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+struct TypeChecker {
+    /// The return types of interface methods by their messages.
+    return_types: HashMap<String, Type>,
+    states: HashMap<String, StateSignature>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl TypeChecker {
+    fn new(system_node: &SystemNode) -> TypeChecker {
+        let mut checker = TypeChecker {
+            return_types: HashMap::new(),
+            states: HashMap::new(),
+            diagnostics: Vec::new(),
+        };
+        if let Some(interface_block_node) = &system_node.interface_block_node_opt {
+            for interface_method_node_rcref in &interface_block_node.interface_methods {
+                let interface_method_node = interface_method_node_rcref.borrow();
+                let message = match &interface_method_node.alias {
+                    Some(message_node) => message_node.name.clone(),
+                    None => interface_method_node.name.clone(),
+                };
+                checker
+                    .return_types
+                    .insert(message, Type::of(&interface_method_node.return_type_opt));
+            }
+        }
+        if let Some(machine_block_node) = &system_node.machine_block_node_opt {
+            for state_node_rcref in &machine_block_node.states {
+                let state_node = state_node_rcref.borrow();
+                let params = state_node
+                    .params_opt
+                    .iter()
+                    .flatten()
+                    .map(|param| Type::of(&param.param_type_opt))
+                    .collect();
+                checker.states.insert(
+                    state_node.name.clone(),
+                    StateSignature {
+                        params,
+                        enter_params: handler_params(&state_node.enter_event_handler_opt),
+                        exit_params: handler_params(&state_node.exit_event_handler_opt),
+                    },
+                );
+            }
+        }
+        checker
+    }
+
+    fn state(&mut self, state_node: &StateNode) {
+        for variable_decl_node_rcref in state_node.vars_opt.iter().flatten() {
+            self.variable_decl(&variable_decl_node_rcref.borrow(), state_node.line);
+        }
+        for handler_rcref in &state_node.evt_handlers_rcref {
+            let handler_node = handler_rcref.borrow();
+            let mut handler = HandlerChecker {
+                checker: self,
+                state_name: &state_node.name,
+                line: handler_node.line,
+            };
+            handler.statements(&handler_node.statements);
+            handler.terminator(&handler_node.terminator_node);
+            if let MessageType::CustomMessage { message_node } = &handler_node.msg_t {
+                handler.return_value(&message_node.name, &handler_node.terminator_node);
+            }
+        }
+    }
+
+    fn variable_decl(&mut self, variable_decl_node: &VariableDeclNode, line: usize) {
+        if let Some(expr_t) = &variable_decl_node.initializer_expr_t_opt {
+            let found = self.expr_type(expr_t);
+            let expected = Type::of(&variable_decl_node.type_opt);
+            if !expected.accepts(&found) {
+                let message = format!(
+                    "Initializer of `{}` has {}, expected {}.",
+                    variable_decl_node.name, found, expected
+                );
+                self.report(&message, expr_t, line);
+            }
+            self.nested_expr(expr_t, line);
+        }
+    }
+
+    /* --------------------------------------------------------------------- */
+
+    /// Infer the type of an expression.
+    fn expr_type(&self, expr_t: &ExprType) -> Type {
+        match expr_t {
+            ExprType::LiteralExprT { literal_expr_node } => match literal_expr_node.token_t {
+                TokenType::Number if literal_expr_node.value.contains('.') => Type::FloatLiteral,
+                TokenType::Number => Type::IntegerLiteral,
+                TokenType::String if !literal_expr_node.is_reference => {
+                    Type::Named(String::from("String"))
+                }
+                TokenType::True | TokenType::False => Type::Named(String::from("bool")),
+                _ => Type::Unknown,
+            },
+            ExprType::VariableExprT { var_node } => variable_type(var_node),
+            ExprType::CallChainLiteralExprT {
+                call_chain_expr_node,
+            } => match call_chain_expr_node.call_chain.iter().collect::<Vec<_>>()[..] {
+                [CallChainLiteralNodeType::VariableNodeT { var_node }] => variable_type(var_node),
+                _ => Type::Unknown,
+            },
+            ExprType::ExprListT { expr_list_node } if expr_list_node.exprs_t.len() == 1 => {
+                self.expr_type(&expr_list_node.exprs_t[0])
+            }
+            ExprType::UnaryExprT { unary_expr_node } => match unary_expr_node.operator {
+                OperatorType::Not => Type::Named(String::from("bool")),
+                _ => self.expr_type(&unary_expr_node.right_rcref.borrow()),
+            },
+            ExprType::BinaryExprT { binary_expr_node } => match binary_expr_node.operator {
+                OperatorType::Plus
+                | OperatorType::Minus
+                | OperatorType::Multiply
+                | OperatorType::Divide => {
+                    let left = self.expr_type(&binary_expr_node.left_rcref.borrow());
+                    let right = self.expr_type(&binary_expr_node.right_rcref.borrow());
+                    match (left, right) {
+                        (Type::Named(name), _) | (_, Type::Named(name)) => Type::Named(name),
+                        (left, right) if left == right => left,
+                        _ => Type::Unknown,
+                    }
+                }
+                _ => Type::Named(String::from("bool")),
+            },
+            _ => Type::Unknown,
+        }
+    }
+
+    /// Check the calls nested in an expression.
+    fn nested_expr(&mut self, expr_t: &ExprType, line: usize) {
+        match expr_t {
+            ExprType::AssignmentExprT {
+                assignment_expr_node,
+            } => self.assignment(assignment_expr_node),
+            ExprType::ActionCallExprT {
+                action_call_expr_node,
+            } => self.nested_exprs(&action_call_expr_node.call_expr_list.exprs_t, line),
+            ExprType::CallChainLiteralExprT {
+                call_chain_expr_node,
+            } => self.call_chain(call_chain_expr_node, line),
+            ExprType::CallExprT { call_expr_node } => {
+                self.nested_exprs(&call_expr_node.call_expr_list.exprs_t, line)
+            }
+            ExprType::CallExprListT {
+                call_expr_list_node,
+            } => self.nested_exprs(&call_expr_list_node.exprs_t, line),
+            ExprType::ExprListT { expr_list_node } => {
+                self.nested_exprs(&expr_list_node.exprs_t, line)
+            }
+            ExprType::UnaryExprT { unary_expr_node } => {
+                self.nested_expr(&unary_expr_node.right_rcref.borrow(), line)
+            }
+            ExprType::BinaryExprT { binary_expr_node } => {
+                self.nested_expr(&binary_expr_node.left_rcref.borrow(), line);
+                self.nested_expr(&binary_expr_node.right_rcref.borrow(), line);
+            }
+            _ => {}
+        }
+    }
+
+    fn nested_exprs(&mut self, exprs_t: &[ExprType], line: usize) {
+        for expr_t in exprs_t {
+            self.nested_expr(expr_t, line);
+        }
+    }
+
+    fn call_chain(&mut self, call_chain_expr_node: &CallChainLiteralExprNode, line: usize) {
+        for call_chain_node_t in &call_chain_expr_node.call_chain {
+            match call_chain_node_t {
+                CallChainLiteralNodeType::CallT { call } => {
+                    self.nested_exprs(&call.call_expr_list.exprs_t, line)
+                }
+                CallChainLiteralNodeType::InterfaceMethodCallT {
+                    interface_method_call_expr_node,
+                } => self.nested_exprs(
+                    &interface_method_call_expr_node.call_expr_list.exprs_t,
+                    line,
+                ),
+                CallChainLiteralNodeType::ActionCallT {
+                    action_call_expr_node,
+                } => self.nested_exprs(&action_call_expr_node.call_expr_list.exprs_t, line),
+                CallChainLiteralNodeType::VariableNodeT { .. }
+                | CallChainLiteralNodeType::IdentifierNodeT { .. } => {}
+            }
+        }
+    }
+
+    fn assignment(&mut self, assignment_expr_node: &AssignmentExprNode) {
+        let line = assignment_expr_node.line;
+        let l_value = &assignment_expr_node.l_value_box;
+        let r_value = &assignment_expr_node.r_value_box;
+        let expected = self.expr_type(l_value);
+        let found = self.expr_type(r_value);
+        if !expected.accepts(&found) {
+            let name = expr_token(l_value).map_or(String::new(), |token| token.lexeme);
+            let message = format!(
+                "Value assigned to `{}` has {}, expected {}.",
+                name, found, expected
+            );
+            self.report(&message, r_value, line);
+        }
+        self.nested_expr(r_value, line);
+    }
+
+    /// Check the number and the types of arguments passed to the parameters of `what`.
+    fn arguments(
+        &mut self,
+        what: &str,
+        kind: &str,
+        params: &[Type],
+        args: &[ExprType],
+        line: usize,
+    ) {
+        if params.len() != args.len() {
+            let message = format!(
+                "{} takes {} {}{}, but {} {} given.",
+                capitalize(what),
+                params.len(),
+                kind,
+                if params.len() == 1 { "" } else { "s" },
+                args.len(),
+                if args.len() == 1 { "was" } else { "were" }
+            );
+            let mut diagnostic = Diagnostic::error("type_mismatch", &message).with_line(line);
+            if let Some(token) = args.iter().find_map(expr_token) {
+                diagnostic = located(diagnostic, &token);
+            }
+            self.diagnostics.push(diagnostic);
+            return;
+        }
+        for (i, (expected, arg)) in params.iter().zip(args).enumerate() {
+            let found = self.expr_type(arg);
+            if !expected.accepts(&found) {
+                let message = format!(
+                    "{} {} of {} has {}, expected {}.",
+                    capitalize(kind),
+                    i + 1,
+                    what,
+                    found,
+                    expected
+                );
+                self.report(&message, arg, line);
+            }
+        }
+    }
+
+    /// Report a mismatch located at the first identifier of an expression, or at `line`.
+    fn report(&mut self, message: &str, expr_t: &ExprType, line: usize) {
+        let diagnostic = Diagnostic::error("type_mismatch", message).with_line(line);
+        self.diagnostics.push(match expr_token(expr_t) {
+            Some(token) => located(diagnostic, &token),
+            None => diagnostic,
+        });
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+/// Checks the statements of one event handler.
+struct HandlerChecker<'a> {
+    checker: &'a mut TypeChecker,
+    state_name: &'a str,
+    line: usize,
+}
+
+impl<'a> HandlerChecker<'a> {
+    fn statements(&mut self, statements: &[DeclOrStmtType]) {
+        for decl_or_stmt_t in statements {
+            match decl_or_stmt_t {
+                DeclOrStmtType::VarDeclT { var_decl_t_rc_ref } => self
+                    .checker
+                    .variable_decl(&var_decl_t_rc_ref.borrow(), self.line),
+                DeclOrStmtType::StmtT { stmt_t } => self.statement(stmt_t),
+            }
+        }
+    }
+
+    fn statement(&mut self, stmt_t: &StatementType) {
+        match stmt_t {
+            StatementType::ExpressionStmt { expr_stmt_t } => self.expr_stmt(expr_stmt_t),
+            StatementType::TransitionStmt {
+                transition_statement,
+            } => {
+                if let Some(expr_list_node) = &transition_statement.exit_args_opt {
+                    let exit_params = match self.checker.states.get(self.state_name) {
+                        Some(state) => state.exit_params.clone(),
+                        None => Vec::new(),
+                    };
+                    let what = format!("the exit handler of ${}", self.state_name);
+                    self.checker.arguments(
+                        &what,
+                        "exit argument",
+                        &exit_params,
+                        &expr_list_node.exprs_t,
+                        self.line,
+                    );
+                    self.checker
+                        .nested_exprs(&expr_list_node.exprs_t, self.line);
+                }
+                for expr_stmt_t in &transition_statement.actions {
+                    self.expr_stmt(expr_stmt_t);
+                }
+                self.state_context(&transition_statement.target_state_context_t, true);
+            }
+            StatementType::ChangeStateStmt { change_state_stmt } => {
+                self.state_context(&change_state_stmt.state_context_t, false)
+            }
+            StatementType::TestStmt { test_stmt_node } => self.test(&test_stmt_node.test_t),
+            StatementType::StateStackStmt { .. } | StatementType::NoStmt => {}
+        }
+    }
+
+    fn expr_stmt(&mut self, expr_stmt_t: &ExprStmtType) {
+        match expr_stmt_t {
+            ExprStmtType::CallStmtT { call_stmt_node } => self.checker.nested_exprs(
+                &call_stmt_node.call_expr_node.call_expr_list.exprs_t,
+                self.line,
+            ),
+            ExprStmtType::ActionCallStmtT {
+                action_call_stmt_node,
+            } => self.checker.nested_exprs(
+                &action_call_stmt_node
+                    .action_call_expr_node
+                    .call_expr_list
+                    .exprs_t,
+                self.line,
+            ),
+            ExprStmtType::CallChainLiteralStmtT {
+                call_chain_literal_stmt_node,
+            } => self.checker.call_chain(
+                &call_chain_literal_stmt_node.call_chain_literal_expr_node,
+                self.line,
+            ),
+            ExprStmtType::AssignmentStmtT {
+                assignment_stmt_node,
+            } => self
+                .checker
+                .assignment(&assignment_stmt_node.assignment_expr_node),
+            ExprStmtType::VariableStmtT { .. } => {}
+        }
+    }
+
+    /// Check the arguments passed to the target state, and to its enter handler when `enter` is
+    /// set, which is not the case for changes of state.
+    fn state_context(&mut self, state_context_t: &StateContextType, enter: bool) {
+        let state_context_node = match state_context_t {
+            StateContextType::StateRef { state_context_node } => state_context_node,
+            StateContextType::StateStackPop {} => return,
+        };
+        let target = &state_context_node.state_ref_node.name;
+        let (params, enter_params) = match self.checker.states.get(target) {
+            Some(state) => (state.params.clone(), state.enter_params.clone()),
+            None => return,
+        };
+        // transitions to deep history restore the arguments the state was entered with
+        if state_context_node.deep_history {
+            return;
+        }
+        let no_args = Vec::new();
+        let args = state_context_node
+            .state_ref_args_opt
+            .as_ref()
+            .map_or(&no_args, |expr_list_node| &expr_list_node.exprs_t);
+        let what = format!("${}", target);
+        self.checker
+            .arguments(&what, "state argument", &params, args, self.line);
+        self.checker.nested_exprs(args, self.line);
+
+        if !enter {
+            return;
+        }
+        let args = state_context_node
+            .enter_args_opt
+            .as_ref()
+            .map_or(&no_args, |expr_list_node| &expr_list_node.exprs_t);
+        let what = format!("the enter handler of ${}", target);
+        self.checker
+            .arguments(&what, "enter argument", &enter_params, args, self.line);
+        self.checker.nested_exprs(args, self.line);
+    }
+
+    fn test(&mut self, test_t: &TestType) {
+        match test_t {
+            TestType::BoolTest { bool_test_node } => {
+                for branch_node in &bool_test_node.conditional_branch_nodes {
+                    let found = self.checker.expr_type(&branch_node.expr_t);
+                    if !Type::Named(String::from("bool")).accepts(&found) {
+                        let message = format!("Condition has {}, expected `bool`.", found);
+                        self.checker
+                            .report(&message, &branch_node.expr_t, self.line);
+                    }
+                    self.checker.nested_expr(&branch_node.expr_t, self.line);
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &bool_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::StringMatchTest {
+                string_match_test_node,
+            } => {
+                self.checker
+                    .nested_expr(&string_match_test_node.expr_t, self.line);
+                for branch_node in &string_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &string_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::NumberMatchTest {
+                number_match_test_node,
+            } => {
+                self.checker
+                    .nested_expr(&number_match_test_node.expr_t, self.line);
+                for branch_node in &number_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &number_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                for branch_node in &enum_match_test_node.match_branch_nodes {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+                if let Some(branch_node) = &enum_match_test_node.else_branch_node_opt {
+                    self.branch(
+                        &branch_node.statements,
+                        &branch_node.branch_terminator_expr_opt,
+                    );
+                }
+            }
+        }
+    }
+
+    fn branch(&mut self, statements: &[DeclOrStmtType], terminator_opt: &Option<TerminatorExpr>) {
+        self.statements(statements);
+        if let Some(terminator_expr) = terminator_opt {
+            self.terminator(terminator_expr);
+        }
+    }
+
+    fn terminator(&mut self, terminator_expr: &TerminatorExpr) {
+        if let Some(expr_t) = &terminator_expr.return_expr_t_opt {
+            self.checker.nested_expr(expr_t, terminator_expr.line);
+        }
+    }
+
+    /// Check the value returned by the handler of an interface method against its return type.
+    fn return_value(&mut self, message: &str, terminator_expr: &TerminatorExpr) {
+        let expr_t = match &terminator_expr.return_expr_t_opt {
+            Some(expr_t) => expr_t,
+            None => return,
+        };
+        let expected = match self.checker.return_types.get(message) {
+            Some(return_type) => return_type.clone(),
+            None => return,
+        };
+        let found = self.checker.expr_type(expr_t);
+        if !expected.accepts(&found) {
+            let message = format!(
+                "Return value of `{}` in ${} has {}, expected {}.",
+                message, self.state_name, found, expected
+            );
+            self.checker.report(&message, expr_t, terminator_expr.line);
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+
+/// The declared type of a variable or parameter.
+fn variable_type(var_node: &VariableNode) -> Type {
+    let symbol_type_rcref = match &var_node.symbol_type_rcref_opt {
+        Some(symbol_type_rcref) => symbol_type_rcref,
+        None => return Type::Unknown,
+    };
+    match &*symbol_type_rcref.borrow() {
+        SymbolType::DomainVariable {
+            domain_variable_symbol_rcref: variable_symbol_rcref,
+        }
+        | SymbolType::StateVariable {
+            state_variable_symbol_rcref: variable_symbol_rcref,
+        }
+        | SymbolType::EventHandlerVariable {
+            event_handler_variable_symbol_rcref: variable_symbol_rcref,
+        } => Type::of(&variable_symbol_rcref.borrow().var_type),
+        SymbolType::StateParam {
+            state_param_symbol_rcref: param_symbol_rcref,
+        }
+        | SymbolType::EventHandlerParam {
+            event_handler_param_symbol_rcref: param_symbol_rcref,
+        } => Type::of(&param_symbol_rcref.borrow().param_type_opt),
+        _ => Type::Unknown,
+    }
+}
+
+/// The first identifier in an expression, which locates a mismatch in the specification.
+fn expr_token(expr_t: &ExprType) -> Option<Token> {
+    match expr_t {
+        ExprType::VariableExprT { var_node } => Some(var_node.id_node.name.clone()),
+        ExprType::ActionCallExprT {
+            action_call_expr_node,
+        } => Some(action_call_expr_node.identifier.name.clone()),
+        ExprType::CallExprT { call_expr_node } => Some(call_expr_node.identifier.name.clone()),
+        ExprType::CallChainLiteralExprT {
+            call_chain_expr_node,
+        } => match call_chain_expr_node.call_chain.front()? {
+            CallChainLiteralNodeType::VariableNodeT { var_node } => {
+                Some(var_node.id_node.name.clone())
+            }
+            CallChainLiteralNodeType::IdentifierNodeT { id_node } => Some(id_node.name.clone()),
+            CallChainLiteralNodeType::CallT { call } => Some(call.identifier.name.clone()),
+            CallChainLiteralNodeType::InterfaceMethodCallT {
+                interface_method_call_expr_node,
+            } => Some(interface_method_call_expr_node.identifier.name.clone()),
+            CallChainLiteralNodeType::ActionCallT {
+                action_call_expr_node,
+            } => Some(action_call_expr_node.identifier.name.clone()),
+        },
+        ExprType::ExprListT { expr_list_node } => {
+            expr_list_node.exprs_t.iter().find_map(expr_token)
+        }
+        ExprType::UnaryExprT { unary_expr_node } => {
+            expr_token(&unary_expr_node.right_rcref.borrow())
+        }
+        ExprType::BinaryExprT { binary_expr_node } => {
+            expr_token(&binary_expr_node.left_rcref.borrow())
+                .or_else(|| expr_token(&binary_expr_node.right_rcref.borrow()))
+        }
+        _ => None,
+    }
+}
+
+fn located(diagnostic: Diagnostic, token: &Token) -> Diagnostic {
+    diagnostic
+        .with_line(token.line)
+        .with_span(token.start, token.length)
+        .with_lexeme(&token.lexeme)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_c::compiler::{Exe, TargetLanguage};
+    use crate::frame_c::diagnostics::Severity;
+
+    const SPEC: &str = "#Types
+    -interface-
+    isOn : bool
+    setLevel [level:i32]
+    -machine-
+    $Off
+        |isOn| ^(brightness)
+        |setLevel| [level:i32]
+            var name:String = level
+            brightness = \"dim\"
+            level ? -> $On(1.5) ^ :: ^
+    $On [n:i32]
+        |>| [msg:String] ^
+        |isOn| ^(true)
+    -domain-
+    var brightness:i32 = 0
+    var level:Level = 0
+##
+";
+
+    fn messages(spec: &str) -> Vec<(usize, String)> {
+        let system_node = Exe::new()
+            .parse(None, spec.to_string())
+            .unwrap_or_else(|err| panic!("{}", err.error));
+        check(&system_node)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line.unwrap(), diagnostic.message))
+            .collect()
+    }
+
+    /// Test that mismatches between primitive types and literals are reported on their lines, or
+    /// on the line of the handler for transitions.
+    #[test]
+    fn mismatches() {
+        assert_eq!(
+            messages(SPEC),
+            vec![
+                (
+                    7,
+                    "Return value of `isOn` in $Off has `i32`, expected `bool`.".to_string()
+                ),
+                (
+                    9,
+                    "Initializer of `name` has `i32`, expected `String`.".to_string()
+                ),
+                (
+                    10,
+                    "Value assigned to `brightness` has `String`, expected `i32`.".to_string()
+                ),
+                (11, "Condition has `i32`, expected `bool`.".to_string()),
+                (
+                    8,
+                    "State argument 1 of $On has a float literal, expected `i32`.".to_string()
+                ),
+                (
+                    8,
+                    "The enter handler of $On takes 1 enter argument, but 0 were given."
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    /// Test that types that aren't known to be primitive types are not reported.
+    #[test]
+    fn unknown_types() {
+        let spec = SPEC
+            .replace("var name:String", "var name:Name")
+            .replace("var brightness:i32", "var brightness:Brightness")
+            .replace("level ?", "level > 1 ?")
+            .replace("$On(1.5)", "(`String::new()`) $On(3)");
+        assert_eq!(messages(&spec), vec![]);
+    }
+
+    /// Test that mismatches are only errors when the lint denies them, and that a mismatch is
+    /// located at the first identifier of its expression.
+    #[test]
+    fn spans() {
+        Exe::new()
+            .run(&None, None, SPEC.to_string(), Some(TargetLanguage::Rust))
+            .unwrap();
+
+        let spec = format!("#[lint.levels.type_mismatch:str=\"deny\"]\n{}", SPEC);
+        let err = Exe::new()
+            .run(&None, None, spec, Some(TargetLanguage::Rust))
+            .unwrap_err();
+        let diagnostic = &err.diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.code, "type_mismatch");
+        assert_eq!(diagnostic.line, Some(8));
+        assert_eq!(diagnostic.column, Some(18));
+        assert_eq!(diagnostic.lexeme.as_deref(), Some("brightness"));
+    }
+}
@@ -1,8 +1,10 @@
 extern crate exitcode;
+use crate::frame_c::diagnostics::{Diagnostic, MessageFormat};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 
+#[derive(Clone)]
 pub struct Node {
     pub name: String,
     pub parent_name: String,
@@ -34,6 +36,7 @@ impl Node {
     }
 }
 
+#[derive(Clone)]
 pub struct SystemHierarchy {
     pub index: HashMap<String, Node>,
     pub system_name: String,
@@ -80,6 +83,21 @@ impl SystemHierarchy {
         self.set_parent(&node_name, &parent_node_name);
     }
 
+    /// Remove a node without children, such as a state removed by the optimizer.
+    pub fn remove_node(&mut self, node_name: &str) {
+        if let Some(node) = self.index.remove(node_name) {
+            // nodes without a parent are children of the system
+            let parent_name = if node.parent_name.is_empty() {
+                self.system_name.clone()
+            } else {
+                node.parent_name
+            };
+            if let Some(parent_node) = self.index.get_mut(&parent_name) {
+                parent_node.remove_child(node_name);
+            }
+        }
+    }
+
     fn set_parent(&mut self, node_name: &str, new_parent_name: &str) {
         // let node_name_debug = node_name.clone();
         // let new_parent_name_debug = new_parent_name.clone();
@@ -136,26 +154,75 @@ pub(crate) mod frame_exitcode {
     /// Framepiler parse error exit
     pub const PARSE_ERR: FrameExitCode = 1;
     pub const CONFIG_ERR: FrameExitCode = 2;
+    pub const LINT_ERR: FrameExitCode = 3;
 
     pub fn as_string(code: FrameExitCode) -> String {
         match code {
             PARSE_ERR => "Frame parse error".to_string(),
             CONFIG_ERR => "Configuration error".to_string(),
+            LINT_ERR => "Lint error".to_string(),
             _ => format!("Unknown error code {}", code),
         }
     }
+
+    /// The diagnostic code used for errors that exit with the given code.
+    pub fn diagnostic_code(code: FrameExitCode) -> &'static str {
+        match code {
+            PARSE_ERR => "parse-error",
+            CONFIG_ERR => "config-error",
+            LINT_ERR => "lint-error",
+            exitcode::USAGE => "usage-error",
+            exitcode::NOINPUT => "input-error",
+            _ => "error",
+        }
+    }
 }
 
 pub struct RunError {
     pub code: frame_exitcode::FrameExitCode,
     pub error: String,
+    /// Structured form of `error`. Always contains at least one diagnostic.
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl RunError {
     pub fn new(code: frame_exitcode::FrameExitCode, msg: &str) -> RunError {
+        let diagnostic = Diagnostic::error(frame_exitcode::diagnostic_code(code), msg.trim_end());
+        RunError {
+            code,
+            error: String::from(msg),
+            diagnostics: vec![diagnostic],
+        }
+    }
+
+    pub fn with_diagnostics(
+        code: frame_exitcode::FrameExitCode,
+        msg: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> RunError {
+        if diagnostics.is_empty() {
+            return RunError::new(code, msg);
+        }
         RunError {
             code,
             error: String::from(msg),
+            diagnostics,
+        }
+    }
+
+    /// Render the error in the requested message format. JSON output contains one diagnostic
+    /// per line.
+    pub fn render(&self, format: MessageFormat) -> String {
+        match format {
+            MessageFormat::Human => self.error.clone(),
+            MessageFormat::Json => {
+                let mut output = String::new();
+                for diagnostic in &self.diagnostics {
+                    output.push_str(&diagnostic.to_json());
+                    output.push('\n');
+                }
+                output
+            }
         }
     }
 }
@@ -224,6 +224,11 @@ impl CsVisitor {
                 None => String::from("<?>"),
             };
             self.add_code(&format!("{} {}", param_type, param.param_name));
+            if let Some(default) = &param.default_opt {
+                let mut default_code = String::new();
+                default.accept_to_string(self, &mut default_code);
+                self.add_code(&format!(" = {}", default_code));
+            }
             separator = ",";
         }
     }
@@ -930,6 +935,11 @@ impl AstVisitor for CsVisitor {
         );
         self.newline();
         self.newline();
+        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+            for enum_decl_node_rcref in &domain_block_node.enums {
+                enum_decl_node_rcref.borrow().accept(self);
+            }
+        }
         self.add_code(&format!("public partial class {} {{", system_node.name));
         self.indent();
         self.newline();
@@ -1269,6 +1279,44 @@ impl AstVisitor for CsVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_enum_decl_node(&mut self, enum_decl_node: &EnumDeclNode) {
+        self.add_code(&format!("public enum {} {{", enum_decl_node.name));
+        self.indent();
+        for enumerator in &enum_decl_node.enums {
+            self.newline();
+            self.add_code(&format!("{},", enumerator.name));
+        }
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+        self.newline();
+        self.newline();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enumerator_expr_node(&mut self, enum_expr_node: &EnumeratorExprNode) {
+        self.add_code(&format!(
+            "{}.{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enumerator_expr_node_to_string(
+        &mut self,
+        enum_expr_node: &EnumeratorExprNode,
+        output: &mut String,
+    ) {
+        output.push_str(&format!(
+            "{}.{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_state_node(&mut self, state_node: &StateNode) {
         self.generate_comment(state_node.line);
         self.current_state_name_opt = Some(state_node.name.clone());
@@ -1588,6 +1636,11 @@ impl AstVisitor for CsVisitor {
             } => {
                 number_match_test_node.accept(self);
             }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                enum_match_test_node.accept(self);
+            }
         }
     }
 
@@ -2102,6 +2155,172 @@ impl AstVisitor for CsVisitor {
         self.add_code(&match_pattern_node.match_pattern_number.to_string());
     }
 
+    //-----------------------------------------------------//
+
+    fn visit_enum_match_test_node(&mut self, enum_match_test_node: &EnumMatchTestNode) {
+        let mut if_or_else_if = "if";
+
+        self.newline();
+        for match_branch_node in &enum_match_test_node.match_branch_nodes {
+            self.add_code(&format!("{} (", if_or_else_if));
+            match &enum_match_test_node.expr_t {
+                ExprType::CallExprT {
+                    call_expr_node: method_call_expr_node,
+                } => method_call_expr_node.accept(self),
+                ExprType::ActionCallExprT {
+                    action_call_expr_node,
+                } => action_call_expr_node.accept(self),
+                ExprType::CallChainLiteralExprT {
+                    call_chain_expr_node,
+                } => call_chain_expr_node.accept(self),
+                ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                ExprType::ExprListT { expr_list_node } => {
+                    // must be only 1 expression in the list
+                    if expr_list_node.exprs_t.len() != 1 {
+                        // TODO: how to do this better.
+                        self.errors
+                            .push("Error - expression list is not testable.".to_string());
+                    }
+                    let x = expr_list_node.exprs_t.first().unwrap();
+                    x.accept(self);
+                }
+                _ => self.errors.push("TODO.".to_string()),
+            }
+
+            let mut first_match = true;
+            for match_enumerator in &match_branch_node.enum_match_pattern_nodes {
+                if first_match {
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {})", pattern));
+                    first_match = false;
+                } else {
+                    self.add_code(" || (");
+                    match &enum_match_test_node.expr_t {
+                        ExprType::CallExprT {
+                            call_expr_node: method_call_expr_node,
+                        } => method_call_expr_node.accept(self),
+                        ExprType::ActionCallExprT {
+                            action_call_expr_node,
+                        } => action_call_expr_node.accept(self),
+                        ExprType::CallChainLiteralExprT {
+                            call_chain_expr_node,
+                        } => call_chain_expr_node.accept(self),
+                        ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                        _ => self.errors.push("TODO.".to_string()),
+                    }
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {})", pattern));
+                }
+            }
+
+            self.add_code(") {");
+            self.indent();
+
+            match_branch_node.accept(self);
+
+            self.outdent();
+            self.newline();
+            self.add_code("}");
+
+            if_or_else_if = " else if";
+        }
+
+        // (':' enum_test_else_branch)?
+        if let Some(enum_match_else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+            enum_match_else_branch_node.accept(self);
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_match_branch_node(
+        &mut self,
+        enum_match_test_match_branch_node: &EnumMatchTestMatchBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_match_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_match_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => match &branch_terminator_expr.return_expr_t_opt {
+                        Some(expr_t) => {
+                            self.add_code("e._return = ");
+                            expr_t.accept(self);
+                            self.add_code(";");
+                            self.newline();
+                            self.add_code("return;");
+                        }
+                        None => self.add_code("return;"),
+                    },
+                    TerminatorType::Continue => {
+                        self.add_code("break;");
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_else_branch_node(
+        &mut self,
+        enum_match_test_else_branch_node: &EnumMatchTestElseBranchNode,
+    ) {
+        self.add_code(" else {");
+        self.indent();
+
+        self.visit_decl_stmts(&enum_match_test_else_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_else_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => match &branch_terminator_expr.return_expr_t_opt {
+                        Some(expr_t) => {
+                            self.add_code("e._return = ");
+                            expr_t.accept(self);
+                            self.add_code(";");
+                            self.newline();
+                            self.add_code("return;");
+                        }
+                        None => self.add_code("return;"),
+                    },
+                    TerminatorType::Continue => {
+                        self.add_code("break;");
+                    }
+                }
+            }
+            None => {}
+        }
+
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_pattern_node(
+        &mut self,
+        match_pattern_node: &EnumMatchTestPatternNode,
+    ) {
+        let mut pattern = String::new();
+        match_pattern_node
+            .enumerator_expr_node
+            .accept_to_string(self, &mut pattern);
+        self.add_code(&pattern);
+    }
+
     //* --------------------------------------------------------------------- *//
 
     fn visit_expression_list_node(&mut self, expr_list: &ExprListNode) {
@@ -2357,7 +2576,17 @@ impl AstVisitor for CsVisitor {
         self.newline();
         let mut code = String::new();
         var_init_expr.accept_to_string(self, &mut code);
-        self.add_code(&format!("{} {} = {};", var_type, var_name, code));
+        let modifier = if variable_decl_node.is_constant
+            && variable_decl_node.identifier_decl_scope == IdentifierDeclScope::DomainBlock
+        {
+            "readonly "
+        } else {
+            ""
+        };
+        self.add_code(&format!(
+            "{}{} {} = {};",
+            modifier, var_type, var_name, code
+        ));
 
         self.serialize
             .push(format!("\tbag.domain[\"{}\"] = {};", var_name, var_name));
@@ -0,0 +1,400 @@
+//! Annotations of the edges of state diagrams, shared by the diagram backends.
+//!
+//! An edge is drawn for each transition and change of state of an event handler. Besides the
+//! event, its label may show the parameters of the event, the conditions of the branches the
+//! transition is in and the actions called on the way to the transition, e.g.
+//! `open(speed) [speed > 0] / unlock(); log()`. Expressions are formatted in Frame syntax.
+
+use crate::frame_c::ast::*;
+use crate::frame_c::scanner::TokenType;
+
+/// The guard and actions of a transition or change of state.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct EdgeAnnotation {
+    /// The conditions of the branches the transition is in, outermost first. The branch taken
+    /// when no other branch of a test matches is `else`.
+    pub guards: Vec<String>,
+    /// The actions called by the event handler before the transition, followed by the actions
+    /// of the transition, e.g. `unlock()`.
+    pub actions: Vec<String>,
+}
+
+impl EdgeAnnotation {
+    /// Format the guards as one condition, or `None` if the transition isn't in a branch.
+    pub fn format_guard(&self) -> Option<String> {
+        match self.guards.len() {
+            0 => None,
+            1 => Some(self.guards[0].clone()),
+            _ => Some(
+                self.guards
+                    .iter()
+                    .map(|guard| {
+                        if guard.contains(" || ") || guard.contains(" &| ") {
+                            format!("({})", guard)
+                        } else {
+                            guard.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" && "),
+            ),
+        }
+    }
+
+    /// Format the actions as a list separated by `; `, or `None` if no action is called.
+    pub fn format_actions(&self) -> Option<String> {
+        if self.actions.is_empty() {
+            None
+        } else {
+            Some(self.actions.join("; "))
+        }
+    }
+}
+
+/// Format the parameters of the event handled, e.g. `(speed, force)`, or an empty string if the
+/// event has no parameters.
+pub fn format_event_params(evt_handler_node: &EventHandlerNode) -> String {
+    let event_symbol = evt_handler_node.event_symbol_rcref.borrow();
+    match &event_symbol.params_opt {
+        Some(params) if !params.is_empty() => format!(
+            "({})",
+            params
+                .iter()
+                .map(|param| param.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => String::new(),
+    }
+}
+
+/// A transition or change of state of an event handler.
+pub enum EdgeStatement<'a> {
+    Transition(&'a TransitionStatementNode),
+    ChangeState(&'a ChangeStateStatementNode),
+}
+
+/// A transition or change of state of an event handler, with its annotation.
+pub struct Edge<'a> {
+    pub statement: EdgeStatement<'a>,
+    pub annotation: EdgeAnnotation,
+}
+
+/// Collect the transitions and changes of state of an event handler, in the order the diagram
+/// backends visit them.
+pub fn edges(evt_handler_node: &EventHandlerNode) -> Vec<Edge<'_>> {
+    let mut walker = Walker::default();
+    walker.decl_stmts(&evt_handler_node.statements);
+    walker.edges
+}
+
+/// Collect the annotations of the transitions and changes of state of an event handler, in the
+/// order the diagram backends visit them.
+pub fn edge_annotations(evt_handler_node: &EventHandlerNode) -> Vec<EdgeAnnotation> {
+    edges(evt_handler_node)
+        .into_iter()
+        .map(|edge| edge.annotation)
+        .collect()
+}
+
+#[derive(Default)]
+struct Walker<'a> {
+    guards: Vec<String>,
+    actions: Vec<String>,
+    edges: Vec<Edge<'a>>,
+}
+
+impl<'a> Walker<'a> {
+    fn decl_stmts(&mut self, decl_stmt_types: &'a [DeclOrStmtType]) {
+        for decl_stmt_t in decl_stmt_types {
+            if let DeclOrStmtType::StmtT { stmt_t } = decl_stmt_t {
+                match stmt_t {
+                    StatementType::ExpressionStmt { expr_stmt_t } => {
+                        if let Some(action) = format_action_call(expr_stmt_t) {
+                            self.actions.push(action);
+                        }
+                    }
+                    StatementType::TransitionStmt {
+                        transition_statement,
+                    } => {
+                        let mut actions = self.actions.clone();
+                        actions.extend(
+                            transition_statement
+                                .actions
+                                .iter()
+                                .filter_map(format_action_call),
+                        );
+                        self.add_edge(EdgeStatement::Transition(transition_statement), actions);
+                    }
+                    StatementType::ChangeStateStmt { change_state_stmt } => {
+                        let actions = self.actions.clone();
+                        self.add_edge(EdgeStatement::ChangeState(change_state_stmt), actions);
+                    }
+                    StatementType::TestStmt { test_stmt_node } => self.test(test_stmt_node),
+                    StatementType::StateStackStmt { .. } | StatementType::NoStmt => {}
+                }
+            }
+        }
+    }
+
+    fn add_edge(&mut self, statement: EdgeStatement<'a>, actions: Vec<String>) {
+        self.edges.push(Edge {
+            statement,
+            annotation: EdgeAnnotation {
+                guards: self.guards.clone(),
+                actions,
+            },
+        });
+    }
+
+    fn branch(&mut self, guard: String, statements: &'a [DeclOrStmtType]) {
+        self.guards.push(guard);
+        let actions_len = self.actions.len();
+        self.decl_stmts(statements);
+        self.actions.truncate(actions_len);
+        self.guards.pop();
+    }
+
+    fn test(&mut self, test_stmt_node: &'a TestStatementNode) {
+        match &test_stmt_node.test_t {
+            TestType::BoolTest { bool_test_node } => {
+                for branch_node in &bool_test_node.conditional_branch_nodes {
+                    let condition = format_expr(&branch_node.expr_t);
+                    let guard = if !branch_node.is_negated {
+                        condition
+                    } else if matches!(branch_node.expr_t, ExprType::BinaryExprT { .. }) {
+                        format!("!({})", condition)
+                    } else {
+                        format!("!{}", condition)
+                    };
+                    self.branch(guard, &branch_node.statements);
+                }
+                if let Some(else_branch_node) = &bool_test_node.else_branch_node_opt {
+                    self.branch("else".to_string(), &else_branch_node.statements);
+                }
+            }
+            TestType::StringMatchTest {
+                string_match_test_node,
+            } => {
+                let value = format_expr(&string_match_test_node.expr_t);
+                for branch_node in &string_match_test_node.match_branch_nodes {
+                    let patterns = branch_node
+                        .string_match_pattern_node
+                        .match_pattern_strings
+                        .iter()
+                        .map(|pattern| format!("\"{}\"", pattern));
+                    let guard = format_match_guard(&value, patterns);
+                    self.branch(guard, &branch_node.statements);
+                }
+                if let Some(else_branch_node) = &string_match_test_node.else_branch_node_opt {
+                    self.branch("else".to_string(), &else_branch_node.statements);
+                }
+            }
+            TestType::NumberMatchTest {
+                number_match_test_node,
+            } => {
+                let value = format_expr(&number_match_test_node.expr_t);
+                for branch_node in &number_match_test_node.match_branch_nodes {
+                    let patterns = branch_node
+                        .number_match_pattern_nodes
+                        .iter()
+                        .map(|pattern| pattern.match_pattern_number.clone());
+                    let guard = format_match_guard(&value, patterns);
+                    self.branch(guard, &branch_node.statements);
+                }
+                if let Some(else_branch_node) = &number_match_test_node.else_branch_node_opt {
+                    self.branch("else".to_string(), &else_branch_node.statements);
+                }
+            }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                let value = format_expr(&enum_match_test_node.expr_t);
+                for branch_node in &enum_match_test_node.match_branch_nodes {
+                    let patterns = branch_node
+                        .enum_match_pattern_nodes
+                        .iter()
+                        .map(|pattern| format_enumerator(&pattern.enumerator_expr_node));
+                    let guard = format_match_guard(&value, patterns);
+                    self.branch(guard, &branch_node.statements);
+                }
+                if let Some(else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+                    self.branch("else".to_string(), &else_branch_node.statements);
+                }
+            }
+        }
+    }
+}
+
+/// Format the condition of a match branch, e.g. `color == "red" || color == "green"`.
+fn format_match_guard(value: &str, patterns: impl Iterator<Item = String>) -> String {
+    patterns
+        .map(|pattern| format!("{} == {}", value, pattern))
+        .collect::<Vec<_>>()
+        .join(" || ")
+}
+
+/// Format a statement calling an action as the name of the action, e.g. `unlock()`, or `None`
+/// for other statements.
+fn format_action_call(expr_stmt_t: &ExprStmtType) -> Option<String> {
+    match expr_stmt_t {
+        ExprStmtType::ActionCallStmtT {
+            action_call_stmt_node,
+        } => Some(format!(
+            "{}()",
+            action_call_stmt_node
+                .action_call_expr_node
+                .identifier
+                .name
+                .lexeme
+        )),
+        ExprStmtType::CallChainLiteralStmtT {
+            call_chain_literal_stmt_node,
+        } => {
+            let call_chain = &call_chain_literal_stmt_node
+                .call_chain_literal_expr_node
+                .call_chain;
+            match call_chain.front() {
+                Some(CallChainLiteralNodeType::ActionCallT {
+                    action_call_expr_node,
+                }) if call_chain.len() == 1 => Some(format!(
+                    "{}()",
+                    action_call_expr_node.identifier.name.lexeme
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn format_enumerator(enum_expr_node: &EnumeratorExprNode) -> String {
+    format!("{}.{}", enum_expr_node.enum_type, enum_expr_node.enumerator)
+}
+
+fn format_args(exprs_t: &[ExprType]) -> String {
+    exprs_t
+        .iter()
+        .map(format_expr)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_operator(operator: &OperatorType) -> &'static str {
+    match operator {
+        OperatorType::Plus => "+",
+        OperatorType::Minus | OperatorType::Negated => "-",
+        OperatorType::Multiply => "*",
+        OperatorType::Divide => "/",
+        OperatorType::Greater => ">",
+        OperatorType::GreaterEqual => ">=",
+        OperatorType::EqualEqual => "==",
+        OperatorType::NotEqual => "!=",
+        OperatorType::Less => "<",
+        OperatorType::LessEqual => "<=",
+        OperatorType::Not => "!",
+        OperatorType::LogicalAnd => "&&",
+        OperatorType::LogicalOr => "||",
+        OperatorType::LogicalXor => "&|",
+    }
+}
+
+/// Format a literal in Frame syntax, e.g. `"on"` or `3`.
+pub fn format_literal(literal_expr_node: &LiteralExprNode) -> String {
+    match literal_expr_node.token_t {
+        TokenType::String => format!("\"{}\"", literal_expr_node.value),
+        TokenType::SuperString => format!("`{}`", literal_expr_node.value),
+        _ => literal_expr_node.value.clone(),
+    }
+}
+
+/// Format an expression in Frame syntax, e.g. `speed > 0 && @[force] != 1`.
+pub fn format_expr(expr_t: &ExprType) -> String {
+    match expr_t {
+        ExprType::AssignmentExprT {
+            assignment_expr_node,
+        } => format!(
+            "{} = {}",
+            format_expr(&assignment_expr_node.l_value_box),
+            format_expr(&assignment_expr_node.r_value_box)
+        ),
+        ExprType::ActionCallExprT {
+            action_call_expr_node,
+        } => format!(
+            "{}({})",
+            action_call_expr_node.identifier.name.lexeme,
+            format_args(&action_call_expr_node.call_expr_list.exprs_t)
+        ),
+        ExprType::CallChainLiteralExprT {
+            call_chain_expr_node,
+        } => call_chain_expr_node
+            .call_chain
+            .iter()
+            .map(|call_chain_node_t| match call_chain_node_t {
+                CallChainLiteralNodeType::VariableNodeT { var_node } => {
+                    var_node.id_node.name.lexeme.clone()
+                }
+                CallChainLiteralNodeType::IdentifierNodeT { id_node } => {
+                    id_node.name.lexeme.clone()
+                }
+                CallChainLiteralNodeType::CallT { call } => format!(
+                    "{}({})",
+                    call.identifier.name.lexeme,
+                    format_args(&call.call_expr_list.exprs_t)
+                ),
+                CallChainLiteralNodeType::InterfaceMethodCallT {
+                    interface_method_call_expr_node,
+                } => format!(
+                    "{}({})",
+                    interface_method_call_expr_node.identifier.name.lexeme,
+                    format_args(&interface_method_call_expr_node.call_expr_list.exprs_t)
+                ),
+                CallChainLiteralNodeType::ActionCallT {
+                    action_call_expr_node,
+                } => format!(
+                    "{}({})",
+                    action_call_expr_node.identifier.name.lexeme,
+                    format_args(&action_call_expr_node.call_expr_list.exprs_t)
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("."),
+        ExprType::CallExprT { call_expr_node } => format!(
+            "{}({})",
+            call_expr_node.identifier.name.lexeme,
+            format_args(&call_expr_node.call_expr_list.exprs_t)
+        ),
+        ExprType::CallExprListT {
+            call_expr_list_node,
+        } => format!("({})", format_args(&call_expr_list_node.exprs_t)),
+        ExprType::ExprListT { expr_list_node } => {
+            format!("({})", format_args(&expr_list_node.exprs_t))
+        }
+        ExprType::VariableExprT { var_node } => var_node.id_node.name.lexeme.clone(),
+        ExprType::LiteralExprT { literal_expr_node } => format_literal(literal_expr_node),
+        ExprType::StateStackOperationExprT {
+            state_stack_op_node,
+        } => match state_stack_op_node.operation_t {
+            StateStackOperationType::Push => "$$[+]".to_string(),
+            StateStackOperationType::Pop => "$$[-]".to_string(),
+        },
+        ExprType::FrameEventExprT { frame_event_part } => match frame_event_part {
+            FrameEventPart::Event { .. } => "@".to_string(),
+            FrameEventPart::Message { .. } => "@||".to_string(),
+            FrameEventPart::Param { param_tok, .. } => format!("@[{}]", param_tok.lexeme),
+            FrameEventPart::Return { .. } => "@^".to_string(),
+        },
+        ExprType::UnaryExprT { unary_expr_node } => format!(
+            "{}{}",
+            format_operator(&unary_expr_node.operator),
+            format_expr(&unary_expr_node.right_rcref.borrow())
+        ),
+        ExprType::BinaryExprT { binary_expr_node } => format!(
+            "{} {} {}",
+            format_expr(&binary_expr_node.left_rcref.borrow()),
+            format_operator(&binary_expr_node.operator),
+            format_expr(&binary_expr_node.right_rcref.borrow())
+        ),
+        ExprType::EnumeratorExprT { enum_expr_node } => format_enumerator(enum_expr_node),
+    }
+}
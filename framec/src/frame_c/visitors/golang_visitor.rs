@@ -1401,6 +1401,11 @@ impl AstVisitor for GolangVisitor {
             }
         }
 
+        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+            for enum_decl_node_rcref in &domain_block_node.enums {
+                enum_decl_node_rcref.borrow().accept(self);
+            }
+        }
         // generate New factory
         self.generate_new_fn(&domain_vec, system_node);
 
@@ -1922,6 +1927,50 @@ impl AstVisitor for GolangVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_enum_decl_node(&mut self, enum_decl_node: &EnumDeclNode) {
+        self.newline();
+        self.newline();
+        self.add_code(&format!("type {} uint", enum_decl_node.name));
+        self.newline();
+        self.newline();
+        self.add_code("const (");
+        self.indent();
+        for (i, enumerator) in enum_decl_node.enums.iter().enumerate() {
+            self.newline();
+            self.add_code(&format!("{}_{}", enum_decl_node.name, enumerator.name));
+            if i == 0 {
+                self.add_code(&format!(" {} = iota", enum_decl_node.name));
+            }
+        }
+        self.outdent();
+        self.newline();
+        self.add_code(")");
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enumerator_expr_node(&mut self, enum_expr_node: &EnumeratorExprNode) {
+        self.add_code(&format!(
+            "{}_{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enumerator_expr_node_to_string(
+        &mut self,
+        enum_expr_node: &EnumeratorExprNode,
+        output: &mut String,
+    ) {
+        output.push_str(&format!(
+            "{}_{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_state_node(&mut self, state_node: &StateNode) {
         self.generate_comment(state_node.line);
         self.current_state_name_opt = Some(state_node.name.clone());
@@ -2215,6 +2264,11 @@ impl AstVisitor for GolangVisitor {
             } => {
                 number_match_test_node.accept(self);
             }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                enum_match_test_node.accept(self);
+            }
         }
     }
 
@@ -2727,6 +2781,172 @@ impl AstVisitor for GolangVisitor {
         self.add_code(&match_pattern_node.match_pattern_number.to_string());
     }
 
+    //-----------------------------------------------------//
+
+    fn visit_enum_match_test_node(&mut self, enum_match_test_node: &EnumMatchTestNode) {
+        let mut if_or_else_if = "if";
+
+        self.newline();
+        for match_branch_node in &enum_match_test_node.match_branch_nodes {
+            self.add_code(&format!("{} ", if_or_else_if));
+            match &enum_match_test_node.expr_t {
+                ExprType::CallExprT {
+                    call_expr_node: method_call_expr_node,
+                } => method_call_expr_node.accept(self),
+                ExprType::ActionCallExprT {
+                    action_call_expr_node,
+                } => action_call_expr_node.accept(self),
+                ExprType::CallChainLiteralExprT {
+                    call_chain_expr_node,
+                } => call_chain_expr_node.accept(self),
+                ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                ExprType::ExprListT { expr_list_node } => {
+                    // must be only 1 expression in the list
+                    if expr_list_node.exprs_t.len() != 1 {
+                        // TODO: how to do this better.
+                        self.errors
+                            .push("Error - expression list is not testable.".to_string());
+                    }
+                    let x = expr_list_node.exprs_t.first().unwrap();
+                    x.accept(self);
+                }
+                _ => self.errors.push("TODO.".to_string()),
+            }
+
+            let mut first_match = true;
+            for match_enumerator in &match_branch_node.enum_match_pattern_nodes {
+                if first_match {
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {}", pattern));
+                    first_match = false;
+                } else {
+                    self.add_code(" || ");
+                    match &enum_match_test_node.expr_t {
+                        ExprType::CallExprT {
+                            call_expr_node: method_call_expr_node,
+                        } => method_call_expr_node.accept(self),
+                        ExprType::ActionCallExprT {
+                            action_call_expr_node,
+                        } => action_call_expr_node.accept(self),
+                        ExprType::CallChainLiteralExprT {
+                            call_chain_expr_node,
+                        } => call_chain_expr_node.accept(self),
+                        ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                        _ => self.errors.push("TODO.".to_string()),
+                    }
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {}", pattern));
+                }
+            }
+
+            self.add_code(" {");
+            self.indent();
+
+            match_branch_node.accept(self);
+
+            self.outdent();
+            self.newline();
+            self.add_code("}");
+
+            if_or_else_if = " else if";
+        }
+
+        // (':' enum_test_else_branch)?
+        if let Some(enum_match_else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+            enum_match_else_branch_node.accept(self);
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_match_branch_node(
+        &mut self,
+        enum_match_test_match_branch_node: &EnumMatchTestMatchBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_match_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_match_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => match &branch_terminator_expr.return_expr_t_opt {
+                        Some(expr_t) => {
+                            self.add_code("e.Ret = ");
+                            expr_t.accept(self);
+                            //                            self.add_code(";");
+                            self.newline();
+                            self.add_code("return");
+                        }
+                        None => self.add_code("return"),
+                    },
+                    TerminatorType::Continue => {
+                        self.add_code("break");
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_else_branch_node(
+        &mut self,
+        enum_match_test_else_branch_node: &EnumMatchTestElseBranchNode,
+    ) {
+        self.add_code(" else {");
+        self.indent();
+
+        self.visit_decl_stmts(&enum_match_test_else_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_else_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => match &branch_terminator_expr.return_expr_t_opt {
+                        Some(expr_t) => {
+                            self.add_code("e.Ret = ");
+                            expr_t.accept(self);
+                            //                            self.add_code(";");
+                            self.newline();
+                            self.add_code("return");
+                        }
+                        None => self.add_code("return"),
+                    },
+                    TerminatorType::Continue => {
+                        self.add_code("break");
+                    }
+                }
+            }
+            None => {}
+        }
+
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_pattern_node(
+        &mut self,
+        match_pattern_node: &EnumMatchTestPatternNode,
+    ) {
+        let mut pattern = String::new();
+        match_pattern_node
+            .enumerator_expr_node
+            .accept_to_string(self, &mut pattern);
+        self.add_code(&pattern);
+    }
+
     //* --------------------------------------------------------------------- *//
 
     fn visit_expression_list_node(&mut self, expr_list: &ExprListNode) {
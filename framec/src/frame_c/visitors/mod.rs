@@ -8,16 +8,62 @@ pub enum TargetLanguage {
     CSharpForBob,
     GdScript,
     GoLang,
+    Ir,
     Java8,
     JavaScript,
+    NuSmv,
     PlantUml,
     Python3,
     Rust,
     Smcat,
+    Template,
+    Tla,
     // XState,
 }
 
 impl TargetLanguage {
+    /// All the target languages, in the order of their declaration.
+    pub fn all() -> &'static [TargetLanguage] {
+        &[
+            TargetLanguage::Cpp,
+            TargetLanguage::CSharp,
+            TargetLanguage::CSharpForBob,
+            TargetLanguage::GdScript,
+            TargetLanguage::GoLang,
+            TargetLanguage::Ir,
+            TargetLanguage::Java8,
+            TargetLanguage::JavaScript,
+            TargetLanguage::NuSmv,
+            TargetLanguage::PlantUml,
+            TargetLanguage::Python3,
+            TargetLanguage::Rust,
+            TargetLanguage::Smcat,
+            TargetLanguage::Template,
+            TargetLanguage::Tla,
+        ]
+    }
+
+    /// The name of the target language on the command line, e.g. `python_3`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetLanguage::Cpp => "cpp",
+            TargetLanguage::CSharp => "c_sharp",
+            TargetLanguage::CSharpForBob => "c_sharp_bob",
+            TargetLanguage::GdScript => "gdscript",
+            TargetLanguage::GoLang => "golang",
+            TargetLanguage::Ir => "ir",
+            TargetLanguage::Java8 => "java_8",
+            TargetLanguage::JavaScript => "javascript",
+            TargetLanguage::NuSmv => "nusmv",
+            TargetLanguage::PlantUml => "plantuml",
+            TargetLanguage::Python3 => "python_3",
+            TargetLanguage::Rust => "rust",
+            TargetLanguage::Smcat => "smcat",
+            TargetLanguage::Template => "template",
+            TargetLanguage::Tla => "tla",
+        }
+    }
+
     pub fn file_extension(&self) -> &'static str {
         match self {
             TargetLanguage::Cpp => "cpp",
@@ -25,12 +71,16 @@ impl TargetLanguage {
             TargetLanguage::CSharpForBob => "cs",
             TargetLanguage::GdScript => "gd",
             TargetLanguage::GoLang => "go",
+            TargetLanguage::Ir => "json",
             TargetLanguage::Java8 => "java",
             TargetLanguage::JavaScript => "js",
+            TargetLanguage::NuSmv => "smv",
             TargetLanguage::PlantUml => "puml",
             TargetLanguage::Python3 => "py",
             TargetLanguage::Rust => "rs",
             TargetLanguage::Smcat => "smcat",
+            TargetLanguage::Template => "txt",
+            TargetLanguage::Tla => "tla",
         }
     }
 }
@@ -48,10 +98,14 @@ impl TryFrom<&str> for TargetLanguage {
             Ok(TargetLanguage::GdScript)
         } else if value == "golang" {
             Ok(TargetLanguage::GoLang)
+        } else if value == "ir" {
+            Ok(TargetLanguage::Ir)
         } else if value == "java_8" {
             Ok(TargetLanguage::Java8)
         } else if value == "javascript" {
             Ok(TargetLanguage::JavaScript)
+        } else if value == "nusmv" {
+            Ok(TargetLanguage::NuSmv)
         } else if value == "plantuml" {
             Ok(TargetLanguage::PlantUml)
         } else if value == "python_3" {
@@ -60,6 +114,10 @@ impl TryFrom<&str> for TargetLanguage {
             Ok(TargetLanguage::Rust)
         } else if value == "smcat" {
             Ok(TargetLanguage::Smcat)
+        } else if value == "template" {
+            Ok(TargetLanguage::Template)
+        } else if value == "tla" {
+            Ok(TargetLanguage::Tla)
         // } else if value == "xstate" {
         //     Ok(TargetLanguage::XState)
         } else {
@@ -78,6 +136,7 @@ impl TryFrom<String> for TargetLanguage {
 pub mod cpp_visitor;
 pub mod cs_visitor;
 pub mod cs_visitor_for_bob;
+pub mod edge_labels;
 pub mod gdscript_3_2_visitor;
 pub mod golang_visitor;
 pub mod java_8_visitor;
@@ -127,6 +186,10 @@ pub trait AstVisitor {
     fn visit_number_match_test_match_branch_node(&mut self, _node: &NumberMatchTestMatchBranchNode) {}
     fn visit_number_match_test_else_branch_node(&mut self, _node: &NumberMatchTestElseBranchNode) {}
     fn visit_number_match_test_pattern_node(&mut self, _node: &NumberMatchTestPatternNode) {}
+    fn visit_enum_match_test_node(&mut self, _node: &EnumMatchTestNode) {}
+    fn visit_enum_match_test_match_branch_node(&mut self, _node: &EnumMatchTestMatchBranchNode) {}
+    fn visit_enum_match_test_else_branch_node(&mut self, _node: &EnumMatchTestElseBranchNode) {}
+    fn visit_enum_match_test_pattern_node(&mut self, _node: &EnumMatchTestPatternNode) {}
     fn visit_expression_list_node(&mut self, _expr_list: &ExprListNode) {}
     fn visit_expression_list_node_to_string(&mut self, _expr_list: &ExprListNode, _output: &mut String) {}
     fn visit_literal_expression_node(&mut self, _node: &LiteralExprNode) {}
@@ -150,6 +213,9 @@ pub trait AstVisitor {
     fn visit_action_call_statement_node(&mut self, _node: &ActionCallStmtNode) {}
     fn visit_domain_block_node(&mut self, _node: &DomainBlockNode) {}
     fn visit_domain_variable_decl_node(&mut self, _node: &VariableDeclNode) {}
+    fn visit_enum_decl_node(&mut self, _node: &EnumDeclNode) {}
+    fn visit_enumerator_expr_node(&mut self, _node: &EnumeratorExprNode) {}
+    fn visit_enumerator_expr_node_to_string(&mut self, _node: &EnumeratorExprNode, _output: &mut String) {}
     fn visit_variable_decl_node(&mut self, _node: &VariableDeclNode) {}
     fn visit_variable_expr_node(&mut self, _node: &VariableNode) {}
     fn visit_variable_expr_node_to_string(&mut self, _node: &VariableNode, _output: &mut String) {}
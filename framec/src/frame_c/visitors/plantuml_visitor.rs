@@ -6,13 +6,16 @@
 #![allow(non_snake_case)]
 
 use crate::frame_c::ast::*;
+use crate::frame_c::config::{FrameConfig, PlantUmlConfig};
 use crate::frame_c::scanner::{Token, TokenType};
 use crate::frame_c::symbol_table::*;
 use crate::frame_c::utils::SystemHierarchy;
+use crate::frame_c::visitors::edge_labels::{self, EdgeAnnotation};
 use crate::frame_c::visitors::*;
 
 pub struct PlantUmlVisitor {
     compiler_version: String,
+    config: PlantUmlConfig,
     pub code: String,
     pub dent: usize,
     pub current_state_name_opt: Option<String>,
@@ -32,6 +35,8 @@ pub struct PlantUmlVisitor {
     transitions: String,
     system_hierarchy: SystemHierarchy,
     event_handler_msg: String,
+    event_handler_params: String,
+    edge_annotations: std::vec::IntoIter<EdgeAnnotation>,
 }
 
 impl PlantUmlVisitor {
@@ -46,6 +51,7 @@ impl PlantUmlVisitor {
         _generate_transition_state: bool,
         compiler_version: &str,
         _comments: Vec<Token>,
+        config: FrameConfig,
     ) -> PlantUmlVisitor {
         // These closures are needed to do the same actions as add_code() and newline()
         // when inside a borrowed self reference as they modify self.
@@ -56,6 +62,7 @@ impl PlantUmlVisitor {
 
         PlantUmlVisitor {
             compiler_version: compiler_version.to_string(),
+            config: config.codegen.plantuml,
             code: String::from(""),
             dent: 0,
             current_state_name_opt: None,
@@ -75,6 +82,8 @@ impl PlantUmlVisitor {
             transitions: String::new(),
             system_hierarchy,
             event_handler_msg: String::new(),
+            event_handler_params: String::new(),
+            edge_annotations: Vec::new().into_iter(),
         }
     }
 
@@ -486,13 +495,7 @@ impl PlantUmlVisitor {
             current_state = state_name.clone();
         }
 
-        let label = match &change_state_stmt_node.label_opt {
-            Some(label) => {
-                let cleaned = str::replace(label, "|", "&#124;");
-                format!(" : {}", cleaned)
-            }
-            None => format!(" : {}", self.event_handler_msg.clone()),
-        };
+        let label = self.format_edge_label(&change_state_stmt_node.label_opt);
 
         let transition_code = &format!(
             "{} -[dashed]-> {}{}\n",
@@ -724,13 +727,7 @@ impl PlantUmlVisitor {
             current_state = state_name.clone();
         }
 
-        let label = match &transition_statement.label_opt {
-            Some(label) => {
-                let cleaned = str::replace(label, "|", "&#124;");
-                format!(" : {}", cleaned)
-            }
-            None => format!(" : {}", self.event_handler_msg.clone()),
-        };
+        let label = self.format_edge_label(&transition_statement.label_opt);
 
         let transition_code = &format!(
             "{} --> {}{}\n",
@@ -744,6 +741,34 @@ impl PlantUmlVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    // Format the label of the edge of the next transition or change of state of the current
+    // event handler: the label of the transition or the event, followed by the annotations
+    // enabled in the configuration.
+    fn format_edge_label(&mut self, label_opt: &Option<String>) -> String {
+        let features = &self.config.features;
+        let annotation = self.edge_annotations.next().unwrap_or_default();
+        let mut label = match label_opt {
+            Some(label) => str::replace(label, "|", "&#124;"),
+            None if features.edge_params => {
+                format!("{}{}", self.event_handler_msg, self.event_handler_params)
+            }
+            None => self.event_handler_msg.clone(),
+        };
+        if features.edge_guards {
+            if let Some(guard) = annotation.format_guard() {
+                label.push_str(&format!(" [{}]", str::replace(&guard, "|", "&#124;")));
+            }
+        }
+        if features.edge_actions {
+            if let Some(actions) = annotation.format_actions() {
+                label.push_str(&format!(" / {}", actions));
+            }
+        }
+        format!(" : {}", label)
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn format_target_state_name(&self, state_name: &str) -> String {
         state_name.to_string()
     }
@@ -822,13 +847,7 @@ impl PlantUmlVisitor {
         // self.add_code(&format!("StateContext stateContext = _stateStack_pop();"));
         // self.newline();
 
-        let label = match &transition_statement.label_opt {
-            Some(label) => {
-                let cleaned = str::replace(label, "|", "&#124;");
-                format!(" : {}", cleaned)
-            }
-            None => format!(" : {}", self.event_handler_msg.clone()),
-        };
+        let label = self.format_edge_label(&transition_statement.label_opt);
         self.transitions.push_str(&format!(
             "{} --> [H*]{}\n",
             &self.current_state_name_opt.as_ref().unwrap(),
@@ -848,6 +867,12 @@ impl AstVisitor for PlantUmlVisitor {
                                                // self.add_code(&format!("// {}",self.compiler_version));
                                                // self.newline();
         self.add_code(&"@startuml\n".to_string());
+        if !self.config.code.theme.is_empty() {
+            self.add_code(&format!("!theme {}\n", self.config.code.theme));
+        }
+        for skinparam in &self.config.code.skinparams.clone() {
+            self.add_code(&format!("skinparam {}\n", skinparam.trim()));
+        }
         // self.indent();
         // self.newline();
         //        self.add_code(&format!("public FrameController self;"));
@@ -1183,6 +1208,8 @@ impl AstVisitor for PlantUmlVisitor {
             // AnyMessage ( ||* )
             self.event_handler_msg = "&#124;&#124;*".to_string();
         }
+        self.event_handler_params = edge_labels::format_event_params(evt_handler_node);
+        self.edge_annotations = edge_labels::edge_annotations(evt_handler_node).into_iter();
         //         self.generate_comment(evt_handler_node.line);
         //
         //         self.indent();
@@ -1421,6 +1448,11 @@ impl AstVisitor for PlantUmlVisitor {
             } => {
                 number_match_test_node.accept(self);
             }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                enum_match_test_node.accept(self);
+            }
         }
     }
 
@@ -1894,6 +1926,165 @@ impl AstVisitor for PlantUmlVisitor {
         self.add_code(&match_pattern_node.match_pattern_number.to_string());
     }
 
+    //-----------------------------------------------------//
+
+    fn visit_enum_match_test_node(&mut self, enum_match_test_node: &EnumMatchTestNode) {
+        // let mut if_or_else_if = "if";
+
+        self.newline();
+        for match_branch_node in &enum_match_test_node.match_branch_nodes {
+            // self.add_code(&format!("{} (", if_or_else_if));
+            match &enum_match_test_node.expr_t {
+                ExprType::CallExprT {
+                    call_expr_node: method_call_expr_node,
+                } => method_call_expr_node.accept(self),
+                ExprType::ActionCallExprT {
+                    action_call_expr_node,
+                } => action_call_expr_node.accept(self),
+                ExprType::CallChainLiteralExprT {
+                    call_chain_expr_node,
+                } => call_chain_expr_node.accept(self),
+                ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                _ => panic!("TODO"),
+            }
+
+            let mut first_match = true;
+            for _match_enumerator in &match_branch_node.enum_match_pattern_nodes {
+                if first_match {
+                    //        self.add_code(&format!(" == {})",match_enumerator.match_pattern_enumerator));
+                    first_match = false;
+                } else {
+                    // self.add_code(&format!(" || ("));
+                    match &enum_match_test_node.expr_t {
+                        ExprType::CallExprT {
+                            call_expr_node: method_call_expr_node,
+                        } => method_call_expr_node.accept(self),
+                        ExprType::ActionCallExprT {
+                            action_call_expr_node,
+                        } => action_call_expr_node.accept(self),
+                        ExprType::CallChainLiteralExprT {
+                            call_chain_expr_node,
+                        } => call_chain_expr_node.accept(self),
+                        ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                        _ => panic!("TODO"),
+                    }
+                    //        self.add_code(&format!(" == {})",match_enumerator.match_pattern_enumerator));
+                }
+            }
+
+            // self.add_code(&format!(") {{"));
+            // self.indent();
+
+            match_branch_node.accept(self);
+
+            // self.outdent(); self.newline();
+            // self.add_code(&format!("}}"));
+
+            //           self.indent();
+
+            // if_or_else_if = " else if";
+        }
+
+        // (':' enum_test_else_branch)?
+        if let Some(enum_match_else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+            enum_match_else_branch_node.accept(self);
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_match_branch_node(
+        &mut self,
+        enum_match_test_match_branch_node: &EnumMatchTestMatchBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_match_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        // match &enum_match_test_match_branch_node.branch_terminator_expr_opt {
+        //     Some(branch_terminator_expr) => {
+        //         self.newline();
+        //         match enum_match_test_match_branch_node {
+        //             Return => {
+        //                 match &branch_terminator_expr.return_expr_t_opt {
+        //                     Some(expr_t) => {
+        //                         // self.add_code(&format!("e.Return = "));
+        //                         expr_t.accept(self);
+        //                         // self.add_code(";");
+        //                         // self.newline();
+        //                         self.add_code("return;");
+        //                     },
+        //                     None => {
+        //                         // self.add_code("return;")
+        //                     },
+        //                 }
+        //             }
+        //             Continue => {
+        //                 // self.add_code("break;");
+        //
+        //             }
+        //         }
+        //     }
+        //     None => {}
+        // }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_else_branch_node(
+        &mut self,
+        enum_match_test_else_branch_node: &EnumMatchTestElseBranchNode,
+    ) {
+        // self.add_code(&format!(" else {{"));
+        // self.indent();
+
+        self.visit_decl_stmts(&enum_match_test_else_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        //     match &enum_match_test_else_branch_node.branch_terminator_expr_opt {
+        //         Some(branch_terminator_expr) => {
+        //             self.newline();
+        //             match enum_match_test_else_branch_node {
+        //                 Return => {
+        //                     match &branch_terminator_expr.return_expr_t_opt {
+        //                         Some(expr_t) => {
+        //                             // self.add_code(&format!("e.Return = "));
+        //                             expr_t.accept(self);
+        //                             // self.add_code(";");
+        //                             // self.newline();
+        //                             // self.add_code("return;");
+        //                         },
+        //                         None => {
+        //                             // self.add_code("return;")
+        //                         },
+        //                     }
+        //                 }
+        //                 Continue => {
+        //                     // self.add_code("break;");
+        //
+        //                 }
+        //             }
+        //         }
+        //         None => {}
+        //     }
+
+        // self.outdent();
+        // self.newline();
+        // self.add_code(&format!("}}"));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_pattern_node(
+        &mut self,
+        match_pattern_node: &EnumMatchTestPatternNode,
+    ) {
+        let mut pattern = String::new();
+        match_pattern_node
+            .enumerator_expr_node
+            .accept_to_string(self, &mut pattern);
+        self.add_code(&pattern);
+    }
+
     //* --------------------------------------------------------------------- *//
 
     fn visit_expression_list_node(&mut self, expr_list: &ExprListNode) {
@@ -1938,6 +2129,19 @@ impl AstVisitor for PlantUmlVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_enumerator_expr_node_to_string(
+        &mut self,
+        enum_expr_node: &EnumeratorExprNode,
+        output: &mut String,
+    ) {
+        output.push_str(&format!(
+            "{}.{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_literal_expression_node_to_string(
         &mut self,
         literal_expression_node: &LiteralExprNode,
@@ -1,22 +1,46 @@
 use convert_case::{Case, Casing};
-use std::cell::Ref;
-use std::collections::HashSet;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
 
 use crate::frame_c::ast::*;
+use crate::frame_c::codemap::{CodeMap, CodeMapKind, CodeMapping};
 use crate::frame_c::config::*;
+use crate::frame_c::diagnostics::Span;
 use crate::frame_c::scanner::{Token, TokenType};
 use crate::frame_c::symbol_table::*;
+use crate::frame_c::template::FragmentTemplates;
 use crate::frame_c::visitors::*;
+use serde_json::json;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct TransitionInfo {
     is_change_state: bool,
+    is_deep_history: bool,
     event_name: String,
     label: String,
     source_name: String,
     target_name: Option<String>,
 }
 
+/// A state whose substates are divided into regions, which are active at the same time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ParallelState {
+    name: String,
+    regions: Vec<Region>,
+}
+
+/// A region of a parallel state, with its states in the order they are declared. The first is
+/// the initial state of the region.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Region {
+    name: String,
+    states: Vec<String>,
+}
+
+/// The fragments of the generated code that templates can override, see
+/// `codegen.rust.code.override_template_dir`.
+pub const FRAGMENTS: [&str; 3] = ["file_header", "interface_method", "state_handler"];
+
 pub struct RustVisitor {
     // general config and system info
     compiler_version: String,
@@ -34,12 +58,15 @@ pub struct RustVisitor {
     generate_state_stack: bool,
     generate_change_state: bool,
     generate_transition_state: bool,
+    generate_deep_history: bool,
+    generate_transition_actions: bool,
     generate_change_state_hook: bool,
     generate_transition_hook: bool,
 
     // static info about the state machine
     system_name: String,
     state_names: Vec<String>,
+    parallel_states: Vec<ParallelState>,
 
     // keeping track of traversal context
     current_state_name_opt: Option<String>,
@@ -57,6 +84,16 @@ pub struct RustVisitor {
     deserialize: Vec<String>,
     errors: Vec<String>,
     warnings: Vec<String>,
+    code_map: Vec<CodeMapping>,
+    // files of the state modules, by path relative to the generated code, and the names of
+    // the modules, if the `split_states` feature is enabled
+    state_files: Vec<(String, String)>,
+    state_modules: Vec<(String, String)>,
+    // byte offset in `code` up to which lines have been counted, and the count
+    counted_lines: (usize, usize),
+
+    // templates overriding fragments of the generated code, see `FRAGMENTS`
+    fragment_templates: Option<FragmentTemplates>,
 
     // comments from the spec to be inserted in generated code
     // (don't really understand how these work, see `generate_comment()`)
@@ -78,7 +115,10 @@ impl RustVisitor {
         generate_state_stack: bool,
         generate_change_state: bool,
         generate_transition_state: bool,
+        generate_deep_history: bool,
+        generate_transition_actions: bool,
         comments: Vec<Token>,
+        fragment_templates: Option<FragmentTemplates>,
     ) -> RustVisitor {
         let rust_config = config.codegen.rust;
         RustVisitor {
@@ -95,6 +135,8 @@ impl RustVisitor {
             generate_state_stack,
             generate_change_state,
             generate_transition_state,
+            generate_deep_history,
+            generate_transition_actions,
             generate_change_state_hook: rust_config.features.generate_hook_methods
                 && generate_change_state,
             generate_transition_hook: rust_config.features.generate_hook_methods
@@ -102,6 +144,7 @@ impl RustVisitor {
 
             system_name: String::new(),
             state_names: Vec::new(),
+            parallel_states: Vec::new(),
 
             current_state_name_opt: None,
             current_message: String::new(),
@@ -117,6 +160,12 @@ impl RustVisitor {
             deserialize: Vec::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
+            code_map: Vec::new(),
+            state_files: Vec::new(),
+            state_modules: Vec::new(),
+            counted_lines: (0, 0),
+
+            fragment_templates,
 
             comments,
             current_comment_idx: 0,
@@ -204,13 +253,37 @@ impl RustVisitor {
         } else {
             "as_ref().borrow()"
         };
+        // lightweight handlers access the state context through the state machine
+        let this_state_context = if self.config.features.lightweight {
+            let state_name = self.current_state_name_opt.clone().unwrap_or_default();
+            format!(
+                "self.{}.{}()",
+                self.config.code.state_context_var_name,
+                if self.in_assignment_lvalue {
+                    self.format_state_context_mut_method_name(&state_name)
+                } else {
+                    self.format_state_context_method_name(&state_name)
+                }
+            )
+        } else {
+            self.config.code.this_state_context_var_name.clone()
+        };
+        let borrow = if self.config.features.lightweight {
+            String::new()
+        } else {
+            format!(".{}", borrow)
+        };
         let mut borrowed = false;
         match var_node.scope {
             IdentifierDeclScope::DomainBlock => {
                 if var_node.id_node.is_reference {
                     code.push('&');
                 }
-                code.push_str(&format!("self.{}", var_name));
+                if var_node.is_constant() {
+                    code.push_str(&var_node.id_node.name.lexeme);
+                } else {
+                    code.push_str(&format!("self.{}", var_name));
+                }
             }
             IdentifierDeclScope::StateParam => {
                 borrowed = true;
@@ -221,11 +294,8 @@ impl RustVisitor {
                     code.push('&');
                 }
                 code.push_str(&format!(
-                    "{}.{}.{}.{}",
-                    self.config.code.this_state_context_var_name,
-                    self.config.code.state_args_var_name,
-                    borrow,
-                    var_name,
+                    "{}.{}{}.{}",
+                    this_state_context, self.config.code.state_args_var_name, borrow, var_name,
                 ));
                 if self.visiting_call_chain_literal_variable {
                     code.push(')');
@@ -240,11 +310,8 @@ impl RustVisitor {
                     code.push('&');
                 }
                 code.push_str(&format!(
-                    "{}.{}.{}.{}",
-                    self.config.code.this_state_context_var_name,
-                    self.config.code.state_vars_var_name,
-                    borrow,
-                    var_name,
+                    "{}.{}{}.{}",
+                    this_state_context, self.config.code.state_vars_var_name, borrow, var_name,
                 ));
                 if self.visiting_call_chain_literal_variable {
                     code.push(')');
@@ -264,7 +331,7 @@ impl RustVisitor {
                 );
                 let args_method_name = self.format_args_method_name(&event_type_name);
                 code.push_str(&format!(
-                    "{}.{}.{}.{}().{}",
+                    "{}.{}{}.{}().{}",
                     self.config.code.frame_event_variable_name,
                     self.config.code.frame_event_args_attribute_name,
                     borrow,
@@ -304,10 +371,14 @@ impl RustVisitor {
     fn format_parameter_list(&mut self, params: &[ParameterNode]) {
         for param in params {
             self.add_code(&", ".to_string());
-            let param_type: String = match &param.param_type_opt {
+            let mut param_type: String = match &param.param_type_opt {
                 Some(ret_type) => ret_type.get_type_str(),
                 None => String::from("<?>"),
             };
+            // parameters with a default value may be passed `None`
+            if param.default_opt.is_some() {
+                param_type = format!("Option<{}>", param_type);
+            }
             self.add_code(&format!(
                 "{}: {}",
                 self.format_value_name(&param.param_name),
@@ -357,8 +428,10 @@ impl RustVisitor {
 
     fn state_enum_type_name(&self) -> String {
         self.format_type_name(&format!(
-            "{}{}",
-            self.system_name, self.config.code.state_enum_suffix
+            "{}{}{}",
+            self.config.code.state_enum_prefix,
+            self.system_name,
+            self.config.code.state_enum_suffix
         ))
     }
 
@@ -405,6 +478,26 @@ impl RustVisitor {
         )
     }
 
+    /// Generate the implementation of the runtime interface and the event monitor integration?
+    fn runtime_event_monitor(&self) -> bool {
+        self.config.features.runtime_support && self.config.features.runtime_event_monitor
+    }
+
+    /// Generate the static machine info tables?
+    fn runtime_machine_info(&self) -> bool {
+        self.config.features.runtime_support && self.config.features.runtime_machine_info
+    }
+
+    /// Generate `Environment` implementations for arguments and variables?
+    fn runtime_environments(&self) -> bool {
+        self.config.features.runtime_support && self.config.features.runtime_environments
+    }
+
+    /// Generate the `Dispatch` implementation calling interface methods by name?
+    fn runtime_dispatch(&self) -> bool {
+        self.config.features.runtime_support && self.config.features.runtime_dispatch
+    }
+
     /// Get the type of reference-counted pointers.
     fn rc_type(&self) -> &str {
         if self.config.features.thread_safe {
@@ -423,11 +516,117 @@ impl RustVisitor {
         }
     }
 
+    /// Get the type of the field recording the calls of the mock actions.
+    fn action_calls_type(&self) -> &str {
+        if self.config.features.thread_safe {
+            "std::sync::Mutex<Vec<String>>"
+        } else {
+            "std::cell::RefCell<Vec<String>>"
+        }
+    }
+
+    /// Get an expression that mutably borrows the calls recorded by the mock actions.
+    fn action_calls_mut(&self) -> String {
+        format!(
+            "self.{}.{}",
+            self.config.code.action_calls_var_name,
+            if self.config.features.thread_safe {
+                "lock().unwrap()"
+            } else {
+                "borrow_mut()"
+            }
+        )
+    }
+
+    /// Get the type of the event parameter of handlers.
+    fn frame_event_param_type(&self) -> String {
+        if self.config.features.lightweight {
+            format!("&mut {}", self.config.code.frame_event_type_name)
+        } else {
+            format!(
+                "{}<{}>",
+                self.rc_type(),
+                self.config.code.frame_event_type_name
+            )
+        }
+    }
+
+    /// Get the type of the current state context stored in the state machine.
+    fn state_context_ptr_type(&self) -> String {
+        if self.config.features.lightweight {
+            self.config.code.state_context_type_name.clone()
+        } else {
+            format!(
+                "{}<{}>",
+                self.rc_type(),
+                self.config.code.state_context_type_name
+            )
+        }
+    }
+
+    /// Get the type of a field of a state context that holds the given struct.
+    fn state_context_field_type(&self, struct_name: &str) -> String {
+        if self.config.features.lightweight {
+            struct_name.to_string()
+        } else {
+            format!("{}<{}<{}>>", self.rc_type(), self.cell_type(), struct_name)
+        }
+    }
+
+    /// Get the expression that initializes a field of a state context with the given struct value.
+    fn state_context_field_value(&self, value: &str) -> String {
+        if self.config.features.lightweight {
+            value.to_string()
+        } else {
+            format!(
+                "{}::new({}::new({}))",
+                self.rc_type(),
+                self.cell_type(),
+                value
+            )
+        }
+    }
+
+    /// Get the beginning of a statement that creates a new event named `var_name`. The statement
+    /// is completed by the message and arguments of the event followed by `new_event_end`.
+    fn new_event_start(&self, var_name: &str) -> String {
+        if self.config.features.lightweight {
+            format!(
+                "let mut {} = {}::new(",
+                var_name, self.config.code.frame_event_type_name
+            )
+        } else {
+            format!(
+                "let {} = {}::new({}::new(",
+                var_name,
+                self.rc_type(),
+                self.config.code.frame_event_type_name
+            )
+        }
+    }
+
+    fn new_event_end(&self) -> &'static str {
+        if self.config.features.lightweight {
+            ");"
+        } else {
+            "));"
+        }
+    }
+
+    /// Get the expression that passes the event named `var_name` to `handle_event`.
+    fn event_arg(&self, var_name: &str) -> String {
+        if self.config.features.lightweight {
+            format!("&mut {}", var_name)
+        } else {
+            var_name.to_string()
+        }
+    }
+
     //* --------------------------------------------------------------------- *//
 
     /// Disable formatting/style warnings on generated type definitions.
     fn disable_type_style_warnings(&mut self) {
-        if !self.config.features.follow_rust_naming {
+        if !self.config.follows_rust_naming() {
             self.add_code("#[allow(clippy::upper_case_acronyms)]");
             self.newline();
             self.add_code("#[allow(non_camel_case_types)]");
@@ -471,21 +670,13 @@ impl RustVisitor {
     /// Format a "type-level" name, e.g. a type, trait, or enum variant.
     /// If Rust naming conventions are followed, these are in CamelCase.
     fn format_type_name(&self, name: &str) -> String {
-        let mut formatted = name.to_string();
-        if self.config.features.follow_rust_naming {
-            formatted = formatted.to_case(Case::UpperCamel);
-        }
-        formatted
+        self.config.format_type_name(name)
     }
 
     /// Format a "value-level" name, e.g. a function, method, variable.
     /// If Rust naming conventions are followed, these are  in snake_case.
     fn format_value_name(&self, name: &str) -> String {
-        let mut formatted = name.to_string();
-        if self.config.features.follow_rust_naming {
-            formatted = formatted.to_case(Case::Snake);
-        }
-        formatted
+        self.config.format_value_name(name)
     }
 
     fn format_getter_name(&self, member_name: &str) -> String {
@@ -589,6 +780,10 @@ impl RustVisitor {
         )
     }
 
+    fn format_state_context_mut_method_name(&self, state_name: &str) -> String {
+        format!("{}_mut", self.format_state_context_method_name(state_name))
+    }
+
     fn format_state_handler_name(&self, state_name: &str) -> String {
         format!(
             "{}{}{}",
@@ -598,6 +793,182 @@ impl RustVisitor {
         )
     }
 
+    /// Name of the field holding the current state of a region.
+    fn format_region_var_name(&self, parallel_state_name: &str, region_name: &str) -> String {
+        format!(
+            "{}_{}{}",
+            self.format_value_name(parallel_state_name),
+            self.format_value_name(region_name),
+            self.config.code.region_suffix
+        )
+    }
+
+    /// Name of the method dispatching events to the current state of a region.
+    fn format_region_handler_name(&self, parallel_state_name: &str, region_name: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.config.code.state_handler_name_prefix,
+            self.format_region_var_name(parallel_state_name, region_name),
+            self.config.code.state_handler_name_suffix
+        )
+    }
+
+    /// Name of the method changing the current state of a region.
+    fn format_region_transition_name(
+        &self,
+        parallel_state_name: &str,
+        region_name: &str,
+    ) -> String {
+        format!(
+            "{}_{}",
+            self.format_region_var_name(parallel_state_name, region_name),
+            self.config.code.transition_method_name
+        )
+    }
+
+    /// Name of the method dispatching the events of a parallel state to its regions and itself.
+    fn format_regions_handler_name(&self, parallel_state_name: &str) -> String {
+        format!(
+            "{}{}{}s{}",
+            self.config.code.state_handler_name_prefix,
+            self.format_value_name(parallel_state_name),
+            self.config.code.region_suffix,
+            self.config.code.state_handler_name_suffix
+        )
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Add a state to its region, adding the parallel state and region if new.
+    fn add_region_state(&mut self, parallel_state_name: &str, region_name: &str, state_name: &str) {
+        let parallel_state_idx = match self
+            .parallel_states
+            .iter()
+            .position(|parallel_state| parallel_state.name == parallel_state_name)
+        {
+            Some(idx) => idx,
+            None => {
+                self.parallel_states.push(ParallelState {
+                    name: parallel_state_name.to_string(),
+                    regions: Vec::new(),
+                });
+                self.parallel_states.len() - 1
+            }
+        };
+        let regions = &mut self.parallel_states[parallel_state_idx].regions;
+        match regions.iter_mut().find(|region| region.name == region_name) {
+            Some(region) => region.states.push(state_name.to_string()),
+            None => regions.push(Region {
+                name: region_name.to_string(),
+                states: vec![state_name.to_string()],
+            }),
+        }
+    }
+
+    /// The regions of all parallel states, with the name of their parallel state.
+    fn regions(&self) -> Vec<(String, Region)> {
+        self.parallel_states
+            .iter()
+            .flat_map(|parallel_state| {
+                parallel_state
+                    .regions
+                    .iter()
+                    .map(move |region| (parallel_state.name.clone(), region.clone()))
+            })
+            .collect()
+    }
+
+    fn is_parallel_state(&self, state_name: &str) -> bool {
+        self.parallel_states
+            .iter()
+            .any(|parallel_state| parallel_state.name == state_name)
+    }
+
+    /// The parallel state and region a state is in, if any.
+    fn region_of(&self, state_name: &str) -> Option<(String, String)> {
+        self.regions()
+            .into_iter()
+            .find(|(_, region)| region.states.iter().any(|name| name == state_name))
+            .map(|(parallel_state_name, region)| (parallel_state_name, region.name))
+    }
+
+    /// The parallel state that is or contains a state, if any.
+    fn enclosing_parallel_state(&self, state_name: &str) -> Option<String> {
+        if self.is_parallel_state(state_name) {
+            return Some(state_name.to_string());
+        }
+        self.region_of(state_name)
+            .map(|(parallel_state_name, _)| parallel_state_name)
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// The files of the state modules included by the generated code, by path relative to it,
+    /// if the `split_states` feature is enabled.
+    pub fn get_state_files(&self) -> Vec<(String, String)> {
+        self.state_files.clone()
+    }
+
+    /// The locations in the spec of the generated items, if the `generate_source_map` feature
+    /// is enabled.
+    pub fn get_code_map(&self) -> Option<CodeMap> {
+        if self.config.features.generate_source_map {
+            Some(CodeMap::new(self.code_map.clone()))
+        } else {
+            None
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    // Record the spec location of the item whose code starts on the current line.
+    fn record_mapping(&mut self, kind: CodeMapKind, name: &str, source_line: usize, span: Span) {
+        if !self.config.features.generate_source_map {
+            return;
+        }
+        let (counted_offset, counted_lines) = &mut self.counted_lines;
+        *counted_lines += self.code[*counted_offset..].matches('\n').count();
+        *counted_offset = self.code.len();
+        self.code_map.push(CodeMapping {
+            generated_line: *counted_lines + 1,
+            kind,
+            name: name.to_string(),
+            source_file: None,
+            source_line,
+            source_span: span,
+        });
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Replace the code generated since `start` with the template of the fragment, if it is
+    /// overridden. The template is rendered with the fields of `context` and `default`, the code
+    /// generated since `start`.
+    fn override_fragment(
+        &mut self,
+        fragment: &str,
+        start: usize,
+        context: impl FnOnce(&Self) -> serde_json::Value,
+    ) {
+        if !matches!(&self.fragment_templates, Some(templates) if templates.has(fragment)) {
+            return;
+        }
+        let mut context = context(self);
+        context["default"] = json!(&self.code[start..]);
+        let templates = self.fragment_templates.as_mut().unwrap();
+        if let Some(code) = templates.render(fragment, &context) {
+            self.code.truncate(start);
+            self.code.push_str(&code);
+        }
+    }
+
+    /// The first error rendering a template overriding a fragment, if any.
+    pub fn get_template_error(&self) -> Option<String> {
+        self.fragment_templates
+            .as_ref()
+            .and_then(|templates| templates.error().cloned())
+    }
+
     //* --------------------------------------------------------------------- *//
 
     pub fn run(&mut self, system_node: &SystemNode) {
@@ -668,6 +1039,24 @@ impl RustVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_expr_stmt(&mut self, expr_stmt_t: &ExprStmtType) {
+        match expr_stmt_t {
+            ExprStmtType::ActionCallStmtT {
+                action_call_stmt_node,
+            } => action_call_stmt_node.accept(self),
+            ExprStmtType::CallStmtT { call_stmt_node } => call_stmt_node.accept(self),
+            ExprStmtType::CallChainLiteralStmtT {
+                call_chain_literal_stmt_node,
+            } => call_chain_literal_stmt_node.accept(self),
+            ExprStmtType::AssignmentStmtT {
+                assignment_stmt_node,
+            } => assignment_stmt_node.accept(self),
+            ExprStmtType::VariableStmtT { variable_stmt_node } => variable_stmt_node.accept(self),
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_decl_stmts(&mut self, decl_stmt_types: &[DeclOrStmtType]) {
         for decl_stmt_t in decl_stmt_types.iter() {
             match decl_stmt_t {
@@ -677,23 +1066,9 @@ impl RustVisitor {
                 }
                 DeclOrStmtType::StmtT { stmt_t } => {
                     match stmt_t {
-                        StatementType::ExpressionStmt { expr_stmt_t } => match expr_stmt_t {
-                            ExprStmtType::ActionCallStmtT {
-                                action_call_stmt_node,
-                            } => action_call_stmt_node.accept(self),
-                            ExprStmtType::CallStmtT { call_stmt_node } => {
-                                call_stmt_node.accept(self)
-                            }
-                            ExprStmtType::CallChainLiteralStmtT {
-                                call_chain_literal_stmt_node,
-                            } => call_chain_literal_stmt_node.accept(self),
-                            ExprStmtType::AssignmentStmtT {
-                                assignment_stmt_node,
-                            } => assignment_stmt_node.accept(self),
-                            ExprStmtType::VariableStmtT { variable_stmt_node } => {
-                                variable_stmt_node.accept(self)
-                            }
-                        },
+                        StatementType::ExpressionStmt { expr_stmt_t } => {
+                            self.visit_expr_stmt(expr_stmt_t)
+                        }
                         StatementType::TransitionStmt {
                             transition_statement,
                         } => {
@@ -818,6 +1193,9 @@ impl RustVisitor {
         if let Some(domain_block_node) = &system_node.domain_block_node_opt {
             self.indent();
             for var_rcref in &domain_block_node.member_variables {
+                if var_rcref.borrow().is_constant {
+                    continue;
+                }
                 let var_name = var_rcref.borrow().name.clone();
                 let var_type = var_rcref
                     .borrow()
@@ -885,8 +1263,7 @@ impl RustVisitor {
     /// Generate the info entries for all of the declared actions.
     fn generate_action_info(&mut self) {
         self.add_code("const ACTIONS: &[&MethodInfo] = &[");
-        let mut action_names = self.arcanum.get_action_names();
-        action_names.sort();
+        let action_names = self.arcanum.get_action_names();
         if !action_names.is_empty() {
             self.indent();
             for action_name in action_names {
@@ -1015,6 +1392,8 @@ impl RustVisitor {
                     "kind: TransitionKind::{},",
                     if transition.is_change_state {
                         "ChangeState"
+                    } else if transition.is_deep_history {
+                        "DeepHistory"
                     } else {
                         "Transition"
                     }
@@ -1177,82 +1556,708 @@ impl RustVisitor {
     //* --------------------------------------------------------------------- *//
 
     /// Generate an enum type that enumerates the states of the machine.
-    fn generate_state_enum(&mut self, system_node: &SystemNode) {
-        // add derived traits
-        let mut traits = self.config.code.state_enum_traits.clone();
-        match &system_node.attributes_opt {
-            Some(attributes) => {
-                if let Some(new_traits) = attributes.get("override_state_enum_traits") {
-                    if let AttributeNode::MetaNameValueStr { attr } = new_traits {
-                        traits = attr.value.clone();
-                    }
-                }
-
-                if let Some(new_traits) = attributes.get("extend_state_enum_traits") {
-                    let new_traits_value: String = match new_traits {
-                        AttributeNode::MetaNameValueStr { attr } => attr.value.clone(),
-                        _ => String::new(),
-                    };
-                    if traits.is_empty() {
-                        traits = new_traits_value;
-                    } else {
-                        traits = format!("{}, {}", traits, new_traits_value);
-                    }
+    fn generate_domain_constants(&mut self, domain_block_node: &DomainBlockNode) {
+        for variable_decl_node_rcref in &domain_block_node.member_variables {
+            let variable_decl_node = variable_decl_node_rcref.borrow();
+            if !variable_decl_node.is_constant {
+                continue;
+            }
+            let var_type = variable_decl_node.type_opt.as_ref().unwrap().get_type_str();
+            let var_init_expr = variable_decl_node.initializer_expr_t_opt.as_ref().unwrap();
+            let mut code = String::new();
+            match var_init_expr {
+                // String::from() can't be evaluated in a const context
+                ExprType::LiteralExprT { literal_expr_node }
+                    if literal_expr_node.token_t == TokenType::String =>
+                {
+                    code.push_str(&format!("\"{}\"", literal_expr_node.value));
                 }
+                _ => var_init_expr.accept_to_string(self, &mut code),
             }
-            None => {}
-        }
-        self.disable_type_style_warnings();
-        if !traits.is_empty() {
-            self.add_code(&format!("#[derive({})]", traits));
             self.newline();
+            self.newline();
+            self.add_code("#[allow(dead_code)]");
+            self.newline();
+            self.add_code(&format!(
+                "const {}: {} = {};",
+                variable_decl_node.name, var_type, code
+            ));
         }
+    }
 
-        // add the state enum type
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the `Display` implementation of the state enum, which writes the name of the
+    /// state in the spec.
+    fn generate_state_enum_display(&mut self) {
         let state_enum_type = self.state_enum_type_name();
-        self.add_code(&format!("pub enum {} {{", state_enum_type));
+        self.add_code(&format!("impl std::fmt::Display for {}", state_enum_type));
+        self.enter_block();
+        self.add_code("fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result");
+        self.enter_block();
+        self.add_code("f.write_str(match self {");
         self.indent();
         for state_name in self.state_names.clone() {
             self.newline();
-            self.add_code(&format!("{},", self.format_type_name(&state_name)));
+            self.add_code(&format!(
+                "{}::{} => {:?},",
+                state_enum_type,
+                self.format_type_name(&state_name),
+                state_name
+            ));
         }
         self.outdent();
         self.newline();
-        self.add_code("}");
+        self.add_code("})");
+        self.exit_block();
+        self.exit_block();
+    }
 
-        // generate trivial runtime state impl if no state contexts
-        if self.config.features.runtime_support && !self.generate_state_context {
-            self.newline();
+    /// Generate the `FromStr` implementation of the state enum, which parses the name of a
+    /// state in the spec.
+    fn generate_state_enum_from_str(&mut self) {
+        let state_enum_type = self.state_enum_type_name();
+        self.add_code(&format!("impl std::str::FromStr for {}", state_enum_type));
+        self.enter_block();
+        self.add_code("type Err = String;");
+        self.newline();
+        self.add_code("fn from_str(s: &str) -> Result<Self, Self::Err>");
+        self.enter_block();
+        self.add_code("match s {");
+        self.indent();
+        for state_name in self.state_names.clone() {
             self.newline();
             self.add_code(&format!(
-                "impl {}::State<{}> for {}",
-                self.config.code.runtime_module_use_as_name,
-                self.system_type_name(),
+                "{:?} => Ok({}::{}),",
+                state_name,
                 state_enum_type,
+                self.format_type_name(&state_name)
             ));
-            self.enter_block();
+        }
+        self.newline();
+        self.add_code(&format!(
+            "_ => Err(format!(\"no state named `{{}}` in {}\", s)),",
+            self.system_name
+        ));
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+        self.exit_block();
+        self.exit_block();
+    }
 
+    /// Generate the `Display` implementation of the state machine, which writes the current
+    /// state followed by the values of the domain variables.
+    fn generate_machine_display(&mut self, system_node: &SystemNode) {
+        let mut domain_vars = Vec::new();
+        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+            for variable_decl_node_rcref in &domain_block_node.member_variables {
+                let variable_decl_node = variable_decl_node_rcref.borrow();
+                if !variable_decl_node.is_constant {
+                    domain_vars.push(variable_decl_node.name.clone());
+                }
+            }
+        }
+        self.add_code(&format!(
+            "impl std::fmt::Display for {}",
+            self.system_type_name()
+        ));
+        self.enter_block();
+        self.add_code("fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result");
+        self.enter_block();
+        if domain_vars.is_empty() {
             self.add_code(&format!(
-                "fn info(&self) -> &'static {}::StateInfo",
-                self.config.code.runtime_module_use_as_name,
+                "write!(f, \"{{}}\", self.{})",
+                self.config.code.state_var_name
             ));
-            self.enter_block();
-            self.add_code("match self {");
-            self.indent();
-            for (state_index, state_name) in self.state_names.clone().into_iter().enumerate() {
-                self.newline();
-                self.add_code(&format!(
-                    "{}::{} => {}::{}().states[{}],",
-                    state_enum_type,
-                    self.format_type_name(&state_name),
-                    self.config.code.runtime_info_module_name,
-                    self.config.code.machine_info_function_name,
-                    state_index
-                ));
-            }
-            self.exit_block();
-            self.exit_block();
-
+        } else {
+            let fields: Vec<String> = domain_vars
+                .iter()
+                .map(|name| format!("{}: {{:?}}", name))
+                .collect();
+            let values: Vec<String> = domain_vars
+                .iter()
+                .map(|name| format!("self.{}", self.format_value_name(name)))
+                .collect();
+            self.add_code(&format!(
+                "write!(f, \"{{}} {{{{ {} }}}}\", self.{}, {})",
+                fields.join(", "),
+                self.config.code.state_var_name,
+                values.join(", ")
+            ));
+        }
+        self.exit_block();
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the implementation of the `Dispatch` trait, which calls the interface method
+    /// with a given name, taking its arguments from a vector of `Box<dyn Any>`.
+    fn generate_dispatch(&mut self, system_node: &SystemNode) {
+        let runtime = self.config.code.runtime_module_use_as_name.clone();
+        let methods = match &system_node.interface_block_node_opt {
+            Some(interface_block_node) => interface_block_node.interface_methods.clone(),
+            None => Vec::new(),
+        };
+        self.add_code(&format!(
+            "impl {}::Dispatch for {}",
+            runtime,
+            self.system_type_name()
+        ));
+        self.enter_block();
+        self.add_code(&format!(
+            "fn dispatch(&mut self, method: &str, {}: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, {}::DispatchError>",
+            if methods.is_empty() { "_arguments" } else { "arguments" },
+            runtime,
+        ));
+        self.enter_block();
+        self.add_code("match method");
+        self.enter_block();
+        for method_rcref in &methods {
+            let method = method_rcref.borrow();
+            self.add_code(&format!("\"{}\" =>", method.name));
+            self.enter_block();
+            let params = method.params.clone().unwrap_or_default();
+            if params.is_empty() {
+                self.add_code(&format!(
+                    "{}::DispatchArgs::new(method, arguments).finish()?;",
+                    runtime
+                ));
+            } else {
+                self.add_code(&format!(
+                    "let mut args = {}::DispatchArgs::new(method, arguments);",
+                    runtime
+                ));
+            }
+            let mut arg_names = Vec::new();
+            for param in &params {
+                let param_type = match &param.param_type_opt {
+                    Some(param_type) => param_type.get_type_str(),
+                    None => String::from("<?>"),
+                };
+                let arg_name = self.format_value_name(&param.param_name);
+                self.newline();
+                self.add_code(&format!(
+                    "let {} = args.{}::<{}>(\"{}\", \"{}\")?;",
+                    arg_name,
+                    if param.default_opt.is_some() {
+                        "optional"
+                    } else {
+                        "required"
+                    },
+                    param_type,
+                    param.param_name,
+                    param_type,
+                ));
+                arg_names.push(arg_name);
+            }
+            if !params.is_empty() {
+                self.newline();
+                self.add_code("args.finish()?;");
+            }
+            self.newline();
+            let call = format!(
+                "self.{}({})",
+                self.format_value_name(&method.name),
+                arg_names.join(", ")
+            );
+            if method.return_type_opt.is_some() {
+                self.add_code(&format!("Ok(Some(Box::new({})))", call));
+            } else {
+                self.add_code(&format!("{};", call));
+                self.newline();
+                self.add_code("Ok(None)");
+            }
+            self.exit_block();
+            self.newline();
+        }
+        self.add_code(&format!(
+            "_ => Err({}::DispatchError::UnknownMethod(method.to_string())),",
+            runtime
+        ));
+        self.exit_block();
+        self.exit_block();
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn interface_trait_type_name(&self) -> String {
+        format!(
+            "{}{}",
+            self.system_type_name(),
+            self.config.code.interface_trait_suffix
+        )
+    }
+
+    fn fake_type_name(&self) -> String {
+        format!(
+            "{}{}",
+            self.config.code.fake_type_prefix,
+            self.system_type_name()
+        )
+    }
+
+    /// The signature of an interface method, as generated for the machine but without `pub`.
+    fn interface_method_signature(&self, method: &InterfaceMethodNode) -> String {
+        let mut signature = format!("fn {}(&mut self", self.format_value_name(&method.name));
+        for param in method.params.iter().flatten() {
+            let mut param_type = match &param.param_type_opt {
+                Some(param_type) => param_type.get_type_str(),
+                None => String::from("<?>"),
+            };
+            if param.default_opt.is_some() {
+                param_type = format!("Option<{}>", param_type);
+            }
+            signature.push_str(&format!(
+                ", {}: {}",
+                self.format_value_name(&param.param_name),
+                param_type
+            ));
+        }
+        signature.push(')');
+        if let Some(return_type) = &method.return_type_opt {
+            signature.push_str(&format!(" -> {}", return_type.get_type_str()));
+        }
+        signature
+    }
+
+    /// Generate the interface trait of the machine, implemented by the machine and by a fake
+    /// whose interface methods follow a script, so that code using the machine through the
+    /// trait can be tested without driving the machine.
+    fn generate_fake(&mut self, system_node: &SystemNode) {
+        let methods = match &system_node.interface_block_node_opt {
+            Some(interface_block_node) => interface_block_node.interface_methods.clone(),
+            None => Vec::new(),
+        };
+        let system_type = self.system_type_name();
+        let state_enum_type = self.state_enum_type_name();
+        let state_var = self.config.code.state_var_name.clone();
+        let interface_trait = self.interface_trait_type_name();
+        let fake_type = self.fake_type_name();
+
+        // the interface trait
+        self.add_code(&format!(
+            "/// The interface of `{}`, implemented by the machine and by `{}`.",
+            system_type, fake_type
+        ));
+        self.newline();
+        self.disable_type_style_warnings();
+        self.add_code(&format!("pub trait {}", interface_trait));
+        self.enter_block();
+        for method_rcref in &methods {
+            let signature = self.interface_method_signature(&method_rcref.borrow());
+            self.add_code(&format!("{};", signature));
+            self.newline();
+        }
+        self.add_code("/// The current state of the machine.");
+        self.newline();
+        self.add_code(&format!("fn {}(&self) -> {};", state_var, state_enum_type));
+        self.exit_block();
+        self.newline();
+        self.newline();
+
+        // its implementation by the machine
+        self.add_code(&format!("impl {} for {}", interface_trait, system_type));
+        self.enter_block();
+        for method_rcref in &methods {
+            let method = method_rcref.borrow();
+            let signature = self.interface_method_signature(&method);
+            let args: Vec<String> = method
+                .params
+                .iter()
+                .flatten()
+                .map(|param| self.format_value_name(&param.param_name))
+                .collect();
+            self.add_code(&signature);
+            self.enter_block();
+            self.add_code(&format!(
+                "{}::{}(self{})",
+                system_type,
+                self.format_value_name(&method.name),
+                args.iter()
+                    .map(|arg| format!(", {}", arg))
+                    .collect::<String>()
+            ));
+            self.exit_block();
+            self.newline();
+        }
+        self.add_code(&format!("fn {}(&self) -> {}", state_var, state_enum_type));
+        self.enter_block();
+        self.add_code(&format!("self.{}", state_var));
+        self.exit_block();
+        self.exit_block();
+        self.newline();
+        self.newline();
+
+        // the fake, with a queue of scripted calls per interface method
+        self.add_code(&format!(
+            "/// A stand-in for `{}`, whose interface methods return scripted values and enter",
+            system_type
+        ));
+        self.newline();
+        self.add_code(
+            "/// scripted states instead of running the machine. Calls that are not scripted return the",
+        );
+        self.newline();
+        self.add_code("/// default value of their return type and keep the current state.");
+        self.newline();
+        self.disable_type_style_warnings();
+        self.add_code(&format!("pub struct {}", fake_type));
+        self.enter_block();
+        self.add_code(&format!("{}: {},", state_var, state_enum_type));
+        self.newline();
+        self.add_code("calls: Vec<String>,");
+        for method_rcref in &methods {
+            let method = method_rcref.borrow();
+            let script_type = match &method.return_type_opt {
+                Some(return_type) => format!(
+                    "({}, Option<{}>)",
+                    return_type.get_type_str(),
+                    state_enum_type
+                ),
+                None => format!("Option<{}>", state_enum_type),
+            };
+            self.newline();
+            self.add_code(&format!(
+                "{}_script: std::collections::VecDeque<{}>,",
+                self.format_value_name(&method.name),
+                script_type
+            ));
+        }
+        self.exit_block();
+        self.newline();
+        self.newline();
+
+        self.disable_type_style_warnings();
+        self.add_code(&format!("impl {}", fake_type));
+        self.enter_block();
+        self.add_code("/// A fake in the given state, with an empty script.");
+        self.newline();
+        self.add_code(&format!(
+            "pub fn new({}: {}) -> Self",
+            state_var, state_enum_type
+        ));
+        self.enter_block();
+        self.add_code(&fake_type);
+        self.enter_block();
+        self.add_code(&format!("{},", state_var));
+        self.newline();
+        self.add_code("calls: Vec::new(),");
+        for method_rcref in &methods {
+            self.newline();
+            self.add_code(&format!(
+                "{}_script: Default::default(),",
+                self.format_value_name(&method_rcref.borrow().name)
+            ));
+        }
+        self.exit_block();
+        self.exit_block();
+        for method_rcref in &methods {
+            let method = method_rcref.borrow();
+            let method_name = self.format_value_name(&method.name);
+            self.newline();
+            self.newline();
+            match &method.return_type_opt {
+                Some(return_type) => {
+                    self.add_code(&format!(
+                        "/// Script the next call of `{}` to return `value`, and to enter `{}` unless it is `None`.",
+                        method_name, state_var
+                    ));
+                    self.newline();
+                    self.add_code(&format!(
+                        "pub fn script_{}(&mut self, value: {}, {}: Option<{}>) -> &mut Self",
+                        method_name,
+                        return_type.get_type_str(),
+                        state_var,
+                        state_enum_type
+                    ));
+                    self.enter_block();
+                    self.add_code(&format!(
+                        "self.{}_script.push_back((value, {}));",
+                        method_name, state_var
+                    ));
+                }
+                None => {
+                    self.add_code(&format!(
+                        "/// Script the next call of `{}` to enter `{}` unless it is `None`.",
+                        method_name, state_var
+                    ));
+                    self.newline();
+                    self.add_code(&format!(
+                        "pub fn script_{}(&mut self, {}: Option<{}>) -> &mut Self",
+                        method_name, state_var, state_enum_type
+                    ));
+                    self.enter_block();
+                    self.add_code(&format!(
+                        "self.{}_script.push_back({});",
+                        method_name, state_var
+                    ));
+                }
+            }
+            self.newline();
+            self.add_code("self");
+            self.exit_block();
+        }
+        self.newline();
+        self.newline();
+        self.add_code(
+            "/// Take the calls of the interface methods since the last call, formatted like call",
+        );
+        self.newline();
+        self.add_code("/// expressions with the arguments formatted with `Debug`.");
+        self.newline();
+        self.add_code("pub fn take_calls(&mut self) -> Vec<String>");
+        self.enter_block();
+        self.add_code("std::mem::take(&mut self.calls)");
+        self.exit_block();
+        self.newline();
+        self.newline();
+        self.add_code("/// Whether all of the scripted calls have been made.");
+        self.newline();
+        self.add_code("pub fn is_script_done(&self) -> bool");
+        self.enter_block();
+        let done: Vec<String> = methods
+            .iter()
+            .map(|method_rcref| {
+                format!(
+                    "self.{}_script.is_empty()",
+                    self.format_value_name(&method_rcref.borrow().name)
+                )
+            })
+            .collect();
+        if done.is_empty() {
+            self.add_code("true");
+        } else {
+            self.add_code(&done.join(" && "));
+        }
+        self.exit_block();
+        self.exit_block();
+        self.newline();
+        self.newline();
+
+        self.add_code(&format!("impl {} for {}", interface_trait, fake_type));
+        self.enter_block();
+        for method_rcref in &methods {
+            let method = method_rcref.borrow();
+            let method_name = self.format_value_name(&method.name);
+            let signature = self.interface_method_signature(&method);
+            let args: Vec<String> = method
+                .params
+                .iter()
+                .flatten()
+                .map(|param| self.format_value_name(&param.param_name))
+                .collect();
+            self.add_code(&signature);
+            self.enter_block();
+            let call = if args.is_empty() {
+                format!("\"{}()\".to_string()", method_name)
+            } else {
+                format!(
+                    "format!(\"{}({})\", {})",
+                    method_name,
+                    vec!["{:?}"; args.len()].join(", "),
+                    args.join(", ")
+                )
+            };
+            self.add_code(&format!("self.calls.push({});", call));
+            self.newline();
+            if method.return_type_opt.is_some() {
+                self.add_code(&format!(
+                    "let (value, {}) = self.{}_script.pop_front().unwrap_or_default();",
+                    state_var, method_name
+                ));
+            } else {
+                self.add_code(&format!(
+                    "let {} = self.{}_script.pop_front().flatten();",
+                    state_var, method_name
+                ));
+            }
+            self.newline();
+            self.add_code(&format!("if let Some({}) = {}", state_var, state_var));
+            self.enter_block();
+            self.add_code(&format!("self.{} = {};", state_var, state_var));
+            self.exit_block();
+            if method.return_type_opt.is_some() {
+                self.newline();
+                self.add_code("value");
+            }
+            self.exit_block();
+            self.newline();
+        }
+        self.add_code(&format!("fn {}(&self) -> {}", state_var, state_enum_type));
+        self.enter_block();
+        self.add_code(&format!("self.{}", state_var));
+        self.exit_block();
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// The domain variables of the system, excluding constants.
+    fn domain_variables(system_node: &SystemNode) -> Vec<Rc<RefCell<VariableDeclNode>>> {
+        match &system_node.domain_block_node_opt {
+            Some(domain_block_node) => domain_block_node
+                .member_variables
+                .iter()
+                .filter(|var_rcref| !var_rcref.borrow().is_constant)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn domain_type_name(&self) -> String {
+        format!(
+            "{}{}",
+            self.system_type_name(),
+            self.config.code.domain_type_suffix
+        )
+    }
+
+    /// Generate the struct of initial domain variable values taken by the `new_with`
+    /// constructor, with a `Default` implementation using the initializers in the spec.
+    fn generate_domain_struct(&mut self, system_node: &SystemNode) {
+        let domain_vars = Self::domain_variables(system_node);
+        let domain_type_name = self.domain_type_name();
+        self.add_code(&format!(
+            "/// Initial values of the domain variables of `{}`.",
+            self.system_type_name()
+        ));
+        self.newline();
+        self.add_code(&format!("pub struct {}", domain_type_name));
+        self.enter_block();
+        for (i, variable_decl_node_rcref) in domain_vars.iter().enumerate() {
+            let variable_decl_node = variable_decl_node_rcref.borrow();
+            let var_type = match &variable_decl_node.type_opt {
+                Some(x) => x.get_type_str(),
+                None => String::from("<?>"),
+            };
+            if i > 0 {
+                self.newline();
+            }
+            self.add_code(&format!(
+                "pub {}: {},",
+                self.format_value_name(&variable_decl_node.name),
+                var_type
+            ));
+        }
+        self.exit_block();
+        self.newline();
+        self.newline();
+        self.add_code(&format!("impl Default for {}", domain_type_name));
+        self.enter_block();
+        self.add_code("fn default() -> Self");
+        self.enter_block();
+        self.add_code(&domain_type_name);
+        self.enter_block();
+        for (i, variable_decl_node_rcref) in domain_vars.iter().enumerate() {
+            let variable_decl_node = variable_decl_node_rcref.borrow();
+            let var_init_expr = &variable_decl_node.initializer_expr_t_opt.as_ref().unwrap();
+            let mut code = String::new();
+            var_init_expr.accept_to_string(self, &mut code);
+            if i > 0 {
+                self.newline();
+            }
+            self.add_code(&format!(
+                "{}: {},",
+                self.format_value_name(&variable_decl_node.name),
+                code
+            ));
+        }
+        self.exit_block();
+        self.exit_block();
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn generate_state_enum(&mut self, system_node: &SystemNode) {
+        // add derived traits
+        let mut traits = self.config.code.state_enum_traits.clone();
+        match &system_node.attributes_opt {
+            Some(attributes) => {
+                if let Some(new_traits) = attributes.get("override_state_enum_traits") {
+                    if let AttributeNode::MetaNameValueStr { attr } = new_traits {
+                        traits = attr.value.clone();
+                    }
+                }
+
+                if let Some(new_traits) = attributes.get("extend_state_enum_traits") {
+                    let new_traits_value: String = match new_traits {
+                        AttributeNode::MetaNameValueStr { attr } => attr.value.clone(),
+                        _ => String::new(),
+                    };
+                    if traits.is_empty() {
+                        traits = new_traits_value;
+                    } else {
+                        traits = format!("{}, {}", traits, new_traits_value);
+                    }
+                }
+            }
+            None => {}
+        }
+        self.disable_type_style_warnings();
+        if !traits.is_empty() {
+            self.add_code(&format!("#[derive({})]", traits));
+            self.newline();
+        }
+
+        // add the state enum type
+        let state_enum_type = self.state_enum_type_name();
+        self.add_code(&format!("pub enum {} {{", state_enum_type));
+        self.indent();
+        for state_name in self.state_names.clone() {
+            self.newline();
+            self.add_code(&format!("{},", self.format_type_name(&state_name)));
+        }
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+
+        if self.config.features.generate_display && self.has_states {
+            self.newline();
+            self.newline();
+            self.generate_state_enum_display();
+            self.newline();
+            self.newline();
+            self.generate_state_enum_from_str();
+        }
+
+        // generate trivial runtime state impl if no state contexts
+        if self.runtime_event_monitor() && !self.generate_state_context {
+            self.newline();
+            self.newline();
+            self.add_code(&format!(
+                "impl {}::State<{}> for {}",
+                self.config.code.runtime_module_use_as_name,
+                self.system_type_name(),
+                state_enum_type,
+            ));
+            self.enter_block();
+
+            self.add_code(&format!(
+                "fn info(&self) -> &'static {}::StateInfo",
+                self.config.code.runtime_module_use_as_name,
+            ));
+            self.enter_block();
+            self.add_code("match self {");
+            self.indent();
+            for (state_index, state_name) in self.state_names.clone().into_iter().enumerate() {
+                self.newline();
+                self.add_code(&format!(
+                    "{}::{} => {}::{}().states[{}],",
+                    state_enum_type,
+                    self.format_type_name(&state_name),
+                    self.config.code.runtime_info_module_name,
+                    self.config.code.machine_info_function_name,
+                    state_index
+                ));
+            }
+            self.exit_block();
+            self.exit_block();
+
             self.newline();
             self.add_code(&format!(
                 "fn arguments(&self) -> {}::EnvironmentPtr",
@@ -1300,20 +2305,34 @@ impl RustVisitor {
             self.config.code.frame_event_message_type_name
         ));
         self.newline();
-        self.add_code(&format!(
-            "{}: {}<{}<{}>>,",
-            self.config.code.frame_event_args_attribute_name,
-            self.rc_type(),
-            self.cell_type(),
-            self.config.code.frame_event_args_type_name
-        ));
-        self.newline();
-        self.add_code(&format!(
-            "{}: {}<{}>,",
-            self.config.code.frame_event_return_attribute_name,
-            self.cell_type(),
-            self.config.code.frame_event_return_type_name
-        ));
+        if self.config.features.lightweight {
+            self.add_code(&format!(
+                "{}: {},",
+                self.config.code.frame_event_args_attribute_name,
+                self.config.code.frame_event_args_type_name
+            ));
+            self.newline();
+            self.add_code(&format!(
+                "{}: {},",
+                self.config.code.frame_event_return_attribute_name,
+                self.config.code.frame_event_return_type_name
+            ));
+        } else {
+            self.add_code(&format!(
+                "{}: {}<{}<{}>>,",
+                self.config.code.frame_event_args_attribute_name,
+                self.rc_type(),
+                self.cell_type(),
+                self.config.code.frame_event_args_type_name
+            ));
+            self.newline();
+            self.add_code(&format!(
+                "{}: {}<{}>,",
+                self.config.code.frame_event_return_attribute_name,
+                self.cell_type(),
+                self.config.code.frame_event_return_type_name
+            ));
+        }
         self.exit_block();
 
         self.newline();
@@ -1337,24 +2356,37 @@ impl RustVisitor {
             self.config.code.frame_event_message_attribute_name
         ));
         self.newline();
-        self.add_code(&format!(
-            "{0}: {1}::new({2}::new({0})),",
-            self.config.code.frame_event_args_attribute_name,
-            self.rc_type(),
-            self.cell_type(),
-        ));
-        self.newline();
-        self.add_code(&format!(
-            "{}: {}::new({}::None),",
-            self.config.code.frame_event_return_attribute_name,
-            self.cell_type(),
-            self.config.code.frame_event_return_type_name,
-        ));
+        if self.config.features.lightweight {
+            self.add_code(&format!(
+                "{},",
+                self.config.code.frame_event_args_attribute_name
+            ));
+            self.newline();
+            self.add_code(&format!(
+                "{}: {}::None,",
+                self.config.code.frame_event_return_attribute_name,
+                self.config.code.frame_event_return_type_name,
+            ));
+        } else {
+            self.add_code(&format!(
+                "{0}: {1}::new({2}::new({0})),",
+                self.config.code.frame_event_args_attribute_name,
+                self.rc_type(),
+                self.cell_type(),
+            ));
+            self.newline();
+            self.add_code(&format!(
+                "{}: {}::new({}::None),",
+                self.config.code.frame_event_return_attribute_name,
+                self.cell_type(),
+                self.config.code.frame_event_return_type_name,
+            ));
+        }
         self.exit_block();
         self.exit_block();
         self.exit_block();
 
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.newline();
             self.add_code(&format!(
@@ -1444,7 +2476,7 @@ impl RustVisitor {
     /// Generate the structs, enum, and supporting definitions related to event return values.
     fn generate_event_return_defs(&mut self, system_node: &SystemNode) {
         self.add_code("#[allow(dead_code)]");
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.add_code("#[derive(Clone)]");
         }
@@ -1514,7 +2546,8 @@ impl RustVisitor {
 
     /// Generate the structs, enum, and supporting function definitions related to event arguments.
     fn generate_event_arg_defs(&mut self) {
-        let mut has_params: HashSet<String> = HashSet::new();
+        // event type names of the events with parameters, in declaration order
+        let mut has_params: Vec<String> = Vec::new();
 
         // generate an arg struct for all events that have parameters
         for event_name in self.arcanum.get_event_names() {
@@ -1546,10 +2579,12 @@ impl RustVisitor {
                     self.newline();
 
                     // generate the env
-                    if self.config.features.runtime_support {
+                    if self.runtime_environments() {
                         self.generate_environment_impl(&args_struct_name, &bound_names);
                     }
-                    has_params.insert(event_type_name);
+                    if !has_params.contains(&event_type_name) {
+                        has_params.push(event_type_name);
+                    }
                 }
             }
         }
@@ -1579,7 +2614,7 @@ impl RustVisitor {
         self.newline();
 
         // generate environment impl for enum type
-        if self.config.features.runtime_support {
+        if self.runtime_environments() {
             self.add_code(&format!(
                 "impl {}::Environment for {}",
                 self.config.code.runtime_module_use_as_name,
@@ -1674,6 +2709,9 @@ impl RustVisitor {
     /// Generate the struct, enum, and supporting function definitions related to state contexts,
     /// which are used to store the values of state parameters and state variables.
     fn generate_state_context_defs(&mut self, system_node: &SystemNode) {
+        // lightweight state contexts are saved on the state stack and for deep history by value
+        let clone_contexts = self.config.features.lightweight
+            && (self.generate_state_stack || self.generate_deep_history);
         if let Some(machine_block_node) = &system_node.machine_block_node_opt {
             let states = machine_block_node.states.clone();
             for (state_index, state) in states.into_iter().enumerate() {
@@ -1688,7 +2726,7 @@ impl RustVisitor {
                         let mut bound_names: Vec<String> = Vec::new();
 
                         self.disable_type_style_warnings();
-                        if self.config.features.thread_safe {
+                        if self.config.features.thread_safe || clone_contexts {
                             self.add_code("#[derive(Clone)]");
                             self.newline();
                         }
@@ -1708,7 +2746,7 @@ impl RustVisitor {
                         self.newline();
                         self.newline();
 
-                        if self.config.features.runtime_support {
+                        if self.runtime_environments() {
                             self.generate_environment_impl(&state_args_struct_name, &bound_names);
                         }
                     }
@@ -1724,7 +2762,10 @@ impl RustVisitor {
                         let mut bound_names: Vec<String> = Vec::new();
 
                         self.disable_type_style_warnings();
-                        if self.generate_state_stack || self.config.features.thread_safe {
+                        if self.generate_state_stack
+                            || self.config.features.thread_safe
+                            || clone_contexts
+                        {
                             self.add_code("#[derive(Clone)]");
                             self.newline();
                         }
@@ -1744,7 +2785,7 @@ impl RustVisitor {
                         self.newline();
                         self.newline();
 
-                        if self.config.features.runtime_support {
+                        if self.runtime_environments() {
                             self.generate_environment_impl(&state_vars_struct_name, &bound_names);
                         }
                     }
@@ -1754,28 +2795,28 @@ impl RustVisitor {
                 // generate state context struct for this state
                 let context_struct_name = self.format_state_context_struct_name(&state_node.name);
                 self.disable_type_style_warnings();
+                if clone_contexts {
+                    self.add_code("#[derive(Clone)]");
+                    self.newline();
+                }
                 self.add_code(&format!("struct {} {{", context_struct_name));
                 self.indent();
 
                 if has_state_args {
                     self.newline();
                     self.add_code(&format!(
-                        "{}: {}<{}<{}>>,",
+                        "{}: {},",
                         self.config.code.state_args_var_name,
-                        self.rc_type(),
-                        self.cell_type(),
-                        state_args_struct_name,
+                        self.state_context_field_type(&state_args_struct_name),
                     ));
                 }
 
                 if has_state_vars {
                     self.newline();
                     self.add_code(&format!(
-                        "{}: {}<{}<{}>>,",
+                        "{}: {},",
                         self.config.code.state_vars_var_name,
-                        self.rc_type(),
-                        self.cell_type(),
-                        state_vars_struct_name,
+                        self.state_context_field_type(&state_vars_struct_name),
                     ));
                 }
 
@@ -1784,7 +2825,7 @@ impl RustVisitor {
                 self.newline();
 
                 // generate a deep-clone function to save snapshots for state stack
-                if self.generate_state_stack {
+                if self.generate_state_stack && !self.config.features.lightweight {
                     self.add_code(&format!("impl {}", context_struct_name));
                     self.enter_block();
                     self.add_code(&format!("fn deep_clone(&self) -> {}", context_struct_name));
@@ -1815,7 +2856,7 @@ impl RustVisitor {
                 }
 
                 // generate implementation of runtime state
-                if self.config.features.runtime_support {
+                if self.runtime_event_monitor() {
                     self.add_code(&format!(
                         "impl {}::State<{}> for {}",
                         self.config.code.runtime_module_use_as_name,
@@ -1879,6 +2920,10 @@ impl RustVisitor {
 
             // generate the enum type that unions all the state context types
             self.disable_type_style_warnings();
+            if clone_contexts {
+                self.add_code("#[derive(Clone)]");
+                self.newline();
+            }
             self.add_code(&format!(
                 "enum {} {{",
                 self.config.code.state_context_type_name
@@ -1928,10 +2973,35 @@ impl RustVisitor {
                 ));
                 self.exit_block();
                 self.exit_block();
+                if self.config.features.lightweight {
+                    self.newline();
+                    self.add_code(&format!(
+                        "fn {}(&mut self) -> &mut {} {{",
+                        self.format_state_context_mut_method_name(state_name),
+                        self.format_state_context_struct_name(state_name)
+                    ));
+                    self.indent();
+                    self.newline();
+                    self.add_code("match self {");
+                    self.indent();
+                    self.newline();
+                    self.add_code(&format!(
+                        "{}::{}(context) => context,",
+                        self.config.code.state_context_type_name,
+                        self.format_type_name(state_name)
+                    ));
+                    self.newline();
+                    self.add_code(&format!(
+                        "_ => panic!(\"Failed conversion to {}\"),",
+                        self.format_state_context_struct_name(state_name)
+                    ));
+                    self.exit_block();
+                    self.exit_block();
+                }
             }
 
             // generate a deep-clone function to save snapshots for state stack
-            if self.generate_state_stack {
+            if self.generate_state_stack && !self.config.features.lightweight {
                 self.newline();
                 self.add_code(&format!(
                     "fn deep_clone(&self) -> {}",
@@ -1955,7 +3025,7 @@ impl RustVisitor {
             self.exit_block();
 
             // generate runtime implementation for the enum type
-            if self.config.features.runtime_support {
+            if self.runtime_event_monitor() {
                 self.newline();
                 self.newline();
                 self.add_code(&format!(
@@ -2068,8 +3138,24 @@ impl RustVisitor {
     //* --------------------------------------------------------------------- *//
 
     /// Generate the constructor function.
-    fn generate_constructor(&mut self, system_node: &SystemNode) {
-        self.add_code("pub fn new() -> Self {");
+    /// Generate the `new` constructor, or the `new_with` constructor taking the initial values of
+    /// the domain variables and whether to enter the initial state.
+    fn generate_constructor(&mut self, system_node: &SystemNode, with_domain: bool) {
+        let domain_vars = Self::domain_variables(system_node);
+        if with_domain {
+            self.add_code(&format!(
+                "pub fn {}({}: {}, enter: bool) -> Self {{",
+                self.config.code.new_with_method_name,
+                if domain_vars.is_empty() {
+                    "_domain"
+                } else {
+                    "domain"
+                },
+                self.domain_type_name()
+            ));
+        } else {
+            self.add_code("pub fn new() -> Self {");
+        }
         self.indent();
 
         let init_state_name = self.init_state_name();
@@ -2102,6 +3188,15 @@ impl RustVisitor {
             self.state_enum_type_name(),
             self.format_type_name(&init_state_name)
         ));
+        for (parallel_state_name, region) in self.regions() {
+            self.newline();
+            self.add_code(&format!(
+                "{}: {}::{},",
+                self.format_region_var_name(&parallel_state_name, &region.name),
+                self.state_enum_type_name(),
+                self.format_type_name(&region.states[0])
+            ));
+        }
         if self.generate_state_context {
             self.newline();
             self.add_code(&format!(
@@ -2119,8 +3214,17 @@ impl RustVisitor {
             ));
         }
 
+        // initialize the deep history
+        if self.generate_deep_history {
+            self.newline();
+            self.add_code(&format!(
+                "{}: std::collections::HashMap::new(),",
+                self.config.code.state_history_var_name
+            ));
+        }
+
         // initialize runtime support
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.add_code(&format!(
                 "{}: {}::EventMonitor::new({:?}, {:?}),",
@@ -2131,17 +3235,29 @@ impl RustVisitor {
             ));
         }
 
+        // initialize the calls recorded by the mock actions
+        if self.config.features.generate_mock_actions {
+            self.newline();
+            self.add_code(&format!(
+                "{}: Default::default(),",
+                self.config.code.action_calls_var_name
+            ));
+        }
+
         // initialize domain variables
-        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
-            for variable_decl_node_rcref in &domain_block_node.member_variables {
-                let variable_decl_node = variable_decl_node_rcref.borrow();
-                let variable_name = self.format_value_name(&variable_decl_node.name);
+        for variable_decl_node_rcref in &domain_vars {
+            let variable_decl_node = variable_decl_node_rcref.borrow();
+            let variable_name = self.format_value_name(&variable_decl_node.name);
+            let code = if with_domain {
+                format!("domain.{}", variable_name)
+            } else {
                 let var_init_expr = &variable_decl_node.initializer_expr_t_opt.as_ref().unwrap();
                 let mut code = String::new();
                 var_init_expr.accept_to_string(self, &mut code);
-                self.newline();
-                self.add_code(&format!("{}: {},", variable_name, code));
-            }
+                code
+            };
+            self.newline();
+            self.add_code(&format!("{}: {},", variable_name, code));
         }
 
         self.outdent();
@@ -2151,10 +3267,20 @@ impl RustVisitor {
 
         // run the initialize method on the new machine
         self.newline();
-        self.add_code(&format!(
-            "machine.{}();",
-            self.config.code.initialize_method_name
-        ));
+        if with_domain {
+            self.add_code("if enter");
+            self.enter_block();
+            self.add_code(&format!(
+                "machine.{}();",
+                self.config.code.initialize_method_name
+            ));
+            self.exit_block();
+        } else {
+            self.add_code(&format!(
+                "machine.{}();",
+                self.config.code.initialize_method_name
+            ));
+        }
         self.newline();
 
         // return the new machine
@@ -2167,6 +3293,88 @@ impl RustVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    /// Generate the handler of a state into the file of its own module, instead of the code of
+    /// the state machine.
+    fn generate_state_file(&mut self, state_node: &StateNode) {
+        let machine_code = std::mem::take(&mut self.code);
+        let machine_dent = self.dent;
+        self.dent = 0;
+
+        self.add_code(&format!("// {}", self.compiler_version));
+        self.newline();
+        self.add_code("use super::*;");
+        self.newline();
+        self.newline();
+        self.disable_all_style_warnings();
+        self.add_code(&format!("impl {} {{", self.system_type_name()));
+        self.indent();
+        state_node.accept(self);
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+        self.newline();
+
+        let state_code = std::mem::replace(&mut self.code, machine_code);
+        self.dent = machine_dent;
+        // the directory is named after the spec, like the generated file
+        let dir_name = self
+            .input_path
+            .as_ref()
+            .and_then(|path| std::path::Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.system_name.to_case(Case::Snake));
+        let state_file_name = state_node.name.to_case(Case::Snake);
+        let path = format!("{}/{}.rs", dir_name, state_file_name);
+        self.state_files.push((path.clone(), state_code));
+        self.state_modules
+            .push((format!("{}_state", state_file_name), path));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the method that returns the calls recorded by the mock actions.
+    fn generate_take_action_calls(&mut self) {
+        self.add_code(&format!(
+            "pub fn {}(&self) -> Vec<String>",
+            self.config.code.take_action_calls_method_name
+        ));
+        self.enter_block();
+        self.add_code(&format!(
+            "std::mem::take(&mut *{})",
+            self.action_calls_mut()
+        ));
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the statement of a mock action that records its call, formatted like a call
+    /// expression with the arguments formatted with `Debug`.
+    fn generate_action_call_record(
+        &mut self,
+        action_name: &str,
+        params_opt: &Option<Vec<ParameterNode>>,
+    ) {
+        let param_names: Vec<String> = params_opt
+            .iter()
+            .flatten()
+            .map(|param| self.format_value_name(&param.param_name))
+            .collect();
+        let call = if param_names.is_empty() {
+            format!("\"{}()\".to_string()", action_name)
+        } else {
+            format!(
+                "format!(\"{}({})\", {})",
+                action_name,
+                vec!["{:?}"; param_names.len()].join(", "),
+                param_names.join(", ")
+            )
+        };
+        self.add_code(&format!("{}.push({});", self.action_calls_mut(), call));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     /// Generate the initialize method.
     fn generate_initialize(&mut self) {
         self.add_code(&format!(
@@ -2175,12 +3383,7 @@ impl RustVisitor {
         ));
         self.enter_block();
 
-        self.add_code(&format!(
-            "let {} = {}::new({}::new(",
-            self.config.code.frame_event_variable_name,
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
-        ));
+        self.add_code(&self.new_event_start(&self.config.code.frame_event_variable_name));
         self.indent();
         self.newline();
         self.add_code(&format!(
@@ -2196,12 +3399,13 @@ impl RustVisitor {
         ));
         self.outdent();
         self.newline();
-        self.add_code("));");
+        self.add_code(self.new_event_end());
 
         self.newline();
         self.add_code(&format!(
             "self.{}({});",
-            self.config.code.handle_event_method_name, self.config.code.frame_event_variable_name,
+            self.config.code.handle_event_method_name,
+            self.event_arg(&self.config.code.frame_event_variable_name),
         ));
 
         self.exit_block();
@@ -2218,6 +3422,10 @@ impl RustVisitor {
         if system_node.get_first_state().is_some() {
             self.newline();
             self.generate_handle_event();
+            for parallel_state in self.parallel_states.clone() {
+                self.newline();
+                self.generate_region_methods(&parallel_state);
+            }
             if self.generate_transition_state {
                 self.newline();
                 self.generate_transition();
@@ -2226,6 +3434,10 @@ impl RustVisitor {
                 self.newline();
                 self.generate_state_stack_methods();
             }
+            if self.generate_deep_history {
+                self.newline();
+                self.generate_state_history_methods(system_node);
+            }
             if self.generate_change_state {
                 self.newline();
                 self.generate_change_state();
@@ -2257,7 +3469,7 @@ impl RustVisitor {
             "fn {}(&mut self, ",
             self.config.code.change_state_method_name,
         ));
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.add_code(&format!(
                 "{}: &'static {}::TransitionInfo, ",
                 self.config.code.transition_info_arg_name,
@@ -2271,18 +3483,26 @@ impl RustVisitor {
         ));
         if self.generate_state_context {
             self.add_code(&format!(
-                ", {}: {}<{}>",
+                ", {}: {}",
                 new_state_context_var,
-                self.rc_type(),
-                self.config.code.state_context_type_name,
+                self.state_context_ptr_type(),
             ));
         }
         self.add_code(")");
         self.enter_block();
 
+        // remember the state being left for deep history transitions
+        if self.generate_deep_history {
+            self.add_code(&format!(
+                "self.{}();",
+                self.config.code.state_history_record_method_name
+            ));
+            self.newline();
+        }
+
         // save old state
         if self.generate_change_state_hook
-            || self.config.features.runtime_support && !self.generate_state_context
+            || self.runtime_event_monitor() && !self.generate_state_context
         {
             self.add_code(&format!(
                 "let {} = self.{};",
@@ -2290,7 +3510,7 @@ impl RustVisitor {
             ));
             self.newline();
         }
-        if self.config.features.runtime_support && self.generate_state_context {
+        if self.runtime_event_monitor() && self.generate_state_context {
             self.add_code(&format!(
                 "let {} = self.{}.clone();",
                 old_state_context_var, self.config.code.state_context_var_name
@@ -2309,7 +3529,7 @@ impl RustVisitor {
                 "self.{} = {}{};",
                 self.config.code.state_context_var_name,
                 new_state_context_var,
-                if self.config.features.runtime_support {
+                if self.runtime_event_monitor() {
                     ".clone()"
                 } else {
                     ""
@@ -2327,7 +3547,7 @@ impl RustVisitor {
         }
 
         // call transition callbacks
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.add_code(&format!(
                 "self.{}.transition_occurred({}::Transition::new_change_state(",
@@ -2367,38 +3587,150 @@ impl RustVisitor {
             }
             self.outdent();
             self.newline();
-            self.add_code("));");
+            self.add_code("));");
+        }
+
+        self.exit_block();
+        self.newline();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate a call to the method that records the current state as the most recently active
+    /// descendant of each of its ancestors.
+    fn generate_state_history_record_call(&mut self) {
+        if self.generate_deep_history {
+            self.newline();
+            self.add_code(&format!(
+                "self.{}();",
+                self.config.code.state_history_record_method_name
+            ));
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the transition method.
+    fn generate_transition(&mut self) {
+        let new_state_context_var = self.new_var_name(&self.config.code.state_context_var_name);
+        let new_state_var = self.new_var_name(&self.config.code.state_var_name);
+
+        // generate method signature
+        self.add_code(&format!(
+            "fn {}(&mut self, ",
+            self.config.code.transition_method_name
+        ));
+        if self.runtime_event_monitor() {
+            self.add_code(&format!(
+                "{}: &'static {}::TransitionInfo, ",
+                self.config.code.transition_info_arg_name,
+                self.config.code.runtime_module_use_as_name,
+            ));
+        }
+        if self.generate_exit_args {
+            self.add_code(&format!(
+                "{}: {}, ",
+                self.config.code.exit_args_member_name, self.config.code.frame_event_args_type_name
+            ));
+        }
+        if self.generate_enter_args {
+            self.add_code(&format!(
+                "{}: {}, ",
+                self.config.code.enter_args_member_name,
+                self.config.code.frame_event_args_type_name
+            ));
+        }
+        self.add_code(&format!(
+            "{}: {}",
+            new_state_var,
+            self.state_enum_type_name()
+        ));
+        if self.generate_state_context {
+            self.add_code(&format!(
+                ", {}: {}",
+                new_state_context_var,
+                self.state_context_ptr_type(),
+            ));
+        }
+        self.add_code(")");
+        self.enter_block();
+
+        if self.generate_transition_actions {
+            // transitions with actions call the halves of the transition themselves
+            let mut exit_args = Vec::new();
+            if self.generate_exit_args {
+                exit_args.push(self.config.code.exit_args_member_name.clone());
+            }
+            self.generate_transition_exit_call(&exit_args);
+            let mut enter_args = Vec::new();
+            if self.runtime_event_monitor() {
+                enter_args.push(self.config.code.transition_info_arg_name.clone());
+            }
+            if self.generate_enter_args {
+                enter_args.push(self.config.code.enter_args_member_name.clone());
+            }
+            enter_args.push(new_state_var);
+            if self.generate_state_context {
+                enter_args.push(new_state_context_var);
+            }
+            self.generate_transition_enter_call(&enter_args);
+            self.exit_block();
+            self.newline();
+            self.newline();
+            self.generate_transition_exit();
+            self.newline();
+            self.generate_transition_enter();
+        } else {
+            self.generate_transition_exit_code();
+            self.generate_transition_enter_code();
+            self.exit_block();
+            self.newline();
+        }
+    }
+
+    /// Generate the first half of the transition method, sending the exit event to the state
+    /// left. Returns the exit event if the runtime event monitor is enabled.
+    fn generate_transition_exit(&mut self) {
+        self.add_code(&format!(
+            "fn {}(&mut self",
+            self.config.code.transition_exit_method_name
+        ));
+        if self.generate_exit_args {
+            self.add_code(&format!(
+                ", {}: {}",
+                self.config.code.exit_args_member_name, self.config.code.frame_event_args_type_name
+            ));
+        }
+        self.add_code(")");
+        if self.runtime_event_monitor() {
+            self.add_code(&format!(" -> {}", self.frame_event_param_type()));
+        }
+        self.enter_block();
+        self.generate_transition_exit_code();
+        if self.runtime_event_monitor() {
+            self.newline();
+            self.add_code("exit_event");
         }
-
         self.exit_block();
         self.newline();
     }
 
-    //* --------------------------------------------------------------------- *//
-
-    /// Generate the transition method.
-    fn generate_transition(&mut self) {
-        let old_state_context_var = self.old_var_name(&self.config.code.state_context_var_name);
+    /// Generate the second half of the transition method, changing the state and sending the
+    /// enter event to the new state.
+    fn generate_transition_enter(&mut self) {
         let new_state_context_var = self.new_var_name(&self.config.code.state_context_var_name);
-        let old_state_var = self.old_var_name(&self.config.code.state_var_name);
         let new_state_var = self.new_var_name(&self.config.code.state_var_name);
 
-        // generate method signature
         self.add_code(&format!(
             "fn {}(&mut self, ",
-            self.config.code.transition_method_name
+            self.config.code.transition_enter_method_name
         ));
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.add_code(&format!(
-                "{}: &'static {}::TransitionInfo, ",
+                "{}: &'static {}::TransitionInfo, exit_event: {}, ",
                 self.config.code.transition_info_arg_name,
                 self.config.code.runtime_module_use_as_name,
-            ));
-        }
-        if self.generate_exit_args {
-            self.add_code(&format!(
-                "{}: {}, ",
-                self.config.code.exit_args_member_name, self.config.code.frame_event_args_type_name
+                self.frame_event_param_type(),
             ));
         }
         if self.generate_enter_args {
@@ -2415,21 +3747,53 @@ impl RustVisitor {
         ));
         if self.generate_state_context {
             self.add_code(&format!(
-                ", {}: {}<{}>",
+                ", {}: {}",
                 new_state_context_var,
-                self.rc_type(),
-                self.config.code.state_context_type_name,
+                self.state_context_ptr_type(),
             ));
         }
-        self.add_code(")");
-        self.enter_block();
+        self.add_code(") {");
+        // the code of the second half starts on a new line
+        self.indent();
+        self.generate_transition_enter_code();
+        self.exit_block();
+        self.newline();
+    }
 
-        // create exit event for old state
+    /// Generate the call of the first half of the transition method with the given arguments,
+    /// keeping the exit event for the second half if the runtime event monitor is enabled.
+    fn generate_transition_exit_call(&mut self, exit_args: &[String]) {
         self.add_code(&format!(
-            "let exit_event = {}::new({}::new(",
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
+            "{}self.{}({});",
+            if self.runtime_event_monitor() {
+                "let exit_event = "
+            } else {
+                ""
+            },
+            self.config.code.transition_exit_method_name,
+            exit_args.join(", ")
+        ));
+    }
+
+    /// Generate the call of the second half of the transition method with the given arguments,
+    /// inserting the exit event after the transition info if the runtime event monitor is enabled.
+    fn generate_transition_enter_call(&mut self, enter_args: &[String]) {
+        let mut args = enter_args.to_vec();
+        if self.runtime_event_monitor() {
+            args.insert(1, String::from("exit_event"));
+        }
+        self.newline();
+        self.add_code(&format!(
+            "self.{}({});",
+            self.config.code.transition_enter_method_name,
+            args.join(", ")
         ));
+    }
+
+    /// Generate the code creating the exit event for the state left and sending it.
+    fn generate_transition_exit_code(&mut self) {
+        // create exit event for old state
+        self.add_code(&self.new_event_start("exit_event"));
         self.indent();
         self.newline();
         self.add_code(&format!(
@@ -2449,23 +3813,36 @@ impl RustVisitor {
         };
         self.outdent();
         self.newline();
-        self.add_code("));");
+        self.add_code(self.new_event_end());
 
         // send exit event
         self.newline();
         self.add_code(&format!(
-            "self.{}(exit_event{});",
+            "self.{}({}{});",
             self.config.code.handle_event_method_name,
-            if self.config.features.runtime_support {
+            self.event_arg("exit_event"),
+            if self.runtime_event_monitor() {
                 ".clone()"
             } else {
                 ""
             }
         ));
 
+        // remember the state being left for deep history transitions
+        self.generate_state_history_record_call();
+    }
+
+    /// Generate the code changing the state, and creating the enter event for the new state and
+    /// sending it.
+    fn generate_transition_enter_code(&mut self) {
+        let old_state_context_var = self.old_var_name(&self.config.code.state_context_var_name);
+        let new_state_context_var = self.new_var_name(&self.config.code.state_context_var_name);
+        let old_state_var = self.old_var_name(&self.config.code.state_var_name);
+        let new_state_var = self.new_var_name(&self.config.code.state_var_name);
+
         // save old state
         if self.generate_transition_hook
-            || self.config.features.runtime_support && !self.generate_state_context
+            || self.runtime_event_monitor() && !self.generate_state_context
         {
             self.newline();
             self.add_code(&format!(
@@ -2473,7 +3850,7 @@ impl RustVisitor {
                 old_state_var, self.config.code.state_var_name
             ));
         }
-        if self.config.features.runtime_support && self.generate_state_context {
+        if self.runtime_event_monitor() && self.generate_state_context {
             self.newline();
             self.add_code(&format!(
                 "let {} = self.{}.clone();",
@@ -2493,7 +3870,7 @@ impl RustVisitor {
                 "self.{} = {}{};",
                 self.config.code.state_context_var_name,
                 new_state_context_var,
-                if self.config.features.runtime_support {
+                if self.runtime_event_monitor() {
                     ".clone()"
                 } else {
                     ""
@@ -2512,11 +3889,7 @@ impl RustVisitor {
 
         // create enter event for new state
         self.newline();
-        self.add_code(&format!(
-            "let enter_event = {}::new({}::new(",
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
-        ));
+        self.add_code(&self.new_event_start("enter_event"));
         self.indent();
         self.newline();
         self.add_code(&format!(
@@ -2536,10 +3909,10 @@ impl RustVisitor {
         };
         self.outdent();
         self.newline();
-        self.add_code("));");
+        self.add_code(self.new_event_end());
 
         // call transition callbacks
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.add_code(&format!(
                 "self.{}.transition_occurred({}::Transition::new(",
@@ -2595,14 +3968,128 @@ impl RustVisitor {
         // send enter event
         self.newline();
         self.add_code(&format!(
-            "self.{}(enter_event);",
+            "self.{}({});",
             self.config.code.handle_event_method_name,
+            self.event_arg("enter_event"),
+        ));
+    }
+
+    /// Generate deep history methods. Every state is recorded as the most recently active
+    /// descendant of each of its ancestors when it is left. Restoring the history of a state that
+    /// has no recorded descendant yields the state itself and its fresh state context.
+    fn generate_state_history_methods(&mut self, system_node: &SystemNode) {
+        // map each state to its parent
+        let mut parents: Vec<(String, String)> = Vec::new();
+        if let Some(machine_block_node) = &system_node.machine_block_node_opt {
+            for state_node_rcref in &machine_block_node.states {
+                let state_node = state_node_rcref.borrow();
+                if let Some(dispatch_node) = &state_node.dispatch_opt {
+                    parents.push((
+                        state_node.name.clone(),
+                        dispatch_node.target_state_ref.name.clone(),
+                    ));
+                }
+            }
+        }
+
+        let history_entry = if self.generate_state_context {
+            format!(
+                "(self.{}, self.{}.clone())",
+                self.config.code.state_var_name, self.config.code.state_context_var_name
+            )
+        } else {
+            format!("self.{}", self.config.code.state_var_name)
+        };
+
+        self.add_code(&format!(
+            "fn {}(&mut self)",
+            self.config.code.state_history_record_method_name
         ));
+        self.enter_block();
+        self.add_code(&format!("match self.{}", self.config.code.state_var_name));
+        self.enter_block();
+        for (state_name, _) in &parents {
+            // collect the ancestors, guarding against cycles in malformed hierarchies
+            let mut ancestors: Vec<String> = Vec::new();
+            let mut current = state_name.clone();
+            while let Some((_, parent)) = parents.iter().find(|(child, _)| *child == current) {
+                if ancestors.contains(parent) || parent == state_name {
+                    break;
+                }
+                ancestors.push(parent.clone());
+                current = parent.clone();
+            }
+            self.add_code(&format!(
+                "{}::{} =>",
+                self.state_enum_type_name(),
+                self.format_type_name(state_name)
+            ));
+            self.enter_block();
+            for (i, ancestor) in ancestors.iter().enumerate() {
+                if i > 0 {
+                    self.newline();
+                }
+                self.add_code(&format!(
+                    "self.{}.insert({}::{}, {});",
+                    self.config.code.state_history_var_name,
+                    self.state_enum_type_name(),
+                    self.format_type_name(ancestor),
+                    history_entry
+                ));
+            }
+            self.exit_block();
+            self.newline();
+        }
+        self.add_code("#[allow(unreachable_patterns)]");
+        self.newline();
+        self.add_code("_ => {}");
+        self.exit_block();
+        self.exit_block();
 
+        self.newline();
+        self.newline();
+        if self.generate_state_context {
+            self.add_code(&format!(
+                "fn {}(&self, state: {}, state_context: {}) -> ({}, {})",
+                self.config.code.state_history_restore_method_name,
+                self.state_enum_type_name(),
+                self.state_context_ptr_type(),
+                self.state_enum_type_name(),
+                self.state_context_ptr_type(),
+            ));
+            self.enter_block();
+            self.add_code(&format!(
+                "match self.{}.get(&state)",
+                self.config.code.state_history_var_name
+            ));
+            self.enter_block();
+            self.add_code("Some((last_state, last_state_context)) => (*last_state, last_state_context.clone()),");
+            self.newline();
+            self.add_code("None => (state, state_context),");
+        } else {
+            self.add_code(&format!(
+                "fn {}(&self, state: {}) -> {}",
+                self.config.code.state_history_restore_method_name,
+                self.state_enum_type_name(),
+                self.state_enum_type_name(),
+            ));
+            self.enter_block();
+            self.add_code(&format!(
+                "match self.{}.get(&state)",
+                self.config.code.state_history_var_name
+            ));
+            self.enter_block();
+            self.add_code("Some(last_state) => *last_state,");
+            self.newline();
+            self.add_code("None => state,");
+        }
+        self.exit_block();
         self.exit_block();
         self.newline();
     }
 
+    //* --------------------------------------------------------------------- *//
+
     /// Generate state stack methods.
     fn generate_state_stack_methods(&mut self) {
         self.add_code(&format!(
@@ -2610,7 +4097,14 @@ impl RustVisitor {
             self.config.code.state_stack_push_method_name
         ));
         self.enter_block();
-        if self.generate_state_context {
+        if self.generate_state_context && self.config.features.lightweight {
+            self.add_code(&format!(
+                "self.{}.push((self.{}, self.{}.clone()));",
+                self.config.code.state_stack_var_name,
+                self.config.code.state_var_name,
+                self.config.code.state_context_var_name
+            ));
+        } else if self.generate_state_context {
             self.add_code(&format!(
                 "self.{}.push((self.{}, (*self.{}).deep_clone()));",
                 self.config.code.state_stack_var_name,
@@ -2641,89 +4135,335 @@ impl RustVisitor {
                 self.state_enum_type_name()
             ));
         }
-        self.enter_block();
+        self.enter_block();
+        self.add_code(&format!(
+            "match self.{}.pop()",
+            self.config.code.state_stack_var_name
+        ));
+        self.enter_block();
+        self.add_code("Some(elem) => elem,");
+        self.newline();
+        self.add_code("None => panic!(\"Error: attempted to pop when history stack is empty.\")");
+        self.exit_block();
+
+        self.exit_block();
+        self.newline();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate a return statement within a handler. Call this rather than adding a return
+    /// statement directly to ensure that the control-flow state is properly maintained.
+    fn generate_return(&mut self) {
+        self.newline();
+        self.add_code("return;");
+        self.this_branch_transitioned = false;
+    }
+
+    /// Generate a return statement if the current branch contained a transition or change-state.
+    fn generate_return_if_transitioned(&mut self) {
+        if self.this_branch_transitioned {
+            self.generate_return();
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn generate_handle_event(&mut self) {
+        self.add_code("#[allow(clippy::redundant_clone)]");
+        self.newline();
+        self.add_code(&format!(
+            "fn {}(&mut self, {}: {})",
+            self.config.code.handle_event_method_name,
+            self.config.code.frame_event_variable_name,
+            self.frame_event_param_type(),
+        ));
+        self.enter_block();
+
+        if self.runtime_event_monitor() {
+            self.add_code(&format!(
+                "self.{}.event_sent({}.clone());",
+                self.config.code.event_monitor_var_name, self.config.code.frame_event_variable_name,
+            ));
+            self.newline();
+        }
+
+        self.add_code(&format!(
+            "match self.{} {{",
+            self.config.code.state_var_name
+        ));
+        self.indent();
+        for state_name in &self.state_names.clone() {
+            // events of parallel states are dispatched to their regions first
+            let handler_name = if self.is_parallel_state(state_name) {
+                self.format_regions_handler_name(state_name)
+            } else {
+                self.format_state_handler_name(state_name)
+            };
+            self.newline();
+            self.add_code(&format!(
+                "{}::{} => self.{}({}{}),",
+                self.state_enum_type_name(),
+                self.format_type_name(state_name),
+                handler_name,
+                self.config.code.frame_event_variable_name,
+                if self.config.features.lightweight {
+                    ""
+                } else {
+                    ".clone()"
+                },
+            ));
+        }
+        self.exit_block();
+
+        if self.runtime_event_monitor() {
+            self.newline();
+            self.add_code(&format!(
+                "self.{}.event_handled({});",
+                self.config.code.event_monitor_var_name, self.config.code.frame_event_variable_name,
+            ));
+        }
+
+        self.exit_block();
+        self.newline();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    /// Generate the methods dispatching the events of a parallel state to its regions. Events
+    /// are dispatched to the current state of each region in the order the regions are declared,
+    /// and then to the parallel state itself, unless a transition left the parallel state. The
+    /// regions are entered after the parallel state and exited before it, and return to their
+    /// initial states when exited.
+    fn generate_region_methods(&mut self, parallel_state: &ParallelState) {
+        let event_var = self.config.code.frame_event_variable_name.clone();
+        let event_clone = if self.config.features.lightweight {
+            event_var.clone()
+        } else {
+            format!("{}.clone()", event_var)
+        };
+        let state_check = format!(
+            "if self.{} != {}::{}",
+            self.config.code.state_var_name,
+            self.state_enum_type_name(),
+            self.format_type_name(&parallel_state.name)
+        );
+        let regions = &parallel_state.regions;
+
+        self.add_code(&format!(
+            "fn {}(&mut self, {}: {})",
+            self.format_regions_handler_name(&parallel_state.name),
+            event_var,
+            self.frame_event_param_type(),
+        ));
+        self.enter_block();
+        self.add_code(&format!(
+            "match {}.{}",
+            event_var, self.config.code.frame_event_message_attribute_name
+        ));
+        self.enter_block();
+
+        // enter the parallel state, then its regions
+        self.add_code(&format!(
+            "{}::{}(_) =>",
+            self.config.code.frame_event_message_type_name, self.config.code.enter_msg
+        ));
+        self.enter_block();
+        self.add_code(&format!(
+            "self.{}({});",
+            self.format_state_handler_name(&parallel_state.name),
+            event_clone
+        ));
+        for region in regions {
+            self.newline();
+            self.add_code(&state_check);
+            self.enter_block();
+            self.add_code("return;");
+            self.exit_block();
+            self.newline();
+            self.generate_region_event(
+                "enter_event",
+                &self.config.code.enter_msg.clone(),
+                &parallel_state.name,
+                &region.name,
+            );
+        }
+        self.exit_block();
+
+        // exit the regions, then the parallel state
+        self.newline();
+        self.add_code(&format!(
+            "{}::{}(_) =>",
+            self.config.code.frame_event_message_type_name, self.config.code.exit_msg
+        ));
+        self.enter_block();
+        for region in regions.iter().rev() {
+            self.generate_region_event(
+                "exit_event",
+                &self.config.code.exit_msg.clone(),
+                &parallel_state.name,
+                &region.name,
+            );
+            self.newline();
+            self.add_code(&format!(
+                "self.{} = {}::{};",
+                self.format_region_var_name(&parallel_state.name, &region.name),
+                self.state_enum_type_name(),
+                self.format_type_name(&region.states[0])
+            ));
+            self.newline();
+        }
+        self.add_code(&format!(
+            "self.{}({});",
+            self.format_state_handler_name(&parallel_state.name),
+            event_var
+        ));
+        self.exit_block();
+
+        // other events go to each region, then the parallel state
+        self.newline();
+        self.add_code("_ =>");
+        self.enter_block();
+        for region in regions {
+            self.add_code(&format!(
+                "self.{}({});",
+                self.format_region_handler_name(&parallel_state.name, &region.name),
+                event_clone
+            ));
+            self.newline();
+            self.add_code(&state_check);
+            self.enter_block();
+            self.add_code("return;");
+            self.exit_block();
+            self.newline();
+        }
         self.add_code(&format!(
-            "match self.{}.pop()",
-            self.config.code.state_stack_var_name
+            "self.{}({});",
+            self.format_state_handler_name(&parallel_state.name),
+            event_var
         ));
-        self.enter_block();
-        self.add_code("Some(elem) => elem,");
-        self.newline();
-        self.add_code("None => panic!(\"Error: attempted to pop when history stack is empty.\")");
         self.exit_block();
 
+        self.exit_block();
         self.exit_block();
         self.newline();
-    }
-
-    //* --------------------------------------------------------------------- *//
-
-    /// Generate a return statement within a handler. Call this rather than adding a return
-    /// statement directly to ensure that the control-flow state is properly maintained.
-    fn generate_return(&mut self) {
-        self.newline();
-        self.add_code("return;");
-        self.this_branch_transitioned = false;
-    }
 
-    /// Generate a return statement if the current branch contained a transition or change-state.
-    fn generate_return_if_transitioned(&mut self) {
-        if self.this_branch_transitioned {
-            self.generate_return();
+        for region in regions {
+            self.generate_region_handler(&parallel_state.name, region);
+            self.generate_region_transition(&parallel_state.name, region);
         }
     }
 
-    //* --------------------------------------------------------------------- *//
-
-    fn generate_handle_event(&mut self) {
-        self.add_code("#[allow(clippy::redundant_clone)]");
+    /// Generate the method dispatching events to the current state of a region.
+    fn generate_region_handler(&mut self, parallel_state_name: &str, region: &Region) {
+        let event_var = self.config.code.frame_event_variable_name.clone();
         self.newline();
         self.add_code(&format!(
-            "fn {}(&mut self, {}: {}<{}>)",
-            self.config.code.handle_event_method_name,
-            self.config.code.frame_event_variable_name,
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
+            "fn {}(&mut self, {}: {})",
+            self.format_region_handler_name(parallel_state_name, &region.name),
+            event_var,
+            self.frame_event_param_type(),
         ));
         self.enter_block();
-
-        if self.config.features.runtime_support {
-            self.add_code(&format!(
-                "self.{}.event_sent({}.clone());",
-                self.config.code.event_monitor_var_name, self.config.code.frame_event_variable_name,
-            ));
-            self.newline();
-        }
-
         self.add_code(&format!(
-            "match self.{} {{",
-            self.config.code.state_var_name
+            "match self.{}",
+            self.format_region_var_name(parallel_state_name, &region.name)
         ));
-        self.indent();
-        for state_name in &self.state_names.clone() {
-            self.newline();
+        self.enter_block();
+        for state_name in &region.states {
             self.add_code(&format!(
-                "{}::{} => self.{}({}.clone()),",
+                "{}::{} => self.{}({}),",
                 self.state_enum_type_name(),
                 self.format_type_name(state_name),
                 self.format_state_handler_name(state_name),
-                self.config.code.frame_event_variable_name,
+                event_var
             ));
+            self.newline();
         }
+        self.add_code("_ => {}");
+        self.exit_block();
         self.exit_block();
+        self.newline();
+    }
 
-        if self.config.features.runtime_support {
+    /// Generate the method changing the current state of a region, sending the exit event to the
+    /// state left and the enter event to the new state.
+    fn generate_region_transition(&mut self, parallel_state_name: &str, region: &Region) {
+        let region_var = self.format_region_var_name(parallel_state_name, &region.name);
+        let old_state_var = self.old_var_name(&self.config.code.state_var_name);
+        let new_state_var = self.new_var_name(&self.config.code.state_var_name);
+        self.newline();
+        self.add_code(&format!(
+            "fn {}(&mut self, {}: {})",
+            self.format_region_transition_name(parallel_state_name, &region.name),
+            new_state_var,
+            self.state_enum_type_name()
+        ));
+        self.enter_block();
+        self.generate_region_event(
+            "exit_event",
+            &self.config.code.exit_msg.clone(),
+            parallel_state_name,
+            &region.name,
+        );
+        if self.generate_transition_hook {
+            self.newline();
+            self.add_code(&format!("let {} = self.{};", old_state_var, region_var));
+        }
+        self.newline();
+        self.add_code(&format!("self.{} = {};", region_var, new_state_var));
+        if self.generate_transition_hook {
             self.newline();
             self.add_code(&format!(
-                "self.{}.event_handled({});",
-                self.config.code.event_monitor_var_name, self.config.code.frame_event_variable_name,
+                "self.{}({}, {});",
+                self.config.code.transition_hook_method_name, old_state_var, new_state_var,
             ));
         }
-
+        self.newline();
+        self.generate_region_event(
+            "enter_event",
+            &self.config.code.enter_msg.clone(),
+            parallel_state_name,
+            &region.name,
+        );
         self.exit_block();
         self.newline();
     }
 
+    /// Generate an enter or exit event for the current state of a region, and send it to the
+    /// state.
+    fn generate_region_event(
+        &mut self,
+        var_name: &str,
+        msg: &str,
+        parallel_state_name: &str,
+        region_name: &str,
+    ) {
+        self.add_code(&self.new_event_start(var_name));
+        self.indent();
+        self.newline();
+        self.add_code(&format!(
+            "{}::{}(self.{}),",
+            self.config.code.frame_event_message_type_name,
+            msg,
+            self.format_region_var_name(parallel_state_name, region_name),
+        ));
+        self.newline();
+        self.add_code(&format!(
+            "{}::None,",
+            self.config.code.frame_event_args_type_name
+        ));
+        self.outdent();
+        self.newline();
+        self.add_code(self.new_event_end());
+        self.newline();
+        self.add_code(&format!(
+            "self.{}({});",
+            self.format_region_handler_name(parallel_state_name, region_name),
+            self.event_arg(var_name),
+        ));
+    }
+
     //* --------------------------------------------------------------------- *//
 
     fn generate_comment(&mut self, line: usize) {
@@ -3020,33 +4760,37 @@ impl RustVisitor {
         if has_state_args {
             self.newline();
             self.add_code(&format!(
-                "{}: {}::new({}::new({})),",
+                "{}: {},",
                 self.config.code.state_args_var_name,
-                self.rc_type(),
-                self.cell_type(),
-                state_args,
+                self.state_context_field_value(state_args),
             ));
         }
         if has_state_vars {
             self.newline();
             self.add_code(&format!(
-                "{}: {}::new({}::new({})),",
+                "{}: {},",
                 self.config.code.state_vars_var_name,
-                self.rc_type(),
-                self.cell_type(),
-                state_vars,
+                self.state_context_field_value(state_vars),
             ));
         }
         self.outdent();
         self.newline();
         self.add_code("};");
         self.newline();
-        self.add_code(&format!(
-            "let next_state_context = {}::new({}::{}(context));",
-            self.rc_type(),
-            self.config.code.state_context_type_name,
-            self.format_type_name(&target_state_name.to_string())
-        ));
+        if self.config.features.lightweight {
+            self.add_code(&format!(
+                "let next_state_context = {}::{}(context);",
+                self.config.code.state_context_type_name,
+                self.format_type_name(target_state_name)
+            ));
+        } else {
+            self.add_code(&format!(
+                "let next_state_context = {}::new({}::{}(context));",
+                self.rc_type(),
+                self.config.code.state_context_type_name,
+                self.format_type_name(&target_state_name.to_string())
+            ));
+        }
     }
 
     //* --------------------------------------------------------------------- *//
@@ -3117,6 +4861,7 @@ impl RustVisitor {
         let event_name = self.get_qualified_event_name(source_state_name, &self.current_message);
         self.transitions.push(TransitionInfo {
             is_change_state: true,
+            is_deep_history: false,
             event_name,
             label,
             source_name: source_state_name.to_string(),
@@ -3129,7 +4874,7 @@ impl RustVisitor {
             "self.{}(",
             self.config.code.change_state_method_name
         ));
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.add_code(&format!(
                 "{}::{}().transitions[{}], ",
                 self.config.code.runtime_info_module_name,
@@ -3155,13 +4900,14 @@ impl RustVisitor {
         self.add_code("// Start transition");
 
         // get the name of the next state
-        let target_state_name = match &transition_stmt.target_state_context_t {
-            StateContextType::StateRef { state_context_node } => {
-                &state_context_node.state_ref_node.name
-            }
+        let (target_state_name, deep_history) = match &transition_stmt.target_state_context_t {
+            StateContextType::StateRef { state_context_node } => (
+                state_context_node.state_ref_node.name.as_str(),
+                state_context_node.deep_history,
+            ),
             _ => {
                 self.errors.push("Transition target not found.".to_string());
-                ""
+                ("", false)
             }
         };
 
@@ -3250,41 +4996,117 @@ impl RustVisitor {
         let event_name = self.get_qualified_event_name(source_state_name, &self.current_message);
         self.transitions.push(TransitionInfo {
             is_change_state: false,
+            is_deep_history: deep_history,
             event_name,
             label,
             source_name: source_state_name.to_string(),
             target_name: Some(target_state_name.to_string()),
         });
 
-        // call the transition method
-        self.newline();
-        self.add_code(&format!(
-            "self.{}(",
-            self.config.code.transition_method_name
-        ));
-        if self.config.features.runtime_support {
+        // restore the most recently active descendant of the target, if any
+        if deep_history {
+            self.newline();
+            if self.generate_state_context {
+                self.add_code(&format!(
+                    "let (next_state, next_state_context) = self.{}({}::{}, next_state_context);",
+                    self.config.code.state_history_restore_method_name,
+                    self.state_enum_type_name(),
+                    self.format_type_name(target_state_name)
+                ));
+            } else {
+                self.add_code(&format!(
+                    "let next_state = self.{}({}::{});",
+                    self.config.code.state_history_restore_method_name,
+                    self.state_enum_type_name(),
+                    self.format_type_name(target_state_name)
+                ));
+            }
+        }
+
+        // transitions between the states of a parallel state only change the state of a region,
+        // while transitions into a region from outside enter the parallel state
+        let mut transition_target_name = target_state_name.to_string();
+        if let Some((parallel_state_name, region_name)) = self.region_of(target_state_name) {
+            let source_state_name = self.current_state_name_opt.clone().unwrap_or_default();
+            if self.enclosing_parallel_state(&source_state_name)
+                == Some(parallel_state_name.clone())
+            {
+                self.newline();
+                self.add_code(&format!(
+                    "self.{}({}::{});",
+                    self.format_region_transition_name(&parallel_state_name, &region_name),
+                    self.state_enum_type_name(),
+                    self.format_type_name(target_state_name)
+                ));
+                return;
+            }
+            self.newline();
             self.add_code(&format!(
-                "{}::{}().transitions[{}], ",
-                self.config.code.runtime_info_module_name,
-                self.config.code.machine_info_function_name,
-                self.transitions.len() - 1,
+                "self.{} = {}::{};",
+                self.format_region_var_name(&parallel_state_name, &region_name),
+                self.state_enum_type_name(),
+                self.format_type_name(target_state_name)
             ));
+            transition_target_name = parallel_state_name;
         }
+
+        // call the transition method
+        let mut exit_args = Vec::new();
         if self.generate_exit_args {
-            self.add_code(&format!("{}, ", self.config.code.exit_args_member_name));
+            exit_args.push(self.config.code.exit_args_member_name.clone());
         }
+        let mut enter_args = Vec::new();
         if self.generate_enter_args {
-            self.add_code(&format!("{}, ", self.config.code.enter_args_member_name));
+            enter_args.push(self.config.code.enter_args_member_name.clone());
+        }
+        if deep_history {
+            enter_args.push(String::from("next_state"));
+        } else {
+            enter_args.push(format!(
+                "{}::{}",
+                self.state_enum_type_name(),
+                self.format_type_name(&transition_target_name)
+            ));
         }
-        self.add_code(&format!(
-            "{}::{}",
-            self.state_enum_type_name(),
-            self.format_type_name(&target_state_name.to_string())
-        ));
         if self.generate_state_context {
-            self.add_code(", next_state_context");
+            enter_args.push(String::from("next_state_context"));
+        }
+        self.generate_transition_call(transition_stmt, &exit_args, enter_args);
+    }
+
+    /// Generate the call of the transition method, passing the info of the last transition
+    /// recorded if the runtime event monitor is enabled. Transitions with actions call the halves
+    /// of the transition method instead, with the actions in between.
+    fn generate_transition_call(
+        &mut self,
+        transition_stmt: &TransitionStatementNode,
+        exit_args: &[String],
+        enter_args: Vec<String>,
+    ) {
+        let mut info_args = Vec::new();
+        if self.runtime_event_monitor() {
+            info_args.push(format!(
+                "{}::{}().transitions[{}]",
+                self.config.code.runtime_info_module_name,
+                self.config.code.machine_info_function_name,
+                self.transitions.len() - 1,
+            ));
+        }
+        self.newline();
+        if transition_stmt.actions.is_empty() {
+            let args = [info_args, exit_args.to_vec(), enter_args].concat();
+            self.add_code(&format!(
+                "self.{}({});",
+                self.config.code.transition_method_name,
+                args.join(", ")
+            ));
+        } else {
+            self.generate_transition_exit_call(exit_args);
+            for expr_stmt_t in &transition_stmt.actions {
+                self.visit_expr_stmt(expr_stmt_t);
+            }
+            self.generate_transition_enter_call(&[info_args, enter_args].concat());
         }
-        self.add_code(");");
     }
 
     //* --------------------------------------------------------------------- *//
@@ -3312,6 +5134,7 @@ impl RustVisitor {
         let event_name = self.get_qualified_event_name(source_state_name, &self.current_message);
         self.transitions.push(TransitionInfo {
             is_change_state: true,
+            is_deep_history: false,
             event_name,
             label,
             source_name: source_state_name.to_string(),
@@ -3326,10 +5149,14 @@ impl RustVisitor {
                 self.config.code.state_stack_pop_method_name
             ));
             self.newline();
-            self.add_code(&format!(
-                "let next_state_context = {}::new(popped_state_context);",
-                self.rc_type()
-            ));
+            if self.config.features.lightweight {
+                self.add_code("let next_state_context = popped_state_context;");
+            } else {
+                self.add_code(&format!(
+                    "let next_state_context = {}::new(popped_state_context);",
+                    self.rc_type()
+                ));
+            }
         } else {
             self.add_code(&format!(
                 "let next_state = self.{}();",
@@ -3343,7 +5170,7 @@ impl RustVisitor {
             "self.{}(",
             self.config.code.change_state_method_name
         ));
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.add_code(&format!(
                 "{}::{}().transitions[{}], ",
                 self.config.code.runtime_info_module_name,
@@ -3381,6 +5208,7 @@ impl RustVisitor {
         let event_name = self.get_qualified_event_name(source_state_name, &self.current_message);
         self.transitions.push(TransitionInfo {
             is_change_state: false,
+            is_deep_history: false,
             event_name,
             label,
             source_name: source_state_name.to_string(),
@@ -3395,10 +5223,14 @@ impl RustVisitor {
                 self.config.code.state_stack_pop_method_name
             ));
             self.newline();
-            self.add_code(&format!(
-                "let next_state_context = {}::new(popped_state_context);",
-                self.rc_type()
-            ));
+            if self.config.features.lightweight {
+                self.add_code("let next_state_context = popped_state_context;");
+            } else {
+                self.add_code(&format!(
+                    "let next_state_context = {}::new(popped_state_context);",
+                    self.rc_type()
+                ));
+            }
         } else {
             self.add_code(&format!(
                 "let next_state = self.{}();",
@@ -3407,24 +5239,11 @@ impl RustVisitor {
         }
 
         // call the transition method
-        self.newline();
-        self.add_code(&format!(
-            "self.{}(",
-            self.config.code.transition_method_name
-        ));
-        if self.config.features.runtime_support {
-            self.add_code(&format!(
-                "{}::{}().transitions[{}], ",
-                self.config.code.runtime_info_module_name,
-                self.config.code.machine_info_function_name,
-                self.transitions.len() - 1,
-            ));
-        }
-        self.add_code("next_state");
+        let mut enter_args = vec![String::from("next_state")];
         if self.generate_state_context {
-            self.add_code(", next_state_context");
+            enter_args.push(String::from("next_state_context"));
         }
-        self.add_code(");");
+        self.generate_transition_call(transition_stmt, &[], enter_args);
     }
 }
 
@@ -3435,10 +5254,21 @@ impl AstVisitor for RustVisitor {
 
     fn visit_system_node(&mut self, system_node: &SystemNode) {
         self.system_name = system_node.name.clone();
+        let system_type_name = self.system_type_name();
+        self.config.code.expand_system_name(&system_type_name);
         if let Some(machine_block_node) = &system_node.machine_block_node_opt {
             for state in &machine_block_node.states {
                 self.state_names.push(state.borrow().name.clone());
                 self.has_states = true;
+                if let Some(dispatch_node) = &state.borrow().dispatch_opt {
+                    if let Some(region_name) = &dispatch_node.region_opt {
+                        self.add_region_state(
+                            &dispatch_node.target_state_ref.name,
+                            region_name,
+                            &state.borrow().name,
+                        );
+                    }
+                }
             }
         }
 
@@ -3446,24 +5276,33 @@ impl AstVisitor for RustVisitor {
         self.newline();
         self.add_code(&system_node.header);
         self.newline();
+        self.override_fragment("file_header", 0, |visitor| {
+            json!({
+                "compiler_version": visitor.compiler_version,
+                "name": system_node.name,
+                "header": system_node.header,
+            })
+        });
         self.add_code("#[allow(unused_imports)]");
         self.newline();
         self.add_code("use std::borrow::Borrow;");
         self.newline();
-        self.add_code("#[allow(unused_imports)]");
-        self.newline();
-        if self.config.features.thread_safe {
-            self.add_code("use std::sync::{Arc, Mutex};");
-        } else {
-            self.add_code("use std::cell::RefCell;");
-            self.newline();
+        if !self.config.features.lightweight {
             self.add_code("#[allow(unused_imports)]");
             self.newline();
-            self.add_code("use std::rc::Rc;");
+            if self.config.features.thread_safe {
+                self.add_code("use std::sync::{Arc, Mutex};");
+            } else {
+                self.add_code("use std::cell::RefCell;");
+                self.newline();
+                self.add_code("#[allow(unused_imports)]");
+                self.newline();
+                self.add_code("use std::rc::Rc;");
+            }
+            self.newline();
         }
-        self.newline();
 
-        if self.config.features.runtime_support {
+        if self.runtime_environments() || self.runtime_event_monitor() {
             self.add_code("#[allow(unused_imports)]");
             self.newline();
             self.add_code("use std::any::Any;");
@@ -3485,6 +5324,14 @@ impl AstVisitor for RustVisitor {
         self.newline();
         self.generate_event_defs(system_node);
 
+        // domain enums and constants
+        if let Some(domain_block_node) = &system_node.domain_block_node_opt {
+            for enum_decl_node_rcref in &domain_block_node.enums {
+                enum_decl_node_rcref.borrow().accept(self);
+            }
+            self.generate_domain_constants(domain_block_node);
+        }
+
         // state enum
         self.newline();
         self.newline();
@@ -3524,14 +5371,23 @@ impl AstVisitor for RustVisitor {
             self.state_enum_type_name()
         ));
 
+        // current state of each region
+        for (parallel_state_name, region) in self.regions() {
+            self.newline();
+            self.add_code(&format!(
+                "{}: {},",
+                self.format_region_var_name(&parallel_state_name, &region.name),
+                self.state_enum_type_name()
+            ));
+        }
+
         // state context variable
         if self.generate_state_context {
             self.newline();
             self.add_code(&format!(
-                "{}: {}<{}>,",
+                "{}: {},",
                 self.config.code.state_context_var_name,
-                self.rc_type(),
-                self.config.code.state_context_type_name,
+                self.state_context_ptr_type(),
             ));
         }
 
@@ -3554,8 +5410,29 @@ impl AstVisitor for RustVisitor {
             }
         }
 
+        // deep history variable, maps each parent state to its most recently active descendant
+        if self.generate_deep_history {
+            self.newline();
+            if self.generate_state_context {
+                self.add_code(&format!(
+                    "{}: std::collections::HashMap<{}, ({}, {})>,",
+                    self.config.code.state_history_var_name,
+                    self.state_enum_type_name(),
+                    self.state_enum_type_name(),
+                    self.state_context_ptr_type(),
+                ));
+            } else {
+                self.add_code(&format!(
+                    "{}: std::collections::HashMap<{}, {}>,",
+                    self.config.code.state_history_var_name,
+                    self.state_enum_type_name(),
+                    self.state_enum_type_name()
+                ));
+            }
+        }
+
         // event monitor
-        if self.config.features.runtime_support {
+        if self.runtime_event_monitor() {
             self.newline();
             self.add_code(&format!(
                 "{}: {}::EventMonitor<Self>,",
@@ -3564,6 +5441,16 @@ impl AstVisitor for RustVisitor {
             ));
         }
 
+        // calls recorded by the mock actions
+        if self.config.features.generate_mock_actions {
+            self.newline();
+            self.add_code(&format!(
+                "{}: {},",
+                self.config.code.action_calls_var_name,
+                self.action_calls_type(),
+            ));
+        }
+
         // domain variables
         let mut domain_vars: Vec<String> = Vec::new();
         if let Some(domain_block_node) = &system_node.domain_block_node_opt {
@@ -3571,6 +5458,7 @@ impl AstVisitor for RustVisitor {
             domain_vars = domain_block_node
                 .member_variables
                 .iter()
+                .filter(|decl_rc| !decl_rc.borrow().is_constant)
                 .map(|decl_rc| self.format_value_name(&decl_rc.borrow().name))
                 .collect();
         }
@@ -3580,9 +5468,10 @@ impl AstVisitor for RustVisitor {
         self.newline();
 
         // add runtime support
-        if self.config.features.runtime_support {
+        if self.runtime_environments() {
             self.generate_environment_impl(&self.system_type_name(), &domain_vars);
-
+        }
+        if self.runtime_event_monitor() {
             self.add_code(&format!(
                 "impl {}::Machine for {}",
                 self.config.code.runtime_module_use_as_name,
@@ -3731,11 +5620,21 @@ impl AstVisitor for RustVisitor {
         if self.has_states {
             self.newline();
             self.newline();
-            self.generate_constructor(system_node);
+            self.generate_constructor(system_node, false);
+            if self.config.features.generate_new_with {
+                self.newline();
+                self.newline();
+                self.generate_constructor(system_node, true);
+            }
             self.newline();
             self.newline();
             self.generate_initialize();
         }
+        if self.config.features.generate_mock_actions {
+            self.newline();
+            self.newline();
+            self.generate_take_action_calls();
+        }
 
         self.serialize.push("".to_string());
         self.serialize.push("Bag _serialize__do() {".to_string());
@@ -3783,6 +5682,16 @@ impl AstVisitor for RustVisitor {
         self.add_code("} // end system controller");
         self.newline();
 
+        // include the state modules
+        for (module_name, path) in self.state_modules.clone() {
+            self.newline();
+            self.add_code(&format!("mod {}", module_name));
+            self.enter_block();
+            self.add_code(&format!("include!({:?});", path));
+            self.exit_block();
+            self.newline();
+        }
+
         // generate Default trait implementation
         if self.has_states {
             self.newline();
@@ -3796,8 +5705,36 @@ impl AstVisitor for RustVisitor {
             self.newline();
         }
 
+        // generate the initial values of the domain variables taken by `new_with`
+        if self.config.features.generate_new_with && self.has_states {
+            self.newline();
+            self.generate_domain_struct(system_node);
+            self.newline();
+        }
+
+        // generate Display trait implementation
+        if self.config.features.generate_display && self.has_states {
+            self.newline();
+            self.generate_machine_display(system_node);
+            self.newline();
+        }
+
+        // generate the interface trait and the scripted fake implementing it
+        if self.config.features.generate_fake && self.has_states {
+            self.newline();
+            self.generate_fake(system_node);
+            self.newline();
+        }
+
+        // generate the calls of interface methods by name
+        if self.runtime_dispatch() && self.has_states {
+            self.newline();
+            self.generate_dispatch(system_node);
+            self.newline();
+        }
+
         // generate runtime info module used by implementations of the runtime interface
-        if self.config.features.runtime_support {
+        if self.runtime_machine_info() {
             self.newline();
             self.generate_runtime_info(system_node);
             self.newline();
@@ -4006,6 +5943,13 @@ impl AstVisitor for RustVisitor {
 
     fn visit_interface_method_node(&mut self, interface_method_node: &InterfaceMethodNode) {
         self.newline();
+        let start = self.code.len();
+        self.record_mapping(
+            CodeMapKind::InterfaceMethod,
+            &interface_method_node.name,
+            interface_method_node.line,
+            interface_method_node.name_span,
+        );
         self.add_code(&format!(
             "pub fn {}(&mut self",
             self.format_value_name(&interface_method_node.name)
@@ -4027,6 +5971,20 @@ impl AstVisitor for RustVisitor {
         }
         self.enter_block();
 
+        // replace the parameters passed `None` with their default values
+        for param in interface_method_node.params.iter().flatten() {
+            if let Some(default) = &param.default_opt {
+                let mut default_code = String::new();
+                default.accept_to_string(self, &mut default_code);
+                let param_name = self.format_value_name(&param.param_name);
+                self.add_code(&format!(
+                    "let {} = {}.unwrap_or({});",
+                    param_name, param_name, default_code
+                ));
+                self.newline();
+            }
+        }
+
         let event_type_name = self.format_type_name(&interface_method_node.name);
         self.add_code(&format!(
             "let frame_args = {}::",
@@ -4053,31 +6011,42 @@ impl AstVisitor for RustVisitor {
 
         self.newline();
         self.add_code(&format!(
-            "let {} = {}::new({}::new({}::{}, frame_args));",
-            self.config.code.frame_event_variable_name,
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
+            "{}{}::{}, frame_args{}",
+            self.new_event_start(&self.config.code.frame_event_variable_name),
             self.config.code.frame_event_message_type_name,
             event_type_name,
+            self.new_event_end(),
         ));
         self.newline();
         if interface_method_node.return_type_opt.is_some() {
             self.add_code(&format!(
-                "self.{}({}.clone());",
+                "self.{}({});",
                 self.config.code.handle_event_method_name,
-                self.config.code.frame_event_variable_name,
-            ));
-            self.newline();
-            self.add_code(&format!(
-                "let return_value = match *{}.{}.{}",
-                self.config.code.frame_event_variable_name,
-                self.config.code.frame_event_return_attribute_name,
-                if self.config.features.thread_safe {
-                    "lock().unwrap()"
+                if self.config.features.lightweight {
+                    self.event_arg(&self.config.code.frame_event_variable_name)
                 } else {
-                    "borrow()"
+                    format!("{}.clone()", self.config.code.frame_event_variable_name)
                 },
             ));
+            self.newline();
+            if self.config.features.lightweight {
+                self.add_code(&format!(
+                    "let return_value = match {}.{}",
+                    self.config.code.frame_event_variable_name,
+                    self.config.code.frame_event_return_attribute_name,
+                ));
+            } else {
+                self.add_code(&format!(
+                    "let return_value = match *{}.{}.{}",
+                    self.config.code.frame_event_variable_name,
+                    self.config.code.frame_event_return_attribute_name,
+                    if self.config.features.thread_safe {
+                        "lock().unwrap()"
+                    } else {
+                        "borrow()"
+                    },
+                ));
+            }
             self.enter_block();
             self.add_code(&format!(
                 "{}::{} {{ return_value }} => return_value.clone(),",
@@ -4097,11 +6066,36 @@ impl AstVisitor for RustVisitor {
             self.add_code(&format!(
                 "self.{}({});",
                 self.config.code.handle_event_method_name,
-                self.config.code.frame_event_variable_name,
+                self.event_arg(&self.config.code.frame_event_variable_name),
             ));
         }
 
         self.exit_block();
+        self.override_fragment("interface_method", start, |visitor| {
+            let params: Vec<serde_json::Value> = interface_method_node
+                .params
+                .iter()
+                .flatten()
+                .map(|param| {
+                    json!({
+                        "name": visitor.format_value_name(&param.param_name),
+                        "type": param
+                            .param_type_opt
+                            .as_ref()
+                            .map(|type_node| type_node.get_type_str()),
+                    })
+                })
+                .collect();
+            json!({
+                "name": interface_method_node.name,
+                "method_name": visitor.format_value_name(&interface_method_node.name),
+                "params": params,
+                "return_type": interface_method_node
+                    .return_type_opt
+                    .as_ref()
+                    .map(|return_type| return_type.get_type_str()),
+            })
+        });
         self.newline();
     }
 
@@ -4121,7 +6115,11 @@ impl AstVisitor for RustVisitor {
         // self.deserialize.push("\tswitch (bag.state) {".to_string());
 
         for state_node_rcref in &machine_block_node.states {
-            state_node_rcref.borrow().accept(self);
+            if self.config.features.split_states {
+                self.generate_state_file(&state_node_rcref.borrow());
+            } else {
+                state_node_rcref.borrow().accept(self);
+            }
         }
 
         // self.serialize.push("".to_string());
@@ -4239,7 +6237,12 @@ impl AstVisitor for RustVisitor {
     //* --------------------------------------------------------------------- *//
 
     fn visit_domain_block_node(&mut self, domain_block_node: &DomainBlockNode) {
-        let var_nodes = &domain_block_node.member_variables;
+        // constants are generated as module-level items, not struct fields
+        let var_nodes: Vec<_> = domain_block_node
+            .member_variables
+            .iter()
+            .filter(|var_rcref| !var_rcref.borrow().is_constant)
+            .collect();
         if !var_nodes.is_empty() {
             self.newline();
             self.add_code("//===================== Domain Block ===================//");
@@ -4252,11 +6255,35 @@ impl AstVisitor for RustVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_enum_decl_node(&mut self, enum_decl_node: &EnumDeclNode) {
+        self.newline();
+        self.newline();
+        self.add_code("#[allow(dead_code)]");
+        self.newline();
+        let traits = self.config.code.enum_traits.clone();
+        if !traits.trim().is_empty() {
+            self.add_code(&format!("#[derive({})]", traits));
+            self.newline();
+        }
+        self.add_code(&format!("pub enum {}", enum_decl_node.name));
+        self.enter_block();
+        for (i, enumerator) in enum_decl_node.enums.iter().enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            self.add_code(&format!("{},", enumerator.name));
+        }
+        self.exit_block();
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_state_node(&mut self, state_node: &StateNode) {
         self.generate_comment(state_node.line);
         self.current_state_name_opt = Some(state_node.name.clone());
         self.newline();
         self.newline();
+        let start = self.code.len();
 
         self.add_code("#[allow(clippy::collapsible_else_if)]");
         self.newline();
@@ -4272,15 +6299,25 @@ impl AstVisitor for RustVisitor {
         self.newline();
         self.add_code("#[allow(unused_variables)]");
         self.newline();
+        self.record_mapping(
+            CodeMapKind::State,
+            &state_node.name,
+            state_node.line,
+            state_node.name_span,
+        );
         self.add_code(&format!(
-            "fn {}(&mut self, {}: {}<{}>) {{",
+            "{}fn {}(&mut self, {}: {}) {{",
+            if self.config.features.split_states {
+                "pub(super) "
+            } else {
+                ""
+            },
             self.format_state_handler_name(&state_node.name),
             self.config.code.frame_event_variable_name,
-            self.rc_type(),
-            self.config.code.frame_event_type_name,
+            self.frame_event_param_type(),
         ));
         self.indent();
-        if self.generate_state_context {
+        if self.generate_state_context && !self.config.features.lightweight {
             self.newline();
             self.generate_this_state_context();
         }
@@ -4319,16 +6356,23 @@ impl AstVisitor for RustVisitor {
         self.newline();
         self.add_code("}");
 
-        // generate call to parent handler, if applicable
+        // generate call to parent handler, if applicable. Parallel states handle the events of
+        // their regions after dispatching them to all regions.
         match &state_node.dispatch_opt {
-            Some(dispatch) => {
+            Some(dispatch) if dispatch.region_opt.is_none() => {
                 dispatch.accept(self);
             }
-            None => {}
+            _ => {}
         }
         self.outdent();
         self.newline();
         self.add_code("}");
+        self.override_fragment("state_handler", start, |visitor| {
+            json!({
+                "state": state_node.name,
+                "handler_name": visitor.format_state_handler_name(&state_node.name),
+            })
+        });
 
         self.current_state_name_opt = None;
     }
@@ -4342,6 +6386,13 @@ impl AstVisitor for RustVisitor {
         //        let mut generate_final_close_paren = true;
         if let MessageType::CustomMessage { message_node } = &evt_handler_node.msg_t {
             self.current_message = message_node.name.clone();
+            let handler_name = format!("{}:{}", evt_handler_node.state_name, message_node.name);
+            self.record_mapping(
+                CodeMapKind::EventHandler,
+                &handler_name,
+                message_node.line,
+                message_node.span,
+            );
             self.add_code(&format!(
                 "{}::{} => {{",
                 self.config.code.frame_event_message_type_name,
@@ -4401,6 +6452,12 @@ impl AstVisitor for RustVisitor {
                             ));
                             self.newline();
                             self.add_code("*ret = ");
+                        } else if self.config.features.lightweight {
+                            self.add_code(&format!(
+                                "{}.{} = ",
+                                self.config.code.frame_event_variable_name,
+                                self.config.code.frame_event_return_attribute_name,
+                            ));
                         } else {
                             self.add_code(&format!(
                                 "{}.{}.replace(",
@@ -4417,7 +6474,7 @@ impl AstVisitor for RustVisitor {
                         self.add_code("return_value: ");
                         expr_t.accept(self);
                         self.exit_block();
-                        if !self.config.features.thread_safe {
+                        if !self.config.features.thread_safe && !self.config.features.lightweight {
                             self.add_code(")");
                         }
                         self.add_code(";");
@@ -4613,6 +6670,11 @@ impl AstVisitor for RustVisitor {
             } => {
                 number_match_test_node.accept(self);
             }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                enum_match_test_node.accept(self);
+            }
         }
     }
 
@@ -5182,6 +7244,174 @@ impl AstVisitor for RustVisitor {
         self.add_code(&match_pattern_node.match_pattern_number.to_string());
     }
 
+    //-----------------------------------------------------//
+
+    fn visit_enum_match_test_node(&mut self, enum_match_test_node: &EnumMatchTestNode) {
+        let mut if_or_else_if = "if";
+
+        self.newline();
+        for match_branch_node in &enum_match_test_node.match_branch_nodes {
+            self.add_code(&format!("{} ", if_or_else_if));
+            match &enum_match_test_node.expr_t {
+                ExprType::CallExprT {
+                    call_expr_node: method_call_expr_node,
+                } => method_call_expr_node.accept(self),
+                ExprType::ActionCallExprT {
+                    action_call_expr_node,
+                } => action_call_expr_node.accept(self),
+                ExprType::CallChainLiteralExprT {
+                    call_chain_expr_node,
+                } => call_chain_expr_node.accept(self),
+                ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                ExprType::ExprListT { expr_list_node } => {
+                    // must be only 1 expression in the list
+                    if expr_list_node.exprs_t.len() != 1 {
+                        // TODO: how to do this better.
+                        self.errors
+                            .push("Error - expression list is not testable.".to_string());
+                    }
+                    let x = expr_list_node.exprs_t.first().unwrap();
+                    x.accept(self);
+                }
+                _ => self.errors.push("TODO.".to_string()),
+            }
+
+            let mut first_match = true;
+            for match_enumerator in &match_branch_node.enum_match_pattern_nodes {
+                if first_match {
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {}", pattern));
+                    first_match = false;
+                } else {
+                    self.add_code(" || ");
+                    match &enum_match_test_node.expr_t {
+                        ExprType::CallExprT {
+                            call_expr_node: method_call_expr_node,
+                        } => method_call_expr_node.accept(self),
+                        ExprType::ActionCallExprT {
+                            action_call_expr_node,
+                        } => action_call_expr_node.accept(self),
+                        ExprType::CallChainLiteralExprT {
+                            call_chain_expr_node,
+                        } => call_chain_expr_node.accept(self),
+                        ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                        _ => self.errors.push("TODO.".to_string()),
+                    }
+                    let mut pattern = String::new();
+                    match_enumerator
+                        .enumerator_expr_node
+                        .accept_to_string(self, &mut pattern);
+                    self.add_code(&format!(" == {}", pattern));
+                }
+            }
+
+            self.add_code(" {");
+            self.indent();
+
+            match_branch_node.accept(self);
+
+            self.generate_return_if_transitioned();
+
+            self.outdent();
+            self.newline();
+            self.add_code("}");
+
+            if_or_else_if = " else if";
+        }
+
+        // (':' enum_test_else_branch)?
+        if let Some(enum_match_else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+            enum_match_else_branch_node.accept(self);
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_match_branch_node(
+        &mut self,
+        enum_match_test_match_branch_node: &EnumMatchTestMatchBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_match_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_match_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => {
+                        if let Some(expr_t) = &branch_terminator_expr.return_expr_t_opt {
+                            self.add_code("e._return = ");
+                            expr_t.accept(self);
+                            self.add_code(";");
+                        }
+                        self.generate_return();
+                    }
+                    TerminatorType::Continue => {
+                        self.generate_return_if_transitioned();
+                    }
+                }
+            }
+            None => {
+                self.generate_return_if_transitioned();
+            }
+        }
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_else_branch_node(
+        &mut self,
+        enum_match_test_else_branch_node: &EnumMatchTestElseBranchNode,
+    ) {
+        self.add_code(" else {");
+        self.indent();
+
+        self.visit_decl_stmts(&enum_match_test_else_branch_node.statements);
+
+        // TODO - factor this out to work w/ other terminator code.
+        match &enum_match_test_else_branch_node.branch_terminator_expr_opt {
+            Some(branch_terminator_expr) => {
+                self.newline();
+                match &branch_terminator_expr.terminator_type {
+                    TerminatorType::Return => {
+                        if let Some(expr_t) = &branch_terminator_expr.return_expr_t_opt {
+                            self.add_code("e._return = ");
+                            expr_t.accept(self);
+                            self.add_code(";");
+                        }
+                        self.generate_return();
+                    }
+                    TerminatorType::Continue => {
+                        self.generate_return_if_transitioned();
+                    }
+                }
+            }
+            None => {
+                self.generate_return_if_transitioned();
+            }
+        }
+
+        self.outdent();
+        self.newline();
+        self.add_code("}");
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enum_match_test_pattern_node(
+        &mut self,
+        match_pattern_node: &EnumMatchTestPatternNode,
+    ) {
+        let mut pattern = String::new();
+        match_pattern_node
+            .enumerator_expr_node
+            .accept_to_string(self, &mut pattern);
+        self.add_code(&pattern);
+    }
+
     //* --------------------------------------------------------------------- *//
 
     fn visit_expression_list_node(&mut self, expr_list: &ExprListNode) {
@@ -5247,6 +7477,28 @@ impl AstVisitor for RustVisitor {
 
     //* --------------------------------------------------------------------- *//
 
+    fn visit_enumerator_expr_node(&mut self, enum_expr_node: &EnumeratorExprNode) {
+        self.add_code(&format!(
+            "{}::{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
+    fn visit_enumerator_expr_node_to_string(
+        &mut self,
+        enum_expr_node: &EnumeratorExprNode,
+        output: &mut String,
+    ) {
+        output.push_str(&format!(
+            "{}::{}",
+            enum_expr_node.enum_type, enum_expr_node.enumerator
+        ));
+    }
+
+    //* --------------------------------------------------------------------- *//
+
     fn visit_literal_expression_node_to_string(
         &mut self,
         literal_expression_node: &LiteralExprNode,
@@ -5333,7 +7585,7 @@ impl AstVisitor for RustVisitor {
                     "self.{}();",
                     self.config.code.state_stack_push_method_name
                 ));
-                if self.generate_state_context {
+                if self.generate_state_context && !self.config.features.lightweight {
                     self.newline();
                     self.generate_this_state_context();
                 }
@@ -5521,6 +7773,22 @@ impl AstVisitor for RustVisitor {
 
         self.add_code(" {");
 
+        if self.config.features.generate_mock_actions {
+            self.indent();
+            self.newline();
+            self.generate_action_call_record(&action_name, &action_node.params);
+            if action_node.code_opt.is_some() {
+                self.newline();
+            } else if action_node.type_opt.is_some() {
+                self.newline();
+                self.add_code("Default::default()");
+            }
+            self.outdent();
+            if action_node.code_opt.is_none() {
+                self.newline();
+            }
+        }
+
         match &action_node.code_opt {
             Some(code) => {
                 self.indent();
@@ -5620,6 +7888,12 @@ impl AstVisitor for RustVisitor {
                     ));
                     self.newline_to_string(output);
                     output.push_str("*ret = ");
+                } else if self.config.features.lightweight {
+                    output.push_str(&format!(
+                        "{}.{} = ",
+                        self.config.code.frame_event_variable_name,
+                        self.config.code.frame_event_return_attribute_name,
+                    ));
                 } else {
                     output.push_str(&format!(
                         "{}.{}.replace(",
@@ -5638,7 +7912,7 @@ impl AstVisitor for RustVisitor {
                     .r_value_box
                     .accept_to_string(self, output);
                 self.exit_block_to_string(output);
-                if !self.config.features.thread_safe {
+                if !self.config.features.thread_safe && !self.config.features.lightweight {
                     output.push(')');
                 }
                 output.push(';');
@@ -1,12 +1,18 @@
 use crate::frame_c::ast::*;
 use crate::frame_c::config::{FrameConfig, SmcatConfig};
 use crate::frame_c::utils::SystemHierarchy;
+use crate::frame_c::visitors::edge_labels::{self, EdgeAnnotation};
 use crate::frame_c::visitors::*;
+use std::collections::HashMap;
 
 fn indent_str(indent: usize) -> String {
     "  ".repeat(indent)
 }
 
+// The pseudo-state that transitions to the state popped off the state stack go to. The suffix
+// sets the type of the state, drawn as a circle labeled H*.
+const STATE_STACK_POP_STATE: &str = "H*.deephistory";
+
 fn format_styling(style: &str) -> String {
     if style.is_empty() {
         String::new()
@@ -21,6 +27,11 @@ pub struct SmcatVisitor {
     system_hierarchy: SystemHierarchy,
     current_state: Option<String>,
     current_handler: Option<String>,
+    // the parameters of the current event and the annotations of its edges, in visiting order
+    current_event_params: String,
+    edge_annotations: std::vec::IntoIter<EdgeAnnotation>,
+    // the state stack operations of each state, as "event/ operation" activities
+    state_stack_activities: HashMap<String, Vec<String>>,
     code: String,
 }
 
@@ -37,6 +48,9 @@ impl SmcatVisitor {
             system_hierarchy,
             current_state: None,
             current_handler: None,
+            current_event_params: String::new(),
+            edge_annotations: Vec::new().into_iter(),
+            state_stack_activities: HashMap::new(),
             code: String::from(""),
         }
     }
@@ -71,6 +85,9 @@ impl SmcatVisitor {
             node_name,
             format_styling(style)
         ));
+        if let Some(activities) = self.state_stack_activities.get(node_name) {
+            output.push_str(&format!(": \"{}\"", activities.join("\\n")));
+        }
 
         // add children
         if has_children {
@@ -122,85 +139,68 @@ impl SmcatVisitor {
         source_name: &str,
         target_name: &str,
         style: &str,
-        event_name: &str,
         label: Option<&String>,
     ) {
+        let features = &self.config.features;
+        let annotation = self.edge_annotations.next().unwrap_or_default();
+        let mut text = self.current_handler.as_ref().unwrap().clone();
+        if features.edge_params {
+            text.push_str(&self.current_event_params);
+        }
+        if features.edge_guards {
+            if let Some(guard) = annotation.format_guard() {
+                text.push_str(&format!(" [{}]", guard));
+            }
+        }
+        let mut activities: Vec<String> = label.into_iter().cloned().collect();
+        if features.edge_actions {
+            activities.extend(annotation.format_actions());
+        }
+        if !activities.is_empty() {
+            text.push_str(&format!("/ {}", activities.join("; ")));
+        }
         self.add_code(&format!(
-            "{} -> {}{} : \"  {}{}  \";\n",
+            "{} -> {}{} : \"  {}  \";\n",
             source_name,
             target_name,
             style,
-            event_name,
-            match label {
-                Some(text) => format!("/ {}", text),
-                None => String::new(),
-            }
+            text.replace('"', "\\\"")
         ));
     }
 
-    fn generate_state_ref_change_state(&mut self, change_state_stmt: &ChangeStateStatementNode) {
+    fn generate_change_state(&mut self, change_state_stmt: &ChangeStateStatementNode) {
         let source_state = self.current_state.as_ref().unwrap().to_string();
         let target_state = match &change_state_stmt.state_context_t {
             StateContextType::StateRef { state_context_node } => {
                 &state_context_node.state_ref_node.name
             }
-            StateContextType::StateStackPop {} => {
-                panic!("TODO")
-            }
+            StateContextType::StateStackPop {} => STATE_STACK_POP_STATE,
         };
         let style = format_styling(&self.config.code.change_state_edge_style);
-        let event = self.current_handler.as_ref().unwrap().clone();
         self.generate_transition(
             &source_state,
             target_state,
             &style,
-            &event,
             change_state_stmt.label_opt.as_ref(),
         );
     }
 
-    fn generate_state_ref_transition(&mut self, transition_stmt: &TransitionStatementNode) {
+    fn generate_transition_statement(&mut self, transition_stmt: &TransitionStatementNode) {
         let source_state = self.current_state.as_ref().unwrap().clone();
         let target_state = match &transition_stmt.target_state_context_t {
             StateContextType::StateRef { state_context_node } => {
                 &state_context_node.state_ref_node.name
             }
-            StateContextType::StateStackPop {} => {
-                panic!("TODO")
-            }
+            StateContextType::StateStackPop {} => STATE_STACK_POP_STATE,
         };
         let style = format_styling(&self.config.code.transition_edge_style);
-        let event = self.current_handler.as_ref().unwrap().clone();
         self.generate_transition(
             &source_state,
             target_state,
             &style,
-            &event,
             transition_stmt.label_opt.as_ref(),
         );
     }
-
-    // TODO: Review if this is correct handling. At least with regular statecharts,
-    // each state with children can have a separate history that's used to determine
-    // initial child state on reentry to parent state
-    fn generate_state_stack_pop_transition(
-        &mut self,
-        transition_statement: &TransitionStatementNode,
-    ) {
-        let event = self.current_handler.as_ref().unwrap().clone();
-        let label = match &transition_statement.label_opt {
-            Some(label) => label,
-            None => &event,
-        };
-        // .deephistory suffix overrides target state label with H* and sets shape to
-        // circle
-        let transition = &format!(
-            "{} -> H*.deephistory : {};\n",
-            &self.current_state.as_ref().unwrap(),
-            label
-        );
-        self.add_code(transition);
-    }
 }
 
 impl AstVisitor for SmcatVisitor {
@@ -216,6 +216,13 @@ impl AstVisitor for SmcatVisitor {
     }
 
     fn visit_machine_block_node(&mut self, machine_block_node: &MachineBlockNode) {
+        // visit the states first to find their state stack operations
+        let code = std::mem::take(&mut self.code);
+        for state_node_rcref in &machine_block_node.states {
+            state_node_rcref.borrow().accept(self);
+        }
+        let transitions = std::mem::replace(&mut self.code, code);
+
         let mut output = String::new();
         let system_name = &self.system_hierarchy.system_name;
         let system_node = self.system_hierarchy.get_node(system_name).unwrap();
@@ -233,9 +240,7 @@ impl AstVisitor for SmcatVisitor {
                 first_state.borrow().name.clone()
             ));
         }
-        for state_node_rcref in &machine_block_node.states {
-            state_node_rcref.borrow().accept(self);
-        }
+        self.add_code(&transitions);
     }
 
     fn visit_action_node_rust_trait(&mut self, _: &ActionsBlockNode) {
@@ -279,6 +284,8 @@ impl AstVisitor for SmcatVisitor {
         }
         qualified_event_name.push_str(event_name);
         self.current_handler = Some(qualified_event_name);
+        self.current_event_params = edge_labels::format_event_params(evt_handler_node);
+        self.edge_annotations = edge_labels::edge_annotations(evt_handler_node).into_iter();
 
         // process statements, looking for transitions
         self.visit_decl_stmts(&evt_handler_node.statements);
@@ -290,14 +297,7 @@ impl AstVisitor for SmcatVisitor {
     fn visit_action_call_statement_node(&mut self, _action_call_stmt_node: &ActionCallStmtNode) {}
 
     fn visit_transition_statement_node(&mut self, transition_statement: &TransitionStatementNode) {
-        match &transition_statement.target_state_context_t {
-            StateContextType::StateRef { .. } => {
-                self.generate_state_ref_transition(transition_statement)
-            }
-            StateContextType::StateStackPop {} => {
-                self.generate_state_stack_pop_transition(transition_statement)
-            }
-        };
+        self.generate_transition_statement(transition_statement);
     }
 
     fn visit_state_ref_node(&mut self, state_ref: &StateRefNode) {
@@ -308,15 +308,26 @@ impl AstVisitor for SmcatVisitor {
         &mut self,
         change_state_stmt_node: &ChangeStateStatementNode,
     ) {
-        match &change_state_stmt_node.state_context_t {
-            StateContextType::StateRef { .. } => {
-                self.generate_state_ref_change_state(change_state_stmt_node)
-            }
-            StateContextType::StateStackPop {} => {
-                // self.generate_state_stack_pop_change_state(transition_statement)
-                panic!("change-state to state-stack pop not implemented");
-            }
+        self.generate_change_state(change_state_stmt_node);
+    }
+
+    fn visit_state_stack_operation_statement_node(
+        &mut self,
+        state_stack_op_statement_node: &StateStackOperationStatementNode,
+    ) {
+        let operation = match state_stack_op_statement_node
+            .state_stack_operation_node
+            .operation_t
+        {
+            StateStackOperationType::Push => "$$[+]",
+            StateStackOperationType::Pop => "$$[-]",
         };
+        let state_name = self.current_state.as_ref().unwrap().clone();
+        let activity = format!("{}/ {}", self.current_handler.as_ref().unwrap(), operation);
+        self.state_stack_activities
+            .entry(state_name)
+            .or_default()
+            .push(activity);
     }
 
     fn visit_test_statement_node(&mut self, test_stmt_node: &TestStatementNode) {
@@ -334,6 +345,11 @@ impl AstVisitor for SmcatVisitor {
             } => {
                 number_match_test_node.accept(self);
             }
+            TestType::EnumMatchTest {
+                enum_match_test_node,
+            } => {
+                enum_match_test_node.accept(self);
+            }
         }
     }
 
@@ -481,4 +497,66 @@ impl AstVisitor for SmcatVisitor {
     ) {
         self.visit_decl_stmts(&number_match_test_else_branch_node.statements);
     }
+
+    //-----------------------------------------------------//
+
+    fn visit_enum_match_test_node(&mut self, enum_match_test_node: &EnumMatchTestNode) {
+        for match_branch_node in &enum_match_test_node.match_branch_nodes {
+            // self.add_code(&format!("{} (", if_or_else_if));
+            match &enum_match_test_node.expr_t {
+                ExprType::CallExprT {
+                    call_expr_node: method_call_expr_node,
+                } => method_call_expr_node.accept(self),
+                ExprType::ActionCallExprT {
+                    action_call_expr_node,
+                } => action_call_expr_node.accept(self),
+                ExprType::CallChainLiteralExprT {
+                    call_chain_expr_node,
+                } => call_chain_expr_node.accept(self),
+                ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                _ => panic!("TODO"),
+            }
+
+            let mut first_match = true;
+            for _match_enumerator in &match_branch_node.enum_match_pattern_nodes {
+                if first_match {
+                    first_match = false;
+                } else {
+                    match &enum_match_test_node.expr_t {
+                        ExprType::CallExprT {
+                            call_expr_node: method_call_expr_node,
+                        } => method_call_expr_node.accept(self),
+                        ExprType::ActionCallExprT {
+                            action_call_expr_node,
+                        } => action_call_expr_node.accept(self),
+                        ExprType::CallChainLiteralExprT {
+                            call_chain_expr_node,
+                        } => call_chain_expr_node.accept(self),
+                        ExprType::VariableExprT { var_node: id_node } => id_node.accept(self),
+                        _ => panic!("TODO"),
+                    }
+                }
+            }
+
+            match_branch_node.accept(self);
+        }
+
+        if let Some(enum_match_else_branch_node) = &enum_match_test_node.else_branch_node_opt {
+            enum_match_else_branch_node.accept(self);
+        }
+    }
+
+    fn visit_enum_match_test_match_branch_node(
+        &mut self,
+        enum_match_test_match_branch_node: &EnumMatchTestMatchBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_match_branch_node.statements);
+    }
+
+    fn visit_enum_match_test_else_branch_node(
+        &mut self,
+        enum_match_test_else_branch_node: &EnumMatchTestElseBranchNode,
+    ) {
+        self.visit_decl_stmts(&enum_match_test_else_branch_node.statements);
+    }
 }
@@ -1,11 +1,13 @@
 pub mod frame_c;
+pub mod wasm;
 use crate::compiler::{Exe, TargetLanguage};
 use crate::frame_c::*;
 use std::convert::TryFrom;
 use wasm_bindgen::prelude::*;
 
 /// Entry point for the online framepiler. See `frame_c::cli::run_with()` for
-/// a more full-featured entry point.
+/// a more full-featured entry point, and the `wasm` module for the API with
+/// diagnostics.
 #[wasm_bindgen]
 pub fn run(frame_code: &str, format: &str) -> String {
     let exe = Exe::new();
@@ -0,0 +1,203 @@
+//! The API of Framec for JavaScript, when compiled to WebAssembly with `wasm-pack`, e.g. for a
+//! playground or the preview of an editor that transpiles specs in the browser.
+//!
+//! ```js
+//! import init, { Compiler } from "./pkg/framec.js";
+//!
+//! await init();
+//! const compiler = new Compiler();
+//! const result = compiler.compile(spec, "rust");
+//! if (result.ok) {
+//!     show(result.code);
+//! }
+//! for (const diagnostic of JSON.parse(result.diagnostics)) {
+//!     mark(diagnostic.line, diagnostic.column, diagnostic.message);
+//! }
+//! ```
+//!
+//! There is no file system in the browser, so there is no `config.yaml` file, and specs are
+//! configured by their attributes only.
+
+use crate::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use crate::frame_c::diagnostics::Diagnostic;
+use crate::frame_c::formatter;
+use crate::frame_c::utils::RunError;
+use std::convert::TryFrom;
+use wasm_bindgen::prelude::*;
+
+/// A Frame compiler that can compile any number of specs. Recompiling an unchanged spec, e.g.
+/// to another target, reuses its analysis, so an editor should keep one for its previews.
+#[wasm_bindgen]
+pub struct Compiler {
+    exe: Exe,
+}
+
+#[wasm_bindgen]
+impl Compiler {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Compiler {
+        Compiler {
+            exe: Exe::with_options(CompilerOptions {
+                load_local_config: false,
+                ..CompilerOptions::default()
+            }),
+        }
+    }
+
+    /// Compile a spec to the target language with the given name, e.g. `python_3`. See
+    /// `targets` for the names.
+    pub fn compile(&self, spec: &str, target: &str) -> Compilation {
+        let target = match TargetLanguage::try_from(target) {
+            Ok(target) => target,
+            Err(err) => return Compilation::failed(RunError::new(exitcode::USAGE, &err)),
+        };
+        match self.exe.run(&None, None, spec.to_string(), Some(target)) {
+            Ok(code) => Compilation {
+                code,
+                diagnostics: self.exe.warnings(),
+                ok: true,
+            },
+            Err(run_error) => Compilation::failed(run_error),
+        }
+    }
+
+    /// Generate a state diagram of a spec, in the `smcat` or `plantuml` language.
+    pub fn diagram(&self, spec: &str, format: &str) -> Compilation {
+        match format {
+            "smcat" | "plantuml" => self.compile(spec, format),
+            _ => Compilation::failed(RunError::new(
+                exitcode::USAGE,
+                &format!("Unrecognized diagram format {}", format),
+            )),
+        }
+    }
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Compiler::new()
+    }
+}
+
+/// The result of compiling a spec.
+#[wasm_bindgen]
+pub struct Compilation {
+    code: String,
+    diagnostics: Vec<Diagnostic>,
+    ok: bool,
+}
+
+impl Compilation {
+    fn failed(run_error: RunError) -> Compilation {
+        Compilation {
+            code: String::new(),
+            diagnostics: run_error.diagnostics,
+            ok: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Compilation {
+    /// Whether the spec compiled. A spec that compiled may still have warnings.
+    #[wasm_bindgen(getter)]
+    pub fn ok(&self) -> bool {
+        self.ok
+    }
+
+    /// The generated code, which is empty if the spec didn't compile.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    /// The errors or warnings, as a JSON array of the diagnostics of `--message-format json`.
+    #[wasm_bindgen(getter)]
+    pub fn diagnostics(&self) -> String {
+        // Serializing a plain struct of strings and numbers cannot fail.
+        serde_json::to_string(&self.diagnostics).unwrap()
+    }
+
+    /// The errors or warnings, one per line, as Framec prints them.
+    #[wasm_bindgen(getter)]
+    pub fn messages(&self) -> String {
+        let mut messages = String::new();
+        for diagnostic in &self.diagnostics {
+            messages.push_str(&diagnostic.to_string());
+            messages.push('\n');
+        }
+        messages
+    }
+}
+
+/// Compile a spec to the target language with the given name. See `Compiler.compile`.
+#[wasm_bindgen]
+pub fn compile(spec: &str, target: &str) -> Compilation {
+    Compiler::new().compile(spec, target)
+}
+
+/// Format a spec in the canonical style, as `framec fmt` does.
+#[wasm_bindgen]
+pub fn format(spec: &str) -> String {
+    formatter::format_source(spec)
+}
+
+/// The names of the target languages.
+#[wasm_bindgen]
+pub fn targets() -> Vec<String> {
+    TargetLanguage::all()
+        .iter()
+        .map(|target| target.name().to_string())
+        .collect()
+}
+
+/// The version of Framec.
+#[wasm_bindgen]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAMP: &str = "#Lamp\n-interface-\ntoggle\n-machine-\n$Off\n|toggle| -> $On ^\n$On\n|toggle| -> $Off ^\n##\n";
+
+    #[test]
+    fn compiles() {
+        let compiler = Compiler::new();
+        let result = compiler.compile(LAMP, "rust");
+        assert!(result.ok());
+        assert!(result.code().contains("pub struct Lamp"));
+
+        let diagram = compiler.diagram(LAMP, "smcat");
+        assert!(diagram.ok());
+        assert!(diagram.code().contains("Off -> On"));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let result = compile(&LAMP.replace("$On\n", "$Off\n"), "rust");
+        assert!(!result.ok());
+        assert!(result.code().is_empty());
+        let diagnostics: serde_json::Value = serde_json::from_str(&result.diagnostics()).unwrap();
+        assert_eq!(diagnostics[0]["code"], "parse-error");
+        assert!(result.messages().contains("error[parse-error]"));
+
+        let result = compile(LAMP, "cobol");
+        assert!(!result.ok());
+        assert!(result
+            .messages()
+            .contains("Unrecognized target language cobol"));
+        assert!(!Compiler::new().diagram(LAMP, "rust").ok());
+    }
+
+    #[test]
+    fn lists_targets() {
+        let targets = targets();
+        assert_eq!(targets.len(), TargetLanguage::all().len());
+        for target in targets {
+            assert!(TargetLanguage::try_from(target.as_str()).is_ok());
+        }
+    }
+}
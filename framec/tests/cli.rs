@@ -0,0 +1,399 @@
+//! Tests of the `framec` executable used as a stage of a shell pipeline.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const SPEC: &str = "#Lamp
+    -interface-
+    toggle
+    -machine-
+    $Off
+        |toggle| -> $On ^
+    $On
+        |toggle| -> $Off ^
+##
+";
+
+/// Run `framec` with the given arguments, piping `input` to its stdin.
+fn framec(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_framec"))
+        .args(args)
+        .current_dir(std::env::temp_dir())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // framec may exit without reading its input, e.g. on a usage error
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+/// Test that a spec piped to stdin is compiled to stdout, with or without `-` as the path.
+#[test]
+fn stdin_to_stdout() {
+    for args in [&["-l", "python_3"][..], &["-l", "python_3", "-"][..]] {
+        let output = framec(args, SPEC);
+        assert!(output.status.success());
+        let code = String::from_utf8(output.stdout).unwrap();
+        assert!(code.starts_with("# emitted from framec"));
+        assert!(code.contains("class Lamp"));
+        assert!(output.stderr.is_empty());
+    }
+}
+
+/// Test that errors in a spec piped to stdin are reported against the `--stdin-name` path, and
+/// nothing is written to stdout.
+#[test]
+fn stdin_name_in_errors() {
+    let broken = "#Broken\n-interface-\nfoo [\n##\n";
+    let output = framec(
+        &[
+            "-l",
+            "rust",
+            "--stdin-name",
+            "specs/broken.frm",
+            "--message-format=json",
+        ],
+        broken,
+    );
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("\"file\":\"specs/broken.frm\""));
+}
+
+/// Test that includes of a spec piped to stdin are resolved relative to its `--stdin-name`.
+#[test]
+fn stdin_name_resolves_includes() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("interface.frmi"), "toggle\n").unwrap();
+    let spec = SPEC.replacen("    toggle\n", "    #include \"interface.frmi\"\n", 1);
+    let stdin_name = dir.join("lamp.frm");
+
+    let output = framec(
+        &[
+            "-l",
+            "python_3",
+            "--stdin-name",
+            stdin_name.to_str().unwrap(),
+        ],
+        &spec,
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("def toggle(self)"));
+}
+
+/// Test that several inputs are compiled to an output directory that mirrors their layout, and
+/// that a failing spec doesn't stop the others from being compiled.
+#[test]
+fn batch_to_out_dir() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_batch_{}", std::process::id()));
+    let specs = dir.join("specs");
+    std::fs::create_dir_all(specs.join("sub")).unwrap();
+    std::fs::write(specs.join("lamp.frm"), SPEC).unwrap();
+    std::fs::write(specs.join("sub/switch.frm"), SPEC.replace("Lamp", "Switch")).unwrap();
+    std::fs::write(
+        specs.join("sub/broken.frm"),
+        "#Broken\n-interface-\nfoo [\n##\n",
+    )
+    .unwrap();
+    let out_dir = dir.join("out");
+
+    let pattern = specs.join("**").join("*.frm");
+    let output = framec(
+        &[
+            "-l",
+            "python_3",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            pattern.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("broken.frm"));
+    let lamp = std::fs::read_to_string(out_dir.join("lamp.py")).unwrap();
+    assert!(lamp.contains("class Lamp"));
+    let switch = std::fs::read_to_string(out_dir.join("sub/switch.py")).unwrap();
+    assert!(switch.contains("class Switch"));
+    assert!(!out_dir.join("sub/broken.py").exists());
+
+    // several inputs can't be written to stdout
+    let output = framec(&["-l", "python_3", specs.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(64));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that several target languages are compiled into the output directory at once, and that
+/// they require one.
+#[test]
+fn several_targets() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_targets_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spec_path = dir.join("lamp.frm");
+    std::fs::write(&spec_path, SPEC).unwrap();
+    let out_dir = dir.join("out");
+
+    let output = framec(
+        &[
+            "-l",
+            "rust,python_3,smcat",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            spec_path.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(output.status.success());
+    let rust = std::fs::read_to_string(out_dir.join("lamp.rs")).unwrap();
+    assert!(rust.contains("pub struct Lamp"));
+    let python = std::fs::read_to_string(out_dir.join("lamp.py")).unwrap();
+    assert!(python.contains("class Lamp"));
+    assert!(out_dir.join("lamp.smcat").exists());
+
+    // targets generating the same files can't be combined
+    let output = framec(
+        &[
+            "-l",
+            "c_sharp,c_sharp_bob",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            spec_path.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(64));
+
+    // several targets can't be written to stdout
+    let output = framec(&["-l", "rust,python_3", spec_path.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(64));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that the state files of split machines are written next to the generated file, and
+/// can't be written to stdout.
+#[test]
+fn split_states_to_out_dir() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_split_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spec = dir.join("lamp.frm");
+    let split_spec = format!(
+        "#[codegen.rust.features.split_states:bool=\"true\"]\n{}",
+        SPEC
+    );
+    std::fs::write(&spec, split_spec).unwrap();
+    let out_dir = dir.join("out");
+
+    let output = framec(
+        &[
+            "-l",
+            "rust",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            spec.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(output.status.success());
+    let lamp = std::fs::read_to_string(out_dir.join("lamp.rs")).unwrap();
+    assert!(lamp.contains("mod off_state {\n    include!(\"lamp/off.rs\");\n}"));
+    let off = std::fs::read_to_string(out_dir.join("lamp/off.rs")).unwrap();
+    assert!(off.contains("pub(super) fn off_handler("));
+    assert!(out_dir.join("lamp/on.rs").exists());
+
+    let output = framec(&["-l", "rust", spec.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(64));
+    assert!(output.stdout.is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `-A`, `-W` and `-D` set the levels of lint rules, with `-D warnings` failing the
+/// compilation on any warning.
+#[test]
+fn lint_levels() {
+    let spec = SPEC.replace("$On", "$on");
+    let output = framec(&["-l", "python_3"], &spec);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("state_naming"));
+
+    let output = framec(&["-l", "python_3", "-D", "warnings"], &spec);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+
+    let output = framec(
+        &["-l", "python_3", "-D", "warnings", "-A", "state_naming"],
+        &spec,
+    );
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+
+    let output = framec(&["-l", "python_3", "-W", "state_names"], &spec);
+    assert_eq!(output.status.code(), Some(64));
+}
+
+/// Test that `framec compat` fails on breaking changes between two versions of a spec.
+#[test]
+fn compat() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_compat_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = dir.join("old.frm");
+    let new = dir.join("new.frm");
+    std::fs::write(&old, SPEC).unwrap();
+    std::fs::write(&new, SPEC.replace("$On", "$Lit")).unwrap();
+    let compat = |args: &[&str]| {
+        let mut all_args = vec!["compat", old.to_str().unwrap(), new.to_str().unwrap()];
+        all_args.extend(args);
+        framec(&all_args, "")
+    };
+
+    // states of machines that are not persisted may change
+    let output = compat(&[]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("note[removed_state]: State $On was removed."));
+
+    let output = compat(&["--persisted", "--message-format=json"]);
+    assert_eq!(output.status.code(), Some(65));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("\"code\":\"removed_state\""));
+    assert!(stderr.contains("\"file\":\"") && stderr.contains("old.frm\""));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `framec test-skeleton` prints a test module with a test per interface method.
+#[test]
+fn test_skeleton() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_skeleton_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spec = dir.join("lamp.frm");
+    std::fs::write(&spec, SPEC).unwrap();
+
+    let output = framec(&["test-skeleton", spec.to_str().unwrap()], "");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("#[cfg(test)]\nmod tests {\n"));
+    assert!(stdout.contains("    fn toggle() {\n        let mut sm = Lamp::new();\n"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `framec simulate` runs the commands piped to it against a spec or its IR, and
+/// fails at the first failing command.
+#[test]
+fn simulate() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_simulate_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spec = dir.join("lamp.frm");
+    std::fs::write(&spec, SPEC).unwrap();
+
+    let output = framec(
+        &["simulate", spec.to_str().unwrap()],
+        "toggle\nstate On\ntoggle\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "new Lamp\n|toggle|\n  $Off -> $On\n|toggle|\n  $On -> $Off\n"
+    );
+
+    let ir = dir.join("lamp.json");
+    let output = framec(&["-l", "ir", spec.to_str().unwrap()], "");
+    std::fs::write(&ir, output.stdout).unwrap();
+    let output = framec(&["simulate", ir.to_str().unwrap()], "toggle\nstate Off\n");
+    assert_eq!(output.status.code(), Some(exitcode::DATAERR));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Expected state $Off, but in $On. (line 2)"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `framec doc` prints the page of a single machine, and writes the pages of several
+/// machines and an index to `--out-dir`.
+#[test]
+fn doc() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_doc_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let lamp = dir.join("lamp.frm");
+    std::fs::write(&lamp, format!("--- A lamp.\n{}", SPEC)).unwrap();
+    let door = dir.join("door.frm");
+    std::fs::write(&door, SPEC.replace("#Lamp", "#Door")).unwrap();
+
+    let output = framec(&["doc", lamp.to_str().unwrap()], "");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<h1>Lamp</h1>\n<p>A lamp.</p>"));
+
+    let output = framec(&["doc", lamp.to_str().unwrap(), door.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(exitcode::USAGE));
+
+    let out_dir = dir.join("doc");
+    let output = framec(
+        &[
+            "doc",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            lamp.to_str().unwrap(),
+            door.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(output.status.success());
+    let index = std::fs::read_to_string(out_dir.join("index.html")).unwrap();
+    assert!(index.contains("<a href=\"Lamp.html\">Lamp</a></td><td>A lamp.</td>"));
+    assert!(index.contains("<a href=\"Door.html\">Door</a>"));
+    assert!(std::fs::read_to_string(out_dir.join("Door.html"))
+        .unwrap()
+        .contains("<h1>Door</h1>"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test that `framec diff` reports the changes between two versions of a spec as text, JSON or
+/// a diagram.
+#[test]
+fn diff() {
+    let dir = std::env::temp_dir().join(format!("framec_cli_diff_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = dir.join("old.frm");
+    std::fs::write(&old, SPEC).unwrap();
+    let new = dir.join("new.frm");
+    std::fs::write(&new, SPEC.replace("$On", "$Lit")).unwrap();
+    let paths = [old.to_str().unwrap(), new.to_str().unwrap()];
+
+    let output = framec(&["diff", paths[0], paths[1]], "");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "~ state $On renamed to $Lit\n"
+    );
+
+    let output = framec(&["diff", "--format", "json", paths[0], paths[1]], "");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("\"change\": \"renamed_state\""));
+
+    let output = framec(&["diff", "--format", "dot", paths[0], paths[1]], "");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("digraph {"));
+
+    let output = framec(&["diff", paths[0], paths[0]], "");
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
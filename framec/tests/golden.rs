@@ -0,0 +1,196 @@
+//! Golden-output tests for the code generators.
+//!
+//! Each `.frm` fixture in `tests/golden` is compiled to every target language and the result is
+//! compared against the checked-in output in the directory named after the fixture, e.g.
+//! `tests/golden/lamp/rust.rs` for the Rust code generated from `tests/golden/lamp.frm`. When a
+//! backend reports an error, the error is checked in instead, e.g. `tests/golden/lamp/cpp.error`.
+//! A backend that panics fails the test, unless the fixture uses a feature the backend is known
+//! not to support, which is listed in [`UNSUPPORTED`] instead.
+//!
+//! After an intended change to a backend, update the golden outputs with
+//!
+//! ```text
+//! FRAMEC_BLESS=1 cargo test -p framec --test golden
+//! ```
+//!
+//! and review the changes to the generated code along with the change to the backend.
+
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// The target languages and the names of their golden outputs.
+const TARGETS: [(&str, TargetLanguage); 14] = [
+    ("cpp", TargetLanguage::Cpp),
+    ("c_sharp", TargetLanguage::CSharp),
+    ("c_sharp_bob", TargetLanguage::CSharpForBob),
+    ("gdscript", TargetLanguage::GdScript),
+    ("golang", TargetLanguage::GoLang),
+    ("ir", TargetLanguage::Ir),
+    ("java_8", TargetLanguage::Java8),
+    ("javascript", TargetLanguage::JavaScript),
+    ("nusmv", TargetLanguage::NuSmv),
+    ("plantuml", TargetLanguage::PlantUml),
+    ("python_3", TargetLanguage::Python3),
+    ("rust", TargetLanguage::Rust),
+    ("smcat", TargetLanguage::Smcat),
+    ("tla", TargetLanguage::Tla),
+];
+
+/// The fixtures that backends are known to panic on, with the targets and the feature they don't
+/// support. These are checked to still panic, so that they are tested once they are supported.
+const UNSUPPORTED: [(&str, &str, &str); 5] = [
+    (
+        "hierarchy",
+        "cpp",
+        "enter arguments for an enter handler without parameters",
+    ),
+    (
+        "hierarchy",
+        "javascript",
+        "enter arguments for an enter handler without parameters",
+    ),
+    ("stack", "cpp", "transitions to a popped state"),
+    ("stack", "javascript", "transitions to a popped state"),
+    ("stack", "plantuml", "transitions to a popped state"),
+];
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+/// Compile a fixture to a target and return the generated code or the error, along with the
+/// extension of the golden file, or the message of the panic of the backend.
+fn generate(
+    exe: &Exe,
+    file_name: &str,
+    spec: &str,
+    target: TargetLanguage,
+) -> Result<(String, String), String> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        exe.run(&None, Some(file_name), spec.to_string(), Some(target))
+    }));
+    match result {
+        Ok(Ok(code)) => Ok((code, target.file_extension().to_string())),
+        Ok(Err(err)) => Ok((
+            format!("error {}: {}\n", err.code, err.error),
+            "error".to_string(),
+        )),
+        Err(payload) => Err(payload
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default()),
+    }
+}
+
+/// Describe the first difference between the golden output and the generated output.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) => return "line endings differ".to_string(),
+            (e, a) => {
+                return format!(
+                    "line {}:\n  - {}\n  + {}",
+                    line,
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                )
+            }
+        }
+    }
+}
+
+#[test]
+fn golden_outputs() {
+    let bless = std::env::var_os("FRAMEC_BLESS").is_some();
+    let exe = Exe::with_options(CompilerOptions {
+        load_local_config: false,
+        ..CompilerOptions::default()
+    });
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(golden_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().unwrap_or_default() == "frm")
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no fixtures in {:?}", golden_dir());
+
+    // silence the backends that panic, their panics are reported as failures
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        let file_name = fixture.file_name().unwrap().to_str().unwrap();
+        let fixture_name = fixture.file_stem().unwrap().to_str().unwrap();
+        let spec = fs::read_to_string(fixture).unwrap();
+        let output_dir = fixture.with_extension("");
+        if bless {
+            let _ = fs::remove_dir_all(&output_dir);
+            fs::create_dir_all(&output_dir).unwrap();
+        }
+
+        let mut expected_files = Vec::new();
+        for (name, target) in TARGETS {
+            let unsupported = UNSUPPORTED
+                .iter()
+                .find(|(fixture, target, _)| *fixture == fixture_name && *target == name);
+            let generated = generate(&exe, file_name, &spec, target);
+            let (output, extension) = match (generated, unsupported) {
+                (Ok(_), Some((_, _, feature))) => {
+                    failures.push(format!(
+                        "{} compiles {} with {}, remove it from UNSUPPORTED",
+                        name, file_name, feature
+                    ));
+                    continue;
+                }
+                (Ok(generated), None) => generated,
+                (Err(_), Some(_)) => continue,
+                (Err(msg), None) => {
+                    failures.push(format!("{} panicked on {}: {}", name, file_name, msg));
+                    continue;
+                }
+            };
+            let golden_path = output_dir.join(format!("{}.{}", name, extension));
+            expected_files.push(golden_path.clone());
+            if bless {
+                fs::write(&golden_path, output).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&golden_path) {
+                Ok(golden) if golden == output => {}
+                Ok(golden) => failures.push(format!(
+                    "{} differs at {}",
+                    golden_path.display(),
+                    first_difference(&golden, &output)
+                )),
+                Err(_) => failures.push(format!("{} is missing", golden_path.display())),
+            }
+        }
+
+        // golden outputs that are no longer generated, e.g. because a backend stopped failing
+        if let Ok(entries) = fs::read_dir(&output_dir) {
+            for entry in entries {
+                let path = entry.unwrap().path();
+                if !expected_files.contains(&path) {
+                    failures.push(format!("{} is not generated", path.display()));
+                }
+            }
+        }
+    }
+
+    panic::set_hook(default_hook);
+    assert!(
+        failures.is_empty(),
+        "{} golden test(s) failed, run with FRAMEC_BLESS=1 to update changed outputs:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,103 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class Door {
+    public Door() {
+        
+        _state_ = _sClosed_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void open(i32 speed) {
+        Dictionary<String,object> parameters = new Dictionary<String,object>();
+        parameters["speed"] = speed;
+
+        FrameEvent e = new FrameEvent("open",parameters);
+        _state_(e);
+    }
+    
+    public void close() {
+        FrameEvent e = new FrameEvent("close",null);
+        _state_(e);
+    }
+    
+    public void lock(&String code) {
+        Dictionary<String,object> parameters = new Dictionary<String,object>();
+        parameters["code"] = code;
+
+        FrameEvent e = new FrameEvent("lock",parameters);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sClosed_(FrameEvent e) {
+        if (e._message.Equals("open")) {
+            if (((i32) e._parameters["speed"]) > 0) {
+                unlock_do();
+                // push
+                _transition_(_sOpened_);
+            } else {
+                log_do("stuck");
+                _transition_(_sClosed_);
+            }
+            return;
+        }
+        else if (e._message.Equals("lock")) {
+            if (((&String) e._parameters["code"]) == "1234") {
+                _transition_(_sLocked_);
+            } else {
+                log_do("wrong code");
+                return;
+            }
+            return;
+        }
+    }
+    
+    private void _sOpened_(FrameEvent e) {
+        if (e._message.Equals("close")) {
+            _changeState_(_sClosed_);
+            return;
+        }
+    }
+    
+    private void _sLocked_(FrameEvent e) {
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected virtual void unlock_do() { throw new NotImplementedException(); }
+    protected virtual void log_do(&String msg) { throw new NotImplementedException(); }
+    
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private delegate void FrameState(FrameEvent e);
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+    private void _changeState_(FrameState newState) {
+        _state_ = newState;
+    }
+    
+}
+
+/********************
+
+public partial class DoorController : Door {
+
+    protected override void unlock_do() {}
+
+    protected override void log_do(&String msg) {}
+}
+
+********************/
@@ -0,0 +1,89 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class DoorBase : FrameController {
+    public FrameController self;
+    
+    public DoorBase() {
+        self = this;
+        _state_ = _sClosed_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void open(i32 speed) {
+        FrameEventParams parameters = new FrameEventParams();
+        parameters["speed"] = speed;
+
+        FrameEvent e = new FrameEvent("open",parameters);
+        _state_(e);
+    }
+    
+    public void close() {
+        FrameEvent e = new FrameEvent("close",null);
+        _state_(e);
+    }
+    
+    public void lock(&String code) {
+        FrameEventParams parameters = new FrameEventParams();
+        parameters["code"] = code;
+
+        FrameEvent e = new FrameEvent("lock",parameters);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sClosed_(FrameEvent e) {
+        if (e.Msg.EqualsEx("open")) {
+            if (((i32) e.Parameters["speed"]) > 0) {
+                unlock_do();
+                
+                // push
+                _transition_(_sOpened_);
+            } else {
+                log_do("stuck");
+                
+                _transition_(_sClosed_);
+            }
+            return;
+        }
+        else if (e.Msg.EqualsEx("lock")) {
+            if (((&String) e.Parameters["code"]) == "1234") {
+                
+                _transition_(_sLocked_);
+            } else {
+                log_do("wrong code");
+                return;
+            }
+            return;
+        }
+    }
+    
+    private void _sOpened_(FrameEvent e) {
+        if (e.Msg.EqualsEx("close")) {
+            _changeState_(_sClosed_);
+            return;
+        }
+    }
+    
+    private void _sLocked_(FrameEvent e) {
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected virtual void Print_do(object o1) { FrameConsolePrint(o1); }
+    protected virtual void Print_do(object o1, object o2) { FrameConsolePrint(o1, o2); }
+    protected virtual void Print_do(object o1, object o2, object o3) { FrameConsolePrint(o1, o2, o3); }
+    protected virtual void unlock_do() { throw new NotImplementedException(); }
+    protected virtual void log_do(&String msg) { throw new NotImplementedException(); }
+    
+}
+
+/********************
+public partial class Door : DoorBase {
+	protected virtual void unlock_do() { throw new NotImplementedException(); }
+	protected virtual void log_do(&String msg) { throw new NotImplementedException(); }
+}
+********************/
@@ -0,0 +1,112 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Door {
+
+    class StateContext;
+    
+    public:
+    
+    Door() {
+        _state_ = &Door::_sClosed_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    void open(i32 speed) {
+        map<string,void *> params;
+        params["speed"] = (void*) &speed;
+
+        FrameEvent e(string("open"),&params);
+        (this->*_state_)(e);
+    }
+    
+    void close() {
+        FrameEvent e(string("close"),nullptr);
+        (this->*_state_)(e);
+    }
+    
+    void lock(&String code) {
+        map<string,void *> params;
+        params["code"] = (void*) &code;
+
+        FrameEvent e(string("lock"),&params);
+        (this->*_state_)(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private:
+    
+    void _sClosed_(FrameEvent& e) {
+        if (e._message == "open") {
+            if ((*(i32*) e._parameters["speed"]) > 0) {
+                unlock_do();
+                
+                // push
+                _transition_(&Door::_sOpened_);
+            } else {
+                log_do("stuck");
+                
+                _transition_(&Door::_sClosed_);
+            }
+            return;
+        }
+        else if (e._message == "lock") {
+            if ((*(&String*) e._parameters["code"]) == "1234") {
+                
+                _transition_(&Door::_sLocked_);
+            } else {
+                log_do("wrong code");
+                return;
+            }
+            return;
+        }
+    }
+    
+    void _sOpened_(FrameEvent& e) {
+        if (e._message == "close") {
+            _changeState_(&Door::_sClosed_);
+            return;
+        }
+    }
+    
+    void _sLocked_(FrameEvent& e) {
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected:
+    
+    virtual void unlock_do() {}
+    virtual void log_do(&String msg) {}
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    public:
+    virtual ~Door() {};
+    
+    
+    private:
+    
+    typedef void (Door::*FrameState)(FrameEvent& e);
+    typedef map<string,void*> FrameMap;
+    
+    
+    FrameState _state_;
+    StateContext* _pStateContext_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent("<",nullptr);
+        _state_ = newState;
+        if (_pStateContext_ && !_pStateContext_->isOnStateStack()) delete _pStateContext_;
+        FrameEvent enterEvent(">",nullptr);
+        
+        (this->*_state_)(enterEvent);
+    }
+    
+    private void _changeState_(FrameState newState) {
+        _state_ = newState;
+    }
+}
@@ -0,0 +1,85 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class_name Door
+
+func _init():
+    self._state_ = funcref(self,"_sClosed_")
+
+
+# ===================== Interface Block =================== #
+
+func open(speed):
+    var params = {}
+    params["speed"] = speed;
+
+    var e = FrameEvent.new("open",params)
+    self._state_.call_func(e)
+
+func close():
+    var e = FrameEvent.new("close",null)
+    self._state_.call_func(e)
+
+func lock(code):
+    var params = {}
+    params["code"] = code;
+
+    var e = FrameEvent.new("lock",params)
+    self._state_.call_func(e)
+
+
+# ===================== Machine Block =================== #
+
+func _sClosed_(e):
+    if e._message == "open":
+        if  e._parameters["speed"] > 0:
+            self.unlock_do()
+            # push
+            self._transition_(funcref(self,"_sOpened_"))
+        else:
+            self.log_do("stuck")
+            self._transition_(funcref(self,"_sClosed_"))
+        
+        return
+    
+    elif e._message == "lock":
+        if (e._parameters["code"] == "1234"):
+            self._transition_(funcref(self,"_sLocked_"))
+        else:
+            self.log_do("wrong code")
+            return
+        
+        return
+    
+func _sOpened_(e):
+    if e._message == "close":
+        self._changeState_(funcref(self,"_sClosed_"))
+        return
+    
+func _sLocked_(e):
+    pass
+
+
+# =============== Machinery and Mechanisms ============== #
+
+var _state_ = null
+
+func _transition_(newState):
+    var exitEvent = FrameEvent.new("<",null)
+    self._state_.call_func(exitEvent)
+    self._state_ = newState
+    var enterEvent = FrameEvent.new(">",null)
+    self._state_.call_func(enterEvent)
+
+
+func _changeState_(newState):
+    self._state_ = newState
+
+
+# ===================== Actions Block =================== #
+
+func unlock_do():
+    assert(true,"Error - unimplemented action")
+
+func log_do(msg):
+    assert(true,"Error - unimplemented action")
@@ -0,0 +1,186 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+
+func NewDoor() Door {
+    m := &doorStruct{}
+    
+    // Validate interfaces
+    var _ Door = m
+    var _ Door_actions = m
+    m._compartment_ = NewDoorCompartment(DoorState_Closed)
+    
+    // Initialize domain
+    
+    // Send system start event
+    e := framelang.FrameEvent{Msg:">"}
+    m._mux_(&e)
+    return m
+}
+
+
+type DoorState uint
+
+const (
+    DoorState_Closed DoorState = iota
+    DoorState_Opened
+    DoorState_Locked
+)
+
+type Door interface {
+    Open(speed i32) 
+    Close() 
+    Lock(code &String) 
+}
+
+type Door_actions interface {
+    unlock() 
+    log(msg &String) 
+}
+
+
+type doorStruct struct {
+    _compartment_ *DoorCompartment
+    _nextCompartment_ *DoorCompartment
+}
+
+//===================== Interface Block ===================//
+
+func (m *doorStruct) Open(speed i32)  {
+    params := make(map[string]interface{})
+    params["speed"] = speed
+    e := framelang.FrameEvent{Msg:"open", Params:params}
+    m._mux_(&e)
+}
+
+func (m *doorStruct) Close()  {
+    e := framelang.FrameEvent{Msg:"close"}
+    m._mux_(&e)
+}
+
+func (m *doorStruct) Lock(code &String)  {
+    params := make(map[string]interface{})
+    params["code"] = code
+    e := framelang.FrameEvent{Msg:"lock", Params:params}
+    m._mux_(&e)
+}
+
+//====================== Multiplexer ====================//
+
+func (m *doorStruct) _mux_(e *framelang.FrameEvent) {
+    switch m._compartment_.State {
+    case DoorState_Closed:
+        m._DoorState_Closed_(e)
+    case DoorState_Opened:
+        m._DoorState_Opened_(e)
+    case DoorState_Locked:
+        m._DoorState_Locked_(e)
+    }
+    
+    if m._nextCompartment_ != nil {
+        nextCompartment := m._nextCompartment_
+        m._nextCompartment_ = nil
+        if nextCompartment._forwardEvent_ != nil && 
+           nextCompartment._forwardEvent_.Msg == ">" {
+            m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+            m._compartment_ = nextCompartment
+            m._mux_(nextCompartment._forwardEvent_)
+        } else {
+            m._do_transition_(nextCompartment)
+            if nextCompartment._forwardEvent_ != nil {
+                m._mux_(nextCompartment._forwardEvent_)
+            }
+        }
+        nextCompartment._forwardEvent_ = nil
+    }
+}
+
+//===================== Machine Block ===================//
+
+func (m *doorStruct) _DoorState_Closed_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case "open":
+        if e.Params["speed"].(i32) > 0 {
+            m.unlock()
+            // push
+            compartment := NewDoorCompartment(DoorState_Opened)
+            m._transition_(compartment)
+        } else {
+            m.log("stuck")
+            compartment := NewDoorCompartment(DoorState_Closed)
+            m._transition_(compartment)
+        }
+        return
+    case "lock":
+        if e.Params["code"].(&String) == "1234" {
+            compartment := NewDoorCompartment(DoorState_Locked)
+            m._transition_(compartment)
+        } else {
+            m.log("wrong code")
+            return
+        }
+        return
+    }
+}
+
+func (m *doorStruct) _DoorState_Opened_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case "close":
+        compartment := NewDoorCompartment(DoorState_Closed)
+        m._changeState_(compartment)
+        return
+    }
+}
+
+func (m *doorStruct) _DoorState_Locked_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    }
+}
+
+//=============== Machinery and Mechanisms ==============//
+
+func (m *doorStruct) _transition_(compartment *DoorCompartment) {
+    m._nextCompartment_ = compartment
+}
+
+func (m *doorStruct) _do_transition_(nextCompartment *DoorCompartment) {
+    m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+    m._compartment_ = nextCompartment
+    m._mux_(&framelang.FrameEvent{Msg: ">", Params: m._compartment_.EnterArgs, Ret: nil})
+}
+
+func (m *doorStruct) _changeState_(compartment *DoorCompartment) {
+    m._compartment_ = compartment
+}
+
+//===================== Actions Block ===================//
+
+
+/********************************************************
+
+// Unimplemented Actions
+
+func (m *doorStruct) unlock()  {}
+func (m *doorStruct) log(msg &String)  {}
+
+********************************************************/
+
+//=============== Compartment ==============//
+
+type DoorCompartment struct {
+    State DoorState
+    StateArgs map[string]interface{}
+    StateVars map[string]interface{}
+    EnterArgs map[string]interface{}
+    ExitArgs map[string]interface{}
+    _forwardEvent_ *framelang.FrameEvent
+}
+
+func NewDoorCompartment(state DoorState) *DoorCompartment {
+    c := &DoorCompartment{State: state}
+    c.StateArgs = make(map[string]interface{})
+    c.StateVars = make(map[string]interface{})
+    c.EnterArgs = make(map[string]interface{})
+    c.ExitArgs = make(map[string]interface{})
+    return c
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Door {
+    Door() {
+        
+        _state_ = this::_sClosed_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void open(i32 speed) {
+        HashMap<String,Object> parameters = new HashMap<String,Object>();
+        parameters["speed"] = speed;
+
+        FrameEvent e = new FrameEvent("open",parameters);
+        _state_.call(e);
+    }
+    
+    public void close() {
+        FrameEvent e = new FrameEvent("close",null);
+        _state_.call(e);
+    }
+    
+    public void lock(&String code) {
+        HashMap<String,Object> parameters = new HashMap<String,Object>();
+        parameters["code"] = code;
+
+        FrameEvent e = new FrameEvent("lock",parameters);
+        _state_.call(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sClosed_(FrameEvent e) {
+        if (e._message == "open") {
+            if (((i32) e._parameters.get("speed")) > 0) {
+                unlock_do();
+                // push
+                _transition_(this::_sOpened_);
+            } else {
+                log_do("stuck");
+                _transition_(this::_sClosed_);
+            }
+            return;
+        }
+        else if (e._message == "lock") {
+            if (((&String) e._parameters.get("code")) == "1234") {
+                _transition_(this::_sLocked_);
+            } else {
+                log_do("wrong code");
+                return;
+            }
+            return;
+        }
+    }
+    
+    private void _sOpened_(FrameEvent e) {
+        if (e._message == "close") {
+            _changeState_(_sClosed_);
+            return;
+        }
+    }
+    
+    private void _sLocked_(FrameEvent e) {
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected void unlock_do() { throw new UnsupportedOperationException(); }
+    protected void log_do(&String msg) { throw new UnsupportedOperationException(); }
+    
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_.call(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_.call(enterEvent);
+    }
+    
+    private void _changeState_(newState) {
+        _state_ = newState;
+    }
+    
+}
+
+/********************
+
+public class DoorController extends Door {
+
+    protected void unlock_do() {}
+
+    protected void log_do(&String msg) {}
+}
+
+********************/
@@ -0,0 +1,92 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+let Door = function () {
+    
+    let that = {};
+    that.constructor = Door;
+    
+    //===================== Interface Block ===================//
+    
+    that.open = function (speed) {
+        let e = FrameEvent("open",{"speed":speed});
+        _state_(e);
+    }
+    
+    that.close = function () {
+        let e = FrameEvent("close",null);
+        _state_(e);
+    }
+    
+    that.lock = function (code) {
+        let e = FrameEvent("lock",{"code":code});
+        _state_(e);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    let _sClosed_ = function (e) {
+        if (e._message == "open") {
+            if ((e._parameters["speed"]) > 0) {
+                that.unlock_do();
+                // push
+                _transition_(_sOpened_);
+            } else {
+                that.log_do("stuck");
+                _transition_(_sClosed_);
+            }
+            return;
+        }
+        else if (e._message == "lock") {
+            if (((e._parameters["code"]) == "1234")) {
+                _transition_(_sLocked_);
+            } else {
+                that.log_do("wrong code");
+                return;
+            }
+            return;
+        }
+    }
+    
+    let _sOpened_ = function (e) {
+        if (e._message == "close") {
+            _changeState_(_sClosed_);
+            return;
+        }
+    }
+    
+    let _sLocked_ = function (e) {
+    }
+    
+    //===================== Actions Block ===================//
+    
+    that.unlock_do = function () { throw new Error('Action not implemented.'); }
+    that.log_do = function (msg) { throw new Error('Action not implemented.'); }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    let _state_ = _sClosed_;
+    
+    let _transition_ = function(newState) {
+        let exitEvent = FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        let enterEvent = FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+    let _changeState_ = function(newState) {
+        _state_ = newState;
+    }
+    
+    return that; 
+};
+
+/********************
+let DoorController = function () {
+	let that = Door.call(this);
+	that.unlock_do = function () {}
+	that.log_do = function (msg) {}
+	return that;
+};
+********************/
@@ -0,0 +1,96 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Door:
+    
+    def __init__(self):
+        self._state_ = self._sClosed_
+    
+    # ===================== Interface Block =================== #
+    
+    def open(self,speed):
+        parameters = {}
+        parameters["speed"] = speed;
+
+        e = FrameEvent("open",parameters)
+        self._state_(e)
+    
+    def close(self):
+        e = FrameEvent("close",None)
+        self._state_(e)
+    
+    def lock(self,code):
+        parameters = {}
+        parameters["code"] = code;
+
+        e = FrameEvent("lock",parameters)
+        self._state_(e)
+    
+    # ===================== Machine Block =================== #
+    
+    def _sClosed_(self, e):
+        if e._message == "open":
+            if  e.params["speed"] > 0:
+                self.unlock_do()
+                # push
+                self._transition_(self._sOpened_)
+            else:
+                self.log_do("stuck")
+                self._transition_(self._sClosed_)
+            
+            return
+        
+        elif e._message == "lock":
+            if (e.params["code"] == "1234"):
+                self._transition_(self._sLocked_)
+             else:
+                self.log_do("wrong code")
+                return
+            
+            return
+        
+    def _sOpened_(self, e):
+        if e._message == "close":
+            self._changeState_(_sClosed_);
+            return
+        
+    def _sLocked_(self, e):
+        pass
+        
+    
+    # ===================== Actions Block =================== #
+    
+    def unlock_do(self):
+        raise NotImplementedError
+    
+    def log_do(self,msg):
+        raise NotImplementedError
+    
+    
+    # =============== Machinery and Mechanisms ============== #
+    
+    def _transition_(self, newState):
+        exitEvent = FrameEvent("<",None)
+        self._state_(exitEvent)
+        self._state_ = newState
+        enterEvent = FrameEvent(">",None)
+        self._state_(enterEvent)
+    
+    
+    def _changeState_(newState):
+        self._state_ = newState
+    
+    
+
+# ********************
+
+#class DoorController(Door):
+
+    #def unlock_do(self):
+        #pass
+
+    #def log_do(self,msg):
+        #pass
+
+# ********************
+
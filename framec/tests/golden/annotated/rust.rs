@@ -0,0 +1,295 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DoorState),
+    Exit(DoorState),
+    Open,
+    Close,
+    Lock,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DoorState::Closed) => write!(f, "Closed:>"),
+            FrameMessage::Enter(DoorState::Opened) => write!(f, "Opened:>"),
+            FrameMessage::Enter(DoorState::Locked) => write!(f, "Locked:>"),
+            FrameMessage::Exit(DoorState::Closed) => write!(f, "Closed:<"),
+            FrameMessage::Exit(DoorState::Opened) => write!(f, "Opened:<"),
+            FrameMessage::Exit(DoorState::Locked) => write!(f, "Locked:<"),
+            FrameMessage::Open => write!(f, "open"),
+            FrameMessage::Close => write!(f, "close"),
+            FrameMessage::Lock => write!(f, "lock"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OpenArgs {
+    speed: i32,
+}
+
+#[allow(dead_code)]
+struct LockArgs {
+    code: &String,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Open(OpenArgs),
+    Lock(LockArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn open_args(&self) -> &OpenArgs {
+        match self {
+            FrameEventArgs::Open(args) => args,
+            _ => panic!("Failed conversion to OpenArgs"),
+        }
+    }
+    fn lock_args(&self) -> &LockArgs {
+        match self {
+            FrameEventArgs::Lock(args) => args,
+            _ => panic!("Failed conversion to LockArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DoorState {
+    Closed,
+    Opened,
+    Locked,
+}
+
+#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait DoorActions { 
+    fn unlock(&self);
+    fn log(&self, msg: &String);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Door {
+    state: DoorState,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Door {
+    
+    pub fn new() -> Self {
+        let mut machine = Door {
+            state: DoorState::Closed,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn open(&mut self, speed: i32) {
+        let frame_args = FrameEventArgs::Open(OpenArgs { speed, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Open, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn close(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Close, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn lock(&mut self, code: &String) {
+        let frame_args = FrameEventArgs::Lock(LockArgs { code, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Lock, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn closed_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Open => {
+                if (frame_event.arguments.as_ref().borrow().open_args().speed) > 0 {
+                    self.unlock();
+                    // Start transition
+                    // push
+                    self.transition(DoorState::Opened);
+                    return;
+                } else {
+                    self.log(String::from("stuck"));
+                    // Start transition
+                    self.transition(DoorState::Closed);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Lock => {
+                if (frame_event.arguments.as_ref().borrow().lock_args().code).eq("1234") {
+                    // Start transition
+                    self.transition(DoorState::Locked);
+                    return;
+                } else {
+                    self.log(String::from("wrong code"));
+                    
+                    return;
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn opened_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Close => {
+                // Start change state
+                self.change_state(DoorState::Closed);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn locked_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            DoorState::Closed => self.closed_handler(frame_event.clone()),
+            DoorState::Opened => self.opened_handler(frame_event.clone()),
+            DoorState::Locked => self.locked_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: DoorState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, new_state: DoorState) {
+        self.state = new_state;
+    }
+    
+} // end system controller
+
+impl Default for Door {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl DoorActions for Door { 
+    fn unlock(&self) {}
+    fn log(&self, msg: &String) {}
+}
\ No newline at end of file
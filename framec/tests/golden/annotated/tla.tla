@@ -0,0 +1,43 @@
+\* emitted from framec_v0.8.0
+---- MODULE annotated ----
+\* The states of the machine Door and the events sent to it.
+
+VARIABLES state, event
+
+vars == <<state, event>>
+
+States == {"Closed", "Opened", "Locked"}
+
+Events == {"open", "close", "lock"}
+
+TypeOK ==
+    /\ state \in States
+    /\ event \in Events \cup {"none"}
+
+Init ==
+    /\ state = "Closed"
+    /\ event = "none"
+
+Send_open ==
+    /\ event' = "open"
+    /\ CASE state = "Closed" -> state' \in {"Opened", "Closed"} \* speed > 0; else
+         [] OTHER -> UNCHANGED state
+
+Send_close ==
+    /\ event' = "close"
+    /\ CASE state = "Opened" -> state' = "Closed"
+         [] OTHER -> UNCHANGED state
+
+Send_lock ==
+    /\ event' = "lock"
+    /\ CASE state = "Closed" -> state' \in {"Locked", "Closed"} \* code == "1234"
+         [] OTHER -> UNCHANGED state
+
+Next ==
+    \/ Send_open
+    \/ Send_close
+    \/ Send_lock
+
+Spec == Init /\ [][Next]_vars
+
+====
@@ -0,0 +1 @@
+Invalid number of arguments for "Done:>" event handler.
\ No newline at end of file
@@ -0,0 +1 @@
+The "Done:>" event handler was passed arguments, but it does not accept any.
\ No newline at end of file
@@ -0,0 +1,43 @@
+\* emitted from framec_v0.8.0
+---- MODULE hierarchy ----
+\* The states of the machine Hierarchy and the events sent to it.
+
+VARIABLES state, event
+
+vars == <<state, event>>
+
+States == {"Init", "S", "S1", "S2", "Done"}
+
+Events == {"a", "b", "c"}
+
+TypeOK ==
+    /\ state \in States
+    /\ event \in Events \cup {"none"}
+
+Init ==
+    /\ state = "S1"
+    /\ event = "none"
+
+Send_a ==
+    /\ event' = "a"
+    /\ CASE state = "S" -> state' = "S2"
+         [] state = "S1" -> state' = "S2"
+         [] state = "S2" -> state' = "Done"
+         [] OTHER -> UNCHANGED state
+
+Send_b ==
+    /\ event' = "b"
+    /\ UNCHANGED state
+
+Send_c ==
+    /\ event' = "c"
+    /\ UNCHANGED state
+
+Next ==
+    \/ Send_a
+    \/ Send_b
+    \/ Send_c
+
+Spec == Init /\ [][Next]_vars
+
+====
@@ -0,0 +1,118 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class Lamp {
+    public Lamp() {
+        
+        _state_ = _sOff_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void turnOn() {
+        FrameEvent e = new FrameEvent("turnOn",null);
+        _state_(e);
+    }
+    
+    public void turnOff() {
+        FrameEvent e = new FrameEvent("turnOff",null);
+        _state_(e);
+    }
+    
+    public string getColor() {
+        FrameEvent e = new FrameEvent("getColor",null);
+        _state_(e);
+        return (string) e._return;
+    }
+    
+    public void setColor(string color) {
+        Dictionary<String,object> parameters = new Dictionary<String,object>();
+        parameters["color"] = color;
+
+        FrameEvent e = new FrameEvent("setColor",parameters);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sOff_(FrameEvent e) {
+        if (e._message.Equals(">")) {
+            closeSwitch_do();
+            return;
+        }
+        else if (e._message.Equals("turnOn")) {
+            // turn on
+            _transition_(_sOn_);
+            return;
+        }
+        else if (e._message.Equals("getColor")) {
+            e._return = this.color;
+            return;
+            
+        }
+    }
+    
+    private void _sOn_(FrameEvent e) {
+        if (e._message.Equals(">")) {
+            openSwitch_do();
+            return;
+        }
+        else if (e._message.Equals("<")) {
+            log_do("leaving On");
+            return;
+        }
+        else if (e._message.Equals("turnOff")) {
+            // turn off
+            _transition_(_sOff_);
+            return;
+        }
+        else if (e._message.Equals("getColor")) {
+            e._return = this.color;
+            return;
+            
+        }
+        else if (e._message.Equals("setColor")) {
+            this.color = ((string) e._parameters["color"]);
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected virtual void openSwitch_do() { throw new NotImplementedException(); }
+    protected virtual void closeSwitch_do() { throw new NotImplementedException(); }
+    protected virtual void log_do(string msg) { throw new NotImplementedException(); }
+    
+    
+    //===================== Domain Block ===================//
+    
+    string color = "white";
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private delegate void FrameState(FrameEvent e);
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+}
+
+/********************
+
+public partial class LampController : Lamp {
+
+    protected override void openSwitch_do() {}
+
+    protected override void closeSwitch_do() {}
+
+    protected override void log_do(string msg) {}
+}
+
+********************/
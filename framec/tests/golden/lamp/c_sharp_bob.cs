@@ -0,0 +1,106 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class LampBase : FrameController {
+    public FrameController self;
+    
+    public LampBase() {
+        self = this;
+        _state_ = _sOff_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void turnOn() {
+        FrameEvent e = new FrameEvent("turnOn",null);
+        _state_(e);
+    }
+    
+    public void turnOff() {
+        FrameEvent e = new FrameEvent("turnOff",null);
+        _state_(e);
+    }
+    
+    public string getColor() {
+        FrameEvent e = new FrameEvent("getColor",null);
+        _state_(e);
+        return (string) e.Return;
+    }
+    
+    public void setColor(string color) {
+        FrameEventParams parameters = new FrameEventParams();
+        parameters["color"] = color;
+
+        FrameEvent e = new FrameEvent("setColor",parameters);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sOff_(FrameEvent e) {
+        if (e.Msg.EqualsEx(">")) {
+            closeSwitch_do();
+            return;
+        }
+        else if (e.Msg.EqualsEx("turnOn")) {
+            
+            // turn on
+            _transition_(_sOn_);
+            return;
+        }
+        else if (e.Msg.EqualsEx("getColor")) {
+            e.Return = this.color
+            return;
+            
+        }
+    }
+    
+    private void _sOn_(FrameEvent e) {
+        if (e.Msg.EqualsEx(">")) {
+            openSwitch_do();
+            return;
+        }
+        else if (e.Msg.EqualsEx("<")) {
+            log_do("leaving On");
+            return;
+        }
+        else if (e.Msg.EqualsEx("turnOff")) {
+            
+            // turn off
+            _transition_(_sOff_);
+            return;
+        }
+        else if (e.Msg.EqualsEx("getColor")) {
+            e.Return = this.color
+            return;
+            
+        }
+        else if (e.Msg.EqualsEx("setColor")) {
+            this.color = ((string) e.Parameters["color"]);
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected virtual void Print_do(object o1) { FrameConsolePrint(o1); }
+    protected virtual void Print_do(object o1, object o2) { FrameConsolePrint(o1, o2); }
+    protected virtual void Print_do(object o1, object o2, object o3) { FrameConsolePrint(o1, o2, o3); }
+    protected virtual void openSwitch_do() { throw new NotImplementedException(); }
+    protected virtual void closeSwitch_do() { throw new NotImplementedException(); }
+    protected virtual void log_do(string msg) { throw new NotImplementedException(); }
+    
+    
+    //===================== Domain Block ===================//
+    
+    string color = "white";
+}
+
+/********************
+public partial class Lamp : LampBase {
+	protected virtual void openSwitch_do() { throw new NotImplementedException(); }
+	protected virtual void closeSwitch_do() { throw new NotImplementedException(); }
+	protected virtual void log_do(string msg) { throw new NotImplementedException(); }
+}
+********************/
@@ -0,0 +1,125 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Lamp {
+
+    class StateContext;
+    
+    public:
+    
+    Lamp() {
+        _state_ = &Lamp::_sOff_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    void turnOn() {
+        FrameEvent e(string("turnOn"),nullptr);
+        (this->*_state_)(e);
+    }
+    
+    void turnOff() {
+        FrameEvent e(string("turnOff"),nullptr);
+        (this->*_state_)(e);
+    }
+    
+    string getColor() {
+        FrameEvent e(string("getColor"),nullptr);
+        (this->*_state_)(e);
+        return (string) e.ret;
+    }
+    
+    void setColor(string color) {
+        map<string,void *> params;
+        params["color"] = (void*) &color;
+
+        FrameEvent e(string("setColor"),&params);
+        (this->*_state_)(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private:
+    
+    void _sOff_(FrameEvent& e) {
+        if (e._message == ">") {
+            closeSwitch_do();
+            return;
+        }
+        else if (e._message == "turnOn") {
+            
+            // turn on
+            _transition_(&Lamp::_sOn_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = (void*) new string(this->color);
+            return;
+            
+        }
+    }
+    
+    void _sOn_(FrameEvent& e) {
+        if (e._message == ">") {
+            openSwitch_do();
+            return;
+        }
+        else if (e._message == "<") {
+            log_do("leaving On");
+            return;
+        }
+        else if (e._message == "turnOff") {
+            
+            // turn off
+            _transition_(&Lamp::_sOff_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = (void*) new string(this->color);
+            return;
+            
+        }
+        else if (e._message == "setColor") {
+            this->color = (*(string*) e._parameters["color"]);
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected:
+    
+    virtual void openSwitch_do() {}
+    virtual void closeSwitch_do() {}
+    virtual void log_do(string msg) {}
+    
+    //===================== Domain Block ===================//
+    
+    
+    string color = "white";
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    public:
+    virtual ~Lamp() {};
+    
+    
+    private:
+    
+    typedef void (Lamp::*FrameState)(FrameEvent& e);
+    typedef map<string,void*> FrameMap;
+    
+    
+    FrameState _state_;
+    StateContext* _pStateContext_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent("<",nullptr);
+        _state_ = newState;
+        if (_pStateContext_ && !_pStateContext_->isOnStateStack()) delete _pStateContext_;
+        FrameEvent enterEvent(">",nullptr);
+        
+        (this->*_state_)(enterEvent);
+    }
+}
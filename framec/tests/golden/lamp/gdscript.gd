@@ -0,0 +1,100 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class_name Lamp
+
+func _init():
+    self._state_ = funcref(self,"_sOff_")
+
+
+# ===================== Interface Block =================== #
+
+func turnOn():
+    var e = FrameEvent.new("turnOn",null)
+    self._state_.call_func(e)
+
+func turnOff():
+    var e = FrameEvent.new("turnOff",null)
+    self._state_.call_func(e)
+
+func getColor():
+    var e = FrameEvent.new("getColor",null)
+    self._state_.call_func(e)
+    return e._return
+
+func setColor(color):
+    var params = {}
+    params["color"] = color;
+
+    var e = FrameEvent.new("setColor",params)
+    self._state_.call_func(e)
+
+
+# ===================== Machine Block =================== #
+
+func _sOff_(e):
+    if e._message == ">":
+        self.closeSwitch_do()
+        return
+    
+    elif e._message == "turnOn":
+        # turn on
+        self._transition_(funcref(self,"_sOn_"))
+        return
+    
+    elif e._message == "getColor":
+        e._return = self.color
+        return
+        
+    
+func _sOn_(e):
+    if e._message == ">":
+        self.openSwitch_do()
+        return
+    
+    elif e._message == "<":
+        self.log_do("leaving On")
+        return
+    
+    elif e._message == "turnOff":
+        # turn off
+        self._transition_(funcref(self,"_sOff_"))
+        return
+    
+    elif e._message == "getColor":
+        e._return = self.color
+        return
+        
+    
+    elif e._message == "setColor":
+        self.color = e._parameters["color"]
+        return
+    
+
+
+# ===================== Domain Block =================== #
+
+var color:string = "white"
+
+# =============== Machinery and Mechanisms ============== #
+
+var _state_ = null
+
+func _transition_(newState):
+    var exitEvent = FrameEvent.new("<",null)
+    self._state_.call_func(exitEvent)
+    self._state_ = newState
+    var enterEvent = FrameEvent.new(">",null)
+    self._state_.call_func(enterEvent)
+
+
+# ===================== Actions Block =================== #
+
+func openSwitch_do():
+    assert(true,"Error - unimplemented action")
+
+func closeSwitch_do():
+    assert(true,"Error - unimplemented action")
+
+func log_do(msg):
+    assert(true,"Error - unimplemented action")
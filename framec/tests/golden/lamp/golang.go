@@ -0,0 +1,188 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+
+func NewLamp() Lamp {
+    m := &lampStruct{}
+    
+    // Validate interfaces
+    var _ Lamp = m
+    var _ Lamp_actions = m
+    m._compartment_ = NewLampCompartment(LampState_Off)
+    
+    // Initialize domain
+    m.color = "white"
+    
+    // Send system start event
+    e := framelang.FrameEvent{Msg:">"}
+    m._mux_(&e)
+    return m
+}
+
+
+type LampState uint
+
+const (
+    LampState_Off LampState = iota
+    LampState_On
+)
+
+type Lamp interface {
+    TurnOn() 
+    TurnOff() 
+    GetColor() string
+    SetColor(color string) 
+}
+
+type Lamp_actions interface {
+    openSwitch() 
+    closeSwitch() 
+    log(msg string) 
+}
+
+
+type lampStruct struct {
+    _compartment_ *LampCompartment
+    _nextCompartment_ *LampCompartment
+    color string
+}
+
+//===================== Interface Block ===================//
+
+func (m *lampStruct) TurnOn()  {
+    e := framelang.FrameEvent{Msg:"turnOn"}
+    m._mux_(&e)
+}
+
+func (m *lampStruct) TurnOff()  {
+    e := framelang.FrameEvent{Msg:"turnOff"}
+    m._mux_(&e)
+}
+
+func (m *lampStruct) GetColor() string {
+    e := framelang.FrameEvent{Msg:"getColor"}
+    m._mux_(&e)
+    return  e.Ret.(string)
+}
+
+func (m *lampStruct) SetColor(color string)  {
+    params := make(map[string]interface{})
+    params["color"] = color
+    e := framelang.FrameEvent{Msg:"setColor", Params:params}
+    m._mux_(&e)
+}
+
+//====================== Multiplexer ====================//
+
+func (m *lampStruct) _mux_(e *framelang.FrameEvent) {
+    switch m._compartment_.State {
+    case LampState_Off:
+        m._LampState_Off_(e)
+    case LampState_On:
+        m._LampState_On_(e)
+    }
+    
+    if m._nextCompartment_ != nil {
+        nextCompartment := m._nextCompartment_
+        m._nextCompartment_ = nil
+        if nextCompartment._forwardEvent_ != nil && 
+           nextCompartment._forwardEvent_.Msg == ">" {
+            m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+            m._compartment_ = nextCompartment
+            m._mux_(nextCompartment._forwardEvent_)
+        } else {
+            m._do_transition_(nextCompartment)
+            if nextCompartment._forwardEvent_ != nil {
+                m._mux_(nextCompartment._forwardEvent_)
+            }
+        }
+        nextCompartment._forwardEvent_ = nil
+    }
+}
+
+//===================== Machine Block ===================//
+
+func (m *lampStruct) _LampState_Off_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case ">":
+        m.closeSwitch()
+        return
+    case "turnOn":
+        // turn on
+        compartment := NewLampCompartment(LampState_On)
+        m._transition_(compartment)
+        return
+    case "getColor":
+        e.Ret = m.color
+        return
+        
+    }
+}
+
+func (m *lampStruct) _LampState_On_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case ">":
+        m.openSwitch()
+        return
+    case "<":
+        m.log("leaving On")
+        return
+    case "turnOff":
+        // turn off
+        compartment := NewLampCompartment(LampState_Off)
+        m._transition_(compartment)
+        return
+    case "getColor":
+        e.Ret = m.color
+        return
+        
+    case "setColor":
+        m.color = e.Params["color"].(string)
+        return
+    }
+}
+
+//=============== Machinery and Mechanisms ==============//
+
+func (m *lampStruct) _transition_(compartment *LampCompartment) {
+    m._nextCompartment_ = compartment
+}
+
+func (m *lampStruct) _do_transition_(nextCompartment *LampCompartment) {
+    m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+    m._compartment_ = nextCompartment
+    m._mux_(&framelang.FrameEvent{Msg: ">", Params: m._compartment_.EnterArgs, Ret: nil})
+}
+
+//===================== Actions Block ===================//
+
+
+/********************************************************
+
+// Unimplemented Actions
+
+func (m *lampStruct) openSwitch()  {}
+func (m *lampStruct) closeSwitch()  {}
+func (m *lampStruct) log(msg string)  {}
+
+********************************************************/
+
+//=============== Compartment ==============//
+
+type LampCompartment struct {
+    State LampState
+    StateArgs map[string]interface{}
+    StateVars map[string]interface{}
+    EnterArgs map[string]interface{}
+    ExitArgs map[string]interface{}
+    _forwardEvent_ *framelang.FrameEvent
+}
+
+func NewLampCompartment(state LampState) *LampCompartment {
+    c := &LampCompartment{State: state}
+    c.StateArgs = make(map[string]interface{})
+    c.StateVars = make(map[string]interface{})
+    c.EnterArgs = make(map[string]interface{})
+    c.ExitArgs = make(map[string]interface{})
+    return c
+}
\ No newline at end of file
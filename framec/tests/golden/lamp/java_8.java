@@ -0,0 +1,117 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Lamp {
+    Lamp() {
+        
+        _state_ = this::_sOff_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void turnOn() {
+        FrameEvent e = new FrameEvent("turnOn",null);
+        _state_.call(e);
+    }
+    
+    public void turnOff() {
+        FrameEvent e = new FrameEvent("turnOff",null);
+        _state_.call(e);
+    }
+    
+    public string getColor() {
+        FrameEvent e = new FrameEvent("getColor",null);
+        _state_.call(e);
+        return (string) e._return;
+    }
+    
+    public void setColor(string color) {
+        HashMap<String,Object> parameters = new HashMap<String,Object>();
+        parameters["color"] = color;
+
+        FrameEvent e = new FrameEvent("setColor",parameters);
+        _state_.call(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sOff_(FrameEvent e) {
+        if (e._message == ">") {
+            closeSwitch_do();
+            return;
+        }
+        else if (e._message == "turnOn") {
+            // turn on
+            _transition_(this::_sOn_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = this.color;
+            return;
+            
+        }
+    }
+    
+    private void _sOn_(FrameEvent e) {
+        if (e._message == ">") {
+            openSwitch_do();
+            return;
+        }
+        else if (e._message == "<") {
+            log_do("leaving On");
+            return;
+        }
+        else if (e._message == "turnOff") {
+            // turn off
+            _transition_(this::_sOff_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = this.color;
+            return;
+            
+        }
+        else if (e._message == "setColor") {
+            this.color = ((string) e._parameters.get("color"));
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected void openSwitch_do() { throw new UnsupportedOperationException(); }
+    protected void closeSwitch_do() { throw new UnsupportedOperationException(); }
+    protected void log_do(string msg) { throw new UnsupportedOperationException(); }
+    
+    
+    //===================== Domain Block ===================//
+    
+    string color = "white";
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_.call(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_.call(enterEvent);
+    }
+    
+}
+
+/********************
+
+public class LampController extends Lamp {
+
+    protected void openSwitch_do() {}
+
+    protected void closeSwitch_do() {}
+
+    protected void log_do(string msg) {}
+}
+
+********************/
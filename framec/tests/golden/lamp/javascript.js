@@ -0,0 +1,109 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+let Lamp = function () {
+    
+    let that = {};
+    that.constructor = Lamp;
+    
+    //===================== Interface Block ===================//
+    
+    that.turnOn = function () {
+        let e = FrameEvent("turnOn",null);
+        _state_(e);
+    }
+    
+    that.turnOff = function () {
+        let e = FrameEvent("turnOff",null);
+        _state_(e);
+    }
+    
+    that.getColor = function () {
+        let e = FrameEvent("getColor",null);
+        _state_(e);
+        return e._return;
+    }
+    
+    that.setColor = function (color) {
+        let e = FrameEvent("setColor",{"color":color});
+        _state_(e);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    let _sOff_ = function (e) {
+        if (e._message == ">") {
+            that.closeSwitch_do();
+            return;
+        }
+        else if (e._message == "turnOn") {
+            // turn on
+            _transition_(_sOn_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = that.color
+            return;
+            
+        }
+    }
+    
+    let _sOn_ = function (e) {
+        if (e._message == ">") {
+            that.openSwitch_do();
+            return;
+        }
+        else if (e._message == "<") {
+            that.log_do("leaving On");
+            return;
+        }
+        else if (e._message == "turnOff") {
+            // turn off
+            _transition_(_sOff_);
+            return;
+        }
+        else if (e._message == "getColor") {
+            e._return = that.color
+            return;
+            
+        }
+        else if (e._message == "setColor") {
+            that.color = (e._parameters["color"]);
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    that.openSwitch_do = function () { throw new Error('Action not implemented.'); }
+    that.closeSwitch_do = function () { throw new Error('Action not implemented.'); }
+    that.log_do = function (msg) { throw new Error('Action not implemented.'); }
+    
+    //===================== Domain Block ===================//
+    
+    that.color = "white";
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    let _state_ = _sOff_;
+    
+    let _transition_ = function(newState) {
+        let exitEvent = FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        let enterEvent = FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+    return that; 
+};
+
+/********************
+let LampController = function () {
+	let that = Lamp.call(this);
+	that.openSwitch_do = function () {}
+	that.closeSwitch_do = function () {}
+	that.log_do = function (msg) {}
+	return that;
+};
+********************/
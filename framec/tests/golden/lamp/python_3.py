@@ -0,0 +1,113 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Lamp:
+    
+    def __init__(self):
+        self._state_ = self._sOff_
+        
+        # ===================== Domain Block =================== #
+        
+        self.color = "white";
+    
+    # ===================== Interface Block =================== #
+    
+    def turnOn(self):
+        e = FrameEvent("turnOn",None)
+        self._state_(e)
+    
+    def turnOff(self):
+        e = FrameEvent("turnOff",None)
+        self._state_(e)
+    
+    def getColor(self):
+        e = FrameEvent("getColor",None)
+        self._state_(e)
+        return e._return
+    
+    def setColor(self,color):
+        parameters = {}
+        parameters["color"] = color;
+
+        e = FrameEvent("setColor",parameters)
+        self._state_(e)
+    
+    # ===================== Machine Block =================== #
+    
+    def _sOff_(self, e):
+        if e._message == ">":
+            self.closeSwitch_do()
+            return
+        
+        elif e._message == "turnOn":
+            # turn on
+            self._transition_(self._sOn_)
+            return
+        
+        elif e._message == "getColor":
+            e._return = self.color
+            return
+            
+        
+    def _sOn_(self, e):
+        if e._message == ">":
+            self.openSwitch_do()
+            return
+        
+        elif e._message == "<":
+            self.log_do("leaving On")
+            return
+        
+        elif e._message == "turnOff":
+            # turn off
+            self._transition_(self._sOff_)
+            return
+        
+        elif e._message == "getColor":
+            e._return = self.color
+            return
+            
+        
+        elif e._message == "setColor":
+            self.color = e.params["color"]
+            return
+        
+    
+    # ===================== Actions Block =================== #
+    
+    def openSwitch_do(self):
+        raise NotImplementedError
+    
+    def closeSwitch_do(self):
+        raise NotImplementedError
+    
+    def log_do(self,msg):
+        raise NotImplementedError
+    
+    
+    # =============== Machinery and Mechanisms ============== #
+    
+    def _transition_(self, newState):
+        exitEvent = FrameEvent("<",None)
+        self._state_(exitEvent)
+        self._state_ = newState
+        enterEvent = FrameEvent(">",None)
+        self._state_(enterEvent)
+    
+    
+
+# ********************
+
+#class LampController(Lamp):
+
+    #def openSwitch_do(self):
+        #pass
+
+    #def closeSwitch_do(self):
+        #pass
+
+    #def log_do(self,msg):
+        #pass
+
+# ********************
+
@@ -0,0 +1,298 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(LampState),
+    Exit(LampState),
+    TurnOn,
+    TurnOff,
+    GetColor,
+    SetColor,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(LampState::Off) => write!(f, "Off:>"),
+            FrameMessage::Enter(LampState::On) => write!(f, "On:>"),
+            FrameMessage::Exit(LampState::Off) => write!(f, "Off:<"),
+            FrameMessage::Exit(LampState::On) => write!(f, "On:<"),
+            FrameMessage::TurnOn => write!(f, "turnOn"),
+            FrameMessage::TurnOff => write!(f, "turnOff"),
+            FrameMessage::GetColor => write!(f, "getColor"),
+            FrameMessage::SetColor => write!(f, "setColor"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct SetColorArgs {
+    color: string,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    SetColor(SetColorArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn set_color_args(&self) -> &SetColorArgs {
+        match self {
+            FrameEventArgs::SetColor(args) => args,
+            _ => panic!("Failed conversion to SetColorArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    GetColor { return_value: string },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_color_ret(&self) -> string {
+        match self {
+            FrameEventReturn::GetColor { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum LampState {
+    Off,
+    On,
+}
+
+#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait LampActions { 
+    fn open_switch(&self);
+    fn close_switch(&self);
+    fn log(&self, msg: string);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Lamp {
+    state: LampState,
+    //===================== Domain Block ===================//
+    color: string,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Lamp {
+    
+    pub fn new() -> Self {
+        let mut machine = Lamp {
+            state: LampState::Off,
+            color: String::from("white"),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn turn_on(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::TurnOn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn turn_off(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::TurnOff, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_color(&mut self) -> string {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetColor, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetColor { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getColor"),
+        };
+        return_value
+    }
+    
+    pub fn set_color(&mut self, color: string) {
+        let frame_args = FrameEventArgs::SetColor(SetColorArgs { color, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::SetColor, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.close_switch();
+                return;
+            }
+            FrameMessage::TurnOn => {
+                // Start transition
+                // turn on
+                self.transition(LampState::On);
+                return;
+            }
+            FrameMessage::GetColor => {
+                frame_event.ret.replace(FrameEventReturn::GetColor {
+                    return_value: self.color
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.open_switch();
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("leaving On"));
+                return;
+            }
+            FrameMessage::TurnOff => {
+                // Start transition
+                // turn off
+                self.transition(LampState::Off);
+                return;
+            }
+            FrameMessage::GetColor => {
+                frame_event.ret.replace(FrameEventReturn::GetColor {
+                    return_value: self.color
+                });
+                return;
+            }
+            FrameMessage::SetColor => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().set_color_args().color
+                };
+                self.color = assign_temp;
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            LampState::Off => self.off_handler(frame_event.clone()),
+            LampState::On => self.on_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: LampState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Lamp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl LampActions for Lamp { 
+    fn open_switch(&self) {}
+    fn close_switch(&self) {}
+    fn log(&self, msg: string) {}
+}
\ No newline at end of file
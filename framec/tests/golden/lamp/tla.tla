@@ -0,0 +1,47 @@
+\* emitted from framec_v0.8.0
+---- MODULE lamp ----
+\* The states of the machine Lamp and the events sent to it.
+
+VARIABLES state, event
+
+vars == <<state, event>>
+
+States == {"Off", "On"}
+
+Events == {"turnOn", "turnOff", "getColor", "setColor"}
+
+TypeOK ==
+    /\ state \in States
+    /\ event \in Events \cup {"none"}
+
+Init ==
+    /\ state = "Off"
+    /\ event = "none"
+
+Send_turnOn ==
+    /\ event' = "turnOn"
+    /\ CASE state = "Off" -> state' = "On"
+         [] OTHER -> UNCHANGED state
+
+Send_turnOff ==
+    /\ event' = "turnOff"
+    /\ CASE state = "On" -> state' = "Off"
+         [] OTHER -> UNCHANGED state
+
+Send_getColor ==
+    /\ event' = "getColor"
+    /\ UNCHANGED state
+
+Send_setColor ==
+    /\ event' = "setColor"
+    /\ UNCHANGED state
+
+Next ==
+    \/ Send_turnOn
+    \/ Send_turnOff
+    \/ Send_getColor
+    \/ Send_setColor
+
+Spec == Init /\ [][Next]_vars
+
+====
@@ -0,0 +1,65 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class Turnstile {
+    public Turnstile() {
+        
+        _state_ = _sLocked_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void coin() {
+        FrameEvent e = new FrameEvent("coin",null);
+        _state_(e);
+    }
+    
+    public void push() {
+        FrameEvent e = new FrameEvent("push",null);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sLocked_(FrameEvent e) {
+        if (e._message.Equals("coin")) {
+            _transition_(_sUnlocked_);
+            return;
+        }
+        else if (e._message.Equals("push")) {
+            return;
+        }
+    }
+    
+    private void _sUnlocked_(FrameEvent e) {
+        if (e._message.Equals("push")) {
+            _transition_(_sLocked_);
+            return;
+        }
+        else if (e._message.Equals("coin")) {
+            return;
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private delegate void FrameState(FrameEvent e);
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+}
+
+/********************
+
+public partial class TurnstileController : Turnstile {
+}
+
+********************/
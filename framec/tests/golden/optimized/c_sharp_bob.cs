@@ -0,0 +1,59 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+public partial class TurnstileBase : FrameController {
+    public FrameController self;
+    
+    public TurnstileBase() {
+        self = this;
+        _state_ = _sLocked_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void coin() {
+        FrameEvent e = new FrameEvent("coin",null);
+        _state_(e);
+    }
+    
+    public void push() {
+        FrameEvent e = new FrameEvent("push",null);
+        _state_(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sLocked_(FrameEvent e) {
+        if (e.Msg.EqualsEx("coin")) {
+            
+            _transition_(_sUnlocked_);
+            return;
+        }
+        else if (e.Msg.EqualsEx("push")) {
+            return;
+        }
+    }
+    
+    private void _sUnlocked_(FrameEvent e) {
+        if (e.Msg.EqualsEx("push")) {
+            
+            _transition_(_sLocked_);
+            return;
+        }
+        else if (e.Msg.EqualsEx("coin")) {
+            return;
+        }
+    }
+    
+    //===================== Actions Block ===================//
+    
+    protected virtual void Print_do(object o1) { FrameConsolePrint(o1); }
+    protected virtual void Print_do(object o1, object o2) { FrameConsolePrint(o1, o2); }
+    protected virtual void Print_do(object o1, object o2, object o3) { FrameConsolePrint(o1, o2, o3); }
+}
+
+/********************
+public partial class Turnstile : TurnstileBase {
+}
+********************/
@@ -0,0 +1,76 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Turnstile {
+
+    class StateContext;
+    
+    public:
+    
+    Turnstile() {
+        _state_ = &Turnstile::_sLocked_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    void coin() {
+        FrameEvent e(string("coin"),nullptr);
+        (this->*_state_)(e);
+    }
+    
+    void push() {
+        FrameEvent e(string("push"),nullptr);
+        (this->*_state_)(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private:
+    
+    void _sLocked_(FrameEvent& e) {
+        if (e._message == "coin") {
+            
+            _transition_(&Turnstile::_sUnlocked_);
+            return;
+        }
+        else if (e._message == "push") {
+            return;
+        }
+    }
+    
+    void _sUnlocked_(FrameEvent& e) {
+        if (e._message == "push") {
+            
+            _transition_(&Turnstile::_sLocked_);
+            return;
+        }
+        else if (e._message == "coin") {
+            return;
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    public:
+    virtual ~Turnstile() {};
+    
+    
+    private:
+    
+    typedef void (Turnstile::*FrameState)(FrameEvent& e);
+    typedef map<string,void*> FrameMap;
+    
+    
+    FrameState _state_;
+    StateContext* _pStateContext_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent("<",nullptr);
+        _state_ = newState;
+        if (_pStateContext_ && !_pStateContext_->isOnStateStack()) delete _pStateContext_;
+        FrameEvent enterEvent(">",nullptr);
+        
+        (this->*_state_)(enterEvent);
+    }
+}
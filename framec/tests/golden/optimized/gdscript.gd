@@ -0,0 +1,51 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class_name Turnstile
+
+func _init():
+    self._state_ = funcref(self,"_sLocked_")
+
+
+# ===================== Interface Block =================== #
+
+func coin():
+    var e = FrameEvent.new("coin",null)
+    self._state_.call_func(e)
+
+func push():
+    var e = FrameEvent.new("push",null)
+    self._state_.call_func(e)
+
+
+# ===================== Machine Block =================== #
+
+func _sLocked_(e):
+    if e._message == "coin":
+        self._transition_(funcref(self,"_sUnlocked_"))
+        return
+    
+    elif e._message == "push":
+        return
+    
+func _sUnlocked_(e):
+    if e._message == "push":
+        self._transition_(funcref(self,"_sLocked_"))
+        return
+    
+    elif e._message == "coin":
+        return
+    
+
+
+# =============== Machinery and Mechanisms ============== #
+
+var _state_ = null
+
+func _transition_(newState):
+    var exitEvent = FrameEvent.new("<",null)
+    self._state_.call_func(exitEvent)
+    self._state_ = newState
+    var enterEvent = FrameEvent.new(">",null)
+    self._state_.call_func(enterEvent)
+
@@ -0,0 +1,134 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+
+func NewTurnstile() Turnstile {
+    m := &turnstileStruct{}
+    
+    // Validate interfaces
+    var _ Turnstile = m
+    
+    m._compartment_ = NewTurnstileCompartment(TurnstileState_Locked)
+    
+    // Initialize domain
+    
+    // Send system start event
+    e := framelang.FrameEvent{Msg:">"}
+    m._mux_(&e)
+    return m
+}
+
+
+type TurnstileState uint
+
+const (
+    TurnstileState_Locked TurnstileState = iota
+    TurnstileState_Unlocked
+)
+
+type Turnstile interface {
+    Coin() 
+    Push() 
+}
+
+type turnstileStruct struct {
+    _compartment_ *TurnstileCompartment
+    _nextCompartment_ *TurnstileCompartment
+}
+
+//===================== Interface Block ===================//
+
+func (m *turnstileStruct) Coin()  {
+    e := framelang.FrameEvent{Msg:"coin"}
+    m._mux_(&e)
+}
+
+func (m *turnstileStruct) Push()  {
+    e := framelang.FrameEvent{Msg:"push"}
+    m._mux_(&e)
+}
+
+//====================== Multiplexer ====================//
+
+func (m *turnstileStruct) _mux_(e *framelang.FrameEvent) {
+    switch m._compartment_.State {
+    case TurnstileState_Locked:
+        m._TurnstileState_Locked_(e)
+    case TurnstileState_Unlocked:
+        m._TurnstileState_Unlocked_(e)
+    }
+    
+    if m._nextCompartment_ != nil {
+        nextCompartment := m._nextCompartment_
+        m._nextCompartment_ = nil
+        if nextCompartment._forwardEvent_ != nil && 
+           nextCompartment._forwardEvent_.Msg == ">" {
+            m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+            m._compartment_ = nextCompartment
+            m._mux_(nextCompartment._forwardEvent_)
+        } else {
+            m._do_transition_(nextCompartment)
+            if nextCompartment._forwardEvent_ != nil {
+                m._mux_(nextCompartment._forwardEvent_)
+            }
+        }
+        nextCompartment._forwardEvent_ = nil
+    }
+}
+
+//===================== Machine Block ===================//
+
+func (m *turnstileStruct) _TurnstileState_Locked_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case "coin":
+        compartment := NewTurnstileCompartment(TurnstileState_Unlocked)
+        m._transition_(compartment)
+        return
+    case "push":
+        return
+    }
+}
+
+func (m *turnstileStruct) _TurnstileState_Unlocked_(e *framelang.FrameEvent) {
+    switch e.Msg {
+    case "push":
+        compartment := NewTurnstileCompartment(TurnstileState_Locked)
+        m._transition_(compartment)
+        return
+    case "coin":
+        return
+    }
+}
+
+//=============== Machinery and Mechanisms ==============//
+
+func (m *turnstileStruct) _transition_(compartment *TurnstileCompartment) {
+    m._nextCompartment_ = compartment
+}
+
+func (m *turnstileStruct) _do_transition_(nextCompartment *TurnstileCompartment) {
+    m._mux_(&framelang.FrameEvent{Msg: "<", Params: m._compartment_.ExitArgs, Ret: nil})
+    m._compartment_ = nextCompartment
+    m._mux_(&framelang.FrameEvent{Msg: ">", Params: m._compartment_.EnterArgs, Ret: nil})
+}
+
+
+//=============== Compartment ==============//
+
+type TurnstileCompartment struct {
+    State TurnstileState
+    StateArgs map[string]interface{}
+    StateVars map[string]interface{}
+    EnterArgs map[string]interface{}
+    ExitArgs map[string]interface{}
+    _forwardEvent_ *framelang.FrameEvent
+}
+
+func NewTurnstileCompartment(state TurnstileState) *TurnstileCompartment {
+    c := &TurnstileCompartment{State: state}
+    c.StateArgs = make(map[string]interface{})
+    c.StateVars = make(map[string]interface{})
+    c.EnterArgs = make(map[string]interface{})
+    c.ExitArgs = make(map[string]interface{})
+    return c
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Turnstile {
+    Turnstile() {
+        
+        _state_ = this::_sLocked_;
+    }
+    
+    //===================== Interface Block ===================//
+    
+    public void coin() {
+        FrameEvent e = new FrameEvent("coin",null);
+        _state_.call(e);
+    }
+    
+    public void push() {
+        FrameEvent e = new FrameEvent("push",null);
+        _state_.call(e);
+    }
+    
+    
+    //===================== Machine Block ===================//
+    
+    private void _sLocked_(FrameEvent e) {
+        if (e._message == "coin") {
+            _transition_(this::_sUnlocked_);
+            return;
+        }
+        else if (e._message == "push") {
+            return;
+        }
+    }
+    
+    private void _sUnlocked_(FrameEvent e) {
+        if (e._message == "push") {
+            _transition_(this::_sLocked_);
+            return;
+        }
+        else if (e._message == "coin") {
+            return;
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    private FrameState _state_;
+    
+    private void _transition_(FrameState newState) {
+        FrameEvent exitEvent = new FrameEvent("<",null);
+        _state_.call(exitEvent);
+        _state_ = newState;
+        FrameEvent enterEvent = new FrameEvent(">",null);
+        _state_.call(enterEvent);
+    }
+    
+}
+
+/********************
+
+public class TurnstileController extends Turnstile {
+}
+
+********************/
@@ -0,0 +1,63 @@
+// emitted from framec_v0.8.0
+// get include files at https://github.com/frame-lang/frame-ancillary-files
+
+let Turnstile = function () {
+    
+    let that = {};
+    that.constructor = Turnstile;
+    
+    //===================== Interface Block ===================//
+    
+    that.coin = function () {
+        let e = FrameEvent("coin",null);
+        _state_(e);
+    }
+    
+    that.push = function () {
+        let e = FrameEvent("push",null);
+        _state_(e);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    let _sLocked_ = function (e) {
+        if (e._message == "coin") {
+            _transition_(_sUnlocked_);
+            return;
+        }
+        else if (e._message == "push") {
+            return;
+        }
+    }
+    
+    let _sUnlocked_ = function (e) {
+        if (e._message == "push") {
+            _transition_(_sLocked_);
+            return;
+        }
+        else if (e._message == "coin") {
+            return;
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    let _state_ = _sLocked_;
+    
+    let _transition_ = function(newState) {
+        let exitEvent = FrameEvent("<",null);
+        _state_(exitEvent);
+        _state_ = newState;
+        let enterEvent = FrameEvent(">",null);
+        _state_(enterEvent);
+    }
+    
+    return that; 
+};
+
+/********************
+let TurnstileController = function () {
+	let that = Turnstile.call(this);
+	return that;
+};
+********************/
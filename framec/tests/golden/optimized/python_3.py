@@ -0,0 +1,55 @@
+# emitted from framec_v0.8.0
+# get include files at https://github.com/frame-lang/frame-ancillary-files
+
+class Turnstile:
+    
+    def __init__(self):
+        self._state_ = self._sLocked_
+    
+    # ===================== Interface Block =================== #
+    
+    def coin(self):
+        e = FrameEvent("coin",None)
+        self._state_(e)
+    
+    def push(self):
+        e = FrameEvent("push",None)
+        self._state_(e)
+    
+    # ===================== Machine Block =================== #
+    
+    def _sLocked_(self, e):
+        if e._message == "coin":
+            self._transition_(self._sUnlocked_)
+            return
+        
+        elif e._message == "push":
+            return
+        
+    def _sUnlocked_(self, e):
+        if e._message == "push":
+            self._transition_(self._sLocked_)
+            return
+        
+        elif e._message == "coin":
+            return
+        
+    
+    
+    # =============== Machinery and Mechanisms ============== #
+    
+    def _transition_(self, newState):
+        exitEvent = FrameEvent("<",None)
+        self._state_(exitEvent)
+        self._state_ = newState
+        enterEvent = FrameEvent(">",None)
+        self._state_(enterEvent)
+    
+    
+
+# ********************
+
+#class TurnstileController(Turnstile):
+
+# ********************
+
@@ -0,0 +1,198 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(TurnstileState),
+    Exit(TurnstileState),
+    Coin,
+    Push,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(TurnstileState::Locked) => write!(f, "Locked:>"),
+            FrameMessage::Enter(TurnstileState::Unlocked) => write!(f, "Unlocked:>"),
+            FrameMessage::Exit(TurnstileState::Locked) => write!(f, "Locked:<"),
+            FrameMessage::Exit(TurnstileState::Unlocked) => write!(f, "Unlocked:<"),
+            FrameMessage::Coin => write!(f, "coin"),
+            FrameMessage::Push => write!(f, "push"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TurnstileState {
+    Locked,
+    Unlocked,
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Turnstile {
+    state: TurnstileState,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Turnstile {
+    
+    pub fn new() -> Self {
+        let mut machine = Turnstile {
+            state: TurnstileState::Locked,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn coin(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Coin, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Push, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn locked_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Coin => {
+                // Start transition
+                self.transition(TurnstileState::Unlocked);
+                return;
+            }
+            FrameMessage::Push => {
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn unlocked_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Push => {
+                // Start transition
+                self.transition(TurnstileState::Locked);
+                return;
+            }
+            FrameMessage::Coin => {
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            TurnstileState::Locked => self.locked_handler(frame_event.clone()),
+            TurnstileState::Unlocked => self.unlocked_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: TurnstileState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Turnstile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,37 @@
+\* emitted from framec_v0.8.0
+---- MODULE optimized ----
+\* The states of the machine Turnstile and the events sent to it.
+
+VARIABLES state, event
+
+vars == <<state, event>>
+
+States == {"Locked", "Unlocked"}
+
+Events == {"coin", "push"}
+
+TypeOK ==
+    /\ state \in States
+    /\ event \in Events \cup {"none"}
+
+Init ==
+    /\ state = "Locked"
+    /\ event = "none"
+
+Send_coin ==
+    /\ event' = "coin"
+    /\ CASE state = "Locked" -> state' = "Unlocked"
+         [] OTHER -> UNCHANGED state
+
+Send_push ==
+    /\ event' = "push"
+    /\ CASE state = "Unlocked" -> state' = "Locked"
+         [] OTHER -> UNCHANGED state
+
+Next ==
+    \/ Send_coin
+    \/ Send_push
+
+Spec == Init /\ [][Next]_vars
+
+====
@@ -0,0 +1 @@
+Fatal error - change state stack pop not implemented.
\ No newline at end of file
@@ -0,0 +1,254 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StackState),
+    Exit(StackState),
+    Push,
+    Pop,
+    Next,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StackState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(StackState::B) => write!(f, "B:>"),
+            FrameMessage::Enter(StackState::C) => write!(f, "C:>"),
+            FrameMessage::Exit(StackState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(StackState::B) => write!(f, "B:<"),
+            FrameMessage::Exit(StackState::C) => write!(f, "C:<"),
+            FrameMessage::Push => write!(f, "push"),
+            FrameMessage::Pop => write!(f, "pop"),
+            FrameMessage::Next => write!(f, "next"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StackState {
+    A,
+    B,
+    C,
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Stack {
+    state: StackState,
+    state_stack: Vec<StackState>,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Stack {
+    
+    pub fn new() -> Self {
+        let mut machine = Stack {
+            state: StackState::A,
+            state_stack: Vec::new(),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Push, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pop(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Pop, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                self.transition(StackState::B);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let next_state = self.state_stack_pop();
+                self.transition(next_state);
+                return;
+            }
+            FrameMessage::Next => {
+                // Start change state
+                self.change_state(StackState::C);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn c_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Pop => {
+                // Start change state
+                let next_state = self.state_stack_pop();
+                self.change_state(next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            StackState::A => self.a_handler(frame_event.clone()),
+            StackState::B => self.b_handler(frame_event.clone()),
+            StackState::C => self.c_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: StackState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn state_stack_push(&mut self) {
+        self.state_stack.push(self.state);
+    }
+    
+    fn state_stack_pop(&mut self) -> StackState {
+        match self.state_stack.pop() {
+            Some(elem) => elem,
+            None => panic!("Error: attempted to pop when history stack is empty.")
+        }
+    }
+    
+    fn change_state(&mut self, new_state: StackState) {
+        self.state = new_state;
+    }
+    
+} // end system controller
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,44 @@
+\* emitted from framec_v0.8.0
+---- MODULE stack ----
+\* The states of the machine Stack and the events sent to it.
+
+VARIABLES state, event
+
+vars == <<state, event>>
+
+States == {"A", "B", "C"}
+
+Events == {"push", "pop", "next"}
+
+TypeOK ==
+    /\ state \in States
+    /\ event \in Events \cup {"none"}
+
+Init ==
+    /\ state = "A"
+    /\ event = "none"
+
+Send_push ==
+    /\ event' = "push"
+    /\ UNCHANGED state
+
+Send_pop ==
+    /\ event' = "pop"
+    /\ CASE state = "B" -> state' \in {"A", "B", "C"}
+         [] state = "C" -> state' \in {"A", "B", "C"}
+         [] OTHER -> UNCHANGED state
+
+Send_next ==
+    /\ event' = "next"
+    /\ CASE state = "A" -> state' = "B"
+         [] state = "B" -> state' = "C"
+         [] OTHER -> UNCHANGED state
+
+Next ==
+    \/ Send_push
+    \/ Send_pop
+    \/ Send_next
+
+Spec == Init /\ [][Next]_vars
+
+====
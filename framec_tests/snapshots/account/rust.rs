@@ -0,0 +1,517 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(AccountState),
+    Exit(AccountState),
+    Deposit,
+    Withdraw,
+    Close,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(AccountState::Open) => write!(f, "Open:>"),
+            FrameMessage::Enter(AccountState::Closed) => write!(f, "Closed:>"),
+            FrameMessage::Exit(AccountState::Open) => write!(f, "Open:<"),
+            FrameMessage::Exit(AccountState::Closed) => write!(f, "Closed:<"),
+            FrameMessage::Deposit => write!(f, "deposit"),
+            FrameMessage::Withdraw => write!(f, "withdraw"),
+            FrameMessage::Close => write!(f, "close"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DepositArgs {
+    amount: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for DepositArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "amount" => Some(Box::new(self.amount.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct WithdrawArgs {
+    amount: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for WithdrawArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "amount" => Some(Box::new(self.amount.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Deposit(DepositArgs),
+    Withdraw(WithdrawArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Deposit(_) => false,
+            FrameEventArgs::Withdraw(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Deposit(args) => args.lookup(name),
+            FrameEventArgs::Withdraw(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn deposit_args(&self) -> &DepositArgs {
+        match self {
+            FrameEventArgs::Deposit(args) => args,
+            _ => panic!("Failed conversion to DepositArgs"),
+        }
+    }
+    fn withdraw_args(&self) -> &WithdrawArgs {
+        match self {
+            FrameEventArgs::Withdraw(args) => args,
+            _ => panic!("Failed conversion to WithdrawArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Account> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Account as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum AccountState {
+    Open,
+    Closed,
+}
+
+impl runtime::State<Account> for AccountState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            AccountState::Open => runtime_info::machine_info().states[0],
+            AccountState::Closed => runtime_info::machine_info().states[1],
+        }
+    }
+    fn arguments(&self) -> <Account as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Account as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Account {
+    state: AccountState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    balance: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Account {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "balance" => Some(Box::new(self.balance.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Account {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Account as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Account {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Account {
+    
+    pub fn new() -> Self {
+        let mut machine = Account {
+            state: AccountState::Open,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            balance: 0,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn deposit(&mut self, amount: i32) {
+        let frame_args = FrameEventArgs::Deposit(DepositArgs { amount, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Deposit, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn withdraw(&mut self, amount: i32) {
+        let frame_args = FrameEventArgs::Withdraw(WithdrawArgs { amount, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Withdraw, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn close(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Close, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn open_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Deposit => {
+                if (frame_event.arguments.as_ref().borrow().deposit_args().amount) > 0 {
+                    let assign_temp = {
+                        self.balance + frame_event.arguments.as_ref().borrow().deposit_args().amount
+                    };
+                    self.balance = assign_temp;
+                }
+                return;
+            }
+            FrameMessage::Withdraw => {
+                if (frame_event.arguments.as_ref().borrow().withdraw_args().amount) > 0 && (frame_event.arguments.as_ref().borrow().withdraw_args().amount) <= self.balance {
+                    let assign_temp = {
+                        self.balance - frame_event.arguments.as_ref().borrow().withdraw_args().amount
+                    };
+                    self.balance = assign_temp;
+                }
+                return;
+            }
+            FrameMessage::Close => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], AccountState::Closed);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn closed_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            AccountState::Open => self.open_handler(frame_event.clone()),
+            AccountState::Closed => self.closed_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: AccountState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <Account as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <Account as runtime::Machine>::StatePtr,
+            exit_event as <Account as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Account as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Account {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl runtime::Dispatch for Account {
+    fn dispatch(&mut self, method: &str, arguments: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, runtime::DispatchError> {
+        match method {
+            "deposit" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let amount = args.required::<i32>("amount", "i32")?;
+                args.finish()?;
+                self.deposit(amount);
+                Ok(None)
+            }
+            "withdraw" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let amount = args.required::<i32>("amount", "i32")?;
+                args.finish()?;
+                self.withdraw(amount);
+                Ok(None)
+            }
+            "close" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                self.close();
+                Ok(None)
+            }
+            _ => Err(runtime::DispatchError::UnknownMethod(method.to_string())),
+        }
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/account.frm"),
+        sha256: Some("ad1e89366a2837d14e315d052626afb1fba9bc9d9b664a184d344b458bd7f0cc"),
+        name: "Account",
+        variables: &[
+            NameInfo {
+                name: "balance",
+                vtype: "i32",
+            },
+        ],
+        states: &[
+            STATE_OPEN,
+            STATE_CLOSED,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "deposit",
+            parameters: &[
+                NameInfo {
+                    name: "amount",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "withdraw",
+            parameters: &[
+                NameInfo {
+                    name: "amount",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "close",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_OPEN,
+            target: STATE_CLOSED,
+        },
+    ];
+    static STATE_OPEN: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Open",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_CLOSED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Closed",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[],
+        is_stack_pop: false,
+    };
+}
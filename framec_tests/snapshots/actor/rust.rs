@@ -0,0 +1,519 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::sync::{Arc, Mutex};
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(TurnstileState),
+    Exit(TurnstileState),
+    Coin,
+    Push,
+    GetTotal,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(TurnstileState::Locked) => write!(f, "Locked:>"),
+            FrameMessage::Enter(TurnstileState::Unlocked) => write!(f, "Unlocked:>"),
+            FrameMessage::Exit(TurnstileState::Locked) => write!(f, "Locked:<"),
+            FrameMessage::Exit(TurnstileState::Unlocked) => write!(f, "Unlocked:<"),
+            FrameMessage::Coin => write!(f, "coin"),
+            FrameMessage::Push => write!(f, "push"),
+            FrameMessage::GetTotal => write!(f, "getTotal"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct CoinArgs {
+    amount: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for CoinArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "amount" => Some(Box::new(self.amount.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventArgs {
+    None,
+    Coin(CoinArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Coin(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Coin(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn coin_args(&self) -> &CoinArgs {
+        match self {
+            FrameEventArgs::Coin(args) => args,
+            _ => panic!("Failed conversion to CoinArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    GetTotal { return_value: u32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_total_ret(&self) -> u32 {
+        match self {
+            FrameEventReturn::GetTotal { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Arc<Mutex<FrameEventArgs>>,
+    ret: Mutex<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Arc::new(Mutex::new(arguments)),
+            ret: Mutex::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Turnstile> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Turnstile as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match *self.ret.lock().unwrap() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::GetTotal { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TurnstileState {
+    Locked,
+    Unlocked,
+}
+
+impl runtime::State<Turnstile> for TurnstileState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            TurnstileState::Locked => runtime_info::machine_info().states[0],
+            TurnstileState::Unlocked => runtime_info::machine_info().states[1],
+        }
+    }
+    fn arguments(&self) -> <Turnstile as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::arc()
+    }
+    fn variables(&self) -> <Turnstile as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::arc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Turnstile {
+    state: TurnstileState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    total: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Turnstile {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "total" => Some(Box::new(self.total.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Turnstile {
+    type EnvironmentPtr = Arc<dyn runtime::Environment>;
+    type StatePtr = Arc<dyn runtime::State<Self> + Send + Sync>;
+    type EventPtr = Arc<dyn runtime::Event<Self> + Send + Sync>;
+    type EventFn = runtime::CallbackSend<Self::EventPtr>;
+    type TransitionFn = runtime::CallbackSend<runtime::Transition<Self>>;
+    fn state(&self) -> <Turnstile as runtime::Machine>::StatePtr {
+        Arc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::arc()
+    }
+}
+
+impl runtime::ThreadSafeMachine for Turnstile {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Turnstile {
+    
+    pub fn new() -> Self {
+        let mut machine = Turnstile {
+            state: TurnstileState::Locked,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            total: 0,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Arc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn coin(&mut self, amount: u32) {
+        let frame_args = FrameEventArgs::Coin(CoinArgs { amount, });
+        let frame_event = Arc::new(FrameEvent::new(FrameMessage::Coin, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Arc::new(FrameEvent::new(FrameMessage::Push, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_total(&mut self) -> u32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Arc::new(FrameEvent::new(FrameMessage::GetTotal, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.lock().unwrap() {
+            FrameEventReturn::GetTotal { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getTotal"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn locked_handler(&mut self, frame_event: Arc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Coin => {
+                let assign_temp = {
+                    self.total + ({ let var_value = frame_event.arguments.lock().unwrap().coin_args().amount; var_value })
+                };
+                self.total = assign_temp;
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], TurnstileState::Unlocked);
+                return;
+            }
+            FrameMessage::GetTotal => {
+                let mut ret = frame_event.ret.lock().unwrap();
+                *ret = FrameEventReturn::GetTotal {
+                    return_value: self.total
+                };
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn unlocked_handler(&mut self, frame_event: Arc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Coin => {
+                let assign_temp = {
+                    self.total + ({ let var_value = frame_event.arguments.lock().unwrap().coin_args().amount; var_value })
+                };
+                self.total = assign_temp;
+                return;
+            }
+            FrameMessage::Push => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[1], TurnstileState::Locked);
+                return;
+            }
+            FrameMessage::GetTotal => {
+                let mut ret = frame_event.ret.lock().unwrap();
+                *ret = FrameEventReturn::GetTotal {
+                    return_value: self.total
+                };
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Arc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            TurnstileState::Locked => self.locked_handler(frame_event.clone()),
+            TurnstileState::Unlocked => self.unlocked_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: TurnstileState) {
+        let exit_event = Arc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Arc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Arc::new(old_state) as <Turnstile as runtime::Machine>::StatePtr,
+            Arc::new(new_state) as <Turnstile as runtime::Machine>::StatePtr,
+            exit_event as <Turnstile as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Turnstile as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Turnstile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl runtime::Dispatch for Turnstile {
+    fn dispatch(&mut self, method: &str, arguments: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, runtime::DispatchError> {
+        match method {
+            "coin" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let amount = args.required::<u32>("amount", "u32")?;
+                args.finish()?;
+                self.coin(amount);
+                Ok(None)
+            }
+            "push" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                self.push();
+                Ok(None)
+            }
+            "getTotal" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                Ok(Some(Box::new(self.get_total())))
+            }
+            _ => Err(runtime::DispatchError::UnknownMethod(method.to_string())),
+        }
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/actor.frm"),
+        sha256: Some("197c135da519520cce8af06cd9b44578c92bbd1c053d87795c826629939ff3ab"),
+        name: "Turnstile",
+        variables: &[
+            NameInfo {
+                name: "total",
+                vtype: "u32",
+            },
+        ],
+        states: &[
+            STATE_LOCKED,
+            STATE_UNLOCKED,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "coin",
+            parameters: &[
+                NameInfo {
+                    name: "amount",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "push",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "getTotal",
+            parameters: &[],
+            return_type: Some("u32"),
+        },
+        &MethodInfo {
+            name: "Locked:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Locked:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Unlocked:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Unlocked:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_LOCKED,
+            target: STATE_UNLOCKED,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_UNLOCKED,
+            target: STATE_LOCKED,
+        },
+    ];
+    static STATE_LOCKED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Locked",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_UNLOCKED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Unlocked",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+}
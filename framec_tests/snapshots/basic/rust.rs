@@ -0,0 +1,477 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(BasicState),
+    Exit(BasicState),
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(BasicState::S0) => write!(f, "S0:>"),
+            FrameMessage::Enter(BasicState::S1) => write!(f, "S1:>"),
+            FrameMessage::Exit(BasicState::S0) => write!(f, "S0:<"),
+            FrameMessage::Exit(BasicState::S1) => write!(f, "S1:<"),
+            FrameMessage::A => write!(f, "A"),
+            FrameMessage::B => write!(f, "B"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Basic> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Basic as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum BasicState {
+    S0,
+    S1,
+}
+
+impl runtime::State<Basic> for BasicState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            BasicState::S0 => runtime_info::machine_info().states[0],
+            BasicState::S1 => runtime_info::machine_info().states[1],
+        }
+    }
+    fn arguments(&self) -> <Basic as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Basic as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait BasicActions { 
+    fn entered(&self, msg: &String);
+    fn left(&self, msg: &String);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Basic {
+    state: BasicState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    entry_log: Log,
+    exit_log: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Basic {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "entry_log" => Some(Box::new(self.entry_log.clone())),
+            "exit_log" => Some(Box::new(self.exit_log.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Basic {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Basic as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Basic {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Basic {
+    
+    pub fn new() -> Self {
+        let mut machine = Basic {
+            state: BasicState::S0,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            entry_log: vec![],
+            exit_log: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::A, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::B, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_0_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.entered(String::from("S0"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.left(String::from("S0"));
+                return;
+            }
+            FrameMessage::A => {
+                // Start transition
+                // ooh
+                self.transition(runtime_info::machine_info().transitions[0], BasicState::S1);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.entered(String::from("S1"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.left(String::from("S1"));
+                return;
+            }
+            FrameMessage::B => {
+                // Start transition
+                // aah
+                self.transition(runtime_info::machine_info().transitions[1], BasicState::S0);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            BasicState::S0 => self.s_0_handler(frame_event.clone()),
+            BasicState::S1 => self.s_1_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: BasicState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <Basic as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <Basic as runtime::Machine>::StatePtr,
+            exit_event as <Basic as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Basic as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Basic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/basic.frm"),
+        sha256: Some("b57614667812b082be1f4211c947a06b0301643a0656354116186737951ca85a"),
+        name: "Basic",
+        variables: &[
+            NameInfo {
+                name: "entry_log",
+                vtype: "Log",
+            },
+            NameInfo {
+                name: "exit_log",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_S_0,
+            STATE_S_1,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "entered",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "&String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "left",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "&String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "A",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "ooh",
+            source: STATE_S_0,
+            target: STATE_S_1,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "aah",
+            source: STATE_S_1,
+            target: STATE_S_0,
+        },
+    ];
+    static STATE_S_0: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S0",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_1: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S1",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[4],
+            EVENTS[5],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl BasicActions for Basic { 
+    fn entered(&self, msg: &String) {}
+    fn left(&self, msg: &String) {}
+}
\ No newline at end of file
@@ -0,0 +1,617 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(BranchState),
+    Exit(BranchState),
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    OnBool,
+    OnInt,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(BranchState::I) => write!(f, "I:>"),
+            FrameMessage::Enter(BranchState::SimpleIf) => write!(f, "SimpleIf:>"),
+            FrameMessage::Enter(BranchState::NegatedIf) => write!(f, "NegatedIf:>"),
+            FrameMessage::Enter(BranchState::Precedence) => write!(f, "Precedence:>"),
+            FrameMessage::Enter(BranchState::NestedIf) => write!(f, "NestedIf:>"),
+            FrameMessage::Enter(BranchState::GuardedTransition) => write!(f, "GuardedTransition:>"),
+            FrameMessage::Enter(BranchState::NestedGuardedTransition) => write!(f, "NestedGuardedTransition:>"),
+            FrameMessage::Enter(BranchState::F1) => write!(f, "F1:>"),
+            FrameMessage::Enter(BranchState::F2) => write!(f, "F2:>"),
+            FrameMessage::Enter(BranchState::F3) => write!(f, "F3:>"),
+            FrameMessage::Exit(BranchState::I) => write!(f, "I:<"),
+            FrameMessage::Exit(BranchState::SimpleIf) => write!(f, "SimpleIf:<"),
+            FrameMessage::Exit(BranchState::NegatedIf) => write!(f, "NegatedIf:<"),
+            FrameMessage::Exit(BranchState::Precedence) => write!(f, "Precedence:<"),
+            FrameMessage::Exit(BranchState::NestedIf) => write!(f, "NestedIf:<"),
+            FrameMessage::Exit(BranchState::GuardedTransition) => write!(f, "GuardedTransition:<"),
+            FrameMessage::Exit(BranchState::NestedGuardedTransition) => write!(f, "NestedGuardedTransition:<"),
+            FrameMessage::Exit(BranchState::F1) => write!(f, "F1:<"),
+            FrameMessage::Exit(BranchState::F2) => write!(f, "F2:<"),
+            FrameMessage::Exit(BranchState::F3) => write!(f, "F3:<"),
+            FrameMessage::A => write!(f, "A"),
+            FrameMessage::B => write!(f, "B"),
+            FrameMessage::C => write!(f, "C"),
+            FrameMessage::D => write!(f, "D"),
+            FrameMessage::E => write!(f, "E"),
+            FrameMessage::F => write!(f, "F"),
+            FrameMessage::OnBool => write!(f, "OnBool"),
+            FrameMessage::OnInt => write!(f, "OnInt"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OnBoolArgs {
+    b: bool,
+}
+
+#[allow(dead_code)]
+struct OnIntArgs {
+    i: i16,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    OnBool(OnBoolArgs),
+    OnInt(OnIntArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn on_bool_args(&self) -> &OnBoolArgs {
+        match self {
+            FrameEventArgs::OnBool(args) => args,
+            _ => panic!("Failed conversion to OnBoolArgs"),
+        }
+    }
+    fn on_int_args(&self) -> &OnIntArgs {
+        match self {
+            FrameEventArgs::OnInt(args) => args,
+            _ => panic!("Failed conversion to OnIntArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum BranchState {
+    I,
+    SimpleIf,
+    NegatedIf,
+    Precedence,
+    NestedIf,
+    GuardedTransition,
+    NestedGuardedTransition,
+    F1,
+    F2,
+    F3,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Branch {
+    state: BranchState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Branch {
+    
+    pub fn new() -> Self {
+        let mut machine = Branch {
+            state: BranchState::I,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::A, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::B, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::C, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn d(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::D, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn e(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::E, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn f(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::F, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn on_bool(&mut self, b: bool) {
+        let frame_args = FrameEventArgs::OnBool(OnBoolArgs { b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::OnBool, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn on_int(&mut self, i: i16) {
+        let frame_args = FrameEventArgs::OnInt(OnIntArgs { i, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::OnInt, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn i_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                // Start transition
+                self.transition(BranchState::SimpleIf);
+                return;
+            }
+            FrameMessage::B => {
+                // Start transition
+                self.transition(BranchState::NegatedIf);
+                return;
+            }
+            FrameMessage::C => {
+                // Start transition
+                self.transition(BranchState::Precedence);
+                return;
+            }
+            FrameMessage::D => {
+                // Start transition
+                self.transition(BranchState::NestedIf);
+                return;
+            }
+            FrameMessage::E => {
+                // Start transition
+                self.transition(BranchState::GuardedTransition);
+                return;
+            }
+            FrameMessage::F => {
+                // Start transition
+                self.transition(BranchState::NestedGuardedTransition);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn simple_if_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnBool => {
+                if (frame_event.arguments.as_ref().borrow().on_bool_args().b) {
+                    self.log(String::from("then 1"));
+                } else {
+                }
+                if (frame_event.arguments.as_ref().borrow().on_bool_args().b) {
+                } else {
+                    self.log(String::from("else 1"));
+                }
+                if (frame_event.arguments.as_ref().borrow().on_bool_args().b) {
+                    self.log(String::from("then 2"));
+                } else {
+                    self.log(String::from("else 2"));
+                }
+                if (frame_event.arguments.as_ref().borrow().on_bool_args().b) {
+                    // Start transition
+                    self.transition(BranchState::F1);
+                    return;
+                } else {
+                    // Start transition
+                    self.transition(BranchState::F2);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 5 {
+                    self.log(String::from("> 5"));
+                } else {
+                    self.log(String::from("<= 5"));
+                }
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) < 10 {
+                    self.log(String::from("< 10"));
+                } else {
+                    self.log(String::from(">= 10"));
+                }
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 7 {
+                    self.log(String::from("== 7"));
+                    // Start transition
+                    self.transition(BranchState::F1);
+                    return;
+                } else {
+                    self.log(String::from("!= 7"));
+                    // Start transition
+                    self.transition(BranchState::F2);
+                    return;
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn negated_if_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnBool => {
+                if !((frame_event.arguments.as_ref().borrow().on_bool_args().b)) {
+                    self.log(String::from("then 1"));
+                } else {
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_bool_args().b)) {
+                } else {
+                    self.log(String::from("else 1"));
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_bool_args().b)) {
+                    self.log(String::from("then 2"));
+                } else {
+                    self.log(String::from("else 2"));
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_bool_args().b)) {
+                    // Start transition
+                    self.transition(BranchState::F1);
+                    return;
+                } else {
+                    // Start transition
+                    self.transition(BranchState::F2);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::OnInt => {
+                if !((frame_event.arguments.as_ref().borrow().on_int_args().i) >= 5) {
+                    self.log(String::from("< 5"));
+                } else {
+                    self.log(String::from(">= 5"));
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_int_args().i) <= 10) {
+                    self.log(String::from("> 10"));
+                } else {
+                    self.log(String::from("<= 10"));
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_int_args().i) != 7) {
+                    self.log(String::from("== 7"));
+                    // Start transition
+                    self.transition(BranchState::F1);
+                    return;
+                } else {
+                    self.log(String::from("!= 7"));
+                    // Start transition
+                    self.transition(BranchState::F2);
+                    return;
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn precedence_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if -(frame_event.arguments.as_ref().borrow().on_int_args().i) >= 0 && -(frame_event.arguments.as_ref().borrow().on_int_args().i) <= 5 {
+                    self.log(String::from("then 1"));
+                } else {
+                    self.log(String::from("else 1"));
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_int_args().i) >= -5 && (frame_event.arguments.as_ref().borrow().on_int_args().i) <= 5) && ((frame_event.arguments.as_ref().borrow().on_int_args().i) >= -10 && (frame_event.arguments.as_ref().borrow().on_int_args().i) <= 10) {
+                    self.log(String::from("then 2"));
+                } else {
+                    self.log(String::from("else 2"));
+                }
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) >= 0 && (frame_event.arguments.as_ref().borrow().on_int_args().i) <= 5 || (frame_event.arguments.as_ref().borrow().on_int_args().i) >= 10 && (frame_event.arguments.as_ref().borrow().on_int_args().i) <= 20 {
+                    self.log(String::from("then 3"));
+                } else {
+                    self.log(String::from("else 3"));
+                }
+                if !(((frame_event.arguments.as_ref().borrow().on_int_args().i) < 0 || (frame_event.arguments.as_ref().borrow().on_int_args().i) > 10) && (frame_event.arguments.as_ref().borrow().on_int_args().i) + 5 < 20) {
+                    self.log(String::from("then 4"));
+                } else {
+                    self.log(String::from("else 4"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn nested_if_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 0 {
+                    self.log(String::from("> 0"));
+                    if (frame_event.arguments.as_ref().borrow().on_int_args().i) < 100 {
+                        self.log(String::from("< 100"));
+                        // Start transition
+                        self.transition(BranchState::F1);
+                        return;
+                    } else {
+                        self.log(String::from(">= 100"));
+                    }
+                } else {
+                    self.log(String::from("<= 0"));
+                    if (frame_event.arguments.as_ref().borrow().on_int_args().i) > -10 {
+                        self.log(String::from("> -10"));
+                    } else {
+                        self.log(String::from("<= -10"));
+                        // Start transition
+                        self.transition(BranchState::F2);
+                        return;
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn guarded_transition_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 100 {
+                    self.log(String::from("-> $F1"));
+                    // Start transition
+                    self.transition(BranchState::F1);
+                    return;
+                } else {
+                }
+                if !((frame_event.arguments.as_ref().borrow().on_int_args().i) > 10) {
+                } else {
+                    self.log(String::from("-> $F2"));
+                    // Start transition
+                    self.transition(BranchState::F2);
+                    return;
+                }
+                self.log(String::from("-> $F3"));
+                // Start transition
+                self.transition(BranchState::F3);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn nested_guarded_transition_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 10 {
+                    if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 100 {
+                        self.log(String::from("-> $F1"));
+                        // Start transition
+                        self.transition(BranchState::F1);
+                        return;
+                    } else {
+                    }
+                    if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 50 {
+                    } else {
+                        self.log(String::from("-> $F2"));
+                        // Start transition
+                        self.transition(BranchState::F2);
+                        return;
+                    }
+                } else {
+                }
+                self.log(String::from("-> $F3"));
+                // Start transition
+                self.transition(BranchState::F3);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn f_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn f_2_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn f_3_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            BranchState::I => self.i_handler(frame_event.clone()),
+            BranchState::SimpleIf => self.simple_if_handler(frame_event.clone()),
+            BranchState::NegatedIf => self.negated_if_handler(frame_event.clone()),
+            BranchState::Precedence => self.precedence_handler(frame_event.clone()),
+            BranchState::NestedIf => self.nested_if_handler(frame_event.clone()),
+            BranchState::GuardedTransition => self.guarded_transition_handler(frame_event.clone()),
+            BranchState::NestedGuardedTransition => self.nested_guarded_transition_handler(frame_event.clone()),
+            BranchState::F1 => self.f_1_handler(frame_event.clone()),
+            BranchState::F2 => self.f_2_handler(frame_event.clone()),
+            BranchState::F3 => self.f_3_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: BranchState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Branch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,191 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(ConfigNation),
+    Exit(ConfigNation),
+    Next,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(ConfigNation::A) => write!(f, "A:>"),
+            FrameMessage::Enter(ConfigNation::B) => write!(f, "B:>"),
+            FrameMessage::Exit(ConfigNation::A) => write!(f, "A:<"),
+            FrameMessage::Exit(ConfigNation::B) => write!(f, "B:<"),
+            FrameMessage::Next => write!(f, "Next"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum ConfigNation {
+    A,
+    B,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Config {
+    nation: ConfigNation,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Config {
+    
+    pub fn new() -> Self {
+        let mut machine = Config {
+            nation: ConfigNation::A,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.nation),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                self.transition(ConfigNation::B);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                self.transition(ConfigNation::A);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.nation {
+            ConfigNation::A => self.a_handler(frame_event.clone()),
+            ConfigNation::B => self.b_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_nation: ConfigNation) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.nation),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        let old_nation = self.nation;
+        self.nation = new_nation;
+        self.oh_its_a_transition(old_nation, new_nation);
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.nation),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,320 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(ConstantsState),
+    Exit(ConstantsState),
+    Fail,
+    Succeed,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(ConstantsState::Trying) => write!(f, "Trying:>"),
+            FrameMessage::Enter(ConstantsState::Done) => write!(f, "Done:>"),
+            FrameMessage::Enter(ConstantsState::GaveUp) => write!(f, "GaveUp:>"),
+            FrameMessage::Exit(ConstantsState::Trying) => write!(f, "Trying:<"),
+            FrameMessage::Exit(ConstantsState::Done) => write!(f, "Done:<"),
+            FrameMessage::Exit(ConstantsState::GaveUp) => write!(f, "GaveUp:<"),
+            FrameMessage::Fail => write!(f, "fail"),
+            FrameMessage::Succeed => write!(f, "succeed"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DoneEnterArgs {
+    remaining: u32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    DoneEnter(DoneEnterArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn done_enter_args(&self) -> &DoneEnterArgs {
+        match self {
+            FrameEventArgs::DoneEnter(args) => args,
+            _ => panic!("Failed conversion to DoneEnterArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+const MAX_RETRIES: u32 = 3;
+
+#[allow(dead_code)]
+const GREETING: &str = "giving up";
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum ConstantsState {
+    Trying,
+    Done,
+    GaveUp,
+}
+
+#[allow(dead_code)]
+struct TryingStateContext {
+}
+
+#[allow(dead_code)]
+struct DoneStateContext {
+}
+
+#[allow(dead_code)]
+struct GaveUpStateContext {
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Trying(TryingStateContext),
+    Done(DoneStateContext),
+    GaveUp(GaveUpStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn trying_context(&self) -> &TryingStateContext {
+        match self {
+            StateContext::Trying(context) => context,
+            _ => panic!("Failed conversion to TryingStateContext"),
+        }
+    }
+    fn done_context(&self) -> &DoneStateContext {
+        match self {
+            StateContext::Done(context) => context,
+            _ => panic!("Failed conversion to DoneStateContext"),
+        }
+    }
+    fn gave_up_context(&self) -> &GaveUpStateContext {
+        match self {
+            StateContext::GaveUp(context) => context,
+            _ => panic!("Failed conversion to GaveUpStateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Constants {
+    state: ConstantsState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    retries: u32,
+    left: u32,
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Constants {
+    
+    pub fn new() -> Self {
+        let context = TryingStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Trying(context));
+        let mut machine = Constants {
+            state: ConstantsState::Trying,
+            state_context: next_state_context,
+            retries: 0,
+            left: 0,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn fail(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Fail, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn succeed(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Succeed, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn trying_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.trying_context();
+        match frame_event.message {
+            FrameMessage::Fail => {
+                let assign_temp = {
+                    self.retries + 1
+                };
+                self.retries = assign_temp;
+                if self.retries >= MAX_RETRIES {
+                    // Start transition
+                    let enter_args = FrameEventArgs::None;
+                    let context = GaveUpStateContext {
+                    };
+                    let next_state_context = Rc::new(StateContext::GaveUp(context));
+                    self.transition(enter_args, ConstantsState::GaveUp, next_state_context);
+                    
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Succeed => {
+                // Start transition
+                let enter_args = FrameEventArgs::DoneEnter(DoneEnterArgs {
+                    remaining: MAX_RETRIES - self.retries,
+                });
+                let context = DoneStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Done(context));
+                self.transition(enter_args, ConstantsState::Done, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn done_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.done_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().done_enter_args().remaining
+                };
+                self.left = assign_temp;
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn gave_up_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.gave_up_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(GREETING.to_string());
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            ConstantsState::Trying => self.trying_handler(frame_event.clone()),
+            ConstantsState::Done => self.done_handler(frame_event.clone()),
+            ConstantsState::GaveUp => self.gave_up_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, enter_args: FrameEventArgs, new_state: ConstantsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Constants {
+    fn default() -> Self {
+        Self::new()
+    }
+}
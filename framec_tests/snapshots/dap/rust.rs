@@ -0,0 +1,728 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DoorState),
+    Exit(DoorState),
+    Open,
+    Close,
+    Lock,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DoorState::Closed) => write!(f, "Closed:>"),
+            FrameMessage::Enter(DoorState::Open) => write!(f, "Open:>"),
+            FrameMessage::Enter(DoorState::Locked) => write!(f, "Locked:>"),
+            FrameMessage::Exit(DoorState::Closed) => write!(f, "Closed:<"),
+            FrameMessage::Exit(DoorState::Open) => write!(f, "Open:<"),
+            FrameMessage::Exit(DoorState::Locked) => write!(f, "Locked:<"),
+            FrameMessage::Open => write!(f, "open"),
+            FrameMessage::Close => write!(f, "close"),
+            FrameMessage::Lock => write!(f, "lock"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OpenArgs {
+    force: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for OpenArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "force" => Some(Box::new(self.force.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LockArgs {
+    code: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for LockArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "code" => Some(Box::new(self.code.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Open(OpenArgs),
+    Lock(LockArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Open(_) => false,
+            FrameEventArgs::Lock(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Open(args) => args.lookup(name),
+            FrameEventArgs::Lock(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn open_args(&self) -> &OpenArgs {
+        match self {
+            FrameEventArgs::Open(args) => args,
+            _ => panic!("Failed conversion to OpenArgs"),
+        }
+    }
+    fn lock_args(&self) -> &LockArgs {
+        match self {
+            FrameEventArgs::Lock(args) => args,
+            _ => panic!("Failed conversion to LockArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Door> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DoorState {
+    Closed,
+    Open,
+    Locked,
+}
+
+#[allow(dead_code)]
+struct ClosedStateContext {
+}
+
+impl runtime::State<Door> for ClosedStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct OpenStateArgs {
+    force: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for OpenStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "force" => Some(Box::new(self.force.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OpenStateVars {
+    swings: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for OpenStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "swings" => Some(Box::new(self.swings.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OpenStateContext {
+    state_args: Rc<RefCell<OpenStateArgs>>,
+    state_vars: Rc<RefCell<OpenStateVars>>,
+}
+
+impl runtime::State<Door> for OpenStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+struct LockedStateArgs {
+    code: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for LockedStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "code" => Some(Box::new(self.code.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LockedStateContext {
+    state_args: Rc<RefCell<LockedStateArgs>>,
+}
+
+impl runtime::State<Door> for LockedStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Closed(ClosedStateContext),
+    Open(OpenStateContext),
+    Locked(LockedStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn closed_context(&self) -> &ClosedStateContext {
+        match self {
+            StateContext::Closed(context) => context,
+            _ => panic!("Failed conversion to ClosedStateContext"),
+        }
+    }
+    fn open_context(&self) -> &OpenStateContext {
+        match self {
+            StateContext::Open(context) => context,
+            _ => panic!("Failed conversion to OpenStateContext"),
+        }
+    }
+    fn locked_context(&self) -> &LockedStateContext {
+        match self {
+            StateContext::Locked(context) => context,
+            _ => panic!("Failed conversion to LockedStateContext"),
+        }
+    }
+}
+
+impl runtime::State<Door> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Closed(context) => context.info(),
+            StateContext::Open(context) => context.info(),
+            StateContext::Locked(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Closed(context) => context.arguments(),
+            StateContext::Open(context) => context.arguments(),
+            StateContext::Locked(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <Door as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Closed(context) => context.variables(),
+            StateContext::Open(context) => context.variables(),
+            StateContext::Locked(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Door {
+    state: DoorState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    opens: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Door {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "opens" => Some(Box::new(self.opens.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Door {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Door as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Door {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Door {
+    
+    pub fn new() -> Self {
+        let context = ClosedStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Closed(context));
+        let mut machine = Door {
+            state: DoorState::Closed,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            opens: 0,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn open(&mut self, force: u32) {
+        let frame_args = FrameEventArgs::Open(OpenArgs { force, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Open, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn close(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Close, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn lock(&mut self, code: String) {
+        let frame_args = FrameEventArgs::Lock(LockArgs { code, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Lock, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn closed_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.closed_context();
+        match frame_event.message {
+            FrameMessage::Open => {
+                let assign_temp = {
+                    self.opens + 1
+                };
+                self.opens = assign_temp;
+                // Start transition
+                let context = OpenStateContext {
+                    state_args: Rc::new(RefCell::new(OpenStateArgs {
+                        force: frame_event.arguments.as_ref().borrow().open_args().force,
+                    })),
+                    state_vars: Rc::new(RefCell::new(OpenStateVars {
+                        swings: 1,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Open(context));
+                self.transition(runtime_info::machine_info().transitions[0], DoorState::Open, next_state_context);
+                return;
+            }
+            FrameMessage::Lock => {
+                // Start transition
+                let context = LockedStateContext {
+                    state_args: Rc::new(RefCell::new(LockedStateArgs {
+                        code: frame_event.arguments.as_ref().borrow().lock_args().code.clone(),
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Locked(context));
+                self.transition(runtime_info::machine_info().transitions[1], DoorState::Locked, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn open_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.open_context();
+        match frame_event.message {
+            FrameMessage::Close => {
+                // Start transition
+                let context = ClosedStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Closed(context));
+                self.transition(runtime_info::machine_info().transitions[2], DoorState::Closed, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn locked_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.locked_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            DoorState::Closed => self.closed_handler(frame_event.clone()),
+            DoorState::Open => self.open_handler(frame_event.clone()),
+            DoorState::Locked => self.locked_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: DoorState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <Door as runtime::Machine>::StatePtr,
+            new_state_context as <Door as runtime::Machine>::StatePtr,
+            exit_event as <Door as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Door as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Door {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/dap.frm"),
+        sha256: Some("da743e742bf992265f581f8ce17129bb8c73b1f99670324dc6077f9f1c9abd1d"),
+        name: "Door",
+        variables: &[
+            NameInfo {
+                name: "opens",
+                vtype: "u32",
+            },
+        ],
+        states: &[
+            STATE_CLOSED,
+            STATE_OPEN,
+            STATE_LOCKED,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "open",
+            parameters: &[
+                NameInfo {
+                    name: "force",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "close",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "lock",
+            parameters: &[
+                NameInfo {
+                    name: "code",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Locked:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Locked:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_CLOSED,
+            target: STATE_OPEN,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_CLOSED,
+            target: STATE_LOCKED,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_OPEN,
+            target: STATE_CLOSED,
+        },
+    ];
+    static STATE_CLOSED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Closed",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_OPEN: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Open",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "force",
+                vtype: "u32",
+            },
+        ],
+        variables: &[
+            NameInfo {
+                name: "swings",
+                vtype: "u32",
+            },
+        ],
+        handlers: &[
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_LOCKED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Locked",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "code",
+                vtype: "String",
+            },
+        ],
+        variables: &[],
+        handlers: &[
+            EVENTS[7],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,513 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DimmerState),
+    Exit(DimmerState),
+    Toggle,
+    SetLevel,
+    GetLevel,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DimmerState::Off) => write!(f, "Off:>"),
+            FrameMessage::Enter(DimmerState::On) => write!(f, "On:>"),
+            FrameMessage::Exit(DimmerState::Off) => write!(f, "Off:<"),
+            FrameMessage::Exit(DimmerState::On) => write!(f, "On:<"),
+            FrameMessage::Toggle => write!(f, "toggle"),
+            FrameMessage::SetLevel => write!(f, "setLevel"),
+            FrameMessage::GetLevel => write!(f, "getLevel"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct SetLevelArgs {
+    level: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for SetLevelArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "level" => Some(Box::new(self.level.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    SetLevel(SetLevelArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::SetLevel(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::SetLevel(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn set_level_args(&self) -> &SetLevelArgs {
+        match self {
+            FrameEventArgs::SetLevel(args) => args,
+            _ => panic!("Failed conversion to SetLevelArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    GetLevel { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_level_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::GetLevel { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Dimmer> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Dimmer as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::GetLevel { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DimmerState {
+    Off,
+    On,
+}
+
+impl runtime::State<Dimmer> for DimmerState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            DimmerState::Off => runtime_info::machine_info().states[0],
+            DimmerState::On => runtime_info::machine_info().states[1],
+        }
+    }
+    fn arguments(&self) -> <Dimmer as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Dimmer as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Dimmer {
+    state: DimmerState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    brightness: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Dimmer {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "brightness" => Some(Box::new(self.brightness.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Dimmer {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Dimmer as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Dimmer {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Dimmer {
+    
+    pub fn new() -> Self {
+        let mut machine = Dimmer {
+            state: DimmerState::Off,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            brightness: 1,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn toggle(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Toggle, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn set_level(&mut self, level: i32) {
+        let frame_args = FrameEventArgs::SetLevel(SetLevelArgs { level, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::SetLevel, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_level(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetLevel, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetLevel { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getLevel"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Toggle => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], DimmerState::On);
+                return;
+            }
+            FrameMessage::GetLevel => {
+                frame_event.ret.replace(FrameEventReturn::GetLevel {
+                    return_value: 0
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Toggle => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[1], DimmerState::Off);
+                return;
+            }
+            FrameMessage::SetLevel => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().set_level_args().level
+                };
+                self.brightness = assign_temp;
+                return;
+            }
+            FrameMessage::GetLevel => {
+                frame_event.ret.replace(FrameEventReturn::GetLevel {
+                    return_value: self.brightness
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            DimmerState::Off => self.off_handler(frame_event.clone()),
+            DimmerState::On => self.on_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: DimmerState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <Dimmer as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <Dimmer as runtime::Machine>::StatePtr,
+            exit_event as <Dimmer as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Dimmer as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Dimmer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl runtime::Dispatch for Dimmer {
+    fn dispatch(&mut self, method: &str, arguments: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, runtime::DispatchError> {
+        match method {
+            "toggle" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                self.toggle();
+                Ok(None)
+            }
+            "setLevel" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let level = args.required::<i32>("level", "i32")?;
+                args.finish()?;
+                self.set_level(level);
+                Ok(None)
+            }
+            "getLevel" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                Ok(Some(Box::new(self.get_level())))
+            }
+            _ => Err(runtime::DispatchError::UnknownMethod(method.to_string())),
+        }
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/debug_server.frm"),
+        sha256: Some("a7c790cfe9941956ee91df69b0f992b5ce29d377800fcac10d41893d8f516677"),
+        name: "Dimmer",
+        variables: &[
+            NameInfo {
+                name: "brightness",
+                vtype: "i32",
+            },
+        ],
+        states: &[
+            STATE_OFF,
+            STATE_ON,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "toggle",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "setLevel",
+            parameters: &[
+                NameInfo {
+                    name: "level",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "getLevel",
+            parameters: &[],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "Off:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Off:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "On:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "On:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_OFF,
+            target: STATE_ON,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_ON,
+            target: STATE_OFF,
+        },
+    ];
+    static STATE_OFF: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Off",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_ON: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "On",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,676 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DeepHistoryState),
+    Exit(DeepHistoryState),
+    Next,
+    Pause,
+    Resume,
+    ResumeShallow,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DeepHistoryState::Idle) => write!(f, "Idle:>"),
+            FrameMessage::Enter(DeepHistoryState::Running) => write!(f, "Running:>"),
+            FrameMessage::Enter(DeepHistoryState::Playing) => write!(f, "Playing:>"),
+            FrameMessage::Enter(DeepHistoryState::Buffering) => write!(f, "Buffering:>"),
+            FrameMessage::Enter(DeepHistoryState::Paused) => write!(f, "Paused:>"),
+            FrameMessage::Exit(DeepHistoryState::Idle) => write!(f, "Idle:<"),
+            FrameMessage::Exit(DeepHistoryState::Running) => write!(f, "Running:<"),
+            FrameMessage::Exit(DeepHistoryState::Playing) => write!(f, "Playing:<"),
+            FrameMessage::Exit(DeepHistoryState::Buffering) => write!(f, "Buffering:<"),
+            FrameMessage::Exit(DeepHistoryState::Paused) => write!(f, "Paused:<"),
+            FrameMessage::Next => write!(f, "next"),
+            FrameMessage::Pause => write!(f, "pause"),
+            FrameMessage::Resume => write!(f, "resume"),
+            FrameMessage::ResumeShallow => write!(f, "resume_shallow"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<DeepHistory> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <DeepHistory as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DeepHistoryState {
+    Idle,
+    Running,
+    Playing,
+    Buffering,
+    Paused,
+}
+
+impl runtime::State<DeepHistory> for DeepHistoryState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            DeepHistoryState::Idle => runtime_info::machine_info().states[0],
+            DeepHistoryState::Running => runtime_info::machine_info().states[1],
+            DeepHistoryState::Playing => runtime_info::machine_info().states[2],
+            DeepHistoryState::Buffering => runtime_info::machine_info().states[3],
+            DeepHistoryState::Paused => runtime_info::machine_info().states[4],
+        }
+    }
+    fn arguments(&self) -> <DeepHistory as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <DeepHistory as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct DeepHistory {
+    state: DeepHistoryState,
+    state_history: std::collections::HashMap<DeepHistoryState, DeepHistoryState>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for DeepHistory {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for DeepHistory {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <DeepHistory as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for DeepHistory {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl DeepHistory {
+    
+    pub fn new() -> Self {
+        let mut machine = DeepHistory {
+            state: DeepHistoryState::Idle,
+            state_history: std::collections::HashMap::new(),
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pause(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Pause, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn resume(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Resume, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn resume_shallow(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ResumeShallow, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn idle_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Resume => {
+                // Start transition
+                let next_state = self.state_history_restore(DeepHistoryState::Running);
+                self.transition(runtime_info::machine_info().transitions[0], next_state);
+                return;
+            }
+            FrameMessage::ResumeShallow => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[1], DeepHistoryState::Running);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn running_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("Running:>"));
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[2], DeepHistoryState::Playing);
+                return;
+            }
+            FrameMessage::Pause => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[3], DeepHistoryState::Paused);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn playing_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("Playing:>"));
+            }
+            FrameMessage::Next => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[4], DeepHistoryState::Buffering);
+                return;
+            }
+            _ => {}
+        }
+        self.running_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn buffering_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("Buffering:>"));
+            }
+            FrameMessage::Next => {
+                return;
+            }
+            _ => {}
+        }
+        self.playing_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn paused_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Resume => {
+                // Start transition
+                let next_state = self.state_history_restore(DeepHistoryState::Running);
+                self.transition(runtime_info::machine_info().transitions[5], next_state);
+                return;
+            }
+            FrameMessage::ResumeShallow => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[6], DeepHistoryState::Running);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            DeepHistoryState::Idle => self.idle_handler(frame_event.clone()),
+            DeepHistoryState::Running => self.running_handler(frame_event.clone()),
+            DeepHistoryState::Playing => self.playing_handler(frame_event.clone()),
+            DeepHistoryState::Buffering => self.buffering_handler(frame_event.clone()),
+            DeepHistoryState::Paused => self.paused_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: DeepHistoryState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        self.state_history_record();
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <DeepHistory as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <DeepHistory as runtime::Machine>::StatePtr,
+            exit_event as <DeepHistory as runtime::Machine>::EventPtr,
+            enter_event.clone() as <DeepHistory as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn state_history_record(&mut self) {
+        match self.state {
+            DeepHistoryState::Playing => {
+                self.state_history.insert(DeepHistoryState::Running, self.state);
+            }
+            DeepHistoryState::Buffering => {
+                self.state_history.insert(DeepHistoryState::Playing, self.state);
+                self.state_history.insert(DeepHistoryState::Running, self.state);
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    }
+    
+    fn state_history_restore(&self, state: DeepHistoryState) -> DeepHistoryState {
+        match self.state_history.get(&state) {
+            Some(last_state) => *last_state,
+            None => state,
+        }
+    }
+    
+} // end system controller
+
+impl Default for DeepHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/deep_history.frm"),
+        sha256: Some("146c903a7241ea28281ca2f6ff85e8230925e61cd0f8cd7e9a5453ac42b2ca0d"),
+        name: "DeepHistory",
+        variables: &[
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_IDLE,
+            STATE_RUNNING,
+            STATE_PLAYING,
+            STATE_BUFFERING,
+            STATE_PAUSED,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "next",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "pause",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "resume",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "resume_shallow",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Idle:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Idle:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Running:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Running:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Playing:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Playing:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Buffering:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Buffering:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Paused:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Paused:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::DeepHistory,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_IDLE,
+            target: STATE_RUNNING,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_IDLE,
+            target: STATE_RUNNING,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_RUNNING,
+            target: STATE_PLAYING,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_RUNNING,
+            target: STATE_PAUSED,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_PLAYING,
+            target: STATE_BUFFERING,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::DeepHistory,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_PAUSED,
+            target: STATE_RUNNING,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::Transition,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_PAUSED,
+            target: STATE_RUNNING,
+        },
+    ];
+    static STATE_IDLE: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Idle",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_RUNNING: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Running",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[6],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_PLAYING: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Playing",
+        parent: Some(STATE_RUNNING),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[8],
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_BUFFERING: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Buffering",
+        parent: Some(STATE_PLAYING),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[10],
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_PAUSED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Paused",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+}
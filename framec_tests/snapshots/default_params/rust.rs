@@ -0,0 +1,313 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DefaultParamsState),
+    Exit(DefaultParamsState),
+    Add,
+    Next,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DefaultParamsState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(DefaultParamsState::B) => write!(f, "B:>"),
+            FrameMessage::Exit(DefaultParamsState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(DefaultParamsState::B) => write!(f, "B:<"),
+            FrameMessage::Add => write!(f, "add"),
+            FrameMessage::Next => write!(f, "next"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AddArgs {
+    step: i32,
+}
+
+#[allow(dead_code)]
+struct BEnterArgs {
+    msg: String,
+    val: i32,
+}
+
+#[allow(dead_code)]
+struct BExitArgs {
+    msg: String,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Add(AddArgs),
+    BEnter(BEnterArgs),
+    BExit(BExitArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn add_args(&self) -> &AddArgs {
+        match self {
+            FrameEventArgs::Add(args) => args,
+            _ => panic!("Failed conversion to AddArgs"),
+        }
+    }
+    fn b_enter_args(&self) -> &BEnterArgs {
+        match self {
+            FrameEventArgs::BEnter(args) => args,
+            _ => panic!("Failed conversion to BEnterArgs"),
+        }
+    }
+    fn b_exit_args(&self) -> &BExitArgs {
+        match self {
+            FrameEventArgs::BExit(args) => args,
+            _ => panic!("Failed conversion to BExitArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DefaultParamsState {
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    A(AStateContext),
+    B(BStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct DefaultParams {
+    state: DefaultParamsState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    total: i32,
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl DefaultParams {
+    
+    pub fn new() -> Self {
+        let context = AStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::A(context));
+        let mut machine = DefaultParams {
+            state: DefaultParamsState::A,
+            state_context: next_state_context,
+            total: 0,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn add(&mut self, step: Option<i32>) {
+        let step = step.unwrap_or(1);
+        let frame_args = FrameEventArgs::Add(AddArgs { step, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Add, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::Add => {
+                let assign_temp = {
+                    self.total + frame_event.arguments.as_ref().borrow().add_args().step
+                };
+                self.total = assign_temp;
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                let exit_args = FrameEventArgs::None;
+                let enter_args = FrameEventArgs::BEnter(BEnterArgs {
+                    msg: String::from("hi B"),
+                    val: -1,
+                });
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(exit_args, enter_args, DefaultParamsState::B, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log((frame_event.arguments.as_ref().borrow().b_enter_args().msg).clone());
+                self.log((frame_event.arguments.as_ref().borrow().b_enter_args().val).to_string());
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log((frame_event.arguments.as_ref().borrow().b_exit_args().msg).clone());
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                let exit_args = FrameEventArgs::BExit(BExitArgs {
+                    msg: String::from("bye B"),
+                });
+                let enter_args = FrameEventArgs::BEnter(BEnterArgs {
+                    msg: String::from("again"),
+                    val: 2,
+                });
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(exit_args, enter_args, DefaultParamsState::B, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            DefaultParamsState::A => self.a_handler(frame_event.clone()),
+            DefaultParamsState::B => self.b_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, exit_args: FrameEventArgs, enter_args: FrameEventArgs, new_state: DefaultParamsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            exit_args,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for DefaultParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
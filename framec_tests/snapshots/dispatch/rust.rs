@@ -0,0 +1,842 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(RegisterState),
+    Exit(RegisterState),
+    Deposit,
+    Add,
+    Label,
+    GetBalance,
+    Close,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(RegisterState::Open) => write!(f, "Open:>"),
+            FrameMessage::Enter(RegisterState::Labeled) => write!(f, "Labeled:>"),
+            FrameMessage::Enter(RegisterState::Closed) => write!(f, "Closed:>"),
+            FrameMessage::Exit(RegisterState::Open) => write!(f, "Open:<"),
+            FrameMessage::Exit(RegisterState::Labeled) => write!(f, "Labeled:<"),
+            FrameMessage::Exit(RegisterState::Closed) => write!(f, "Closed:<"),
+            FrameMessage::Deposit => write!(f, "deposit"),
+            FrameMessage::Add => write!(f, "add"),
+            FrameMessage::Label => write!(f, "label"),
+            FrameMessage::GetBalance => write!(f, "getBalance"),
+            FrameMessage::Close => write!(f, "close"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DepositArgs {
+    amount: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for DepositArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "amount" => Some(Box::new(self.amount.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AddArgs {
+    step: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AddArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "step" => Some(Box::new(self.step.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LabelArgs {
+    text: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for LabelArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "text" => Some(Box::new(self.text.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Deposit(DepositArgs),
+    Add(AddArgs),
+    Label(LabelArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Deposit(_) => false,
+            FrameEventArgs::Add(_) => false,
+            FrameEventArgs::Label(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Deposit(args) => args.lookup(name),
+            FrameEventArgs::Add(args) => args.lookup(name),
+            FrameEventArgs::Label(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn deposit_args(&self) -> &DepositArgs {
+        match self {
+            FrameEventArgs::Deposit(args) => args,
+            _ => panic!("Failed conversion to DepositArgs"),
+        }
+    }
+    fn add_args(&self) -> &AddArgs {
+        match self {
+            FrameEventArgs::Add(args) => args,
+            _ => panic!("Failed conversion to AddArgs"),
+        }
+    }
+    fn label_args(&self) -> &LabelArgs {
+        match self {
+            FrameEventArgs::Label(args) => args,
+            _ => panic!("Failed conversion to LabelArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    GetBalance { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_balance_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::GetBalance { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Register> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::GetBalance { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum RegisterState {
+    Open,
+    Labeled,
+    Closed,
+}
+
+#[allow(dead_code)]
+struct OpenStateContext {
+}
+
+impl runtime::State<Register> for OpenStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct LabeledStateArgs {
+    text: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for LabeledStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "text" => Some(Box::new(self.text.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LabeledStateVars {
+    count: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for LabeledStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "count" => Some(Box::new(self.count.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LabeledStateContext {
+    state_args: Rc<RefCell<LabeledStateArgs>>,
+    state_vars: Rc<RefCell<LabeledStateVars>>,
+}
+
+impl runtime::State<Register> for LabeledStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+struct ClosedStateContext {
+}
+
+impl runtime::State<Register> for ClosedStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Open(OpenStateContext),
+    Labeled(LabeledStateContext),
+    Closed(ClosedStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn open_context(&self) -> &OpenStateContext {
+        match self {
+            StateContext::Open(context) => context,
+            _ => panic!("Failed conversion to OpenStateContext"),
+        }
+    }
+    fn labeled_context(&self) -> &LabeledStateContext {
+        match self {
+            StateContext::Labeled(context) => context,
+            _ => panic!("Failed conversion to LabeledStateContext"),
+        }
+    }
+    fn closed_context(&self) -> &ClosedStateContext {
+        match self {
+            StateContext::Closed(context) => context,
+            _ => panic!("Failed conversion to ClosedStateContext"),
+        }
+    }
+}
+
+impl runtime::State<Register> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Open(context) => context.info(),
+            StateContext::Labeled(context) => context.info(),
+            StateContext::Closed(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Open(context) => context.arguments(),
+            StateContext::Labeled(context) => context.arguments(),
+            StateContext::Closed(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <Register as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Open(context) => context.variables(),
+            StateContext::Labeled(context) => context.variables(),
+            StateContext::Closed(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Register {
+    state: RegisterState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    balance: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Register {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "balance" => Some(Box::new(self.balance.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Register {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Register as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Register {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Register {
+    
+    pub fn new() -> Self {
+        let context = OpenStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Open(context));
+        let mut machine = Register {
+            state: RegisterState::Open,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            balance: 0,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn deposit(&mut self, amount: i32) {
+        let frame_args = FrameEventArgs::Deposit(DepositArgs { amount, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Deposit, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn add(&mut self, step: Option<i32>) {
+        let step = step.unwrap_or(1);
+        let frame_args = FrameEventArgs::Add(AddArgs { step, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Add, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn label(&mut self, text: String) {
+        let frame_args = FrameEventArgs::Label(LabelArgs { text, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Label, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_balance(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetBalance, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetBalance { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getBalance"),
+        };
+        return_value
+    }
+    
+    pub fn close(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Close, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn open_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.open_context();
+        match frame_event.message {
+            FrameMessage::Deposit => {
+                let assign_temp = {
+                    self.balance + frame_event.arguments.as_ref().borrow().deposit_args().amount
+                };
+                self.balance = assign_temp;
+                return;
+            }
+            FrameMessage::Add => {
+                let assign_temp = {
+                    self.balance + frame_event.arguments.as_ref().borrow().add_args().step
+                };
+                self.balance = assign_temp;
+                return;
+            }
+            FrameMessage::Label => {
+                // Start transition
+                let context = LabeledStateContext {
+                    state_args: Rc::new(RefCell::new(LabeledStateArgs {
+                        text: frame_event.arguments.as_ref().borrow().label_args().text.clone(),
+                    })),
+                    state_vars: Rc::new(RefCell::new(LabeledStateVars {
+                        count: 1,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Labeled(context));
+                self.transition(runtime_info::machine_info().transitions[0], RegisterState::Labeled, next_state_context);
+                return;
+            }
+            FrameMessage::GetBalance => {
+                frame_event.ret.replace(FrameEventReturn::GetBalance {
+                    return_value: self.balance
+                });
+                return;
+            }
+            FrameMessage::Close => {
+                // Start transition
+                let context = ClosedStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Closed(context));
+                self.transition(runtime_info::machine_info().transitions[1], RegisterState::Closed, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn labeled_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.labeled_context();
+        match frame_event.message {
+            FrameMessage::GetBalance => {
+                frame_event.ret.replace(FrameEventReturn::GetBalance {
+                    return_value: self.balance
+                });
+                return;
+            }
+            FrameMessage::Close => {
+                // Start transition
+                let context = ClosedStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Closed(context));
+                self.transition(runtime_info::machine_info().transitions[2], RegisterState::Closed, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn closed_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.closed_context();
+        match frame_event.message {
+            FrameMessage::GetBalance => {
+                frame_event.ret.replace(FrameEventReturn::GetBalance {
+                    return_value: self.balance
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            RegisterState::Open => self.open_handler(frame_event.clone()),
+            RegisterState::Labeled => self.labeled_handler(frame_event.clone()),
+            RegisterState::Closed => self.closed_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: RegisterState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <Register as runtime::Machine>::StatePtr,
+            new_state_context as <Register as runtime::Machine>::StatePtr,
+            exit_event as <Register as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Register as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Register {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl runtime::Dispatch for Register {
+    fn dispatch(&mut self, method: &str, arguments: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, runtime::DispatchError> {
+        match method {
+            "deposit" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let amount = args.required::<i32>("amount", "i32")?;
+                args.finish()?;
+                self.deposit(amount);
+                Ok(None)
+            }
+            "add" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let step = args.optional::<i32>("step", "i32")?;
+                args.finish()?;
+                self.add(step);
+                Ok(None)
+            }
+            "label" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let text = args.required::<String>("text", "String")?;
+                args.finish()?;
+                self.label(text);
+                Ok(None)
+            }
+            "getBalance" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                Ok(Some(Box::new(self.get_balance())))
+            }
+            "close" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                self.close();
+                Ok(None)
+            }
+            _ => Err(runtime::DispatchError::UnknownMethod(method.to_string())),
+        }
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/dispatch.frm"),
+        sha256: Some("65946cddb7bdca20a79eea705a51ee55c2a2fbe134c490f4326fd86372238772"),
+        name: "Register",
+        variables: &[
+            NameInfo {
+                name: "balance",
+                vtype: "i32",
+            },
+        ],
+        states: &[
+            STATE_OPEN,
+            STATE_LABELED,
+            STATE_CLOSED,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "deposit",
+            parameters: &[
+                NameInfo {
+                    name: "amount",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "add",
+            parameters: &[
+                NameInfo {
+                    name: "step",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "label",
+            parameters: &[
+                NameInfo {
+                    name: "text",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "getBalance",
+            parameters: &[],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "close",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Open:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Labeled:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Labeled:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Closed:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_OPEN,
+            target: STATE_LABELED,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_OPEN,
+            target: STATE_CLOSED,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_LABELED,
+            target: STATE_CLOSED,
+        },
+    ];
+    static STATE_OPEN: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Open",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_LABELED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Labeled",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "text",
+                vtype: "String",
+            },
+        ],
+        variables: &[
+            NameInfo {
+                name: "count",
+                vtype: "u32",
+            },
+        ],
+        handlers: &[
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_CLOSED: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Closed",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,284 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(EnumMatchState),
+    Exit(EnumMatchState),
+    Paint,
+    Inspect,
+    Select,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(EnumMatchState::Ready) => write!(f, "Ready:>"),
+            FrameMessage::Enter(EnumMatchState::Round) => write!(f, "Round:>"),
+            FrameMessage::Exit(EnumMatchState::Ready) => write!(f, "Ready:<"),
+            FrameMessage::Exit(EnumMatchState::Round) => write!(f, "Round:<"),
+            FrameMessage::Paint => write!(f, "paint"),
+            FrameMessage::Inspect => write!(f, "inspect"),
+            FrameMessage::Select => write!(f, "select"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct PaintArgs {
+    color: Color,
+}
+
+#[allow(dead_code)]
+struct InspectArgs {
+    color: Color,
+}
+
+#[allow(dead_code)]
+struct SelectArgs {
+    shape: Shape,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Paint(PaintArgs),
+    Inspect(InspectArgs),
+    Select(SelectArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn paint_args(&self) -> &PaintArgs {
+        match self {
+            FrameEventArgs::Paint(args) => args,
+            _ => panic!("Failed conversion to PaintArgs"),
+        }
+    }
+    fn inspect_args(&self) -> &InspectArgs {
+        match self {
+            FrameEventArgs::Inspect(args) => args,
+            _ => panic!("Failed conversion to InspectArgs"),
+        }
+    }
+    fn select_args(&self) -> &SelectArgs {
+        match self {
+            FrameEventArgs::Select(args) => args,
+            _ => panic!("Failed conversion to SelectArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Color {
+    Red,
+    Yellow,
+    Green,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Shape {
+    Circle,
+    Square,
+    Triangle,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum EnumMatchState {
+    Ready,
+    Round,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct EnumMatch {
+    state: EnumMatchState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl EnumMatch {
+    
+    pub fn new() -> Self {
+        let mut machine = EnumMatch {
+            state: EnumMatchState::Ready,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn paint(&mut self, color: Color) {
+        let frame_args = FrameEventArgs::Paint(PaintArgs { color, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Paint, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn inspect(&mut self, color: Color) {
+        let frame_args = FrameEventArgs::Inspect(InspectArgs { color, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Inspect, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn select(&mut self, shape: Shape) {
+        let frame_args = FrameEventArgs::Select(SelectArgs { shape, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Select, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn ready_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Paint => {
+                if (frame_event.arguments.as_ref().borrow().paint_args().color) == Color::Red {
+                    self.log(String::from("stop"));
+                } else if (frame_event.arguments.as_ref().borrow().paint_args().color) == Color::Yellow || (frame_event.arguments.as_ref().borrow().paint_args().color) == Color::Green {
+                    self.log(String::from("go"));
+                }
+                return;
+            }
+            FrameMessage::Inspect => {
+                if (frame_event.arguments.as_ref().borrow().inspect_args().color) == Color::Green {
+                    self.log(String::from("green"));
+                    
+                    return;
+                } else {
+                    self.log(String::from("not green"));
+                }
+                return;
+            }
+            FrameMessage::Select => {
+                if (frame_event.arguments.as_ref().borrow().select_args().shape) == Shape::Circle {
+                    // Start transition
+                    self.transition(EnumMatchState::Round);
+                    
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().select_args().shape) == Shape::Square || (frame_event.arguments.as_ref().borrow().select_args().shape) == Shape::Triangle {
+                    self.log(String::from("angular"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn round_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("round"));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            EnumMatchState::Ready => self.ready_handler(frame_event.clone()),
+            EnumMatchState::Round => self.round_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: EnumMatchState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for EnumMatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
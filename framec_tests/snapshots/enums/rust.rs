@@ -0,0 +1,205 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(EnumsState),
+    Exit(EnumsState),
+    Paint,
+    Reset,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(EnumsState::Ready) => write!(f, "Ready:>"),
+            FrameMessage::Exit(EnumsState::Ready) => write!(f, "Ready:<"),
+            FrameMessage::Paint => write!(f, "paint"),
+            FrameMessage::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct PaintArgs {
+    color: Color,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Paint(PaintArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn paint_args(&self) -> &PaintArgs {
+        match self {
+            FrameEventArgs::Paint(args) => args,
+            _ => panic!("Failed conversion to PaintArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Color {
+    Red,
+    Yellow,
+    Green,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum EnumsState {
+    Ready,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Enums {
+    state: EnumsState,
+    //===================== Domain Block ===================//
+    light: Color,
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Enums {
+    
+    pub fn new() -> Self {
+        let mut machine = Enums {
+            state: EnumsState::Ready,
+            light: Color::Green,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn paint(&mut self, color: Color) {
+        let frame_args = FrameEventArgs::Paint(PaintArgs { color, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Paint, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn reset(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Reset, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn ready_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Paint => {
+                if (frame_event.arguments.as_ref().borrow().paint_args().color) == Color::Red {
+                    self.log(String::from("stop"));
+                } else {
+                    if (frame_event.arguments.as_ref().borrow().paint_args().color) == Color::Green {
+                        self.log(String::from("go"));
+                    } else {
+                        self.log(String::from("wait"));
+                    }
+                }
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().paint_args().color
+                };
+                self.light = assign_temp;
+                return;
+            }
+            FrameMessage::Reset => {
+                let assign_temp = {
+                    Color::Red
+                };
+                self.light = assign_temp;
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            EnumsState::Ready => self.ready_handler(frame_event.clone()),
+        }
+    }
+    
+} // end system controller
+
+impl Default for Enums {
+    fn default() -> Self {
+        Self::new()
+    }
+}
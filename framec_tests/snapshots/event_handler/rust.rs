@@ -0,0 +1,398 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(EventHandlerState),
+    Exit(EventHandlerState),
+    LogIt,
+    LogAdd,
+    LogReturn,
+    PassAdd,
+    PassReturn,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(EventHandlerState::S1) => write!(f, "S1:>"),
+            FrameMessage::Enter(EventHandlerState::S2) => write!(f, "S2:>"),
+            FrameMessage::Exit(EventHandlerState::S1) => write!(f, "S1:<"),
+            FrameMessage::Exit(EventHandlerState::S2) => write!(f, "S2:<"),
+            FrameMessage::LogIt => write!(f, "LogIt"),
+            FrameMessage::LogAdd => write!(f, "LogAdd"),
+            FrameMessage::LogReturn => write!(f, "LogReturn"),
+            FrameMessage::PassAdd => write!(f, "PassAdd"),
+            FrameMessage::PassReturn => write!(f, "PassReturn"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct LogItArgs {
+    x: i32,
+}
+
+#[allow(dead_code)]
+struct LogAddArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(dead_code)]
+struct LogReturnArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(dead_code)]
+struct PassAddArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(dead_code)]
+struct PassReturnArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    LogIt(LogItArgs),
+    LogAdd(LogAddArgs),
+    LogReturn(LogReturnArgs),
+    PassAdd(PassAddArgs),
+    PassReturn(PassReturnArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn log_it_args(&self) -> &LogItArgs {
+        match self {
+            FrameEventArgs::LogIt(args) => args,
+            _ => panic!("Failed conversion to LogItArgs"),
+        }
+    }
+    fn log_add_args(&self) -> &LogAddArgs {
+        match self {
+            FrameEventArgs::LogAdd(args) => args,
+            _ => panic!("Failed conversion to LogAddArgs"),
+        }
+    }
+    fn log_return_args(&self) -> &LogReturnArgs {
+        match self {
+            FrameEventArgs::LogReturn(args) => args,
+            _ => panic!("Failed conversion to LogReturnArgs"),
+        }
+    }
+    fn pass_add_args(&self) -> &PassAddArgs {
+        match self {
+            FrameEventArgs::PassAdd(args) => args,
+            _ => panic!("Failed conversion to PassAddArgs"),
+        }
+    }
+    fn pass_return_args(&self) -> &PassReturnArgs {
+        match self {
+            FrameEventArgs::PassReturn(args) => args,
+            _ => panic!("Failed conversion to PassReturnArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    LogReturn { return_value: i32 },
+    PassReturn { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_log_return_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::LogReturn { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+    fn get_pass_return_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::PassReturn { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum EventHandlerState {
+    S1,
+    S2,
+}
+
+#[allow(dead_code)]
+struct S1StateContext {
+}
+
+#[allow(dead_code)]
+struct S2StateArgs {
+    p: i32,
+}
+
+#[allow(dead_code)]
+struct S2StateContext {
+    state_args: Rc<RefCell<S2StateArgs>>,
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    S1(S1StateContext),
+    S2(S2StateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn s_1_context(&self) -> &S1StateContext {
+        match self {
+            StateContext::S1(context) => context,
+            _ => panic!("Failed conversion to S1StateContext"),
+        }
+    }
+    fn s_2_context(&self) -> &S2StateContext {
+        match self {
+            StateContext::S2(context) => context,
+            _ => panic!("Failed conversion to S2StateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct EventHandler {
+    state: EventHandlerState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl EventHandler {
+    
+    pub fn new() -> Self {
+        let context = S1StateContext {
+        };
+        let next_state_context = Rc::new(StateContext::S1(context));
+        let mut machine = EventHandler {
+            state: EventHandlerState::S1,
+            state_context: next_state_context,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn log_it(&mut self, x: i32) {
+        let frame_args = FrameEventArgs::LogIt(LogItArgs { x, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::LogIt, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn log_add(&mut self, a: i32, b: i32) {
+        let frame_args = FrameEventArgs::LogAdd(LogAddArgs { a, b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::LogAdd, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn log_return(&mut self, a: i32, b: i32) -> i32 {
+        let frame_args = FrameEventArgs::LogReturn(LogReturnArgs { a, b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::LogReturn, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::LogReturn { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for LogReturn"),
+        };
+        return_value
+    }
+    
+    pub fn pass_add(&mut self, a: i32, b: i32) {
+        let frame_args = FrameEventArgs::PassAdd(PassAddArgs { a, b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::PassAdd, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pass_return(&mut self, a: i32, b: i32) -> i32 {
+        let frame_args = FrameEventArgs::PassReturn(PassReturnArgs { a, b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::PassReturn, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::PassReturn { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for PassReturn"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.s_1_context();
+        match frame_event.message {
+            FrameMessage::LogIt => {
+                self.log(String::from("x"),(frame_event.arguments.as_ref().borrow().log_it_args().x));
+                return;
+            }
+            FrameMessage::LogAdd => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().log_add_args().a));
+                self.log(String::from("b"),(frame_event.arguments.as_ref().borrow().log_add_args().b));
+                self.log(String::from("a+b"),(frame_event.arguments.as_ref().borrow().log_add_args().a) + (frame_event.arguments.as_ref().borrow().log_add_args().b));
+                return;
+            }
+            FrameMessage::LogReturn => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().log_return_args().a));
+                self.log(String::from("b"),(frame_event.arguments.as_ref().borrow().log_return_args().b));
+                let r = frame_event.arguments.as_ref().borrow().log_return_args().a + frame_event.arguments.as_ref().borrow().log_return_args().b;
+                self.log(String::from("r"),r);
+                frame_event.ret.replace(FrameEventReturn::LogReturn {
+                    return_value: r
+                });
+                return;
+            }
+            FrameMessage::PassAdd => {
+                // Start transition
+                let context = S2StateContext {
+                    state_args: Rc::new(RefCell::new(S2StateArgs {
+                        p: frame_event.arguments.as_ref().borrow().pass_add_args().a + frame_event.arguments.as_ref().borrow().pass_add_args().b,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::S2(context));
+                self.transition(EventHandlerState::S2, next_state_context);
+                return;
+            }
+            FrameMessage::PassReturn => {
+                let r = frame_event.arguments.as_ref().borrow().pass_return_args().a + frame_event.arguments.as_ref().borrow().pass_return_args().b;
+                self.log(String::from("r"),r);
+                // Start transition
+                let context = S2StateContext {
+                    state_args: Rc::new(RefCell::new(S2StateArgs {
+                        p: r,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::S2(context));
+                self.transition(EventHandlerState::S2, next_state_context);
+                frame_event.ret.replace(FrameEventReturn::PassReturn {
+                    return_value: r
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_2_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.s_2_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("p"),(this_state_context.state_args.as_ref().borrow().p));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            EventHandlerState::S1 => self.s_1_handler(frame_event.clone()),
+            EventHandlerState::S2 => self.s_2_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: EventHandlerState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,1213 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(EventMonitorSmState),
+    Exit(EventMonitorSmState),
+    Change,
+    Transit,
+    Mult,
+    Reset,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(EventMonitorSmState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(EventMonitorSmState::B) => write!(f, "B:>"),
+            FrameMessage::Enter(EventMonitorSmState::C) => write!(f, "C:>"),
+            FrameMessage::Enter(EventMonitorSmState::D) => write!(f, "D:>"),
+            FrameMessage::Exit(EventMonitorSmState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(EventMonitorSmState::B) => write!(f, "B:<"),
+            FrameMessage::Exit(EventMonitorSmState::C) => write!(f, "C:<"),
+            FrameMessage::Exit(EventMonitorSmState::D) => write!(f, "D:<"),
+            FrameMessage::Change => write!(f, "change"),
+            FrameMessage::Transit => write!(f, "transit"),
+            FrameMessage::Mult => write!(f, "mult"),
+            FrameMessage::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct TransitArgs {
+    x: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for TransitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "x" => Some(Box::new(self.x.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct MultArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for MultArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "a" => Some(Box::new(self.a.clone())),
+            "b" => Some(Box::new(self.b.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AExitArgs {
+    a_out: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "a_out" => Some(Box::new(self.a_out.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BEnterArgs {
+    b_in: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "b_in" => Some(Box::new(self.b_in.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BExitArgs {
+    b_out: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "b_out" => Some(Box::new(self.b_out.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct CEnterArgs {
+    c_in: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for CEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "c_in" => Some(Box::new(self.c_in.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct CExitArgs {
+    c_out: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for CExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "c_out" => Some(Box::new(self.c_out.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DEnterArgs {
+    d_in: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for DEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "d_in" => Some(Box::new(self.d_in.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DExitArgs {
+    d_out: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for DExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "d_out" => Some(Box::new(self.d_out.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Transit(TransitArgs),
+    Mult(MultArgs),
+    AExit(AExitArgs),
+    BEnter(BEnterArgs),
+    BExit(BExitArgs),
+    CEnter(CEnterArgs),
+    CExit(CExitArgs),
+    DEnter(DEnterArgs),
+    DExit(DExitArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Transit(_) => false,
+            FrameEventArgs::Mult(_) => false,
+            FrameEventArgs::AExit(_) => false,
+            FrameEventArgs::BEnter(_) => false,
+            FrameEventArgs::BExit(_) => false,
+            FrameEventArgs::CEnter(_) => false,
+            FrameEventArgs::CExit(_) => false,
+            FrameEventArgs::DEnter(_) => false,
+            FrameEventArgs::DExit(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Transit(args) => args.lookup(name),
+            FrameEventArgs::Mult(args) => args.lookup(name),
+            FrameEventArgs::AExit(args) => args.lookup(name),
+            FrameEventArgs::BEnter(args) => args.lookup(name),
+            FrameEventArgs::BExit(args) => args.lookup(name),
+            FrameEventArgs::CEnter(args) => args.lookup(name),
+            FrameEventArgs::CExit(args) => args.lookup(name),
+            FrameEventArgs::DEnter(args) => args.lookup(name),
+            FrameEventArgs::DExit(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn transit_args(&self) -> &TransitArgs {
+        match self {
+            FrameEventArgs::Transit(args) => args,
+            _ => panic!("Failed conversion to TransitArgs"),
+        }
+    }
+    fn mult_args(&self) -> &MultArgs {
+        match self {
+            FrameEventArgs::Mult(args) => args,
+            _ => panic!("Failed conversion to MultArgs"),
+        }
+    }
+    fn a_exit_args(&self) -> &AExitArgs {
+        match self {
+            FrameEventArgs::AExit(args) => args,
+            _ => panic!("Failed conversion to AExitArgs"),
+        }
+    }
+    fn b_enter_args(&self) -> &BEnterArgs {
+        match self {
+            FrameEventArgs::BEnter(args) => args,
+            _ => panic!("Failed conversion to BEnterArgs"),
+        }
+    }
+    fn b_exit_args(&self) -> &BExitArgs {
+        match self {
+            FrameEventArgs::BExit(args) => args,
+            _ => panic!("Failed conversion to BExitArgs"),
+        }
+    }
+    fn c_enter_args(&self) -> &CEnterArgs {
+        match self {
+            FrameEventArgs::CEnter(args) => args,
+            _ => panic!("Failed conversion to CEnterArgs"),
+        }
+    }
+    fn c_exit_args(&self) -> &CExitArgs {
+        match self {
+            FrameEventArgs::CExit(args) => args,
+            _ => panic!("Failed conversion to CExitArgs"),
+        }
+    }
+    fn d_enter_args(&self) -> &DEnterArgs {
+        match self {
+            FrameEventArgs::DEnter(args) => args,
+            _ => panic!("Failed conversion to DEnterArgs"),
+        }
+    }
+    fn d_exit_args(&self) -> &DExitArgs {
+        match self {
+            FrameEventArgs::DExit(args) => args,
+            _ => panic!("Failed conversion to DExitArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    Change { return_value: u32 },
+    Mult { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_change_ret(&self) -> u32 {
+        match self {
+            FrameEventReturn::Change { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+    fn get_mult_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::Mult { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<EventMonitorSm> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::Change { return_value } => Some(Box::new(return_value.clone())),
+            FrameEventReturn::Mult { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum EventMonitorSmState {
+    A,
+    B,
+    C,
+    D,
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+}
+
+impl runtime::State<EventMonitorSm> for AStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+}
+
+impl runtime::State<EventMonitorSm> for BStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct CStateContext {
+}
+
+impl runtime::State<EventMonitorSm> for CStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct DStateContext {
+}
+
+impl runtime::State<EventMonitorSm> for DStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[3]
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    A(AStateContext),
+    B(BStateContext),
+    C(CStateContext),
+    D(DStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+    fn c_context(&self) -> &CStateContext {
+        match self {
+            StateContext::C(context) => context,
+            _ => panic!("Failed conversion to CStateContext"),
+        }
+    }
+    fn d_context(&self) -> &DStateContext {
+        match self {
+            StateContext::D(context) => context,
+            _ => panic!("Failed conversion to DStateContext"),
+        }
+    }
+}
+
+impl runtime::State<EventMonitorSm> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::A(context) => context.info(),
+            StateContext::B(context) => context.info(),
+            StateContext::C(context) => context.info(),
+            StateContext::D(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::A(context) => context.arguments(),
+            StateContext::B(context) => context.arguments(),
+            StateContext::C(context) => context.arguments(),
+            StateContext::D(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <EventMonitorSm as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::A(context) => context.variables(),
+            StateContext::B(context) => context.variables(),
+            StateContext::C(context) => context.variables(),
+            StateContext::D(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct EventMonitorSm {
+    state: EventMonitorSmState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for EventMonitorSm {
+    fn is_empty(&self) -> bool {
+        true
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for EventMonitorSm {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <EventMonitorSm as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for EventMonitorSm {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl EventMonitorSm {
+    
+    pub fn new() -> Self {
+        let context = AStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::A(context));
+        let mut machine = EventMonitorSm {
+            state: EventMonitorSmState::A,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(5), Some(3)),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn change(&mut self) -> u32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Change, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::Change { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for change"),
+        };
+        return_value
+    }
+    
+    pub fn transit(&mut self, x: u32) {
+        let frame_args = FrameEventArgs::Transit(TransitArgs { x, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Transit, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn mult(&mut self, a: i32, b: i32) -> i32 {
+        let frame_args = FrameEventArgs::Mult(MultArgs { a, b, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Mult, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::Mult { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for mult"),
+        };
+        return_value
+    }
+    
+    pub fn reset(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Reset, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::Exit(_) => {
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.change_state(runtime_info::machine_info().transitions[0], EventMonitorSmState::B, next_state_context);
+                frame_event.ret.replace(FrameEventReturn::Change {
+                    return_value: 2
+                });
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                let exit_args = FrameEventArgs::AExit(AExitArgs {
+                    a_out: 3,
+                });
+                let enter_args = FrameEventArgs::BEnter(BEnterArgs {
+                    b_in: 4,
+                });
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[1], exit_args, enter_args, EventMonitorSmState::B, next_state_context);
+                return;
+            }
+            FrameMessage::Mult => {
+                let out = frame_event.arguments.as_ref().borrow().mult_args().a * frame_event.arguments.as_ref().borrow().mult_args().b;
+                frame_event.ret.replace(FrameEventReturn::Mult {
+                    return_value: out
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.transit(11);
+                return;
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = CStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::C(context));
+                self.change_state(runtime_info::machine_info().transitions[2], EventMonitorSmState::C, next_state_context);
+                frame_event.ret.replace(FrameEventReturn::Change {
+                    return_value: 12
+                });
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                let exit_args = FrameEventArgs::BExit(BExitArgs {
+                    b_out: 13,
+                });
+                let enter_args = FrameEventArgs::CEnter(CEnterArgs {
+                    c_in: 14,
+                });
+                let context = CStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::C(context));
+                self.transition(runtime_info::machine_info().transitions[3], exit_args, enter_args, EventMonitorSmState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Mult => {
+                let out = frame_event.arguments.as_ref().borrow().mult_args().a * frame_event.arguments.as_ref().borrow().mult_args().b;
+                frame_event.ret.replace(FrameEventReturn::Mult {
+                    return_value: out
+                });
+                return;
+            }
+            FrameMessage::Reset => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[4], EventMonitorSmState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn c_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.c_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.transit(21);
+                return;
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = DStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::D(context));
+                self.change_state(runtime_info::machine_info().transitions[5], EventMonitorSmState::D, next_state_context);
+                frame_event.ret.replace(FrameEventReturn::Change {
+                    return_value: 22
+                });
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                let exit_args = FrameEventArgs::CExit(CExitArgs {
+                    c_out: 23,
+                });
+                let enter_args = FrameEventArgs::DEnter(DEnterArgs {
+                    d_in: 24,
+                });
+                let context = DStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::D(context));
+                self.transition(runtime_info::machine_info().transitions[6], exit_args, enter_args, EventMonitorSmState::D, next_state_context);
+                return;
+            }
+            FrameMessage::Mult => {
+                let out = frame_event.arguments.as_ref().borrow().mult_args().a * frame_event.arguments.as_ref().borrow().mult_args().b;
+                frame_event.ret.replace(FrameEventReturn::Mult {
+                    return_value: out
+                });
+                return;
+            }
+            FrameMessage::Reset => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[7], EventMonitorSmState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn d_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.d_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.change();
+                return;
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[8], EventMonitorSmState::A, next_state_context);
+                frame_event.ret.replace(FrameEventReturn::Change {
+                    return_value: 32
+                });
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                let exit_args = FrameEventArgs::DExit(DExitArgs {
+                    d_out: 33,
+                });
+                let enter_args = FrameEventArgs::None;
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[9], exit_args, enter_args, EventMonitorSmState::A, next_state_context);
+                return;
+            }
+            FrameMessage::Mult => {
+                let out = frame_event.arguments.as_ref().borrow().mult_args().a * frame_event.arguments.as_ref().borrow().mult_args().b;
+                frame_event.ret.replace(FrameEventReturn::Mult {
+                    return_value: out
+                });
+                return;
+            }
+            FrameMessage::Reset => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[10], EventMonitorSmState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            EventMonitorSmState::A => self.a_handler(frame_event.clone()),
+            EventMonitorSmState::B => self.b_handler(frame_event.clone()),
+            EventMonitorSmState::C => self.c_handler(frame_event.clone()),
+            EventMonitorSmState::D => self.d_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, exit_args: FrameEventArgs, enter_args: FrameEventArgs, new_state: EventMonitorSmState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            exit_args,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <EventMonitorSm as runtime::Machine>::StatePtr,
+            new_state_context as <EventMonitorSm as runtime::Machine>::StatePtr,
+            exit_event as <EventMonitorSm as runtime::Machine>::EventPtr,
+            enter_event.clone() as <EventMonitorSm as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: EventMonitorSmState, new_state_context: Rc<StateContext>) {
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            old_state_context as <EventMonitorSm as runtime::Machine>::StatePtr,
+            new_state_context as <EventMonitorSm as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for EventMonitorSm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/event_monitor.frm"),
+        sha256: Some("665460d7081571f970b99caa23876e6f2e3db211c32091457147b1aa46265eed"),
+        name: "EventMonitorSm",
+        variables: &[
+        ],
+        states: &[
+            STATE_A,
+            STATE_B,
+            STATE_C,
+            STATE_D,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "change",
+            parameters: &[],
+            return_type: Some("u32"),
+        },
+        &MethodInfo {
+            name: "transit",
+            parameters: &[
+                NameInfo {
+                    name: "x",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "mult",
+            parameters: &[
+                NameInfo {
+                    name: "a",
+                    vtype: "i32",
+                },
+                NameInfo {
+                    name: "b",
+                    vtype: "i32",
+                },
+            ],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "reset",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[
+                NameInfo {
+                    name: "a_out",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[
+                NameInfo {
+                    name: "b_in",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[
+                NameInfo {
+                    name: "b_out",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:>",
+            parameters: &[
+                NameInfo {
+                    name: "c_in",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:<",
+            parameters: &[
+                NameInfo {
+                    name: "c_out",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "D:>",
+            parameters: &[
+                NameInfo {
+                    name: "d_in",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "D:<",
+            parameters: &[
+                NameInfo {
+                    name: "d_out",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_B,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_C,
+            target: STATE_D,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_C,
+            target: STATE_D,
+        },
+        &TransitionInfo {
+            id: 7,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_C,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 8,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_D,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 9,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_D,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 10,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_D,
+            target: STATE_A,
+        },
+    ];
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[5],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[6],
+            EVENTS[7],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_C: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "C",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[8],
+            EVENTS[9],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_D: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "D",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[10],
+            EVENTS[11],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+}
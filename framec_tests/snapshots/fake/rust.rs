@@ -0,0 +1,348 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(DimmerState),
+    Exit(DimmerState),
+    TurnOn,
+    Dim,
+    GetBrightness,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(DimmerState::Off) => write!(f, "Off:>"),
+            FrameMessage::Enter(DimmerState::On) => write!(f, "On:>"),
+            FrameMessage::Exit(DimmerState::Off) => write!(f, "Off:<"),
+            FrameMessage::Exit(DimmerState::On) => write!(f, "On:<"),
+            FrameMessage::TurnOn => write!(f, "turnOn"),
+            FrameMessage::Dim => write!(f, "dim"),
+            FrameMessage::GetBrightness => write!(f, "getBrightness"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DimArgs {
+    level: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Dim(DimArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn dim_args(&self) -> &DimArgs {
+        match self {
+            FrameEventArgs::Dim(args) => args,
+            _ => panic!("Failed conversion to DimArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    GetBrightness { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_brightness_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::GetBrightness { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum DimmerState {
+    Off,
+    On,
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Dimmer {
+    state: DimmerState,
+    //===================== Domain Block ===================//
+    brightness: i32,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Dimmer {
+    
+    pub fn new() -> Self {
+        let mut machine = Dimmer {
+            state: DimmerState::Off,
+            brightness: 10,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn turn_on(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::TurnOn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn dim(&mut self, level: Option<i32>) {
+        let level = level.unwrap_or(5);
+        let frame_args = FrameEventArgs::Dim(DimArgs { level, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Dim, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_brightness(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetBrightness, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetBrightness { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getBrightness"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::TurnOn => {
+                // Start transition
+                self.transition(DimmerState::On);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Dim => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().dim_args().level
+                };
+                self.brightness = assign_temp;
+                return;
+            }
+            FrameMessage::GetBrightness => {
+                frame_event.ret.replace(FrameEventReturn::GetBrightness {
+                    return_value: self.brightness
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            DimmerState::Off => self.off_handler(frame_event.clone()),
+            DimmerState::On => self.on_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: DimmerState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Dimmer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The interface of `Dimmer`, implemented by the machine and by `FakeDimmer`.
+#[allow(dead_code)]
+pub trait DimmerInterface {
+    fn turn_on(&mut self);
+    fn dim(&mut self, level: Option<i32>);
+    fn get_brightness(&mut self) -> i32;
+    /// The current state of the machine.
+    fn state(&self) -> DimmerState;
+}
+
+impl DimmerInterface for Dimmer {
+    fn turn_on(&mut self) {
+        Dimmer::turn_on(self)
+    }
+    fn dim(&mut self, level: Option<i32>) {
+        Dimmer::dim(self, level)
+    }
+    fn get_brightness(&mut self) -> i32 {
+        Dimmer::get_brightness(self)
+    }
+    fn state(&self) -> DimmerState {
+        self.state
+    }
+}
+
+/// A stand-in for `Dimmer`, whose interface methods return scripted values and enter
+/// scripted states instead of running the machine. Calls that are not scripted return the
+/// default value of their return type and keep the current state.
+#[allow(dead_code)]
+pub struct FakeDimmer {
+    state: DimmerState,
+    calls: Vec<String>,
+    turn_on_script: std::collections::VecDeque<Option<DimmerState>>,
+    dim_script: std::collections::VecDeque<Option<DimmerState>>,
+    get_brightness_script: std::collections::VecDeque<(i32, Option<DimmerState>)>,
+}
+
+#[allow(dead_code)]
+impl FakeDimmer {
+    /// A fake in the given state, with an empty script.
+    pub fn new(state: DimmerState) -> Self {
+        FakeDimmer {
+            state,
+            calls: Vec::new(),
+            turn_on_script: Default::default(),
+            dim_script: Default::default(),
+            get_brightness_script: Default::default(),
+        }
+    }
+    
+    /// Script the next call of `turn_on` to enter `state` unless it is `None`.
+    pub fn script_turn_on(&mut self, state: Option<DimmerState>) -> &mut Self {
+        self.turn_on_script.push_back(state);
+        self
+    }
+    
+    /// Script the next call of `dim` to enter `state` unless it is `None`.
+    pub fn script_dim(&mut self, state: Option<DimmerState>) -> &mut Self {
+        self.dim_script.push_back(state);
+        self
+    }
+    
+    /// Script the next call of `get_brightness` to return `value`, and to enter `state` unless it is `None`.
+    pub fn script_get_brightness(&mut self, value: i32, state: Option<DimmerState>) -> &mut Self {
+        self.get_brightness_script.push_back((value, state));
+        self
+    }
+    
+    /// Take the calls of the interface methods since the last call, formatted like call
+    /// expressions with the arguments formatted with `Debug`.
+    pub fn take_calls(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.calls)
+    }
+    
+    /// Whether all of the scripted calls have been made.
+    pub fn is_script_done(&self) -> bool {
+        self.turn_on_script.is_empty() && self.dim_script.is_empty() && self.get_brightness_script.is_empty()
+    }
+}
+
+impl DimmerInterface for FakeDimmer {
+    fn turn_on(&mut self) {
+        self.calls.push("turn_on()".to_string());
+        let state = self.turn_on_script.pop_front().flatten();
+        if let Some(state) = state {
+            self.state = state;
+        }
+    }
+    fn dim(&mut self, level: Option<i32>) {
+        self.calls.push(format!("dim({:?})", level));
+        let state = self.dim_script.pop_front().flatten();
+        if let Some(state) = state {
+            self.state = state;
+        }
+    }
+    fn get_brightness(&mut self) -> i32 {
+        self.calls.push("get_brightness()".to_string());
+        let (value, state) = self.get_brightness_script.pop_front().unwrap_or_default();
+        if let Some(state) = state {
+            self.state = state;
+        }
+        value
+    }
+    fn state(&self) -> DimmerState {
+        self.state
+    }
+}
@@ -0,0 +1,607 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(HandlerCallsState),
+    Exit(HandlerCallsState),
+    NonRec,
+    SelfRec,
+    MutRec,
+    Call,
+    Foo,
+    Bar,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(HandlerCallsState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(HandlerCallsState::NonRecursive) => write!(f, "NonRecursive:>"),
+            FrameMessage::Enter(HandlerCallsState::SelfRecursive) => write!(f, "SelfRecursive:>"),
+            FrameMessage::Enter(HandlerCallsState::MutuallyRecursive) => write!(f, "MutuallyRecursive:>"),
+            FrameMessage::Enter(HandlerCallsState::Final) => write!(f, "Final:>"),
+            FrameMessage::Exit(HandlerCallsState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(HandlerCallsState::NonRecursive) => write!(f, "NonRecursive:<"),
+            FrameMessage::Exit(HandlerCallsState::SelfRecursive) => write!(f, "SelfRecursive:<"),
+            FrameMessage::Exit(HandlerCallsState::MutuallyRecursive) => write!(f, "MutuallyRecursive:<"),
+            FrameMessage::Exit(HandlerCallsState::Final) => write!(f, "Final:<"),
+            FrameMessage::NonRec => write!(f, "NonRec"),
+            FrameMessage::SelfRec => write!(f, "SelfRec"),
+            FrameMessage::MutRec => write!(f, "MutRec"),
+            FrameMessage::Call => write!(f, "Call"),
+            FrameMessage::Foo => write!(f, "Foo"),
+            FrameMessage::Bar => write!(f, "Bar"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct CallArgs {
+    event: String,
+    arg: i32,
+}
+
+#[allow(dead_code)]
+struct FooArgs {
+    arg: i32,
+}
+
+#[allow(dead_code)]
+struct BarArgs {
+    arg: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Call(CallArgs),
+    Foo(FooArgs),
+    Bar(BarArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn call_args(&self) -> &CallArgs {
+        match self {
+            FrameEventArgs::Call(args) => args,
+            _ => panic!("Failed conversion to CallArgs"),
+        }
+    }
+    fn foo_args(&self) -> &FooArgs {
+        match self {
+            FrameEventArgs::Foo(args) => args,
+            _ => panic!("Failed conversion to FooArgs"),
+        }
+    }
+    fn bar_args(&self) -> &BarArgs {
+        match self {
+            FrameEventArgs::Bar(args) => args,
+            _ => panic!("Failed conversion to BarArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum HandlerCallsState {
+    Init,
+    NonRecursive,
+    SelfRecursive,
+    MutuallyRecursive,
+    Final,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+#[allow(dead_code)]
+struct NonRecursiveStateVars {
+    counter: i32,
+}
+
+#[allow(dead_code)]
+struct NonRecursiveStateContext {
+    state_vars: Rc<RefCell<NonRecursiveStateVars>>,
+}
+
+#[allow(dead_code)]
+struct SelfRecursiveStateVars {
+    counter: i32,
+}
+
+#[allow(dead_code)]
+struct SelfRecursiveStateContext {
+    state_vars: Rc<RefCell<SelfRecursiveStateVars>>,
+}
+
+#[allow(dead_code)]
+struct MutuallyRecursiveStateVars {
+    counter: i32,
+}
+
+#[allow(dead_code)]
+struct MutuallyRecursiveStateContext {
+    state_vars: Rc<RefCell<MutuallyRecursiveStateVars>>,
+}
+
+#[allow(dead_code)]
+struct FinalStateArgs {
+    counter: i32,
+}
+
+#[allow(dead_code)]
+struct FinalStateContext {
+    state_args: Rc<RefCell<FinalStateArgs>>,
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    NonRecursive(NonRecursiveStateContext),
+    SelfRecursive(SelfRecursiveStateContext),
+    MutuallyRecursive(MutuallyRecursiveStateContext),
+    Final(FinalStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn non_recursive_context(&self) -> &NonRecursiveStateContext {
+        match self {
+            StateContext::NonRecursive(context) => context,
+            _ => panic!("Failed conversion to NonRecursiveStateContext"),
+        }
+    }
+    fn self_recursive_context(&self) -> &SelfRecursiveStateContext {
+        match self {
+            StateContext::SelfRecursive(context) => context,
+            _ => panic!("Failed conversion to SelfRecursiveStateContext"),
+        }
+    }
+    fn mutually_recursive_context(&self) -> &MutuallyRecursiveStateContext {
+        match self {
+            StateContext::MutuallyRecursive(context) => context,
+            _ => panic!("Failed conversion to MutuallyRecursiveStateContext"),
+        }
+    }
+    fn final_context(&self) -> &FinalStateContext {
+        match self {
+            StateContext::Final(context) => context,
+            _ => panic!("Failed conversion to FinalStateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct HandlerCalls {
+    state: HandlerCallsState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl HandlerCalls {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = HandlerCalls {
+            state: HandlerCallsState::Init,
+            state_context: next_state_context,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn non_rec(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::NonRec, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn self_rec(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::SelfRec, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn mut_rec(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::MutRec, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn call(&mut self, event: String, arg: i32) {
+        let frame_args = FrameEventArgs::Call(CallArgs { event, arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Call, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn foo(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Foo(FooArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Foo, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn bar(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Bar(BarArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Bar, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::NonRec => {
+                // Start transition
+                let context = NonRecursiveStateContext {
+                    state_vars: Rc::new(RefCell::new(NonRecursiveStateVars {
+                        counter: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::NonRecursive(context));
+                self.transition(HandlerCallsState::NonRecursive, next_state_context);
+                return;
+            }
+            FrameMessage::SelfRec => {
+                // Start transition
+                let context = SelfRecursiveStateContext {
+                    state_vars: Rc::new(RefCell::new(SelfRecursiveStateVars {
+                        counter: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::SelfRecursive(context));
+                self.transition(HandlerCallsState::SelfRecursive, next_state_context);
+                return;
+            }
+            FrameMessage::MutRec => {
+                // Start transition
+                let context = MutuallyRecursiveStateContext {
+                    state_vars: Rc::new(RefCell::new(MutuallyRecursiveStateVars {
+                        counter: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::MutuallyRecursive(context));
+                self.transition(HandlerCallsState::MutuallyRecursive, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn non_recursive_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.non_recursive_context();
+        match frame_event.message {
+            FrameMessage::Foo => {
+                self.log(String::from("Foo"),(frame_event.arguments.as_ref().borrow().foo_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().foo_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                self.bar((frame_event.arguments.as_ref().borrow().foo_args().arg) * 2);
+                return;
+                self.log(String::from("Unreachable"),0);
+                return;
+            }
+              //  the front-end should report the next line as a static error
+            FrameMessage::Bar => {
+                self.log(String::from("Bar"),(frame_event.arguments.as_ref().borrow().bar_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().bar_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        counter: this_state_context.state_vars.as_ref().borrow().counter,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(HandlerCallsState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Foo") {
+                    self.foo((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Bar") {
+                    self.bar((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else {
+                    self.call(String::from("Foo"),1000);
+                    return;
+                    return;
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn self_recursive_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.self_recursive_context();
+        match frame_event.message {
+            FrameMessage::Foo => {
+                self.log(String::from("Foo"),(frame_event.arguments.as_ref().borrow().foo_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().foo_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                if (this_state_context.state_vars.as_ref().borrow().counter) < 100 {
+                    self.foo((frame_event.arguments.as_ref().borrow().foo_args().arg) * 2);
+                    return;
+                    return;
+                } else {
+                    // Start transition
+                    let context = FinalStateContext {
+                        state_args: Rc::new(RefCell::new(FinalStateArgs {
+                            counter: this_state_context.state_vars.as_ref().borrow().counter,
+                        })),
+                    };
+                    let next_state_context = Rc::new(StateContext::Final(context));
+                    self.transition(HandlerCallsState::Final, next_state_context);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Bar => {
+                self.log(String::from("Bar"),(frame_event.arguments.as_ref().borrow().bar_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().bar_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        counter: this_state_context.state_vars.as_ref().borrow().counter,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(HandlerCallsState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Foo") {
+                    self.foo((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Bar") {
+                    self.bar((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn mutually_recursive_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.mutually_recursive_context();
+        match frame_event.message {
+            FrameMessage::Foo => {
+                self.log(String::from("Foo"),(frame_event.arguments.as_ref().borrow().foo_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().foo_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                if (this_state_context.state_vars.as_ref().borrow().counter) > 100 {
+                    // Start transition
+                    let context = FinalStateContext {
+                        state_args: Rc::new(RefCell::new(FinalStateArgs {
+                            counter: this_state_context.state_vars.as_ref().borrow().counter,
+                        })),
+                    };
+                    let next_state_context = Rc::new(StateContext::Final(context));
+                    self.transition(HandlerCallsState::Final, next_state_context);
+                    return;
+                } else {
+                    self.bar((frame_event.arguments.as_ref().borrow().foo_args().arg) * 2);
+                    return;
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Bar => {
+                self.log(String::from("Bar"),(frame_event.arguments.as_ref().borrow().bar_args().arg));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().counter + frame_event.arguments.as_ref().borrow().bar_args().arg
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().counter = assign_temp;
+                if (frame_event.arguments.as_ref().borrow().bar_args().arg) == 4 {
+                    self.foo((frame_event.arguments.as_ref().borrow().bar_args().arg));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().bar_args().arg) == 8 {
+                    self.foo((frame_event.arguments.as_ref().borrow().bar_args().arg) * 2);
+                    return;
+                    return;
+                } else {
+                    self.foo((frame_event.arguments.as_ref().borrow().bar_args().arg) * 3);
+                    return;
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Foo") {
+                    self.foo((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("Bar") {
+                    self.bar((frame_event.arguments.as_ref().borrow().call_args().arg));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn final_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.final_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("Final"),(this_state_context.state_args.as_ref().borrow().counter));
+                // Start transition
+                let context = InitStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Init(context));
+                self.transition(HandlerCallsState::Init, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            HandlerCallsState::Init => self.init_handler(frame_event.clone()),
+            HandlerCallsState::NonRecursive => self.non_recursive_handler(frame_event.clone()),
+            HandlerCallsState::SelfRecursive => self.self_recursive_handler(frame_event.clone()),
+            HandlerCallsState::MutuallyRecursive => self.mutually_recursive_handler(frame_event.clone()),
+            HandlerCallsState::Final => self.final_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: HandlerCallsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for HandlerCalls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,864 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(HierarchicalState),
+    Exit(HierarchicalState),
+    A,
+    B,
+    C,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(HierarchicalState::I) => write!(f, "I:>"),
+            FrameMessage::Enter(HierarchicalState::S) => write!(f, "S:>"),
+            FrameMessage::Enter(HierarchicalState::S0) => write!(f, "S0:>"),
+            FrameMessage::Enter(HierarchicalState::S1) => write!(f, "S1:>"),
+            FrameMessage::Enter(HierarchicalState::S2) => write!(f, "S2:>"),
+            FrameMessage::Enter(HierarchicalState::S3) => write!(f, "S3:>"),
+            FrameMessage::Enter(HierarchicalState::T) => write!(f, "T:>"),
+            FrameMessage::Exit(HierarchicalState::I) => write!(f, "I:<"),
+            FrameMessage::Exit(HierarchicalState::S) => write!(f, "S:<"),
+            FrameMessage::Exit(HierarchicalState::S0) => write!(f, "S0:<"),
+            FrameMessage::Exit(HierarchicalState::S1) => write!(f, "S1:<"),
+            FrameMessage::Exit(HierarchicalState::S2) => write!(f, "S2:<"),
+            FrameMessage::Exit(HierarchicalState::S3) => write!(f, "S3:<"),
+            FrameMessage::Exit(HierarchicalState::T) => write!(f, "T:<"),
+            FrameMessage::A => write!(f, "A"),
+            FrameMessage::B => write!(f, "B"),
+            FrameMessage::C => write!(f, "C"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Hierarchical> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Hierarchical as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum HierarchicalState {
+    I,
+    S,
+    S0,
+    S1,
+    S2,
+    S3,
+    T,
+}
+
+impl runtime::State<Hierarchical> for HierarchicalState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            HierarchicalState::I => runtime_info::machine_info().states[0],
+            HierarchicalState::S => runtime_info::machine_info().states[1],
+            HierarchicalState::S0 => runtime_info::machine_info().states[2],
+            HierarchicalState::S1 => runtime_info::machine_info().states[3],
+            HierarchicalState::S2 => runtime_info::machine_info().states[4],
+            HierarchicalState::S3 => runtime_info::machine_info().states[5],
+            HierarchicalState::T => runtime_info::machine_info().states[6],
+        }
+    }
+    fn arguments(&self) -> <Hierarchical as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Hierarchical as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Hierarchical {
+    state: HierarchicalState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    enters: Log,
+    exits: Log,
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Hierarchical {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "enters" => Some(Box::new(self.enters.clone())),
+            "exits" => Some(Box::new(self.exits.clone())),
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Hierarchical {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Hierarchical as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Hierarchical {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Hierarchical {
+    
+    pub fn new() -> Self {
+        let mut machine = Hierarchical {
+            state: HierarchicalState::I,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            enters: vec![],
+            exits: vec![],
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::A, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::B, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::C, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn i_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], HierarchicalState::S);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S"));
+                return;
+            }
+            FrameMessage::A => {
+                self.log(String::from("S.A"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[1], HierarchicalState::S0);
+                return;
+            }
+            FrameMessage::B => {
+                self.log(String::from("S.B"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[2], HierarchicalState::S1);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_0_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S0"));
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S0"));
+            }
+              //  override parent handler
+            FrameMessage::A => {
+                self.log(String::from("S0.A"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[3], HierarchicalState::T);
+                return;
+            }
+              //  do this, then parent handler
+            FrameMessage::B => {
+                self.log(String::from("S0.B"));
+            }
+              //  extend parent handler
+            FrameMessage::C => {
+                self.log(String::from("S0.C"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[4], HierarchicalState::S2);
+                return;
+            }
+            _ => {}
+        }
+        self.s_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S1"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S1"));
+                return;
+            }
+              //  defer to parent for A
+              //  do this, then parent, which transitions here
+            FrameMessage::B => {
+                self.log(String::from("S1.B"));
+            }
+              //  propagate message not handled by parent
+            FrameMessage::C => {
+                self.log(String::from("S1.C"));
+            }
+            _ => {}
+        }
+        self.s_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_2_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S2"));
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S2"));
+            }
+              //  will propagate to S0 and S
+            FrameMessage::B => {
+                self.log(String::from("S2.B"));
+            }
+            FrameMessage::C => {
+                self.log(String::from("S2.C"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[5], HierarchicalState::T);
+                return;
+            }
+            _ => {}
+        }
+        self.s_0_handler(frame_event);
+    }  //  continue after transition (should be ignored)
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_3_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S3"));
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S3"));
+            }
+              //  defer to grandparent for A
+              //  override and move to sibling
+            FrameMessage::B => {
+                self.log(String::from("S3.B"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[6], HierarchicalState::S2);
+                return;
+            }
+            _ => {}
+        }
+        self.s_1_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn t_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("T"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("T"));
+                return;
+            }
+            FrameMessage::A => {
+                self.log(String::from("T.A"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[7], HierarchicalState::S);
+                return;
+            }
+            FrameMessage::B => {
+                self.log(String::from("T.B"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[8], HierarchicalState::S2);
+                return;
+            }
+            FrameMessage::C => {
+                self.log(String::from("T.C"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[9], HierarchicalState::S3);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            HierarchicalState::I => self.i_handler(frame_event.clone()),
+            HierarchicalState::S => self.s_handler(frame_event.clone()),
+            HierarchicalState::S0 => self.s_0_handler(frame_event.clone()),
+            HierarchicalState::S1 => self.s_1_handler(frame_event.clone()),
+            HierarchicalState::S2 => self.s_2_handler(frame_event.clone()),
+            HierarchicalState::S3 => self.s_3_handler(frame_event.clone()),
+            HierarchicalState::T => self.t_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: HierarchicalState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <Hierarchical as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <Hierarchical as runtime::Machine>::StatePtr,
+            exit_event as <Hierarchical as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Hierarchical as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Hierarchical {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/hierarchical.frm"),
+        sha256: Some("6712f5dbd040c593edd33350fc4fe1299d20cb1fc41e94704ccfdb5f0b2bf953"),
+        name: "Hierarchical",
+        variables: &[
+            NameInfo {
+                name: "enters",
+                vtype: "Log",
+            },
+            NameInfo {
+                name: "exits",
+                vtype: "Log",
+            },
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_I,
+            STATE_S,
+            STATE_S_0,
+            STATE_S_1,
+            STATE_S_2,
+            STATE_S_3,
+            STATE_T,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "enter",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "exit",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "A",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "I:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "I:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S2:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S2:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S3:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S3:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "T:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "T:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_I,
+            target: STATE_S,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S,
+            target: STATE_S_0,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S,
+            target: STATE_S_1,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S_0,
+            target: STATE_T,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_S_0,
+            target: STATE_S_2,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_S_2,
+            target: STATE_T,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S_3,
+            target: STATE_S_2,
+        },
+        &TransitionInfo {
+            id: 7,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_T,
+            target: STATE_S,
+        },
+        &TransitionInfo {
+            id: 8,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_T,
+            target: STATE_S_2,
+        },
+        &TransitionInfo {
+            id: 9,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_T,
+            target: STATE_S_3,
+        },
+    ];
+    static STATE_I: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "I",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[5],
+            EVENTS[6],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_0: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S0",
+        parent: Some(STATE_S),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[7],
+            EVENTS[8],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_1: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S1",
+        parent: Some(STATE_S),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[9],
+            EVENTS[10],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_2: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S2",
+        parent: Some(STATE_S_0),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[11],
+            EVENTS[12],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_3: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S3",
+        parent: Some(STATE_S_1),
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[13],
+            EVENTS[14],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_T: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "T",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[15],
+            EVENTS[16],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,405 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(HierarchicalGuardState),
+    Exit(HierarchicalGuardState),
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(HierarchicalGuardState::I) => write!(f, "I:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S) => write!(f, "S:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S0) => write!(f, "S0:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S1) => write!(f, "S1:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S2) => write!(f, "S2:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S3) => write!(f, "S3:>"),
+            FrameMessage::Enter(HierarchicalGuardState::S4) => write!(f, "S4:>"),
+            FrameMessage::Exit(HierarchicalGuardState::I) => write!(f, "I:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S) => write!(f, "S:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S0) => write!(f, "S0:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S1) => write!(f, "S1:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S2) => write!(f, "S2:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S3) => write!(f, "S3:<"),
+            FrameMessage::Exit(HierarchicalGuardState::S4) => write!(f, "S4:<"),
+            FrameMessage::A => write!(f, "A"),
+            FrameMessage::B => write!(f, "B"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AArgs {
+    i: i16,
+}
+
+#[allow(dead_code)]
+struct BArgs {
+    i: i16,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    A(AArgs),
+    B(BArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn a_args(&self) -> &AArgs {
+        match self {
+            FrameEventArgs::A(args) => args,
+            _ => panic!("Failed conversion to AArgs"),
+        }
+    }
+    fn b_args(&self) -> &BArgs {
+        match self {
+            FrameEventArgs::B(args) => args,
+            _ => panic!("Failed conversion to BArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum HierarchicalGuardState {
+    I,
+    S,
+    S0,
+    S1,
+    S2,
+    S3,
+    S4,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct HierarchicalGuard {
+    state: HierarchicalGuardState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl HierarchicalGuard {
+    
+    pub fn new() -> Self {
+        let mut machine = HierarchicalGuard {
+            state: HierarchicalGuardState::I,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn a(&mut self, i: i16) {
+        let frame_args = FrameEventArgs::A(AArgs { i, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::A, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn b(&mut self, i: i16) {
+        let frame_args = FrameEventArgs::B(BArgs { i, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::B, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn i_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                // Start transition
+                self.transition(HierarchicalGuardState::S);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                self.log(String::from("S.A"));
+                if (frame_event.arguments.as_ref().borrow().a_args().i) < 10 {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S0);
+                    return;
+                } else {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S1);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::B => {
+                self.log(String::from("S.B"));
+                if (frame_event.arguments.as_ref().borrow().b_args().i) < 10 {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S2);
+                    return;
+                } else {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S3);
+                    return;
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_0_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                self.log(String::from("S0.A"));
+                if (frame_event.arguments.as_ref().borrow().a_args().i) > 0 {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S2);
+                    return;
+                } else {
+                }
+            }
+              //  fall through else branch
+            FrameMessage::B => {
+                self.log(String::from("S0.B"));
+                if (frame_event.arguments.as_ref().borrow().b_args().i) > 0 {
+                } else {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S1);
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.s_handler(frame_event);
+    }  //  fall through then branch
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                self.log(String::from("S1.A"));
+                if (frame_event.arguments.as_ref().borrow().a_args().i) > 5 {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S3);
+                    return;
+                } else {
+                }
+            }
+            _ => {}
+        }
+        self.s_0_handler(frame_event);
+    }  //  fall through else branch
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_2_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                self.log(String::from("S2.A"));
+                if (frame_event.arguments.as_ref().borrow().a_args().i) > 10 {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S4);
+                    return;
+                } else {
+                }
+            }
+              //  fall through then branch
+            FrameMessage::B => {
+                self.log(String::from("S2.B"));
+                if !((frame_event.arguments.as_ref().borrow().b_args().i) > 10) {
+                } else {
+                    // Start transition
+                    self.transition(HierarchicalGuardState::S4);
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.s_1_handler(frame_event);
+    }  //  fall through then branch
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_3_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                self.log(String::from("S3.A"));
+                if (frame_event.arguments.as_ref().borrow().a_args().i) > 0 {
+                    self.log(String::from("stop"));
+                    
+                    return;
+                } else {
+                    self.log(String::from("continue"));
+                }
+            }
+            FrameMessage::B => {
+                self.log(String::from("S3.B"));
+                if (frame_event.arguments.as_ref().borrow().b_args().i) > 0 {
+                    self.log(String::from("continue"));
+                } else {
+                    self.log(String::from("stop"));
+                    
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.s_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_4_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            HierarchicalGuardState::I => self.i_handler(frame_event.clone()),
+            HierarchicalGuardState::S => self.s_handler(frame_event.clone()),
+            HierarchicalGuardState::S0 => self.s_0_handler(frame_event.clone()),
+            HierarchicalGuardState::S1 => self.s_1_handler(frame_event.clone()),
+            HierarchicalGuardState::S2 => self.s_2_handler(frame_event.clone()),
+            HierarchicalGuardState::S3 => self.s_3_handler(frame_event.clone()),
+            HierarchicalGuardState::S4 => self.s_4_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: HierarchicalGuardState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for HierarchicalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,512 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(MatchState),
+    Exit(MatchState),
+    Empty,
+    Simple,
+    Multi,
+    Nested,
+    Child,
+    OnInt,
+    OnString,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(MatchState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(MatchState::EmptyMatch) => write!(f, "EmptyMatch:>"),
+            FrameMessage::Enter(MatchState::SimpleMatch) => write!(f, "SimpleMatch:>"),
+            FrameMessage::Enter(MatchState::MultiMatch) => write!(f, "MultiMatch:>"),
+            FrameMessage::Enter(MatchState::NestedMatch) => write!(f, "NestedMatch:>"),
+            FrameMessage::Enter(MatchState::ChildMatch) => write!(f, "ChildMatch:>"),
+            FrameMessage::Enter(MatchState::Final) => write!(f, "Final:>"),
+            FrameMessage::Exit(MatchState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(MatchState::EmptyMatch) => write!(f, "EmptyMatch:<"),
+            FrameMessage::Exit(MatchState::SimpleMatch) => write!(f, "SimpleMatch:<"),
+            FrameMessage::Exit(MatchState::MultiMatch) => write!(f, "MultiMatch:<"),
+            FrameMessage::Exit(MatchState::NestedMatch) => write!(f, "NestedMatch:<"),
+            FrameMessage::Exit(MatchState::ChildMatch) => write!(f, "ChildMatch:<"),
+            FrameMessage::Exit(MatchState::Final) => write!(f, "Final:<"),
+            FrameMessage::Empty => write!(f, "Empty"),
+            FrameMessage::Simple => write!(f, "Simple"),
+            FrameMessage::Multi => write!(f, "Multi"),
+            FrameMessage::Nested => write!(f, "Nested"),
+            FrameMessage::Child => write!(f, "Child"),
+            FrameMessage::OnInt => write!(f, "OnInt"),
+            FrameMessage::OnString => write!(f, "OnString"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OnIntArgs {
+    i: i32,
+}
+
+#[allow(dead_code)]
+struct OnStringArgs {
+    s: String,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    OnInt(OnIntArgs),
+    OnString(OnStringArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn on_int_args(&self) -> &OnIntArgs {
+        match self {
+            FrameEventArgs::OnInt(args) => args,
+            _ => panic!("Failed conversion to OnIntArgs"),
+        }
+    }
+    fn on_string_args(&self) -> &OnStringArgs {
+        match self {
+            FrameEventArgs::OnString(args) => args,
+            _ => panic!("Failed conversion to OnStringArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum MatchState {
+    Init,
+    EmptyMatch,
+    SimpleMatch,
+    MultiMatch,
+    NestedMatch,
+    ChildMatch,
+    Final,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Match {
+    state: MatchState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Match {
+    
+    pub fn new() -> Self {
+        let mut machine = Match {
+            state: MatchState::Init,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn empty(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Empty, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn simple(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Simple, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn multi(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Multi, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn nested(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Nested, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn child(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Child, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn on_int(&mut self, i: i32) {
+        let frame_args = FrameEventArgs::OnInt(OnIntArgs { i, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::OnInt, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn on_string(&mut self, s: String) {
+        let frame_args = FrameEventArgs::OnString(OnStringArgs { s, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::OnString, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Empty => {
+                // Start transition
+                self.transition(MatchState::EmptyMatch);
+                return;
+            }
+            FrameMessage::Simple => {
+                // Start transition
+                self.transition(MatchState::SimpleMatch);
+                return;
+            }
+            FrameMessage::Multi => {
+                // Start transition
+                self.transition(MatchState::MultiMatch);
+                return;
+            }
+            FrameMessage::Nested => {
+                // Start transition
+                self.transition(MatchState::NestedMatch);
+                return;
+            }
+            FrameMessage::Child => {
+                // Start transition
+                self.transition(MatchState::ChildMatch);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn empty_match_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnString => {
+                if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("foo") {
+                    self.log(String::from("empty"));
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }  //  TODO: matching only the empty string is broken
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn simple_match_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 0 {
+                    self.log(String::from("0"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 42 {
+                    self.log(String::from("42"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 42 {
+                    self.log(String::from("!!!"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == -200 {
+                    self.log(String::from("-200"));
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            FrameMessage::OnString => {
+                if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hello") {
+                    self.log(String::from("hello"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hello") {
+                    self.log(String::from("!!!"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("goodbye") {
+                    self.log(String::from("goodbye"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("Testing 1, 2, 3...") {
+                    self.log(String::from("testing"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("$10!") {
+                    self.log(String::from("money"));
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn multi_match_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 3 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == -7 {
+                    self.log(String::from("3|-7"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == -4 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == 5 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == 6 {
+                    self.log(String::from("-4|5|6"));
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            FrameMessage::OnString => {
+                if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("$10") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("12.5%") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("@#*!") {
+                    self.log(String::from("symbols"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq(" ") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("  ") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("\t") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("\n") {
+                    self.log(String::from("whitespace"));
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn nested_match_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) > 0 {
+                    if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 1 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == 2 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == 3 {
+                        self.log(String::from("1-3"));
+                        if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 1 {
+                            self.log(String::from("1"));
+                        } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 2 {
+                            self.log(String::from("2"));
+                        } else {
+                            self.log(String::from("3"));
+                        }
+                    } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 4 || (frame_event.arguments.as_ref().borrow().on_int_args().i) == 5 {
+                        self.log(String::from("4-5"));
+                        if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 4 {
+                            self.log(String::from("4"));
+                        } else {
+                            self.log(String::from("5"));
+                        }
+                    } else {
+                        self.log(String::from("too big"));
+                    }
+                } else {
+                    self.log(String::from("too small"));
+                }
+                return;
+            }
+            FrameMessage::OnString => {
+                if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hello") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hola") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("bonjour") {
+                    self.log(String::from("greeting"));
+                    if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hello") {
+                        self.log(String::from("English"));
+                    } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hola") {
+                        self.log(String::from("Spanish"));
+                    } else {
+                        self.log(String::from("French"));
+                    }
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("goodbye") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("adios") || (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("au revoir") {
+                    self.log(String::from("farewell"));
+                    if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("goodbye") {
+                        self.log(String::from("English"));
+                    } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("adios") {
+                        self.log(String::from("Spanish"));
+                    } else {
+                        self.log(String::from("French"));
+                    }
+                } else {
+                    self.log(String::from("?"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn child_match_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::OnInt => {
+                if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 0 {
+                    // Start transition
+                    self.transition(MatchState::Final);
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 3 {
+                    self.log(String::from("3"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 4 {
+                    self.log(String::from("4"));
+                    
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 42 {
+                    self.log(String::from("42 in child"));
+                } else if (frame_event.arguments.as_ref().borrow().on_int_args().i) == 5 {
+                    self.log(String::from("5"));
+                    // Start transition
+                    self.transition(MatchState::Final);
+                    return;
+                } else {
+                    self.log(String::from("no match in child"));
+                }
+            }
+            FrameMessage::OnString => {
+                if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("hello") {
+                    self.log(String::from("hello in child"));
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("goodbye") {
+                    // Start transition
+                    self.transition(MatchState::Final);
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().on_string_args().s).eq("Testing 1, 2, 3...") {
+                    self.log(String::from("testing in child"));
+                    
+                    return;
+                } else {
+                    self.log(String::from("no match in child"));
+                }
+            }
+            _ => {}
+        }
+        self.simple_match_handler(frame_event);
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn final_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            MatchState::Init => self.init_handler(frame_event.clone()),
+            MatchState::EmptyMatch => self.empty_match_handler(frame_event.clone()),
+            MatchState::SimpleMatch => self.simple_match_handler(frame_event.clone()),
+            MatchState::MultiMatch => self.multi_match_handler(frame_event.clone()),
+            MatchState::NestedMatch => self.nested_match_handler(frame_event.clone()),
+            MatchState::ChildMatch => self.child_match_handler(frame_event.clone()),
+            MatchState::Final => self.final_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: MatchState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Match {
+    fn default() -> Self {
+        Self::new()
+    }
+}
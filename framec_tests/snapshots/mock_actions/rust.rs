@@ -0,0 +1,273 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(MockActionsState),
+    Exit(MockActionsState),
+    TurnOn,
+    Dim,
+    IsBright,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(MockActionsState::Off) => write!(f, "Off:>"),
+            FrameMessage::Enter(MockActionsState::On) => write!(f, "On:>"),
+            FrameMessage::Exit(MockActionsState::Off) => write!(f, "Off:<"),
+            FrameMessage::Exit(MockActionsState::On) => write!(f, "On:<"),
+            FrameMessage::TurnOn => write!(f, "turnOn"),
+            FrameMessage::Dim => write!(f, "dim"),
+            FrameMessage::IsBright => write!(f, "isBright"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct DimArgs {
+    level: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Dim(DimArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn dim_args(&self) -> &DimArgs {
+        match self {
+            FrameEventArgs::Dim(args) => args,
+            _ => panic!("Failed conversion to DimArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    IsBright { return_value: bool },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_is_bright_ret(&self) -> bool {
+        match self {
+            FrameEventReturn::IsBright { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum MockActionsState {
+    Off,
+    On,
+}
+
+#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait MockActionsActions { 
+    fn open_switch(&self);
+    fn set_level(&self, level: i32, reason: String);
+    fn brightness(&self) -> bool;
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct MockActions {
+    state: MockActionsState,
+    action_calls: std::cell::RefCell<Vec<String>>,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl MockActions {
+    
+    pub fn new() -> Self {
+        let mut machine = MockActions {
+            state: MockActionsState::Off,
+            action_calls: Default::default(),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn take_action_calls(&self) -> Vec<String> {
+        std::mem::take(&mut *self.action_calls.borrow_mut())
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn turn_on(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::TurnOn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn dim(&mut self, level: i32) {
+        let frame_args = FrameEventArgs::Dim(DimArgs { level, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Dim, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn is_bright(&mut self) -> bool {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::IsBright, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::IsBright { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for isBright"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::TurnOn => {
+                self.open_switch();
+                // Start transition
+                self.transition(MockActionsState::On);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Dim => {
+                self.set_level((frame_event.arguments.as_ref().borrow().dim_args().level),String::from("dim"));
+                return;
+            }
+            FrameMessage::IsBright => {
+                frame_event.ret.replace(FrameEventReturn::IsBright {
+                    return_value: self.brightness()
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            MockActionsState::Off => self.off_handler(frame_event.clone()),
+            MockActionsState::On => self.on_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: MockActionsState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for MockActions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl MockActionsActions for MockActions { 
+    fn open_switch(&self) {
+        self.action_calls.borrow_mut().push("open_switch()".to_string());
+    }
+    fn set_level(&self, level: i32, reason: String) {
+        self.action_calls.borrow_mut().push(format!("set_level({:?}, {:?})", level, reason));
+    }
+    fn brightness(&self) -> bool {
+        self.action_calls.borrow_mut().push("brightness()".to_string());
+        Default::default()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,240 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+enum NamingMessage {
+    Enter(TheNamingState),
+    Exit(TheNamingState),
+    NextState,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl std::fmt::Display for NamingMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NamingMessage::Enter(TheNamingState::First) => write!(f, "First:>"),
+            NamingMessage::Enter(TheNamingState::Second) => write!(f, "Second:>"),
+            NamingMessage::Exit(TheNamingState::First) => write!(f, "First:<"),
+            NamingMessage::Exit(TheNamingState::Second) => write!(f, "Second:<"),
+            NamingMessage::NextState => write!(f, "next_state"),
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct NextStateArgs {
+    stepSize: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    NextState(NextStateArgs),
+}
+
+
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn nextState_args(&self) -> &NextStateArgs {
+        match self {
+            FrameEventArgs::NextState(args) => args,
+            _ => panic!("Failed conversion to NextStateArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+pub struct NamingEvent {
+    message: NamingMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl NamingEvent {
+    fn new(message: NamingMessage, arguments: FrameEventArgs) -> NamingEvent {
+        NamingEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TheNamingState {
+    First,
+    Second,
+}
+
+
+
+// System Controller 
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+pub struct Naming {
+    state: TheNamingState,
+    //===================== Domain Block ===================//
+    stepLog: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl Naming {
+    
+    pub fn new() -> Self {
+        let mut machine = Naming {
+            state: TheNamingState::First,
+            stepLog: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(NamingEvent::new(
+            NamingMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn nextState(&mut self, stepSize: i32) {
+        let frame_args = FrameEventArgs::NextState(NextStateArgs { stepSize, });
+        let frame_event = Rc::new(NamingEvent::new(NamingMessage::NextState, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn first_handler(&mut self, frame_event: Rc<NamingEvent>) {
+        match frame_event.message {
+            NamingMessage::NextState => {
+                self.record((frame_event.arguments.as_ref().borrow().nextState_args().stepSize));
+                // Start transition
+                self.transition(TheNamingState::Second);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn second_handler(&mut self, frame_event: Rc<NamingEvent>) {
+        match frame_event.message {
+            NamingMessage::NextState => {
+                self.record((frame_event.arguments.as_ref().borrow().nextState_args().stepSize));
+                // Start transition
+                self.transition(TheNamingState::First);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<NamingEvent>) {
+        match self.state {
+            TheNamingState::First => self.first_handler(frame_event.clone()),
+            TheNamingState::Second => self.second_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: TheNamingState) {
+        let exit_event = Rc::new(NamingEvent::new(
+            NamingMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(NamingEvent::new(
+            NamingMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,226 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(NewWithState),
+    Exit(NewWithState),
+    GetBrightness,
+    GetEntries,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(NewWithState::Init) => write!(f, "Init:>"),
+            FrameMessage::Exit(NewWithState::Init) => write!(f, "Init:<"),
+            FrameMessage::GetBrightness => write!(f, "getBrightness"),
+            FrameMessage::GetEntries => write!(f, "getEntries"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    GetBrightness { return_value: i32 },
+    GetEntries { return_value: u32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_brightness_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::GetBrightness { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+    fn get_get_entries_ret(&self) -> u32 {
+        match self {
+            FrameEventReturn::GetEntries { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum NewWithState {
+    Init,
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct NewWith {
+    state: NewWithState,
+    //===================== Domain Block ===================//
+    brightness: i32,
+    entries: u32,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl NewWith {
+    
+    pub fn new() -> Self {
+        let mut machine = NewWith {
+            state: NewWithState::Init,
+            brightness: 5,
+            entries: 0,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    pub fn new_with(domain: NewWithDomain, enter: bool) -> Self {
+        let mut machine = NewWith {
+            state: NewWithState::Init,
+            brightness: domain.brightness,
+            entries: domain.entries,
+        };
+        if enter {
+            machine.initialize();
+        }
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn get_brightness(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetBrightness, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetBrightness { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getBrightness"),
+        };
+        return_value
+    }
+    
+    pub fn get_entries(&mut self) -> u32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetEntries, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetEntries { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getEntries"),
+        };
+        return_value
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                let assign_temp = {
+                    self.entries + 1
+                };
+                self.entries = assign_temp;
+                return;
+            }
+            FrameMessage::GetBrightness => {
+                frame_event.ret.replace(FrameEventReturn::GetBrightness {
+                    return_value: self.brightness
+                });
+                return;
+            }
+            FrameMessage::GetEntries => {
+                frame_event.ret.replace(FrameEventReturn::GetEntries {
+                    return_value: self.entries
+                });
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            NewWithState::Init => self.init_handler(frame_event.clone()),
+        }
+    }
+    
+} // end system controller
+
+impl Default for NewWith {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Initial values of the domain variables of `NewWith`.
+pub struct NewWithDomain {
+    pub brightness: i32,
+    pub entries: u32,
+}
+
+impl Default for NewWithDomain {
+    fn default() -> Self {
+        NewWithDomain {
+            brightness: 5,
+            entries: 0,
+        }
+    }
+}
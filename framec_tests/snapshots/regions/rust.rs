@@ -0,0 +1,453 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(RegionsState),
+    Exit(RegionsState),
+    Start,
+    Motor,
+    Light,
+    Stop,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(RegionsState::Idle) => write!(f, "Idle:>"),
+            FrameMessage::Enter(RegionsState::Running) => write!(f, "Running:>"),
+            FrameMessage::Enter(RegionsState::MotorOff) => write!(f, "MotorOff:>"),
+            FrameMessage::Enter(RegionsState::MotorOn) => write!(f, "MotorOn:>"),
+            FrameMessage::Enter(RegionsState::LightOff) => write!(f, "LightOff:>"),
+            FrameMessage::Enter(RegionsState::LightOn) => write!(f, "LightOn:>"),
+            FrameMessage::Exit(RegionsState::Idle) => write!(f, "Idle:<"),
+            FrameMessage::Exit(RegionsState::Running) => write!(f, "Running:<"),
+            FrameMessage::Exit(RegionsState::MotorOff) => write!(f, "MotorOff:<"),
+            FrameMessage::Exit(RegionsState::MotorOn) => write!(f, "MotorOn:<"),
+            FrameMessage::Exit(RegionsState::LightOff) => write!(f, "LightOff:<"),
+            FrameMessage::Exit(RegionsState::LightOn) => write!(f, "LightOn:<"),
+            FrameMessage::Start => write!(f, "start"),
+            FrameMessage::Motor => write!(f, "motor"),
+            FrameMessage::Light => write!(f, "light"),
+            FrameMessage::Stop => write!(f, "stop"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum RegionsState {
+    Idle,
+    Running,
+    MotorOff,
+    MotorOn,
+    LightOff,
+    LightOn,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Regions {
+    state: RegionsState,
+    running_motor_region: RegionsState,
+    running_light_region: RegionsState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Regions {
+    
+    pub fn new() -> Self {
+        let mut machine = Regions {
+            state: RegionsState::Idle,
+            running_motor_region: RegionsState::MotorOff,
+            running_light_region: RegionsState::LightOff,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn start(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Start, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn motor(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Motor, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn light(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Light, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn stop(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Stop, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn idle_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Start => {
+                // Start transition
+                self.transition(RegionsState::Running);
+                return;
+            }
+            FrameMessage::Light => {
+                // Start transition
+                self.running_light_region = RegionsState::LightOn;
+                self.transition(RegionsState::Running);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn running_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter Running"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit Running"));
+                return;
+            }
+            FrameMessage::Stop => {
+                // Start transition
+                self.transition(RegionsState::Idle);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn motor_off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter MotorOff"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit MotorOff"));
+                return;
+            }
+            FrameMessage::Motor => {
+                // Start transition
+                self.running_motor_region_transition(RegionsState::MotorOn);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn motor_on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter MotorOn"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit MotorOn"));
+                return;
+            }
+            FrameMessage::Motor => {
+                // Start transition
+                self.running_motor_region_transition(RegionsState::MotorOff);
+                return;
+            }
+            FrameMessage::Light => {
+                self.log(String::from("MotorOn light"));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn light_off_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter LightOff"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit LightOff"));
+                return;
+            }
+            FrameMessage::Light => {
+                // Start transition
+                self.running_light_region_transition(RegionsState::LightOn);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn light_on_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter LightOn"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit LightOn"));
+                return;
+            }
+            FrameMessage::Light => {
+                // Start transition
+                self.running_light_region_transition(RegionsState::LightOff);
+                return;
+            }
+            FrameMessage::Stop => {
+                self.log(String::from("LightOn stop"));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            RegionsState::Idle => self.idle_handler(frame_event.clone()),
+            RegionsState::Running => self.running_regions_handler(frame_event.clone()),
+            RegionsState::MotorOff => self.motor_off_handler(frame_event.clone()),
+            RegionsState::MotorOn => self.motor_on_handler(frame_event.clone()),
+            RegionsState::LightOff => self.light_off_handler(frame_event.clone()),
+            RegionsState::LightOn => self.light_on_handler(frame_event.clone()),
+        }
+    }
+    
+    fn running_regions_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.running_handler(frame_event.clone());
+                if self.state != RegionsState::Running {
+                    return;
+                }
+                let enter_event = Rc::new(FrameEvent::new(
+                    FrameMessage::Enter(self.running_motor_region),
+                    FrameEventArgs::None,
+                ));
+                self.running_motor_region_handler(enter_event);
+                if self.state != RegionsState::Running {
+                    return;
+                }
+                let enter_event = Rc::new(FrameEvent::new(
+                    FrameMessage::Enter(self.running_light_region),
+                    FrameEventArgs::None,
+                ));
+                self.running_light_region_handler(enter_event);
+            }
+            FrameMessage::Exit(_) => {
+                let exit_event = Rc::new(FrameEvent::new(
+                    FrameMessage::Exit(self.running_light_region),
+                    FrameEventArgs::None,
+                ));
+                self.running_light_region_handler(exit_event);
+                self.running_light_region = RegionsState::LightOff;
+                let exit_event = Rc::new(FrameEvent::new(
+                    FrameMessage::Exit(self.running_motor_region),
+                    FrameEventArgs::None,
+                ));
+                self.running_motor_region_handler(exit_event);
+                self.running_motor_region = RegionsState::MotorOff;
+                self.running_handler(frame_event);
+            }
+            _ => {
+                self.running_motor_region_handler(frame_event.clone());
+                if self.state != RegionsState::Running {
+                    return;
+                }
+                self.running_light_region_handler(frame_event.clone());
+                if self.state != RegionsState::Running {
+                    return;
+                }
+                self.running_handler(frame_event);
+            }
+        }
+    }
+    
+    fn running_motor_region_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.running_motor_region {
+            RegionsState::MotorOff => self.motor_off_handler(frame_event),
+            RegionsState::MotorOn => self.motor_on_handler(frame_event),
+            _ => {}
+        }
+    }
+    
+    fn running_motor_region_transition(&mut self, new_state: RegionsState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.running_motor_region),
+            FrameEventArgs::None,
+        ));
+        self.running_motor_region_handler(exit_event);
+        self.running_motor_region = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.running_motor_region),
+            FrameEventArgs::None,
+        ));
+        self.running_motor_region_handler(enter_event);
+    }
+    
+    fn running_light_region_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.running_light_region {
+            RegionsState::LightOff => self.light_off_handler(frame_event),
+            RegionsState::LightOn => self.light_on_handler(frame_event),
+            _ => {}
+        }
+    }
+    
+    fn running_light_region_transition(&mut self, new_state: RegionsState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.running_light_region),
+            FrameEventArgs::None,
+        ));
+        self.running_light_region_handler(exit_event);
+        self.running_light_region = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.running_light_region),
+            FrameEventArgs::None,
+        ));
+        self.running_light_region_handler(enter_event);
+    }
+    
+    fn transition(&mut self, new_state: RegionsState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for Regions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
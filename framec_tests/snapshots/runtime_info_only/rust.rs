@@ -0,0 +1,410 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(RuntimeInfoOnlyState),
+    Exit(RuntimeInfoOnlyState),
+    X,
+    Y,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(RuntimeInfoOnlyState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(RuntimeInfoOnlyState::B) => write!(f, "B:>"),
+            FrameMessage::Exit(RuntimeInfoOnlyState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(RuntimeInfoOnlyState::B) => write!(f, "B:<"),
+            FrameMessage::X => write!(f, "X"),
+            FrameMessage::Y => write!(f, "Y"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum RuntimeInfoOnlyState {
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+struct AStateVars {
+    x: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "x" => Some(Box::new(self.x.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+    state_vars: Rc<RefCell<AStateVars>>,
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    A(AStateContext),
+    B(BStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+}// System Controller 
+#[allow(dead_code)]
+pub struct RuntimeInfoOnly {
+    state: RuntimeInfoOnlyState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    count: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for RuntimeInfoOnly {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "count" => Some(Box::new(self.count.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl RuntimeInfoOnly {
+    
+    pub fn new() -> Self {
+        let context = AStateContext {
+            state_vars: Rc::new(RefCell::new(AStateVars {
+                x: 0,
+            })),
+        };
+        let next_state_context = Rc::new(StateContext::A(context));
+        let mut machine = RuntimeInfoOnly {
+            state: RuntimeInfoOnlyState::A,
+            state_context: next_state_context,
+            count: 3,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn x(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::X, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn y(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Y, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::X => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                return;
+            }
+            FrameMessage::Y => {
+                // Start transition
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(RuntimeInfoOnlyState::B, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::X => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(RuntimeInfoOnlyState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            RuntimeInfoOnlyState::A => self.a_handler(frame_event.clone()),
+            RuntimeInfoOnlyState::B => self.b_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: RuntimeInfoOnlyState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for RuntimeInfoOnly {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/runtime_info_only.frm"),
+        sha256: Some("2a1fb281d2935906abb07010fa53f19ec8c8509202f92d652e51e70fc20f7e34"),
+        name: "RuntimeInfoOnly",
+        variables: &[
+            NameInfo {
+                name: "count",
+                vtype: "u32",
+            },
+        ],
+        states: &[
+            STATE_A,
+            STATE_B,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "X",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Y",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+    ];
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "x",
+                vtype: "u32",
+            },
+        ],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,793 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(RustNamingState),
+    Exit(RustNamingState),
+    snake_event,
+    CamelEvent,
+    event123,
+    call,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(RustNamingState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(RustNamingState::snake_state) => write!(f, "snake_state:>"),
+            FrameMessage::Enter(RustNamingState::CamelState) => write!(f, "CamelState:>"),
+            FrameMessage::Enter(RustNamingState::state123) => write!(f, "state123:>"),
+            FrameMessage::Enter(RustNamingState::Final) => write!(f, "Final:>"),
+            FrameMessage::Exit(RustNamingState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(RustNamingState::snake_state) => write!(f, "snake_state:<"),
+            FrameMessage::Exit(RustNamingState::CamelState) => write!(f, "CamelState:<"),
+            FrameMessage::Exit(RustNamingState::state123) => write!(f, "state123:<"),
+            FrameMessage::Exit(RustNamingState::Final) => write!(f, "Final:<"),
+            FrameMessage::snake_event => write!(f, "snake_event"),
+            FrameMessage::CamelEvent => write!(f, "CamelEvent"),
+            FrameMessage::event123 => write!(f, "event123"),
+            FrameMessage::call => write!(f, "call"),
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct snake_eventArgs {
+    snake_param: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct CamelEventArgs {
+    CamelParam: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct event123Args {
+    param123: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct callArgs {
+    event: String,
+    param: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    snake_event(snake_eventArgs),
+    CamelEvent(CamelEventArgs),
+    event123(event123Args),
+    call(callArgs),
+}
+
+
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn snake_event_args(&self) -> &snake_eventArgs {
+        match self {
+            FrameEventArgs::snake_event(args) => args,
+            _ => panic!("Failed conversion to snake_eventArgs"),
+        }
+    }
+    fn CamelEvent_args(&self) -> &CamelEventArgs {
+        match self {
+            FrameEventArgs::CamelEvent(args) => args,
+            _ => panic!("Failed conversion to CamelEventArgs"),
+        }
+    }
+    fn event123_args(&self) -> &event123Args {
+        match self {
+            FrameEventArgs::event123(args) => args,
+            _ => panic!("Failed conversion to event123Args"),
+        }
+    }
+    fn call_args(&self) -> &callArgs {
+        match self {
+            FrameEventArgs::call(args) => args,
+            _ => panic!("Failed conversion to callArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum RustNamingState {
+    Init,
+    snake_state,
+    CamelState,
+    state123,
+    Final,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct snake_stateStateArgs {
+    snake_state_param: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct snake_stateStateVars {
+    snake_state_var: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct snake_stateStateContext {
+    state_args: Rc<RefCell<snake_stateStateArgs>>,
+    state_vars: Rc<RefCell<snake_stateStateVars>>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct CamelStateStateArgs {
+    CamelStateParam: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct CamelStateStateVars {
+    CamelStateVar: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct CamelStateStateContext {
+    state_args: Rc<RefCell<CamelStateStateArgs>>,
+    state_vars: Rc<RefCell<CamelStateStateVars>>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct state123StateArgs {
+    stateParam123: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct state123StateVars {
+    stateVar123: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct state123StateContext {
+    state_args: Rc<RefCell<state123StateArgs>>,
+    state_vars: Rc<RefCell<state123StateVars>>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct FinalStateArgs {
+    result: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+struct FinalStateContext {
+    state_args: Rc<RefCell<FinalStateArgs>>,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    snake_state(snake_stateStateContext),
+    CamelState(CamelStateStateContext),
+    state123(state123StateContext),
+    Final(FinalStateContext),
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl StateContext {
+    fn Init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn snake_state_context(&self) -> &snake_stateStateContext {
+        match self {
+            StateContext::snake_state(context) => context,
+            _ => panic!("Failed conversion to snake_stateStateContext"),
+        }
+    }
+    fn CamelState_context(&self) -> &CamelStateStateContext {
+        match self {
+            StateContext::CamelState(context) => context,
+            _ => panic!("Failed conversion to CamelStateStateContext"),
+        }
+    }
+    fn state123_context(&self) -> &state123StateContext {
+        match self {
+            StateContext::state123(context) => context,
+            _ => panic!("Failed conversion to state123StateContext"),
+        }
+    }
+    fn Final_context(&self) -> &FinalStateContext {
+        match self {
+            StateContext::Final(context) => context,
+            _ => panic!("Failed conversion to FinalStateContext"),
+        }
+    }
+}#[allow(clippy::ptr_arg)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+trait RustNamingActions { 
+    fn snake_action(&self, snake_param: i32);
+    fn CamelAction(&self, CamelParam: i32);
+    fn action123(&self, param123: i32);
+    fn logFinal(&self, r: i32);
+}
+
+// System Controller 
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+pub struct RustNaming {
+    state: RustNamingState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    snake_domain_var: i32,
+    CamelDomainVar: i32,
+    domainVar123: i32,
+    snake_log: Log,
+    CamelLog: Log,
+    log123: Log,
+    finalLog: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl RustNaming {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = RustNaming {
+            state: RustNamingState::Init,
+            state_context: next_state_context,
+            snake_domain_var: 300,
+            CamelDomainVar: 550,
+            domainVar123: 150,
+            snake_log: vec![],
+            CamelLog: vec![],
+            log123: vec![],
+            finalLog: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn snake_event(&mut self, snake_param: i32) {
+        let frame_args = FrameEventArgs::snake_event(snake_eventArgs { snake_param, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::snake_event, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn CamelEvent(&mut self, CamelParam: i32) {
+        let frame_args = FrameEventArgs::CamelEvent(CamelEventArgs { CamelParam, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::CamelEvent, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn event123(&mut self, param123: i32) {
+        let frame_args = FrameEventArgs::event123(event123Args { param123, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::event123, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn call(&mut self, event: String, param: i32) {
+        let frame_args = FrameEventArgs::call(callArgs { event, param, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::call, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn Init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.Init_context();
+        match frame_event.message {
+            FrameMessage::snake_event => {
+                // Start transition
+                let context = snake_stateStateContext {
+                    state_args: Rc::new(RefCell::new(snake_stateStateArgs {
+                        snake_state_param: frame_event.arguments.as_ref().borrow().snake_event_args().snake_param,
+                    })),
+                    state_vars: Rc::new(RefCell::new(snake_stateStateVars {
+                        snake_state_var: self.snake_domain_var + self.CamelDomainVar + self.domainVar123 + 100,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::snake_state(context));
+                self.transition(RustNamingState::snake_state, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                // Start transition
+                let context = CamelStateStateContext {
+                    state_args: Rc::new(RefCell::new(CamelStateStateArgs {
+                        CamelStateParam: frame_event.arguments.as_ref().borrow().CamelEvent_args().CamelParam,
+                    })),
+                    state_vars: Rc::new(RefCell::new(CamelStateStateVars {
+                        CamelStateVar: self.snake_domain_var + self.CamelDomainVar + self.domainVar123 + 200,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::CamelState(context));
+                self.transition(RustNamingState::CamelState, next_state_context);
+                return;
+            }
+            FrameMessage::event123 => {
+                // Start transition
+                let context = state123StateContext {
+                    state_args: Rc::new(RefCell::new(state123StateArgs {
+                        stateParam123: frame_event.arguments.as_ref().borrow().event123_args().param123,
+                    })),
+                    state_vars: Rc::new(RefCell::new(state123StateVars {
+                        stateVar123: self.snake_domain_var + self.CamelDomainVar + self.domainVar123 + 300,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::state123(context));
+                self.transition(RustNamingState::state123, next_state_context);
+                return;
+            }
+            FrameMessage::call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.CamelEvent((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn snake_state_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.snake_state_context();
+        match frame_event.message {
+              //  1100
+            FrameMessage::snake_event => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let CamelLocalVar: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().CamelEvent_args().CamelParam;
+                self.CamelAction(CamelLocalVar);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: CamelLocalVar,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::event123 => {
+                let localVar123: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().event123_args().param123;
+                self.action123(localVar123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: localVar123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.CamelEvent((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn CamelState_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.CamelState_context();
+        match frame_event.message {
+              //  1200
+            FrameMessage::snake_event => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().CamelStateVar + this_state_context.state_args.as_ref().borrow().CamelStateParam + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let CamelLocalVar: i32 = this_state_context.state_vars.as_ref().borrow().CamelStateVar + this_state_context.state_args.as_ref().borrow().CamelStateParam + frame_event.arguments.as_ref().borrow().CamelEvent_args().CamelParam;
+                self.CamelAction(CamelLocalVar);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: CamelLocalVar,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::event123 => {
+                let localVar123: i32 = this_state_context.state_vars.as_ref().borrow().CamelStateVar + this_state_context.state_args.as_ref().borrow().CamelStateParam + frame_event.arguments.as_ref().borrow().event123_args().param123;
+                self.action123(localVar123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: localVar123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.CamelEvent((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn state123_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.state123_context();
+        match frame_event.message {
+              //  1300
+            FrameMessage::snake_event => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().stateVar123 + this_state_context.state_args.as_ref().borrow().stateParam123 + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let CamelLocalVar: i32 = this_state_context.state_vars.as_ref().borrow().stateVar123 + this_state_context.state_args.as_ref().borrow().stateParam123 + frame_event.arguments.as_ref().borrow().CamelEvent_args().CamelParam;
+                self.CamelAction(CamelLocalVar);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: CamelLocalVar,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::event123 => {
+                let localVar123: i32 = this_state_context.state_vars.as_ref().borrow().stateVar123 + this_state_context.state_args.as_ref().borrow().stateParam123 + frame_event.arguments.as_ref().borrow().event123_args().param123;
+                self.action123(localVar123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: localVar123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.CamelEvent((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn Final_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.Final_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.logFinal((this_state_context.state_args.as_ref().borrow().result));
+                // Start transition
+                let context = InitStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Init(context));
+                self.transition(RustNamingState::Init, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            RustNamingState::Init => self.Init_handler(frame_event.clone()),
+            RustNamingState::snake_state => self.snake_state_handler(frame_event.clone()),
+            RustNamingState::CamelState => self.CamelState_handler(frame_event.clone()),
+            RustNamingState::state123 => self.state123_handler(frame_event.clone()),
+            RustNamingState::Final => self.Final_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: RustNamingState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for RustNaming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(clippy::upper_case_acronyms)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl RustNamingActions for RustNaming { 
+    fn snake_action(&self, snake_param: i32) {}
+    fn CamelAction(&self, CamelParam: i32) {}
+    fn action123(&self, param123: i32) {}
+    fn logFinal(&self, r: i32) {}
+}
\ No newline at end of file
@@ -0,0 +1,703 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(RustNamingState),
+    Exit(RustNamingState),
+    SnakeEvent,
+    CamelEvent,
+    Event123,
+    Call,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(RustNamingState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(RustNamingState::SnakeState) => write!(f, "snake_state:>"),
+            FrameMessage::Enter(RustNamingState::CamelState) => write!(f, "CamelState:>"),
+            FrameMessage::Enter(RustNamingState::State123) => write!(f, "state123:>"),
+            FrameMessage::Enter(RustNamingState::Final) => write!(f, "Final:>"),
+            FrameMessage::Exit(RustNamingState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(RustNamingState::SnakeState) => write!(f, "snake_state:<"),
+            FrameMessage::Exit(RustNamingState::CamelState) => write!(f, "CamelState:<"),
+            FrameMessage::Exit(RustNamingState::State123) => write!(f, "state123:<"),
+            FrameMessage::Exit(RustNamingState::Final) => write!(f, "Final:<"),
+            FrameMessage::SnakeEvent => write!(f, "snake_event"),
+            FrameMessage::CamelEvent => write!(f, "CamelEvent"),
+            FrameMessage::Event123 => write!(f, "event123"),
+            FrameMessage::Call => write!(f, "call"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct SnakeEventArgs {
+    snake_param: i32,
+}
+
+#[allow(dead_code)]
+struct CamelEventArgs {
+    camel_param: i32,
+}
+
+#[allow(dead_code)]
+struct Event123Args {
+    param_123: i32,
+}
+
+#[allow(dead_code)]
+struct CallArgs {
+    event: String,
+    param: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    SnakeEvent(SnakeEventArgs),
+    CamelEvent(CamelEventArgs),
+    Event123(Event123Args),
+    Call(CallArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn snake_event_args(&self) -> &SnakeEventArgs {
+        match self {
+            FrameEventArgs::SnakeEvent(args) => args,
+            _ => panic!("Failed conversion to SnakeEventArgs"),
+        }
+    }
+    fn camel_event_args(&self) -> &CamelEventArgs {
+        match self {
+            FrameEventArgs::CamelEvent(args) => args,
+            _ => panic!("Failed conversion to CamelEventArgs"),
+        }
+    }
+    fn event_123_args(&self) -> &Event123Args {
+        match self {
+            FrameEventArgs::Event123(args) => args,
+            _ => panic!("Failed conversion to Event123Args"),
+        }
+    }
+    fn call_args(&self) -> &CallArgs {
+        match self {
+            FrameEventArgs::Call(args) => args,
+            _ => panic!("Failed conversion to CallArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum RustNamingState {
+    Init,
+    SnakeState,
+    CamelState,
+    State123,
+    Final,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+#[allow(dead_code)]
+struct SnakeStateStateArgs {
+    snake_state_param: i32,
+}
+
+#[allow(dead_code)]
+struct SnakeStateStateVars {
+    snake_state_var: i32,
+}
+
+#[allow(dead_code)]
+struct SnakeStateStateContext {
+    state_args: Rc<RefCell<SnakeStateStateArgs>>,
+    state_vars: Rc<RefCell<SnakeStateStateVars>>,
+}
+
+#[allow(dead_code)]
+struct CamelStateStateArgs {
+    camel_state_param: i32,
+}
+
+#[allow(dead_code)]
+struct CamelStateStateVars {
+    camel_state_var: i32,
+}
+
+#[allow(dead_code)]
+struct CamelStateStateContext {
+    state_args: Rc<RefCell<CamelStateStateArgs>>,
+    state_vars: Rc<RefCell<CamelStateStateVars>>,
+}
+
+#[allow(dead_code)]
+struct State123StateArgs {
+    state_param_123: i32,
+}
+
+#[allow(dead_code)]
+struct State123StateVars {
+    state_var_123: i32,
+}
+
+#[allow(dead_code)]
+struct State123StateContext {
+    state_args: Rc<RefCell<State123StateArgs>>,
+    state_vars: Rc<RefCell<State123StateVars>>,
+}
+
+#[allow(dead_code)]
+struct FinalStateArgs {
+    result: i32,
+}
+
+#[allow(dead_code)]
+struct FinalStateContext {
+    state_args: Rc<RefCell<FinalStateArgs>>,
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    SnakeState(SnakeStateStateContext),
+    CamelState(CamelStateStateContext),
+    State123(State123StateContext),
+    Final(FinalStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn snake_state_context(&self) -> &SnakeStateStateContext {
+        match self {
+            StateContext::SnakeState(context) => context,
+            _ => panic!("Failed conversion to SnakeStateStateContext"),
+        }
+    }
+    fn camel_state_context(&self) -> &CamelStateStateContext {
+        match self {
+            StateContext::CamelState(context) => context,
+            _ => panic!("Failed conversion to CamelStateStateContext"),
+        }
+    }
+    fn state_123_context(&self) -> &State123StateContext {
+        match self {
+            StateContext::State123(context) => context,
+            _ => panic!("Failed conversion to State123StateContext"),
+        }
+    }
+    fn final_context(&self) -> &FinalStateContext {
+        match self {
+            StateContext::Final(context) => context,
+            _ => panic!("Failed conversion to FinalStateContext"),
+        }
+    }
+}#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait RustNamingActions { 
+    fn snake_action(&self, snake_param: i32);
+    fn camel_action(&self, camel_param: i32);
+    fn action_123(&self, param_123: i32);
+    fn log_final(&self, r: i32);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct RustNaming {
+    state: RustNamingState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    snake_domain_var: i32,
+    camel_domain_var: i32,
+    domain_var_123: i32,
+    snake_log: Log,
+    camel_log: Log,
+    log_123: Log,
+    final_log: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl RustNaming {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = RustNaming {
+            state: RustNamingState::Init,
+            state_context: next_state_context,
+            snake_domain_var: 300,
+            camel_domain_var: 550,
+            domain_var_123: 150,
+            snake_log: vec![],
+            camel_log: vec![],
+            log_123: vec![],
+            final_log: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn snake_event(&mut self, snake_param: i32) {
+        let frame_args = FrameEventArgs::SnakeEvent(SnakeEventArgs { snake_param, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::SnakeEvent, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn camel_event(&mut self, camel_param: i32) {
+        let frame_args = FrameEventArgs::CamelEvent(CamelEventArgs { camel_param, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::CamelEvent, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn event_123(&mut self, param_123: i32) {
+        let frame_args = FrameEventArgs::Event123(Event123Args { param_123, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Event123, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn call(&mut self, event: String, param: i32) {
+        let frame_args = FrameEventArgs::Call(CallArgs { event, param, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Call, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::SnakeEvent => {
+                // Start transition
+                let context = SnakeStateStateContext {
+                    state_args: Rc::new(RefCell::new(SnakeStateStateArgs {
+                        snake_state_param: frame_event.arguments.as_ref().borrow().snake_event_args().snake_param,
+                    })),
+                    state_vars: Rc::new(RefCell::new(SnakeStateStateVars {
+                        snake_state_var: self.snake_domain_var + self.camel_domain_var + self.domain_var_123 + 100,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::SnakeState(context));
+                self.transition(RustNamingState::SnakeState, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                // Start transition
+                let context = CamelStateStateContext {
+                    state_args: Rc::new(RefCell::new(CamelStateStateArgs {
+                        camel_state_param: frame_event.arguments.as_ref().borrow().camel_event_args().camel_param,
+                    })),
+                    state_vars: Rc::new(RefCell::new(CamelStateStateVars {
+                        camel_state_var: self.snake_domain_var + self.camel_domain_var + self.domain_var_123 + 200,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::CamelState(context));
+                self.transition(RustNamingState::CamelState, next_state_context);
+                return;
+            }
+            FrameMessage::Event123 => {
+                // Start transition
+                let context = State123StateContext {
+                    state_args: Rc::new(RefCell::new(State123StateArgs {
+                        state_param_123: frame_event.arguments.as_ref().borrow().event_123_args().param_123,
+                    })),
+                    state_vars: Rc::new(RefCell::new(State123StateVars {
+                        state_var_123: self.snake_domain_var + self.camel_domain_var + self.domain_var_123 + 300,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::State123(context));
+                self.transition(RustNamingState::State123, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.camel_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event_123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn snake_state_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.snake_state_context();
+        match frame_event.message {
+              //  1100
+            FrameMessage::SnakeEvent => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let camel_local_var: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().camel_event_args().camel_param;
+                self.camel_action(camel_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: camel_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Event123 => {
+                let local_var_123: i32 = this_state_context.state_vars.as_ref().borrow().snake_state_var + this_state_context.state_args.as_ref().borrow().snake_state_param + frame_event.arguments.as_ref().borrow().event_123_args().param_123;
+                self.action_123(local_var_123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: local_var_123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.camel_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event_123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn camel_state_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.camel_state_context();
+        match frame_event.message {
+              //  1200
+            FrameMessage::SnakeEvent => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().camel_state_var + this_state_context.state_args.as_ref().borrow().camel_state_param + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let camel_local_var: i32 = this_state_context.state_vars.as_ref().borrow().camel_state_var + this_state_context.state_args.as_ref().borrow().camel_state_param + frame_event.arguments.as_ref().borrow().camel_event_args().camel_param;
+                self.camel_action(camel_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: camel_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Event123 => {
+                let local_var_123: i32 = this_state_context.state_vars.as_ref().borrow().camel_state_var + this_state_context.state_args.as_ref().borrow().camel_state_param + frame_event.arguments.as_ref().borrow().event_123_args().param_123;
+                self.action_123(local_var_123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: local_var_123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.camel_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event_123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn state_123_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.state_123_context();
+        match frame_event.message {
+              //  1300
+            FrameMessage::SnakeEvent => {
+                let snake_local_var: i32 = this_state_context.state_vars.as_ref().borrow().state_var_123 + this_state_context.state_args.as_ref().borrow().state_param_123 + frame_event.arguments.as_ref().borrow().snake_event_args().snake_param;
+                self.snake_action(snake_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: snake_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::CamelEvent => {
+                let camel_local_var: i32 = this_state_context.state_vars.as_ref().borrow().state_var_123 + this_state_context.state_args.as_ref().borrow().state_param_123 + frame_event.arguments.as_ref().borrow().camel_event_args().camel_param;
+                self.camel_action(camel_local_var);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: camel_local_var,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Event123 => {
+                let local_var_123: i32 = this_state_context.state_vars.as_ref().borrow().state_var_123 + this_state_context.state_args.as_ref().borrow().state_param_123 + frame_event.arguments.as_ref().borrow().event_123_args().param_123;
+                self.action_123(local_var_123);
+                // Start transition
+                let context = FinalStateContext {
+                    state_args: Rc::new(RefCell::new(FinalStateArgs {
+                        result: local_var_123,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Final(context));
+                self.transition(RustNamingState::Final, next_state_context);
+                return;
+            }
+            FrameMessage::Call => {
+                if (frame_event.arguments.as_ref().borrow().call_args().event).eq("snake_event") {
+                    self.snake_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("CamelEvent") {
+                    self.camel_event((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else if (frame_event.arguments.as_ref().borrow().call_args().event).eq("event123") {
+                    self.event_123((frame_event.arguments.as_ref().borrow().call_args().param));
+                    return;
+                    return;
+                } else {
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn final_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.final_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log_final((this_state_context.state_args.as_ref().borrow().result));
+                // Start transition
+                let context = InitStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Init(context));
+                self.transition(RustNamingState::Init, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            RustNamingState::Init => self.init_handler(frame_event.clone()),
+            RustNamingState::SnakeState => self.snake_state_handler(frame_event.clone()),
+            RustNamingState::CamelState => self.camel_state_handler(frame_event.clone()),
+            RustNamingState::State123 => self.state_123_handler(frame_event.clone()),
+            RustNamingState::Final => self.final_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: RustNamingState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for RustNaming {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl RustNamingActions for RustNaming { 
+    fn snake_action(&self, snake_param: i32) {}
+    fn camel_action(&self, camel_param: i32) {}
+    fn action_123(&self, param_123: i32) {}
+    fn log_final(&self, r: i32) {}
+}
\ No newline at end of file
@@ -0,0 +1,252 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(SimpleHandlerCallsState),
+    Exit(SimpleHandlerCallsState),
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(SimpleHandlerCallsState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(SimpleHandlerCallsState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(SimpleHandlerCallsState::B) => write!(f, "B:>"),
+            FrameMessage::Exit(SimpleHandlerCallsState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(SimpleHandlerCallsState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(SimpleHandlerCallsState::B) => write!(f, "B:<"),
+            FrameMessage::A => write!(f, "A"),
+            FrameMessage::B => write!(f, "B"),
+            FrameMessage::C => write!(f, "C"),
+            FrameMessage::D => write!(f, "D"),
+            FrameMessage::E => write!(f, "E"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum SimpleHandlerCallsState {
+    Init,
+    A,
+    B,
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct SimpleHandlerCalls {
+    state: SimpleHandlerCallsState,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl SimpleHandlerCalls {
+    
+    pub fn new() -> Self {
+        let mut machine = SimpleHandlerCalls {
+            state: SimpleHandlerCallsState::Init,
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::A, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::B, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::C, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn d(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::D, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn e(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::E, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::A => {
+                // Start transition
+                self.transition(SimpleHandlerCallsState::A);
+                return;
+            }
+            FrameMessage::B => {
+                // Start transition
+                self.transition(SimpleHandlerCallsState::B);
+                return;
+            }
+            FrameMessage::C => {
+                self.a();
+                return;
+                return;
+            }
+            FrameMessage::D => {
+                self.b();
+                return;
+                // Start transition
+                self.transition(SimpleHandlerCallsState::A);
+                return;
+            }
+            FrameMessage::E => {
+                self.d();
+                return;
+                self.c();
+                return;
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            SimpleHandlerCallsState::Init => self.init_handler(frame_event.clone()),
+            SimpleHandlerCallsState::A => self.a_handler(frame_event.clone()),
+            SimpleHandlerCallsState::B => self.b_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: SimpleHandlerCallsState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for SimpleHandlerCalls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
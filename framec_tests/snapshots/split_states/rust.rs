@@ -0,0 +1,185 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(SplitStatesState),
+    Exit(SplitStatesState),
+    Next,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(SplitStatesState::First) => write!(f, "First:>"),
+            FrameMessage::Enter(SplitStatesState::Second) => write!(f, "Second:>"),
+            FrameMessage::Enter(SplitStatesState::Parent) => write!(f, "Parent:>"),
+            FrameMessage::Exit(SplitStatesState::First) => write!(f, "First:<"),
+            FrameMessage::Exit(SplitStatesState::Second) => write!(f, "Second:<"),
+            FrameMessage::Exit(SplitStatesState::Parent) => write!(f, "Parent:<"),
+            FrameMessage::Next => write!(f, "next"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct NextArgs {
+    step: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Next(NextArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn next_args(&self) -> &NextArgs {
+        match self {
+            FrameEventArgs::Next(args) => args,
+            _ => panic!("Failed conversion to NextArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum SplitStatesState {
+    First,
+    Second,
+    Parent,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct SplitStates {
+    state: SplitStatesState,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl SplitStates {
+    
+    pub fn new() -> Self {
+        let mut machine = SplitStates {
+            state: SplitStatesState::First,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn next(&mut self, step: i32) {
+        let frame_args = FrameEventArgs::Next(NextArgs { step, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            SplitStatesState::First => self.first_handler(frame_event.clone()),
+            SplitStatesState::Second => self.second_handler(frame_event.clone()),
+            SplitStatesState::Parent => self.parent_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: SplitStatesState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+mod first_state {
+    include!("split_states/first.rs");
+}
+
+mod second_state {
+    include!("split_states/second.rs");
+}
+
+mod parent_state {
+    include!("split_states/parent.rs");
+}
+
+impl Default for SplitStates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
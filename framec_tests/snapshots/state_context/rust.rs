@@ -0,0 +1,557 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateContextSmState),
+    Exit(StateContextSmState),
+    Start,
+    LogState,
+    Inc,
+    Next,
+    Change,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateContextSmState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(StateContextSmState::Foo) => write!(f, "Foo:>"),
+            FrameMessage::Enter(StateContextSmState::Bar) => write!(f, "Bar:>"),
+            FrameMessage::Exit(StateContextSmState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(StateContextSmState::Foo) => write!(f, "Foo:<"),
+            FrameMessage::Exit(StateContextSmState::Bar) => write!(f, "Bar:<"),
+            FrameMessage::Start => write!(f, "Start"),
+            FrameMessage::LogState => write!(f, "LogState"),
+            FrameMessage::Inc => write!(f, "Inc"),
+            FrameMessage::Next => write!(f, "Next"),
+            FrameMessage::Change => write!(f, "Change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct NextArgs {
+    arg: i32,
+}
+
+#[allow(dead_code)]
+struct ChangeArgs {
+    arg: i32,
+}
+
+#[allow(dead_code)]
+struct FooEnterArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(dead_code)]
+struct FooExitArgs {
+    c: i32,
+}
+
+#[allow(dead_code)]
+struct BarEnterArgs {
+    a: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Next(NextArgs),
+    Change(ChangeArgs),
+    FooEnter(FooEnterArgs),
+    FooExit(FooExitArgs),
+    BarEnter(BarEnterArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn next_args(&self) -> &NextArgs {
+        match self {
+            FrameEventArgs::Next(args) => args,
+            _ => panic!("Failed conversion to NextArgs"),
+        }
+    }
+    fn change_args(&self) -> &ChangeArgs {
+        match self {
+            FrameEventArgs::Change(args) => args,
+            _ => panic!("Failed conversion to ChangeArgs"),
+        }
+    }
+    fn foo_enter_args(&self) -> &FooEnterArgs {
+        match self {
+            FrameEventArgs::FooEnter(args) => args,
+            _ => panic!("Failed conversion to FooEnterArgs"),
+        }
+    }
+    fn foo_exit_args(&self) -> &FooExitArgs {
+        match self {
+            FrameEventArgs::FooExit(args) => args,
+            _ => panic!("Failed conversion to FooExitArgs"),
+        }
+    }
+    fn bar_enter_args(&self) -> &BarEnterArgs {
+        match self {
+            FrameEventArgs::BarEnter(args) => args,
+            _ => panic!("Failed conversion to BarEnterArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    Inc { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_inc_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::Inc { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateContextSmState {
+    Init,
+    Foo,
+    Bar,
+}
+
+#[allow(dead_code)]
+struct InitStateVars {
+    w: i32,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+    state_vars: Rc<RefCell<InitStateVars>>,
+}
+
+#[allow(dead_code)]
+struct FooStateVars {
+    x: i32,
+}
+
+#[allow(dead_code)]
+struct FooStateContext {
+    state_vars: Rc<RefCell<FooStateVars>>,
+}
+
+#[allow(dead_code)]
+struct BarStateArgs {
+    y: i32,
+}
+
+#[allow(dead_code)]
+struct BarStateVars {
+    z: i32,
+}
+
+#[allow(dead_code)]
+struct BarStateContext {
+    state_args: Rc<RefCell<BarStateArgs>>,
+    state_vars: Rc<RefCell<BarStateVars>>,
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    Foo(FooStateContext),
+    Bar(BarStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn foo_context(&self) -> &FooStateContext {
+        match self {
+            StateContext::Foo(context) => context,
+            _ => panic!("Failed conversion to FooStateContext"),
+        }
+    }
+    fn bar_context(&self) -> &BarStateContext {
+        match self {
+            StateContext::Bar(context) => context,
+            _ => panic!("Failed conversion to BarStateContext"),
+        }
+    }
+}#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait StateContextSmActions { 
+    fn log(&self, name: String, val: i32);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateContextSm {
+    state: StateContextSmState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextSm {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+            state_vars: Rc::new(RefCell::new(InitStateVars {
+                w: 0,
+            })),
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = StateContextSm {
+            state: StateContextSmState::Init,
+            state_context: next_state_context,
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn start(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Start, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn log_state(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::LogState, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn inc(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Inc, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::Inc { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for Inc"),
+        };
+        return_value
+    }
+    
+    pub fn next(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Next(NextArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn change(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Change(ChangeArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Change, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                let assign_temp = {
+                    3
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().w = assign_temp;
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().w + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().w = assign_temp;
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().w)
+                });
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                return;
+            }
+            FrameMessage::Start => {
+                // Start transition
+                let exit_args = FrameEventArgs::None;
+                let enter_args = FrameEventArgs::FooEnter(FooEnterArgs {
+                    a: 3,
+                    b: this_state_context.state_vars.as_ref().borrow().w,
+                });
+                let context = FooStateContext {
+                    state_vars: Rc::new(RefCell::new(FooStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Foo(context));
+                self.transition(exit_args, enter_args, StateContextSmState::Foo, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn foo_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.foo_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().foo_enter_args().a));
+                self.log(String::from("b"),(frame_event.arguments.as_ref().borrow().foo_enter_args().b));
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().foo_enter_args().a * frame_event.arguments.as_ref().borrow().foo_enter_args().b
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("c"),(frame_event.arguments.as_ref().borrow().foo_exit_args().c));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + frame_event.arguments.as_ref().borrow().foo_exit_args().c
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().x)
+                });
+                return;
+            }
+            FrameMessage::Next => {
+                let tmp = frame_event.arguments.as_ref().borrow().next_args().arg * 10;
+                // Start transition
+                let exit_args = FrameEventArgs::FooExit(FooExitArgs {
+                    c: 10,
+                });
+                let enter_args = FrameEventArgs::BarEnter(BarEnterArgs {
+                    a: tmp,
+                });
+                let context = BarStateContext {
+                    state_args: Rc::new(RefCell::new(BarStateArgs {
+                        y: this_state_context.state_vars.as_ref().borrow().x,
+                    })),
+                    state_vars: Rc::new(RefCell::new(BarStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Bar(context));
+                self.transition(exit_args, enter_args, StateContextSmState::Bar, next_state_context);
+                return;
+            }
+              //  FIXME: Swapping this to 10 * arg causes a parse error!
+            FrameMessage::Change => {
+                let tmp = this_state_context.state_vars.as_ref().borrow().x + frame_event.arguments.as_ref().borrow().change_args().arg;
+                // Start change state
+                let context = BarStateContext {
+                    state_args: Rc::new(RefCell::new(BarStateArgs {
+                        y: tmp,
+                    })),
+                    state_vars: Rc::new(RefCell::new(BarStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Bar(context));
+                self.change_state(StateContextSmState::Bar, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn bar_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.bar_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().bar_enter_args().a));
+                self.log(String::from("y"),(this_state_context.state_args.as_ref().borrow().y));
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().bar_enter_args().a + this_state_context.state_args.as_ref().borrow().y
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("y"),(this_state_context.state_args.as_ref().borrow().y));
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().z + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().z)
+                });
+                return;
+            }
+            FrameMessage::Change => {
+                let tmp = this_state_context.state_args.as_ref().borrow().y + this_state_context.state_vars.as_ref().borrow().z + frame_event.arguments.as_ref().borrow().change_args().arg;
+                self.log(String::from("tmp"),tmp);
+                // Start change state
+                let context = InitStateContext {
+                    state_vars: Rc::new(RefCell::new(InitStateVars {
+                        w: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Init(context));
+                self.change_state(StateContextSmState::Init, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            StateContextSmState::Init => self.init_handler(frame_event.clone()),
+            StateContextSmState::Foo => self.foo_handler(frame_event.clone()),
+            StateContextSmState::Bar => self.bar_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, exit_args: FrameEventArgs, enter_args: FrameEventArgs, new_state: StateContextSmState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            exit_args,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, new_state: StateContextSmState, new_state_context: Rc<StateContext>) {
+        self.state = new_state;
+        self.state_context = new_state_context;
+    }
+    
+} // end system controller
+
+impl Default for StateContextSm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextSmActions for StateContextSm { 
+    fn log(&self, name: String, val: i32) {}
+}
\ No newline at end of file
@@ -0,0 +1,1071 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateContextSmState),
+    Exit(StateContextSmState),
+    Start,
+    LogState,
+    Inc,
+    Next,
+    Change,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateContextSmState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(StateContextSmState::Foo) => write!(f, "Foo:>"),
+            FrameMessage::Enter(StateContextSmState::Bar) => write!(f, "Bar:>"),
+            FrameMessage::Exit(StateContextSmState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(StateContextSmState::Foo) => write!(f, "Foo:<"),
+            FrameMessage::Exit(StateContextSmState::Bar) => write!(f, "Bar:<"),
+            FrameMessage::Start => write!(f, "Start"),
+            FrameMessage::LogState => write!(f, "LogState"),
+            FrameMessage::Inc => write!(f, "Inc"),
+            FrameMessage::Next => write!(f, "Next"),
+            FrameMessage::Change => write!(f, "Change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct NextArgs {
+    arg: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for NextArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "arg" => Some(Box::new(self.arg.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct ChangeArgs {
+    arg: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for ChangeArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "arg" => Some(Box::new(self.arg.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct FooEnterArgs {
+    a: i32,
+    b: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for FooEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "a" => Some(Box::new(self.a.clone())),
+            "b" => Some(Box::new(self.b.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct FooExitArgs {
+    c: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for FooExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "c" => Some(Box::new(self.c.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BarEnterArgs {
+    a: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BarEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "a" => Some(Box::new(self.a.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Next(NextArgs),
+    Change(ChangeArgs),
+    FooEnter(FooEnterArgs),
+    FooExit(FooExitArgs),
+    BarEnter(BarEnterArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::Next(_) => false,
+            FrameEventArgs::Change(_) => false,
+            FrameEventArgs::FooEnter(_) => false,
+            FrameEventArgs::FooExit(_) => false,
+            FrameEventArgs::BarEnter(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::Next(args) => args.lookup(name),
+            FrameEventArgs::Change(args) => args.lookup(name),
+            FrameEventArgs::FooEnter(args) => args.lookup(name),
+            FrameEventArgs::FooExit(args) => args.lookup(name),
+            FrameEventArgs::BarEnter(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn next_args(&self) -> &NextArgs {
+        match self {
+            FrameEventArgs::Next(args) => args,
+            _ => panic!("Failed conversion to NextArgs"),
+        }
+    }
+    fn change_args(&self) -> &ChangeArgs {
+        match self {
+            FrameEventArgs::Change(args) => args,
+            _ => panic!("Failed conversion to ChangeArgs"),
+        }
+    }
+    fn foo_enter_args(&self) -> &FooEnterArgs {
+        match self {
+            FrameEventArgs::FooEnter(args) => args,
+            _ => panic!("Failed conversion to FooEnterArgs"),
+        }
+    }
+    fn foo_exit_args(&self) -> &FooExitArgs {
+        match self {
+            FrameEventArgs::FooExit(args) => args,
+            _ => panic!("Failed conversion to FooExitArgs"),
+        }
+    }
+    fn bar_enter_args(&self) -> &BarEnterArgs {
+        match self {
+            FrameEventArgs::BarEnter(args) => args,
+            _ => panic!("Failed conversion to BarEnterArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    Inc { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_inc_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::Inc { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<StateContextSm> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::Inc { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateContextSmState {
+    Init,
+    Foo,
+    Bar,
+}
+
+#[allow(dead_code)]
+struct InitStateVars {
+    w: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for InitStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "w" => Some(Box::new(self.w.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+    state_vars: Rc<RefCell<InitStateVars>>,
+}
+
+impl runtime::State<StateContextSm> for InitStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+struct FooStateVars {
+    x: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for FooStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "x" => Some(Box::new(self.x.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct FooStateContext {
+    state_vars: Rc<RefCell<FooStateVars>>,
+}
+
+impl runtime::State<StateContextSm> for FooStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+struct BarStateArgs {
+    y: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BarStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "y" => Some(Box::new(self.y.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BarStateVars {
+    z: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BarStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "z" => Some(Box::new(self.z.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BarStateContext {
+    state_args: Rc<RefCell<BarStateArgs>>,
+    state_vars: Rc<RefCell<BarStateVars>>,
+}
+
+impl runtime::State<StateContextSm> for BarStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    Foo(FooStateContext),
+    Bar(BarStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn foo_context(&self) -> &FooStateContext {
+        match self {
+            StateContext::Foo(context) => context,
+            _ => panic!("Failed conversion to FooStateContext"),
+        }
+    }
+    fn bar_context(&self) -> &BarStateContext {
+        match self {
+            StateContext::Bar(context) => context,
+            _ => panic!("Failed conversion to BarStateContext"),
+        }
+    }
+}
+
+impl runtime::State<StateContextSm> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Init(context) => context.info(),
+            StateContext::Foo(context) => context.info(),
+            StateContext::Bar(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.arguments(),
+            StateContext::Foo(context) => context.arguments(),
+            StateContext::Bar(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <StateContextSm as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.variables(),
+            StateContext::Foo(context) => context.variables(),
+            StateContext::Bar(context) => context.variables(),
+        }
+    }
+}#[allow(clippy::ptr_arg)]
+#[allow(dead_code)]
+trait StateContextSmActions { 
+    fn log(&self, name: String, val: i32);
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateContextSm {
+    state: StateContextSmState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for StateContextSm {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for StateContextSm {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <StateContextSm as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for StateContextSm {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextSm {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+            state_vars: Rc::new(RefCell::new(InitStateVars {
+                w: 0,
+            })),
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = StateContextSm {
+            state: StateContextSmState::Init,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn start(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Start, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn log_state(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::LogState, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn inc(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Inc, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::Inc { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for Inc"),
+        };
+        return_value
+    }
+    
+    pub fn next(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Next(NextArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn change(&mut self, arg: i32) {
+        let frame_args = FrameEventArgs::Change(ChangeArgs { arg, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Change, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                let assign_temp = {
+                    3
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().w = assign_temp;
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().w + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().w = assign_temp;
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().w)
+                });
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("w"),(this_state_context.state_vars.as_ref().borrow().w));
+                return;
+            }
+            FrameMessage::Start => {
+                // Start transition
+                // transition 1
+                let exit_args = FrameEventArgs::None;
+                let enter_args = FrameEventArgs::FooEnter(FooEnterArgs {
+                    a: 3,
+                    b: this_state_context.state_vars.as_ref().borrow().w,
+                });
+                let context = FooStateContext {
+                    state_vars: Rc::new(RefCell::new(FooStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Foo(context));
+                self.transition(runtime_info::machine_info().transitions[0], exit_args, enter_args, StateContextSmState::Foo, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn foo_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.foo_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().foo_enter_args().a));
+                self.log(String::from("b"),(frame_event.arguments.as_ref().borrow().foo_enter_args().b));
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().foo_enter_args().a * frame_event.arguments.as_ref().borrow().foo_enter_args().b
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("c"),(frame_event.arguments.as_ref().borrow().foo_exit_args().c));
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + frame_event.arguments.as_ref().borrow().foo_exit_args().c
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                self.log(String::from("x"),(this_state_context.state_vars.as_ref().borrow().x));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().x)
+                });
+                return;
+            }
+            FrameMessage::Next => {
+                let tmp = frame_event.arguments.as_ref().borrow().next_args().arg * 10;
+                // Start transition
+                // transition 2
+                let exit_args = FrameEventArgs::FooExit(FooExitArgs {
+                    c: 10,
+                });
+                let enter_args = FrameEventArgs::BarEnter(BarEnterArgs {
+                    a: tmp,
+                });
+                let context = BarStateContext {
+                    state_args: Rc::new(RefCell::new(BarStateArgs {
+                        y: this_state_context.state_vars.as_ref().borrow().x,
+                    })),
+                    state_vars: Rc::new(RefCell::new(BarStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Bar(context));
+                self.transition(runtime_info::machine_info().transitions[1], exit_args, enter_args, StateContextSmState::Bar, next_state_context);
+                return;
+            }
+              //  FIXME: Swapping this to 10 * arg causes a parse error!
+            FrameMessage::Change => {
+                let tmp = this_state_context.state_vars.as_ref().borrow().x + frame_event.arguments.as_ref().borrow().change_args().arg;
+                // Start change state
+                // change-state 1
+                let context = BarStateContext {
+                    state_args: Rc::new(RefCell::new(BarStateArgs {
+                        y: tmp,
+                    })),
+                    state_vars: Rc::new(RefCell::new(BarStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Bar(context));
+                self.change_state(runtime_info::machine_info().transitions[2], StateContextSmState::Bar, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn bar_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.bar_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("a"),(frame_event.arguments.as_ref().borrow().bar_enter_args().a));
+                self.log(String::from("y"),(this_state_context.state_args.as_ref().borrow().y));
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().bar_enter_args().a + this_state_context.state_args.as_ref().borrow().y
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                return;
+            }
+            FrameMessage::LogState => {
+                self.log(String::from("y"),(this_state_context.state_args.as_ref().borrow().y));
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().z + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                self.log(String::from("z"),(this_state_context.state_vars.as_ref().borrow().z));
+                frame_event.ret.replace(FrameEventReturn::Inc {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().z)
+                });
+                return;
+            }
+            FrameMessage::Change => {
+                let tmp = this_state_context.state_args.as_ref().borrow().y + this_state_context.state_vars.as_ref().borrow().z + frame_event.arguments.as_ref().borrow().change_args().arg;
+                self.log(String::from("tmp"),tmp);
+                // Start change state
+                // change-state 2
+                let context = InitStateContext {
+                    state_vars: Rc::new(RefCell::new(InitStateVars {
+                        w: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Init(context));
+                self.change_state(runtime_info::machine_info().transitions[3], StateContextSmState::Init, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            StateContextSmState::Init => self.init_handler(frame_event.clone()),
+            StateContextSmState::Foo => self.foo_handler(frame_event.clone()),
+            StateContextSmState::Bar => self.bar_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, exit_args: FrameEventArgs, enter_args: FrameEventArgs, new_state: StateContextSmState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            exit_args,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <StateContextSm as runtime::Machine>::StatePtr,
+            new_state_context as <StateContextSm as runtime::Machine>::StatePtr,
+            exit_event as <StateContextSm as runtime::Machine>::EventPtr,
+            enter_event.clone() as <StateContextSm as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateContextSmState, new_state_context: Rc<StateContext>) {
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            old_state_context as <StateContextSm as runtime::Machine>::StatePtr,
+            new_state_context as <StateContextSm as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for StateContextSm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/state_context_runtime.frm"),
+        sha256: Some("ec14f1438d6c84364954a697d22b81de7073d398c9aa9b081d80e5b8be37f856"),
+        name: "StateContextSm",
+        variables: &[
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_INIT,
+            STATE_FOO,
+            STATE_BAR,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "name",
+                    vtype: "String",
+                },
+                NameInfo {
+                    name: "val",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "Start",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "LogState",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Inc",
+            parameters: &[],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "Next",
+            parameters: &[
+                NameInfo {
+                    name: "arg",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Change",
+            parameters: &[
+                NameInfo {
+                    name: "arg",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Foo:>",
+            parameters: &[
+                NameInfo {
+                    name: "a",
+                    vtype: "i32",
+                },
+                NameInfo {
+                    name: "b",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Foo:<",
+            parameters: &[
+                NameInfo {
+                    name: "c",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Bar:>",
+            parameters: &[
+                NameInfo {
+                    name: "a",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Bar:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "transition 1",
+            source: STATE_INIT,
+            target: STATE_FOO,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[3],
+            label: "transition 2",
+            source: STATE_FOO,
+            target: STATE_BAR,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[4],
+            label: "change-state 1",
+            source: STATE_FOO,
+            target: STATE_BAR,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[4],
+            label: "change-state 2",
+            source: STATE_BAR,
+            target: STATE_INIT,
+        },
+    ];
+    static STATE_INIT: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Init",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "w",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[5],
+            EVENTS[2],
+            EVENTS[1],
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_FOO: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Foo",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "x",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[7],
+            EVENTS[8],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_BAR: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Bar",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "y",
+                vtype: "i32",
+            },
+        ],
+        variables: &[
+            NameInfo {
+                name: "z",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[9],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextSmActions for StateContextSm { 
+    fn log(&self, name: String, val: i32) {}
+}
\ No newline at end of file
@@ -0,0 +1,1130 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateContextStackState),
+    Exit(StateContextStackState),
+    ToA,
+    ToB,
+    ToC,
+    Inc,
+    Value,
+    Push,
+    Pop,
+    PopChange,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateContextStackState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(StateContextStackState::B) => write!(f, "B:>"),
+            FrameMessage::Enter(StateContextStackState::C) => write!(f, "C:>"),
+            FrameMessage::Exit(StateContextStackState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(StateContextStackState::B) => write!(f, "B:<"),
+            FrameMessage::Exit(StateContextStackState::C) => write!(f, "C:<"),
+            FrameMessage::ToA => write!(f, "to_a"),
+            FrameMessage::ToB => write!(f, "to_b"),
+            FrameMessage::ToC => write!(f, "to_c"),
+            FrameMessage::Inc => write!(f, "inc"),
+            FrameMessage::Value => write!(f, "value"),
+            FrameMessage::Push => write!(f, "push"),
+            FrameMessage::Pop => write!(f, "pop"),
+            FrameMessage::PopChange => write!(f, "pop_change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    Value { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_value_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::Value { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<StateContextStack> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::Value { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateContextStackState {
+    A,
+    B,
+    C,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct AStateVars {
+    x: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "x" => Some(Box::new(self.x.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+    state_vars: Rc<RefCell<AStateVars>>,
+}
+
+impl AStateContext {
+    fn deep_clone(&self) -> AStateContext {
+        AStateContext {
+            state_vars: Rc::new((*self.state_vars).clone())
+        }
+    }
+}
+
+impl runtime::State<StateContextStack> for AStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct BStateVars {
+    y: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "y" => Some(Box::new(self.y.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+    state_vars: Rc<RefCell<BStateVars>>,
+}
+
+impl BStateContext {
+    fn deep_clone(&self) -> BStateContext {
+        BStateContext {
+            state_vars: Rc::new((*self.state_vars).clone())
+        }
+    }
+}
+
+impl runtime::State<StateContextStack> for BStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct CStateVars {
+    z: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for CStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "z" => Some(Box::new(self.z.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct CStateContext {
+    state_vars: Rc<RefCell<CStateVars>>,
+}
+
+impl CStateContext {
+    fn deep_clone(&self) -> CStateContext {
+        CStateContext {
+            state_vars: Rc::new((*self.state_vars).clone())
+        }
+    }
+}
+
+impl runtime::State<StateContextStack> for CStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    A(AStateContext),
+    B(BStateContext),
+    C(CStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+    fn c_context(&self) -> &CStateContext {
+        match self {
+            StateContext::C(context) => context,
+            _ => panic!("Failed conversion to CStateContext"),
+        }
+    }
+    fn deep_clone(&self) -> StateContext {
+        match self {
+            StateContext::A(context) => StateContext::A(context.deep_clone()),
+            StateContext::B(context) => StateContext::B(context.deep_clone()),
+            StateContext::C(context) => StateContext::C(context.deep_clone()),
+        }
+    }
+}
+
+impl runtime::State<StateContextStack> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::A(context) => context.info(),
+            StateContext::B(context) => context.info(),
+            StateContext::C(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::A(context) => context.arguments(),
+            StateContext::B(context) => context.arguments(),
+            StateContext::C(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <StateContextStack as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::A(context) => context.variables(),
+            StateContext::B(context) => context.variables(),
+            StateContext::C(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateContextStack {
+    state: StateContextStackState,
+    state_context: Rc<StateContext>,
+    state_stack: Vec<(StateContextStackState, StateContext)>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for StateContextStack {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for StateContextStack {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <StateContextStack as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for StateContextStack {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextStack {
+    
+    pub fn new() -> Self {
+        let context = AStateContext {
+            state_vars: Rc::new(RefCell::new(AStateVars {
+                x: 0,
+            })),
+        };
+        let next_state_context = Rc::new(StateContext::A(context));
+        let mut machine = StateContextStack {
+            state: StateContextStackState::A,
+            state_context: next_state_context,
+            state_stack: Vec::new(),
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn to_a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToA, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToB, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToC, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn inc(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Inc, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn value(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Value, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::Value { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for value"),
+        };
+        return_value
+    }
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Push, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pop(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Pop, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pop_change(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::PopChange, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("A:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("A:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret.replace(FrameEventReturn::Value {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().x)
+                });
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[0], StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: Rc::new(RefCell::new(BStateVars {
+                        y: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[1], StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: Rc::new(RefCell::new(CStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::C(context));
+                self.transition(runtime_info::machine_info().transitions[2], StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                let state_context_clone = self.state_context.clone();
+                let this_state_context = state_context_clone.a_context();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.transition(runtime_info::machine_info().transitions[3], next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.change_state(runtime_info::machine_info().transitions[4], next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("B:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("B:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().y + 5
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().y = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret.replace(FrameEventReturn::Value {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().y)
+                });
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[5], StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: Rc::new(RefCell::new(BStateVars {
+                        y: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[6], StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: Rc::new(RefCell::new(CStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::C(context));
+                self.transition(runtime_info::machine_info().transitions[7], StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                let state_context_clone = self.state_context.clone();
+                let this_state_context = state_context_clone.b_context();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.transition(runtime_info::machine_info().transitions[8], next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.change_state(runtime_info::machine_info().transitions[9], next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn c_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.c_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("C:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("C:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().z + 10
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret.replace(FrameEventReturn::Value {
+                    return_value: (this_state_context.state_vars.as_ref().borrow().z)
+                });
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[10], StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: Rc::new(RefCell::new(BStateVars {
+                        y: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[11], StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: Rc::new(RefCell::new(CStateVars {
+                        z: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::C(context));
+                self.transition(runtime_info::machine_info().transitions[12], StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                let state_context_clone = self.state_context.clone();
+                let this_state_context = state_context_clone.c_context();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.transition(runtime_info::machine_info().transitions[13], next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = Rc::new(popped_state_context);
+                self.change_state(runtime_info::machine_info().transitions[14], next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            StateContextStackState::A => self.a_handler(frame_event.clone()),
+            StateContextStackState::B => self.b_handler(frame_event.clone()),
+            StateContextStackState::C => self.c_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateContextStackState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <StateContextStack as runtime::Machine>::StatePtr,
+            new_state_context as <StateContextStack as runtime::Machine>::StatePtr,
+            exit_event as <StateContextStack as runtime::Machine>::EventPtr,
+            enter_event.clone() as <StateContextStack as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn state_stack_push(&mut self) {
+        self.state_stack.push((self.state, (*self.state_context).deep_clone()));
+    }
+    
+    fn state_stack_pop(&mut self) -> (StateContextStackState, StateContext) {
+        match self.state_stack.pop() {
+            Some(elem) => elem,
+            None => panic!("Error: attempted to pop when history stack is empty.")
+        }
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateContextStackState, new_state_context: Rc<StateContext>) {
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            old_state_context as <StateContextStack as runtime::Machine>::StatePtr,
+            new_state_context as <StateContextStack as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for StateContextStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/state_context_stack.frm"),
+        sha256: Some("3469b5ccddf1b390e2340719a58ce6bef2de241764cd440c494f6b35fd5a84f7"),
+        name: "StateContextStack",
+        variables: &[
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_A,
+            STATE_B,
+            STATE_C,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[5],
+            EVENTS[6],
+            EVENTS[7],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "to_a",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "to_b",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "to_c",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "inc",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "value",
+            parameters: &[],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "push",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "pop",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "pop_change",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_A,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_A,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[6],
+            label: "",
+            source: STATE_A,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[7],
+            label: "",
+            source: STATE_A,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_B,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 7,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_B,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 8,
+            kind: TransitionKind::Transition,
+            event: EVENTS[6],
+            label: "",
+            source: STATE_B,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 9,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[7],
+            label: "",
+            source: STATE_B,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 10,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_C,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 11,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_C,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 12,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_C,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 13,
+            kind: TransitionKind::Transition,
+            event: EVENTS[6],
+            label: "",
+            source: STATE_C,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 14,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[7],
+            label: "",
+            source: STATE_C,
+            target: SPECIAL_STATE_POP,
+        },
+    ];
+    static SPECIAL_STATE_POP: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "$$[-]",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[],
+        is_stack_pop: true,
+    };
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "x",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[8],
+            EVENTS[9],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[5],
+            EVENTS[6],
+            EVENTS[7],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "y",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[10],
+            EVENTS[11],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[5],
+            EVENTS[6],
+            EVENTS[7],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_C: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "C",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "z",
+                vtype: "i32",
+            },
+        ],
+        handlers: &[
+            EVENTS[12],
+            EVENTS[13],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[5],
+            EVENTS[6],
+            EVENTS[7],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,585 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateContextStackState),
+    Exit(StateContextStackState),
+    ToA,
+    ToB,
+    ToC,
+    Inc,
+    Value,
+    Push,
+    Pop,
+    PopChange,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateContextStackState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(StateContextStackState::B) => write!(f, "B:>"),
+            FrameMessage::Enter(StateContextStackState::C) => write!(f, "C:>"),
+            FrameMessage::Exit(StateContextStackState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(StateContextStackState::B) => write!(f, "B:<"),
+            FrameMessage::Exit(StateContextStackState::C) => write!(f, "C:<"),
+            FrameMessage::ToA => write!(f, "to_a"),
+            FrameMessage::ToB => write!(f, "to_b"),
+            FrameMessage::ToC => write!(f, "to_c"),
+            FrameMessage::Inc => write!(f, "inc"),
+            FrameMessage::Value => write!(f, "value"),
+            FrameMessage::Push => write!(f, "push"),
+            FrameMessage::Pop => write!(f, "pop"),
+            FrameMessage::PopChange => write!(f, "pop_change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+    Value { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_value_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::Value { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: FrameEventArgs,
+    ret: FrameEventReturn,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments,
+            ret: FrameEventReturn::None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateContextStackState {
+    A,
+    B,
+    C,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct AStateVars {
+    x: i32,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct AStateContext {
+    state_vars: AStateVars,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct BStateVars {
+    y: i32,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct BStateContext {
+    state_vars: BStateVars,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct CStateVars {
+    z: i32,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+struct CStateContext {
+    state_vars: CStateVars,
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum StateContext {
+    A(AStateContext),
+    B(BStateContext),
+    C(CStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn a_context_mut(&mut self) -> &mut AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+    fn b_context_mut(&mut self) -> &mut BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+    fn c_context(&self) -> &CStateContext {
+        match self {
+            StateContext::C(context) => context,
+            _ => panic!("Failed conversion to CStateContext"),
+        }
+    }
+    fn c_context_mut(&mut self) -> &mut CStateContext {
+        match self {
+            StateContext::C(context) => context,
+            _ => panic!("Failed conversion to CStateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateContextStack {
+    state: StateContextStackState,
+    state_context: StateContext,
+    state_stack: Vec<(StateContextStackState, StateContext)>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateContextStack {
+    
+    pub fn new() -> Self {
+        let context = AStateContext {
+            state_vars: AStateVars {
+                x: 0,
+            },
+        };
+        let next_state_context = StateContext::A(context);
+        let mut machine = StateContextStack {
+            state: StateContextStackState::A,
+            state_context: next_state_context,
+            state_stack: Vec::new(),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let mut frame_event = FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        );
+        self.handle_event(&mut frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn to_a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::ToA, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn to_b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::ToB, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn to_c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::ToC, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn inc(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::Inc, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn value(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::Value, frame_args);
+        self.handle_event(&mut frame_event);
+        let return_value = match frame_event.ret {
+            FrameEventReturn::Value { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for value"),
+        };
+        return_value
+    }
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::Push, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn pop(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::Pop, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    pub fn pop_change(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let mut frame_event = FrameEvent::new(FrameMessage::PopChange, frame_args);
+        self.handle_event(&mut frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: &mut FrameEvent) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("A:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("A:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    self.state_context.a_context().state_vars.x + 1
+                };
+                self.state_context.a_context_mut().state_vars.x = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret = FrameEventReturn::Value {
+                    return_value: (self.state_context.a_context().state_vars.x)
+                };
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: AStateVars {
+                        x: 0,
+                    },
+                };
+                let next_state_context = StateContext::A(context);
+                self.transition(StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: BStateVars {
+                        y: 0,
+                    },
+                };
+                let next_state_context = StateContext::B(context);
+                self.transition(StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: CStateVars {
+                        z: 0,
+                    },
+                };
+                let next_state_context = StateContext::C(context);
+                self.transition(StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.transition(next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.change_state(next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: &mut FrameEvent) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("B:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("B:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    self.state_context.b_context().state_vars.y + 5
+                };
+                self.state_context.b_context_mut().state_vars.y = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret = FrameEventReturn::Value {
+                    return_value: (self.state_context.b_context().state_vars.y)
+                };
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: AStateVars {
+                        x: 0,
+                    },
+                };
+                let next_state_context = StateContext::A(context);
+                self.transition(StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: BStateVars {
+                        y: 0,
+                    },
+                };
+                let next_state_context = StateContext::B(context);
+                self.transition(StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: CStateVars {
+                        z: 0,
+                    },
+                };
+                let next_state_context = StateContext::C(context);
+                self.transition(StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.transition(next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.change_state(next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn c_handler(&mut self, frame_event: &mut FrameEvent) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("C:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("C:<"));
+                return;
+            }
+            FrameMessage::Inc => {
+                let assign_temp = {
+                    self.state_context.c_context().state_vars.z + 10
+                };
+                self.state_context.c_context_mut().state_vars.z = assign_temp;
+                return;
+            }
+            FrameMessage::Value => {
+                frame_event.ret = FrameEventReturn::Value {
+                    return_value: (self.state_context.c_context().state_vars.z)
+                };
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: AStateVars {
+                        x: 0,
+                    },
+                };
+                let next_state_context = StateContext::A(context);
+                self.transition(StateContextStackState::A, next_state_context);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: BStateVars {
+                        y: 0,
+                    },
+                };
+                let next_state_context = StateContext::B(context);
+                self.transition(StateContextStackState::B, next_state_context);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                let context = CStateContext {
+                    state_vars: CStateVars {
+                        z: 0,
+                    },
+                };
+                let next_state_context = StateContext::C(context);
+                self.transition(StateContextStackState::C, next_state_context);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.transition(next_state, next_state_context);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let (next_state, popped_state_context) = self.state_stack_pop();
+                let next_state_context = popped_state_context;
+                self.change_state(next_state, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: &mut FrameEvent) {
+        match self.state {
+            StateContextStackState::A => self.a_handler(frame_event),
+            StateContextStackState::B => self.b_handler(frame_event),
+            StateContextStackState::C => self.c_handler(frame_event),
+        }
+    }
+    
+    fn transition(&mut self, new_state: StateContextStackState, new_state_context: StateContext) {
+        let mut exit_event = FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        );
+        self.handle_event(&mut exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let mut enter_event = FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        );
+        self.handle_event(&mut enter_event);
+    }
+    
+    fn state_stack_push(&mut self) {
+        self.state_stack.push((self.state, self.state_context.clone()));
+    }
+    
+    fn state_stack_pop(&mut self) -> (StateContextStackState, StateContext) {
+        match self.state_stack.pop() {
+            Some(elem) => elem,
+            None => panic!("Error: attempted to pop when history stack is empty.")
+        }
+    }
+    
+    fn change_state(&mut self, new_state: StateContextStackState, new_state_context: StateContext) {
+        self.state = new_state;
+        self.state_context = new_state_context;
+    }
+    
+} // end system controller
+
+impl Default for StateContextStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
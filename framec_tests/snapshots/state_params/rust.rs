@@ -0,0 +1,705 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateParamsState),
+    Exit(StateParamsState),
+    Next,
+    Prev,
+    Log,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateParamsState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(StateParamsState::Split) => write!(f, "Split:>"),
+            FrameMessage::Enter(StateParamsState::Merge) => write!(f, "Merge:>"),
+            FrameMessage::Exit(StateParamsState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(StateParamsState::Split) => write!(f, "Split:<"),
+            FrameMessage::Exit(StateParamsState::Merge) => write!(f, "Merge:<"),
+            FrameMessage::Next => write!(f, "Next"),
+            FrameMessage::Prev => write!(f, "Prev"),
+            FrameMessage::Log => write!(f, "Log"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<StateParams> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateParamsState {
+    Init,
+    Split,
+    Merge,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+impl runtime::State<StateParams> for InitStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct SplitStateArgs {
+    val: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for SplitStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "val" => Some(Box::new(self.val.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct SplitStateContext {
+    state_args: Rc<RefCell<SplitStateArgs>>,
+}
+
+impl runtime::State<StateParams> for SplitStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct MergeStateArgs {
+    left: u32,
+    right: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for MergeStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "left" => Some(Box::new(self.left.clone())),
+            "right" => Some(Box::new(self.right.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct MergeStateContext {
+    state_args: Rc<RefCell<MergeStateArgs>>,
+}
+
+impl runtime::State<StateParams> for MergeStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    Split(SplitStateContext),
+    Merge(MergeStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn split_context(&self) -> &SplitStateContext {
+        match self {
+            StateContext::Split(context) => context,
+            _ => panic!("Failed conversion to SplitStateContext"),
+        }
+    }
+    fn merge_context(&self) -> &MergeStateContext {
+        match self {
+            StateContext::Merge(context) => context,
+            _ => panic!("Failed conversion to MergeStateContext"),
+        }
+    }
+}
+
+impl runtime::State<StateParams> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Init(context) => context.info(),
+            StateContext::Split(context) => context.info(),
+            StateContext::Merge(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.arguments(),
+            StateContext::Split(context) => context.arguments(),
+            StateContext::Merge(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <StateParams as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.variables(),
+            StateContext::Split(context) => context.variables(),
+            StateContext::Merge(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateParams {
+    state: StateParamsState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    param_log: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for StateParams {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "param_log" => Some(Box::new(self.param_log.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for StateParams {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <StateParams as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for StateParams {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateParams {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = StateParams {
+            state: StateParamsState::Init,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            param_log: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn prev(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Prev, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn log(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Log, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                let context = SplitStateContext {
+                    state_args: Rc::new(RefCell::new(SplitStateArgs {
+                        val: 1,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Split(context));
+                self.transition(runtime_info::machine_info().transitions[0], StateParamsState::Split, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn split_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.split_context();
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                let context = MergeStateContext {
+                    state_args: Rc::new(RefCell::new(MergeStateArgs {
+                        left: this_state_context.state_args.as_ref().borrow().val,
+                        right: this_state_context.state_args.as_ref().borrow().val + 1,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Merge(context));
+                self.transition(runtime_info::machine_info().transitions[1], StateParamsState::Merge, next_state_context);
+                return;
+            }
+            FrameMessage::Prev => {
+                // Start transition
+                let context = MergeStateContext {
+                    state_args: Rc::new(RefCell::new(MergeStateArgs {
+                        left: this_state_context.state_args.as_ref().borrow().val + 1,
+                        right: this_state_context.state_args.as_ref().borrow().val,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Merge(context));
+                self.transition(runtime_info::machine_info().transitions[2], StateParamsState::Merge, next_state_context);
+                return;
+            }
+            FrameMessage::Log => {
+                self.got_param(String::from("val"),(this_state_context.state_args.as_ref().borrow().val));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn merge_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.merge_context();
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                let context = SplitStateContext {
+                    state_args: Rc::new(RefCell::new(SplitStateArgs {
+                        val: this_state_context.state_args.as_ref().borrow().left + this_state_context.state_args.as_ref().borrow().right,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Split(context));
+                self.transition(runtime_info::machine_info().transitions[3], StateParamsState::Split, next_state_context);
+                return;
+            }
+            FrameMessage::Prev => {
+                // Start transition
+                let context = SplitStateContext {
+                    state_args: Rc::new(RefCell::new(SplitStateArgs {
+                        val: this_state_context.state_args.as_ref().borrow().left * this_state_context.state_args.as_ref().borrow().right,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Split(context));
+                self.transition(runtime_info::machine_info().transitions[4], StateParamsState::Split, next_state_context);
+                return;
+            }
+            FrameMessage::Log => {
+                self.got_param(String::from("left"),(this_state_context.state_args.as_ref().borrow().left));
+                self.got_param(String::from("right"),(this_state_context.state_args.as_ref().borrow().right));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            StateParamsState::Init => self.init_handler(frame_event.clone()),
+            StateParamsState::Split => self.split_handler(frame_event.clone()),
+            StateParamsState::Merge => self.merge_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateParamsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <StateParams as runtime::Machine>::StatePtr,
+            new_state_context as <StateParams as runtime::Machine>::StatePtr,
+            exit_event as <StateParams as runtime::Machine>::EventPtr,
+            enter_event.clone() as <StateParams as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for StateParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/state_params.frm"),
+        sha256: Some("979977c8b3f8d3afd9bcec8e84c6eeaeffe7652bf6ab9e1f5a70878d32a01166"),
+        name: "StateParams",
+        variables: &[
+            NameInfo {
+                name: "param_log",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_INIT,
+            STATE_SPLIT,
+            STATE_MERGE,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "got_param",
+            parameters: &[
+                NameInfo {
+                    name: "name",
+                    vtype: "&String",
+                },
+                NameInfo {
+                    name: "val",
+                    vtype: "u32",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "Next",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Prev",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Log",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Split:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Split:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Merge:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Merge:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_INIT,
+            target: STATE_SPLIT,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_SPLIT,
+            target: STATE_MERGE,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_SPLIT,
+            target: STATE_MERGE,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_MERGE,
+            target: STATE_SPLIT,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_MERGE,
+            target: STATE_SPLIT,
+        },
+    ];
+    static STATE_INIT: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Init",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_SPLIT: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Split",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "val",
+                vtype: "u32",
+            },
+        ],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_MERGE: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Merge",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "left",
+                vtype: "u32",
+            },
+            NameInfo {
+                name: "right",
+                vtype: "u32",
+            },
+        ],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+}
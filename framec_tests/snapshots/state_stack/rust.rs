@@ -0,0 +1,776 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateStackState),
+    Exit(StateStackState),
+    ToA,
+    ToB,
+    ToC,
+    Push,
+    Pop,
+    PopChange,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateStackState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(StateStackState::B) => write!(f, "B:>"),
+            FrameMessage::Enter(StateStackState::C) => write!(f, "C:>"),
+            FrameMessage::Exit(StateStackState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(StateStackState::B) => write!(f, "B:<"),
+            FrameMessage::Exit(StateStackState::C) => write!(f, "C:<"),
+            FrameMessage::ToA => write!(f, "to_a"),
+            FrameMessage::ToB => write!(f, "to_b"),
+            FrameMessage::ToC => write!(f, "to_c"),
+            FrameMessage::Push => write!(f, "push"),
+            FrameMessage::Pop => write!(f, "pop"),
+            FrameMessage::PopChange => write!(f, "pop_change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<StateStack> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <StateStack as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateStackState {
+    A,
+    B,
+    C,
+}
+
+impl runtime::State<StateStack> for StateStackState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateStackState::A => runtime_info::machine_info().states[0],
+            StateStackState::B => runtime_info::machine_info().states[1],
+            StateStackState::C => runtime_info::machine_info().states[2],
+        }
+    }
+    fn arguments(&self) -> <StateStack as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateStack as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateStack {
+    state: StateStackState,
+    state_stack: Vec<StateStackState>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for StateStack {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for StateStack {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <StateStack as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for StateStack {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateStack {
+    
+    pub fn new() -> Self {
+        let mut machine = StateStack {
+            state: StateStackState::A,
+            state_stack: Vec::new(),
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn to_a(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToA, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_b(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToB, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_c(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToC, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn push(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Push, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pop(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Pop, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn pop_change(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::PopChange, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("A:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("A:<"));
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], StateStackState::A);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[1], StateStackState::B);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[2], StateStackState::C);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let next_state = self.state_stack_pop();
+                self.transition(runtime_info::machine_info().transitions[3], next_state);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let next_state = self.state_stack_pop();
+                self.change_state(runtime_info::machine_info().transitions[4], next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("B:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("B:<"));
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[5], StateStackState::A);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[6], StateStackState::B);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[7], StateStackState::C);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let next_state = self.state_stack_pop();
+                self.transition(runtime_info::machine_info().transitions[8], next_state);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let next_state = self.state_stack_pop();
+                self.change_state(runtime_info::machine_info().transitions[9], next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn c_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("C:>"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("C:<"));
+                return;
+            }
+            FrameMessage::ToA => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[10], StateStackState::A);
+                return;
+            }
+            FrameMessage::ToB => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[11], StateStackState::B);
+                return;
+            }
+            FrameMessage::ToC => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[12], StateStackState::C);
+                return;
+            }
+            FrameMessage::Push => {
+                self.state_stack_push();
+                return;
+            }
+            FrameMessage::Pop => {
+                // Start transition
+                let next_state = self.state_stack_pop();
+                self.transition(runtime_info::machine_info().transitions[13], next_state);
+                return;
+            }
+            FrameMessage::PopChange => {
+                // Start change state
+                let next_state = self.state_stack_pop();
+                self.change_state(runtime_info::machine_info().transitions[14], next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            StateStackState::A => self.a_handler(frame_event.clone()),
+            StateStackState::B => self.b_handler(frame_event.clone()),
+            StateStackState::C => self.c_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateStackState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <StateStack as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <StateStack as runtime::Machine>::StatePtr,
+            exit_event as <StateStack as runtime::Machine>::EventPtr,
+            enter_event.clone() as <StateStack as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn state_stack_push(&mut self) {
+        self.state_stack.push(self.state);
+    }
+    
+    fn state_stack_pop(&mut self) -> StateStackState {
+        match self.state_stack.pop() {
+            Some(elem) => elem,
+            None => panic!("Error: attempted to pop when history stack is empty.")
+        }
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateStackState) {
+        let old_state = self.state;
+        self.state = new_state;
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            Rc::new(old_state) as <StateStack as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <StateStack as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for StateStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/state_stack.frm"),
+        sha256: Some("2b265c734885d5292f97905cad861a7cfdb3604ed66243a5013ac9a1e0140d17"),
+        name: "StateStack",
+        variables: &[
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_A,
+            STATE_B,
+            STATE_C,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[5],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "to_a",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "to_b",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "to_c",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "push",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "pop",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "pop_change",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "C:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_A,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_A,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_A,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[5],
+            label: "",
+            source: STATE_A,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_B,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 7,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_B,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 8,
+            kind: TransitionKind::Transition,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_B,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 9,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[5],
+            label: "",
+            source: STATE_B,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 10,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_C,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 11,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_C,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 12,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_C,
+            target: STATE_C,
+        },
+        &TransitionInfo {
+            id: 13,
+            kind: TransitionKind::Transition,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_C,
+            target: SPECIAL_STATE_POP,
+        },
+        &TransitionInfo {
+            id: 14,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[5],
+            label: "",
+            source: STATE_C,
+            target: SPECIAL_STATE_POP,
+        },
+    ];
+    static SPECIAL_STATE_POP: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "$$[-]",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[],
+        is_stack_pop: true,
+    };
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[6],
+            EVENTS[7],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[5],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[8],
+            EVENTS[9],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[5],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_C: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "C",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[10],
+            EVENTS[11],
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+            EVENTS[5],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,678 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(StateVarsState),
+    Exit(StateVarsState),
+    X,
+    Y,
+    Z,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(StateVarsState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(StateVarsState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(StateVarsState::B) => write!(f, "B:>"),
+            FrameMessage::Exit(StateVarsState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(StateVarsState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(StateVarsState::B) => write!(f, "B:<"),
+            FrameMessage::X => write!(f, "X"),
+            FrameMessage::Y => write!(f, "Y"),
+            FrameMessage::Z => write!(f, "Z"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<StateVars> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StateVarsState {
+    Init,
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+impl runtime::State<StateVars> for InitStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct AStateVars {
+    x: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "x" => Some(Box::new(self.x.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+    state_vars: Rc<RefCell<AStateVars>>,
+}
+
+impl runtime::State<StateVars> for AStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+struct BStateVars {
+    y: u32,
+    z: u32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BStateVars {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "y" => Some(Box::new(self.y.clone())),
+            "z" => Some(Box::new(self.z.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+    state_vars: Rc<RefCell<BStateVars>>,
+}
+
+impl runtime::State<StateVars> for BStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        self.state_vars.clone()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    A(AStateContext),
+    B(BStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+}
+
+impl runtime::State<StateVars> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Init(context) => context.info(),
+            StateContext::A(context) => context.info(),
+            StateContext::B(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.arguments(),
+            StateContext::A(context) => context.arguments(),
+            StateContext::B(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <StateVars as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.variables(),
+            StateContext::A(context) => context.variables(),
+            StateContext::B(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct StateVars {
+    state: StateVarsState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for StateVars {
+    fn is_empty(&self) -> bool {
+        true
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for StateVars {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <StateVars as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for StateVars {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl StateVars {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = StateVars {
+            state: StateVarsState::Init,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn x(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::X, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn y(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Y, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn z(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Z, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[0], StateVarsState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::X => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().x + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().x = assign_temp;
+                return;
+            }
+            FrameMessage::Y => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: Rc::new(RefCell::new(BStateVars {
+                        y: 10,
+                        z: 100,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[1], StateVarsState::B, next_state_context);
+                return;
+            }
+            FrameMessage::Z => {
+                // Start transition
+                let context = BStateContext {
+                    state_vars: Rc::new(RefCell::new(BStateVars {
+                        y: 10,
+                        z: 100,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[2], StateVarsState::B, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::X => {
+                // Start transition
+                let context = AStateContext {
+                    state_vars: Rc::new(RefCell::new(AStateVars {
+                        x: 0,
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[3], StateVarsState::A, next_state_context);
+                return;
+            }
+            FrameMessage::Y => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().y + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().y = assign_temp;
+                return;
+            }
+            FrameMessage::Z => {
+                let assign_temp = {
+                    this_state_context.state_vars.as_ref().borrow().z + 1
+                };
+                this_state_context.state_vars.as_ref().borrow_mut().z = assign_temp;
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            StateVarsState::Init => self.init_handler(frame_event.clone()),
+            StateVarsState::A => self.a_handler(frame_event.clone()),
+            StateVarsState::B => self.b_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: StateVarsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <StateVars as runtime::Machine>::StatePtr,
+            new_state_context as <StateVars as runtime::Machine>::StatePtr,
+            exit_event as <StateVars as runtime::Machine>::EventPtr,
+            enter_event.clone() as <StateVars as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for StateVars {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/state_vars.frm"),
+        sha256: Some("bc7f6198932a00835d39c123012b101fd4fef119fd49d6377d52077cd9e32d84"),
+        name: "StateVars",
+        variables: &[
+        ],
+        states: &[
+            STATE_INIT,
+            STATE_A,
+            STATE_B,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "X",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Y",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Z",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[3],
+            label: "",
+            source: STATE_INIT,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[2],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+    ];
+    static STATE_INIT: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Init",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[3],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "x",
+                vtype: "u32",
+            },
+        ],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[
+            NameInfo {
+                name: "y",
+                vtype: "u32",
+            },
+            NameInfo {
+                name: "z",
+                vtype: "u32",
+            },
+        ],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+        ],
+        is_stack_pop: false,
+    };
+}
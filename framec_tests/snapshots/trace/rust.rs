@@ -0,0 +1,784 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(ThermostatState),
+    Exit(ThermostatState),
+    SetTarget,
+    Reading,
+    Rename,
+    GetTarget,
+    Reset,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(ThermostatState::Idle) => write!(f, "Idle:>"),
+            FrameMessage::Enter(ThermostatState::Heating) => write!(f, "Heating:>"),
+            FrameMessage::Exit(ThermostatState::Idle) => write!(f, "Idle:<"),
+            FrameMessage::Exit(ThermostatState::Heating) => write!(f, "Heating:<"),
+            FrameMessage::SetTarget => write!(f, "setTarget"),
+            FrameMessage::Reading => write!(f, "reading"),
+            FrameMessage::Rename => write!(f, "rename"),
+            FrameMessage::GetTarget => write!(f, "getTarget"),
+            FrameMessage::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct SetTargetArgs {
+    degrees: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for SetTargetArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "degrees" => Some(Box::new(self.degrees.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct ReadingArgs {
+    degrees: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for ReadingArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "degrees" => Some(Box::new(self.degrees.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct RenameArgs {
+    name: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for RenameArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "name" => Some(Box::new(self.name.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    SetTarget(SetTargetArgs),
+    Reading(ReadingArgs),
+    Rename(RenameArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::SetTarget(_) => false,
+            FrameEventArgs::Reading(_) => false,
+            FrameEventArgs::Rename(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::SetTarget(args) => args.lookup(name),
+            FrameEventArgs::Reading(args) => args.lookup(name),
+            FrameEventArgs::Rename(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn set_target_args(&self) -> &SetTargetArgs {
+        match self {
+            FrameEventArgs::SetTarget(args) => args,
+            _ => panic!("Failed conversion to SetTargetArgs"),
+        }
+    }
+    fn reading_args(&self) -> &ReadingArgs {
+        match self {
+            FrameEventArgs::Reading(args) => args,
+            _ => panic!("Failed conversion to ReadingArgs"),
+        }
+    }
+    fn rename_args(&self) -> &RenameArgs {
+        match self {
+            FrameEventArgs::Rename(args) => args,
+            _ => panic!("Failed conversion to RenameArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+    GetTarget { return_value: i32 },
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+    fn get_get_target_ret(&self) -> i32 {
+        match self {
+            FrameEventReturn::GetTarget { return_value } => return_value.clone(),
+            _ => panic!("Invalid return value"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<Thermostat> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+            FrameEventReturn::GetTarget { return_value } => Some(Box::new(return_value.clone())),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum ThermostatState {
+    Idle,
+    Heating,
+}
+
+#[allow(dead_code)]
+struct IdleStateContext {
+}
+
+impl runtime::State<Thermostat> for IdleStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct HeatingStateArgs {
+    since: i32,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for HeatingStateArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "since" => Some(Box::new(self.since.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct HeatingStateContext {
+    state_args: Rc<RefCell<HeatingStateArgs>>,
+}
+
+impl runtime::State<Thermostat> for HeatingStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        self.state_args.clone()
+    }
+    fn variables(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Idle(IdleStateContext),
+    Heating(HeatingStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn idle_context(&self) -> &IdleStateContext {
+        match self {
+            StateContext::Idle(context) => context,
+            _ => panic!("Failed conversion to IdleStateContext"),
+        }
+    }
+    fn heating_context(&self) -> &HeatingStateContext {
+        match self {
+            StateContext::Heating(context) => context,
+            _ => panic!("Failed conversion to HeatingStateContext"),
+        }
+    }
+}
+
+impl runtime::State<Thermostat> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Idle(context) => context.info(),
+            StateContext::Heating(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Idle(context) => context.arguments(),
+            StateContext::Heating(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <Thermostat as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Idle(context) => context.variables(),
+            StateContext::Heating(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct Thermostat {
+    state: ThermostatState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    target: i32,
+    label: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for Thermostat {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "target" => Some(Box::new(self.target.clone())),
+            "label" => Some(Box::new(self.label.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for Thermostat {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <Thermostat as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for Thermostat {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl Thermostat {
+    
+    pub fn new() -> Self {
+        let context = IdleStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Idle(context));
+        let mut machine = Thermostat {
+            state: ThermostatState::Idle,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            target: 20,
+            label: String::from("hall"),
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn set_target(&mut self, degrees: i32) {
+        let frame_args = FrameEventArgs::SetTarget(SetTargetArgs { degrees, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::SetTarget, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn reading(&mut self, degrees: i32) {
+        let frame_args = FrameEventArgs::Reading(ReadingArgs { degrees, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Reading, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn rename(&mut self, name: String) {
+        let frame_args = FrameEventArgs::Rename(RenameArgs { name, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Rename, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn get_target(&mut self) -> i32 {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::GetTarget, frame_args));
+        self.handle_event(frame_event.clone());
+        let return_value = match *frame_event.ret.borrow() {
+            FrameEventReturn::GetTarget { return_value } => return_value.clone(),
+            _ => panic!("Bad return value for getTarget"),
+        };
+        return_value
+    }
+    
+    pub fn reset(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Reset, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn idle_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.idle_context();
+        match frame_event.message {
+            FrameMessage::SetTarget => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().set_target_args().degrees
+                };
+                self.target = assign_temp;
+                return;
+            }
+            FrameMessage::Reading => {
+                if (frame_event.arguments.as_ref().borrow().reading_args().degrees) < self.target {
+                    // Start transition
+                    // too cold
+                    let context = HeatingStateContext {
+                        state_args: Rc::new(RefCell::new(HeatingStateArgs {
+                            since: frame_event.arguments.as_ref().borrow().reading_args().degrees,
+                        })),
+                    };
+                    let next_state_context = Rc::new(StateContext::Heating(context));
+                    self.transition(runtime_info::machine_info().transitions[0], ThermostatState::Heating, next_state_context);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::Rename => {
+                let assign_temp = {
+                    frame_event.arguments.as_ref().borrow().rename_args().name.clone()
+                };
+                self.label = assign_temp;
+                return;
+            }
+            FrameMessage::GetTarget => {
+                frame_event.ret.replace(FrameEventReturn::GetTarget {
+                    return_value: self.target
+                });
+                return;
+            }
+            FrameMessage::Reset => {
+                // Start change state
+                let context = IdleStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Idle(context));
+                self.change_state(runtime_info::machine_info().transitions[1], ThermostatState::Idle, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn heating_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.heating_context();
+        match frame_event.message {
+            FrameMessage::Reading => {
+                if (frame_event.arguments.as_ref().borrow().reading_args().degrees) >= self.target {
+                    // Start transition
+                    let context = IdleStateContext {
+                    };
+                    let next_state_context = Rc::new(StateContext::Idle(context));
+                    self.transition(runtime_info::machine_info().transitions[2], ThermostatState::Idle, next_state_context);
+                    return;
+                }
+                return;
+            }
+            FrameMessage::GetTarget => {
+                frame_event.ret.replace(FrameEventReturn::GetTarget {
+                    return_value: self.target
+                });
+                return;
+            }
+            FrameMessage::Reset => {
+                // Start change state
+                let context = IdleStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::Idle(context));
+                self.change_state(runtime_info::machine_info().transitions[3], ThermostatState::Idle, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            ThermostatState::Idle => self.idle_handler(frame_event.clone()),
+            ThermostatState::Heating => self.heating_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: ThermostatState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <Thermostat as runtime::Machine>::StatePtr,
+            new_state_context as <Thermostat as runtime::Machine>::StatePtr,
+            exit_event as <Thermostat as runtime::Machine>::EventPtr,
+            enter_event.clone() as <Thermostat as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: ThermostatState, new_state_context: Rc<StateContext>) {
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            old_state_context as <Thermostat as runtime::Machine>::StatePtr,
+            new_state_context as <Thermostat as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for Thermostat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl runtime::Dispatch for Thermostat {
+    fn dispatch(&mut self, method: &str, arguments: Vec<Box<dyn Any>>) -> Result<Option<Box<dyn Any>>, runtime::DispatchError> {
+        match method {
+            "setTarget" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let degrees = args.required::<i32>("degrees", "i32")?;
+                args.finish()?;
+                self.set_target(degrees);
+                Ok(None)
+            }
+            "reading" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let degrees = args.required::<i32>("degrees", "i32")?;
+                args.finish()?;
+                self.reading(degrees);
+                Ok(None)
+            }
+            "rename" => {
+                let mut args = runtime::DispatchArgs::new(method, arguments);
+                let name = args.required::<String>("name", "String")?;
+                args.finish()?;
+                self.rename(name);
+                Ok(None)
+            }
+            "getTarget" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                Ok(Some(Box::new(self.get_target())))
+            }
+            "reset" => {
+                runtime::DispatchArgs::new(method, arguments).finish()?;
+                self.reset();
+                Ok(None)
+            }
+            _ => Err(runtime::DispatchError::UnknownMethod(method.to_string())),
+        }
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/trace.frm"),
+        sha256: Some("5ba10dcc444868fb0a6bc405ac3f36f113437dfee1bae4e1658714e07575e00a"),
+        name: "Thermostat",
+        variables: &[
+            NameInfo {
+                name: "target",
+                vtype: "i32",
+            },
+            NameInfo {
+                name: "label",
+                vtype: "String",
+            },
+        ],
+        states: &[
+            STATE_IDLE,
+            STATE_HEATING,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "setTarget",
+            parameters: &[
+                NameInfo {
+                    name: "degrees",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "reading",
+            parameters: &[
+                NameInfo {
+                    name: "degrees",
+                    vtype: "i32",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "rename",
+            parameters: &[
+                NameInfo {
+                    name: "name",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "getTarget",
+            parameters: &[],
+            return_type: Some("i32"),
+        },
+        &MethodInfo {
+            name: "reset",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Idle:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Idle:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Heating:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Heating:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "too cold",
+            source: STATE_IDLE,
+            target: STATE_HEATING,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_IDLE,
+            target: STATE_IDLE,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_HEATING,
+            target: STATE_IDLE,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[4],
+            label: "",
+            source: STATE_HEATING,
+            target: STATE_IDLE,
+        },
+    ];
+    static STATE_IDLE: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Idle",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_HEATING: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Heating",
+        parent: None,
+        parameters: &[
+            NameInfo {
+                name: "since",
+                vtype: "i32",
+            },
+        ],
+        variables: &[],
+        handlers: &[
+            EVENTS[1],
+            EVENTS[3],
+            EVENTS[4],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,723 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(TransitionSmState),
+    Exit(TransitionSmState),
+    Transit,
+    Change,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(TransitionSmState::S0) => write!(f, "S0:>"),
+            FrameMessage::Enter(TransitionSmState::S1) => write!(f, "S1:>"),
+            FrameMessage::Enter(TransitionSmState::S2) => write!(f, "S2:>"),
+            FrameMessage::Enter(TransitionSmState::S3) => write!(f, "S3:>"),
+            FrameMessage::Enter(TransitionSmState::S4) => write!(f, "S4:>"),
+            FrameMessage::Exit(TransitionSmState::S0) => write!(f, "S0:<"),
+            FrameMessage::Exit(TransitionSmState::S1) => write!(f, "S1:<"),
+            FrameMessage::Exit(TransitionSmState::S2) => write!(f, "S2:<"),
+            FrameMessage::Exit(TransitionSmState::S3) => write!(f, "S3:<"),
+            FrameMessage::Exit(TransitionSmState::S4) => write!(f, "S4:<"),
+            FrameMessage::Transit => write!(f, "transit"),
+            FrameMessage::Change => write!(f, "change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+        }
+    }
+    fn lookup(&self, _name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<TransitionSm> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <TransitionSm as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TransitionSmState {
+    S0,
+    S1,
+    S2,
+    S3,
+    S4,
+}
+
+impl runtime::State<TransitionSm> for TransitionSmState {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            TransitionSmState::S0 => runtime_info::machine_info().states[0],
+            TransitionSmState::S1 => runtime_info::machine_info().states[1],
+            TransitionSmState::S2 => runtime_info::machine_info().states[2],
+            TransitionSmState::S3 => runtime_info::machine_info().states[3],
+            TransitionSmState::S4 => runtime_info::machine_info().states[4],
+        }
+    }
+    fn arguments(&self) -> <TransitionSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <TransitionSm as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct TransitionSm {
+    state: TransitionSmState,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    enters: Log,
+    exits: Log,
+    hooks: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for TransitionSm {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "enters" => Some(Box::new(self.enters.clone())),
+            "exits" => Some(Box::new(self.exits.clone())),
+            "hooks" => Some(Box::new(self.hooks.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for TransitionSm {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <TransitionSm as runtime::Machine>::StatePtr {
+        Rc::new(self.state)
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for TransitionSm {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl TransitionSm {
+    
+    pub fn new() -> Self {
+        let mut machine = TransitionSm {
+            state: TransitionSmState::S0,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            enters: vec![],
+            exits: vec![],
+            hooks: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn transit(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Transit, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn change(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Change, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_0_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S0"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S0"));
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[0], TransitionSmState::S1);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                self.change_state(runtime_info::machine_info().transitions[1], TransitionSmState::S1);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_1_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S1"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S1"));
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[2], TransitionSmState::S2);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                self.change_state(runtime_info::machine_info().transitions[3], TransitionSmState::S2);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_2_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S2"));
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[4], TransitionSmState::S3);
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S2"));
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[5], TransitionSmState::S3);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                self.change_state(runtime_info::machine_info().transitions[6], TransitionSmState::S3);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_3_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S3"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S3"));
+                return;
+            }
+            FrameMessage::Transit => {
+                // Start transition
+                self.transition(runtime_info::machine_info().transitions[7], TransitionSmState::S4);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                self.change_state(runtime_info::machine_info().transitions[8], TransitionSmState::S4);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn s_4_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.enter(String::from("S4"));
+                // Start change state
+                self.change_state(runtime_info::machine_info().transitions[9], TransitionSmState::S0);
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.exit(String::from("S4"));
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            TransitionSmState::S0 => self.s_0_handler(frame_event.clone()),
+            TransitionSmState::S1 => self.s_1_handler(frame_event.clone()),
+            TransitionSmState::S2 => self.s_2_handler(frame_event.clone()),
+            TransitionSmState::S3 => self.s_3_handler(frame_event.clone()),
+            TransitionSmState::S4 => self.s_4_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: TransitionSmState) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state = self.state;
+        self.state = new_state;
+        self.transition_hook(old_state, new_state);
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            Rc::new(old_state) as <TransitionSm as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <TransitionSm as runtime::Machine>::StatePtr,
+            exit_event as <TransitionSm as runtime::Machine>::EventPtr,
+            enter_event.clone() as <TransitionSm as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: TransitionSmState) {
+        let old_state = self.state;
+        self.state = new_state;
+        self.change_state_hook(old_state, new_state);
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            Rc::new(old_state) as <TransitionSm as runtime::Machine>::StatePtr,
+            Rc::new(new_state) as <TransitionSm as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for TransitionSm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/transition.frm"),
+        sha256: Some("ed4bb239c553f008e9744871611eb0ce7fd5b401a2287ae135c0ab2893b9ab6c"),
+        name: "TransitionSm",
+        variables: &[
+            NameInfo {
+                name: "enters",
+                vtype: "Log",
+            },
+            NameInfo {
+                name: "exits",
+                vtype: "Log",
+            },
+            NameInfo {
+                name: "hooks",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_S_0,
+            STATE_S_1,
+            STATE_S_2,
+            STATE_S_3,
+            STATE_S_4,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "enter",
+            parameters: &[
+                NameInfo {
+                    name: "state",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "exit",
+            parameters: &[
+                NameInfo {
+                    name: "state",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "transit",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "change",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S0:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S1:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S2:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S2:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S3:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S3:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S4:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "S4:<",
+            parameters: &[],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S_0,
+            target: STATE_S_1,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S_0,
+            target: STATE_S_1,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S_1,
+            target: STATE_S_2,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S_1,
+            target: STATE_S_2,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::Transition,
+            event: EVENTS[6],
+            label: "",
+            source: STATE_S_2,
+            target: STATE_S_3,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S_2,
+            target: STATE_S_3,
+        },
+        &TransitionInfo {
+            id: 6,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S_2,
+            target: STATE_S_3,
+        },
+        &TransitionInfo {
+            id: 7,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_S_3,
+            target: STATE_S_4,
+        },
+        &TransitionInfo {
+            id: 8,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_S_3,
+            target: STATE_S_4,
+        },
+        &TransitionInfo {
+            id: 9,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[10],
+            label: "",
+            source: STATE_S_4,
+            target: STATE_S_0,
+        },
+    ];
+    static STATE_S_0: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S0",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[2],
+            EVENTS[3],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_1: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S1",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[4],
+            EVENTS[5],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_2: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S2",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[6],
+            EVENTS[7],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_3: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S3",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[8],
+            EVENTS[9],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_S_4: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "S4",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[10],
+            EVENTS[11],
+        ],
+        is_stack_pop: false,
+    };
+}
@@ -0,0 +1,309 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(TransitionActionsState),
+    Exit(TransitionActionsState),
+    Open,
+    Close,
+    Inspect,
+    Done,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(TransitionActionsState::Closed) => write!(f, "Closed:>"),
+            FrameMessage::Enter(TransitionActionsState::Open) => write!(f, "Open:>"),
+            FrameMessage::Enter(TransitionActionsState::Inspecting) => write!(f, "Inspecting:>"),
+            FrameMessage::Exit(TransitionActionsState::Closed) => write!(f, "Closed:<"),
+            FrameMessage::Exit(TransitionActionsState::Open) => write!(f, "Open:<"),
+            FrameMessage::Exit(TransitionActionsState::Inspecting) => write!(f, "Inspecting:<"),
+            FrameMessage::Open => write!(f, "open"),
+            FrameMessage::Close => write!(f, "close"),
+            FrameMessage::Inspect => write!(f, "inspect"),
+            FrameMessage::Done => write!(f, "done"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct OpenArgs {
+    speed: i32,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Open(OpenArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn open_args(&self) -> &OpenArgs {
+        match self {
+            FrameEventArgs::Open(args) => args,
+            _ => panic!("Failed conversion to OpenArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TransitionActionsState {
+    Closed,
+    Open,
+    Inspecting,
+}
+
+
+
+// System Controller 
+#[allow(dead_code)]
+pub struct TransitionActions {
+    state: TransitionActionsState,
+    state_stack: Vec<TransitionActionsState>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl TransitionActions {
+    
+    pub fn new() -> Self {
+        let mut machine = TransitionActions {
+            state: TransitionActionsState::Closed,
+            state_stack: Vec::new(),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn open(&mut self, speed: i32) {
+        let frame_args = FrameEventArgs::Open(OpenArgs { speed, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Open, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn close(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Close, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn inspect(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Inspect, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn done(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Done, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn closed_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit Closed"));
+                return;
+            }
+            FrameMessage::Open => {
+                // Start transition
+                // open
+                self.transition_exit();
+                self.unlock();
+                self.log((frame_event.arguments.as_ref().borrow().open_args().speed).to_string());
+                self.transition_enter(TransitionActionsState::Open);
+                return;
+            }
+            FrameMessage::Inspect => {
+                self.state_stack_push();
+                // Start transition
+                self.transition_exit();
+                self.log(String::from("push"));
+                self.transition_enter(TransitionActionsState::Inspecting);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn open_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter Open"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit Open"));
+                return;
+            }
+            FrameMessage::Close => {
+                // Start transition
+                self.transition_exit();
+                self.lock();
+                self.transition_enter(TransitionActionsState::Closed);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn inspecting_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log(String::from("enter Inspecting"));
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("exit Inspecting"));
+                return;
+            }
+            FrameMessage::Done => {
+                // Start transition
+                let next_state = self.state_stack_pop();
+                self.transition_exit();
+                self.log(String::from("pop"));
+                self.transition_enter(next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            TransitionActionsState::Closed => self.closed_handler(frame_event.clone()),
+            TransitionActionsState::Open => self.open_handler(frame_event.clone()),
+            TransitionActionsState::Inspecting => self.inspecting_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: TransitionActionsState) {
+        self.transition_exit();
+        self.transition_enter(new_state);
+    }
+    
+    fn transition_exit(&mut self) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+    }
+    
+    fn transition_enter(&mut self, new_state: TransitionActionsState) {
+        self.state = new_state;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn state_stack_push(&mut self) {
+        self.state_stack.push(self.state);
+    }
+    
+    fn state_stack_pop(&mut self) -> TransitionActionsState {
+        match self.state_stack.pop() {
+            Some(elem) => elem,
+            None => panic!("Error: attempted to pop when history stack is empty.")
+        }
+    }
+    
+} // end system controller
+
+impl Default for TransitionActions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,782 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::any::Any;
+use frame_runtime as runtime;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(TransitParamsState),
+    Exit(TransitParamsState),
+    Next,
+    Change,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(TransitParamsState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(TransitParamsState::A) => write!(f, "A:>"),
+            FrameMessage::Enter(TransitParamsState::B) => write!(f, "B:>"),
+            FrameMessage::Exit(TransitParamsState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(TransitParamsState::A) => write!(f, "A:<"),
+            FrameMessage::Exit(TransitParamsState::B) => write!(f, "B:<"),
+            FrameMessage::Next => write!(f, "Next"),
+            FrameMessage::Change => write!(f, "Change"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct AEnterArgs {
+    msg: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for AEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "msg" => Some(Box::new(self.msg.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BEnterArgs {
+    msg: String,
+    val: i16,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BEnterArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "msg" => Some(Box::new(self.msg.clone())),
+            "val" => Some(Box::new(self.val.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct BExitArgs {
+    val: bool,
+    msg: String,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for BExitArgs {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "val" => Some(Box::new(self.val.clone())),
+            "msg" => Some(Box::new(self.msg.clone())),
+            _ => None
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    AEnter(AEnterArgs),
+    BEnter(BEnterArgs),
+    BExit(BExitArgs),
+}
+
+impl runtime::Environment for FrameEventArgs {
+    fn is_empty(&self) -> bool {
+        match self {
+            FrameEventArgs::None => true,
+            FrameEventArgs::AEnter(_) => false,
+            FrameEventArgs::BEnter(_) => false,
+            FrameEventArgs::BExit(_) => false,
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match self {
+            FrameEventArgs::None => None,
+            FrameEventArgs::AEnter(args) => args.lookup(name),
+            FrameEventArgs::BEnter(args) => args.lookup(name),
+            FrameEventArgs::BExit(args) => args.lookup(name),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn a_enter_args(&self) -> &AEnterArgs {
+        match self {
+            FrameEventArgs::AEnter(args) => args,
+            _ => panic!("Failed conversion to AEnterArgs"),
+        }
+    }
+    fn b_enter_args(&self) -> &BEnterArgs {
+        match self {
+            FrameEventArgs::BEnter(args) => args,
+            _ => panic!("Failed conversion to BEnterArgs"),
+        }
+    }
+    fn b_exit_args(&self) -> &BExitArgs {
+        match self {
+            FrameEventArgs::BExit(args) => args,
+            _ => panic!("Failed conversion to BExitArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+impl runtime::Event<TransitParams> for FrameEvent {
+    fn info(&self) -> &runtime::MethodInfo {
+        let msg = self.message.to_string();
+        runtime_info::machine_info()
+            .get_event(&msg)
+            .unwrap_or_else(|| panic!("No runtime info for event: {}", msg))
+    }
+    fn arguments(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        self.arguments.clone()
+    }
+    #[allow(clippy::clone_on_copy)]
+    fn return_value(&self) -> Option<Box<dyn Any>> {
+        match self.ret.borrow().to_owned() {
+            FrameEventReturn::None => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum TransitParamsState {
+    Init,
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+impl runtime::State<TransitParams> for InitStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[0]
+    }
+    fn arguments(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct AStateContext {
+}
+
+impl runtime::State<TransitParams> for AStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[1]
+    }
+    fn arguments(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+struct BStateContext {
+}
+
+impl runtime::State<TransitParams> for BStateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        runtime_info::machine_info().states[2]
+    }
+    fn arguments(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+    fn variables(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    A(AStateContext),
+    B(BStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn a_context(&self) -> &AStateContext {
+        match self {
+            StateContext::A(context) => context,
+            _ => panic!("Failed conversion to AStateContext"),
+        }
+    }
+    fn b_context(&self) -> &BStateContext {
+        match self {
+            StateContext::B(context) => context,
+            _ => panic!("Failed conversion to BStateContext"),
+        }
+    }
+}
+
+impl runtime::State<TransitParams> for StateContext {
+    fn info(&self) -> &'static runtime::StateInfo {
+        match self {
+            StateContext::Init(context) => context.info(),
+            StateContext::A(context) => context.info(),
+            StateContext::B(context) => context.info(),
+        }
+    }
+    fn arguments(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.arguments(),
+            StateContext::A(context) => context.arguments(),
+            StateContext::B(context) => context.arguments(),
+        }
+    }
+    fn variables(&self) -> <TransitParams as runtime::Machine>::EnvironmentPtr {
+        match self {
+            StateContext::Init(context) => context.variables(),
+            StateContext::A(context) => context.variables(),
+            StateContext::B(context) => context.variables(),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct TransitParams {
+    state: TransitParamsState,
+    state_context: Rc<StateContext>,
+    event_monitor: runtime::EventMonitor<Self>,
+    //===================== Domain Block ===================//
+    tape: Log,
+}
+
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::single_match)]
+impl runtime::Environment for TransitParams {
+    #[allow(clippy::clone_on_copy)]
+    fn lookup(&self, name: &str) -> Option<Box<dyn Any>> {
+        match name {
+            "tape" => Some(Box::new(self.tape.clone())),
+            _ => None
+        }
+    }
+}
+
+impl runtime::Machine for TransitParams {
+    type EnvironmentPtr = Rc<dyn runtime::Environment>;
+    type StatePtr = Rc<dyn runtime::State<Self>>;
+    type EventPtr = Rc<dyn runtime::Event<Self>>;
+    type EventFn = runtime::Callback<Self::EventPtr>;
+    type TransitionFn = runtime::Callback<runtime::Transition<Self>>;
+    fn state(&self) -> <TransitParams as runtime::Machine>::StatePtr {
+        self.state_context.clone()
+    }
+    fn variables(&self) -> &dyn runtime::Environment {
+        self
+    }
+    fn event_monitor(&self) -> &runtime::EventMonitor<Self> {
+        &self.event_monitor
+    }
+    fn event_monitor_mut(&mut self) -> &mut runtime::EventMonitor<Self> {
+        &mut self.event_monitor
+    }
+    fn machine_info() -> &'static runtime::MachineInfo {
+        runtime_info::machine_info()
+    }
+    fn empty_environment() -> Self::EnvironmentPtr {
+        runtime::Empty::rc()
+    }
+}
+
+impl runtime::ThreadUnsafeMachine for TransitParams {}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl TransitParams {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = TransitParams {
+            state: TransitParamsState::Init,
+            state_context: next_state_context,
+            event_monitor: runtime::EventMonitor::new(Some(0), Some(1)),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn next(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Next, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn change(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Change, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::Next => {
+                // Start transition
+                let exit_args = FrameEventArgs::None;
+                let enter_args = FrameEventArgs::AEnter(AEnterArgs {
+                    msg: String::from("hi A"),
+                });
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[0], exit_args, enter_args, TransitParamsState::A, next_state_context);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[1], TransitParamsState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn a_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.a_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log((frame_event.arguments.as_ref().borrow().a_enter_args().msg).clone());
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log(String::from("bye A"));
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                let exit_args = FrameEventArgs::None;
+                let enter_args = FrameEventArgs::BEnter(BEnterArgs {
+                    msg: String::from("hi B"),
+                    val: 42,
+                });
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.transition(runtime_info::machine_info().transitions[2], exit_args, enter_args, TransitParamsState::B, next_state_context);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = BStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::B(context));
+                self.change_state(runtime_info::machine_info().transitions[3], TransitParamsState::B, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn b_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.b_context();
+        match frame_event.message {
+            FrameMessage::Enter(_) => {
+                self.log((frame_event.arguments.as_ref().borrow().b_enter_args().msg).clone());
+                self.log((frame_event.arguments.as_ref().borrow().b_enter_args().val).to_string());
+                return;
+            }
+            FrameMessage::Exit(_) => {
+                self.log((frame_event.arguments.as_ref().borrow().b_exit_args().val).to_string());
+                self.log((frame_event.arguments.as_ref().borrow().b_exit_args().msg).clone());
+                return;
+            }
+            FrameMessage::Next => {
+                // Start transition
+                let exit_args = FrameEventArgs::BExit(BExitArgs {
+                    val: true,
+                    msg: String::from("bye B"),
+                });
+                let enter_args = FrameEventArgs::AEnter(AEnterArgs {
+                    msg: String::from("hi again A"),
+                });
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.transition(runtime_info::machine_info().transitions[4], exit_args, enter_args, TransitParamsState::A, next_state_context);
+                return;
+            }
+            FrameMessage::Change => {
+                // Start change state
+                let context = AStateContext {
+                };
+                let next_state_context = Rc::new(StateContext::A(context));
+                self.change_state(runtime_info::machine_info().transitions[5], TransitParamsState::A, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        self.event_monitor.event_sent(frame_event.clone());
+        match self.state {
+            TransitParamsState::Init => self.init_handler(frame_event.clone()),
+            TransitParamsState::A => self.a_handler(frame_event.clone()),
+            TransitParamsState::B => self.b_handler(frame_event.clone()),
+        }
+        self.event_monitor.event_handled(frame_event);
+    }
+    
+    fn transition(&mut self, transition_info: &'static runtime::TransitionInfo, exit_args: FrameEventArgs, enter_args: FrameEventArgs, new_state: TransitParamsState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            exit_args,
+        ));
+        self.handle_event(exit_event.clone());
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            enter_args,
+        ));
+        self.event_monitor.transition_occurred(runtime::Transition::new(
+            transition_info,
+            old_state_context as <TransitParams as runtime::Machine>::StatePtr,
+            new_state_context as <TransitParams as runtime::Machine>::StatePtr,
+            exit_event as <TransitParams as runtime::Machine>::EventPtr,
+            enter_event.clone() as <TransitParams as runtime::Machine>::EventPtr,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+    fn change_state(&mut self, transition_info: &'static runtime::TransitionInfo, new_state: TransitParamsState, new_state_context: Rc<StateContext>) {
+        let old_state_context = self.state_context.clone();
+        self.state = new_state;
+        self.state_context = new_state_context.clone();
+        self.event_monitor.transition_occurred(runtime::Transition::new_change_state(
+            transition_info,
+            old_state_context as <TransitParams as runtime::Machine>::StatePtr,
+            new_state_context as <TransitParams as runtime::Machine>::StatePtr,
+        ));
+    }
+    
+} // end system controller
+
+impl Default for TransitParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod runtime_info {
+    use frame_runtime::info::*;
+    use once_cell::sync::OnceCell;
+    
+    pub fn machine_info() -> &'static MachineInfo {
+        if MACHINE_CELL.get().is_none() {
+            let _ = MACHINE_CELL.set(MACHINE);
+        }
+        MACHINE
+    }
+    static MACHINE: &MachineInfo = &MachineInfo {
+        path_str: Some("src/transition_params.frm"),
+        sha256: Some("11ff0bcf7e8b59edd0041335b4d2e9bf1c039ee1837e2eb65469ccb7e14ba761"),
+        name: "TransitParams",
+        variables: &[
+            NameInfo {
+                name: "tape",
+                vtype: "Log",
+            },
+        ],
+        states: &[
+            STATE_INIT,
+            STATE_A,
+            STATE_B,
+        ],
+        interface: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        actions: ACTIONS,
+        events: EVENTS,
+        transitions: TRANSITIONS,
+    };
+    static MACHINE_CELL: OnceCell<&MachineInfo> = OnceCell::new();
+    const ACTIONS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "log",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    const EVENTS: &[&MethodInfo] = &[
+        &MethodInfo {
+            name: "Next",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Change",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:>",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "Init:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:>",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "A:<",
+            parameters: &[],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:>",
+            parameters: &[
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+                NameInfo {
+                    name: "val",
+                    vtype: "i16",
+                },
+            ],
+            return_type: None,
+        },
+        &MethodInfo {
+            name: "B:<",
+            parameters: &[
+                NameInfo {
+                    name: "val",
+                    vtype: "bool",
+                },
+                NameInfo {
+                    name: "msg",
+                    vtype: "String",
+                },
+            ],
+            return_type: None,
+        },
+    ];
+    static TRANSITIONS: &[&TransitionInfo] = &[
+        &TransitionInfo {
+            id: 0,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_INIT,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 1,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_INIT,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 2,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 3,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_A,
+            target: STATE_B,
+        },
+        &TransitionInfo {
+            id: 4,
+            kind: TransitionKind::Transition,
+            event: EVENTS[0],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+        &TransitionInfo {
+            id: 5,
+            kind: TransitionKind::ChangeState,
+            event: EVENTS[1],
+            label: "",
+            source: STATE_B,
+            target: STATE_A,
+        },
+    ];
+    static STATE_INIT: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "Init",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_A: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "A",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[4],
+            EVENTS[5],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+    static STATE_B: &StateInfo = &StateInfo {
+        machine_cell: &MACHINE_CELL,
+        name: "B",
+        parent: None,
+        parameters: &[],
+        variables: &[],
+        handlers: &[
+            EVENTS[6],
+            EVENTS[7],
+            EVENTS[0],
+            EVENTS[1],
+        ],
+        is_stack_pop: false,
+    };
+}
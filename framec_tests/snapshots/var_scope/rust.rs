@@ -0,0 +1,749 @@
+// emitted from framec_v0.8.0
+
+#[allow(unused_imports)]
+use std::borrow::Borrow;
+#[allow(unused_imports)]
+use std::cell::RefCell;
+#[allow(unused_imports)]
+use std::rc::Rc;
+
+#[allow(dead_code)]
+enum FrameMessage {
+    Enter(VarScopeState),
+    Exit(VarScopeState),
+    ToNn,
+    ToNy,
+    ToYn,
+    ToYy,
+    Nn,
+    Ny,
+    Yn,
+    Yy,
+    Sigils,
+}
+
+#[allow(dead_code)]
+impl std::fmt::Display for FrameMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FrameMessage::Enter(VarScopeState::Init) => write!(f, "Init:>"),
+            FrameMessage::Enter(VarScopeState::Nn) => write!(f, "NN:>"),
+            FrameMessage::Enter(VarScopeState::Ny) => write!(f, "NY:>"),
+            FrameMessage::Enter(VarScopeState::Yn) => write!(f, "YN:>"),
+            FrameMessage::Enter(VarScopeState::Yy) => write!(f, "YY:>"),
+            FrameMessage::Exit(VarScopeState::Init) => write!(f, "Init:<"),
+            FrameMessage::Exit(VarScopeState::Nn) => write!(f, "NN:<"),
+            FrameMessage::Exit(VarScopeState::Ny) => write!(f, "NY:<"),
+            FrameMessage::Exit(VarScopeState::Yn) => write!(f, "YN:<"),
+            FrameMessage::Exit(VarScopeState::Yy) => write!(f, "YY:<"),
+            FrameMessage::ToNn => write!(f, "to_nn"),
+            FrameMessage::ToNy => write!(f, "to_ny"),
+            FrameMessage::ToYn => write!(f, "to_yn"),
+            FrameMessage::ToYy => write!(f, "to_yy"),
+            FrameMessage::Nn => write!(f, "nn"),
+            FrameMessage::Ny => write!(f, "ny"),
+            FrameMessage::Yn => write!(f, "yn"),
+            FrameMessage::Yy => write!(f, "yy"),
+            FrameMessage::Sigils => write!(f, "sigils"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct NnArgs {
+    d: String,
+}
+
+#[allow(dead_code)]
+struct NyArgs {
+    d: String,
+}
+
+#[allow(dead_code)]
+struct YnArgs {
+    d: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct YyArgs {
+    d: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct SigilsArgs {
+    x: String,
+}
+
+#[allow(dead_code)]
+enum FrameEventArgs {
+    None,
+    Nn(NnArgs),
+    Ny(NyArgs),
+    Yn(YnArgs),
+    Yy(YyArgs),
+    Sigils(SigilsArgs),
+}
+
+
+
+#[allow(dead_code)]
+impl FrameEventArgs {
+    fn nn_args(&self) -> &NnArgs {
+        match self {
+            FrameEventArgs::Nn(args) => args,
+            _ => panic!("Failed conversion to NnArgs"),
+        }
+    }
+    fn ny_args(&self) -> &NyArgs {
+        match self {
+            FrameEventArgs::Ny(args) => args,
+            _ => panic!("Failed conversion to NyArgs"),
+        }
+    }
+    fn yn_args(&self) -> &YnArgs {
+        match self {
+            FrameEventArgs::Yn(args) => args,
+            _ => panic!("Failed conversion to YnArgs"),
+        }
+    }
+    fn yy_args(&self) -> &YyArgs {
+        match self {
+            FrameEventArgs::Yy(args) => args,
+            _ => panic!("Failed conversion to YyArgs"),
+        }
+    }
+    fn sigils_args(&self) -> &SigilsArgs {
+        match self {
+            FrameEventArgs::Sigils(args) => args,
+            _ => panic!("Failed conversion to SigilsArgs"),
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum FrameEventReturn {
+    None,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[allow(dead_code)]
+impl FrameEventReturn {
+}
+
+#[allow(dead_code)]
+pub struct FrameEvent {
+    message: FrameMessage,
+    arguments: Rc<RefCell<FrameEventArgs>>,
+    ret: RefCell<FrameEventReturn>,
+}
+
+#[allow(dead_code)]
+impl FrameEvent {
+    fn new(message: FrameMessage, arguments: FrameEventArgs) -> FrameEvent {
+        FrameEvent {
+            message,
+            arguments: Rc::new(RefCell::new(arguments)),
+            ret: RefCell::new(FrameEventReturn::None),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum VarScopeState {
+    Init,
+    Nn,
+    Ny,
+    Yn,
+    Yy,
+}
+
+#[allow(dead_code)]
+struct InitStateContext {
+}
+
+#[allow(dead_code)]
+struct NnStateArgs {
+    b: String,
+}
+
+#[allow(dead_code)]
+struct NnStateVars {
+    c: String,
+}
+
+#[allow(dead_code)]
+struct NnStateContext {
+    state_args: Rc<RefCell<NnStateArgs>>,
+    state_vars: Rc<RefCell<NnStateVars>>,
+}
+
+#[allow(dead_code)]
+struct NyStateArgs {
+    b: String,
+}
+
+#[allow(dead_code)]
+struct NyStateVars {
+    c: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct NyStateContext {
+    state_args: Rc<RefCell<NyStateArgs>>,
+    state_vars: Rc<RefCell<NyStateVars>>,
+}
+
+#[allow(dead_code)]
+struct YnStateArgs {
+    b: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct YnStateVars {
+    c: String,
+}
+
+#[allow(dead_code)]
+struct YnStateContext {
+    state_args: Rc<RefCell<YnStateArgs>>,
+    state_vars: Rc<RefCell<YnStateVars>>,
+}
+
+#[allow(dead_code)]
+struct YyStateArgs {
+    b: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct YyStateVars {
+    c: String,
+    x: String,
+}
+
+#[allow(dead_code)]
+struct YyStateContext {
+    state_args: Rc<RefCell<YyStateArgs>>,
+    state_vars: Rc<RefCell<YyStateVars>>,
+}
+
+#[allow(dead_code)]
+enum StateContext {
+    Init(InitStateContext),
+    Nn(NnStateContext),
+    Ny(NyStateContext),
+    Yn(YnStateContext),
+    Yy(YyStateContext),
+}
+
+#[allow(dead_code)]
+impl StateContext {
+    fn init_context(&self) -> &InitStateContext {
+        match self {
+            StateContext::Init(context) => context,
+            _ => panic!("Failed conversion to InitStateContext"),
+        }
+    }
+    fn nn_context(&self) -> &NnStateContext {
+        match self {
+            StateContext::Nn(context) => context,
+            _ => panic!("Failed conversion to NnStateContext"),
+        }
+    }
+    fn ny_context(&self) -> &NyStateContext {
+        match self {
+            StateContext::Ny(context) => context,
+            _ => panic!("Failed conversion to NyStateContext"),
+        }
+    }
+    fn yn_context(&self) -> &YnStateContext {
+        match self {
+            StateContext::Yn(context) => context,
+            _ => panic!("Failed conversion to YnStateContext"),
+        }
+    }
+    fn yy_context(&self) -> &YyStateContext {
+        match self {
+            StateContext::Yy(context) => context,
+            _ => panic!("Failed conversion to YyStateContext"),
+        }
+    }
+}
+
+// System Controller 
+#[allow(dead_code)]
+pub struct VarScope {
+    state: VarScopeState,
+    state_context: Rc<StateContext>,
+    //===================== Domain Block ===================//
+    a: String,
+    x: String,
+    tape: Log,
+}
+
+#[allow(clippy::assign_op_pattern)]
+#[allow(clippy::branches_sharing_code)]
+#[allow(clippy::clone_on_copy)]
+#[allow(clippy::collapsible_else_if)]
+#[allow(clippy::double_parens)]
+#[allow(clippy::match_single_binding)]
+#[allow(clippy::needless_return)]
+#[allow(clippy::ptr_arg)]
+#[allow(clippy::single_match)]
+#[allow(clippy::wrong_self_convention)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+impl VarScope {
+    
+    pub fn new() -> Self {
+        let context = InitStateContext {
+        };
+        let next_state_context = Rc::new(StateContext::Init(context));
+        let mut machine = VarScope {
+            state: VarScopeState::Init,
+            state_context: next_state_context,
+            a: String::from("#.a"),
+            x: String::from("#.x"),
+            tape: vec![],
+        };
+        machine.initialize();
+        machine
+    }
+    
+    fn initialize(&mut self) {
+        let frame_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Interface Block ===================//
+    
+    pub fn to_nn(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToNn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_ny(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToNy, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_yn(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToYn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn to_yy(&mut self) {
+        let frame_args = FrameEventArgs::None;
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::ToYy, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn nn(&mut self, d: String) {
+        let frame_args = FrameEventArgs::Nn(NnArgs { d, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Nn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn ny(&mut self, d: String) {
+        let frame_args = FrameEventArgs::Ny(NyArgs { d, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Ny, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn yn(&mut self, d: String, x: String) {
+        let frame_args = FrameEventArgs::Yn(YnArgs { d, x, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Yn, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn yy(&mut self, d: String, x: String) {
+        let frame_args = FrameEventArgs::Yy(YyArgs { d, x, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Yy, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    pub fn sigils(&mut self, x: String) {
+        let frame_args = FrameEventArgs::Sigils(SigilsArgs { x, });
+        let frame_event = Rc::new(FrameEvent::new(FrameMessage::Sigils, frame_args));
+        self.handle_event(frame_event);
+    }
+    
+    //===================== Machine Block ===================//
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn init_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.init_context();
+        match frame_event.message {
+            FrameMessage::ToNn => {
+                // Start transition
+                let context = NnStateContext {
+                    state_args: Rc::new(RefCell::new(NnStateArgs {
+                        b: String::from("$NN[b]"),
+                    })),
+                    state_vars: Rc::new(RefCell::new(NnStateVars {
+                        c: String::from("$NN.c"),
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Nn(context));
+                self.transition(VarScopeState::Nn, next_state_context);
+                return;
+            }
+            FrameMessage::ToNy => {
+                // Start transition
+                let context = NyStateContext {
+                    state_args: Rc::new(RefCell::new(NyStateArgs {
+                        b: String::from("$NY[b]"),
+                    })),
+                    state_vars: Rc::new(RefCell::new(NyStateVars {
+                        c: String::from("$NY.c"),
+                        x: String::from("$NY.x"),
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Ny(context));
+                self.transition(VarScopeState::Ny, next_state_context);
+                return;
+            }
+            FrameMessage::ToYn => {
+                // Start transition
+                let context = YnStateContext {
+                    state_args: Rc::new(RefCell::new(YnStateArgs {
+                        b: String::from("$YN[b]"),
+                        x: String::from("$YN[x]"),
+                    })),
+                    state_vars: Rc::new(RefCell::new(YnStateVars {
+                        c: String::from("$YN.c"),
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Yn(context));
+                self.transition(VarScopeState::Yn, next_state_context);
+                return;
+            }
+            FrameMessage::ToYy => {
+                // Start transition
+                let context = YyStateContext {
+                    state_args: Rc::new(RefCell::new(YyStateArgs {
+                        b: String::from("$YY[b]"),
+                        x: String::from("$YY[x]"),
+                    })),
+                    state_vars: Rc::new(RefCell::new(YyStateVars {
+                        c: String::from("$YY.c"),
+                        x: String::from("$YY.x"),
+                    })),
+                };
+                let next_state_context = Rc::new(StateContext::Yy(context));
+                self.transition(VarScopeState::Yy, next_state_context);
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn nn_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.nn_context();
+        match frame_event.message {
+            FrameMessage::Nn => {
+                let e: String = String::from("|nn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().nn_args().d).clone());
+                self.log(e.clone());
+                self.log(self.x.clone());
+                return;
+            }
+            FrameMessage::Ny => {
+                let e: String = String::from("|ny|.e");
+                let x: String = String::from("|ny|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().ny_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Yn => {
+                let e: String = String::from("|yn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().d).clone());
+                self.log(e.clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().x).clone());
+                return;
+            }
+            FrameMessage::Yy => {
+                let e: String = String::from("|yy|.e");
+                let x: String = String::from("|yy|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yy_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Sigils => {
+                let x: String = String::from("|sigils|.x");
+                self.log(self.x.clone());
+                return;
+            }
+            _ => {}
+        }
+    }  //  log(||[x].clone())
+      //  log(||.x.clone())
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn ny_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.ny_context();
+        match frame_event.message {
+            FrameMessage::Nn => {
+                let e: String = String::from("|nn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().nn_args().d).clone());
+                self.log(e.clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().x).clone());
+                return;
+            }
+            FrameMessage::Ny => {
+                let e: String = String::from("|ny|.e");
+                let x: String = String::from("|ny|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().ny_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Yn => {
+                let e: String = String::from("|yn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().d).clone());
+                self.log(e.clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().x).clone());
+                return;
+            }
+            FrameMessage::Yy => {
+                let e: String = String::from("|yy|.e");
+                let x: String = String::from("|yy|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yy_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Sigils => {
+                let x: String = String::from("|sigils|.x");
+                self.log(self.x.clone());
+                return;
+            }
+            _ => {}
+        }
+    }  //  log($.x.clone())
+      //  log(||[x].clone())
+      //  log(||.x.clone())
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn yn_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.yn_context();
+        match frame_event.message {
+            FrameMessage::Nn => {
+                let e: String = String::from("|nn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().nn_args().d).clone());
+                self.log(e.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().x).clone());
+                return;
+            }
+            FrameMessage::Ny => {
+                let e: String = String::from("|ny|.e");
+                let x: String = String::from("|ny|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().ny_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Yn => {
+                let e: String = String::from("|yn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().d).clone());
+                self.log(e.clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().x).clone());
+                return;
+            }
+            FrameMessage::Yy => {
+                let e: String = String::from("|yy|.e");
+                let x: String = String::from("|yy|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yy_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Sigils => {
+                let x: String = String::from("|sigils|.x");
+                self.log(self.x.clone());
+                return;
+            }
+            _ => {}
+        }
+    }  //  log($[x].clone())
+      //  log(||[x].clone())
+      //  log(||.x.clone())
+    
+    
+    #[allow(clippy::collapsible_else_if)]
+    #[allow(clippy::needless_return)]
+    #[allow(unreachable_code)]
+    #[allow(unreachable_patterns)]
+    #[allow(unused_mut)]
+    #[allow(unused_parens)]
+    #[allow(unused_variables)]
+    fn yy_handler(&mut self, frame_event: Rc<FrameEvent>) {
+        let state_context_clone = self.state_context.clone();
+        let this_state_context = state_context_clone.yy_context();
+        match frame_event.message {
+            FrameMessage::Nn => {
+                let e: String = String::from("|nn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().nn_args().d).clone());
+                self.log(e.clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().x).clone());
+                return;
+            }
+            FrameMessage::Ny => {
+                let e: String = String::from("|ny|.e");
+                let x: String = String::from("|ny|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().ny_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Yn => {
+                let e: String = String::from("|yn|.e");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().d).clone());
+                self.log(e.clone());
+                self.log((frame_event.arguments.as_ref().borrow().yn_args().x).clone());
+                return;
+            }
+            FrameMessage::Yy => {
+                let e: String = String::from("|yy|.e");
+                let x: String = String::from("|yy|.x");
+                self.log(self.a.clone());
+                self.log((this_state_context.state_args.as_ref().borrow().b).clone());
+                self.log((this_state_context.state_vars.as_ref().borrow().c).clone());
+                self.log((frame_event.arguments.as_ref().borrow().yy_args().d).clone());
+                self.log(e.clone());
+                self.log(x.clone());
+                return;
+            }
+            FrameMessage::Sigils => {
+                let x: String = String::from("|sigils|.x");
+                self.log(self.x.clone());
+                return;
+            }
+            _ => {}
+        }
+    }
+    
+    //=============== Machinery and Mechanisms ==============//
+    
+    #[allow(clippy::redundant_clone)]
+    fn handle_event(&mut self, frame_event: Rc<FrameEvent>) {
+        match self.state {
+            VarScopeState::Init => self.init_handler(frame_event.clone()),
+            VarScopeState::Nn => self.nn_handler(frame_event.clone()),
+            VarScopeState::Ny => self.ny_handler(frame_event.clone()),
+            VarScopeState::Yn => self.yn_handler(frame_event.clone()),
+            VarScopeState::Yy => self.yy_handler(frame_event.clone()),
+        }
+    }
+    
+    fn transition(&mut self, new_state: VarScopeState, new_state_context: Rc<StateContext>) {
+        let exit_event = Rc::new(FrameEvent::new(
+            FrameMessage::Exit(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(exit_event);
+        self.state = new_state;
+        self.state_context = new_state_context;
+        let enter_event = Rc::new(FrameEvent::new(
+            FrameMessage::Enter(self.state),
+            FrameEventArgs::None,
+        ));
+        self.handle_event(enter_event);
+    }
+    
+} // end system controller
+
+impl Default for VarScope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,73 @@
+//! Tests that the interface of a machine can be property tested and stress tested with the
+//! strategies derived from its machine info.
+
+include!(concat!(env!("OUT_DIR"), "/", "account.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::proptest::{EventCall, EventStrategies, Value};
+    use frame_runtime::stress::{Failure, StressTest};
+    use frame_runtime::Machine;
+    use proptest::prelude::*;
+
+    fn send(sm: &mut Account, call: &EventCall) {
+        match call.name() {
+            "deposit" => sm.deposit(call.arg("amount")),
+            "withdraw" => sm.withdraw(call.arg("amount")),
+            "close" => sm.close(),
+            name => panic!("Unexpected event {}", name),
+        }
+    }
+
+    proptest! {
+        /// Deposits are limited so that the balance can't overflow; withdrawals are arbitrary.
+        #[test]
+        fn balance_never_negative(calls in EventStrategies::new()
+            .parameter("deposit", "amount", (-1000..1000).prop_map(Value::I32))
+            .events(Account::machine_info(), 0..50))
+        {
+            let mut sm = Account::new();
+            for call in &calls {
+                let before = sm.balance;
+                send(&mut sm, call);
+                prop_assert!(sm.balance >= 0);
+                if sm.state().info().name == "Closed" {
+                    prop_assert_eq!(sm.balance, before);
+                }
+            }
+        }
+    }
+
+    /// The same invariants as `balance_never_negative`, checked by the stress test runner.
+    #[test]
+    fn stress() {
+        let strategies = EventStrategies::new().parameter(
+            "deposit",
+            "amount",
+            (-1000..1000).prop_map(Value::I32),
+        );
+        StressTest::new(Account::machine_info(), Account::new)
+            .strategies(strategies)
+            .invariant("balance is never negative", |sm| sm.balance >= 0)
+            .run()
+            .unwrap_or_else(|failure| panic!("{}", failure));
+    }
+
+    /// Arbitrary deposits overflow the balance, which panics in debug builds, and which the stress
+    /// test runner reduces to two deposits.
+    #[test]
+    #[cfg(debug_assertions)]
+    fn stress_overflow() {
+        let failure = StressTest::new(Account::machine_info(), Account::new)
+            .seed(0)
+            .run()
+            .unwrap_err();
+        assert_eq!(
+            failure.failure,
+            Failure::Panic("attempt to add with overflow".to_string())
+        );
+        let names: Vec<&str> = failure.calls.iter().map(|call| call.name()).collect();
+        assert_eq!(names, vec!["deposit", "deposit"]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Test hosting machines as actors with `frame_runtime::actor`.
+
+include!(concat!(env!("OUT_DIR"), "/", "actor.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::actor::{Actor, ActorError};
+    use frame_runtime::{DispatchError, Machine};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn hosts_many_instances() {
+        let turnstiles: Vec<_> = (0..20).map(|_| Actor::spawn(Turnstile::new())).collect();
+        let mut tasks = Vec::new();
+        for (i, turnstile) in turnstiles.iter().enumerate() {
+            let turnstile = turnstile.clone();
+            tasks.push(tokio::spawn(async move {
+                for _ in 0..10 {
+                    turnstile.tell(move |t| t.coin(i as u32)).unwrap();
+                    turnstile.tell(|t| t.push()).unwrap();
+                }
+                turnstile.ask(|t| t.get_total()).await.unwrap()
+            }));
+        }
+        for (i, task) in tasks.into_iter().enumerate() {
+            assert_eq!(task.await.unwrap(), 10 * i as u32);
+        }
+        for turnstile in turnstiles {
+            let turnstile = turnstile.stop().await.unwrap();
+            assert_eq!(turnstile.state().info().name, "Locked");
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatches_events_by_name() {
+        let turnstile = Actor::spawn(Turnstile::new());
+        let none: Option<()> = turnstile
+            .dispatch("coin", vec![Box::new(5u32)])
+            .await
+            .unwrap();
+        assert_eq!(none, None);
+        let total = turnstile.dispatch::<u32>("getTotal", vec![]).await;
+        assert_eq!(total, Ok(Some(5)));
+        assert_eq!(
+            turnstile.dispatch::<i32>("getTotal", vec![]).await,
+            Err(ActorError::ReturnType {
+                method: "getTotal".to_string(),
+                expected: "i32",
+            })
+        );
+        assert_eq!(
+            turnstile.dispatch::<()>("kick", vec![]).await,
+            Err(ActorError::Dispatch(DispatchError::UnknownMethod(
+                "kick".to_string()
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn stops() {
+        let turnstile = Actor::spawn(Turnstile::new());
+        let other = turnstile.clone();
+        turnstile.tell(|t| t.coin(1)).unwrap();
+        let machine = turnstile.stop().await.unwrap();
+        assert_eq!(machine.state().info().name, "Unlocked");
+        assert!(other.is_stopped());
+        assert_eq!(other.tell(|t| t.push()), Err(ActorError::Stopped));
+        assert_eq!(other.ask(|t| t.get_total()).await, Err(ActorError::Stopped));
+
+        let turnstile = Actor::spawn(Turnstile::new());
+        let _ = turnstile.ask(|_| panic!("broken")).await;
+        assert_eq!(
+            turnstile.ask(|t| t.get_total()).await,
+            Err(ActorError::Stopped)
+        );
+        assert!(turnstile.is_stopped());
+    }
+
+    #[tokio::test]
+    async fn runs_locally() {
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let turnstile = Actor::spawn_local(Turnstile::new());
+                turnstile.tell(|t| t.coin(3)).unwrap();
+                assert_eq!(turnstile.ask(|t| t.get_total()).await, Ok(3));
+            })
+            .await;
+    }
+}
@@ -0,0 +1,42 @@
+//! Test constants declared in the domain block.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "constants.rs"));
+
+impl Constants {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that constants are generated as module-level items.
+    #[test]
+    fn constant_items() {
+        assert_eq!(MAX_RETRIES, 3);
+        assert_eq!(GREETING, "giving up");
+    }
+
+    /// Test that constants can be used in handler expressions.
+    #[test]
+    fn constant_in_condition() {
+        let mut sm = Constants::new();
+        sm.fail();
+        sm.fail();
+        assert!(sm.tape.is_empty());
+        sm.fail();
+        assert_eq!(sm.tape, vec!["giving up"]);
+    }
+
+    /// Test that constants can be used in transition arguments.
+    #[test]
+    fn constant_in_transition_args() {
+        let mut sm = Constants::new();
+        sm.fail();
+        sm.succeed();
+        assert_eq!(sm.left, 2);
+    }
+}
@@ -0,0 +1,201 @@
+//! Test stepping a machine through `frame_runtime::dap` with a Debug Adapter Protocol client.
+
+include!(concat!(env!("OUT_DIR"), "/", "dap.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::dap::DapServer;
+    use frame_runtime::Machine;
+    use serde_json::{json, Value};
+    use std::collections::VecDeque;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::sync::Arc;
+    use std::thread;
+
+    const SOURCE_MAP: &str = include_str!(concat!(env!("OUT_DIR"), "/dap.rs.map.json"));
+
+    /// A minimal debugger, which keeps the events received while waiting for responses.
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        seq: i64,
+        events: VecDeque<Value>,
+    }
+
+    impl Client {
+        fn connect(server: &DapServer) -> Client {
+            let stream = TcpStream::connect(server.local_addr()).unwrap();
+            stream.set_nodelay(true).unwrap();
+            Client {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                seq: 0,
+                events: VecDeque::new(),
+            }
+        }
+
+        fn read(&mut self) -> Value {
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                self.reader.read_line(&mut line).unwrap();
+                match line.trim_end().strip_prefix("Content-Length: ") {
+                    Some(value) => length = value.parse().unwrap(),
+                    None if line.trim_end().is_empty() => break,
+                    None => {}
+                }
+            }
+            let mut body = vec![0; length];
+            self.reader.read_exact(&mut body).unwrap();
+            serde_json::from_slice(&body).unwrap()
+        }
+
+        fn request(&mut self, command: &str, arguments: Value) -> Value {
+            self.seq += 1;
+            let body =
+                json!({"seq": self.seq, "type": "request", "command": command, "arguments": arguments})
+                    .to_string();
+            write!(
+                self.writer,
+                "Content-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            loop {
+                let message = self.read();
+                if message["type"] == "response" && message["request_seq"] == self.seq {
+                    assert_eq!(message["success"], true, "{}", message);
+                    return message["body"].clone();
+                }
+                self.events.push_back(message);
+            }
+        }
+
+        fn event(&mut self, name: &str) -> Value {
+            loop {
+                let message = match self.events.pop_front() {
+                    Some(message) => message,
+                    None => self.read(),
+                };
+                if message["event"] == name {
+                    return message["body"].clone();
+                }
+            }
+        }
+
+        /// The top frame of a stopped thread, and its variables in each scope.
+        fn inspect(&mut self, thread: i64) -> (Value, Vec<Value>) {
+            let frames = self.request("stackTrace", json!({ "threadId": thread }));
+            let frame = frames["stackFrames"][0].clone();
+            let scopes = self.request("scopes", json!({"frameId": frame["id"]}));
+            let variables = scopes["scopes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|scope| {
+                    let variables = self.request(
+                        "variables",
+                        json!({"variablesReference": scope["variablesReference"]}),
+                    );
+                    variables["variables"].clone()
+                })
+                .collect();
+            (frame, variables)
+        }
+    }
+
+    fn variable(name: &str, value: &str, vtype: &str) -> Value {
+        json!({"name": name, "value": value, "type": vtype, "variablesReference": 0})
+    }
+
+    #[test]
+    fn steps_machines() {
+        let server = Arc::new(DapServer::bind("127.0.0.1:0").unwrap());
+        server
+            .add_source_map(Door::machine_info(), SOURCE_MAP)
+            .unwrap();
+        let mut client = Client::connect(&server);
+        let capabilities = client.request("initialize", json!({"adapterID": "frame"}));
+        assert_eq!(capabilities["supportsFunctionBreakpoints"], true);
+        client.event("initialized");
+        let breakpoints = client.request(
+            "setBreakpoints",
+            json!({"source": {"path": "src/dap.frm"}, "breakpoints": [{"line": 12}, {"line": 2}]}),
+        );
+        assert_eq!(
+            breakpoints["breakpoints"][0],
+            json!({"verified": true, "line": 11})
+        );
+        assert_eq!(breakpoints["breakpoints"][1]["verified"], false);
+        client.request(
+            "setFunctionBreakpoints",
+            json!({"breakpoints": [{"name": "Locked:>"}]}),
+        );
+        client.request("configurationDone", json!({}));
+
+        let program = server.clone();
+        let door = thread::spawn(move || {
+            let mut door = Door::new();
+            program.attach("door", &mut door);
+            door.open(3);
+            program.update("door", &door);
+            door.close();
+            door.lock(String::from("1234"));
+            door.state().info().name
+        });
+
+        // stop at the line breakpoint in the handler of `open`
+        let stopped = client.event("stopped");
+        assert_eq!(stopped["reason"], "breakpoint");
+        let thread = stopped["threadId"].as_i64().unwrap();
+        let threads = client.request("threads", json!({}));
+        assert_eq!(threads["threads"][0]["name"], "door (Door)");
+        let (frame, variables) = client.inspect(thread);
+        assert_eq!(frame["name"], "$Closed |open|");
+        assert_eq!(frame["line"], 11);
+        assert_eq!(frame["source"]["path"], "src/dap.frm");
+        assert_eq!(variables[0], json!([variable("force", "3", "u32")]));
+        assert_eq!(variables[1], json!([]));
+        assert_eq!(variables[2], json!([variable("opens", "0", "u32")]));
+
+        // step over the enter event of `$Open` to the next interface event
+        client.request("next", json!({ "threadId": thread }));
+        assert_eq!(client.event("stopped")["reason"], "step");
+        let (frame, variables) = client.inspect(thread);
+        assert_eq!(frame["name"], "$Open |close|");
+        assert_eq!(frame["line"], 19);
+        assert_eq!(
+            variables[1],
+            json!([
+                variable("force", "3", "u32"),
+                variable("swings", "1", "u32")
+            ])
+        );
+        assert_eq!(variables[2], json!([variable("opens", "1", "u32")]));
+
+        // step into the exit event of the transition, which has no handler
+        client.request("stepIn", json!({ "threadId": thread }));
+        client.event("stopped");
+        let (frame, _) = client.inspect(thread);
+        assert_eq!(frame["name"], "$Open |<|");
+        assert_eq!(frame["line"], 17);
+
+        // continue to the function breakpoint on the enter handler of `$Locked`
+        client.request("continue", json!({ "threadId": thread }));
+        assert_eq!(client.event("stopped")["reason"], "breakpoint");
+        let (frame, variables) = client.inspect(thread);
+        assert_eq!(frame["name"], "$Locked |>|");
+        assert_eq!(frame["line"], 22);
+        assert_eq!(
+            variables[1],
+            json!([variable("code", "\"1234\"", "String")])
+        );
+
+        // disconnecting resumes the machine
+        client.request("disconnect", json!({}));
+        assert_eq!(door.join().unwrap(), "Locked");
+    }
+}
@@ -0,0 +1,166 @@
+//! Test streaming the activity of a machine with `frame_runtime::debug_server`, and driving it
+//! with commands.
+
+include!(concat!(env!("OUT_DIR"), "/", "debug_server.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::debug_server::DebugServer;
+    use serde_json::{json, Value};
+    use std::net::TcpStream;
+    use std::time::{Duration, Instant};
+    use tungstenite::stream::MaybeTlsStream;
+    use tungstenite::{Message, WebSocket};
+
+    type Client = WebSocket<MaybeTlsStream<TcpStream>>;
+
+    fn connect(server: &DebugServer) -> Client {
+        let url = format!("ws://{}", server.local_addr());
+        let (client, _) = tungstenite::connect(url).unwrap();
+        if let MaybeTlsStream::Plain(stream) = client.get_ref() {
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+        }
+        client
+    }
+
+    /// Read messages until one matching the given fields, skipping the others.
+    fn next(client: &mut Client, fields: Value) -> Value {
+        loop {
+            if let Message::Text(text) = client.read().unwrap() {
+                let message: Value = serde_json::from_str(&text).unwrap();
+                let fields = fields.as_object().unwrap();
+                if fields.iter().all(|(key, value)| &message[key] == value) {
+                    return message;
+                }
+            }
+        }
+    }
+
+    /// Send a command and read the messages up to its reply, polling the server for it if
+    /// `poll` is set.
+    fn command(
+        client: &mut Client,
+        server: &DebugServer,
+        sm: &mut Dimmer,
+        command: Value,
+        poll: bool,
+    ) -> Vec<Value> {
+        client.send(Message::Text(command.to_string())).unwrap();
+        if poll {
+            let start = Instant::now();
+            while server.poll("dimmer", sm) == 0 {
+                assert!(start.elapsed() < Duration::from_secs(5), "no command");
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        let mut messages = Vec::new();
+        loop {
+            if let Message::Text(text) = client.read().unwrap() {
+                let message: Value = serde_json::from_str(&text).unwrap();
+                let is_reply = message["type"] == "reply";
+                messages.push(message);
+                if is_reply {
+                    return messages;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn streams_activity() {
+        let server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let mut sm = Dimmer::new();
+        server.attach("dimmer", &mut sm);
+        let mut client = connect(&server);
+
+        let snapshot = next(&mut client, json!({"type": "snapshot"}));
+        assert_eq!(snapshot["instance"], "dimmer");
+        assert_eq!(snapshot["snapshot"]["machine"], "Dimmer");
+        assert_eq!(snapshot["snapshot"]["state"]["name"], "Off");
+        assert_eq!(snapshot["snapshot"]["variables"]["brightness"], 1);
+
+        sm.toggle();
+        let transition = next(&mut client, json!({"type": "transition"}));
+        assert_eq!(transition["event"], "toggle");
+        assert_eq!(transition["source"], "Off");
+        assert_eq!(transition["target"], "On");
+        assert_eq!(transition["kind"], "transition");
+        assert_eq!(transition["state"]["name"], "On");
+
+        sm.get_level();
+        let event = next(&mut client, json!({"type": "event", "event": "getLevel"}));
+        assert_eq!(event["instance"], "dimmer");
+        assert_eq!(event["return"], 1);
+        assert!(event["time"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn subscribes_in_process() {
+        let server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let mut sm = Dimmer::new();
+        server.attach("dimmer", &mut sm);
+        let messages = server.subscribe();
+        sm.toggle();
+        let messages: Vec<Value> = messages
+            .try_iter()
+            .map(|text| serde_json::from_str(&text).unwrap())
+            .collect();
+        assert_eq!(messages[0]["type"], "snapshot");
+        assert!(messages
+            .iter()
+            .any(|m| m["type"] == "transition" && m["target"] == "On"));
+    }
+
+    #[test]
+    fn handles_commands() {
+        let server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let mut sm = Dimmer::new();
+        server.attach("dimmer", &mut sm);
+        sm.toggle();
+        let mut client = connect(&server);
+
+        let set_level = json!({"id": 1, "command": "send", "instance": "dimmer",
+            "event": "setLevel", "arguments": [7]});
+        let messages = command(&mut client, &server, &mut sm, set_level, true);
+        assert_eq!(sm.brightness, 7);
+        let event = messages.iter().find(|m| m["event"] == "setLevel").unwrap();
+        assert_eq!(event["arguments"], json!({"level": 7}));
+        let snapshot = messages.iter().rev().find(|m| m["type"] == "snapshot");
+        assert_eq!(snapshot.unwrap()["snapshot"]["variables"]["brightness"], 7);
+        assert_eq!(
+            messages.last().unwrap(),
+            &json!({"type": "reply", "id": 1, "ok": true, "return": null})
+        );
+
+        let get_level = json!({"id": "level", "command": "send", "instance": "dimmer",
+            "event": "getLevel"});
+        let messages = command(&mut client, &server, &mut sm, get_level, true);
+        let reply = messages.last().unwrap();
+        assert_eq!(reply["id"], "level");
+        assert_eq!(reply["return"], 7);
+
+        let bad_level = json!({"id": 2, "command": "send", "instance": "dimmer",
+            "event": "setLevel", "arguments": ["bright"]});
+        let messages = command(&mut client, &server, &mut sm, bad_level, true);
+        let reply = messages.last().unwrap();
+        assert_eq!(reply["ok"], false);
+        assert_eq!(
+            reply["error"],
+            "argument `level` of `setLevel` is not a valid `i32`: bright"
+        );
+
+        let snapshot = json!({"id": 3, "command": "snapshot", "instance": "dimmer"});
+        let messages = command(&mut client, &server, &mut sm, snapshot, true);
+        assert_eq!(messages.last().unwrap()["snapshot"]["state"]["name"], "On");
+
+        // invalid commands are answered without polling
+        let unknown = json!({"id": 4, "command": "send", "instance": "lamp", "event": "toggle"});
+        let messages = command(&mut client, &server, &mut sm, unknown, false);
+        assert_eq!(messages.last().unwrap()["error"], "unknown instance `lamp`");
+        let messages = command(&mut client, &server, &mut sm, json!("toggle"), false);
+        assert_eq!(messages.last().unwrap()["ok"], false);
+    }
+}
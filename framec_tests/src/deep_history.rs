@@ -0,0 +1,76 @@
+//! Test deep history transitions, which restore the most recently active
+//! descendant of a parent state.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "deep_history.rs"));
+
+impl DeepHistory {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::*;
+
+    /// Test that a deep history transition with no recorded history enters the
+    /// target state itself.
+    #[test]
+    fn no_history() {
+        let mut sm = DeepHistory::new();
+        sm.resume();
+        assert_eq!(sm.state, DeepHistoryState::Running);
+        assert_eq!(sm.tape, vec!["Running:>"]);
+    }
+
+    /// Test that a deep history transition restores the most recently active
+    /// nested state.
+    #[test]
+    fn restore_nested_state() {
+        let mut sm = DeepHistory::new();
+        sm.resume();
+        sm.next();
+        sm.next();
+        assert_eq!(sm.state, DeepHistoryState::Buffering);
+        sm.pause();
+        sm.tape.clear();
+        sm.resume();
+        assert_eq!(sm.state, DeepHistoryState::Buffering);
+        assert_eq!(sm.tape, vec!["Buffering:>", "Playing:>", "Running:>"]);
+    }
+
+    /// Test that a regular transition to the parent state ignores the recorded
+    /// history.
+    #[test]
+    fn shallow_transition() {
+        let mut sm = DeepHistory::new();
+        sm.resume();
+        sm.next();
+        sm.pause();
+        sm.tape.clear();
+        sm.resume_shallow();
+        assert_eq!(sm.state, DeepHistoryState::Running);
+        assert_eq!(sm.tape, vec!["Running:>"]);
+    }
+
+    /// Test that deep history transitions are reported as such by the runtime
+    /// interface.
+    #[test]
+    fn runtime_transition_kind() {
+        let info = DeepHistory::machine_info();
+        let resumes: Vec<_> = info
+            .transitions
+            .iter()
+            .filter(|t| t.event.name == "resume")
+            .collect();
+        assert_eq!(resumes.len(), 2);
+        assert!(resumes.iter().all(|t| t.is_deep_history()));
+        assert!(info
+            .transitions
+            .iter()
+            .filter(|t| t.event.name == "resume_shallow")
+            .all(|t| !t.is_deep_history()));
+    }
+}
@@ -0,0 +1,34 @@
+//! Test default values of interface and enter/exit parameters.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "default_params.rs"));
+
+impl DefaultParams {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interface_defaults() {
+        let mut sm = DefaultParams::new();
+        sm.add(None);
+        assert_eq!(sm.total, 1);
+        sm.add(Some(5));
+        assert_eq!(sm.total, 6);
+    }
+
+    #[test]
+    fn enter_and_exit_defaults() {
+        let mut sm = DefaultParams::new();
+        sm.next();
+        assert_eq!(sm.tape, vec!["hi B", "-1"]);
+        sm.tape.clear();
+        sm.next();
+        assert_eq!(sm.tape, vec!["bye B", "again", "2"]);
+    }
+}
@@ -0,0 +1,127 @@
+//! Test the `runtime_dispatch` feature, and driving a machine through the C ABI of
+//! `frame_runtime::ffi`.
+
+include!(concat!(env!("OUT_DIR"), "/", "dispatch.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::ffi::*;
+    use frame_runtime::{Dispatch, DispatchError, Machine};
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_char;
+    use std::ptr;
+
+    #[test]
+    fn dispatch_by_name() {
+        let mut sm = Register::new();
+        assert!(sm.dispatch("deposit", vec![Box::new(5)]).unwrap().is_none());
+        sm.dispatch("add", vec![]).unwrap();
+        sm.dispatch("add", vec![Box::new(3)]).unwrap();
+        let balance = sm.dispatch("getBalance", vec![]).unwrap().unwrap();
+        assert_eq!(balance.downcast_ref::<i32>(), Some(&9));
+
+        sm.dispatch("label", vec![Box::new(String::from("savings"))])
+            .unwrap();
+        assert_eq!(sm.state().info().name, "Labeled");
+    }
+
+    #[test]
+    fn dispatch_errors() {
+        let mut sm = Register::new();
+        assert_eq!(
+            sm.dispatch("withdraw", vec![]).unwrap_err(),
+            DispatchError::UnknownMethod(String::from("withdraw"))
+        );
+        assert!(matches!(
+            sm.dispatch("deposit", vec![Box::new(5u8)]),
+            Err(DispatchError::ArgumentType { .. })
+        ));
+        assert!(matches!(
+            sm.dispatch("close", vec![Box::new(1)]),
+            Err(DispatchError::ArgumentCount { .. })
+        ));
+        assert_eq!(sm.balance, 0);
+        assert_eq!(sm.state().info().name, "Open");
+    }
+
+    /// Take a string returned through the ABI.
+    fn take(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let owned = unsafe { CStr::from_ptr(string) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { frame_string_free(string) };
+        owned
+    }
+
+    fn call(sm: *mut FrameMachine, method: &str, arguments: &[&str]) -> Result<String, String> {
+        let method = CString::new(method).unwrap();
+        let arguments: Vec<CString> = arguments
+            .iter()
+            .map(|a| CString::new(*a).unwrap())
+            .collect();
+        let pointers: Vec<*const c_char> = arguments.iter().map(|a| a.as_ptr()).collect();
+        let mut result = ptr::null_mut();
+        let code = unsafe {
+            frame_machine_dispatch(
+                sm,
+                method.as_ptr(),
+                pointers.as_ptr(),
+                pointers.len(),
+                &mut result,
+            )
+        };
+        if code == FRAME_OK {
+            Ok(take(result))
+        } else {
+            Err(format!("{}: {}", code, take(frame_last_error())))
+        }
+    }
+
+    #[test]
+    fn ffi() {
+        let sm = FrameMachine::into_raw(Register::new());
+        assert_eq!(take(unsafe { frame_machine_name(sm) }), "Register");
+        assert_eq!(call(sm, "deposit", &["12"]), Ok(String::from("null")));
+        assert_eq!(call(sm, "getBalance", &[]), Ok(String::from("12")));
+        assert_eq!(
+            take(unsafe { frame_machine_variables(sm) }),
+            "{\"balance\":12}"
+        );
+
+        assert_eq!(call(sm, "label", &["savings"]), Ok(String::from("null")));
+        assert_eq!(take(unsafe { frame_machine_state(sm) }), "Labeled");
+        assert_eq!(
+            take(unsafe { frame_machine_snapshot(sm) }),
+            "{\"machine\":\"Register\",\"state\":{\"name\":\"Labeled\",\
+             \"arguments\":{\"text\":\"savings\"},\"variables\":{\"count\":1}},\
+             \"variables\":{\"balance\":12}}"
+        );
+
+        assert_eq!(
+            call(sm, "deposit", &["twelve"]),
+            Err(String::from(
+                "-3: argument `amount` of `deposit` is not a valid `i32`: twelve"
+            ))
+        );
+        assert_eq!(
+            call(sm, "withdraw", &[]),
+            Err(String::from("-2: unknown method `withdraw`"))
+        );
+        assert_eq!(
+            call(sm, "deposit", &[]),
+            Err(String::from(
+                "-3: `deposit` takes 1 argument(s) but 0 were given"
+            ))
+        );
+        unsafe { frame_machine_free(sm) };
+
+        assert!(unsafe { frame_machine_state(ptr::null()) }.is_null());
+        assert_eq!(
+            call(ptr::null_mut(), "close", &[]),
+            Err(String::from("-1: the machine is null"))
+        );
+    }
+}
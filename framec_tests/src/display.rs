@@ -0,0 +1,28 @@
+//! Test the `generate_display` feature.
+
+include!(concat!(env!("OUT_DIR"), "/", "display.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that states are formatted and parsed by their names in the spec.
+    #[test]
+    fn state_names() {
+        assert_eq!(DisplayState::On.to_string(), "On");
+        assert_eq!("Off".parse::<DisplayState>(), Ok(DisplayState::Off));
+        assert_eq!(
+            "Dim".parse::<DisplayState>(),
+            Err("no state named `Dim` in Display".to_string())
+        );
+    }
+
+    /// Test that the machine is formatted with its state and domain variables.
+    #[test]
+    fn machine() {
+        let mut sm = Display::new();
+        assert_eq!(sm.to_string(), "Off { brightness: 0, label: \"lamp\" }");
+        sm.turn_on();
+        assert_eq!(sm.to_string(), "On { brightness: 3, label: \"lamp\" }");
+    }
+}
@@ -0,0 +1,47 @@
+//! Test matching on enum event arguments.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "enum_match.rs"));
+
+impl EnumMatch {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that each enumerator selects its branch, including branches with
+    /// several enumerators.
+    #[test]
+    fn exhaustive_match() {
+        let mut sm = EnumMatch::new();
+        sm.paint(Color::Red);
+        sm.paint(Color::Yellow);
+        sm.paint(Color::Green);
+        assert_eq!(sm.tape, vec!["stop", "go", "go"]);
+    }
+
+    /// Test that enumerators without a branch fall through to the else branch.
+    #[test]
+    fn else_branch() {
+        let mut sm = EnumMatch::new();
+        sm.inspect(Color::Green);
+        sm.inspect(Color::Red);
+        sm.inspect(Color::Yellow);
+        assert_eq!(sm.tape, vec!["green", "not green", "not green"]);
+    }
+
+    /// Test that a branch of an enum match can transition.
+    #[test]
+    fn transition_in_branch() {
+        let mut sm = EnumMatch::new();
+        sm.select(Shape::Triangle);
+        assert_eq!(sm.state, EnumMatchState::Ready);
+        sm.select(Shape::Circle);
+        assert_eq!(sm.state, EnumMatchState::Round);
+        assert_eq!(sm.tape, vec!["angular", "round"]);
+    }
+}
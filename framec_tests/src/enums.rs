@@ -0,0 +1,35 @@
+//! Test enum declarations in the domain block.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "enums.rs"));
+
+impl Enums {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that domain variables can be initialized with an enumerator.
+    #[test]
+    fn enum_domain_var() {
+        let mut sm = Enums::new();
+        assert_eq!(sm.light, Color::Green);
+        sm.reset();
+        assert_eq!(sm.light, Color::Red);
+    }
+
+    /// Test that enums can be passed as event parameters and compared in conditionals.
+    #[test]
+    fn enum_param() {
+        let mut sm = Enums::new();
+        sm.paint(Color::Red);
+        sm.paint(Color::Yellow);
+        sm.paint(Color::Green);
+        assert_eq!(sm.tape, vec!["stop", "wait", "go"]);
+        assert_eq!(sm.light, Color::Green);
+    }
+}
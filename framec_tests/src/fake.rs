@@ -0,0 +1,54 @@
+//! Test the `generate_fake` feature.
+
+include!(concat!(env!("OUT_DIR"), "/", "fake.rs"));
+
+/// Code using the machine through its interface trait.
+#[allow(dead_code)]
+fn brighten(light: &mut impl DimmerInterface) -> i32 {
+    if light.state() == DimmerState::Off {
+        light.turn_on();
+    }
+    light.dim(Some(8));
+    light.get_brightness()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that the machine implements its interface trait.
+    fn machine() {
+        let mut sm = Dimmer::new();
+        assert_eq!(brighten(&mut sm), 8);
+        assert_eq!(DimmerInterface::state(&sm), DimmerState::On);
+    }
+
+    #[test]
+    /// Test that the fake returns the scripted values, enters the scripted states, and records
+    /// its calls.
+    fn scripted() {
+        let mut fake = FakeDimmer::new(DimmerState::Off);
+        fake.script_turn_on(Some(DimmerState::On))
+            .script_get_brightness(3, None);
+        assert_eq!(brighten(&mut fake), 3);
+        assert_eq!(fake.state(), DimmerState::On);
+        assert_eq!(
+            fake.take_calls(),
+            vec!["turn_on()", "dim(Some(8))", "get_brightness()"]
+        );
+        assert!(fake.is_script_done());
+        assert!(fake.take_calls().is_empty());
+    }
+
+    #[test]
+    /// Test that calls that are not scripted return the default value and keep the state.
+    fn unscripted() {
+        let mut fake = FakeDimmer::new(DimmerState::On);
+        fake.script_dim(Some(DimmerState::Off));
+        assert!(!fake.is_script_done());
+        assert_eq!(brighten(&mut fake), 0);
+        assert_eq!(fake.state(), DimmerState::Off);
+        assert!(fake.is_script_done());
+    }
+}
@@ -358,4 +358,83 @@ mod tests {
 
         assert_eq!(smcat_static, smcat_dynamic);
     }
+
+    /// Test that the sequences generated for transition coverage take the expected transitions,
+    /// including the transition of the initial state's enter handler and inherited handlers.
+    #[test]
+    fn coverage_sequences() {
+        use frame_runtime::coverage::{Coverage, TestPlan};
+        for coverage in [Coverage::AllTransitions, Coverage::AllTransitionPairs] {
+            let plan = TestPlan::new(Hierarchical::machine_info(), coverage);
+            assert!(plan.uncovered.is_empty());
+            for sequence in &plan.sequences {
+                let mut sm = Hierarchical::new();
+                let monitor = sm.event_monitor_mut();
+                let mut taken: Vec<usize> = monitor
+                    .transition_history()
+                    .iter()
+                    .map(|t| t.info.id)
+                    .collect();
+                monitor.set_transition_history_capacity(None);
+                monitor.clear_transition_history();
+                for event in &sequence.events {
+                    match event.name {
+                        "A" => sm.a(),
+                        "B" => sm.b(),
+                        "C" => sm.c(),
+                        name => panic!("unexpected event {}", name),
+                    }
+                }
+                taken.extend(
+                    sm.event_monitor()
+                        .transition_history()
+                        .iter()
+                        .map(|t| t.info.id),
+                );
+                let expected: Vec<usize> = sequence.transitions.iter().map(|t| t.id).collect();
+                assert_eq!(taken, expected);
+                assert_eq!(sm.state().info().name, sequence.final_state.unwrap().name);
+            }
+        }
+    }
+
+    /// Test that the coverage recorded by tests is aggregated into a report of the states and
+    /// transitions they didn't exercise.
+    #[test]
+    fn recorded_coverage() {
+        use frame_runtime::recorder::{CoverageRecorder, CoverageReport};
+        let recorder = CoverageRecorder::new(Hierarchical::machine_info());
+        let mut sm = Hierarchical::new();
+        recorder.attach(&mut sm);
+        sm.a();
+        let mut other = Hierarchical::new();
+        recorder.attach(&mut other);
+        other.b();
+        other.b();
+
+        let coverage = recorder.coverage();
+        let count = |name: &str| {
+            coverage
+                .states
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap()
+                .count
+        };
+        assert_eq!(count("I"), 2);
+        assert_eq!(count("S"), 5);
+        assert_eq!(count("S0"), 1);
+        assert_eq!(count("S1"), 2);
+        assert_eq!(count("T"), 0);
+
+        let dir = std::env::temp_dir().join(format!("frame_coverage_{}", std::process::id()));
+        let path = recorder.write_to(&dir, "hierarchical::tests").unwrap();
+        assert_eq!(path, dir.join("hierarchical__tests.Hierarchical.frmcov"));
+        let report = CoverageReport::read_dir(&dir).unwrap();
+        assert_eq!(report.machines, vec![coverage]);
+        assert!(report.render().starts_with(
+            "Hierarchical: 4/7 states (57.1%), 3/10 transitions (30.0%)\n  never entered: $S2\n"
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
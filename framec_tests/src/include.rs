@@ -0,0 +1,38 @@
+//! Tests splicing interface methods and states into a spec with `#include`.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "include.rs"));
+
+impl Include {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that interface methods and states from included files are generated.
+    #[test]
+    fn included_definitions() {
+        let mut sm = Include::new();
+        assert_eq!(sm.state, IncludeState::Idle);
+        sm.start();
+        assert_eq!(sm.state, IncludeState::Running);
+        sm.stop();
+        assert_eq!(sm.state, IncludeState::Idle);
+        assert_eq!(sm.tape, vec!["start", "stop"]);
+    }
+
+    /// Test that nested includes are resolved relative to the including file.
+    #[test]
+    fn nested_include() {
+        let mut sm = Include::new();
+        sm.start();
+        sm.pause();
+        assert_eq!(sm.state, IncludeState::Paused);
+        sm.start();
+        assert_eq!(sm.state, IncludeState::Running);
+    }
+}
@@ -1,27 +1,52 @@
 // Tests with runtime_support disabled.
 mod branch;
 mod config;
+mod constants;
+mod display;
 mod empty;
+mod enum_match;
+mod enums;
 mod event_handler;
+mod fake;
 mod handler_calls;
 mod hierarchical_guard;
+mod include;
 mod r#match;
+mod mock_actions;
+mod naming;
+mod new_with;
+mod regions;
 mod rust_naming_off;
 mod rust_naming_on;
 mod simple_handler_calls;
+mod split_states;
 mod state_context;
+mod state_context_lightweight;
+mod state_context_stack_lightweight;
 mod var_scope;
 
 // Tests with runtime_support enabled.
+mod account;
+mod actor;
 mod basic;
 mod basic_sync;
+mod dap;
+mod debug_server;
+mod deep_history;
+mod default_params;
+mod dispatch;
 mod event_monitor;
 mod hierarchical;
+mod metrics;
+mod python;
+mod runtime_info_only;
 mod state_context_runtime;
 mod state_context_runtime_sync;
 mod state_context_stack;
 mod state_params;
 mod state_stack;
 mod state_vars;
+mod trace;
 mod transition;
+mod transition_actions;
 mod transition_params;
@@ -0,0 +1,96 @@
+//! Test exporting the metrics of machines to Prometheus with `frame_runtime::metrics`.
+
+include!(concat!(env!("OUT_DIR"), "/", "metrics.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::metrics::MetricsRegistry;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    const GATE: &str = "machine=\"Turnstile\",instance=\"gate\"";
+
+    #[test]
+    fn render() {
+        let registry = MetricsRegistry::with_buckets(&[60.0]);
+        let mut gate = Turnstile::new();
+        let mut side = Turnstile::new();
+        registry.attach("gate", &mut gate);
+        registry.attach("side", &mut side);
+        gate.coin();
+        gate.push();
+        gate.coin();
+        gate.coin();
+
+        let metrics = registry.render();
+        let lines: Vec<&str> = metrics.lines().collect();
+        for line in [
+            format!("frame_machine_state{{{},state=\"Locked\"}} 0", GATE),
+            format!("frame_machine_state{{{},state=\"Unlocked\"}} 1", GATE),
+            "frame_machine_state{machine=\"Turnstile\",instance=\"side\",state=\"Locked\"} 1"
+                .to_string(),
+            format!(
+                "frame_machine_transitions_total{{{},event=\"coin\",source=\"Locked\",target=\"Unlocked\"}} 2",
+                GATE
+            ),
+            format!(
+                "frame_machine_transitions_total{{{},event=\"push\",source=\"Unlocked\",target=\"Locked\"}} 1",
+                GATE
+            ),
+            format!(
+                "frame_machine_state_dwell_seconds_bucket{{{},state=\"Locked\",le=\"60\"}} 2",
+                GATE
+            ),
+            format!(
+                "frame_machine_state_dwell_seconds_bucket{{{},state=\"Locked\",le=\"+Inf\"}} 2",
+                GATE
+            ),
+            format!(
+                "frame_machine_state_dwell_seconds_count{{{},state=\"Unlocked\"}} 1",
+                GATE
+            ),
+        ] {
+            assert!(lines.contains(&line.as_str()), "no `{}` in\n{}", line, metrics);
+        }
+        // handlers without transitions aren't counted
+        assert_eq!(
+            metrics.matches("frame_machine_transitions_total{").count(),
+            2
+        );
+        assert!(!metrics.contains("instance=\"side\",state=\"Locked\",le="));
+
+        registry.detach("side", &mut side);
+        side.coin();
+        assert!(!registry.render().contains("instance=\"side\""));
+    }
+
+    fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serve() {
+        let registry = MetricsRegistry::new();
+        let mut gate = Turnstile::new();
+        registry.attach("gate", &mut gate);
+        let server = registry.serve("127.0.0.1:0").unwrap();
+
+        gate.coin();
+        let response = get(server.local_addr(), "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.ends_with(&registry.render()));
+        assert!(response.contains(&format!(
+            "frame_machine_state{{{},state=\"Unlocked\"}} 1",
+            GATE
+        )));
+
+        let response = get(server.local_addr(), "/");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Test the `generate_mock_actions` feature.
+
+include!(concat!(env!("OUT_DIR"), "/", "mock_actions.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that the mock actions record their calls with the arguments.
+    fn records_calls() {
+        let mut sm = MockActions::new();
+        assert!(sm.take_action_calls().is_empty());
+        sm.turn_on();
+        sm.dim(20);
+        assert_eq!(
+            sm.take_action_calls(),
+            vec!["open_switch()", "set_level(20, \"dim\")"]
+        );
+        assert!(sm.take_action_calls().is_empty());
+    }
+
+    #[test]
+    /// Test that mock actions with a return type return the default value.
+    fn returns_default() {
+        let mut sm = MockActions::new();
+        sm.turn_on();
+        sm.take_action_calls();
+        assert!(!sm.is_bright());
+        assert_eq!(sm.take_action_calls(), vec!["brightness()"]);
+    }
+}
@@ -0,0 +1,47 @@
+//! Test the naming options of the Rust backend.
+
+type Log = Vec<i32>;
+include!(concat!(env!("OUT_DIR"), "/", "naming.rs"));
+
+#[allow(non_snake_case)]
+impl Naming {
+    pub fn record(&mut self, stepSize: i32) {
+        self.stepLog.push(stepSize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that value names are in the configured case.
+    #[test]
+    fn value_name_case() {
+        let mut sm = Naming::new();
+        sm.nextState(1);
+        sm.nextState(2);
+        assert_eq!(sm.stepLog, vec![1, 2]);
+    }
+
+    /// Test that the state enum has the configured prefix.
+    #[test]
+    fn state_enum_prefix() {
+        let mut sm = Naming::new();
+        assert_eq!(sm.state, TheNamingState::First);
+        sm.nextState(1);
+        assert_eq!(sm.state, TheNamingState::Second);
+    }
+
+    /// Test that `{system}` in the names of the event types is replaced by the system name.
+    #[test]
+    fn system_name_in_type_names() {
+        let event = NamingEvent::new(
+            NamingMessage::Enter(TheNamingState::First),
+            FrameEventArgs::None,
+        );
+        assert!(matches!(
+            event.message,
+            NamingMessage::Enter(TheNamingState::First)
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+//! Test the `generate_new_with` feature.
+
+include!(concat!(env!("OUT_DIR"), "/", "new_with.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the default initial values are the initializers in the spec.
+    #[test]
+    fn default_domain() {
+        let mut sm = NewWith::new_with(NewWithDomain::default(), true);
+        assert_eq!(sm.get_brightness(), 5);
+        assert_eq!(sm.get_entries(), 1);
+    }
+
+    /// Test that the machine starts from the given values, entering the initial state or not.
+    #[test]
+    fn given_domain() {
+        let domain = NewWithDomain {
+            brightness: 8,
+            ..Default::default()
+        };
+        let mut sm = NewWith::new_with(domain, true);
+        assert_eq!(sm.get_brightness(), 8);
+        assert_eq!(sm.get_entries(), 1);
+
+        let domain = NewWithDomain {
+            brightness: 8,
+            entries: 3,
+        };
+        let mut sm = NewWith::new_with(domain, false);
+        assert_eq!(sm.get_entries(), 3);
+    }
+}
@@ -0,0 +1,102 @@
+//! Test scripting a machine from Python with `frame_runtime::python`.
+
+include!(concat!(env!("OUT_DIR"), "/", "python.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::python::PyMachine;
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    /// Run a Python script with a new wallet bound to `wallet`, printing the traceback of any
+    /// error. The error is dropped here, since the machine must be dropped on this thread.
+    fn run(script: &str) -> Result<(), String> {
+        Python::with_gil(|py| {
+            let globals = PyDict::new_bound(py);
+            let result = Py::new(py, PyMachine::new(Wallet::new()))
+                .and_then(|wallet| globals.set_item("wallet", wallet))
+                .and_then(|_| py.run_bound(script, Some(&globals), None));
+            result.map_err(|err| {
+                err.print(py);
+                err.to_string()
+            })
+        })
+    }
+
+    #[test]
+    fn inspects_machines() {
+        run(r#"
+assert wallet.name == "Wallet"
+assert wallet.state == "Open"
+assert repr(wallet) == "<Wallet machine in $Open>"
+info = wallet.info()
+assert [state["name"] for state in info["states"]] == ["Open", "Empty", "Closed"]
+assert info["variables"] == [("balance", "i32"), ("holder", "String")]
+methods = {method["name"]: method for method in info["interface"]}
+assert methods["withdraw"] == {"name": "withdraw", "parameters": [("amount", "i32")], "return_type": None}
+assert methods["getBalance"]["return_type"] == "i32"
+assert {"id": 0, "kind": "transition", "event": "withdraw", "label": "emptied", "source": "Open", "target": "Empty"} in info["transitions"]
+assert wallet.variables() == {"balance": 0, "holder": "ada"}
+assert wallet.snapshot() == {
+    "machine": "Wallet",
+    "state": {"name": "Open", "arguments": {}, "variables": {}},
+    "variables": {"balance": 0, "holder": "ada"},
+}
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn drives_machines() {
+        run(r#"
+assert wallet.send("deposit", 10) is None
+wallet.send("withdraw", 25)
+assert wallet.send("getBalance") == 10
+wallet.send("rename", "grace")
+wallet.send("withdraw", 10)
+assert wallet.state == "Empty"
+wallet.send("deposit", 5)
+assert wallet.variables() == {"balance": 5, "holder": "grace"}
+
+events = wallet.event_history()
+assert [event["event"] for event in events if ":" not in event["event"]] == ["deposit", "withdraw", "getBalance", "rename", "withdraw", "deposit"]
+assert events[2] == {"event": "getBalance", "arguments": {}, "return": 10}
+transitions = wallet.transition_history()
+assert [(t["source"], t["target"], t["label"]) for t in transitions] == [("Open", "Empty", "emptied"), ("Empty", "Open", "")]
+
+coverage = wallet.coverage()
+assert coverage["states"] == {"Open": 2, "Empty": 1, "Closed": 0}
+assert [t["count"] for t in coverage["transitions"]] == [1, 0, 1, 0]
+assert coverage["state_percentage"] > 66.6 and coverage["transition_percentage"] == 50
+
+wallet.clear_history()
+assert wallet.event_history() == [] and wallet.transition_history() == []
+wallet.set_history_capacity(events=1)
+wallet.send("deposit", 1)
+assert len(wallet.event_history()) == 1
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_calls() {
+        Python::with_gil(|py| {
+            let wallet = Py::new(py, PyMachine::new(Wallet::new())).unwrap();
+            let wallet = wallet.bind(py);
+            let err = wallet.call_method1("send", ("spend", 1)).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+            assert_eq!(err.value_bound(py).to_string(), "unknown method `spend`");
+            let err = wallet.call_method1("send", ("deposit", "ten")).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+            assert_eq!(
+                err.value_bound(py).to_string(),
+                "argument `amount` of `deposit` must be of type `i32`"
+            );
+            let err = wallet.call_method1("send", ("deposit",)).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+            let err = wallet.call_method1("send", ("deposit", 1, 2)).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        });
+    }
+}
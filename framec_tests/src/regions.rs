@@ -0,0 +1,84 @@
+//! Test orthogonal regions, i.e. substates of a parallel state that are active at the same time.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "regions.rs"));
+
+impl Regions {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter_regions() {
+        let mut sm = Regions::new();
+        sm.start();
+        assert_eq!(sm.state, RegionsState::Running);
+        assert_eq!(
+            sm.tape,
+            vec!["enter Running", "enter MotorOff", "enter LightOff"]
+        );
+    }
+
+    #[test]
+    fn independent_regions() {
+        let mut sm = Regions::new();
+        sm.start();
+        sm.tape.clear();
+        sm.motor();
+        assert_eq!(sm.running_motor_region, RegionsState::MotorOn);
+        assert_eq!(sm.running_light_region, RegionsState::LightOff);
+        assert_eq!(sm.tape, vec!["exit MotorOff", "enter MotorOn"]);
+        sm.tape.clear();
+        sm.light();
+        assert_eq!(sm.running_motor_region, RegionsState::MotorOn);
+        assert_eq!(sm.running_light_region, RegionsState::LightOn);
+        assert_eq!(
+            sm.tape,
+            vec!["MotorOn light", "exit LightOff", "enter LightOn"]
+        );
+    }
+
+    #[test]
+    fn exit_regions() {
+        let mut sm = Regions::new();
+        sm.start();
+        sm.motor();
+        sm.light();
+        sm.tape.clear();
+        sm.stop();
+        assert_eq!(sm.state, RegionsState::Idle);
+        assert_eq!(
+            sm.tape,
+            vec![
+                "LightOn stop",
+                "exit LightOn",
+                "exit MotorOn",
+                "exit Running"
+            ]
+        );
+        // regions start over in their initial states
+        sm.tape.clear();
+        sm.start();
+        assert_eq!(
+            sm.tape,
+            vec!["enter Running", "enter MotorOff", "enter LightOff"]
+        );
+    }
+
+    #[test]
+    fn enter_region_state() {
+        let mut sm = Regions::new();
+        sm.light();
+        assert_eq!(sm.state, RegionsState::Running);
+        assert_eq!(sm.running_light_region, RegionsState::LightOn);
+        assert_eq!(
+            sm.tape,
+            vec!["enter Running", "enter MotorOff", "enter LightOn"]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Tests that the machine info tables and environments can be generated without the rest of the
+//! runtime interface and the event monitor.
+
+include!(concat!(env!("OUT_DIR"), "/", "runtime_info_only.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::Environment;
+
+    #[test]
+    fn machine_info() {
+        let info = runtime_info::machine_info();
+        assert_eq!(info.name, "RuntimeInfoOnly");
+        assert_eq!(info.states.len(), 2);
+        assert!(info.get_state("A").is_some());
+        assert!(info.get_event("X").is_some());
+        assert_eq!(info.transitions.len(), 2);
+    }
+
+    #[test]
+    fn environments() {
+        let mut sm = RuntimeInfoOnly::new();
+        let count = sm.lookup("count").unwrap();
+        assert_eq!(*count.downcast_ref::<u32>().unwrap(), 3);
+        assert!(sm.lookup("missing").is_none());
+
+        sm.x();
+        sm.x();
+        let state_vars = sm.state_context.a_context().state_vars.as_ref().borrow();
+        let x = state_vars.lookup("x").unwrap();
+        assert_eq!(*x.downcast_ref::<u32>().unwrap(), 2);
+    }
+}
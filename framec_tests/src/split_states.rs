@@ -0,0 +1,37 @@
+//! Test the `split_states` feature, which generates the handler of each state into its own file.
+
+type Log = Vec<i32>;
+include!(concat!(env!("OUT_DIR"), "/", "split_states.rs"));
+
+impl SplitStates {
+    pub fn log(&mut self, step: i32) {
+        self.tape.push(step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the handlers in the state modules handle events, transition and forward events
+    /// to parent states.
+    #[test]
+    fn split_handlers() {
+        let mut sm = SplitStates::new();
+        sm.next(1);
+        assert_eq!(sm.state, SplitStatesState::First);
+        sm.next(2);
+        assert_eq!(sm.state, SplitStatesState::Second);
+        sm.next(3);
+        assert_eq!(sm.tape, vec![1, 1, 2, 3]);
+    }
+
+    /// Test that the state modules are written to a directory named after the spec.
+    #[test]
+    fn state_files() {
+        let dir = std::path::Path::new(env!("OUT_DIR")).join("split_states");
+        for state in ["first", "second", "parent"] {
+            assert!(dir.join(format!("{}.rs", state)).exists());
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Tests the state stack feature when states have associated contexts, with the lightweight
+//! feature enabled. Lightweight state contexts are cloned when pushed, rather than deep-cloned.
+//!
+//! This is the same state machine as `state_context_stack.rs` but with `lightweight=true` and
+//! `runtime_support=false`.
+
+type Log = Vec<String>;
+include!(concat!(
+    env!("OUT_DIR"),
+    "/",
+    "state_context_stack_lightweight.rs"
+));
+
+impl StateContextStack {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that a pop restores a pushed state.
+    #[test]
+    fn push_pop() {
+        let mut sm = StateContextStack::new();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.push();
+        sm.to_b();
+        assert_eq!(sm.state, StateContextStackState::B);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+    }
+
+    /// Test that multiple states can be pushed and subsequently restored by pops, LIFO style.
+    #[test]
+    fn multiple_push_pops() {
+        let mut sm = StateContextStack::new();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.push();
+        sm.to_c();
+        sm.push();
+        sm.to_a();
+        sm.push();
+        sm.push();
+        sm.to_c(); // no push
+        sm.to_b();
+        sm.push();
+        sm.to_c();
+        sm.push(); // stack top-to-bottom: C, B, A, A, C, A
+        sm.to_a();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        sm.to_a();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::B);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        sm.to_b();
+        sm.push();
+        sm.to_c();
+        sm.push(); // stack top-to-bottom: C, B, A
+        sm.to_a();
+        sm.to_b();
+        assert_eq!(sm.state, StateContextStackState::B);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::B);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+    }
+
+    /// Test that pop transitions trigger enter/exit events.
+    #[test]
+    fn pop_transition_events() {
+        let mut sm = StateContextStack::new();
+        sm.to_b();
+        sm.push();
+        sm.to_a();
+        sm.push();
+        sm.to_c();
+        sm.push(); // stack top-to-bottom: C, A, B
+        sm.to_a();
+        sm.tape.clear();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.tape, vec!["A:<", "C:>"]);
+        sm.tape.clear();
+        sm.pop();
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.tape, vec!["C:<", "A:>", "A:<", "B:>"]);
+    }
+
+    /// Test that pop change-states do not trigger enter/exit events.
+    #[test]
+    fn pop_change_state_no_events() {
+        let mut sm = StateContextStack::new();
+        sm.to_b();
+        sm.push();
+        sm.to_a();
+        sm.push();
+        sm.to_c();
+        sm.push(); // stack top-to-bottom: C, A, B
+        sm.to_a();
+        sm.tape.clear();
+        assert_eq!(sm.state, StateContextStackState::A);
+        sm.pop_change();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert!(sm.tape.is_empty());
+        sm.pop();
+        sm.pop_change();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.tape, vec!["C:<", "A:>"]);
+    }
+
+    /// Test that state variables are restored after pop.
+    #[test]
+    fn pop_restores_state_variables() {
+        let mut sm = StateContextStack::new();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 2);
+        sm.to_b();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.value(), 5);
+        sm.to_c();
+        sm.inc();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 30);
+        sm.to_a();
+        sm.inc();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 1);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 30);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.value(), 5);
+        sm.to_a();
+        sm.inc();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 3);
+        sm.to_c();
+        sm.inc();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 10);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 3);
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 2);
+    }
+
+    /// Test that push stores a snapshot of the current values of state variables. Any changes to
+    /// state variables after a push should not be reflected after that state is popped.
+    #[test]
+    fn push_stores_state_variable_snapshot() {
+        let mut sm = StateContextStack::new();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 2);
+        sm.inc();
+        sm.inc();
+        assert_eq!(sm.value(), 4);
+
+        sm.to_b();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.value(), 5);
+        sm.inc();
+        sm.inc();
+        assert_eq!(sm.value(), 15); // these changes should be forgotten
+
+        sm.to_c();
+        sm.inc();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 30);
+        sm.inc();
+        assert_eq!(sm.value(), 40); // forgotten
+
+        sm.to_a();
+        sm.inc();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 1);
+
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 30);
+
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::B);
+        assert_eq!(sm.value(), 5);
+
+        sm.to_a();
+        sm.inc();
+        sm.inc();
+        sm.inc();
+        sm.push();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 3);
+        sm.inc();
+        assert_eq!(sm.value(), 4); // forgotten
+
+        sm.to_c();
+        sm.inc();
+        assert_eq!(sm.state, StateContextStackState::C);
+        assert_eq!(sm.value(), 10);
+
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 3);
+
+        sm.pop();
+        assert_eq!(sm.state, StateContextStackState::A);
+        assert_eq!(sm.value(), 2);
+    }
+}
@@ -0,0 +1,95 @@
+//! Test recording traces of a machine with `frame_runtime::trace`, and replaying them.
+
+include!(concat!(env!("OUT_DIR"), "/", "trace.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_runtime::trace::{Trace, TraceRecord, TraceWriter};
+    use frame_runtime::Machine;
+
+    fn record() -> Trace {
+        let mut sm = Thermostat::new();
+        let writer = TraceWriter::new("Thermostat");
+        writer.attach(&mut sm);
+        sm.set_target(22);
+        sm.rename(String::from("living\troom"));
+        sm.reading(18);
+        assert_eq!(sm.get_target(), 22);
+        sm.reading(23);
+        sm.reset();
+        writer.snapshot(&sm);
+        writer.trace()
+    }
+
+    #[test]
+    fn records_runs() {
+        let trace = record();
+        assert!(trace.records.iter().all(|record| match record {
+            TraceRecord::Event { time, .. }
+            | TraceRecord::Return { time, .. }
+            | TraceRecord::Transition { time, .. }
+            | TraceRecord::Snapshot { time, .. } => *time > 0,
+        }));
+        let lines: Vec<String> = trace
+            .to_string()
+            .lines()
+            .map(|line| {
+                // leave out the times
+                let mut fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() > 2 {
+                    fields.remove(1);
+                }
+                fields.join(" ")
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "frmtrace 1",
+                "machine Thermostat",
+                "snapshot Idle target=20 label=\"hall\"",
+                "event setTarget degrees=22",
+                "event rename name=\"living\\troom\"",
+                "event reading degrees=18",
+                "transition transition reading Idle Heating too cold",
+                "event getTarget",
+                "return getTarget 22",
+                "event reading degrees=23",
+                "transition transition reading Heating Idle ",
+                "event reset",
+                "transition change_state reset Idle Idle ",
+                "snapshot Idle target=22 label=\"living\\troom\"",
+            ]
+        );
+        assert_eq!(Trace::parse(&trace.to_string()).unwrap(), trace);
+    }
+
+    #[test]
+    fn replays_runs() {
+        let trace = record();
+        let mut sm = Thermostat::new();
+        assert_eq!(trace.replay(&mut sm), Ok(6));
+        assert_eq!(sm.state().info().name, "Idle");
+        assert_eq!(sm.get_target(), 22);
+
+        // with a lower target, the machine doesn't start heating as recorded
+        let mut diverging = trace.clone();
+        if let TraceRecord::Event { arguments, .. } = &mut diverging.records[1] {
+            arguments[0].1 = String::from("15");
+        }
+        let err = diverging.replay(&mut Thermostat::new()).unwrap_err();
+        assert_eq!(err.record, 5);
+        assert_eq!(
+            err.to_string(),
+            "record 6: the machine is in $Idle, not $Heating"
+        );
+
+        let mut wrong_return = trace.clone();
+        if let TraceRecord::Return { value, .. } = &mut wrong_return.records[6] {
+            *value = String::from("21");
+        }
+        let err = wrong_return.replay(&mut Thermostat::new()).unwrap_err();
+        assert_eq!(err.to_string(), "record 7: |getTarget| returned 22, not 21");
+    }
+}
@@ -224,4 +224,64 @@ mod tests {
         sm.transit();
         assert_eq!(sm.hooks, vec!["S3->S4", "S4->>S0"]);
     }
+
+    /// Test that the sequences generated for transition coverage take the expected transitions.
+    #[test]
+    fn coverage_sequences() {
+        use frame_runtime::coverage::{Coverage, TestPlan};
+        for coverage in [Coverage::AllTransitions, Coverage::AllTransitionPairs] {
+            let plan = TestPlan::new(TransitionSm::machine_info(), coverage);
+            assert!(plan.uncovered.is_empty());
+            for sequence in &plan.sequences {
+                let mut sm = TransitionSm::new();
+                sm.event_monitor_mut().set_transition_history_capacity(None);
+                for event in &sequence.events {
+                    match event.name {
+                        "transit" => sm.transit(),
+                        "change" => sm.change(),
+                        name => panic!("unexpected event {}", name),
+                    }
+                }
+                let taken: Vec<usize> = sm
+                    .event_monitor()
+                    .transition_history()
+                    .iter()
+                    .map(|t| t.info.id)
+                    .collect();
+                let expected: Vec<usize> = sequence.transitions.iter().map(|t| t.id).collect();
+                assert_eq!(taken, expected);
+                assert_eq!(sm.state().info().name, sequence.final_state.unwrap().name);
+            }
+        }
+    }
+
+    /// Test rendering the sequences of transition coverage as a script and as Rust tests.
+    #[test]
+    fn coverage_rendering() {
+        use frame_runtime::coverage::{Coverage, TestPlan};
+        let plan = TestPlan::new(TransitionSm::machine_info(), Coverage::AllTransitions);
+        assert_eq!(
+            plan.render_script(),
+            "new TransitionSm\n\
+             # S0->S1 |transit|\n\
+             # S1->S2 |transit|\n\
+             # S2->S3 |S2:>|\n\
+             # S3->S4 |transit|\n\
+             # S4->>S0 |S4:>|\n\
+             # S0->>S1 |change|\n\
+             # S1->>S2 |change|\n\
+             # S2->S3 |transit|\n\
+             # S3->>S4 |change|\n\
+             transit\ntransit\ntransit\nchange\nchange\ntransit\nchange\n\
+             state S4\n\
+             new TransitionSm\n\
+             # S0->S1 |transit|\n\
+             # S1->>S2 |change|\n\
+             # S2->>S3 |change|\n\
+             transit\nchange\nchange\n\
+             state S3\n"
+        );
+        let tests = plan.render_rust_tests();
+        assert!(tests.contains("    fn sequence_2() {\n        let mut sm = TransitionSm::new();\n        sm.transit();\n        sm.change();\n        sm.change();\n        assert_eq!(sm.state().info().name, \"S3\");\n    }\n"));
+    }
 }
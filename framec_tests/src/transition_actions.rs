@@ -0,0 +1,52 @@
+//! Test actions attached to transitions, which are called after the exit event is sent to the
+//! state left and before the enter event is sent to the new state.
+
+type Log = Vec<String>;
+include!(concat!(env!("OUT_DIR"), "/", "transition_actions.rs"));
+
+impl TransitionActions {
+    pub fn log(&mut self, msg: String) {
+        self.tape.push(msg);
+    }
+    pub fn lock(&mut self) {
+        self.tape.push("lock".to_string());
+    }
+    pub fn unlock(&mut self) {
+        self.tape.push("unlock".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_between_exit_and_enter() {
+        let mut sm = TransitionActions::new();
+        sm.open(3);
+        assert_eq!(sm.state, TransitionActionsState::Open);
+        assert_eq!(sm.tape, vec!["exit Closed", "unlock", "3", "enter Open"]);
+        sm.tape.clear();
+        sm.close();
+        assert_eq!(sm.state, TransitionActionsState::Closed);
+        assert_eq!(sm.tape, vec!["exit Open", "lock"]);
+    }
+
+    #[test]
+    fn actions_of_pop_transitions() {
+        let mut sm = TransitionActions::new();
+        sm.inspect();
+        sm.done();
+        assert_eq!(sm.state, TransitionActionsState::Closed);
+        assert_eq!(
+            sm.tape,
+            vec![
+                "exit Closed",
+                "push",
+                "enter Inspecting",
+                "exit Inspecting",
+                "pop"
+            ]
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Specs that Framec must reject, each checked against the diagnostics it is expected to report.
+//!
+//! Each `.frm` file in `compile_fail` is compiled to Rust, and the diagnostics of the error,
+//! one per line, are compared with the `.stderr` file next to it. A spec that compiles or
+//! panics fails the test, so the parser and the analyses keep reporting errors for malformed
+//! specs, with the same codes, messages and locations, as the language grows.
+//!
+//! After an intended change to a diagnostic, update the expected diagnostics with
+//!
+//! ```text
+//! FRAMEC_BLESS=1 cargo test -p framec_tests --test compile_fail
+//! ```
+
+use framec::frame_c::compiler::{CompilerOptions, Exe, TargetLanguage};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+fn compile_fail_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("compile_fail")
+}
+
+/// Compile a spec and return its diagnostics, or why it didn't fail as expected.
+fn diagnostics(exe: &Exe, file_name: &str, spec: &str) -> Result<String, String> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        exe.run(
+            &None,
+            Some(file_name),
+            spec.to_string(),
+            Some(TargetLanguage::Rust),
+        )
+    }));
+    match result {
+        Ok(Ok(_)) => Err("compiled without errors".to_string()),
+        Ok(Err(err)) => Ok(err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| format!("{}\n", diagnostic))
+            .collect()),
+        Err(payload) => {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(|msg| msg.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {}", msg))
+        }
+    }
+}
+
+#[test]
+fn compile_fail() {
+    let bless = std::env::var_os("FRAMEC_BLESS").is_some();
+    let exe = Exe::with_options(CompilerOptions {
+        load_local_config: false,
+        ..CompilerOptions::default()
+    });
+
+    let mut specs: Vec<PathBuf> = fs::read_dir(compile_fail_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().unwrap_or_default() == "frm")
+        .collect();
+    specs.sort();
+    assert!(!specs.is_empty(), "no specs in {:?}", compile_fail_dir());
+
+    // silence panics, they are reported as failures below
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failures = Vec::new();
+    for spec_path in &specs {
+        let file_name = spec_path.file_name().unwrap().to_str().unwrap();
+        let spec = fs::read_to_string(spec_path).unwrap();
+        let stderr_path = spec_path.with_extension("stderr");
+        let output = match diagnostics(&exe, file_name, &spec) {
+            Ok(output) => output,
+            Err(reason) => {
+                failures.push(format!("{} {}", spec_path.display(), reason));
+                continue;
+            }
+        };
+        if bless {
+            fs::write(&stderr_path, output).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&stderr_path) {
+            Ok(expected) if expected == output => {}
+            Ok(expected) => failures.push(format!(
+                "{} reported different diagnostics:\n{}\n{}",
+                spec_path.display(),
+                prefix_lines("  - ", &expected),
+                prefix_lines("  + ", &output)
+            )),
+            Err(_) => failures.push(format!("{} is missing", stderr_path.display())),
+        }
+    }
+
+    panic::set_hook(default_hook);
+    assert!(
+        failures.is_empty(),
+        "{} spec(s) didn't fail as expected, run with FRAMEC_BLESS=1 to update the diagnostics:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn prefix_lines(prefix: &str, text: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
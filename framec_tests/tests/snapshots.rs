@@ -0,0 +1,20 @@
+//! Snapshots of the code generated from the specs of the tests, as by `build.rs`, so that
+//! changes to the generated code show up in review along with the changes to its behavior.
+//!
+//! After an intended change to the generated code, update the snapshots with
+//!
+//! ```text
+//! FRAMEC_BLESS=1 cargo test -p framec_tests --test snapshots
+//! ```
+
+use frame_testing::{FrameSnapshots, TargetLanguage};
+
+#[test]
+fn generated_code() {
+    let result = FrameSnapshots::new()
+        .add_target(TargetLanguage::Smcat)
+        .run();
+    if let Err(err) = result {
+        panic!("{}", err);
+    }
+}